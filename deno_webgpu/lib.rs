@@ -410,6 +410,7 @@ pub async fn op_webgpu_request_adapter(
                 flags: wgpu_types::InstanceFlags::from_build_config(),
                 dx12_shader_compiler: wgpu_types::Dx12Compiler::Fxc,
                 gles_minor_version: wgpu_types::Gles3MinorVersion::default(),
+                pipeline_worker_threads: None,
             },
         )));
         state.borrow::<Instance>()
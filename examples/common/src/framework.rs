@@ -264,6 +264,7 @@ impl ExampleContext {
             flags: wgpu::InstanceFlags::from_build_config().with_env(),
             dx12_shader_compiler,
             gles_minor_version,
+            pipeline_worker_threads: None,
         });
         surface.pre_adapter(&instance, window);
         let adapter = wgpu::util::initialize_adapter_from_env_or_default(&instance, surface.get())
@@ -58,7 +58,7 @@ struct Args {
 
     /// the shader model to use if targeting HLSL
     ///
-    /// May be `50`, 51`, or `60`
+    /// May be `50`, `51`, `60`, `61`, `62`, `63`, `64`, `65`, `66`, or `67`
     #[argh(option)]
     shader_model: Option<ShaderModelArg>,
 
@@ -79,6 +79,14 @@ struct Args {
     #[argh(switch, short = 'g')]
     generate_debug_symbols: bool,
 
+    /// optimize the module's IR and revalidate.
+    ///
+    /// This folds constants, removes dead code and common subexpressions,
+    /// inlines small functions and moves loop invariants out of loops. See
+    /// `naga::opt` for details.
+    #[argh(switch)]
+    optimize: bool,
+
     /// compact the module's IR and revalidate.
     ///
     /// Output files will reflect the compacted IR. If you want to see the IR as
@@ -93,6 +101,13 @@ struct Args {
     #[argh(option)]
     before_compaction: Option<String>,
 
+    /// reduce the module to the entry point given by `--entry-point`, and
+    /// everything it uses, and revalidate.
+    ///
+    /// This implies `--compact`.
+    #[argh(switch)]
+    extract_entry_point: bool,
+
     /// show version
     #[argh(switch)]
     version: bool,
@@ -109,6 +124,20 @@ struct Args {
     files: Vec<String>,
 }
 
+/// Format WGSL shaders in place.
+#[derive(argh::FromArgs, Debug, Clone)]
+struct FmtArgs {
+    /// don't write anything, but fail if any of the files isn't formatted
+    #[argh(switch)]
+    check: bool,
+
+    /// the WGSL files to format. If not specified, the shader is read from
+    /// stdin and the formatted shader is written to stdout, unless --check
+    /// is given.
+    #[argh(positional)]
+    files: Vec<String>,
+}
+
 /// Newtype so we can implement [`FromStr`] for `BoundsCheckPolicy`.
 #[derive(Debug, Clone, Copy)]
 struct BoundsCheckPolicyArg(naga::proc::BoundsCheckPolicy);
@@ -144,6 +173,13 @@ impl FromStr for ShaderModelArg {
             "50" => ShaderModel::V5_0,
             "51" => ShaderModel::V5_1,
             "60" => ShaderModel::V6_0,
+            "61" => ShaderModel::V6_1,
+            "62" => ShaderModel::V6_2,
+            "63" => ShaderModel::V6_3,
+            "64" => ShaderModel::V6_4,
+            "65" => ShaderModel::V6_5,
+            "66" => ShaderModel::V6_6,
+            "67" => ShaderModel::V6_7,
             _ => return Err(format!("Invalid value for --shader-model: {s}")),
         }))
     }
@@ -241,6 +277,20 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
     let mut params = Parameters::default();
 
     // Parse commandline arguments
+    let strings: Vec<String> = std::env::args().collect();
+    if strings.get(1).map(String::as_str) == Some("fmt") {
+        let rest: Vec<&str> = strings[2..].iter().map(String::as_str).collect();
+        let args = <FmtArgs as argh::FromArgs>::from_args(&["naga fmt"], &rest).unwrap_or_else(
+            |early_exit| {
+                match early_exit.status {
+                    Ok(()) => println!("{}", early_exit.output),
+                    Err(()) => eprintln!("{}", early_exit.output),
+                }
+                std::process::exit(early_exit.status.map_or(1, |()| 0));
+            },
+        );
+        return fmt(args);
+    }
     let args: Args = argh::from_env();
     if args.version {
         println!("{}", env!("CARGO_PKG_VERSION"));
@@ -415,8 +465,38 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    // Optimize the module, if requested.
+    let info = if args.optimize {
+        // Optimize only if validation succeeded. Otherwise, optimization may panic.
+        if info.is_some() {
+            naga::opt::optimize(&mut module, &naga::opt::Options::default());
+
+            // Re-validate the IR after optimization.
+            match naga::valid::Validator::new(params.validation_flags, validation_caps)
+                .validate(&module)
+            {
+                Ok(info) => Some(info),
+                Err(error) => {
+                    // Validation failure is not fatal. Just report the error.
+                    eprintln!("Error validating optimized module:");
+                    if let Some(input) = &input_text {
+                        let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+                        emit_annotated_error(&error, filename.unwrap_or("input"), input);
+                    }
+                    print_err(&error);
+                    None
+                }
+            }
+        } else {
+            eprintln!("Skipping optimization due to validation failure.");
+            None
+        }
+    } else {
+        info
+    };
+
     // Compact the module, if requested.
-    let info = if args.compact || args.before_compaction.is_some() {
+    let info = if args.compact || args.before_compaction.is_some() || args.extract_entry_point {
         // Compact only if validation succeeded. Otherwise, compaction may panic.
         if info.is_some() {
             // Write out the module state before compaction, if requested.
@@ -424,7 +504,20 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 write_output(&module, &info, &params, before_compaction)?;
             }
 
-            naga::compact::compact(&mut module);
+            if args.extract_entry_point {
+                let name = params.entry_point.as_deref().ok_or(CliError(
+                    "Extracting an entry point requires `--entry-point`",
+                ))?;
+                let stage = module
+                    .entry_points
+                    .iter()
+                    .find(|ep| ep.name == name)
+                    .ok_or(CliError("Unable to find the entry point"))?
+                    .stage;
+                naga::compact::extract_entry_point(&mut module, stage, name);
+            } else {
+                naga::compact::compact(&mut module);
+            }
 
             // Re-validate the IR after compaction.
             match naga::valid::Validator::new(params.validation_flags, validation_caps)
@@ -450,6 +543,40 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
         info
     };
 
+    // SPIR-V, GLSL and HLSL can't pass pointers into some address spaces to
+    // functions, so specialize those functions for each pointer instead.
+    let needs_specialization = output_paths.iter().any(|path| {
+        matches!(
+            Path::new(path).extension().and_then(|ex| ex.to_str()),
+            Some("spv" | "vert" | "frag" | "comp" | "hlsl")
+        )
+    });
+    let info = if needs_specialization
+        && info.is_some()
+        && naga::proc::find_unspecialized_pointer_argument(&module).is_some()
+    {
+        naga::proc::specialize_pointer_arguments(&mut module);
+
+        // Re-validate the IR after specialization.
+        match naga::valid::Validator::new(params.validation_flags, validation_caps)
+            .validate(&module)
+        {
+            Ok(info) => Some(info),
+            Err(error) => {
+                // Validation failure is not fatal. Just report the error.
+                eprintln!("Error validating specialized module:");
+                if let Some(input) = &input_text {
+                    let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+                    emit_annotated_error(&error, filename.unwrap_or("input"), input);
+                }
+                print_err(&error);
+                None
+            }
+        }
+    } else {
+        info
+    };
+
     // If no output was requested, then report validation results and stop here.
     //
     // If the user asked for output, don't stop: some output formats (".txt",
@@ -630,6 +757,54 @@ fn write_output(
     Ok(())
 }
 
+fn fmt(args: FmtArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.files.is_empty() {
+        let mut input = String::new();
+        std::io::stdin().lock().read_to_string(&mut input)?;
+        let output = match naga::front::wgsl::format_str(&input) {
+            Ok(output) => output,
+            Err(e) => {
+                e.emit_to_stderr_with_path(&input, "<stdin>");
+                return Err(CliError("Could not format WGSL").into());
+            }
+        };
+        if !args.check {
+            print!("{output}");
+        } else if output != input {
+            println!("<stdin> is not formatted");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut failed = false;
+    for path in args.files.iter() {
+        let input = fs::read_to_string(path)?;
+        let output = match naga::front::wgsl::format_str(&input) {
+            Ok(output) => output,
+            Err(e) => {
+                e.emit_to_stderr_with_path(&input, path);
+                failed = true;
+                continue;
+            }
+        };
+        if output == input {
+            continue;
+        }
+        if args.check {
+            println!("{path} is not formatted");
+            failed = true;
+        } else {
+            fs::write(path, output)?;
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 use codespan_reporting::{
     diagnostic::{Diagnostic, Label},
     files::SimpleFile,
@@ -0,0 +1,546 @@
+//! Everything the server knows about one WGSL source file.
+//!
+//! The front end and validator give us diagnostics. For everything else we
+//! go to the parsed [`Module`]: its arenas record the [`Span`] of each
+//! declaration, and [`Typifier`] resolves the types of expressions.
+
+use std::ops::Range;
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticRelatedInformation,
+    DiagnosticSeverity, DocumentSymbol, Hover, HoverContents, Location, MarkupContent, MarkupKind,
+    Position, SymbolKind, Url,
+};
+use naga::{front::Typifier, proc::ResolveContext, Handle, Module, Span};
+
+use crate::line_index::LineIndex;
+
+pub struct Document {
+    uri: Url,
+    text: String,
+    lines: LineIndex,
+    /// The module, if `text` parses.
+    module: Option<Module>,
+    /// The functions and entry points in `module`, with their extents.
+    functions: Vec<(Span, FunctionRef)>,
+    declarations: Vec<Declaration>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Clone, Copy)]
+enum FunctionRef {
+    Function(Handle<naga::Function>),
+    EntryPoint(usize),
+}
+
+/// Something declared by name in a document.
+struct Declaration {
+    name: String,
+    kind: SymbolKind,
+    /// The whole declaration.
+    span: Span,
+    /// Just the name being declared.
+    name_span: Span,
+    /// The declaration's type or signature, written as WGSL.
+    detail: String,
+    /// The extent of the function that this is local to, or `None` for
+    /// module-scope declarations.
+    scope: Option<Span>,
+}
+
+impl Document {
+    pub fn new(uri: Url, text: String) -> Self {
+        let mut document = Document {
+            uri,
+            lines: LineIndex::new(&text),
+            text,
+            module: None,
+            functions: Vec::new(),
+            declarations: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let module = match naga::front::wgsl::parse_str(&document.text) {
+            Ok(module) => module,
+            Err(error) => {
+                let labels = error
+                    .labels()
+                    .map(|(span, label)| (span, label.to_string()));
+                let diagnostic = document.diagnostic(error.message().to_string(), labels);
+                document.diagnostics.push(diagnostic);
+                return document;
+            }
+        };
+
+        if let Err(error) = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        {
+            let mut message = error.as_inner().to_string();
+            let mut source: &dyn std::error::Error = error.as_inner();
+            while let Some(next) = source.source() {
+                message.push_str(": ");
+                message.push_str(&next.to_string());
+                source = next;
+            }
+            let labels = error.spans().cloned().collect::<Vec<_>>();
+            let diagnostic = document.diagnostic(message, labels);
+            document.diagnostics.push(diagnostic);
+        }
+
+        document.collect_declarations(&module);
+        document.module = Some(module);
+        document
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.clone()
+    }
+
+    /// Builds a diagnostic placed at the first of `labels`, listing all of
+    /// them as related information.
+    fn diagnostic(
+        &self,
+        message: String,
+        labels: impl IntoIterator<Item = (Span, String)>,
+    ) -> Diagnostic {
+        let related = labels
+            .into_iter()
+            .filter_map(|(span, label)| {
+                let location = Location::new(self.uri.clone(), self.range(span)?);
+                Some(DiagnosticRelatedInformation {
+                    location,
+                    message: label,
+                })
+            })
+            .collect::<Vec<_>>();
+        Diagnostic {
+            range: related
+                .first()
+                .map(|related| related.location.range)
+                .unwrap_or_default(),
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some("naga".to_string()),
+            message,
+            related_information: Some(related),
+            ..Default::default()
+        }
+    }
+
+    fn collect_declarations(&mut self, module: &Module) {
+        for (handle, ty) in module.types.iter() {
+            let span = module.types.get_span(handle);
+            if let (Some(name), naga::TypeInner::Struct { .. }) = (&ty.name, &ty.inner) {
+                let detail = format!("struct {name}");
+                self.declare(name, SymbolKind::STRUCT, span, detail, None);
+            }
+        }
+
+        for (handle, constant) in module.constants.iter() {
+            if let Some(ref name) = constant.name {
+                let span = module.constants.get_span(handle);
+                let detail = format!("const {name}: {}", type_name(module, constant.ty));
+                self.declare(name, SymbolKind::CONSTANT, span, detail, None);
+            }
+        }
+
+        for (handle, var) in module.global_variables.iter() {
+            if let Some(ref name) = var.name {
+                let span = module.global_variables.get_span(handle);
+                let detail = format!("var {name}: {}", type_name(module, var.ty));
+                self.declare(name, SymbolKind::VARIABLE, span, detail, None);
+            }
+        }
+
+        for (handle, function) in module.functions.iter() {
+            let span = module.functions.get_span(handle);
+            if let Some(ref name) = function.name {
+                let detail = signature(module, name, function);
+                self.declare(name, SymbolKind::FUNCTION, span, detail, None);
+            }
+            self.functions.push((span, FunctionRef::Function(handle)));
+            self.collect_locals(module, function, span);
+        }
+
+        for (index, entry_point) in module.entry_points.iter().enumerate() {
+            // Entry points have no span of their own, so find their name
+            // and extend it over everything in their body.
+            let function = &entry_point.function;
+            let name_span = self.find_function_name(&entry_point.name);
+            let span = Span::total_span(
+                std::iter::once(name_span)
+                    .chain(
+                        function
+                            .expressions
+                            .iter()
+                            .map(|(handle, _)| function.expressions.get_span(handle)),
+                    )
+                    .chain(function.body.span_iter().map(|(_, &span)| span)),
+            );
+            let detail = format!(
+                "@{} {}",
+                stage_name(entry_point.stage),
+                signature(module, &entry_point.name, function)
+            );
+            self.declarations.push(Declaration {
+                name: entry_point.name.clone(),
+                kind: SymbolKind::FUNCTION,
+                span,
+                name_span,
+                detail,
+                scope: None,
+            });
+            self.functions.push((span, FunctionRef::EntryPoint(index)));
+            self.collect_locals(module, function, span);
+        }
+    }
+
+    /// Collects the arguments, local variables and `let` bindings of
+    /// `function`, which extends over `scope`.
+    fn collect_locals(&mut self, module: &Module, function: &naga::Function, scope: Span) {
+        let mut typifier = Typifier::new();
+        let resolve_ctx =
+            ResolveContext::with_locals(module, &function.local_variables, &function.arguments);
+
+        for (handle, expression) in function.expressions.iter() {
+            if let naga::Expression::FunctionArgument(index) = *expression {
+                let argument = &function.arguments[index as usize];
+                if let Some(ref name) = argument.name {
+                    // The front end gives argument expressions the span of
+                    // the argument's name.
+                    let span = function.expressions.get_span(handle);
+                    self.declarations.push(Declaration {
+                        name: name.clone(),
+                        kind: SymbolKind::VARIABLE,
+                        span,
+                        name_span: span,
+                        detail: format!("{name}: {}", type_name(module, argument.ty)),
+                        scope: Some(scope),
+                    });
+                }
+            }
+        }
+
+        for (handle, var) in function.local_variables.iter() {
+            if let Some(ref name) = var.name {
+                let span = function.local_variables.get_span(handle);
+                let detail = format!("var {name}: {}", type_name(module, var.ty));
+                self.declare(name, SymbolKind::VARIABLE, span, detail, Some(scope));
+            }
+        }
+
+        for (&handle, name) in function.named_expressions.iter() {
+            if let naga::Expression::FunctionArgument(_) = function.expressions[handle] {
+                continue;
+            }
+            // The name of a `let` comes before its initializer.
+            let init = function.expressions.get_span(handle);
+            let (Some(scope_range), Some(init_range)) = (scope.to_range(), init.to_range()) else {
+                continue;
+            };
+            let Some(name_range) = self.rfind_word(scope_range.start..init_range.start, name)
+            else {
+                continue;
+            };
+            let ty = match typifier.grow(handle, &function.expressions, &resolve_ctx) {
+                Ok(()) => resolution_name(module, &typifier[handle]),
+                Err(_) => continue,
+            };
+            self.declarations.push(Declaration {
+                name: name.clone(),
+                kind: SymbolKind::VARIABLE,
+                span: Span::from(name_range.start..init_range.end),
+                name_span: Span::from(name_range),
+                detail: format!("let {name}: {ty}"),
+                scope: Some(scope),
+            });
+        }
+    }
+
+    /// Declares `name`, finding it within its declaration's `span`.
+    fn declare(
+        &mut self,
+        name: &str,
+        kind: SymbolKind,
+        span: Span,
+        detail: String,
+        scope: Option<Span>,
+    ) {
+        let name_span = span
+            .to_range()
+            .and_then(|range| self.find_word(range, name))
+            .map_or(span, Span::from);
+        self.declarations.push(Declaration {
+            name: name.to_string(),
+            kind,
+            span,
+            name_span,
+            detail,
+            scope,
+        });
+    }
+
+    pub fn symbols(&self) -> Vec<DocumentSymbol> {
+        self.declarations
+            .iter()
+            .filter(|declaration| declaration.scope.is_none())
+            .filter_map(|declaration| {
+                let children = self
+                    .declarations
+                    .iter()
+                    .filter(|local| local.scope == Some(declaration.span))
+                    .filter_map(|local| self.symbol(local, None))
+                    .collect::<Vec<_>>();
+                let children = (!children.is_empty()).then_some(children);
+                self.symbol(declaration, children)
+            })
+            .collect()
+    }
+
+    #[allow(deprecated)] // `DocumentSymbol::deprecated`
+    fn symbol(
+        &self,
+        declaration: &Declaration,
+        children: Option<Vec<DocumentSymbol>>,
+    ) -> Option<DocumentSymbol> {
+        Some(DocumentSymbol {
+            name: declaration.name.clone(),
+            detail: Some(declaration.detail.clone()),
+            kind: declaration.kind,
+            tags: None,
+            deprecated: None,
+            range: self.range(declaration.span)?,
+            selection_range: self.range(declaration.name_span)?,
+            children,
+        })
+    }
+
+    pub fn definition(&self, position: Position) -> Option<Location> {
+        let offset = self.lines.offset(&self.text, position);
+        let (name, _) = self.word_at(offset)?;
+        let declaration = self.resolve(name, offset)?;
+        Some(Location::new(
+            self.uri.clone(),
+            self.range(declaration.name_span)?,
+        ))
+    }
+
+    pub fn hover(&self, position: Position) -> Option<Hover> {
+        let offset = self.lines.offset(&self.text, position);
+        let declaration = self
+            .word_at(offset)
+            .and_then(|(name, range)| Some((self.resolve(name, offset)?, range)));
+        let (detail, span) = match declaration {
+            Some((declaration, range)) => (declaration.detail.clone(), Span::from(range)),
+            None => self.expression_type_at(offset)?,
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```wgsl\n{detail}\n```"),
+            }),
+            range: self.range(span),
+        })
+    }
+
+    /// Returns the type of the smallest expression at `offset`, and its span.
+    fn expression_type_at(&self, offset: usize) -> Option<(String, Span)> {
+        let module = self.module.as_ref()?;
+        let &(_, function) = self
+            .functions
+            .iter()
+            .find(|&&(extent, _)| contains(extent, offset))?;
+        let function = match function {
+            FunctionRef::Function(handle) => &module.functions[handle],
+            FunctionRef::EntryPoint(index) => &module.entry_points[index].function,
+        };
+
+        let (handle, span) = function
+            .expressions
+            .iter()
+            .map(|(handle, _)| (handle, function.expressions.get_span(handle)))
+            .filter(|&(_, span)| contains(span, offset))
+            .min_by_key(|&(_, span)| span.to_range().map_or(usize::MAX, |range| range.len()))?;
+
+        let mut typifier = Typifier::new();
+        let resolve_ctx =
+            ResolveContext::with_locals(module, &function.local_variables, &function.arguments);
+        typifier
+            .grow(handle, &function.expressions, &resolve_ctx)
+            .ok()?;
+        Some((resolution_name(module, &typifier[handle]), span))
+    }
+
+    pub fn completions(&self, position: Position) -> Vec<CompletionItem> {
+        let offset = self.lines.offset(&self.text, position);
+        let declared = self
+            .declarations
+            .iter()
+            .filter(|declaration| match declaration.scope {
+                Some(scope) => contains(scope, offset),
+                None => true,
+            })
+            .map(|declaration| CompletionItem {
+                label: declaration.name.clone(),
+                kind: Some(match declaration.kind {
+                    SymbolKind::STRUCT => CompletionItemKind::STRUCT,
+                    SymbolKind::CONSTANT => CompletionItemKind::CONSTANT,
+                    SymbolKind::FUNCTION => CompletionItemKind::FUNCTION,
+                    _ => CompletionItemKind::VARIABLE,
+                }),
+                detail: Some(declaration.detail.clone()),
+                ..Default::default()
+            });
+        let builtins = naga::keywords::wgsl::BUILTIN_FUNCTIONS
+            .iter()
+            .map(|&name| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::FUNCTION),
+                detail: Some("built-in function".to_string()),
+                ..Default::default()
+            });
+        declared.chain(builtins).collect()
+    }
+
+    /// Finds what `name` refers to at `offset`: a declaration local to the
+    /// enclosing function, preferring the latest one before `offset`, or
+    /// else a module-scope declaration.
+    fn resolve(&self, name: &str, offset: usize) -> Option<&Declaration> {
+        let start = |declaration: &&Declaration| {
+            declaration
+                .name_span
+                .to_range()
+                .map_or(usize::MAX, |range| range.start)
+        };
+        let candidates = || {
+            self.declarations
+                .iter()
+                .filter(move |declaration| declaration.name == name)
+        };
+        let mut locals = candidates().filter(|declaration| {
+            declaration
+                .scope
+                .is_some_and(|scope| contains(scope, offset))
+        });
+        let local = locals
+            .clone()
+            .filter(|declaration| start(declaration) <= offset)
+            .max_by_key(start)
+            .or_else(|| locals.next());
+        local.or_else(|| candidates().find(|declaration| declaration.scope.is_none()))
+    }
+
+    fn range(&self, span: Span) -> Option<lsp_types::Range> {
+        Some(self.lines.range(&self.text, span.to_range()?))
+    }
+
+    /// Returns the identifier at `offset`, and its range.
+    fn word_at(&self, offset: usize) -> Option<(&str, Range<usize>)> {
+        let offset = offset.min(self.text.len());
+        if !self.text.is_char_boundary(offset) {
+            return None;
+        }
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, ch)| is_word_char(ch))
+            .last()
+            .map_or(offset, |(i, _)| i);
+        let end = self.text[offset..]
+            .char_indices()
+            .find(|&(_, ch)| !is_word_char(ch))
+            .map_or(self.text.len(), |(i, _)| offset + i);
+        (start < end).then(|| (&self.text[start..end], start..end))
+    }
+
+    /// Finds the first whole-word occurrence of `word` within `range`.
+    fn find_word(&self, range: Range<usize>, word: &str) -> Option<Range<usize>> {
+        self.text[range.clone()]
+            .match_indices(word)
+            .map(|(i, _)| range.start + i..range.start + i + word.len())
+            .find(|found| self.is_whole_word(found))
+    }
+
+    /// Finds the last whole-word occurrence of `word` within `range`.
+    fn rfind_word(&self, range: Range<usize>, word: &str) -> Option<Range<usize>> {
+        self.text[range.clone()]
+            .rmatch_indices(word)
+            .map(|(i, _)| range.start + i..range.start + i + word.len())
+            .find(|found| self.is_whole_word(found))
+    }
+
+    /// Finds `name` where it follows the `fn` keyword.
+    fn find_function_name(&self, name: &str) -> Span {
+        let mut range = 0..self.text.len();
+        while let Some(found) = self.find_word(range.clone(), name) {
+            if self.text[..found.start].trim_end().ends_with("fn") {
+                return Span::from(found);
+            }
+            range.start = found.end;
+        }
+        Span::UNDEFINED
+    }
+
+    fn is_whole_word(&self, range: &Range<usize>) -> bool {
+        !self.text[..range.start]
+            .chars()
+            .next_back()
+            .is_some_and(is_word_char)
+            && !self.text[range.end..]
+                .chars()
+                .next()
+                .is_some_and(is_word_char)
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn contains(span: Span, offset: usize) -> bool {
+    span.to_range()
+        .is_some_and(|range| range.start <= offset && offset <= range.end)
+}
+
+fn type_name(module: &Module, ty: Handle<naga::Type>) -> String {
+    let ty = &module.types[ty];
+    ty.name
+        .clone()
+        .unwrap_or_else(|| ty.inner.to_wgsl(module.to_ctx()))
+}
+
+fn resolution_name(module: &Module, resolution: &naga::proc::TypeResolution) -> String {
+    match *resolution {
+        naga::proc::TypeResolution::Handle(ty) => type_name(module, ty),
+        naga::proc::TypeResolution::Value(ref inner) => inner.to_wgsl(module.to_ctx()),
+    }
+}
+
+fn signature(module: &Module, name: &str, function: &naga::Function) -> String {
+    let arguments = function
+        .arguments
+        .iter()
+        .map(|argument| {
+            format!(
+                "{}: {}",
+                argument.name.as_deref().unwrap_or("_"),
+                type_name(module, argument.ty)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    match function.result {
+        Some(ref result) => format!("fn {name}({arguments}) -> {}", type_name(module, result.ty)),
+        None => format!("fn {name}({arguments})"),
+    }
+}
+
+const fn stage_name(stage: naga::ShaderStage) -> &'static str {
+    match stage {
+        naga::ShaderStage::Vertex => "vertex",
+        naga::ShaderStage::Fragment => "fragment",
+        naga::ShaderStage::Compute => "compute",
+    }
+}
@@ -0,0 +1,57 @@
+//! Conversion between byte offsets, which naga's spans use, and LSP
+//! positions, which count UTF-16 code units within a line.
+
+use lsp_types::{Position, Range};
+
+pub struct LineIndex {
+    /// The byte offset at which each line starts.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { line_starts }
+    }
+
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let offset = offset.min(text.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let start = self.line_starts[line];
+        let character = text[start..offset].encode_utf16().count();
+        Position::new(line as u32, character as u32)
+    }
+
+    pub fn range(&self, text: &str, range: std::ops::Range<usize>) -> Range {
+        Range::new(
+            self.position(text, range.start),
+            self.position(text, range.end),
+        )
+    }
+
+    /// Returns the byte offset of `position`, clamped to the end of its line.
+    pub fn offset(&self, text: &str, position: Position) -> usize {
+        let line = position.line as usize;
+        let Some(&start) = self.line_starts.get(line) else {
+            return text.len();
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(text.len(), |&next| next - 1);
+
+        let mut units = 0;
+        for (i, ch) in text[start..end].char_indices() {
+            if units >= position.character as usize {
+                return start + i;
+            }
+            units += ch.len_utf16();
+        }
+        end
+    }
+}
@@ -0,0 +1,183 @@
+/*!
+A [Language Server Protocol][lsp] server for WGSL, speaking over stdio.
+
+It reports naga's parse and validation errors as diagnostics, and offers
+document symbols, go-to-definition, hover and completion based on the parsed
+module.
+
+[lsp]: https://microsoft.github.io/language-server-protocol/
+*/
+
+mod document;
+mod line_index;
+
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _},
+    CompletionOptions, CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentSymbolResponse, GotoDefinitionResponse,
+    HoverProviderCapability, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use document::Document;
+
+type BoxedError = Box<dyn Error + Sync + Send>;
+
+fn main() -> Result<(), BoxedError> {
+    // Log to stderr, as stdout carries the protocol.
+    env_logger::init();
+
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    Server::default().run(&connection)?;
+    // The I/O threads finish once the connection is dropped.
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<Url, Document>,
+}
+
+impl Server {
+    fn run(&mut self, connection: &Connection) -> Result<(), BoxedError> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(response.into())?;
+                }
+                Message::Notification(notification) => {
+                    let method = notification.method.clone();
+                    let diagnostics = match self.handle_notification(notification) {
+                        Ok(Some(diagnostics)) => diagnostics,
+                        Ok(None) => continue,
+                        Err(error) => {
+                            // A client bug shouldn't take the server down.
+                            log::error!("Ignoring malformed {method} notification: {error}");
+                            continue;
+                        }
+                    };
+                    let notification =
+                        Notification::new(PublishDiagnostics::METHOD.to_string(), diagnostics);
+                    connection.sender.send(notification.into())?;
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            DocumentSymbolRequest::METHOD => {
+                self.reply::<DocumentSymbolRequest>(request, |server, params| {
+                    let document = server.documents.get(&params.text_document.uri)?;
+                    Some(DocumentSymbolResponse::Nested(document.symbols()))
+                })
+            }
+            GotoDefinition::METHOD => self.reply::<GotoDefinition>(request, |server, params| {
+                let params = params.text_document_position_params;
+                let document = server.documents.get(&params.text_document.uri)?;
+                document
+                    .definition(params.position)
+                    .map(GotoDefinitionResponse::Scalar)
+            }),
+            HoverRequest::METHOD => self.reply::<HoverRequest>(request, |server, params| {
+                let params = params.text_document_position_params;
+                let document = server.documents.get(&params.text_document.uri)?;
+                document.hover(params.position)
+            }),
+            Completion::METHOD => self.reply::<Completion>(request, |server, params| {
+                let params = params.text_document_position;
+                let document = server.documents.get(&params.text_document.uri)?;
+                Some(CompletionResponse::Array(
+                    document.completions(params.position),
+                ))
+            }),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request {method}"),
+            ),
+        }
+    }
+
+    /// Answers a request of type `R` with `handler`.
+    fn reply<R: lsp_types::request::Request>(
+        &self,
+        request: Request,
+        handler: impl FnOnce(&Self, R::Params) -> R::Result,
+    ) -> Response {
+        let id = request.id.clone();
+        match request.extract::<R::Params>(R::METHOD) {
+            Ok((id, params)) => Response::new_ok(id, handler(self, params)),
+            Err(error) => {
+                Response::new_err(id, ErrorCode::InvalidParams as i32, format!("{error:?}"))
+            }
+        }
+    }
+
+    /// Keeps track of open documents, returning the diagnostics to publish
+    /// when one changes.
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<Option<PublishDiagnosticsParams>, serde_json::Error> {
+        let published = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                Some(self.open(document.uri, document.text, Some(document.version)))
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // We ask for full text synchronization, so the last change
+                // holds the whole document.
+                params.content_changes.pop().map(|change| {
+                    let document = params.text_document;
+                    self.open(document.uri, change.text, Some(document.version))
+                })
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                Some(PublishDiagnosticsParams::new(uri, Vec::new(), None))
+            }
+            _ => None,
+        };
+        Ok(published)
+    }
+
+    fn open(&mut self, uri: Url, text: String, version: Option<i32>) -> PublishDiagnosticsParams {
+        let document = Document::new(uri.clone(), text);
+        let diagnostics = document.diagnostics();
+        self.documents.insert(uri.clone(), document);
+        PublishDiagnosticsParams::new(uri, diagnostics, version)
+    }
+}
@@ -0,0 +1,338 @@
+/*!
+Tests that drive the `naga-lsp` binary with scripted LSP messages.
+*/
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///shader.wgsl";
+
+const SHADER: &str = "\
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+const SCALE: f32 = 2.0;
+
+@group(0) @binding(0) var<uniform> light: Light;
+
+fn shade(normal: vec3<f32>) -> vec3<f32> {
+    let brightness = max(dot(normal, vec3(0.0, 0.0, 1.0)), 0.0);
+    return light.color * brightness * SCALE;
+}
+
+@fragment
+fn main(@location(0) normal: vec3<f32>) -> @location(0) vec4<f32> {
+    var color = shade(normal);
+    color *= light.intensity;
+    return vec4(color, 1.0);
+}
+";
+
+struct Client {
+    server: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    /// Starts the server and goes through the initialization handshake.
+    fn start() -> Self {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_naga-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = server.stdin.take().unwrap();
+        let stdout = BufReader::new(server.stdout.take().unwrap());
+        let mut client = Client {
+            server,
+            stdin,
+            stdout,
+            next_id: 0,
+        };
+
+        let result = client.request("initialize", json!({ "capabilities": {} }));
+        assert_eq!(result["capabilities"]["hoverProvider"], true);
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = None;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = Some(value.parse().unwrap());
+            }
+        }
+        let mut body = vec![0; length.expect("missing Content-Length")];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Sends a request and returns the result of its response.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                assert_eq!(message["error"], Value::Null, "{method} failed");
+                return message["result"].clone();
+            }
+        }
+    }
+
+    /// Waits for the diagnostics published for [`URI`].
+    fn diagnostics(&mut self) -> Vec<Value> {
+        loop {
+            let message = self.receive();
+            if message["method"] == "textDocument/publishDiagnostics" {
+                assert_eq!(message["params"]["uri"], URI);
+                return message["params"]["diagnostics"].as_array().unwrap().clone();
+            }
+        }
+    }
+
+    fn open(&mut self, text: &str) -> Vec<Value> {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": URI, "languageId": "wgsl", "version": 1, "text": text }
+            }),
+        );
+        self.diagnostics()
+    }
+
+    fn change(&mut self, version: u32, text: &str) -> Vec<Value> {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": version },
+                "contentChanges": [{ "text": text }]
+            }),
+        );
+        self.diagnostics()
+    }
+
+    fn at(&mut self, method: &str, position: Value) -> Value {
+        self.request(
+            method,
+            json!({ "textDocument": { "uri": URI }, "position": position }),
+        )
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            let _ = self.server.kill();
+            return;
+        }
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.server.wait().unwrap().success());
+    }
+}
+
+/// The position of the `nth` occurrence of `needle` in [`SHADER`], moved
+/// `offset` characters into it.
+fn position(needle: &str, nth: usize, offset: usize) -> Value {
+    let start = SHADER
+        .match_indices(needle)
+        .nth(nth)
+        .unwrap_or_else(|| panic!("no occurrence {nth} of {needle:?}"))
+        .0
+        + offset;
+    let line = SHADER[..start].matches('\n').count();
+    let line_start = SHADER[..start].rfind('\n').map_or(0, |i| i + 1);
+    json!({ "line": line, "character": start - line_start })
+}
+
+fn range(needle: &str, nth: usize) -> Value {
+    json!({ "start": position(needle, nth, 0), "end": position(needle, nth, needle.len()) })
+}
+
+fn hover_text(hover: &Value) -> &str {
+    hover["contents"]["value"].as_str().unwrap()
+}
+
+#[test]
+fn diagnostics() {
+    let mut client = Client::start();
+    assert_eq!(client.open(SHADER), Vec::<Value>::new());
+
+    let diagnostics = client.change(2, "fn main() {\n    let x = ;\n}\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(diagnostics[0]["source"], "naga");
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({ "start": { "line": 1, "character": 12 }, "end": { "line": 1, "character": 13 } })
+    );
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("expected expression"));
+
+    let diagnostics = client.change(3, "var<private> values: array<f32>;\n");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 0);
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("values"));
+
+    assert_eq!(client.change(4, SHADER), Vec::<Value>::new());
+}
+
+#[test]
+fn malformed_notifications() {
+    let mut client = Client::start();
+    for method in [
+        "textDocument/didOpen",
+        "textDocument/didChange",
+        "textDocument/didClose",
+    ] {
+        client.notify(method, json!({ "textDocument": 1 }));
+    }
+    // The server is still running, and `Drop` checks that it shuts down
+    // cleanly.
+    assert_eq!(client.open(SHADER), Vec::<Value>::new());
+}
+
+#[test]
+fn document_symbols() {
+    let mut client = Client::start();
+    client.open(SHADER);
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    let symbols = symbols.as_array().unwrap();
+    let names = symbols
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Light", "SCALE", "light", "shade", "main"]);
+
+    let shade = &symbols[3];
+    assert_eq!(shade["detail"], "fn shade(normal: vec3<f32>) -> vec3<f32>");
+    assert_eq!(shade["selectionRange"], range("shade", 0));
+    let children = shade["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(children, ["normal", "brightness"]);
+
+    let main = &symbols[4];
+    assert_eq!(
+        main["detail"],
+        "@fragment fn main(normal: vec3<f32>) -> vec4<f32>"
+    );
+    assert_eq!(main["selectionRange"], range("main", 0));
+}
+
+#[test]
+fn definition() {
+    let mut client = Client::start();
+    client.open(SHADER);
+
+    let expect = |client: &mut Client, at: Value, target: Value| {
+        let location = client.at("textDocument/definition", at);
+        assert_eq!(location["uri"], URI);
+        assert_eq!(location["range"], target);
+    };
+    // A global variable, a constant and a function.
+    expect(
+        &mut client,
+        position("light.color", 0, 2),
+        range("light", 0),
+    );
+    expect(&mut client, position("SCALE;", 0, 0), range("SCALE", 0));
+    expect(
+        &mut client,
+        position("shade(normal)", 0, 1),
+        range("shade", 0),
+    );
+    // An argument and a `let` in `shade`, and the argument of `main`,
+    // which has the same name.
+    expect(
+        &mut client,
+        position("normal, vec3", 0, 0),
+        range("normal", 0),
+    );
+    expect(
+        &mut client,
+        position("brightness *", 0, 3),
+        range("brightness", 0),
+    );
+    expect(&mut client, position("normal);", 0, 0), range("normal", 2));
+    // A local variable.
+    expect(&mut client, position("color *=", 0, 0), range("color", 2));
+
+    let nothing = client.at("textDocument/definition", position("max", 0, 1));
+    assert_eq!(nothing, Value::Null);
+}
+
+#[test]
+fn hover() {
+    let mut client = Client::start();
+    client.open(SHADER);
+
+    let hover = client.at("textDocument/hover", position("light.intensity", 0, 0));
+    assert_eq!(hover_text(&hover), "```wgsl\nvar light: Light\n```");
+    assert_eq!(hover["range"], range("light", 2));
+
+    let hover = client.at("textDocument/hover", position("brightness *", 0, 0));
+    assert_eq!(hover_text(&hover), "```wgsl\nlet brightness: f32\n```");
+
+    // Expressions that aren't names get their type from the typifier.
+    let hover = client.at("textDocument/hover", position("1.0)", 1, 1));
+    assert_eq!(hover_text(&hover), "```wgsl\nf32\n```");
+    let hover = client.at("textDocument/hover", position("dot(", 0, 1));
+    assert_eq!(hover_text(&hover), "```wgsl\nf32\n```");
+}
+
+#[test]
+fn completion() {
+    let mut client = Client::start();
+    client.open(SHADER);
+
+    let items = client.at("textDocument/completion", position("return vec4", 0, 0));
+    let labels = items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    for label in ["color", "light", "shade", "SCALE", "textureSample", "dot"] {
+        assert!(labels.contains(&label), "missing {label}");
+    }
+    // Locals of other functions are out of scope.
+    assert!(!labels.contains(&"brightness"));
+}
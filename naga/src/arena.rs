@@ -599,7 +599,7 @@ impl<T> UniqueArena<T> {
         }
     }
 
-    #[cfg(feature = "compact")]
+    #[cfg(any(feature = "compact", feature = "link"))]
     pub(crate) fn drain_all(&mut self) -> UniqueArenaDrain<T> {
         UniqueArenaDrain {
             inner_elts: self.set.drain(..),
@@ -610,7 +610,7 @@ impl<T> UniqueArena<T> {
     }
 }
 
-#[cfg(feature = "compact")]
+#[cfg(any(feature = "compact", feature = "link"))]
 pub(crate) struct UniqueArenaDrain<'a, T> {
     inner_elts: indexmap::set::Drain<'a, T>,
     #[cfg(feature = "span")]
@@ -618,7 +618,7 @@ pub(crate) struct UniqueArenaDrain<'a, T> {
     index: Index,
 }
 
-#[cfg(feature = "compact")]
+#[cfg(any(feature = "compact", feature = "link"))]
 impl<'a, T> Iterator for UniqueArenaDrain<'a, T> {
     type Item = (Handle<T>, T, Span);
 
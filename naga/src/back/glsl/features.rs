@@ -367,6 +367,11 @@ impl<'a, W> Writer<'a, W> {
                         },
                         ImageClass::Sampled { multi: false, .. }
                         | ImageClass::Depth { multi: false } => {}
+                        ImageClass::External => {
+                            return Err(Error::Custom(
+                                "external textures must be lowered before writing GLSL".to_string(),
+                            ))
+                        }
                     }
                 }
                 _ => {}
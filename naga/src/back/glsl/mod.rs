@@ -476,6 +476,12 @@ pub enum Error {
     /// A image was used with multiple samplers, which isn't supported.
     #[error("A image was used with multiple samplers")]
     ImageMultipleSamplers,
+    /// A function takes a pointer into the `storage`, `uniform` or `workgroup` address
+    /// space, which must be removed with [`specialize_pointer_arguments`] first.
+    ///
+    /// [`specialize_pointer_arguments`]: proc::specialize_pointer_arguments
+    #[error("Function '{0}' takes a pointer that `proc::specialize_pointer_arguments` must remove first")]
+    UnspecializedPointerArgument(String),
     #[error("{0}")]
     Custom(String),
 }
@@ -552,6 +558,11 @@ impl<'a, W: Write> Writer<'a, W> {
             return Err(Error::VersionNotSupported);
         }
 
+        if let Some((function, _)) = proc::find_unspecialized_pointer_argument(module) {
+            let name = module.functions[function].name.clone().unwrap_or_default();
+            return Err(Error::UnspecializedPointerArgument(name));
+        }
+
         // Try to find the entry point and corresponding index
         let ep_idx = module
             .entry_points
@@ -1068,6 +1079,8 @@ impl<'a, W: Write> Writer<'a, W> {
         let (base, kind, ms, comparison) = match class {
             Ic::Sampled { kind, multi: true } => ("sampler", kind, "MS", ""),
             Ic::Sampled { kind, multi: false } => ("sampler", kind, "", ""),
+            // Rejected while collecting the required features.
+            Ic::External => ("sampler", crate::ScalarKind::Float, "", ""),
             Ic::Depth { multi: true } => ("sampler", crate::ScalarKind::Float, "MS", ""),
             Ic::Depth { multi: false } => ("sampler", crate::ScalarKind::Float, "", "Shadow"),
             Ic::Storage { format, .. } => ("image", format.into(), "", ""),
@@ -1288,6 +1301,19 @@ impl<'a, W: Write> Writer<'a, W> {
                             }
                         }
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
+                    crate::MathFunction::Pack4xI8
+                    | crate::MathFunction::Pack4xU8
+                    | crate::MathFunction::Pack4xI8Clamp
+                    | crate::MathFunction::Pack4xU8Clamp
+                    | crate::MathFunction::Unpack4xI8
+                    | crate::MathFunction::Unpack4xU8
+                    | crate::MathFunction::QuantizeToF16 => {
+                        self.need_bake_expressions.insert(arg);
+                    }
                     crate::MathFunction::CountLeadingZeros => {
                         if let Some(crate::ScalarKind::Sint) = inner.scalar_kind() {
                             self.need_bake_expressions.insert(arg);
@@ -1850,6 +1876,41 @@ impl<'a, W: Write> Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method used to write the byte `index` of the `uint` expression
+    /// `arg` as an `int`, sign-extended, or as a `uint`
+    ///
+    /// # Notes
+    /// `arg` should be baked, since the packed 4x8 functions write it once per byte
+    fn write_unpacked_byte(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        index: u32,
+        signed: bool,
+        ctx: &back::FunctionCtx,
+    ) -> BackendResult {
+        write!(self.out, "(")?;
+        if signed {
+            // Shift the byte to the top, then back down to extend its sign
+            write!(self.out, "int(")?;
+            self.write_expr(arg, ctx)?;
+            write!(self.out, ")")?;
+            if index != 3 {
+                write!(self.out, " << {}", 24 - index * 8)?;
+            }
+            write!(self.out, " >> 24")?;
+        } else {
+            self.write_expr(arg, ctx)?;
+            if index != 0 {
+                write!(self.out, " >> {}", index * 8)?;
+            }
+            if index != 3 {
+                write!(self.out, " & 0xFFu")?;
+            }
+        }
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
     /// Helper method used to write structs
     ///
     /// # Notes
@@ -2417,6 +2478,17 @@ impl<'a, W: Write> Writer<'a, W> {
                     crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                     crate::Literal::I32(value) => write!(self.out, "{}", value)?,
                     crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
+                    crate::Literal::U64(_) | crate::Literal::I64(_) => {
+                        return Err(Error::UnsupportedScalar(
+                            literal.scalar_kind(),
+                            literal.width(),
+                        ));
+                    }
+                    crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
+                        return Err(Error::Custom(
+                            "Abstract types should not appear in IR presented to backends".into(),
+                        ));
+                    }
                 }
             }
             Expression::Constant(handle) => {
@@ -2829,6 +2901,11 @@ impl<'a, W: Write> Writer<'a, W> {
                                 write!(self.out, "imageSize(")?;
                                 self.write_expr(image, ctx)?;
                             }
+                            ImageClass::External => {
+                                write!(self.out, "textureSize(")?;
+                                self.write_expr(image, ctx)?;
+                                write!(self.out, ", 0")?;
+                            }
                         }
                         write!(self.out, ")")?;
                         if components != 1 || self.options.version.is_es() {
@@ -2842,7 +2919,9 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::ImageQuery::NumLayers => {
                         let fun_name = match class {
-                            ImageClass::Sampled { .. } | ImageClass::Depth { .. } => "textureSize",
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => "textureSize",
                             ImageClass::Storage { .. } => "imageSize",
                         };
                         write!(self.out, "{fun_name}(")?;
@@ -2859,9 +2938,9 @@ impl<'a, W: Write> Writer<'a, W> {
                     }
                     crate::ImageQuery::NumSamples => {
                         let fun_name = match class {
-                            ImageClass::Sampled { .. } | ImageClass::Depth { .. } => {
-                                "textureSamples"
-                            }
+                            ImageClass::Sampled { .. }
+                            | ImageClass::Depth { .. }
+                            | ImageClass::External => "textureSamples",
                             ImageClass::Storage { .. } => "imageSamples",
                         };
                         write!(self.out, "{fun_name}(")?;
@@ -3181,6 +3260,20 @@ impl<'a, W: Write> Writer<'a, W> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let signed = fun == Mf::Dot4I8Packed;
+                        write!(self.out, "(")?;
+                        for index in 0..4 {
+                            if index != 0 {
+                                write!(self.out, " + ")?;
+                            }
+                            self.write_unpacked_byte(arg, index, signed, ctx)?;
+                            write!(self.out, " * ")?;
+                            self.write_unpacked_byte(arg1.unwrap(), index, signed, ctx)?;
+                        }
+                        write!(self.out, ")")?;
+                        return Ok(());
+                    }
                     Mf::Outer => "outerProduct",
                     Mf::Cross => "cross",
                     Mf::Distance => "distance",
@@ -3223,6 +3316,42 @@ impl<'a, W: Write> Writer<'a, W> {
                     Mf::Inverse => "inverse",
                     Mf::Transpose => "transpose",
                     Mf::Determinant => "determinant",
+                    Mf::QuantizeToF16 => {
+                        // GLSL can only round to half precision through the
+                        // packing functions, two components at a time.
+                        match *ctx.resolve_type(arg, &self.module.types) {
+                            crate::TypeInner::Vector {
+                                size: crate::VectorSize::Bi,
+                                ..
+                            } => {
+                                write!(self.out, "unpackHalf2x16(packHalf2x16(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, "))")?;
+                            }
+                            crate::TypeInner::Vector { size, .. } => {
+                                let last = match size {
+                                    crate::VectorSize::Tri => "zz",
+                                    _ => "zw",
+                                };
+                                write!(self.out, "vec{}(", back::vector_size_str(size))?;
+                                write!(self.out, "unpackHalf2x16(packHalf2x16(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, ".xy)), unpackHalf2x16(packHalf2x16(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, ".{last}))")?;
+                                if size == crate::VectorSize::Tri {
+                                    write!(self.out, ".x")?;
+                                }
+                                write!(self.out, ")")?;
+                            }
+                            _ => {
+                                write!(self.out, "unpackHalf2x16(packHalf2x16(vec2(")?;
+                                self.write_expr(arg, ctx)?;
+                                write!(self.out, "))).x")?;
+                            }
+                        }
+                        return Ok(());
+                    }
                     // bits
                     Mf::CountTrailingZeros => {
                         match *ctx.resolve_type(arg, &self.module.types) {
@@ -3337,12 +3466,54 @@ impl<'a, W: Write> Writer<'a, W> {
                     Mf::Pack2x16snorm => "packSnorm2x16",
                     Mf::Pack2x16unorm => "packUnorm2x16",
                     Mf::Pack2x16float => "packHalf2x16",
+                    Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => {
+                        let signed = matches!(fun, Mf::Pack4xI8 | Mf::Pack4xI8Clamp);
+                        let clamp = matches!(fun, Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp);
+                        let (mask, min, max) = if signed {
+                            ("0xFF", "-128", "127")
+                        } else {
+                            ("0xFFu", "0u", "255u")
+                        };
+                        write!(self.out, "{}(", if signed { "uint" } else { "" })?;
+                        for (index, component) in back::COMPONENTS.iter().enumerate() {
+                            if index != 0 {
+                                write!(self.out, " | ")?;
+                            }
+                            write!(self.out, "(")?;
+                            if clamp {
+                                write!(self.out, "clamp(")?;
+                            }
+                            self.write_expr(arg, ctx)?;
+                            write!(self.out, ".{component}")?;
+                            if clamp {
+                                write!(self.out, ", {min}, {max})")?;
+                            }
+                            write!(self.out, " & {mask})")?;
+                            if index != 0 {
+                                write!(self.out, " << {}", index * 8)?;
+                            }
+                        }
+                        write!(self.out, ")")?;
+                        return Ok(());
+                    }
                     // data unpacking
                     Mf::Unpack4x8snorm => "unpackSnorm4x8",
                     Mf::Unpack4x8unorm => "unpackUnorm4x8",
                     Mf::Unpack2x16snorm => "unpackSnorm2x16",
                     Mf::Unpack2x16unorm => "unpackUnorm2x16",
                     Mf::Unpack2x16float => "unpackHalf2x16",
+                    Mf::Unpack4xI8 | Mf::Unpack4xU8 => {
+                        let signed = fun == Mf::Unpack4xI8;
+                        write!(self.out, "{}(", if signed { "ivec4" } else { "uvec4" })?;
+                        for index in 0..4 {
+                            if index != 0 {
+                                write!(self.out, ", ")?;
+                            }
+                            self.write_unpacked_byte(arg, index, signed, ctx)?;
+                        }
+                        write!(self.out, ")")?;
+                        return Ok(());
+                    }
                 };
 
                 let extract_bits = fun == Mf::ExtractBits;
@@ -3736,7 +3907,9 @@ impl<'a, W: Write> Writer<'a, W> {
         // and the policy to be used with it.
         let (fun_name, policy) = match class {
             // Sampled images inherit the policy from the user passed policies
-            crate::ImageClass::Sampled { .. } => ("texelFetch", self.policies.image_load),
+            crate::ImageClass::Sampled { .. } | crate::ImageClass::External => {
+                ("texelFetch", self.policies.image_load)
+            }
             crate::ImageClass::Storage { .. } => {
                 // OpenGL ES 3.1 mentiones in Chapter "8.22 Texture Image Loads and Stores" that:
                 // "Invalid image loads will return a vector where the value of R, G, and B components
@@ -4078,6 +4251,9 @@ impl<'a, W: Write> Writer<'a, W> {
         if flags.contains(crate::Barrier::WORK_GROUP) {
             writeln!(self.out, "{level}memoryBarrierShared();")?;
         }
+        if flags.contains(crate::Barrier::TEXTURE) {
+            writeln!(self.out, "{level}memoryBarrierImage();")?;
+        }
         writeln!(self.out, "{level}barrier();")?;
         Ok(())
     }
@@ -4272,14 +4448,15 @@ const fn glsl_scalar(
     use crate::ScalarKind as Sk;
 
     Ok(match kind {
-        Sk::Sint => ScalarString {
+        Sk::Sint if width == 4 => ScalarString {
             prefix: "i",
             full: "int",
         },
-        Sk::Uint => ScalarString {
+        Sk::Uint if width == 4 => ScalarString {
             prefix: "u",
             full: "uint",
         },
+        Sk::Sint | Sk::Uint => return Err(Error::UnsupportedScalar(kind, width)),
         Sk::Float => match width {
             4 => ScalarString {
                 prefix: "",
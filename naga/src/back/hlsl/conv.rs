@@ -19,8 +19,16 @@ impl crate::ScalarKind {
     /// <https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-scalar>
     pub(super) const fn to_hlsl_str(self, width: crate::Bytes) -> Result<&'static str, Error> {
         match self {
-            Self::Sint => Ok("int"),
-            Self::Uint => Ok("uint"),
+            Self::Sint => match width {
+                4 => Ok("int"),
+                8 => Ok("int64_t"),
+                _ => Err(Error::UnsupportedScalar(self, width)),
+            },
+            Self::Uint => match width {
+                4 => Ok("uint"),
+                8 => Ok("uint64_t"),
+                _ => Err(Error::UnsupportedScalar(self, width)),
+            },
             Self::Float => match width {
                 2 => Ok("half"),
                 4 => Ok("float"),
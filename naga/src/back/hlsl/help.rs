@@ -26,7 +26,7 @@ int dim_1d = NagaDimensions1D(image_1d);
 ```
 */
 
-use super::{super::FunctionCtx, BackendResult};
+use super::{super::FunctionCtx, BackendResult, Error};
 use crate::{arena::Handle, proc::NameKey};
 use std::fmt::Write;
 
@@ -59,6 +59,12 @@ pub(super) struct WrappedMatCx2 {
     pub(super) columns: crate::VectorSize,
 }
 
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub(super) struct WrappedMatrixInverse {
+    pub(super) size: crate::VectorSize,
+    pub(super) width: crate::Bytes,
+}
+
 /// HLSL backend requires its own `ImageQuery` enum.
 ///
 /// It is used inside `WrappedImageQuery` and should be unique per ImageQuery function.
@@ -140,6 +146,11 @@ impl<'a, W: Write> super::Writer<'a, W> {
                 let storage_format_str = format.to_hlsl_str();
                 write!(self.out, "<{storage_format_str}>")?
             }
+            crate::ImageClass::External => {
+                return Err(Error::Custom(
+                    "external textures must be lowered before writing HLSL".to_string(),
+                ))
+            }
         }
         Ok(())
     }
@@ -207,7 +218,7 @@ impl<'a, W: Write> super::Writer<'a, W> {
             crate::ImageClass::Sampled { multi: true, .. } => "MS",
             crate::ImageClass::Depth { multi: true } => "DepthMS",
             crate::ImageClass::Depth { multi: false } => "Depth",
-            crate::ImageClass::Sampled { multi: false, .. } => "",
+            crate::ImageClass::Sampled { multi: false, .. } | crate::ImageClass::External => "",
             crate::ImageClass::Storage { .. } => "RW",
         };
         let arrayed_str = if query.arrayed { "Array" } else { "" };
@@ -267,7 +278,9 @@ impl<'a, W: Write> super::Writer<'a, W> {
         // extra parameter is the mip level count or the sample count
         let extra_coords = match wiq.class {
             crate::ImageClass::Storage { .. } => 0,
-            crate::ImageClass::Sampled { .. } | crate::ImageClass::Depth { .. } => 1,
+            crate::ImageClass::Sampled { .. }
+            | crate::ImageClass::Depth { .. }
+            | crate::ImageClass::External => 1,
         };
 
         // GetDimensions Overloaded Methods
@@ -834,6 +847,58 @@ impl<'a, W: Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    pub(super) fn write_wrapped_matrix_inverse_function_name(
+        &mut self,
+        inverse: WrappedMatrixInverse,
+    ) -> BackendResult {
+        let scalar = if inverse.width == 8 {
+            "Double"
+        } else {
+            "Float"
+        };
+        let size = crate::back::vector_size_str(inverse.size);
+        write!(self.out, "NagaInverse{scalar}{size}x{size}")?;
+
+        Ok(())
+    }
+
+    /// Helper function that writes wrapped function for `MathFunction::Inverse`,
+    /// which HLSL has no intrinsic for.
+    pub(super) fn write_wrapped_matrix_inverse_function(
+        &mut self,
+        inverse: WrappedMatrixInverse,
+    ) -> BackendResult {
+        let scalar = if inverse.width == 8 {
+            "double"
+        } else {
+            "float"
+        };
+        let size = crate::back::vector_size_str(inverse.size);
+        let matrix = format!("{scalar}{size}x{size}");
+        let vector = format!("{scalar}{size}");
+
+        // Write function return type and name
+        write!(self.out, "{matrix} ")?;
+        self.write_wrapped_matrix_inverse_function_name(inverse)?;
+
+        // Write function parameters and body
+        writeln!(self.out, "({matrix} m)")?;
+        writeln!(self.out, "{{")?;
+        crate::back::write_matrix_inverse_body(
+            &mut self.out,
+            inverse.size,
+            scalar,
+            &matrix,
+            &vector,
+            crate::back::Level(1),
+        )?;
+        writeln!(self.out, "}}")?;
+        // Write extra new line
+        writeln!(self.out)?;
+
+        Ok(())
+    }
+
     /// Helper function that writes compose wrapped functions
     pub(super) fn write_wrapped_compose_functions(
         &mut self,
@@ -890,6 +955,23 @@ impl<'a, W: Write> super::Writer<'a, W> {
                         self.write_wrapped_array_length_function(wal)?;
                     }
                 }
+                crate::Expression::Math {
+                    fun: crate::MathFunction::Inverse,
+                    arg,
+                    ..
+                } => {
+                    if let crate::TypeInner::Matrix { columns, width, .. } =
+                        *func_ctx.resolve_type(arg, &module.types)
+                    {
+                        let inverse = WrappedMatrixInverse {
+                            size: columns,
+                            width,
+                        };
+                        if self.wrapped.matrix_inverses.insert(inverse) {
+                            self.write_wrapped_matrix_inverse_function(inverse)?;
+                        }
+                    }
+                }
                 crate::Expression::ImageQuery { image, query } => {
                     let wiq = match *func_ctx.resolve_type(image, &module.types) {
                         crate::TypeInner::Image {
@@ -157,6 +157,16 @@ impl<W: fmt::Write> super::Writer<'_, W> {
         func_ctx: &FunctionCtx,
     ) -> BackendResult {
         match *result_ty.inner_with(&module.types) {
+            crate::TypeInner::Scalar { kind, width: 8 } => {
+                // 64-bit values can't be bitcast from `uint`, so use the
+                // templated `Load` from Shader Model 6.2.
+                let chain = mem::take(&mut self.temp_access_chain);
+                let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
+                write!(self.out, "{var_name}.Load<{}>(", kind.to_hlsl_str(8)?)?;
+                self.write_storage_address(module, &chain, func_ctx)?;
+                write!(self.out, ")")?;
+                self.temp_access_chain = chain;
+            }
             crate::TypeInner::Scalar { kind, width: _ } => {
                 // working around the borrow checker in `self.write_expr`
                 let chain = mem::take(&mut self.temp_access_chain);
@@ -167,6 +177,23 @@ impl<W: fmt::Write> super::Writer<'_, W> {
                 write!(self.out, "))")?;
                 self.temp_access_chain = chain;
             }
+            crate::TypeInner::Vector {
+                size,
+                kind,
+                width: 8,
+            } => {
+                let chain = mem::take(&mut self.temp_access_chain);
+                let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
+                write!(
+                    self.out,
+                    "{var_name}.Load<{}{}>(",
+                    kind.to_hlsl_str(8)?,
+                    size as u8
+                )?;
+                self.write_storage_address(module, &chain, func_ctx)?;
+                write!(self.out, ")")?;
+                self.temp_access_chain = chain;
+            }
             crate::TypeInner::Vector {
                 size,
                 kind,
@@ -296,6 +323,23 @@ impl<W: fmt::Write> super::Writer<'_, W> {
             }
         };
         match *ty_resolution.inner_with(&module.types) {
+            ref inner @ (crate::TypeInner::Scalar { kind, width: 8 }
+            | crate::TypeInner::Vector { kind, width: 8, .. }) => {
+                // 64-bit values can't be bitcast to `uint`, so use the
+                // templated `Store` from Shader Model 6.2.
+                let chain = mem::take(&mut self.temp_access_chain);
+                let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
+                write!(self.out, "{level}{var_name}.Store<{}", kind.to_hlsl_str(8)?)?;
+                if let crate::TypeInner::Vector { size, .. } = *inner {
+                    write!(self.out, "{}", size as u8)?;
+                }
+                write!(self.out, ">(")?;
+                self.write_storage_address(module, &chain, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_store_value(module, &value, func_ctx)?;
+                writeln!(self.out, ");")?;
+                self.temp_access_chain = chain;
+            }
             crate::TypeInner::Scalar { .. } => {
                 // working around the borrow checker in `self.write_expr`
                 let chain = mem::take(&mut self.temp_access_chain);
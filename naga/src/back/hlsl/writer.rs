@@ -1,7 +1,10 @@
 use super::{
-    help::{WrappedArrayLength, WrappedConstructor, WrappedImageQuery, WrappedStructMatrixAccess},
+    help::{
+        WrappedArrayLength, WrappedConstructor, WrappedImageQuery, WrappedMatrixInverse,
+        WrappedStructMatrixAccess,
+    },
     storage::StoreValue,
-    BackendResult, Error, Options,
+    BackendResult, Error, Options, ShaderModel,
 };
 use crate::{
     back,
@@ -146,9 +149,25 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     | crate::MathFunction::Pack2x16snorm
                     | crate::MathFunction::Pack2x16unorm
                     | crate::MathFunction::Pack4x8snorm
-                    | crate::MathFunction::Pack4x8unorm => {
+                    | crate::MathFunction::Pack4x8unorm
+                    | crate::MathFunction::Pack4xI8
+                    | crate::MathFunction::Pack4xU8
+                    | crate::MathFunction::Pack4xI8Clamp
+                    | crate::MathFunction::Pack4xU8Clamp
+                    | crate::MathFunction::Unpack4xI8
+                    | crate::MathFunction::Unpack4xU8 => {
                         self.need_bake_expressions.insert(arg);
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed
+                        if self.options.shader_model < ShaderModel::V6_4 =>
+                    {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
+                    crate::MathFunction::Outer => {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::ExtractBits => {
                         self.need_bake_expressions.insert(arg);
                         self.need_bake_expressions.insert(arg1.unwrap());
@@ -179,12 +198,45 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         }
     }
 
+    /// Check that the selected shader model supports every type used by `module`.
+    fn check_shader_model(&self, module: &Module) -> BackendResult {
+        use ScalarKind as Sk;
+
+        for (_, ty) in module.types.iter() {
+            let required = match ty.inner {
+                TypeInner::Scalar {
+                    kind: Sk::Sint | Sk::Uint,
+                    width: 8,
+                }
+                | TypeInner::Vector {
+                    kind: Sk::Sint | Sk::Uint,
+                    width: 8,
+                    ..
+                } => Some(("64-bit integers", ShaderModel::V6_0)),
+                TypeInner::Atomic { width: 8, .. } => Some(("64-bit atomics", ShaderModel::V6_6)),
+                _ => None,
+            };
+            if let Some((what, model)) = required {
+                if self.options.shader_model < model {
+                    return Err(Error::ShaderModelTooLow(what.to_string(), model));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn write(
         &mut self,
         module: &Module,
         module_info: &valid::ModuleInfo,
     ) -> Result<super::ReflectionInfo, Error> {
         self.reset(module);
+        self.check_shader_model(module)?;
+
+        if let Some((function, _)) = proc::find_unspecialized_pointer_argument(module) {
+            let name = module.functions[function].name.clone().unwrap_or_default();
+            return Err(Error::UnspecializedPointerArgument(name));
+        }
 
         // Write special constants, if needed
         if let Some(ref bt) = self.options.special_constants_binding {
@@ -1857,6 +1909,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     .unwrap();
 
                 let fun_str = fun.to_hlsl_suffix();
+                // 64-bit atomics on `RWByteAddressBuffer`s have their own
+                // methods, from Shader Model 6.6.
+                let width_suffix = match *func_ctx.info[result].ty.inner_with(&module.types) {
+                    crate::TypeInner::Scalar { width: 8, .. } => "64",
+                    _ => "",
+                };
                 write!(self.out, " {res_name}; ")?;
                 match pointer_space {
                     crate::AddressSpace::WorkGroup => {
@@ -1870,7 +1928,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         // ownership of our reusable access chain buffer.
                         let chain = mem::take(&mut self.temp_access_chain);
                         let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
-                        write!(self.out, "{var_name}.Interlocked{fun_str}(")?;
+                        write!(self.out, "{var_name}.Interlocked{fun_str}{width_suffix}(")?;
                         self.write_storage_address(module, &chain, func_ctx)?;
                         self.temp_access_chain = chain;
                     }
@@ -2042,7 +2100,14 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 crate::Literal::F32(value) => write!(self.out, "{value:?}")?,
                 crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                 crate::Literal::I32(value) => write!(self.out, "{}", value)?,
+                crate::Literal::U64(value) => write!(self.out, "{}uL", value)?,
+                crate::Literal::I64(value) => write!(self.out, "{}L", value)?,
                 crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
+                crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
+                    return Err(Error::Custom(
+                        "Abstract types should not appear in IR presented to backends".into(),
+                    ));
+                }
             },
             Expression::Constant(handle) => {
                 let constant = &module.constants[handle];
@@ -2093,6 +2158,42 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Helper method to write the four bytes of the `uint` expression `expr`
+    /// as an `int4`, sign-extending each byte, or as a `uint4`
+    ///
+    /// # Notes
+    /// `expr` is written several times, so it should be baked
+    fn write_unpacked_bytes(
+        &mut self,
+        module: &Module,
+        expr: Handle<crate::Expression>,
+        signed: bool,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        if signed {
+            write!(self.out, "(int4(")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, " << 24, ")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, " << 16, ")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, " << 8, ")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, ") >> 24)")?;
+        } else {
+            write!(self.out, "(uint4(")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, ", ")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, " >> 8, ")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, " >> 16, ")?;
+            self.write_expr(module, expr, func_ctx)?;
+            write!(self.out, " >> 24) & 0xFF)")?;
+        }
+        Ok(())
+    }
+
     /// Helper method to write expressions
     ///
     /// # Notes
@@ -2595,9 +2696,22 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                             }
                         };
                     }
-                    None => {
-                        write!(self.out, "{}(", kind.to_hlsl_cast(),)?;
-                    }
+                    None => match *inner {
+                        // `asint` and `asuint` don't accept 64-bit operands, but
+                        // conversions between 64-bit integer types preserve the bits.
+                        TypeInner::Scalar { width: 8, .. } | TypeInner::Vector { width: 8, .. }
+                            if kind != ScalarKind::Float =>
+                        {
+                            write!(self.out, "{}", kind.to_hlsl_str(8)?)?;
+                            if let TypeInner::Vector { size, .. } = *inner {
+                                write!(self.out, "{}", back::vector_size_str(size))?;
+                            }
+                            write!(self.out, "(")?;
+                        }
+                        _ => {
+                            write!(self.out, "{}(", kind.to_hlsl_cast(),)?;
+                        }
+                    },
                 }
                 self.write_expr(module, expr, func_ctx)?;
                 write!(self.out, ")")?;
@@ -2621,11 +2735,17 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Pack2x16unorm,
                     Pack4x8snorm,
                     Pack4x8unorm,
+                    Pack4x8Integer { signed: bool, clamp: bool },
                     Unpack2x16float,
                     Unpack2x16snorm,
                     Unpack2x16unorm,
                     Unpack4x8snorm,
                     Unpack4x8unorm,
+                    Unpack4x8Integer { signed: bool },
+                    Dot4x8Packed { signed: bool },
+                    Outer,
+                    Inverse,
+                    QuantizeToF16,
                     Regular(&'static str),
                     MissingIntOverload(&'static str),
                     MissingIntReturnType(&'static str),
@@ -2673,7 +2793,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::Pow => Function::Regular("pow"),
                     // geometry
                     Mf::Dot => Function::Regular("dot"),
-                    //Mf::Outer => ,
+                    Mf::Dot4I8Packed => Function::Dot4x8Packed { signed: true },
+                    Mf::Dot4U8Packed => Function::Dot4x8Packed { signed: false },
+                    Mf::Outer => Function::Outer,
                     Mf::Cross => Function::Regular("cross"),
                     Mf::Distance => Function::Regular("distance"),
                     Mf::Length => Function::Regular("length"),
@@ -2689,9 +2811,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::SmoothStep => Function::Regular("smoothstep"),
                     Mf::Sqrt => Function::Regular("sqrt"),
                     Mf::InverseSqrt => Function::Regular("rsqrt"),
-                    //Mf::Inverse =>,
+                    Mf::Inverse => Function::Inverse,
                     Mf::Transpose => Function::Regular("transpose"),
                     Mf::Determinant => Function::Regular("determinant"),
+                    Mf::QuantizeToF16 => Function::QuantizeToF16,
                     // bits
                     Mf::CountTrailingZeros => Function::CountTrailingZeros,
                     Mf::CountLeadingZeros => Function::CountLeadingZeros,
@@ -2707,13 +2830,30 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::Pack2x16unorm => Function::Pack2x16unorm,
                     Mf::Pack4x8snorm => Function::Pack4x8snorm,
                     Mf::Pack4x8unorm => Function::Pack4x8unorm,
+                    Mf::Pack4xI8 => Function::Pack4x8Integer {
+                        signed: true,
+                        clamp: false,
+                    },
+                    Mf::Pack4xU8 => Function::Pack4x8Integer {
+                        signed: false,
+                        clamp: false,
+                    },
+                    Mf::Pack4xI8Clamp => Function::Pack4x8Integer {
+                        signed: true,
+                        clamp: true,
+                    },
+                    Mf::Pack4xU8Clamp => Function::Pack4x8Integer {
+                        signed: false,
+                        clamp: true,
+                    },
                     // Data Unpacking
                     Mf::Unpack2x16float => Function::Unpack2x16float,
                     Mf::Unpack2x16snorm => Function::Unpack2x16snorm,
                     Mf::Unpack2x16unorm => Function::Unpack2x16unorm,
                     Mf::Unpack4x8snorm => Function::Unpack4x8snorm,
                     Mf::Unpack4x8unorm => Function::Unpack4x8unorm,
-                    _ => return Err(Error::Unimplemented(format!("write_expr_math {fun:?}"))),
+                    Mf::Unpack4xI8 => Function::Unpack4x8Integer { signed: true },
+                    Mf::Unpack4xU8 => Function::Unpack4x8Integer { signed: false },
                 };
 
                 match fun {
@@ -2869,6 +3009,32 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         self.write_expr(module, arg, func_ctx)?;
                         write!(self.out, "[3], 0.0, 1.0) * {scale}.0)) << 24)")?;
                     }
+                    Function::Pack4x8Integer { signed, clamp } => {
+                        write!(self.out, "{}(", if signed { "asuint" } else { "" })?;
+                        for index in 0..4 {
+                            if index != 0 {
+                                write!(self.out, " | ")?;
+                            }
+                            write!(self.out, "(")?;
+                            match (clamp, signed) {
+                                (true, true) => write!(self.out, "clamp(")?,
+                                (true, false) => write!(self.out, "min(")?,
+                                (false, _) => {}
+                            }
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, "[{index}]")?;
+                            match (clamp, signed) {
+                                (true, true) => write!(self.out, ", -128, 127)")?,
+                                (true, false) => write!(self.out, ", 255u)")?,
+                                (false, _) => {}
+                            }
+                            write!(self.out, " & 0xFF)")?;
+                            if index != 0 {
+                                write!(self.out, " << {}", index * 8)?;
+                            }
+                        }
+                        write!(self.out, ")")?;
+                    }
 
                     Function::Unpack2x16float => {
                         write!(self.out, "float2(f16tof32(")?;
@@ -2921,6 +3087,73 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         self.write_expr(module, arg, func_ctx)?;
                         write!(self.out, " >> 24) / {scale}.0)")?;
                     }
+                    Function::Unpack4x8Integer { signed } => {
+                        self.write_unpacked_bytes(module, arg, signed, func_ctx)?;
+                    }
+                    Function::Dot4x8Packed { signed }
+                        if self.options.shader_model >= ShaderModel::V6_4 =>
+                    {
+                        // Available since shader model 6.4
+                        let (fun_name, zero) = if signed {
+                            ("dot4add_i8packed", "0")
+                        } else {
+                            ("dot4add_u8packed", "0u")
+                        };
+                        write!(self.out, "{fun_name}(")?;
+                        self.write_expr(module, arg, func_ctx)?;
+                        write!(self.out, ", ")?;
+                        self.write_expr(module, arg1.unwrap(), func_ctx)?;
+                        write!(self.out, ", {zero})")?;
+                    }
+                    Function::Dot4x8Packed { signed } => {
+                        let arg1 = arg1.unwrap();
+                        write!(self.out, "dot(")?;
+                        self.write_unpacked_bytes(module, arg, signed, func_ctx)?;
+                        write!(self.out, ", ")?;
+                        self.write_unpacked_bytes(module, arg1, signed, func_ctx)?;
+                        write!(self.out, ")")?;
+                    }
+                    Function::Outer => {
+                        // Column `i` of the outer product is `arg * arg1[i]`.
+                        let arg1 = arg1.unwrap();
+                        let columns = match *func_ctx.resolve_type(arg1, &module.types) {
+                            TypeInner::Vector { size, .. } => size as usize,
+                            _ => unreachable!(),
+                        };
+                        let ty = func_ctx.info[expr].ty.inner_with(&module.types);
+                        self.write_value_type(module, ty)?;
+                        write!(self.out, "(")?;
+                        for column in 0..columns {
+                            if column != 0 {
+                                write!(self.out, ", ")?;
+                            }
+                            self.write_expr(module, arg, func_ctx)?;
+                            write!(self.out, " * ")?;
+                            self.write_expr(module, arg1, func_ctx)?;
+                            write!(self.out, "[{column}]")?;
+                        }
+                        write!(self.out, ")")?;
+                    }
+                    Function::Inverse => {
+                        if let TypeInner::Matrix { columns, width, .. } =
+                            *func_ctx.resolve_type(arg, &module.types)
+                        {
+                            self.write_wrapped_matrix_inverse_function_name(
+                                WrappedMatrixInverse {
+                                    size: columns,
+                                    width,
+                                },
+                            )?;
+                        }
+                        write!(self.out, "(")?;
+                        self.write_expr(module, arg, func_ctx)?;
+                        write!(self.out, ")")?;
+                    }
+                    Function::QuantizeToF16 => {
+                        write!(self.out, "f16tof32(f32tof16(")?;
+                        self.write_expr(module, arg, func_ctx)?;
+                        write!(self.out, "))")?;
+                    }
                     Function::Regular(fun_name) => {
                         write!(self.out, "{fun_name}(")?;
                         self.write_expr(module, arg, func_ctx)?;
@@ -3219,6 +3452,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         if barrier.contains(crate::Barrier::WORK_GROUP) {
             writeln!(self.out, "{level}GroupMemoryBarrierWithGroupSync();")?;
         }
+        if barrier.contains(crate::Barrier::TEXTURE) {
+            writeln!(self.out, "{level}DeviceMemoryBarrierWithGroupSync();")?;
+        }
         Ok(())
     }
 }
@@ -218,6 +218,155 @@ const fn vector_size_str(size: crate::VectorSize) -> &'static str {
     }
 }
 
+/// Helper function that returns the size and width of every matrix whose
+/// [`Inverse`](crate::MathFunction::Inverse) is taken in `module`, sorted.
+///
+/// # Notes
+/// Used by `msl-out` to decide which helper functions to write.
+fn matrix_inverses(
+    module: &crate::Module,
+    info: &crate::valid::ModuleInfo,
+) -> Vec<(crate::VectorSize, crate::Bytes)> {
+    let functions = module
+        .functions
+        .iter()
+        .map(|(handle, function)| (function, &info[handle]));
+    let entry_points = module
+        .entry_points
+        .iter()
+        .enumerate()
+        .map(|(index, ep)| (&ep.function, info.get_entry_point(index)));
+
+    let mut inverses = Vec::new();
+    for (function, function_info) in functions.chain(entry_points) {
+        for (_, expression) in function.expressions.iter() {
+            if let crate::Expression::Math {
+                fun: crate::MathFunction::Inverse,
+                arg,
+                ..
+            } = *expression
+            {
+                if let crate::TypeInner::Matrix { columns, width, .. } =
+                    *function_info[arg].ty.inner_with(&module.types)
+                {
+                    inverses.push((columns, width));
+                }
+            }
+        }
+    }
+    inverses.sort();
+    inverses.dedup();
+    inverses
+}
+
+/// Helper function that writes the body of a function computing the inverse
+/// of the square matrix argument `m`, as a sequence of statements at `level`.
+///
+/// `local` is the keyword that declares a scalar local (`"float"`, `"let"`...),
+/// and `matrix` and `vector` name the matrix type and its column type. The body
+/// only needs `m[column][row]` indexing and scalar arithmetic.
+///
+/// # Notes
+/// Used by `msl-out`, `wgsl-out`, `hlsl-out`, whose languages lack an `inverse` built-in.
+fn write_matrix_inverse_body(
+    out: &mut impl std::fmt::Write,
+    size: crate::VectorSize,
+    local: &str,
+    matrix: &str,
+    vector: &str,
+    level: Level,
+) -> std::fmt::Result {
+    // Cofactor expansions, with `aCR` standing for `m[C][R]`, and the result
+    // elements listed column by column before dividing by the determinant.
+    let (temporaries, determinant, elements): (&[(&str, &str)], &str, &[&str]) = match size {
+        crate::VectorSize::Bi => (
+            &[],
+            "a00 * a11 - a10 * a01",
+            &["a11", "-a01", "-a10", "a00"],
+        ),
+        crate::VectorSize::Tri => (
+            &[
+                ("b01", "a22 * a11 - a12 * a21"),
+                ("b11", "a12 * a20 - a22 * a10"),
+                ("b21", "a21 * a10 - a11 * a20"),
+            ],
+            "a00 * b01 + a01 * b11 + a02 * b21",
+            &[
+                "b01",
+                "a02 * a21 - a22 * a01",
+                "a12 * a01 - a02 * a11",
+                "b11",
+                "a22 * a00 - a02 * a20",
+                "a02 * a10 - a12 * a00",
+                "b21",
+                "a01 * a20 - a21 * a00",
+                "a11 * a00 - a01 * a10",
+            ],
+        ),
+        crate::VectorSize::Quad => (
+            &[
+                ("b00", "a00 * a11 - a01 * a10"),
+                ("b01", "a00 * a12 - a02 * a10"),
+                ("b02", "a00 * a13 - a03 * a10"),
+                ("b03", "a01 * a12 - a02 * a11"),
+                ("b04", "a01 * a13 - a03 * a11"),
+                ("b05", "a02 * a13 - a03 * a12"),
+                ("b06", "a20 * a31 - a21 * a30"),
+                ("b07", "a20 * a32 - a22 * a30"),
+                ("b08", "a20 * a33 - a23 * a30"),
+                ("b09", "a21 * a32 - a22 * a31"),
+                ("b10", "a21 * a33 - a23 * a31"),
+                ("b11", "a22 * a33 - a23 * a32"),
+            ],
+            "b00 * b11 - b01 * b10 + b02 * b09 + b03 * b08 - b04 * b07 + b05 * b06",
+            &[
+                "a11 * b11 - a12 * b10 + a13 * b09",
+                "a02 * b10 - a01 * b11 - a03 * b09",
+                "a31 * b05 - a32 * b04 + a33 * b03",
+                "a22 * b04 - a21 * b05 - a23 * b03",
+                "a12 * b08 - a10 * b11 - a13 * b07",
+                "a00 * b11 - a02 * b08 + a03 * b07",
+                "a32 * b02 - a30 * b05 - a33 * b01",
+                "a20 * b05 - a22 * b02 + a23 * b01",
+                "a10 * b10 - a11 * b08 + a13 * b06",
+                "a01 * b08 - a00 * b10 - a03 * b06",
+                "a30 * b04 - a31 * b02 + a33 * b00",
+                "a21 * b02 - a20 * b04 - a23 * b00",
+                "a11 * b07 - a10 * b09 - a12 * b06",
+                "a00 * b09 - a01 * b07 + a02 * b06",
+                "a31 * b01 - a30 * b03 - a32 * b00",
+                "a20 * b03 - a21 * b01 + a22 * b00",
+            ],
+        ),
+    };
+
+    let size = size as usize;
+    for column in 0..size {
+        for row in 0..size {
+            writeln!(out, "{level}{local} a{column}{row} = m[{column}][{row}];")?;
+        }
+    }
+    for &(name, value) in temporaries {
+        writeln!(out, "{level}{local} {name} = {value};")?;
+    }
+    writeln!(out, "{level}{local} inv_det = 1.0 / ({determinant});")?;
+    write!(out, "{level}return {matrix}(")?;
+    for (column, elements) in elements.chunks(size).enumerate() {
+        if column != 0 {
+            write!(out, ", ")?;
+        }
+        write!(out, "{vector}(")?;
+        for (row, element) in elements.iter().enumerate() {
+            if row != 0 {
+                write!(out, ", ")?;
+            }
+            write!(out, "{element}")?;
+        }
+        write!(out, ") * inv_det")?;
+    }
+    writeln!(out, ");")
+}
+
 impl crate::TypeInner {
     const fn is_handle(&self) -> bool {
         match *self {
@@ -34,6 +34,7 @@ const RAY_QUERY_FUN_MAP_INTERSECTION: &str = "_map_intersection_type";
 
 pub(crate) const MODF_FUNCTION: &str = "naga_modf";
 pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
+pub(crate) const INVERSE_FUNCTION: &str = "naga_inverse";
 
 /// Write the Metal name for a Naga numeric type: scalar, vector, or matrix.
 ///
@@ -46,18 +47,19 @@ pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
 fn put_numeric_type(
     out: &mut impl Write,
     kind: crate::ScalarKind,
+    width: crate::Bytes,
     sizes: &[crate::VectorSize],
 ) -> Result<(), FmtError> {
     match (kind, sizes) {
         (kind, &[]) => {
-            write!(out, "{}", kind.to_msl_name())
+            write!(out, "{}", scalar_msl_name(kind, width))
         }
         (kind, &[rows]) => {
             write!(
                 out,
                 "{}::{}{}",
                 NAMESPACE,
-                kind.to_msl_name(),
+                scalar_msl_name(kind, width),
                 back::vector_size_str(rows)
             )
         }
@@ -96,14 +98,23 @@ impl<'a> Display for TypeContext<'a> {
         }
 
         match ty.inner {
-            crate::TypeInner::Scalar { kind, .. } => put_numeric_type(out, kind, &[]),
-            crate::TypeInner::Atomic { kind, .. } => {
-                write!(out, "{}::atomic_{}", NAMESPACE, kind.to_msl_name())
+            crate::TypeInner::Scalar { kind, width } => put_numeric_type(out, kind, width, &[]),
+            crate::TypeInner::Atomic { kind, width } => {
+                write!(
+                    out,
+                    "{}::atomic_{}",
+                    NAMESPACE,
+                    scalar_msl_name(kind, width)
+                )
             }
-            crate::TypeInner::Vector { size, kind, .. } => put_numeric_type(out, kind, &[size]),
-            crate::TypeInner::Matrix { columns, rows, .. } => {
-                put_numeric_type(out, crate::ScalarKind::Float, &[rows, columns])
+            crate::TypeInner::Vector { size, kind, width } => {
+                put_numeric_type(out, kind, width, &[size])
             }
+            crate::TypeInner::Matrix {
+                columns,
+                rows,
+                width,
+            } => put_numeric_type(out, crate::ScalarKind::Float, width, &[rows, columns]),
             crate::TypeInner::Pointer { base, space } => {
                 let sub = Self {
                     handle: base,
@@ -119,7 +130,7 @@ impl<'a> Display for TypeContext<'a> {
             crate::TypeInner::ValuePointer {
                 size,
                 kind,
-                width: _,
+                width,
                 space,
             } => {
                 match space.to_msl_name() {
@@ -127,8 +138,8 @@ impl<'a> Display for TypeContext<'a> {
                     None => return Ok(()),
                 };
                 match size {
-                    Some(rows) => put_numeric_type(out, kind, &[rows])?,
-                    None => put_numeric_type(out, kind, &[])?,
+                    Some(rows) => put_numeric_type(out, kind, width, &[rows])?,
+                    None => put_numeric_type(out, kind, width, &[])?,
                 };
 
                 write!(out, "&")
@@ -172,6 +183,10 @@ impl<'a> Display for TypeContext<'a> {
                         };
                         ("depth", msaa_str, crate::ScalarKind::Float, access)
                     }
+                    // Rejected by `Writer::write` before we get here.
+                    crate::ImageClass::External => {
+                        ("texture", "", crate::ScalarKind::Float, "sample")
+                    }
                     crate::ImageClass::Storage { format, .. } => {
                         let access = if self
                             .access
@@ -330,6 +345,15 @@ impl crate::ScalarKind {
     }
 }
 
+/// Return the MSL name of the scalar type with the given `kind` and `width`.
+const fn scalar_msl_name(kind: crate::ScalarKind, width: crate::Bytes) -> &'static str {
+    match (kind, width) {
+        (crate::ScalarKind::Sint, 8) => "long",
+        (crate::ScalarKind::Uint, 8) => "ulong",
+        (kind, _) => kind.to_msl_name(),
+    }
+}
+
 const fn separate(need_separator: bool) -> &'static str {
     if need_separator {
         ","
@@ -722,10 +746,10 @@ impl<W: Write> Writer<W> {
         // coordinates in IR are int, but Metal expects uint
         match *context.resolve_type(expr) {
             crate::TypeInner::Scalar { .. } => {
-                put_numeric_type(&mut self.out, crate::ScalarKind::Uint, &[])?
+                put_numeric_type(&mut self.out, crate::ScalarKind::Uint, 4, &[])?
             }
             crate::TypeInner::Vector { size, .. } => {
-                put_numeric_type(&mut self.out, crate::ScalarKind::Uint, &[size])?
+                put_numeric_type(&mut self.out, crate::ScalarKind::Uint, 4, &[size])?
             }
             _ => return Err(Error::Validation),
         };
@@ -1014,6 +1038,20 @@ impl<W: Write> Writer<W> {
             }
         }
 
+        // Metal only guarantees that a thread observes its own writes to a
+        // `read_write` texture after a call to `fence()`.
+        if let crate::TypeInner::Image {
+            class: crate::ImageClass::Storage { access, .. },
+            ..
+        } = *context.expression.resolve_type(image)
+        {
+            if access.contains(crate::StorageAccess::LOAD | crate::StorageAccess::STORE) {
+                write!(self.out, "{level}")?;
+                self.put_expression(image, &context.expression, false)?;
+                writeln!(self.out, ".fence();")?;
+            }
+        }
+
         Ok(())
     }
 
@@ -1149,6 +1187,43 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Emit a 64-bit atomic min or max operation.
+    ///
+    /// Metal only supports `atomic_min_explicit` and `atomic_max_explicit` on
+    /// `atomic_ulong`, and those functions don't return the original value, so
+    /// the `result` expression must not be used.
+    fn put_atomic_64_bit(
+        &mut self,
+        pointer: Handle<crate::Expression>,
+        kind: crate::ScalarKind,
+        fun: &crate::AtomicFunction,
+        value: Handle<crate::Expression>,
+        result: Handle<crate::Expression>,
+        context: &StatementContext,
+    ) -> BackendResult {
+        if context.expression.lang_version < (2, 4) {
+            return Err(Error::UnsupportedFunction(
+                "64-bit atomic min/max".to_string(),
+            ));
+        }
+        if kind != crate::ScalarKind::Uint {
+            return Err(Error::FeatureNotImplemented(
+                "64-bit signed atomics".to_string(),
+            ));
+        }
+        if context.expression.info[result].ref_count != 0 {
+            return Err(Error::FeatureNotImplemented(
+                "using the result of a 64-bit atomic operation".to_string(),
+            ));
+        }
+        let key = match *fun {
+            crate::AtomicFunction::Min => "min",
+            crate::AtomicFunction::Max => "max",
+            _ => return Err(Error::Validation),
+        };
+        self.put_atomic_operation(pointer, key, "", value, &context.expression)
+    }
+
     /// Emit code for the arithmetic expression of the dot product.
     ///
     fn put_dot_product(
@@ -1186,6 +1261,40 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Emit code for the packed 4x8 integer dot product, reading the bytes of
+    /// each operand as a `char4` or `uchar4` vector.
+    ///
+    fn put_dot4_packed(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        arg1: Handle<crate::Expression>,
+        signed: bool,
+        context: &ExpressionContext,
+    ) -> BackendResult {
+        let (scalar, bytes) = if signed {
+            ("int", "char4")
+        } else {
+            ("uint", "uchar4")
+        };
+        write!(self.out, "(")?;
+        for (index, component) in back::COMPONENTS.iter().enumerate() {
+            if index != 0 {
+                write!(self.out, " + ")?;
+            }
+            // Both operands are baked, see the comment in `put_dot_product`.
+            write!(self.out, "{scalar}(as_type<{NAMESPACE}::{bytes}>(")?;
+            self.put_expression(arg, context, true)?;
+            write!(
+                self.out,
+                ").{component}) * {scalar}(as_type<{NAMESPACE}::{bytes}>("
+            )?;
+            self.put_expression(arg1, context, true)?;
+            write!(self.out, ").{component})")?;
+        }
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
     /// Emit code for the sign(i32) expression.
     ///
     fn put_isign(
@@ -1265,9 +1374,18 @@ impl<W: Write> Writer<W> {
                 crate::Literal::I32(value) => {
                     write!(self.out, "{value}")?;
                 }
+                crate::Literal::U64(value) => {
+                    write!(self.out, "{value}uL")?;
+                }
+                crate::Literal::I64(value) => {
+                    write!(self.out, "{value}L")?;
+                }
                 crate::Literal::Bool(value) => {
                     write!(self.out, "{value}")?;
                 }
+                crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
+                    return Err(Error::Validation);
+                }
             },
             crate::Expression::Constant(handle) => {
                 let constant = &module.constants[handle];
@@ -1326,11 +1444,12 @@ impl<W: Write> Writer<W> {
                 }
             }
             crate::Expression::Splat { size, value } => {
-                let scalar_kind = match *get_expr_ty(ctx, value).inner_with(&module.types) {
-                    crate::TypeInner::Scalar { kind, .. } => kind,
+                let (scalar_kind, width) = match *get_expr_ty(ctx, value).inner_with(&module.types)
+                {
+                    crate::TypeInner::Scalar { kind, width } => (kind, width),
                     _ => return Err(Error::Validation),
                 };
-                put_numeric_type(&mut self.out, scalar_kind, &[size])?;
+                put_numeric_type(&mut self.out, scalar_kind, width, &[size])?;
                 write!(self.out, "(")?;
                 put_expression(self, ctx, value)?;
                 write!(self.out, ")")?;
@@ -1742,7 +1861,15 @@ impl<W: Write> Writer<W> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
-                    Mf::Outer => return Err(Error::UnsupportedCall(format!("{fun:?}"))),
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        return self.put_dot4_packed(
+                            arg,
+                            arg1.unwrap(),
+                            fun == Mf::Dot4I8Packed,
+                            context,
+                        )
+                    }
+                    Mf::Outer => "",
                     Mf::Cross => "cross",
                     Mf::Distance => "distance",
                     Mf::Length if scalar_argument => "abs",
@@ -1764,9 +1891,10 @@ impl<W: Write> Writer<W> {
                     Mf::SmoothStep => "smoothstep",
                     Mf::Sqrt => "sqrt",
                     Mf::InverseSqrt => "rsqrt",
-                    Mf::Inverse => return Err(Error::UnsupportedCall(format!("{fun:?}"))),
+                    Mf::Inverse => INVERSE_FUNCTION,
                     Mf::Transpose => "transpose",
                     Mf::Determinant => "determinant",
+                    Mf::QuantizeToF16 => "",
                     // bits
                     Mf::CountTrailingZeros => "ctz",
                     Mf::CountLeadingZeros => "clz",
@@ -1782,12 +1910,14 @@ impl<W: Write> Writer<W> {
                     Mf::Pack2x16snorm => "pack_float_to_snorm2x16",
                     Mf::Pack2x16unorm => "pack_float_to_unorm2x16",
                     Mf::Pack2x16float => "",
+                    Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => "",
                     // data unpacking
                     Mf::Unpack4x8snorm => "unpack_snorm4x8_to_float",
                     Mf::Unpack4x8unorm => "unpack_unorm4x8_to_float",
                     Mf::Unpack2x16snorm => "unpack_snorm2x16_to_float",
                     Mf::Unpack2x16unorm => "unpack_unorm2x16_to_float",
                     Mf::Unpack2x16float => "",
+                    Mf::Unpack4xI8 | Mf::Unpack4xU8 => "",
                 };
 
                 match fun {
@@ -1869,6 +1999,36 @@ impl<W: Write> Writer<W> {
                     write!(self.out, "as_type<uint>(half2(")?;
                     self.put_expression(arg, context, false)?;
                     write!(self.out, "))")?;
+                } else if let Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp =
+                    fun
+                {
+                    let (vector, bytes, min, max) = match fun {
+                        Mf::Pack4xI8 | Mf::Pack4xI8Clamp => ("int4", "char4", "-128", "127"),
+                        _ => ("uint4", "uchar4", "0u", "255u"),
+                    };
+                    write!(self.out, "as_type<uint>({NAMESPACE}::{bytes}(")?;
+                    if let Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp = fun {
+                        write!(self.out, "{NAMESPACE}::clamp(")?;
+                        self.put_expression(arg, context, false)?;
+                        write!(
+                            self.out,
+                            ", {NAMESPACE}::{vector}({min}), {NAMESPACE}::{vector}({max}))"
+                        )?;
+                    } else {
+                        self.put_expression(arg, context, false)?;
+                    }
+                    write!(self.out, "))")?;
+                } else if let Mf::Unpack4xI8 | Mf::Unpack4xU8 = fun {
+                    let (vector, bytes) = match fun {
+                        Mf::Unpack4xI8 => ("int4", "char4"),
+                        _ => ("uint4", "uchar4"),
+                    };
+                    write!(
+                        self.out,
+                        "{NAMESPACE}::{vector}(as_type<{NAMESPACE}::{bytes}>("
+                    )?;
+                    self.put_expression(arg, context, false)?;
+                    write!(self.out, "))")?;
                 } else if fun == Mf::Radians {
                     write!(self.out, "((")?;
                     self.put_expression(arg, context, false)?;
@@ -1877,7 +2037,56 @@ impl<W: Write> Writer<W> {
                     write!(self.out, "((")?;
                     self.put_expression(arg, context, false)?;
                     write!(self.out, ") * 57.295779513082322865)")?;
-                } else if fun == Mf::Modf || fun == Mf::Frexp {
+                } else if fun == Mf::Outer {
+                    // Column `i` of the outer product is `arg * arg1[i]`.
+                    let arg1 = arg1.unwrap();
+                    let columns = match *context.resolve_type(expr_handle) {
+                        crate::TypeInner::Matrix {
+                            columns,
+                            rows,
+                            width,
+                        } => {
+                            put_numeric_type(
+                                &mut self.out,
+                                crate::ScalarKind::Float,
+                                width,
+                                &[rows, columns],
+                            )?;
+                            columns as usize
+                        }
+                        _ => unreachable!(),
+                    };
+                    write!(self.out, "(")?;
+                    for column in 0..columns {
+                        if column != 0 {
+                            write!(self.out, ", ")?;
+                        }
+                        self.put_expression(arg, context, false)?;
+                        write!(self.out, " * ")?;
+                        self.put_expression(arg1, context, false)?;
+                        write!(self.out, ".{}", back::COMPONENTS[column])?;
+                    }
+                    write!(self.out, ")")?;
+                } else if fun == Mf::QuantizeToF16 {
+                    match *arg_type {
+                        crate::TypeInner::Vector { size, width, .. } => {
+                            put_numeric_type(
+                                &mut self.out,
+                                crate::ScalarKind::Float,
+                                width,
+                                &[size],
+                            )?;
+                            write!(
+                                self.out,
+                                "({NAMESPACE}::half{}(",
+                                back::vector_size_str(size)
+                            )?;
+                        }
+                        _ => write!(self.out, "float(half(")?,
+                    }
+                    self.put_expression(arg, context, false)?;
+                    write!(self.out, "))")?;
+                } else if fun == Mf::Inverse || fun == Mf::Modf || fun == Mf::Frexp {
                     write!(self.out, "{fun_name}")?;
                     self.put_call_parameters(iter::once(arg), context)?;
                 } else {
@@ -1909,22 +2118,33 @@ impl<W: Write> Writer<W> {
                         Some(8) if kind == crate::ScalarKind::Float => {
                             return Err(Error::CapabilityNotSupported(valid::Capabilities::FLOAT64))
                         }
-                        Some(_) => return Err(Error::Validation),
+                        // Conversions between 32-bit and 64-bit integers.
+                        Some(_) => "static_cast",
                         None => "as_type",
                     };
+                    let width = convert.unwrap_or(src_width);
                     write!(self.out, "{op}<")?;
                     match *context.resolve_type(expr) {
                         crate::TypeInner::Vector { size, .. } => {
-                            put_numeric_type(&mut self.out, kind, &[size])?
+                            put_numeric_type(&mut self.out, kind, width, &[size])?
                         }
-                        _ => put_numeric_type(&mut self.out, kind, &[])?,
+                        _ => put_numeric_type(&mut self.out, kind, width, &[])?,
                     };
                     write!(self.out, ">(")?;
                     self.put_expression(expr, context, true)?;
                     write!(self.out, ")")?;
                 }
-                crate::TypeInner::Matrix { columns, rows, .. } => {
-                    put_numeric_type(&mut self.out, kind, &[rows, columns])?;
+                crate::TypeInner::Matrix {
+                    columns,
+                    rows,
+                    width,
+                } => {
+                    put_numeric_type(
+                        &mut self.out,
+                        kind,
+                        convert.unwrap_or(width),
+                        &[rows, columns],
+                    )?;
                     write!(self.out, "(")?;
                     self.put_expression(expr, context, true)?;
                     write!(self.out, ")")?;
@@ -2485,9 +2705,17 @@ impl<W: Write> Writer<W> {
                             }
                         }
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::FindMsb => {
                         self.need_bake_expressions.insert(arg);
                     }
+                    crate::MathFunction::Outer => {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::Sign => {
                         // WGSL's `sign` function works also on signed ints, but Metal's only
                         // works on floating points, so we emit inline code for integer `sign`
@@ -2522,14 +2750,23 @@ impl<W: Write> Writer<W> {
                 };
                 write!(self.out, "{ty_name}")?;
             }
-            TypeResolution::Value(crate::TypeInner::Scalar { kind, .. }) => {
-                put_numeric_type(&mut self.out, kind, &[])?;
+            TypeResolution::Value(crate::TypeInner::Scalar { kind, width }) => {
+                put_numeric_type(&mut self.out, kind, width, &[])?;
             }
-            TypeResolution::Value(crate::TypeInner::Vector { size, kind, .. }) => {
-                put_numeric_type(&mut self.out, kind, &[size])?;
+            TypeResolution::Value(crate::TypeInner::Vector { size, kind, width }) => {
+                put_numeric_type(&mut self.out, kind, width, &[size])?;
             }
-            TypeResolution::Value(crate::TypeInner::Matrix { columns, rows, .. }) => {
-                put_numeric_type(&mut self.out, crate::ScalarKind::Float, &[rows, columns])?;
+            TypeResolution::Value(crate::TypeInner::Matrix {
+                columns,
+                rows,
+                width,
+            }) => {
+                put_numeric_type(
+                    &mut self.out,
+                    crate::ScalarKind::Float,
+                    width,
+                    &[rows, columns],
+                )?;
             }
             TypeResolution::Value(ref other) => {
                 log::warn!("Type {:?} isn't a known local", other); //TEMP!
@@ -2857,6 +3094,13 @@ impl<W: Write> Writer<W> {
                     result,
                 } => {
                     write!(self.out, "{level}")?;
+                    if let crate::TypeInner::Scalar { kind, width: 8 } =
+                        *context.expression.resolve_type(result)
+                    {
+                        self.put_atomic_64_bit(pointer, kind, fun, value, result, context)?;
+                        writeln!(self.out, ";")?;
+                        continue;
+                    }
                     let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
                     self.start_baking_expression(result, &context.expression, &res_name)?;
                     self.named_expressions.insert(result, res_name);
@@ -3122,6 +3366,14 @@ impl<W: Write> Writer<W> {
                     }
                     uses_ray_query = true;
                 }
+                crate::TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } => {
+                    return Err(Error::FeatureNotImplemented(
+                        "external textures (use `naga::external_texture::lower`)".to_string(),
+                    ));
+                }
                 _ => (),
             }
         }
@@ -3168,6 +3420,7 @@ impl<W: Write> Writer<W> {
         };
 
         self.write_type_defs(module)?;
+        self.write_matrix_inverse_functions(module, info)?;
         self.write_global_constants(module, info)?;
         self.write_functions(module, info, options, pipeline_options)
     }
@@ -3400,6 +3653,36 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Writes an overload of the inverse helper function for each matrix
+    /// size whose inverse the module takes, since Metal has no built-in.
+    fn write_matrix_inverse_functions(
+        &mut self,
+        module: &crate::Module,
+        mod_info: &valid::ModuleInfo,
+    ) -> BackendResult {
+        for (size, width) in back::matrix_inverses(module, mod_info) {
+            let scalar = scalar_msl_name(crate::ScalarKind::Float, width);
+            let mut matrix = String::new();
+            put_numeric_type(&mut matrix, crate::ScalarKind::Float, width, &[size, size])?;
+            let mut vector = String::new();
+            put_numeric_type(&mut vector, crate::ScalarKind::Float, width, &[size])?;
+
+            writeln!(self.out)?;
+            writeln!(self.out, "{matrix} {INVERSE_FUNCTION}({matrix} m) {{")?;
+            back::write_matrix_inverse_body(
+                &mut self.out,
+                size,
+                scalar,
+                &matrix,
+                &vector,
+                back::Level(1),
+            )?;
+            writeln!(self.out, "}}")?;
+        }
+
+        Ok(())
+    }
+
     /// Writes all named constants
     fn write_global_constants(
         &mut self,
@@ -4058,6 +4341,7 @@ impl<W: Write> Writer<W> {
                                 crate::TypeInner::Image { class, .. } => match class {
                                     crate::ImageClass::Sampled { .. }
                                     | crate::ImageClass::Depth { .. }
+                                    | crate::ImageClass::External
                                     | crate::ImageClass::Storage {
                                         access: crate::StorageAccess::LOAD,
                                         ..
@@ -4346,6 +4630,12 @@ impl<W: Write> Writer<W> {
                 "{level}{NAMESPACE}::threadgroup_barrier({NAMESPACE}::mem_flags::mem_threadgroup);",
             )?;
         }
+        if flags.contains(crate::Barrier::TEXTURE) {
+            writeln!(
+                self.out,
+                "{level}{NAMESPACE}::threadgroup_barrier({NAMESPACE}::mem_flags::mem_texture);",
+            )?;
+        }
         Ok(())
     }
 }
@@ -831,6 +831,57 @@ impl<'w> BlockContext<'w> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed
+                        if self.writer.supports_packed_dot_product() =>
+                    {
+                        self.writer.require_packed_dot_product()?;
+                        let op = if fun == Mf::Dot4I8Packed {
+                            spirv::Op::SDot
+                        } else {
+                            spirv::Op::UDot
+                        };
+                        MathOp::Custom(Instruction::packed_dot(
+                            op,
+                            result_type_id,
+                            id,
+                            arg0_id,
+                            arg1_id,
+                        ))
+                    }
+                    // Without the integer dot product, multiply and add the
+                    // unpacked bytes.
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let signed = fun == Mf::Dot4I8Packed;
+                        let a_ids = self.write_unpacked_bytes(arg0_id, signed, block);
+                        let b_ids = self.write_unpacked_bytes(arg1_id, signed, block);
+
+                        let mut partial_sum = self.writer.get_constant_null(result_type_id);
+                        for (index, (a_id, b_id)) in a_ids.into_iter().zip(b_ids).enumerate() {
+                            let prod_id = self.gen_id();
+                            block.body.push(Instruction::binary(
+                                spirv::Op::IMul,
+                                result_type_id,
+                                prod_id,
+                                a_id,
+                                b_id,
+                            ));
+                            let sum_id = if index == a_ids.len() - 1 {
+                                id
+                            } else {
+                                self.gen_id()
+                            };
+                            block.body.push(Instruction::binary(
+                                spirv::Op::IAdd,
+                                result_type_id,
+                                sum_id,
+                                partial_sum,
+                                prod_id,
+                            ));
+                            partial_sum = sum_id;
+                        }
+                        self.cached[expr_handle] = id;
+                        return Ok(());
+                    }
                     Mf::Outer => MathOp::Custom(Instruction::binary(
                         spirv::Op::OuterProduct,
                         result_type_id,
@@ -908,6 +959,12 @@ impl<'w> BlockContext<'w> {
                         arg0_id,
                     )),
                     Mf::Determinant => MathOp::Ext(spirv::GLOp::Determinant),
+                    Mf::QuantizeToF16 => MathOp::Custom(Instruction::unary(
+                        spirv::Op::QuantizeToF16,
+                        result_type_id,
+                        id,
+                        arg0_id,
+                    )),
                     Mf::ReverseBits => MathOp::Custom(Instruction::unary(
                         spirv::Op::BitReverse,
                         result_type_id,
@@ -1052,11 +1109,20 @@ impl<'w> BlockContext<'w> {
                     Mf::Pack2x16float => MathOp::Ext(spirv::GLOp::PackHalf2x16),
                     Mf::Pack2x16unorm => MathOp::Ext(spirv::GLOp::PackUnorm2x16),
                     Mf::Pack2x16snorm => MathOp::Ext(spirv::GLOp::PackSnorm2x16),
+                    Mf::Pack4xI8 | Mf::Pack4xU8 | Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp => {
+                        self.write_pack4x8(fun, id, result_type_id, arg0_id, block);
+                        self.cached[expr_handle] = id;
+                        return Ok(());
+                    }
                     Mf::Unpack4x8unorm => MathOp::Ext(spirv::GLOp::UnpackUnorm4x8),
                     Mf::Unpack4x8snorm => MathOp::Ext(spirv::GLOp::UnpackSnorm4x8),
                     Mf::Unpack2x16float => MathOp::Ext(spirv::GLOp::UnpackHalf2x16),
                     Mf::Unpack2x16unorm => MathOp::Ext(spirv::GLOp::UnpackUnorm2x16),
                     Mf::Unpack2x16snorm => MathOp::Ext(spirv::GLOp::UnpackSnorm2x16),
+                    Mf::Unpack4xI8 | Mf::Unpack4xU8 => {
+                        let ids = self.write_unpacked_bytes(arg0_id, fun == Mf::Unpack4xI8, block);
+                        MathOp::Custom(Instruction::composite_construct(result_type_id, id, &ids))
+                    }
                 };
 
                 block.body.push(match math_op {
@@ -1734,6 +1800,163 @@ impl<'w> BlockContext<'w> {
         }
     }
 
+    /// Extract the four bytes of the `u32` value `packed_id` as 32-bit
+    /// integers, sign-extended if `signed`, and return their ids.
+    ///
+    /// The first byte is the least significant one.
+    fn write_unpacked_bytes(
+        &mut self,
+        packed_id: Word,
+        signed: bool,
+        block: &mut Block,
+    ) -> [Word; 4] {
+        let (kind, op) = if signed {
+            (crate::ScalarKind::Sint, spirv::Op::BitFieldSExtract)
+        } else {
+            (crate::ScalarKind::Uint, spirv::Op::BitFieldUExtract)
+        };
+        let scalar_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            kind,
+            width: 4,
+            pointer_space: None,
+        }));
+
+        // `OpBitFieldSExtract` returns the type of its base operand.
+        let base_id = if signed {
+            let id = self.gen_id();
+            block.body.push(Instruction::unary(
+                spirv::Op::Bitcast,
+                scalar_type_id,
+                id,
+                packed_id,
+            ));
+            id
+        } else {
+            packed_id
+        };
+
+        let count_id = self.get_index_constant(8);
+        let mut ids = [0; 4];
+        for (index, id) in ids.iter_mut().enumerate() {
+            let offset_id = self.get_index_constant(index as Word * 8);
+            *id = self.gen_id();
+            block.body.push(Instruction::ternary(
+                op,
+                scalar_type_id,
+                *id,
+                base_id,
+                offset_id,
+                count_id,
+            ));
+        }
+        ids
+    }
+
+    /// Write the packing of the four components of the integer vector
+    /// `arg_id` into the bytes of the `u32` value `result_id`.
+    fn write_pack4x8(
+        &mut self,
+        fun: crate::MathFunction,
+        result_id: Word,
+        result_type_id: Word,
+        arg_id: Word,
+        block: &mut Block,
+    ) {
+        use crate::MathFunction as Mf;
+
+        let signed = matches!(fun, Mf::Pack4xI8 | Mf::Pack4xI8Clamp);
+        let kind = if signed {
+            crate::ScalarKind::Sint
+        } else {
+            crate::ScalarKind::Uint
+        };
+
+        let mut vector_id = arg_id;
+        if matches!(fun, Mf::Pack4xI8Clamp | Mf::Pack4xU8Clamp) {
+            let (min, max, op) = if signed {
+                (
+                    crate::Literal::I32(i8::MIN as i32),
+                    crate::Literal::I32(i8::MAX as i32),
+                    spirv::GLOp::SClamp,
+                )
+            } else {
+                (
+                    crate::Literal::U32(0),
+                    crate::Literal::U32(u8::MAX as u32),
+                    spirv::GLOp::UClamp,
+                )
+            };
+            let vector_type: LookupType = LocalType::Value {
+                vector_size: Some(crate::VectorSize::Quad),
+                kind,
+                width: 4,
+                pointer_space: None,
+            }
+            .into();
+            let min_id = self.writer.get_constant_scalar(min);
+            let min_id = self
+                .writer
+                .get_constant_composite(vector_type, &[min_id; 4]);
+            let max_id = self.writer.get_constant_scalar(max);
+            let max_id = self
+                .writer
+                .get_constant_composite(vector_type, &[max_id; 4]);
+
+            vector_id = self.gen_id();
+            let vector_type_id = self.get_type_id(vector_type);
+            block.body.push(Instruction::ext_inst(
+                self.writer.gl450_ext_inst_id,
+                op,
+                vector_type_id,
+                vector_id,
+                &[arg_id, min_id, max_id],
+            ));
+        }
+
+        let scalar_type_id = self.get_type_id(LookupType::Local(LocalType::Value {
+            vector_size: None,
+            kind,
+            width: 4,
+            pointer_space: None,
+        }));
+        let count_id = self.get_index_constant(8);
+        let mut packed_id = self.writer.get_constant_null(result_type_id);
+        for index in 0..4 {
+            let mut component_id = self.gen_id();
+            block.body.push(Instruction::composite_extract(
+                scalar_type_id,
+                component_id,
+                vector_id,
+                &[index],
+            ));
+            // `OpBitFieldInsert` needs its operands to have the same type.
+            if signed {
+                let id = self.gen_id();
+                block.body.push(Instruction::unary(
+                    spirv::Op::Bitcast,
+                    result_type_id,
+                    id,
+                    component_id,
+                ));
+                component_id = id;
+            }
+
+            let offset_id = self.get_index_constant(index * 8);
+            let id = if index == 3 { result_id } else { self.gen_id() };
+            block.body.push(Instruction::quaternary(
+                spirv::Op::BitFieldInsert,
+                result_type_id,
+                id,
+                packed_id,
+                component_id,
+                offset_id,
+                count_id,
+            ));
+            packed_id = id;
+        }
+    }
+
     pub(super) fn write_block(
         &mut self,
         label_id: Word,
@@ -114,9 +114,9 @@ impl Load {
     ) -> Result<Load, Error> {
         let opcode = match image_class {
             crate::ImageClass::Storage { .. } => spirv::Op::ImageRead,
-            crate::ImageClass::Depth { .. } | crate::ImageClass::Sampled { .. } => {
-                spirv::Op::ImageFetch
-            }
+            crate::ImageClass::Depth { .. }
+            | crate::ImageClass::Sampled { .. }
+            | crate::ImageClass::External => spirv::Op::ImageFetch,
         };
 
         // `OpImageRead` and `OpImageFetch` instructions produce vec4<f32>
@@ -826,6 +826,18 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) fn packed_dot(
+        op: Op,
+        result_type_id: Word,
+        id: Word,
+        operand_1: Word,
+        operand_2: Word,
+    ) -> Self {
+        let mut instruction = Self::binary(op, result_type_id, id, operand_1, operand_2);
+        instruction.add_operand(spirv::PackedVectorFormat::PackedVectorFormat4x8Bit as u32);
+        instruction
+    }
+
     pub(super) fn ternary(
         op: Op,
         result_type_id: Word,
@@ -70,6 +70,8 @@ pub enum Error {
     FeatureNotImplemented(&'static str),
     #[error("module is not validated properly: {0}")]
     Validation(&'static str),
+    #[error("function '{0}' takes a pointer that `proc::specialize_pointer_arguments` must remove first")]
+    UnspecializedPointerArgument(String),
 }
 
 #[derive(Default)]
@@ -222,6 +224,14 @@ impl LocalImageType {
                 flags: make_flags(false, ImageTypeFlags::empty()),
                 image_format: format.into(),
             },
+            // Rejected by `Writer::request_type_capabilities`; external
+            // textures have to be lowered before reaching this back end.
+            crate::ImageClass::External => LocalImageType {
+                sampled_type: crate::ScalarKind::Float,
+                dim,
+                flags: make_flags(false, ImageTypeFlags::SAMPLED),
+                image_format: spirv::ImageFormat::Unknown,
+            },
         }
     }
 }
@@ -40,6 +40,11 @@ impl Function {
     }
 }
 
+const PACKED_DOT_PRODUCT_CAPABILITIES: &[spirv::Capability] = &[
+    spirv::Capability::DotProduct,
+    spirv::Capability::DotProductInput4x8BitPacked,
+];
+
 impl Writer {
     pub fn new(options: &Options) -> Result<Self, Error> {
         let (major, minor) = options.lang_version;
@@ -181,6 +186,33 @@ impl Writer {
         self.extensions_used.insert(extension);
     }
 
+    /// Return true if `OpSDot` and `OpUDot` can take packed 4x8-bit operands.
+    ///
+    /// These are core in SPIR-V 1.6, and come from
+    /// `SPV_KHR_integer_dot_product` before that. If the available
+    /// capabilities were given, they decide; otherwise, only SPIR-V 1.6 is
+    /// assumed to support them.
+    pub(super) fn supports_packed_dot_product(&self) -> bool {
+        match self.capabilities_available {
+            Some(ref available) => PACKED_DOT_PRODUCT_CAPABILITIES
+                .iter()
+                .all(|cap| available.contains(cap)),
+            None => self.physical_layout.version >= 0x10600,
+        }
+    }
+
+    /// Record the capabilities, and the extension if needed, for `OpSDot` and
+    /// `OpUDot` on packed 4x8-bit operands.
+    pub(super) fn require_packed_dot_product(&mut self) -> Result<(), Error> {
+        for &cap in PACKED_DOT_PRODUCT_CAPABILITIES {
+            self.require_any("packed integer dot product", &[cap])?;
+        }
+        if self.physical_layout.version < 0x10600 {
+            self.use_extension("SPV_KHR_integer_dot_product");
+        }
+        Ok(())
+    }
+
     pub(super) fn get_type_id(&mut self, lookup_ty: LookupType) -> Word {
         match self.lookup_type.entry(lookup_ty) {
             Entry::Occupied(e) => *e.get(),
@@ -853,6 +885,11 @@ impl Writer {
                         self.request_image_format_capabilities(format.into())?;
                         false
                     }
+                    crate::ImageClass::External => {
+                        return Err(Error::FeatureNotImplemented(
+                            "external textures (use `naga::external_texture::lower`)",
+                        ))
+                    }
                 };
 
                 match dim {
@@ -885,6 +922,9 @@ impl Writer {
             crate::TypeInner::RayQuery => {
                 self.require_any("Ray Query", &[spirv::Capability::RayQueryKHR])?;
             }
+            crate::TypeInner::Atomic { width: 8, .. } => {
+                self.require_any("64-bit atomics", &[spirv::Capability::Int64Atomics])?;
+            }
             _ => {}
         }
         Ok(())
@@ -1197,8 +1237,18 @@ impl Writer {
             crate::Literal::F32(value) => Instruction::constant_32bit(type_id, id, value.to_bits()),
             crate::Literal::U32(value) => Instruction::constant_32bit(type_id, id, value),
             crate::Literal::I32(value) => Instruction::constant_32bit(type_id, id, value as u32),
+            crate::Literal::U64(value) => {
+                Instruction::constant_64bit(type_id, id, value as u32, (value >> 32) as u32)
+            }
+            crate::Literal::I64(value) => {
+                let bits = value as u64;
+                Instruction::constant_64bit(type_id, id, bits as u32, (bits >> 32) as u32)
+            }
             crate::Literal::Bool(true) => Instruction::constant_true(type_id, id),
             crate::Literal::Bool(false) => Instruction::constant_false(type_id, id),
+            crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
+                unreachable!("Abstract types should not appear in IR presented to backends");
+            }
         };
 
         instruction.to_words(&mut self.logical_layout.declarations);
@@ -1314,6 +1364,10 @@ impl Writer {
             spirv::MemorySemantics::WORKGROUP_MEMORY,
             flags.contains(crate::Barrier::WORK_GROUP),
         );
+        semantics.set(
+            spirv::MemorySemantics::IMAGE_MEMORY,
+            flags.contains(crate::Barrier::TEXTURE),
+        );
         let exec_scope_id = self.get_index_constant(spirv::Scope::Workgroup as u32);
         let mem_scope_id = self.get_index_constant(memory_scope as u32);
         let semantics_id = self.get_index_constant(semantics.bits());
@@ -2024,6 +2078,14 @@ impl Writer {
     ) -> Result<(), Error> {
         self.reset();
 
+        if let Some((function, _)) = crate::proc::find_unspecialized_pointer_argument(ir_module) {
+            let name = ir_module.functions[function]
+                .name
+                .clone()
+                .unwrap_or_default();
+            return Err(Error::UnspecializedPointerArgument(name));
+        }
+
         // Try to find the entry point and corresponding index
         let ep_index = match pipeline_options {
             Some(po) => {
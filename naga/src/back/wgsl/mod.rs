@@ -18,8 +18,6 @@ pub enum Error {
     Custom(String),
     #[error("{0}")]
     Unimplemented(String), // TODO: Error used only during development
-    #[error("Unsupported math function: {0:?}")]
-    UnsupportedMathFunction(crate::MathFunction),
     #[error("Unsupported relational function: {0:?}")]
     UnsupportedRelationalFunction(crate::RelationalFunction),
 }
@@ -51,6 +51,23 @@ enum Indirection {
     Reference,
 }
 
+/// A function the writer generates in place of a Naga
+/// [`MathFunction`](crate::MathFunction) that WGSL has no built-in for.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+enum MathHelper {
+    /// The inverse of a square matrix.
+    Inverse {
+        size: crate::VectorSize,
+        width: crate::Bytes,
+    },
+    /// The outer product of a `rows`-component and a `columns`-component vector.
+    Outer {
+        columns: crate::VectorSize,
+        rows: crate::VectorSize,
+        width: crate::Bytes,
+    },
+}
+
 bitflags::bitflags! {
     #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
     #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -68,6 +85,9 @@ pub struct Writer<W> {
     namer: proc::Namer,
     named_expressions: crate::NamedExpressions,
     ep_results: Vec<(ShaderStage, Handle<crate::Type>)>,
+    /// Helper functions used so far, with their names, to be written after
+    /// everything else.
+    math_helpers: crate::FastIndexMap<MathHelper, String>,
 }
 
 impl<W: Write> Writer<W> {
@@ -79,6 +99,7 @@ impl<W: Write> Writer<W> {
             namer: proc::Namer::default(),
             named_expressions: crate::NamedExpressions::default(),
             ep_results: vec![],
+            math_helpers: crate::FastIndexMap::default(),
         }
     }
 
@@ -95,6 +116,7 @@ impl<W: Write> Writer<W> {
         );
         self.named_expressions.clear();
         self.ep_results.clear();
+        self.math_helpers.clear();
     }
 
     fn is_builtin_wgsl_struct(&self, module: &Module, handle: Handle<crate::Type>) -> bool {
@@ -195,6 +217,62 @@ impl<W: Write> Writer<W> {
             }
         }
 
+        // Write the helpers the functions above called
+        for (&helper, name) in self.math_helpers.clone().iter() {
+            writeln!(self.out)?;
+            self.write_math_helper(helper, name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Helper method that writes the definition of a [`MathHelper`] function
+    fn write_math_helper(&mut self, helper: MathHelper, name: &str) -> BackendResult {
+        match helper {
+            MathHelper::Inverse { size, width } => {
+                let scalar = scalar_kind_str(crate::ScalarKind::Float, width);
+                let n = back::vector_size_str(size);
+                let matrix = format!("mat{n}x{n}<{scalar}>");
+                let vector = format!("vec{n}<{scalar}>");
+                writeln!(self.out, "fn {name}(m: {matrix}) -> {matrix} {{")?;
+                back::write_matrix_inverse_body(
+                    &mut self.out,
+                    size,
+                    "let",
+                    &matrix,
+                    &vector,
+                    back::Level(1),
+                )?;
+            }
+            MathHelper::Outer {
+                columns,
+                rows,
+                width,
+            } => {
+                let scalar = scalar_kind_str(crate::ScalarKind::Float, width);
+                let matrix = format!(
+                    "mat{}x{}<{scalar}>",
+                    back::vector_size_str(columns),
+                    back::vector_size_str(rows),
+                );
+                writeln!(
+                    self.out,
+                    "fn {name}(a: vec{}<{scalar}>, b: vec{}<{scalar}>) -> {matrix} {{",
+                    back::vector_size_str(rows),
+                    back::vector_size_str(columns),
+                )?;
+                write!(self.out, "{}return {matrix}(", back::INDENT)?;
+                for column in 0..columns as usize {
+                    if column != 0 {
+                        write!(self.out, ", ")?;
+                    }
+                    write!(self.out, "a * b.{}", back::COMPONENTS[column])?;
+                }
+                writeln!(self.out, ");")?;
+            }
+        }
+        writeln!(self.out, "}}")?;
+
         Ok(())
     }
 
@@ -471,6 +549,10 @@ impl<W: Write> Writer<W> {
                             ",write"
                         },
                     ),
+                    Ic::External => {
+                        write!(self.out, "texture_external")?;
+                        return Ok(());
+                    }
                 };
                 write!(
                     self.out,
@@ -919,6 +1001,10 @@ impl<W: Write> Writer<W> {
                 if barrier.contains(crate::Barrier::WORK_GROUP) {
                     writeln!(self.out, "{level}workgroupBarrier();")?;
                 }
+
+                if barrier.contains(crate::Barrier::TEXTURE) {
+                    writeln!(self.out, "{level}textureBarrier();")?;
+                }
             }
             Statement::RayQuery { .. } => unreachable!(),
         }
@@ -1099,7 +1185,14 @@ impl<W: Write> Writer<W> {
                     crate::Literal::F32(value) => write!(self.out, "{:?}", value)?,
                     crate::Literal::U32(value) => write!(self.out, "{}u", value)?,
                     crate::Literal::I32(value) => write!(self.out, "{}", value)?,
+                    crate::Literal::U64(value) => write!(self.out, "{}lu", value)?,
+                    crate::Literal::I64(value) => write!(self.out, "{}li", value)?,
                     crate::Literal::Bool(value) => write!(self.out, "{}", value)?,
+                    crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) => {
+                        return Err(Error::Custom(
+                            "Abstract types should not appear in IR presented to backends".into(),
+                        ));
+                    }
                 }
             }
             Expression::Constant(handle) => {
@@ -1248,6 +1341,21 @@ impl<W: Write> Writer<W> {
             } => {
                 use crate::SampleLevel as Sl;
 
+                if let TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } = *func_ctx.resolve_type(image, &module.types)
+                {
+                    write!(self.out, "textureSampleBaseClampToEdge(")?;
+                    self.write_expr(module, image, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, sampler, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, coordinate, func_ctx)?;
+                    write!(self.out, ")")?;
+                    return Ok(());
+                }
+
                 let suffix_cmp = match depth_ref {
                     Some(_) => "Compare",
                     None => "",
@@ -1488,6 +1596,7 @@ impl<W: Write> Writer<W> {
 
                 enum Function {
                     Regular(&'static str),
+                    Helper(MathHelper),
                 }
 
                 let function = match fun {
@@ -1529,6 +1638,8 @@ impl<W: Write> Writer<W> {
                     Mf::Pow => Function::Regular("pow"),
                     // geometry
                     Mf::Dot => Function::Regular("dot"),
+                    Mf::Dot4I8Packed => Function::Regular("dot4I8Packed"),
+                    Mf::Dot4U8Packed => Function::Regular("dot4U8Packed"),
                     Mf::Cross => Function::Regular("cross"),
                     Mf::Distance => Function::Regular("distance"),
                     Mf::Length => Function::Regular("length"),
@@ -1546,6 +1657,7 @@ impl<W: Write> Writer<W> {
                     Mf::InverseSqrt => Function::Regular("inverseSqrt"),
                     Mf::Transpose => Function::Regular("transpose"),
                     Mf::Determinant => Function::Regular("determinant"),
+                    Mf::QuantizeToF16 => Function::Regular("quantizeToF16"),
                     // bits
                     Mf::CountTrailingZeros => Function::Regular("countTrailingZeros"),
                     Mf::CountLeadingZeros => Function::Regular("countLeadingZeros"),
@@ -1561,28 +1673,65 @@ impl<W: Write> Writer<W> {
                     Mf::Pack2x16snorm => Function::Regular("pack2x16snorm"),
                     Mf::Pack2x16unorm => Function::Regular("pack2x16unorm"),
                     Mf::Pack2x16float => Function::Regular("pack2x16float"),
+                    Mf::Pack4xI8 => Function::Regular("pack4xI8"),
+                    Mf::Pack4xU8 => Function::Regular("pack4xU8"),
+                    Mf::Pack4xI8Clamp => Function::Regular("pack4xI8Clamp"),
+                    Mf::Pack4xU8Clamp => Function::Regular("pack4xU8Clamp"),
                     // data unpacking
                     Mf::Unpack4x8snorm => Function::Regular("unpack4x8snorm"),
                     Mf::Unpack4x8unorm => Function::Regular("unpack4x8unorm"),
                     Mf::Unpack2x16snorm => Function::Regular("unpack2x16snorm"),
                     Mf::Unpack2x16unorm => Function::Regular("unpack2x16unorm"),
                     Mf::Unpack2x16float => Function::Regular("unpack2x16float"),
-                    Mf::Inverse | Mf::Outer => {
-                        return Err(Error::UnsupportedMathFunction(fun));
-                    }
+                    Mf::Unpack4xI8 => Function::Regular("unpack4xI8"),
+                    Mf::Unpack4xU8 => Function::Regular("unpack4xU8"),
+                    Mf::Inverse => match *func_ctx.resolve_type(arg, &module.types) {
+                        TypeInner::Matrix { columns, width, .. } => {
+                            Function::Helper(MathHelper::Inverse {
+                                size: columns,
+                                width,
+                            })
+                        }
+                        _ => unreachable!(),
+                    },
+                    Mf::Outer => match *func_ctx.resolve_type(expr, &module.types) {
+                        TypeInner::Matrix {
+                            columns,
+                            rows,
+                            width,
+                        } => Function::Helper(MathHelper::Outer {
+                            columns,
+                            rows,
+                            width,
+                        }),
+                        _ => unreachable!(),
+                    },
                 };
 
-                match function {
-                    Function::Regular(fun_name) => {
-                        write!(self.out, "{fun_name}(")?;
-                        self.write_expr(module, arg, func_ctx)?;
-                        for arg in IntoIterator::into_iter([arg1, arg2, arg3]).flatten() {
-                            write!(self.out, ", ")?;
-                            self.write_expr(module, arg, func_ctx)?;
+                let helper_name;
+                let fun_name = match function {
+                    Function::Regular(fun_name) => fun_name,
+                    Function::Helper(helper) => {
+                        if !self.math_helpers.contains_key(&helper) {
+                            let base = match helper {
+                                MathHelper::Inverse { .. } => "naga_inverse",
+                                MathHelper::Outer { .. } => "naga_outer",
+                            };
+                            let name = self.namer.call(base);
+                            self.math_helpers.insert(helper, name);
                         }
-                        write!(self.out, ")")?
+                        helper_name = self.math_helpers[&helper].clone();
+                        &helper_name
                     }
+                };
+
+                write!(self.out, "{fun_name}(")?;
+                self.write_expr(module, arg, func_ctx)?;
+                for arg in IntoIterator::into_iter([arg1, arg2, arg3]).flatten() {
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, arg, func_ctx)?;
                 }
+                write!(self.out, ")")?
             }
 
             Expression::Swizzle {
@@ -1791,6 +1940,8 @@ const fn scalar_kind_str(kind: crate::ScalarKind, width: u8) -> &'static str {
         (Sk::Float, 4) => "f32",
         (Sk::Sint, 4) => "i32",
         (Sk::Uint, 4) => "u32",
+        (Sk::Sint, 8) => "i64",
+        (Sk::Uint, 8) => "u64",
         (Sk::Bool, 1) => "bool",
         _ => unreachable!(),
     }
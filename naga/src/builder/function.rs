@@ -0,0 +1,453 @@
+use super::BuildError;
+use crate::{
+    arena::Handle,
+    front::Typifier,
+    proc::{ConstantEvaluator, Emitter, ExpressionConstnessTracker, ResolveContext},
+    BinaryOperator, Block, Expression, Function, Module, Span, Statement, Type, TypeInner,
+};
+
+/// Builds the arguments, result and body of a function or entry point.
+///
+/// Expressions are appended to the block being built, and emitted there.
+/// Methods that append an expression return its handle, and the type of
+/// the expression is available right away through [`type_of`].
+///
+/// [`type_of`]: FunctionBuilder::type_of
+pub struct FunctionBuilder<'a> {
+    module: &'a mut Module,
+    function: Function,
+    typifier: Typifier,
+    constness: ExpressionConstnessTracker,
+    emitter: Emitter,
+
+    /// The block statements are added to.
+    ///
+    /// The enclosing blocks are kept on the stack of the closures building
+    /// nested blocks.
+    block: Block,
+
+    early_depth_test: Option<crate::EarlyDepthTest>,
+}
+
+impl<'a> FunctionBuilder<'a> {
+    pub(super) fn new(module: &'a mut Module, name: &str) -> Self {
+        let function = Function {
+            name: Some(name.to_string()),
+            ..Function::default()
+        };
+        let mut emitter = Emitter::default();
+        emitter.start(&function.expressions);
+        Self {
+            module,
+            function,
+            typifier: Typifier::new(),
+            constness: ExpressionConstnessTracker::new(),
+            emitter,
+            block: Block::new(),
+            early_depth_test: None,
+        }
+    }
+
+    pub(super) fn finish(mut self) -> (Function, Option<crate::EarlyDepthTest>) {
+        self.block
+            .extend(self.emitter.finish(&self.function.expressions));
+        self.function.body = self.block;
+        (self.function, self.early_depth_test)
+    }
+
+    /// The module the function is added to.
+    pub fn module(&self) -> &Module {
+        self.module
+    }
+
+    /// Add an argument, and return the expression for its value.
+    pub fn argument(
+        &mut self,
+        name: &str,
+        ty: Handle<Type>,
+        binding: Option<crate::Binding>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        let index = self.function.arguments.len() as u32;
+        self.function.arguments.push(crate::FunctionArgument {
+            name: Some(name.to_string()),
+            ty,
+            binding,
+        });
+        self.expr(Expression::FunctionArgument(index))
+    }
+
+    /// Set the function's result type.
+    pub fn result(&mut self, ty: Handle<Type>, binding: Option<crate::Binding>) {
+        self.function.result = Some(crate::FunctionResult { ty, binding });
+    }
+
+    /// Set the early depth test of a fragment entry point.
+    pub fn early_depth_test(&mut self, test: crate::EarlyDepthTest) {
+        self.early_depth_test = Some(test);
+    }
+
+    /// Add a local variable, and return the expression for a pointer to it.
+    ///
+    /// If `init` is a constant expression, it becomes the initializer of the
+    /// variable. Otherwise, it is stored into the variable here.
+    pub fn local(
+        &mut self,
+        name: &str,
+        ty: Handle<Type>,
+        init: Option<Handle<Expression>>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        let const_init = init.filter(|&init| self.constness.is_const(init));
+        let local = self.function.local_variables.append(
+            crate::LocalVariable {
+                name: Some(name.to_string()),
+                ty,
+                init: const_init,
+            },
+            Span::UNDEFINED,
+        );
+        let pointer = self.expr(Expression::LocalVariable(local))?;
+        if let (Some(value), None) = (init, const_init) {
+            self.store(pointer, value);
+        }
+        Ok(pointer)
+    }
+
+    /// Name `expr`, so that back ends store its value in a variable with
+    /// this name.
+    pub fn name(&mut self, expr: Handle<Expression>, name: &str) {
+        self.function
+            .named_expressions
+            .insert(expr, name.to_string());
+    }
+
+    /// The type of `expr`.
+    pub fn type_of(&self, expr: Handle<Expression>) -> &TypeInner {
+        self.typifier.get(expr, &self.module.types)
+    }
+
+    /// The type of `expr`, added to the module's types if needed.
+    pub fn type_handle(&mut self, expr: Handle<Expression>) -> Handle<Type> {
+        self.typifier.register_type(expr, &mut self.module.types)
+    }
+
+    /// Append `expr`, and return its handle.
+    ///
+    /// If all the operands of `expr` are constant, the expression appended
+    /// is its value instead. Statement results, like [`CallResult`], can't
+    /// be appended this way: use the methods adding the statements instead.
+    ///
+    /// [`CallResult`]: Expression::CallResult
+    pub fn expr(&mut self, expr: Expression) -> Result<Handle<Expression>, BuildError> {
+        let mut evaluator = ConstantEvaluator::for_wgsl_function(
+            self.module,
+            &mut self.function.expressions,
+            &mut self.constness,
+            &mut self.emitter,
+            &mut self.block,
+        );
+        let handle = match evaluator.try_eval_and_append(&expr, Span::UNDEFINED) {
+            Ok(handle) => handle,
+            // The expression isn't constant, so it's evaluated at run time.
+            Err(_) if expr.needs_pre_emit() => {
+                self.block
+                    .extend(self.emitter.finish(&self.function.expressions));
+                let handle = self.function.expressions.append(expr, Span::UNDEFINED);
+                self.emitter.start(&self.function.expressions);
+                handle
+            }
+            Err(_) => self.function.expressions.append(expr, Span::UNDEFINED),
+        };
+        self.grow_types(handle)?;
+        Ok(handle)
+    }
+
+    fn grow_types(&mut self, handle: Handle<Expression>) -> Result<(), BuildError> {
+        let resolve_ctx = ResolveContext::with_locals(
+            self.module,
+            &self.function.local_variables,
+            &self.function.arguments,
+        );
+        self.typifier
+            .grow(handle, &self.function.expressions, &resolve_ctx)?;
+        Ok(())
+    }
+
+    pub fn literal(&mut self, literal: crate::Literal) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Literal(literal))
+    }
+
+    /// Return the expression for a pointer to `global`.
+    pub fn global(
+        &mut self,
+        global: Handle<crate::GlobalVariable>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::GlobalVariable(global))
+    }
+
+    pub fn constant(
+        &mut self,
+        constant: Handle<crate::Constant>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Constant(constant))
+    }
+
+    pub fn load(&mut self, pointer: Handle<Expression>) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Load { pointer })
+    }
+
+    pub fn access(
+        &mut self,
+        base: Handle<Expression>,
+        index: Handle<Expression>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Access { base, index })
+    }
+
+    pub fn access_index(
+        &mut self,
+        base: Handle<Expression>,
+        index: u32,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::AccessIndex { base, index })
+    }
+
+    pub fn unary(
+        &mut self,
+        op: crate::UnaryOperator,
+        expr: Handle<Expression>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Unary { op, expr })
+    }
+
+    pub fn binary(
+        &mut self,
+        op: BinaryOperator,
+        left: Handle<Expression>,
+        right: Handle<Expression>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Binary { op, left, right })
+    }
+
+    pub fn compose(
+        &mut self,
+        ty: Handle<Type>,
+        components: Vec<Handle<Expression>>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Compose { ty, components })
+    }
+
+    pub fn splat(
+        &mut self,
+        size: crate::VectorSize,
+        value: Handle<Expression>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Splat { size, value })
+    }
+
+    pub fn swizzle(
+        &mut self,
+        size: crate::VectorSize,
+        vector: Handle<Expression>,
+        pattern: [crate::SwizzleComponent; 4],
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Swizzle {
+            size,
+            vector,
+            pattern,
+        })
+    }
+
+    pub fn select(
+        &mut self,
+        condition: Handle<Expression>,
+        accept: Handle<Expression>,
+        reject: Handle<Expression>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Select {
+            condition,
+            accept,
+            reject,
+        })
+    }
+
+    /// Apply `fun` to `args`, which must have as many handles as it takes
+    /// arguments.
+    pub fn math(
+        &mut self,
+        fun: crate::MathFunction,
+        args: &[Handle<Expression>],
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::Math {
+            fun,
+            arg: args[0],
+            arg1: args.get(1).copied(),
+            arg2: args.get(2).copied(),
+            arg3: args.get(3).copied(),
+        })
+    }
+
+    /// Convert `expr` to `kind`, with the width `convert` if it is
+    /// `Some`, or reinterpret its bits if it is `None`.
+    pub fn cast(
+        &mut self,
+        expr: Handle<Expression>,
+        kind: crate::ScalarKind,
+        convert: Option<crate::Bytes>,
+    ) -> Result<Handle<Expression>, BuildError> {
+        self.expr(Expression::As {
+            expr,
+            kind,
+            convert,
+        })
+    }
+
+    /// Add `statement` to the block being built.
+    ///
+    /// The expressions appended so far are emitted before it.
+    pub fn statement(&mut self, statement: Statement) {
+        self.block
+            .extend(self.emitter.finish(&self.function.expressions));
+        self.block.push(statement, Span::UNDEFINED);
+        self.emitter.start(&self.function.expressions);
+    }
+
+    pub fn store(&mut self, pointer: Handle<Expression>, value: Handle<Expression>) {
+        self.statement(Statement::Store { pointer, value });
+    }
+
+    /// Call `function`, and return the expression for its result, if it
+    /// has one.
+    pub fn call(
+        &mut self,
+        function: Handle<Function>,
+        arguments: Vec<Handle<Expression>>,
+    ) -> Result<Option<Handle<Expression>>, BuildError> {
+        self.block
+            .extend(self.emitter.finish(&self.function.expressions));
+        let result = match self.module.functions[function].result {
+            Some(_) => {
+                let result = self
+                    .function
+                    .expressions
+                    .append(Expression::CallResult(function), Span::UNDEFINED);
+                self.grow_types(result)?;
+                Some(result)
+            }
+            None => None,
+        };
+        self.block.push(
+            Statement::Call {
+                function,
+                arguments,
+                result,
+            },
+            Span::UNDEFINED,
+        );
+        self.emitter.start(&self.function.expressions);
+        Ok(result)
+    }
+
+    pub fn return_(&mut self, value: Option<Handle<Expression>>) {
+        self.statement(Statement::Return { value });
+    }
+
+    pub fn kill(&mut self) {
+        self.statement(Statement::Kill);
+    }
+
+    pub fn break_(&mut self) {
+        self.statement(Statement::Break);
+    }
+
+    pub fn continue_(&mut self) {
+        self.statement(Statement::Continue);
+    }
+
+    pub fn barrier(&mut self, barrier: crate::Barrier) {
+        self.statement(Statement::Barrier(barrier));
+    }
+
+    /// Add an `if` statement, whose branches are built by `accept` and
+    /// `reject`.
+    pub fn if_(
+        &mut self,
+        condition: Handle<Expression>,
+        accept: impl FnOnce(&mut Self) -> Result<(), BuildError>,
+        reject: impl FnOnce(&mut Self) -> Result<(), BuildError>,
+    ) -> Result<(), BuildError> {
+        let accept = self.nested(accept)?;
+        let reject = self.nested(reject)?;
+        self.statement(Statement::If {
+            condition,
+            accept,
+            reject,
+        });
+        Ok(())
+    }
+
+    /// Add a loop, whose body is built by `body`, and whose continuing
+    /// block is built by `continuing`.
+    ///
+    /// If `continuing` returns an expression, the loop ends when it is true
+    /// at the end of an iteration.
+    pub fn loop_(
+        &mut self,
+        body: impl FnOnce(&mut Self) -> Result<(), BuildError>,
+        continuing: impl FnOnce(&mut Self) -> Result<Option<Handle<Expression>>, BuildError>,
+    ) -> Result<(), BuildError> {
+        let body = self.nested(body)?;
+        let mut break_if = None;
+        let continuing = self.nested(|builder| {
+            break_if = continuing(builder)?;
+            Ok(())
+        })?;
+        self.statement(Statement::Loop {
+            body,
+            continuing,
+            break_if,
+        });
+        Ok(())
+    }
+
+    /// Add a `switch` statement, with a case for each of `values`, whose
+    /// body is built by `case`.
+    ///
+    /// Cases don't fall through. One of `values` must be
+    /// [`SwitchValue::Default`](crate::SwitchValue::Default).
+    pub fn switch(
+        &mut self,
+        selector: Handle<Expression>,
+        values: &[crate::SwitchValue],
+        mut case: impl FnMut(&mut Self, crate::SwitchValue) -> Result<(), BuildError>,
+    ) -> Result<(), BuildError> {
+        let cases = values
+            .iter()
+            .map(|&value| {
+                Ok(crate::SwitchCase {
+                    value,
+                    body: self.nested(|builder| case(builder, value))?,
+                    fall_through: false,
+                })
+            })
+            .collect::<Result<_, BuildError>>()?;
+        self.statement(Statement::Switch { selector, cases });
+        Ok(())
+    }
+
+    /// Build a block with `build`, and return it.
+    fn nested(
+        &mut self,
+        build: impl FnOnce(&mut Self) -> Result<(), BuildError>,
+    ) -> Result<Block, BuildError> {
+        self.block
+            .extend(self.emitter.finish(&self.function.expressions));
+        let outer = std::mem::take(&mut self.block);
+        self.emitter.start(&self.function.expressions);
+        let result = build(self);
+        self.block
+            .extend(self.emitter.finish(&self.function.expressions));
+        let inner = std::mem::replace(&mut self.block, outer);
+        self.emitter.start(&self.function.expressions);
+        result.map(|()| inner)
+    }
+}
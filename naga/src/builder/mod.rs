@@ -0,0 +1,243 @@
+/*!
+Building [`Module`]s from Rust code.
+
+Filling a module's arenas by hand means keeping [`Emit`] statements in
+step with the expressions appended, resolving the types of expressions to
+declare variables or compose values, and laying out structs. A
+[`ModuleBuilder`] takes care of this bookkeeping:
+
+- [`ModuleBuilder`] declares types, constants and global variables, and
+  lays out structs.
+
+- [`FunctionBuilder`], used through [`ModuleBuilder::function`] and
+  [`ModuleBuilder::entry_point`], appends expressions and statements to a
+  function's body. Expressions are emitted where they are appended,
+  expressions with constant operands are evaluated as the WGSL front end
+  would, and the type of every expression is available as soon as it is
+  appended. Control flow statements take closures that build their
+  bodies.
+
+```
+use naga::builder::ModuleBuilder;
+use naga::{BinaryOperator, Literal, ScalarKind, ShaderStage, TypeInner};
+
+let mut builder = ModuleBuilder::new();
+let float = builder.ty(TypeInner::Scalar { kind: ScalarKind::Float, width: 4 });
+let square = builder.function("square", |f| {
+    let x = f.argument("x", float, None)?;
+    let result = f.binary(BinaryOperator::Multiply, x, x)?;
+    f.result(float, None);
+    f.return_(Some(result));
+    Ok(())
+})?;
+builder.entry_point("main", ShaderStage::Compute, [1, 1, 1], |f| {
+    let four = f.literal(Literal::F32(4.0))?;
+    f.call(square, vec![four])?;
+    Ok(())
+})?;
+let module = builder.finish();
+# Ok::<(), naga::builder::BuildError>(())
+```
+
+The builder doesn't check that the module is valid: run it through the
+[`Validator`] before using it.
+
+[`Module`]: crate::Module
+[`Emit`]: crate::Statement::Emit
+[`Validator`]: crate::valid::Validator
+*/
+
+mod function;
+
+pub use function::FunctionBuilder;
+
+use crate::{
+    arena::Handle,
+    front::Typifier,
+    proc::{
+        ConstantEvaluator, ConstantEvaluatorError, LayoutError, Layouter, ResolveContext,
+        ResolveError,
+    },
+    Constant, Expression, Function, GlobalVariable, Module, Span, Type, TypeInner,
+};
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BuildError {
+    #[error(transparent)]
+    ConstantEvaluator(#[from] ConstantEvaluatorError),
+    #[error(transparent)]
+    Resolve(#[from] ResolveError),
+    #[error(transparent)]
+    Layout(#[from] LayoutError),
+}
+
+/// Builds a [`Module`], one item at a time.
+///
+/// See the [module-level documentation](self) for an overview.
+#[derive(Debug, Default)]
+pub struct ModuleBuilder {
+    module: Module,
+    layouter: Layouter,
+
+    /// Types of the module's constant expressions.
+    const_typifier: Typifier,
+}
+
+impl ModuleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a builder that adds items to `module`.
+    pub fn from_module(module: Module) -> Self {
+        Self {
+            module,
+            ..Self::default()
+        }
+    }
+
+    /// The module built so far.
+    pub const fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Return the module built.
+    pub fn finish(self) -> Module {
+        self.module
+    }
+
+    /// Add an unnamed type.
+    pub fn ty(&mut self, inner: TypeInner) -> Handle<Type> {
+        self.module
+            .types
+            .insert(Type { name: None, inner }, Span::UNDEFINED)
+    }
+
+    /// Add a named type.
+    pub fn named_ty(&mut self, name: &str, inner: TypeInner) -> Handle<Type> {
+        self.module.types.insert(
+            Type {
+                name: Some(name.to_string()),
+                inner,
+            },
+            Span::UNDEFINED,
+        )
+    }
+
+    /// Add a struct with the given members, laid out following WGSL's
+    /// rules.
+    pub fn struct_ty(
+        &mut self,
+        name: &str,
+        members: &[(&str, Handle<Type>)],
+    ) -> Result<Handle<Type>, BuildError> {
+        self.layouter.update(self.module.to_ctx())?;
+        let mut offset = 0;
+        let mut alignment = crate::proc::Alignment::ONE;
+        let members = members
+            .iter()
+            .map(|&(name, ty)| {
+                let layout = self.layouter[ty];
+                let member_offset = layout.alignment.round_up(offset);
+                offset = member_offset + layout.size;
+                alignment = alignment.max(layout.alignment);
+                crate::StructMember {
+                    name: Some(name.to_string()),
+                    ty,
+                    binding: None,
+                    offset: member_offset,
+                }
+            })
+            .collect();
+        let span = alignment.round_up(offset);
+        Ok(self.named_ty(name, TypeInner::Struct { members, span }))
+    }
+
+    /// Add an array of `base`, with the stride WGSL gives it.
+    pub fn array_ty(
+        &mut self,
+        base: Handle<Type>,
+        size: crate::ArraySize,
+    ) -> Result<Handle<Type>, BuildError> {
+        self.layouter.update(self.module.to_ctx())?;
+        let stride = self.layouter[base].to_stride();
+        Ok(self.ty(TypeInner::Array { base, size, stride }))
+    }
+
+    /// Append `expr` to the module's constant expressions, evaluating it.
+    ///
+    /// The operands of `expr` must be constant expressions too.
+    pub fn const_expr(&mut self, expr: Expression) -> Result<Handle<Expression>, BuildError> {
+        let handle = ConstantEvaluator::for_wgsl_module(&mut self.module)
+            .try_eval_and_append(&expr, Span::UNDEFINED)?;
+        let empty_arena = crate::Arena::new();
+        let resolve_ctx = ResolveContext::with_locals(&self.module, &empty_arena, &[]);
+        self.const_typifier
+            .grow(handle, &self.module.const_expressions, &resolve_ctx)?;
+        Ok(handle)
+    }
+
+    /// Add a constant named `name`, whose value is the constant expression
+    /// `init`.
+    pub fn constant(&mut self, name: &str, init: Handle<Expression>) -> Handle<Constant> {
+        let ty = self
+            .const_typifier
+            .register_type(init, &mut self.module.types);
+        self.module.constants.append(
+            Constant {
+                name: Some(name.to_string()),
+                r#override: crate::Override::None,
+                ty,
+                init,
+            },
+            Span::UNDEFINED,
+        )
+    }
+
+    pub fn global(&mut self, global: GlobalVariable) -> Handle<GlobalVariable> {
+        self.module.global_variables.append(global, Span::UNDEFINED)
+    }
+
+    /// Add a function named `name`, whose arguments, result and body are
+    /// built by `build`.
+    ///
+    /// A function can only call the functions added before it.
+    pub fn function(
+        &mut self,
+        name: &str,
+        build: impl FnOnce(&mut FunctionBuilder) -> Result<(), BuildError>,
+    ) -> Result<Handle<Function>, BuildError> {
+        let mut builder = FunctionBuilder::new(&mut self.module, name);
+        build(&mut builder)?;
+        let (function, _) = builder.finish();
+        Ok(self.module.functions.append(function, Span::UNDEFINED))
+    }
+
+    /// Add an entry point named `name` for `stage`, whose arguments, result
+    /// and body are built by `build`.
+    ///
+    /// The `workgroup_size` is ignored unless `stage` is
+    /// [`ShaderStage::Compute`](crate::ShaderStage::Compute).
+    pub fn entry_point(
+        &mut self,
+        name: &str,
+        stage: crate::ShaderStage,
+        workgroup_size: [u32; 3],
+        build: impl FnOnce(&mut FunctionBuilder) -> Result<(), BuildError>,
+    ) -> Result<(), BuildError> {
+        let mut builder = FunctionBuilder::new(&mut self.module, name);
+        build(&mut builder)?;
+        let (function, early_depth_test) = builder.finish();
+        self.module.entry_points.push(crate::EntryPoint {
+            name: name.to_string(),
+            stage,
+            early_depth_test,
+            workgroup_size: match stage {
+                crate::ShaderStage::Compute => workgroup_size,
+                _ => [0; 3],
+            },
+            function,
+        });
+        Ok(())
+    }
+}
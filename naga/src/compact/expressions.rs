@@ -14,6 +14,12 @@ pub struct ExpressionTracer<'tracer> {
     /// The used map for `constants`.
     pub constants_used: &'tracer mut HandleSet<crate::Constant>,
 
+    /// The used map for the module's global variables.
+    ///
+    /// Global variables' types and initializers are traced separately,
+    /// once all functions have been traced.
+    pub global_variables_used: &'tracer mut HandleSet<crate::GlobalVariable>,
+
     /// The used set for `arena`.
     ///
     /// This points to whatever arena holds the expressions we are
@@ -52,11 +58,13 @@ impl<'tracer> ExpressionTracer<'tracer> {
                 // Expressions that do not contain handles that need to be traced.
                 Ex::Literal(_)
                 | Ex::FunctionArgument(_)
-                | Ex::GlobalVariable(_)
                 | Ex::LocalVariable(_)
                 | Ex::CallResult(_)
                 | Ex::RayQueryProceedResult => {}
 
+                Ex::GlobalVariable(handle) => {
+                    self.global_variables_used.insert(handle);
+                }
                 Ex::Constant(handle) => {
                     self.constants_used.insert(handle);
                     let constant = &self.constants[handle];
@@ -181,6 +189,7 @@ impl<'tracer> ExpressionTracer<'tracer> {
                 constants: self.constants,
                 types_used: self.types_used,
                 constants_used: self.constants_used,
+                global_variables_used: self.global_variables_used,
                 const_expressions: None,
             },
             None => ExpressionTracer {
@@ -190,6 +199,7 @@ impl<'tracer> ExpressionTracer<'tracer> {
                 types_used: self.types_used,
                 constants_used: self.constants_used,
                 expressions_used: self.expressions_used,
+                global_variables_used: self.global_variables_used,
                 const_expressions: None,
             },
         }
@@ -219,12 +229,12 @@ impl ModuleMap {
             // Expressions that do not contain handles that need to be adjusted.
             Ex::Literal(_)
             | Ex::FunctionArgument(_)
-            | Ex::GlobalVariable(_)
             | Ex::LocalVariable(_)
-            | Ex::CallResult(_)
             | Ex::RayQueryProceedResult => {}
 
             // Expressions that contain handles that need to be adjusted.
+            Ex::GlobalVariable(ref mut global) => self.global_variables.adjust(global),
+            Ex::CallResult(ref mut function) => self.functions.adjust(function),
             Ex::Constant(ref mut constant) => self.constants.adjust(constant),
             Ex::ZeroValue(ref mut ty) => self.types.adjust(ty),
             Ex::Compose {
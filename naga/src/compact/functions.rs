@@ -9,6 +9,8 @@ pub struct FunctionTracer<'a> {
     pub types_used: &'a mut HandleSet<crate::Type>,
     pub constants_used: &'a mut HandleSet<crate::Constant>,
     pub const_expressions_used: &'a mut HandleSet<crate::Expression>,
+    pub global_variables_used: &'a mut HandleSet<crate::GlobalVariable>,
+    pub functions_used: &'a mut HandleSet<crate::Function>,
 
     /// Function-local expressions used.
     pub expressions_used: HandleSet<crate::Expression>,
@@ -64,6 +66,7 @@ impl<'a> FunctionTracer<'a> {
             types_used: self.types_used,
             constants_used: self.constants_used,
             expressions_used: &mut self.expressions_used,
+            global_variables_used: self.global_variables_used,
             const_expressions: Some((
                 &self.module.const_expressions,
                 &mut self.const_expressions_used,
@@ -116,6 +119,6 @@ impl FunctionMap {
         assert!(reuse.is_empty());
 
         // Adjust statements.
-        self.adjust_body(function);
+        self.adjust_body(function, module_map);
     }
 }
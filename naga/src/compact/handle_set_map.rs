@@ -34,6 +34,11 @@ impl<T> HandleSet<T> {
         // `Index`, but rather a zero-based `usize`.
         self.members.insert(handle.index())
     }
+
+    /// Return `true` if `handle` is in the set.
+    pub fn contains(&self, handle: Handle<T>) -> bool {
+        self.members.contains(handle.index())
+    }
 }
 
 pub trait ArenaType<T> {
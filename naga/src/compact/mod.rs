@@ -29,52 +29,93 @@ use handle_set_map::{HandleMap, HandleSet};
 ///
 /// If `module` has not passed validation, this may panic.
 pub fn compact(module: &mut crate::Module) {
+    compact_module(module, Roots::Everything);
+}
+
+/// Reduce `module` to the entry point named `name` for `stage`, and
+/// everything it uses.
+///
+/// Remove all other entry points, and then the functions, global
+/// variables, constants, types, and expressions that the remaining
+/// entry point doesn't use, directly or through the functions it calls,
+/// adjusting all handles as necessary. Only the special types are kept
+/// regardless.
+///
+/// Back ends write out everything in the module they are given, so
+/// translating a module with several entry points for a single
+/// pipeline stage produces code for globals and functions the stage
+/// never uses. Extracting the entry point first makes the output
+/// smaller, and independent of the other entry points.
+///
+/// Return `false`, leaving `module` unchanged, if it has no such entry
+/// point.
+///
+/// # Panics
+///
+/// If `module` has not passed validation, this may panic.
+pub fn extract_entry_point(
+    module: &mut crate::Module,
+    stage: crate::ShaderStage,
+    name: &str,
+) -> bool {
+    let Some(index) = module
+        .entry_points
+        .iter()
+        .position(|ep| ep.stage == stage && ep.name == name)
+    else {
+        return false;
+    };
+    let entry_point = module.entry_points.swap_remove(index);
+    module.entry_points.clear();
+    module.entry_points.push(entry_point);
+    compact_module(module, Roots::EntryPoints);
+    true
+}
+
+/// The items of a module that [`compact_module`] treats as used.
+#[derive(Clone, Copy, PartialEq)]
+enum Roots {
+    /// All global variables, named constants, special types, functions
+    /// and entry points.
+    Everything,
+
+    /// The entry points and special types only.
+    EntryPoints,
+}
+
+fn compact_module(module: &mut crate::Module, roots: Roots) {
     let mut module_tracer = ModuleTracer::new(module);
 
-    // We treat all globals as used by definition.
-    log::trace!("tracing global variables");
-    {
-        for (_, global) in module.global_variables.iter() {
-            log::trace!("tracing global {:?}", global.name);
-            module_tracer.as_type().trace_type(global.ty);
-            if let Some(init) = global.init {
-                module_tracer.as_const_expression().trace_expression(init);
-            }
+    if roots == Roots::Everything {
+        // We treat all globals as used by definition. Their types and
+        // initializers are traced below, along with the globals used by
+        // functions.
+        for (handle, _) in module.global_variables.iter() {
+            module_tracer.global_variables_used.insert(handle);
         }
-    }
 
-    // We treat all special types as used by definition.
-    module_tracer.trace_special_types(&module.special_types);
+        // We treat all named constants as used by definition.
+        for (handle, constant) in module.constants.iter() {
+            if constant.name.is_some() {
+                module_tracer.constants_used.insert(handle);
+                module_tracer.as_type().trace_type(constant.ty);
+                module_tracer
+                    .as_const_expression()
+                    .trace_expression(constant.init);
+            }
+        }
 
-    // We treat all named constants as used by definition.
-    for (handle, constant) in module.constants.iter() {
-        if constant.name.is_some() {
-            module_tracer.constants_used.insert(handle);
-            module_tracer.as_type().trace_type(constant.ty);
-            module_tracer
-                .as_const_expression()
-                .trace_expression(constant.init);
+        // We assume that all functions are used.
+        for (handle, _) in module.functions.iter() {
+            module_tracer.functions_used.insert(handle);
         }
     }
 
-    // We assume that all functions are used.
-    //
-    // Observe which types, constant expressions, constants, and
-    // expressions each function uses, and produce maps from
-    // pre-compaction to post-compaction handles.
-    log::trace!("tracing functions");
-    let function_maps: Vec<FunctionMap> = module
-        .functions
-        .iter()
-        .map(|(_, f)| {
-            log::trace!("tracing function {:?}", f.name);
-            let mut function_tracer = module_tracer.enter_function(f);
-            function_tracer.trace();
-            FunctionMap::from(function_tracer)
-        })
-        .collect();
+    // We treat all special types as used by definition.
+    module_tracer.trace_special_types(&module.special_types);
 
-    // Similiarly, observe what each entry point actually uses.
+    // Observe what each entry point actually uses, including which
+    // functions it calls.
     log::trace!("tracing entry points");
     let entry_point_maps: Vec<FunctionMap> = module
         .entry_points
@@ -87,6 +128,43 @@ pub fn compact(module: &mut crate::Module) {
         })
         .collect();
 
+    // Similarly, observe which types, constant expressions, constants,
+    // expressions, and functions each used function uses, and produce
+    // maps from pre-compaction to post-compaction handles.
+    //
+    // Functions only call functions that precede them in the arena, so
+    // visiting them in reverse order reaches every caller of a function
+    // before the function itself.
+    log::trace!("tracing functions");
+    let mut function_maps: Vec<Option<FunctionMap>> = module
+        .functions
+        .iter()
+        .rev()
+        .map(|(handle, f)| {
+            if !module_tracer.functions_used.contains(handle) {
+                return None;
+            }
+            log::trace!("tracing function {:?}", f.name);
+            let mut function_tracer = module_tracer.enter_function(f);
+            function_tracer.trace();
+            Some(FunctionMap::from(function_tracer))
+        })
+        .collect();
+    function_maps.reverse();
+
+    // Trace the types and initializers of the globals used.
+    log::trace!("tracing global variables");
+    for (handle, global) in module.global_variables.iter() {
+        if !module_tracer.global_variables_used.contains(handle) {
+            continue;
+        }
+        log::trace!("tracing global {:?}", global.name);
+        module_tracer.as_type().trace_type(global.ty);
+        if let Some(init) = global.init {
+            module_tracer.as_const_expression().trace_expression(init);
+        }
+    }
+
     // Now that we know what is used and what is never touched,
     // produce maps from the `Handle`s that appear in `module` now to
     // the corresponding `Handle`s that will refer to the same items
@@ -134,25 +212,37 @@ pub fn compact(module: &mut crate::Module) {
         }
     });
 
-    // Adjust global variables' types and initializers.
+    // Drop unused global variables in place, and adjust the types and
+    // initializers of the rest.
     log::trace!("adjusting global variables");
-    for (_, global) in module.global_variables.iter_mut() {
-        log::trace!("adjusting global {:?}", global.name);
-        module_map.types.adjust(&mut global.ty);
-        if let Some(ref mut init) = global.init {
-            module_map.const_expressions.adjust(init);
+    module.global_variables.retain_mut(|handle, global| {
+        if module_map.global_variables.used(handle) {
+            log::trace!("adjusting global {:?}", global.name);
+            module_map.types.adjust(&mut global.ty);
+            if let Some(ref mut init) = global.init {
+                module_map.const_expressions.adjust(init);
+            }
+            true
+        } else {
+            false
         }
-    }
+    });
 
     // Temporary storage to help us reuse allocations of existing
     // named expression tables.
     let mut reused_named_expressions = crate::NamedExpressions::default();
 
-    // Compact each function.
-    for ((_, function), map) in module.functions.iter_mut().zip(function_maps.iter()) {
-        log::trace!("compacting function {:?}", function.name);
-        map.compact(function, &module_map, &mut reused_named_expressions);
-    }
+    // Drop unused functions in place, and compact the rest.
+    let mut function_maps = function_maps.into_iter();
+    module.functions.retain_mut(|_, function| {
+        if let Some(map) = function_maps.next().unwrap() {
+            log::trace!("compacting function {:?}", function.name);
+            map.compact(function, &module_map, &mut reused_named_expressions);
+            true
+        } else {
+            false
+        }
+    });
 
     // Compact each entry point.
     for (entry, map) in module.entry_points.iter_mut().zip(entry_point_maps.iter()) {
@@ -170,6 +260,8 @@ struct ModuleTracer<'module> {
     types_used: HandleSet<crate::Type>,
     constants_used: HandleSet<crate::Constant>,
     const_expressions_used: HandleSet<crate::Expression>,
+    global_variables_used: HandleSet<crate::GlobalVariable>,
+    functions_used: HandleSet<crate::Function>,
 }
 
 impl<'module> ModuleTracer<'module> {
@@ -179,6 +271,8 @@ impl<'module> ModuleTracer<'module> {
             types_used: HandleSet::for_arena(&module.types),
             constants_used: HandleSet::for_arena(&module.constants),
             const_expressions_used: HandleSet::for_arena(&module.const_expressions),
+            global_variables_used: HandleSet::for_arena(&module.global_variables),
+            functions_used: HandleSet::for_arena(&module.functions),
         }
     }
 
@@ -216,6 +310,7 @@ impl<'module> ModuleTracer<'module> {
             types_used: &mut self.types_used,
             constants_used: &mut self.constants_used,
             expressions_used: &mut self.const_expressions_used,
+            global_variables_used: &mut self.global_variables_used,
             const_expressions: None,
         }
     }
@@ -231,6 +326,8 @@ impl<'module> ModuleTracer<'module> {
             types_used: &mut self.types_used,
             constants_used: &mut self.constants_used,
             const_expressions_used: &mut self.const_expressions_used,
+            global_variables_used: &mut self.global_variables_used,
+            functions_used: &mut self.functions_used,
             expressions_used: HandleSet::for_arena(&function.expressions),
         }
     }
@@ -240,6 +337,8 @@ struct ModuleMap {
     types: HandleMap<crate::Type>,
     constants: HandleMap<crate::Constant>,
     const_expressions: HandleMap<crate::Expression>,
+    global_variables: HandleMap<crate::GlobalVariable>,
+    functions: HandleMap<crate::Function>,
 }
 
 impl From<ModuleTracer<'_>> for ModuleMap {
@@ -248,6 +347,8 @@ impl From<ModuleTracer<'_>> for ModuleMap {
             types: HandleMap::from_set(used.types_used),
             constants: HandleMap::from_set(used.constants_used),
             const_expressions: HandleMap::from_set(used.const_expressions_used),
+            global_variables: HandleMap::from_set(used.global_variables_used),
+            functions: HandleMap::from_set(used.functions_used),
         }
     }
 }
@@ -1,5 +1,5 @@
 use super::functions::FunctionTracer;
-use super::FunctionMap;
+use super::{FunctionMap, ModuleMap};
 use crate::arena::Handle;
 
 impl FunctionTracer<'_> {
@@ -80,10 +80,11 @@ impl FunctionTracer<'_> {
                         self.trace_expression(result);
                     }
                     St::Call {
-                        function: _,
+                        function,
                         ref arguments,
                         result,
                     } => {
+                        self.functions_used.insert(function);
                         for expr in arguments {
                             self.trace_expression(*expr);
                         }
@@ -141,7 +142,7 @@ impl FunctionTracer<'_> {
 }
 
 impl FunctionMap {
-    pub fn adjust_body(&self, function: &mut crate::Function) {
+    pub fn adjust_body(&self, function: &mut crate::Function, module_map: &ModuleMap) {
         let block = &mut function.body;
         let mut worklist: Vec<&mut [crate::Statement]> = vec![block];
         let adjust = |handle: &mut Handle<crate::Expression>| {
@@ -226,10 +227,11 @@ impl FunctionMap {
                         adjust(result);
                     }
                     St::Call {
-                        function: _,
+                        ref mut function,
                         ref mut arguments,
                         ref mut result,
                     } => {
+                        module_map.functions.adjust(function);
                         for expr in arguments {
                             adjust(expr);
                         }
@@ -0,0 +1,631 @@
+/*!
+Replacing external textures with the planes and parameters holding them.
+
+An external texture, `texture_external` in WGSL, holds frames produced
+outside the GPU pipeline, like those of a video decoder. Depending on its
+format, a frame is made of one RGBA plane, or of a luma plane and one or
+two chroma planes, as in NV12 and I420. Only the WGSL back end can write
+[`ImageClass::External`] images: [`lower`] replaces them with resources
+the other back ends can translate.
+
+Each external texture global becomes three `texture_2d<f32>` globals, one
+per plane, and a uniform buffer of parameters:
+
+```wgsl
+struct NagaExternalTextureParams {
+    yuv_conversion_matrix: mat4x4<f32>,
+    num_planes: u32,
+}
+```
+
+- The texture keeps its global variable, which becomes plane 0, bound as
+  before. Planes 1 and 2, and the parameters, are new global variables,
+  bound as given by the [`BindingMap`].
+
+- `num_planes` is 1, 2 or 3. When it is 1, plane 0 holds RGBA values.
+  Otherwise, the first component of plane 0 holds luma, and chroma is in
+  the first two components of plane 1, or in the first component of
+  planes 1 and 2. `yuv_conversion_matrix` maps `vec4(y, u, v, 1.0)` to
+  `vec4(r, g, b, 1.0)`. Planes that the texture doesn't have must still be
+  bound, to any texture.
+
+- Sampling and loading the texture become calls to helper functions,
+  added at the start of the function arena, that read all three planes
+  and convert the result. Samples are clamped to half a texel away from
+  the edges of each plane, and loads scale the coordinates to the size of
+  each plane. Querying the size of the texture returns the size of plane
+  0.
+
+External textures can't be passed to functions, or be put in binding
+arrays.
+
+[`ImageClass::External`]: crate::ImageClass::External
+*/
+
+use crate::{
+    arena::Handle,
+    builder::{BuildError, FunctionBuilder, ModuleBuilder},
+    front::Typifier,
+    proc::{ResolveContext, ResolveError},
+    BinaryOperator, Block, Expression, FastHashMap, Function, GlobalVariable, ImageClass, Literal,
+    Module, ResourceBinding, ScalarKind, Span, Statement, Type, TypeInner, VectorSize,
+};
+
+/// The bindings of the planes and parameters of an external texture.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct ExternalTextureBindings {
+    /// The bindings of the planes. Plane 0 must be bound where the
+    /// external texture was.
+    pub planes: [ResourceBinding; 3],
+    /// The binding of the uniform buffer holding the parameters.
+    pub params: ResourceBinding,
+}
+
+impl ExternalTextureBindings {
+    /// Bind the planes and parameters of the texture bound to `binding`
+    /// to `binding` and the three slots following it, in the same group.
+    ///
+    /// This is how `wgpu` binds external textures.
+    pub fn consecutive(binding: &ResourceBinding) -> Self {
+        let at = |offset| ResourceBinding {
+            group: binding.group,
+            binding: binding.binding + offset,
+        };
+        Self {
+            planes: [at(0), at(1), at(2)],
+            params: at(3),
+        }
+    }
+}
+
+/// The bindings of the planes and parameters of each external texture,
+/// indexed by the binding of the texture.
+pub type BindingMap = std::collections::BTreeMap<ResourceBinding, ExternalTextureBindings>;
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum LowerError {
+    #[error("External texture {0:?} is not bound")]
+    Unbound(Option<String>),
+    #[error("The bindings of the external texture at {0:?} are missing from the binding map")]
+    MissingBindings(ResourceBinding),
+    #[error("Plane 0 of the external texture at {0:?} must keep its binding")]
+    MovedPlane(ResourceBinding),
+    #[error("Function {0:?} takes an external texture argument")]
+    ExternalTextureArgument(Option<String>),
+    #[error("Binding arrays of external textures are not supported")]
+    BindingArray,
+    #[error(transparent)]
+    Resolve(#[from] ResolveError),
+    #[error(transparent)]
+    Build(#[from] BuildError),
+}
+
+/// Replace the external textures of `module` with their planes and
+/// parameters, bound as given by `bindings`.
+///
+/// See the [module-level documentation](self) for the resources and
+/// helpers that replace them. The module is compacted afterwards, to drop
+/// the [`External`] image type, so its unused types and expressions are
+/// removed too. Modules without external textures are left unchanged.
+///
+/// # Panics
+///
+/// If `module` has not passed validation, this may panic.
+///
+/// [`External`]: crate::ImageClass::External
+pub fn lower(module: &mut Module, bindings: &BindingMap) -> Result<(), LowerError> {
+    let externals = find_external_textures(module, bindings)?;
+    if externals.is_empty() {
+        return Ok(());
+    }
+
+    let function_accesses = module
+        .functions
+        .iter()
+        .map(|(_, function)| find_accesses(module, function, &externals))
+        .collect::<Result<Vec<_>, _>>()?;
+    let entry_point_accesses = module
+        .entry_points
+        .iter()
+        .map(|ep| find_accesses(module, &ep.function, &externals))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let plane_ty = module.types.insert(
+        Type {
+            name: None,
+            inner: TypeInner::Image {
+                dim: crate::ImageDimension::D2,
+                arrayed: false,
+                class: ImageClass::Sampled {
+                    kind: ScalarKind::Float,
+                    multi: false,
+                },
+            },
+        },
+        Span::UNDEFINED,
+    );
+    for &(global, _) in externals.iter() {
+        module.global_variables.get_mut(global).ty = plane_ty;
+    }
+
+    // Build the helpers in an empty function arena, so that they come
+    // before the functions calling them.
+    let functions = std::mem::take(&mut module.functions);
+    let mut builder = ModuleBuilder::from_module(std::mem::take(module));
+    let types = HelperTypes::new(&mut builder, plane_ty)?;
+    let mut helpers = FastHashMap::default();
+    for &(global, ref bindings) in externals.iter() {
+        let name = builder.module().global_variables[global]
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("external_texture_{}", global.index()));
+        let planes = Planes::declare(&mut builder, &types, global, &name, bindings);
+        let used = function_accesses
+            .iter()
+            .chain(entry_point_accesses.iter())
+            .flatten()
+            .filter(|&&(_, g, _)| g == global);
+        for &(_, _, access) in used {
+            if helpers.contains_key(&(global, access)) {
+                continue;
+            }
+            let helper = match access {
+                Access::Sample => {
+                    builder.function(&format!("{name}_sample"), |f| planes.sample(f, &types))?
+                }
+                Access::Load(kind) => {
+                    builder.function(&format!("{name}_load"), |f| planes.load(f, &types, kind))?
+                }
+            };
+            helpers.insert((global, access), helper);
+        }
+    }
+    *module = builder.finish();
+
+    let spans = functions
+        .iter()
+        .map(|(handle, _)| functions.get_span(handle))
+        .collect::<Vec<_>>();
+    let moved = functions
+        .into_inner()
+        .into_iter()
+        .zip(spans)
+        .map(|(function, span)| module.functions.append(function, span))
+        .collect::<Vec<_>>();
+    for (&handle, accesses) in moved.iter().zip(function_accesses.iter()) {
+        let function = module.functions.get_mut(handle);
+        lower_function(function, accesses, &helpers, &moved);
+    }
+    for (ep, accesses) in module
+        .entry_points
+        .iter_mut()
+        .zip(entry_point_accesses.iter())
+    {
+        lower_function(&mut ep.function, accesses, &helpers, &moved);
+    }
+
+    crate::compact::compact(module);
+    Ok(())
+}
+
+/// The ways of accessing an external texture, each handled by a helper
+/// function.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Access {
+    Sample,
+    /// Loading, with coordinates of this kind.
+    Load(ScalarKind),
+}
+
+/// The external texture globals of a module, with their bindings.
+type Externals = Vec<(Handle<GlobalVariable>, ExternalTextureBindings)>;
+
+/// The expressions of a function accessing an external texture.
+type Accesses = Vec<(Handle<Expression>, Handle<GlobalVariable>, Access)>;
+
+/// Find the external texture globals of `module`, and check that they
+/// aren't used in ways [`lower`] can't handle.
+fn find_external_textures(module: &Module, bindings: &BindingMap) -> Result<Externals, LowerError> {
+    let is_external = |ty: Handle<Type>| {
+        matches!(
+            module.types[ty].inner,
+            TypeInner::Image {
+                class: ImageClass::External,
+                ..
+            }
+        )
+    };
+
+    for (_, ty) in module.types.iter() {
+        if let TypeInner::BindingArray { base, .. } = ty.inner {
+            if is_external(base) {
+                return Err(LowerError::BindingArray);
+            }
+        }
+    }
+    let functions = module
+        .functions
+        .iter()
+        .map(|(_, function)| function)
+        .chain(module.entry_points.iter().map(|ep| &ep.function));
+    for function in functions {
+        if function.arguments.iter().any(|arg| is_external(arg.ty)) {
+            return Err(LowerError::ExternalTextureArgument(function.name.clone()));
+        }
+    }
+
+    let mut externals = Vec::new();
+    for (handle, global) in module.global_variables.iter() {
+        if !is_external(global.ty) {
+            continue;
+        }
+        let binding = global
+            .binding
+            .as_ref()
+            .ok_or_else(|| LowerError::Unbound(global.name.clone()))?;
+        let planes = bindings
+            .get(binding)
+            .ok_or_else(|| LowerError::MissingBindings(binding.clone()))?;
+        if planes.planes[0] != *binding {
+            return Err(LowerError::MovedPlane(binding.clone()));
+        }
+        externals.push((handle, planes.clone()));
+    }
+    Ok(externals)
+}
+
+/// Find the expressions of `function` sampling or loading one of
+/// `externals`.
+fn find_accesses(
+    module: &Module,
+    function: &Function,
+    externals: &Externals,
+) -> Result<Accesses, LowerError> {
+    let mut typifier = Typifier::new();
+    let resolve_ctx =
+        ResolveContext::with_locals(module, &function.local_variables, &function.arguments);
+    let mut accesses = Vec::new();
+    for (handle, expr) in function.expressions.iter() {
+        let (image, access) = match *expr {
+            Expression::ImageSample { image, .. } => (image, Access::Sample),
+            Expression::ImageLoad {
+                image, coordinate, ..
+            } => {
+                typifier.grow(coordinate, &function.expressions, &resolve_ctx)?;
+                let kind = typifier
+                    .get(coordinate, &module.types)
+                    .scalar_kind()
+                    .unwrap_or(ScalarKind::Sint);
+                (image, Access::Load(kind))
+            }
+            _ => continue,
+        };
+        if let Expression::GlobalVariable(global) = function.expressions[image] {
+            if externals.iter().any(|&(g, _)| g == global) {
+                accesses.push((handle, global, access));
+            }
+        }
+    }
+    Ok(accesses)
+}
+
+/// The types used by the helper functions.
+struct HelperTypes {
+    sampler: Handle<Type>,
+    vec2f: Handle<Type>,
+    vec2i: Handle<Type>,
+    vec2u: Handle<Type>,
+    vec4f: Handle<Type>,
+    plane: Handle<Type>,
+    params: Handle<Type>,
+}
+
+impl HelperTypes {
+    fn new(builder: &mut ModuleBuilder, plane: Handle<Type>) -> Result<Self, BuildError> {
+        let mut vector = |size, kind| {
+            builder.ty(TypeInner::Vector {
+                size,
+                kind,
+                width: 4,
+            })
+        };
+        let vec2f = vector(VectorSize::Bi, ScalarKind::Float);
+        let vec2i = vector(VectorSize::Bi, ScalarKind::Sint);
+        let vec2u = vector(VectorSize::Bi, ScalarKind::Uint);
+        let vec4f = vector(VectorSize::Quad, ScalarKind::Float);
+        let mat4x4f = builder.ty(TypeInner::Matrix {
+            columns: VectorSize::Quad,
+            rows: VectorSize::Quad,
+            width: 4,
+        });
+        let u32 = builder.ty(TypeInner::Scalar {
+            kind: ScalarKind::Uint,
+            width: 4,
+        });
+        let params = builder.struct_ty(
+            "NagaExternalTextureParams",
+            &[("yuv_conversion_matrix", mat4x4f), ("num_planes", u32)],
+        )?;
+        Ok(Self {
+            sampler: builder.ty(TypeInner::Sampler { comparison: false }),
+            vec2f,
+            vec2i,
+            vec2u,
+            vec4f,
+            plane,
+            params,
+        })
+    }
+}
+
+/// The globals replacing an external texture.
+struct Planes {
+    planes: [Handle<GlobalVariable>; 3],
+    params: Handle<GlobalVariable>,
+}
+
+impl Planes {
+    /// Declare the globals for planes 1 and 2 and the parameters of the
+    /// external texture `global`, which becomes plane 0.
+    fn declare(
+        builder: &mut ModuleBuilder,
+        types: &HelperTypes,
+        global: Handle<GlobalVariable>,
+        name: &str,
+        bindings: &ExternalTextureBindings,
+    ) -> Self {
+        let mut plane = |index: usize| {
+            builder.global(GlobalVariable {
+                name: Some(format!("{name}_plane{index}")),
+                space: crate::AddressSpace::Handle,
+                binding: Some(bindings.planes[index].clone()),
+                ty: types.plane,
+                init: None,
+            })
+        };
+        let planes = [global, plane(1), plane(2)];
+        let params = builder.global(GlobalVariable {
+            name: Some(format!("{name}_params")),
+            space: crate::AddressSpace::Uniform,
+            binding: Some(bindings.params.clone()),
+            ty: types.params,
+            init: None,
+        });
+        Self { planes, params }
+    }
+
+    /// Build a helper sampling the planes at level 0, taking the sampler
+    /// and coordinates as arguments.
+    fn sample(&self, f: &mut FunctionBuilder, types: &HelperTypes) -> Result<(), BuildError> {
+        let sampler = f.argument("sampler", types.sampler, None)?;
+        let coords = f.argument("coords", types.vec2f, None)?;
+        let half = f.literal(Literal::F32(0.5))?;
+        let half = f.splat(VectorSize::Bi, half)?;
+        let one = f.literal(Literal::F32(1.0))?;
+        let one = f.splat(VectorSize::Bi, one)?;
+
+        let mut texels = Vec::with_capacity(self.planes.len());
+        for &plane in self.planes.iter() {
+            let image = f.global(plane)?;
+            let size = image_size(f, image)?;
+            let min = f.binary(BinaryOperator::Divide, half, size)?;
+            let max = f.binary(BinaryOperator::Subtract, one, min)?;
+            let coordinate = f.math(crate::MathFunction::Clamp, &[coords, min, max])?;
+            texels.push(f.expr(Expression::ImageSample {
+                image,
+                sampler,
+                gather: None,
+                coordinate,
+                array_index: None,
+                offset: None,
+                level: crate::SampleLevel::Zero,
+                depth_ref: None,
+            })?);
+        }
+
+        let result = self.convert(f, &texels, types)?;
+        f.result(types.vec4f, None);
+        f.return_(Some(result));
+        Ok(())
+    }
+
+    /// Build a helper loading texels from level 0 of the planes, taking
+    /// coordinates of `kind` in plane 0 as argument.
+    fn load(
+        &self,
+        f: &mut FunctionBuilder,
+        types: &HelperTypes,
+        kind: ScalarKind,
+    ) -> Result<(), BuildError> {
+        let coords_ty = match kind {
+            ScalarKind::Uint => types.vec2u,
+            _ => types.vec2i,
+        };
+        let coords = f.argument("coords", coords_ty, None)?;
+        let level = f.literal(Literal::I32(0))?;
+        let float_coords = f.cast(coords, ScalarKind::Float, Some(4))?;
+
+        let mut texels = Vec::with_capacity(self.planes.len());
+        let mut size_0 = None;
+        for &plane in self.planes.iter() {
+            let image = f.global(plane)?;
+            let size = image_size(f, image)?;
+            let coordinate = match size_0 {
+                None => {
+                    size_0 = Some(size);
+                    coords
+                }
+                Some(size_0) => {
+                    let scaled = f.binary(BinaryOperator::Multiply, float_coords, size)?;
+                    let scaled = f.binary(BinaryOperator::Divide, scaled, size_0)?;
+                    f.cast(scaled, kind, Some(4))?
+                }
+            };
+            texels.push(f.expr(Expression::ImageLoad {
+                image,
+                coordinate,
+                array_index: None,
+                sample: None,
+                level: Some(level),
+            })?);
+        }
+
+        let result = self.convert(f, &texels, types)?;
+        f.result(types.vec4f, None);
+        f.return_(Some(result));
+        Ok(())
+    }
+
+    /// Combine the `texels` read from each plane into an RGBA value.
+    fn convert(
+        &self,
+        f: &mut FunctionBuilder,
+        texels: &[Handle<Expression>],
+        types: &HelperTypes,
+    ) -> Result<Handle<Expression>, BuildError> {
+        let params = f.global(self.params)?;
+        let matrix = f.access_index(params, 0)?;
+        let matrix = f.load(matrix)?;
+        let num_planes = f.access_index(params, 1)?;
+        let num_planes = f.load(num_planes)?;
+
+        let y = f.access_index(texels[0], 0)?;
+        let u = f.access_index(texels[1], 0)?;
+        let interleaved_v = f.access_index(texels[1], 1)?;
+        let separate_v = f.access_index(texels[2], 0)?;
+        let three = f.literal(Literal::U32(3))?;
+        let is_separate = f.binary(BinaryOperator::Equal, num_planes, three)?;
+        let v = f.select(is_separate, separate_v, interleaved_v)?;
+        let one = f.literal(Literal::F32(1.0))?;
+        let yuv = f.compose(types.vec4f, vec![y, u, v, one])?;
+        let rgb = f.binary(BinaryOperator::Multiply, matrix, yuv)?;
+
+        let one = f.literal(Literal::U32(1))?;
+        let is_rgb = f.binary(BinaryOperator::Equal, num_planes, one)?;
+        f.select(is_rgb, texels[0], rgb)
+    }
+}
+
+/// The size of level 0 of `image`, as a `vec2<f32>`.
+fn image_size(
+    f: &mut FunctionBuilder,
+    image: Handle<Expression>,
+) -> Result<Handle<Expression>, BuildError> {
+    let size = f.expr(Expression::ImageQuery {
+        image,
+        query: crate::ImageQuery::Size { level: None },
+    })?;
+    f.cast(size, ScalarKind::Float, Some(4))
+}
+
+/// Replace the accesses to external textures in `function` with calls to
+/// the `helpers`, and adjust its calls to the functions that were `moved`.
+fn lower_function(
+    function: &mut Function,
+    accesses: &Accesses,
+    helpers: &FastHashMap<(Handle<GlobalVariable>, Access), Handle<Function>>,
+    moved: &[Handle<Function>],
+) {
+    for (_, expr) in function.expressions.iter_mut() {
+        if let Expression::CallResult(ref mut callee) = *expr {
+            *callee = moved[callee.index()];
+        }
+    }
+
+    let mut calls = FastHashMap::default();
+    for &(handle, global, access) in accesses.iter() {
+        let helper = helpers[&(global, access)];
+        let arguments = match function.expressions[handle] {
+            Expression::ImageSample {
+                sampler,
+                coordinate,
+                ..
+            } => vec![sampler, coordinate],
+            Expression::ImageLoad { coordinate, .. } => vec![coordinate],
+            _ => unreachable!(),
+        };
+        *function.expressions.get_mut(handle) = Expression::CallResult(helper);
+        calls.insert(
+            handle,
+            Statement::Call {
+                function: helper,
+                arguments,
+                result: Some(handle),
+            },
+        );
+    }
+
+    lower_block(&mut function.body, &mut calls, moved);
+}
+
+/// Insert the `calls` computing the results that were emitted in `block`,
+/// and adjust its calls to the functions that were `moved`.
+fn lower_block(
+    block: &mut Block,
+    calls: &mut FastHashMap<Handle<Expression>, Statement>,
+    moved: &[Handle<Function>],
+) {
+    let mut new_block = Block::with_capacity(block.len());
+    for (statement, span) in block.span_iter_mut() {
+        let span = span.map_or(Span::default(), |span| *span);
+        match *statement {
+            Statement::Emit(ref range) => {
+                // Call results can't be emitted, so split the range around
+                // them.
+                let mut current = None;
+                for handle in range.clone() {
+                    match calls.remove(&handle) {
+                        Some(call) => {
+                            if let Some((first, last)) = current.take() {
+                                let range = crate::Range::new_from_bounds(first, last);
+                                new_block.push(Statement::Emit(range), span);
+                            }
+                            new_block.push(call, span);
+                        }
+                        None => {
+                            current = Some(match current {
+                                Some((first, _)) => (first, handle),
+                                None => (handle, handle),
+                            });
+                        }
+                    }
+                }
+                if let Some((first, last)) = current {
+                    let range = crate::Range::new_from_bounds(first, last);
+                    new_block.push(Statement::Emit(range), span);
+                }
+                continue;
+            }
+            Statement::Call {
+                ref mut function, ..
+            } => *function = moved[function.index()],
+            Statement::Block(ref mut block) => lower_block(block, calls, moved),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                lower_block(accept, calls, moved);
+                lower_block(reject, calls, moved);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    lower_block(&mut case.body, calls, moved);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                lower_block(body, calls, moved);
+                lower_block(continuing, calls, moved);
+            }
+            _ => {}
+        }
+        new_block.push(std::mem::replace(statement, Statement::Kill), span);
+    }
+    *block = new_block;
+}
@@ -1408,8 +1408,36 @@ fn inject_common_builtin(
                 declaration.overloads.push(module.add_builtin(args, fun))
             }
         }
-        // TODO: https://github.com/gfx-rs/naga/issues/2526
-        // "modf" | "frexp" => { ... }
+        "modf" | "frexp" => {
+            // bits layout
+            // bit 0 through 1 - dims
+            for bits in 0..0b100 {
+                let size = match bits {
+                    0b00 => None,
+                    0b01 => Some(VectorSize::Bi),
+                    0b10 => Some(VectorSize::Tri),
+                    _ => Some(VectorSize::Quad),
+                };
+
+                let (fun, second_kind, second_width) = match name {
+                    "modf" => (MathFunction::Modf, Sk::Float, float_width),
+                    "frexp" => (MathFunction::Frexp, Sk::Sint, 4),
+                    _ => unreachable!(),
+                };
+
+                let args = [(Sk::Float, float_width), (second_kind, second_width)]
+                    .into_iter()
+                    .map(|(kind, width)| match size {
+                        Some(size) => TypeInner::Vector { size, kind, width },
+                        None => TypeInner::Scalar { kind, width },
+                    })
+                    .collect();
+
+                let mut overload = module.add_builtin(args, MacroCall::OutParameter(fun));
+                overload.parameters_info[1].qualifier = ParameterQualifier::Out;
+                declaration.overloads.push(overload)
+            }
+        }
         "cross" => {
             let args = vec![
                 TypeInner::Vector {
@@ -1621,6 +1649,9 @@ pub enum MacroCall {
         /// The size of the splat operation if some
         splatted: Option<VectorSize>,
     },
+    /// Modf and Frexp return their second result through an `out` parameter
+    /// instead of a struct member
+    OutParameter(MathFunction),
 }
 
 impl MacroCall {
@@ -2063,10 +2094,57 @@ impl MacroCall {
                 },
                 Span::default(),
             )?,
+            MacroCall::OutParameter(fun) => {
+                let (size, width) = match *ctx.resolve_type(args[0], meta)? {
+                    TypeInner::Scalar { width, .. } => (None, width),
+                    TypeInner::Vector { size, width, .. } => (Some(size), width),
+                    _ => unreachable!(),
+                };
+                ctx.module.generate_predeclared_type(match fun {
+                    MathFunction::Modf => crate::PredeclaredType::ModfResult { size, width },
+                    _ => crate::PredeclaredType::FrexpResult { size, width },
+                });
+
+                let result = ctx.add_expression(
+                    Expression::Math {
+                        fun,
+                        arg: args[0],
+                        arg1: None,
+                        arg2: None,
+                        arg3: None,
+                    },
+                    Span::default(),
+                )?;
+                let first = ctx.add_expression(
+                    Expression::AccessIndex {
+                        base: result,
+                        index: 0,
+                    },
+                    Span::default(),
+                )?;
+                let second = ctx.add_expression(
+                    Expression::AccessIndex {
+                        base: result,
+                        index: 1,
+                    },
+                    Span::default(),
+                )?;
+                ctx.emit_restart();
+                ctx.body.push(
+                    crate::Statement::Store {
+                        pointer: args[1],
+                        value: second,
+                    },
+                    meta,
+                );
+                first
+            }
             MacroCall::Barrier => {
                 ctx.emit_restart();
-                ctx.body
-                    .push(crate::Statement::Barrier(crate::Barrier::all()), meta);
+                ctx.body.push(
+                    crate::Statement::Barrier(crate::Barrier::STORAGE | crate::Barrier::WORK_GROUP),
+                    meta,
+                );
                 return Ok(None);
             }
             MacroCall::SmoothStep { splatted } => {
@@ -2164,7 +2242,7 @@ impl Frontend {
             let (shadow, storage) = match class {
                 ImageClass::Depth { .. } => (true, false),
                 ImageClass::Storage { .. } => (false, true),
-                ImageClass::Sampled { .. } => (false, false),
+                ImageClass::Sampled { .. } | ImageClass::External => (false, false),
             };
 
             let coordinate = match (image_size, coord_size) {
@@ -2282,7 +2360,7 @@ pub fn sampled_to_depth(
             }
             ImageClass::Depth { .. } => {}
             // Other image classes aren't allowed to be transformed to depth
-            ImageClass::Storage { .. } => errors.push(Error {
+            ImageClass::Storage { .. } | ImageClass::External => errors.push(Error {
                 kind: ErrorKind::SemanticError("Not a texture".into()),
                 meta,
             }),
@@ -841,34 +841,47 @@ impl Frontend {
 
                 ctx.emit_start();
 
-                // Write back all the variables that were scheduled to their original place
-                for proxy_write in proxy_writes {
-                    let mut value = ctx.add_expression(
-                        Expression::Load {
-                            pointer: proxy_write.value,
-                        },
-                        meta,
-                    )?;
+                Self::write_proxies(ctx, proxy_writes, meta)?;
 
-                    if let Some((kind, width)) = proxy_write.convert {
-                        ctx.conversion(&mut value, meta, kind, width)?;
-                    }
-
-                    ctx.emit_restart();
+                Ok(result)
+            }
+            FunctionKind::Macro(builtin) => {
+                let result = builtin.call(self, ctx, arguments.as_mut_slice(), meta)?;
+                Self::write_proxies(ctx, proxy_writes, meta)?;
+                Ok(result)
+            }
+        }
+    }
 
-                    ctx.body.push(
-                        Statement::Store {
-                            pointer: proxy_write.target,
-                            value,
-                        },
-                        meta,
-                    );
-                }
+    /// Writes back all the variables that were scheduled by
+    /// [`process_lhs_argument`] to their original place.
+    ///
+    /// [`process_lhs_argument`]: Self::process_lhs_argument
+    fn write_proxies(ctx: &mut Context, proxy_writes: Vec<ProxyWrite>, meta: Span) -> Result<()> {
+        for proxy_write in proxy_writes {
+            let mut value = ctx.add_expression(
+                Expression::Load {
+                    pointer: proxy_write.value,
+                },
+                meta,
+            )?;
 
-                Ok(result)
+            if let Some((kind, width)) = proxy_write.convert {
+                ctx.conversion(&mut value, meta, kind, width)?;
             }
-            FunctionKind::Macro(builtin) => builtin.call(self, ctx, arguments.as_mut_slice(), meta),
+
+            ctx.emit_restart();
+
+            ctx.body.push(
+                Statement::Store {
+                    pointer: proxy_write.target,
+                    value,
+                },
+                meta,
+            );
         }
+
+        Ok(())
     }
 
     /// Processes a function call argument that appears in place of an output
@@ -43,7 +43,6 @@ use crate::{
     FastHashMap, FastHashSet, FastIndexMap,
 };
 
-use num_traits::cast::FromPrimitive;
 use petgraph::graphmap::GraphMap;
 use std::{convert::TryInto, mem, num::NonZeroU32, path::PathBuf};
 
@@ -603,6 +602,10 @@ pub struct Frontend<I> {
     /// glslang declares those by default even though they are never written to
     /// (see <https://github.com/KhronosGroup/glslang/issues/1868>)
     gl_per_vertex_builtin_access: FastHashSet<crate::BuiltIn>,
+
+    /// Predeclared types that the results of `modf` and `frexp` need, which
+    /// are added to the module once all functions are parsed.
+    predeclared_types: Vec<crate::PredeclaredType>,
 }
 
 impl<I: Iterator<Item = u32>> Frontend<I> {
@@ -635,6 +638,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             options: options.clone(),
             switch_cases: FastIndexMap::default(),
             gl_per_vertex_builtin_access: FastHashSet::default(),
+            predeclared_types: Vec::new(),
         }
     }
 
@@ -661,7 +665,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
         if wc == 0 {
             return Err(Error::InvalidWordCount);
         }
-        let op = spirv::Op::from_u16(opcode).ok_or(Error::UnknownInstruction(opcode))?;
+        let op = spirv::Op::from_u32(opcode.into()).ok_or(Error::UnknownInstruction(opcode))?;
 
         Ok(Instruction { op, wc })
     }
@@ -2322,6 +2326,30 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                         },
                     );
                 }
+                Op::QuantizeToF16 => {
+                    inst.expect(4)?;
+
+                    let result_type_id = self.next()?;
+                    let result_id = self.next()?;
+                    let value_id = self.next()?;
+                    let value_lexp = self.lookup_expression.lookup(value_id)?;
+                    let value_handle = get_expr_handle!(value_id, value_lexp);
+                    let expr = crate::Expression::Math {
+                        fun: crate::MathFunction::QuantizeToF16,
+                        arg: value_handle,
+                        arg1: None,
+                        arg2: None,
+                        arg3: None,
+                    };
+                    self.lookup_expression.insert(
+                        result_id,
+                        LookupExpression {
+                            handle: ctx.expressions.append(expr, span),
+                            type_id: result_type_id,
+                            block_id,
+                        },
+                    );
+                }
                 Op::Dot => {
                     inst.expect(5)?;
 
@@ -2978,8 +3006,8 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                         Glo::UnpackSnorm2x16 => Mf::Unpack2x16snorm,
                         Glo::FindILsb => Mf::FindLsb,
                         Glo::FindUMsb | Glo::FindSMsb => Mf::FindMsb,
-                        // TODO: https://github.com/gfx-rs/naga/issues/2526
-                        Glo::Modf | Glo::Frexp => return Err(Error::UnsupportedExtInst(inst_id)),
+                        Glo::Modf => Mf::Modf,
+                        Glo::Frexp => Mf::Frexp,
                         Glo::IMix
                         | Glo::PackDouble2x32
                         | Glo::UnpackDouble2x32
@@ -2990,13 +3018,44 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                         }
                     };
 
+                    // `Modf` and `Frexp` store their second result through a
+                    // pointer, rather than returning a struct.
+                    let has_out_pointer = matches!(gl_op, Glo::Modf | Glo::Frexp);
                     let arg_count = fun.argument_count();
-                    inst.expect(base_wc + arg_count as u16)?;
+                    inst.expect(base_wc + arg_count as u16 + has_out_pointer as u16)?;
+                    let arg_id = self.next()?;
                     let arg = {
-                        let arg_id = self.next()?;
                         let lexp = self.lookup_expression.lookup(arg_id)?;
                         get_expr_handle!(arg_id, lexp)
                     };
+                    if let Mf::Modf | Mf::Frexp = fun {
+                        let arg_type_id = self.lookup_expression.lookup(arg_id)?.type_id;
+                        let arg_ty = self.lookup_type.lookup(arg_type_id)?.handle;
+                        let (size, width) = match ctx.type_arena[arg_ty].inner {
+                            crate::TypeInner::Scalar { width, .. } => (None, width),
+                            crate::TypeInner::Vector { size, width, .. } => (Some(size), width),
+                            _ => return Err(Error::InvalidInnerType(arg_type_id)),
+                        };
+                        self.predeclared_types.push(if fun == Mf::Modf {
+                            crate::PredeclaredType::ModfResult { size, width }
+                        } else {
+                            crate::PredeclaredType::FrexpResult { size, width }
+                        });
+                    }
+                    let out_pointer = if has_out_pointer {
+                        let pointer_id = self.next()?;
+                        let lexp = self.lookup_expression.lookup(pointer_id)?;
+                        let pointer_ty = self.lookup_type.lookup(lexp.type_id)?.handle;
+                        let pointee_kind = match ctx.type_arena[pointer_ty].inner {
+                            crate::TypeInner::Pointer { base, .. } => {
+                                ctx.type_arena[base].inner.scalar_kind()
+                            }
+                            _ => None,
+                        };
+                        Some((get_expr_handle!(pointer_id, lexp), pointee_kind))
+                    } else {
+                        None
+                    };
                     let arg1 = if arg_count > 1 {
                         let arg_id = self.next()?;
                         let lexp = self.lookup_expression.lookup(arg_id)?;
@@ -3026,10 +3085,42 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                         arg2,
                         arg3,
                     };
+                    let mut handle = ctx.expressions.append(expr, span);
+                    if let Some((pointer, pointee_kind)) = out_pointer {
+                        let result = handle;
+                        handle = ctx.expressions.append(
+                            crate::Expression::AccessIndex {
+                                base: result,
+                                index: 0,
+                            },
+                            span,
+                        );
+                        let mut value = ctx.expressions.append(
+                            crate::Expression::AccessIndex {
+                                base: result,
+                                index: 1,
+                            },
+                            span,
+                        );
+                        // `Frexp` may store the exponent as an unsigned integer.
+                        if fun == Mf::Frexp && pointee_kind == Some(crate::ScalarKind::Uint) {
+                            value = ctx.expressions.append(
+                                crate::Expression::As {
+                                    expr: value,
+                                    kind: crate::ScalarKind::Uint,
+                                    convert: None,
+                                },
+                                span,
+                            );
+                        }
+                        block.extend(emitter.finish(ctx.expressions));
+                        block.push(crate::Statement::Store { pointer, value }, span);
+                        emitter.start(ctx.expressions);
+                    }
                     self.lookup_expression.insert(
                         result_id,
                         LookupExpression {
-                            handle: ctx.expressions.append(expr, span),
+                            handle,
                             type_id: result_type_id,
                             block_id,
                         },
@@ -3668,6 +3759,10 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                                     .bits()
                                 != 0,
                         );
+                        flags.set(
+                            crate::Barrier::TEXTURE,
+                            semantics & spirv::MemorySemantics::IMAGE_MEMORY.bits() != 0,
+                        );
                         block.push(crate::Statement::Barrier(flags), span);
                     } else {
                         log::warn!("Unsupported barrier execution scope: {}", exec_scope);
@@ -3977,6 +4072,10 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                     .append(fun, functions.get_span(lookup.handle));
             }
         }
+        for special_type in self.predeclared_types.drain(..) {
+            module.generate_predeclared_type(special_type);
+        }
+
         // patch all the functions
         for (handle, fun) in module.functions.iter_mut() {
             self.patch_function(Some(handle), fun)?;
@@ -4877,6 +4976,11 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 let low = self.next()?;
                 match width {
                     4 => crate::Literal::U32(low),
+                    8 => {
+                        inst.expect(5)?;
+                        let high = self.next()?;
+                        crate::Literal::U64((u64::from(high) << 32) | u64::from(low))
+                    }
                     _ => return Err(Error::InvalidTypeWidth(width as u32)),
                 }
             }
@@ -4887,6 +4991,11 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 let low = self.next()?;
                 match width {
                     4 => crate::Literal::I32(low as i32),
+                    8 => {
+                        inst.expect(5)?;
+                        let high = self.next()?;
+                        crate::Literal::I64(((u64::from(high) << 32) | u64::from(low)) as i64)
+                    }
                     _ => return Err(Error::InvalidTypeWidth(width as u32)),
                 }
             }
@@ -158,6 +158,7 @@ pub enum Error<'a> {
     /// A break if appeared outside of a continuing block
     InvalidBreakIf(Span),
     InvalidGatherComponent(Span),
+    InvalidSampleBaseClampToEdgeImage(Span),
     InvalidConstructorComponentType(Span, i32),
     InvalidIdentifierUnderscore(Span),
     ReservedIdentifierPrefix(Span),
@@ -350,6 +351,14 @@ impl<'a> Error<'a> {
                 labels: vec![(bad_span, "not an image".into())],
                 notes: vec![],
             },
+            Error::InvalidSampleBaseClampToEdgeImage(bad_span) => ParseError {
+                message: format!(
+                    "`textureSampleBaseClampToEdge` expects a `texture_2d<f32>` or `texture_external`, but found '{}'",
+                    &source[bad_span]
+                ),
+                labels: vec![(bad_span, "invalid texture type".into())],
+                notes: vec![],
+            },
             Error::BadTypeCast {
                 span,
                 ref from_type,
@@ -628,7 +637,11 @@ impl<'a> Error<'a> {
                     .into(),
                 )],
                 notes: vec![if uint {
-                    format!("suffix the integer with a `u`: '{}u'", &source[span])
+                    let value = &source[span];
+                    format!(
+                        "suffix the integer with a `u`: '{}u'",
+                        value.strip_suffix('i').unwrap_or(value)
+                    )
                 } else {
                     let span = span.to_range().unwrap();
                     format!(
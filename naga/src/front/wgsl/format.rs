@@ -0,0 +1,952 @@
+/*!
+A source-preserving formatter for WGSL.
+
+The syntax tree doesn't record everything the formatter needs, so it finds the
+rest, like attributes, in the source text by position. This is why formatting
+requires the `span` feature.
+*/
+
+use std::ops::Range;
+
+use super::{
+    error::Error,
+    parse::{ast, lexer, Parser},
+    ParseError,
+};
+use crate::Handle;
+
+const INDENT: &str = "    ";
+
+/// The number of columns argument lists may take before they are split.
+const MAX_WIDTH: usize = 100;
+
+/// Format the WGSL in `source`.
+///
+/// Unlike the WGSL back end, which generates WGSL from Naga IR, this works from
+/// the parser's syntax tree and the source text. It keeps comments, names,
+/// literals, parentheses and the way types are spelled, and only changes
+/// layout:
+///
+/// - Blocks and struct members are indented by four spaces, with one
+///   statement or member per line.
+///
+/// - Binary operators, `=`, commas and colons get single spaces around them.
+///
+/// - Runs of blank lines are collapsed into one.
+///
+/// - Argument lists are split one argument per line if they don't fit in 100
+///   columns, if they were split that way in the source, or if they contain
+///   comments.
+///
+/// Only syntax errors are reported: the source doesn't need to be a valid
+/// module.
+pub fn format_str(source: &str) -> Result<String, ParseError> {
+    let tu = Parser::new()
+        .parse(source)
+        .map_err(|error| error.as_parse_error(source))?;
+
+    let mut formatter = Formatter::new(source, &tu);
+    formatter.translation_unit();
+    let output = formatter.out;
+
+    // The formatter should only ever move tokens and comments around. Rather
+    // than risk changing the meaning of the shader or losing a comment, check.
+    if Parser::new().parse(&output).is_err() || normalize(source) != normalize(&output) {
+        return Err(
+            Error::Internal("formatting would change the meaning of the source")
+                .as_parse_error(source),
+        );
+    }
+
+    Ok(output)
+}
+
+struct Formatter<'a, 't> {
+    source: &'a str,
+    tu: &'t ast::TranslationUnit<'a>,
+
+    /// The ranges of all the comments in `source`, in order.
+    comments: Vec<Range<usize>>,
+
+    /// The index in `comments` of the first comment not yet written.
+    ///
+    /// Comments are always written in order. Those the formatter doesn't
+    /// find a place for, like comments in the middle of an expression, are
+    /// written on their own lines before the next statement.
+    next_comment: usize,
+
+    /// The offset in `source` of the end of the last thing written.
+    written: usize,
+
+    /// Whether we are at the start of a block, where blank lines are dropped.
+    fresh: bool,
+
+    indent: usize,
+    out: String,
+}
+
+impl<'a, 't> Formatter<'a, 't> {
+    fn new(source: &'a str, tu: &'t ast::TranslationUnit<'a>) -> Self {
+        Formatter {
+            source,
+            tu,
+            comments: lexer::comments(source)
+                .into_iter()
+                .filter_map(|span| span.to_range())
+                .collect(),
+            next_comment: 0,
+            written: 0,
+            fresh: true,
+            indent: 0,
+            out: String::new(),
+        }
+    }
+
+    fn translation_unit(&mut self) {
+        for (handle, decl) in self.tu.decls.iter() {
+            let range = self.tu.decls.get_span(handle).to_range().unwrap();
+            self.global_decl(decl, range);
+        }
+        self.comments_before(self.source.len());
+    }
+
+    fn global_decl(&mut self, decl: &ast::GlobalDecl<'a>, range: Range<usize>) {
+        // Skip any empty declarations, like the `;` some people put after a
+        // struct, to find the declaration's attributes.
+        let mut start = self.next_token(self.written);
+        while self.source[start..].starts_with(';') {
+            start = self.next_token(start + 1);
+        }
+
+        self.item(start);
+        if start < range.start {
+            let attributes = self.squeeze(start..range.start);
+            if self.source[start..range.start].contains('\n') {
+                // Keep attributes on a line of their own, followed by any
+                // comments between them and the declaration.
+                self.out.push_str(&attributes);
+                self.out.push('\n');
+                self.written = range.start;
+                self.fresh = true;
+                self.comments_before(range.start);
+                self.start_line();
+            } else {
+                self.out.push_str(&attributes);
+                self.out.push(' ');
+            }
+        }
+
+        match decl.kind {
+            ast::GlobalDeclKind::Fn(ref function) => self.function(function),
+            ast::GlobalDeclKind::Var(ref var) => {
+                let header = self.squeeze(range.start..self.start(var.name.span));
+                self.out.push_str(&header);
+                self.out.push(' ');
+                self.out.push_str(var.name.name);
+                self.ty_annotation(Some(var.ty));
+                self.initializer(var.init);
+                self.out.push(';');
+            }
+            ast::GlobalDeclKind::Const(ref constant) => {
+                self.out.push_str("const ");
+                self.out.push_str(constant.name.name);
+                self.ty_annotation(constant.ty);
+                self.initializer(Some(constant.init));
+                self.out.push(';');
+            }
+            ast::GlobalDeclKind::Struct(ref decl) => {
+                self.out.push_str("struct ");
+                self.out.push_str(decl.name.name);
+                self.out.push(' ');
+                self.struct_members(decl, range.end - 1);
+            }
+            ast::GlobalDeclKind::Type(ref alias) => {
+                self.out.push_str("alias ");
+                self.out.push_str(alias.name.name);
+                self.out.push_str(" = ");
+                self.ty(alias.ty);
+                self.out.push(';');
+            }
+        }
+
+        self.end_item(range.end);
+    }
+
+    fn function(&mut self, function: &ast::Function<'a>) {
+        self.out.push_str("fn ");
+        self.out.push_str(function.name.name);
+
+        let open = self.find(self.end(function.name.span), b'(');
+        let mut previous = open + 1;
+        let items = function
+            .arguments
+            .iter()
+            .map(|argument| {
+                let start = self.attributes_start(previous, self.start(argument.name.span));
+                previous = self.ty_range(argument.ty).end;
+                start..previous
+            })
+            .collect::<Vec<_>>();
+        let close = self.find(previous, b')');
+        self.list(open, close, &items, |this, i| {
+            let argument = &function.arguments[i];
+            let attributes = this.squeeze(items[i].start..this.start(argument.name.span));
+            if !attributes.is_empty() {
+                this.out.push_str(&attributes);
+                this.out.push(' ');
+            }
+            this.out.push_str(argument.name.name);
+            this.ty_annotation(Some(argument.ty));
+        });
+
+        let body = function.body.span.to_range().unwrap();
+        if let Some(ref result) = function.result {
+            let ty = self.ty_range(result.ty);
+            let arrow = self.find(close, b'-');
+            self.out.push_str(" -> ");
+            let attributes = self.squeeze(arrow + 2..ty.start);
+            if !attributes.is_empty() {
+                self.out.push_str(&attributes);
+                self.out.push(' ');
+            }
+            self.ty(result.ty);
+        } else if self.find(close, b'-') < body.start {
+            self.out.push_str(" -> void");
+        }
+
+        self.out.push(' ');
+        self.block(&function.body);
+    }
+
+    fn struct_members(&mut self, decl: &ast::Struct<'a>, close: usize) {
+        let open = self.find(self.end(decl.name.span), b'{');
+        if decl.members.is_empty() && !self.has_comments(open..close) {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.open(open, '{');
+        let mut previous = open + 1;
+        for member in decl.members.iter() {
+            let name = self.start(member.name.span);
+            let start = self.attributes_start(previous, name);
+            self.item(start);
+            let attributes = self.squeeze(start..name);
+            if !attributes.is_empty() {
+                self.out.push_str(&attributes);
+                self.out.push(' ');
+            }
+            self.out.push_str(member.name.name);
+            self.ty_annotation(Some(member.ty));
+            self.out.push(',');
+            previous = self.ty_range(member.ty).end;
+            self.end_item(previous);
+        }
+        self.close(close, '}');
+    }
+
+    fn block(&mut self, block: &ast::Block<'a>) {
+        let range = block.span.to_range().unwrap();
+        let close = range.end - 1;
+        if block.stmts.is_empty() && !self.has_comments(range.start..close) {
+            self.out.push_str("{}");
+            self.written = range.end;
+            return;
+        }
+
+        self.open(range.start, '{');
+        for statement in block.stmts.iter() {
+            self.statement(statement);
+        }
+        self.close(close, '}');
+    }
+
+    fn statement(&mut self, statement: &ast::Statement<'a>) {
+        let range = statement.span.to_range().unwrap();
+        self.item(range.start);
+
+        match statement.kind {
+            ast::StatementKind::Block(ref block) => self.block(block),
+            ast::StatementKind::If {
+                condition,
+                ref accept,
+                ref reject,
+            } => self.if_statement(condition, accept, reject),
+            ast::StatementKind::Switch {
+                selector,
+                ref cases,
+            } => {
+                self.out.push_str("switch ");
+                self.expression(selector);
+                self.out.push(' ');
+                let open = self.find(self.expression_range(selector).end, b'{');
+                self.open(open, '{');
+                self.switch_cases(cases);
+                self.close(range.end - 1, '}');
+            }
+            ast::StatementKind::Loop {
+                ref body,
+                ref continuing,
+                break_if,
+            } => {
+                self.out.push_str("loop ");
+                let braces = body.span.to_range().unwrap();
+                if body.stmts.is_empty()
+                    && !continuing.span.is_defined()
+                    && !self.has_comments(braces.clone())
+                {
+                    self.out.push_str("{}");
+                } else {
+                    self.open(braces.start, '{');
+                    for statement in body.stmts.iter() {
+                        self.statement(statement);
+                    }
+                    if continuing.span.is_defined() {
+                        self.continuing(continuing, break_if);
+                    }
+                    self.close(braces.end - 1, '}');
+                }
+            }
+            ast::StatementKind::While {
+                condition,
+                ref body,
+            } => {
+                self.out.push_str("while ");
+                self.expression(condition);
+                self.out.push(' ');
+                self.block(body);
+            }
+            ast::StatementKind::For {
+                ref init,
+                test,
+                ref update,
+                ref body,
+            } => {
+                self.out.push_str("for (");
+                if let Some(ref init) = *init {
+                    self.simple_statement(init);
+                }
+                self.out.push(';');
+                if let Some(test) = test {
+                    self.out.push(' ');
+                    self.expression(test);
+                }
+                self.out.push(';');
+                if let Some(ref update) = *update {
+                    self.out.push(' ');
+                    self.simple_statement(update);
+                }
+                self.out.push_str(") ");
+                self.block(body);
+            }
+            ast::StatementKind::Break => self.out.push_str("break;"),
+            ast::StatementKind::Continue => self.out.push_str("continue;"),
+            ast::StatementKind::Kill => self.out.push_str("discard;"),
+            ast::StatementKind::Return { value } => {
+                self.out.push_str("return");
+                if let Some(value) = value {
+                    self.out.push(' ');
+                    self.expression(value);
+                }
+                self.out.push(';');
+            }
+            ast::StatementKind::LocalDecl(_)
+            | ast::StatementKind::Call { .. }
+            | ast::StatementKind::Assign { .. }
+            | ast::StatementKind::Increment(_)
+            | ast::StatementKind::Decrement(_)
+            | ast::StatementKind::Ignore(_) => {
+                self.simple_statement(statement);
+                self.out.push(';');
+            }
+        }
+
+        self.end_item(range.end);
+    }
+
+    /// Write a statement that can appear in the header of a `for` loop,
+    /// without the `;` that ends it elsewhere.
+    fn simple_statement(&mut self, statement: &ast::Statement<'a>) {
+        match statement.kind {
+            ast::StatementKind::LocalDecl(ast::LocalDecl::Var(ref var)) => {
+                self.out.push_str("var ");
+                self.out.push_str(var.name.name);
+                self.ty_annotation(var.ty);
+                self.initializer(var.init);
+            }
+            ast::StatementKind::LocalDecl(ast::LocalDecl::Let(ref l)) => {
+                self.out.push_str("let ");
+                self.out.push_str(l.name.name);
+                self.ty_annotation(l.ty);
+                self.initializer(Some(l.init));
+            }
+            ast::StatementKind::Call {
+                ref function,
+                ref arguments,
+            } => {
+                self.out.push_str(function.name);
+                let open = self.find(self.end(function.span), b'(');
+                let close = statement.span.to_range().unwrap().end - 1;
+                self.arguments(arguments, open, close);
+            }
+            ast::StatementKind::Assign { target, op, value } => {
+                self.expression(target);
+                self.out.push(' ');
+                if let Some(op) = op {
+                    self.out.push_str(binary_operator(op));
+                }
+                self.out.push_str("= ");
+                self.expression(value);
+            }
+            ast::StatementKind::Increment(target) => {
+                self.expression(target);
+                self.out.push_str("++");
+            }
+            ast::StatementKind::Decrement(target) => {
+                self.expression(target);
+                self.out.push_str("--");
+            }
+            ast::StatementKind::Ignore(value) => {
+                self.out.push_str("_ = ");
+                self.expression(value);
+            }
+            _ => unreachable!("not a simple statement"),
+        }
+    }
+
+    fn if_statement(
+        &mut self,
+        condition: Handle<ast::Expression<'a>>,
+        accept: &ast::Block<'a>,
+        reject: &ast::Block<'a>,
+    ) {
+        self.out.push_str("if ");
+        self.expression(condition);
+        self.out.push(' ');
+        self.block(accept);
+
+        if reject.span.is_defined() {
+            self.out.push_str(" else ");
+            self.block(reject);
+        } else if let [ast::Statement {
+            kind:
+                ast::StatementKind::If {
+                    condition,
+                    ref accept,
+                    ref reject,
+                },
+            ..
+        }] = reject.stmts[..]
+        {
+            // The parser represents `else if` as an `if` alone in a block
+            // that doesn't appear in the source.
+            self.out.push_str(" else ");
+            self.if_statement(condition, accept, reject);
+        }
+    }
+
+    fn switch_cases(&mut self, cases: &[ast::SwitchCase<'a>]) {
+        // The parser turns `case 1, 2 {}` into a case for `1` that falls
+        // through to a case for `2`. Put them back together.
+        let mut values = Vec::new();
+        for case in cases {
+            values.push(&case.value);
+            if case.fall_through {
+                continue;
+            }
+
+            let start = self.next_token(self.written);
+            self.item(start);
+            if let [&ast::SwitchValue::Default] = values[..] {
+                self.out.push_str("default");
+            } else {
+                self.out.push_str("case ");
+                for (i, value) in values.drain(..).enumerate() {
+                    if i != 0 {
+                        self.out.push_str(", ");
+                    }
+                    match *value {
+                        ast::SwitchValue::Expr(expr) => self.expression(expr),
+                        ast::SwitchValue::Default => self.out.push_str("default"),
+                    }
+                }
+            }
+            values.clear();
+            self.out.push(' ');
+            self.block(&case.body);
+            self.end_item(case.body.span.to_range().unwrap().end);
+        }
+    }
+
+    fn continuing(
+        &mut self,
+        continuing: &ast::Block<'a>,
+        break_if: Option<Handle<ast::Expression<'a>>>,
+    ) {
+        let braces = continuing.span.to_range().unwrap();
+        let start = self.next_token(self.written);
+        self.item(start);
+        self.out.push_str("continuing ");
+        if break_if.is_none() {
+            self.block(continuing);
+        } else {
+            self.open(braces.start, '{');
+            for statement in continuing.stmts.iter() {
+                self.statement(statement);
+            }
+            if let Some(condition) = break_if {
+                let start = self.next_token(self.written);
+                self.item(start);
+                self.out.push_str("break if ");
+                self.expression(condition);
+                self.out.push(';');
+                self.end_item(self.expression_range(condition).end);
+            }
+            self.close(braces.end - 1, '}');
+        }
+        self.end_item(braces.end);
+    }
+
+    fn expression(&mut self, handle: Handle<ast::Expression<'a>>) {
+        self.expression_in(handle, false);
+    }
+
+    /// Write the expression `handle`.
+    ///
+    /// If `sole_argument` is true, the expression is the only argument of a
+    /// call, and the call's parentheses aren't its own.
+    fn expression_in(&mut self, handle: Handle<ast::Expression<'a>>, sole_argument: bool) {
+        let range = self.expression_range(handle);
+        let grouped = self.parentheses(range.clone()) > usize::from(sole_argument);
+        if grouped {
+            self.out.push('(');
+        }
+
+        match self.tu.expressions[handle] {
+            ast::Expression::Literal(_) | ast::Expression::Ident(_) => {
+                self.out.push_str(&self.source[range]);
+            }
+            ast::Expression::Construct {
+                ty_span,
+                ref components,
+                ..
+            } => {
+                let ty = ty_span.to_range().unwrap();
+                let name = self.squeeze(ty.clone());
+                self.out.push_str(&name);
+                let open = self.find(ty.end, b'(');
+                self.arguments(components, open, range.end - 1);
+            }
+            ast::Expression::Unary { op, expr } => {
+                let op = match op {
+                    crate::UnaryOperator::Negate => '-',
+                    crate::UnaryOperator::LogicalNot => '!',
+                    crate::UnaryOperator::BitwiseNot => '~',
+                };
+                self.prefix(op, expr);
+            }
+            ast::Expression::AddrOf(expr) => self.prefix('&', expr),
+            ast::Expression::Deref(expr) => self.prefix('*', expr),
+            ast::Expression::Binary { op, left, right } => {
+                self.expression(left);
+                self.out.push(' ');
+                self.out.push_str(binary_operator(op));
+                self.out.push(' ');
+                self.expression(right);
+            }
+            ast::Expression::Call {
+                ref function,
+                ref arguments,
+            } => {
+                self.out.push_str(function.name);
+                let open = self.find(self.end(function.span), b'(');
+                self.arguments(arguments, open, range.end - 1);
+            }
+            ast::Expression::Index { base, index } => {
+                self.expression(base);
+                self.out.push('[');
+                self.expression(index);
+                self.out.push(']');
+            }
+            ast::Expression::Member { base, ref field } => {
+                self.expression(base);
+                self.out.push('.');
+                self.out.push_str(field.name);
+            }
+            ast::Expression::Bitcast { expr, to, .. } => {
+                self.out.push_str("bitcast<");
+                self.ty(to);
+                self.out.push_str(">(");
+                self.expression_in(expr, true);
+                self.out.push(')');
+            }
+        }
+
+        if grouped {
+            self.out.push(')');
+        }
+    }
+
+    fn prefix(&mut self, op: char, operand: Handle<ast::Expression<'a>>) {
+        let start = self.out.len();
+        self.out.push(op);
+        self.expression(operand);
+        // Don't turn `- -x` into a decrement, or `& &x` into a logical and.
+        if matches!(op, '-' | '&') && self.out[start + 1..].starts_with(op) {
+            self.out.insert(start + 1, ' ');
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Handle<ast::Expression<'a>>], open: usize, close: usize) {
+        let items = arguments
+            .iter()
+            .map(|&argument| self.expression_range(argument))
+            .collect::<Vec<_>>();
+        let sole_argument = arguments.len() == 1;
+        self.list(open, close, &items, |this, i| {
+            this.expression_in(arguments[i], sole_argument);
+        });
+    }
+
+    /// Write a parenthesized, comma-separated list.
+    ///
+    /// The list's parentheses are at `open` and `close`, and `items` holds the
+    /// source range of each item, which `write` writes. The list goes on one
+    /// line if it fits and the source allows it, and is split one item per
+    /// line otherwise.
+    fn list(
+        &mut self,
+        open: usize,
+        close: usize,
+        items: &[Range<usize>],
+        mut write: impl FnMut(&mut Self, usize),
+    ) {
+        let split_in_source = items
+            .first()
+            .map_or(false, |item| self.source[open..item.start].contains('\n'));
+        if !split_in_source && !self.has_comments(open..close) {
+            let start = self.out.len();
+            let state = (self.next_comment, self.written, self.fresh);
+            self.out.push('(');
+            for i in 0..items.len() {
+                if i != 0 {
+                    self.out.push_str(", ");
+                }
+                write(self, i);
+            }
+            self.out.push(')');
+
+            let line = &self.out[start..];
+            if !line.contains('\n') && self.width() <= MAX_WIDTH {
+                self.written = close + 1;
+                return;
+            }
+            self.out.truncate(start);
+            (self.next_comment, self.written, self.fresh) = state;
+        }
+
+        self.open(open, '(');
+        for (i, item) in items.iter().enumerate() {
+            self.item(item.start);
+            write(self, i);
+            self.out.push(',');
+            self.end_item(item.end);
+        }
+        self.close(close, ')');
+    }
+
+    fn ty(&mut self, ty: Handle<ast::Type<'a>>) {
+        let text = self.squeeze(self.ty_range(ty));
+        self.out.push_str(&text);
+    }
+
+    fn ty_annotation(&mut self, ty: Option<Handle<ast::Type<'a>>>) {
+        if let Some(ty) = ty {
+            self.out.push_str(": ");
+            self.ty(ty);
+        }
+    }
+
+    fn initializer(&mut self, init: Option<Handle<ast::Expression<'a>>>) {
+        if let Some(init) = init {
+            self.out.push_str(" = ");
+            self.expression(init);
+        }
+    }
+
+    /// Start a line for something that starts at `start` in the source,
+    /// after the comments that precede it.
+    fn item(&mut self, start: usize) {
+        self.comments_before(start);
+        self.blank_line(start);
+        self.start_line();
+    }
+
+    /// Finish the line for something that ends at `end` in the source,
+    /// with the comments that follow it on the same line.
+    fn end_item(&mut self, end: usize) {
+        self.written = self.written.max(end);
+        self.trailing_comments(end);
+        self.out.push('\n');
+    }
+
+    /// Write the bracket at `open` in the source, and start a new level of
+    /// indentation.
+    fn open(&mut self, open: usize, bracket: char) {
+        self.out.push(bracket);
+        self.written = open + 1;
+        self.trailing_comments(open + 1);
+        self.out.push('\n');
+        self.indent += 1;
+        self.fresh = true;
+    }
+
+    /// Write the bracket at `close` in the source on a line of its own,
+    /// ending the level of indentation `open` started.
+    fn close(&mut self, close: usize, bracket: char) {
+        self.comments_before(close);
+        self.indent -= 1;
+        self.start_line();
+        self.out.push(bracket);
+        self.written = close + 1;
+    }
+
+    fn start_line(&mut self) {
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    /// Write an empty line if the source has one before `start`.
+    fn blank_line(&mut self, start: usize) {
+        if !self.fresh
+            && start > self.written
+            && self.source[self.written..start].matches('\n').count() > 1
+        {
+            self.out.push('\n');
+        }
+        self.fresh = false;
+    }
+
+    /// Write the comments that start before `position`, each on its own line.
+    fn comments_before(&mut self, position: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment).cloned() {
+            if comment.start >= position {
+                break;
+            }
+            self.blank_line(comment.start);
+            self.start_line();
+            self.comment(comment);
+            self.out.push('\n');
+        }
+    }
+
+    /// Write the comments that follow `end` on the same line in the source.
+    fn trailing_comments(&mut self, end: usize) {
+        let mut end = end;
+        while let Some(comment) = self.comments.get(self.next_comment).cloned() {
+            // Only separators may come between the code and a trailing comment.
+            if comment.start < end
+                || !self.source[end..comment.start]
+                    .chars()
+                    .all(|c| c == ',' || c == ';' || (c.is_whitespace() && c != '\n'))
+            {
+                break;
+            }
+            self.out.push(' ');
+            end = comment.end;
+            self.comment(comment);
+        }
+    }
+
+    fn comment(&mut self, comment: Range<usize>) {
+        self.out.push_str(&self.source[comment.clone()]);
+        self.written = comment.end;
+        self.next_comment += 1;
+    }
+
+    fn has_comments(&self, range: Range<usize>) -> bool {
+        self.comments[self.next_comment..]
+            .iter()
+            .take_while(|comment| comment.start < range.end)
+            .any(|comment| comment.start >= range.start)
+    }
+
+    fn comment_at(&self, position: usize) -> Option<Range<usize>> {
+        self.comments
+            .binary_search_by_key(&position, |comment| comment.start)
+            .ok()
+            .map(|i| self.comments[i].clone())
+    }
+
+    /// The offset of the first token at or after `position`.
+    fn next_token(&self, mut position: usize) -> usize {
+        loop {
+            if let Some(comment) = self.comment_at(position) {
+                position = comment.end;
+                continue;
+            }
+            match self.source[position..].chars().next() {
+                Some(c) if c.is_whitespace() => position += c.len_utf8(),
+                _ => return position,
+            }
+        }
+    }
+
+    /// The offset of the first `byte` at or after `position`, outside
+    /// comments, or the end of the source.
+    fn find(&self, mut position: usize, byte: u8) -> usize {
+        let bytes = self.source.as_bytes();
+        while position < bytes.len() && bytes[position] != byte {
+            position = self
+                .comment_at(position)
+                .map_or(position + 1, |comment| comment.end);
+        }
+        position
+    }
+
+    /// The start of the attributes of something named at `name`, which
+    /// follows `previous` in a list.
+    fn attributes_start(&self, previous: usize, name: usize) -> usize {
+        let mut start = self.next_token(previous);
+        if self.source[start..].starts_with(',') {
+            start = self.next_token(start + 1);
+        }
+        start.min(name)
+    }
+
+    /// The number of pairs of parentheses around `range` in the source.
+    fn parentheses(&self, range: Range<usize>) -> usize {
+        let count = |text: &mut dyn Iterator<Item = char>, paren| {
+            text.take_while(|&c| c == paren || c.is_whitespace())
+                .filter(|&c| c == paren)
+                .count()
+        };
+        let before = count(&mut self.source[..range.start].chars().rev(), '(');
+        let after = count(&mut self.source[range.end..].chars(), ')');
+        before.min(after)
+    }
+
+    /// The text in `range`, without comments and with whitespace normalized.
+    ///
+    /// This is for things that are copied from the source, like types and
+    /// attributes.
+    fn squeeze(&self, range: Range<usize>) -> String {
+        let mut text = String::new();
+        let mut space = false;
+        let mut position = range.start;
+        while position < range.end {
+            if let Some(comment) = self.comment_at(position) {
+                position = comment.end;
+                space = true;
+                continue;
+            }
+            let c = self.source[position..].chars().next().unwrap();
+            position += c.len_utf8();
+            if c.is_whitespace() {
+                space = true;
+                continue;
+            }
+            if let Some(previous) = text.chars().last() {
+                let after_comma = previous == ',' && !")>]".contains(c);
+                let separated = space && !"(<[@.".contains(previous) && !")>]<([,.".contains(c);
+                let attribute = c == '@' && previous != '(';
+                if after_comma || separated || attribute {
+                    text.push(' ');
+                }
+            }
+            text.push(c);
+            space = false;
+        }
+        text
+    }
+
+    /// The width of the current line.
+    fn width(&self) -> usize {
+        let start = self.out.rfind('\n').map_or(0, |i| i + 1);
+        self.out[start..].chars().count()
+    }
+
+    fn expression_range(&self, handle: Handle<ast::Expression<'a>>) -> Range<usize> {
+        self.tu.expressions.get_span(handle).to_range().unwrap()
+    }
+
+    fn ty_range(&self, handle: Handle<ast::Type<'a>>) -> Range<usize> {
+        self.tu.types.get_span(handle).to_range().unwrap()
+    }
+
+    fn start(&self, span: crate::Span) -> usize {
+        span.to_range().unwrap().start
+    }
+
+    fn end(&self, span: crate::Span) -> usize {
+        span.to_range().unwrap().end
+    }
+}
+
+const fn binary_operator(op: crate::BinaryOperator) -> &'static str {
+    use crate::BinaryOperator as Bo;
+    match op {
+        Bo::Add => "+",
+        Bo::Subtract => "-",
+        Bo::Multiply => "*",
+        Bo::Divide => "/",
+        Bo::Modulo => "%",
+        Bo::Equal => "==",
+        Bo::NotEqual => "!=",
+        Bo::Less => "<",
+        Bo::LessEqual => "<=",
+        Bo::Greater => ">",
+        Bo::GreaterEqual => ">=",
+        Bo::And => "&",
+        Bo::ExclusiveOr => "^",
+        Bo::InclusiveOr => "|",
+        Bo::LogicalAnd => "&&",
+        Bo::LogicalOr => "||",
+        Bo::ShiftLeft => "<<",
+        Bo::ShiftRight => ">>",
+    }
+}
+
+/// The code and comments of `source`, minus what the formatter may change:
+/// whitespace, parentheses, trailing commas, the optional `:` after a switch
+/// case's selectors, and empty statements and declarations.
+fn normalize(source: &str) -> (String, Vec<&str>) {
+    let comments = lexer::comments(source)
+        .into_iter()
+        .filter_map(|span| span.to_range())
+        .collect::<Vec<_>>();
+
+    let mut chars = Vec::new();
+    let mut position = 0;
+    for comment in comments
+        .iter()
+        .cloned()
+        .chain(Some(source.len()..source.len()))
+    {
+        chars.extend(
+            source[position..comment.start]
+                .chars()
+                .filter(|c| !c.is_whitespace()),
+        );
+        position = comment.end;
+    }
+
+    let mut code = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let next = chars.get(i + 1).copied();
+        let drop = match c {
+            ',' => matches!(next, Some(')' | ']' | '}' | '>')),
+            ':' => next == Some('{'),
+            ';' => matches!(code.chars().last(), None | Some(';' | '{' | '}')),
+            _ => false,
+        };
+        if !drop {
+            code.push(c);
+        }
+    }
+    code.retain(|c| c != '(' && c != ')');
+
+    let comments = comments.into_iter().map(|range| &source[range]).collect();
+    (code, comments)
+}
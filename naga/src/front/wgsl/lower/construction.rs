@@ -4,6 +4,7 @@ use crate::front::wgsl::parse::ast;
 use crate::{Handle, Span};
 
 use crate::front::wgsl::error::Error;
+use crate::front::wgsl::lower::conversion::Leaf;
 use crate::front::wgsl::lower::{ExpressionContext, Lowerer};
 
 /// A cooked form of `ast::ConstructorType` that uses Naga types whenever
@@ -118,11 +119,17 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
     ) -> Result<Handle<crate::Expression>, Error<'source>> {
         let constructor_h = self.constructor(constructor, ctx)?;
 
-        let components = match *components {
+        let start = ctx.expression_count();
+        let mut lowered = components
+            .iter()
+            .map(|&component| self.expression(component, ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::constructor_conversions(&constructor_h, &mut lowered, start, ctx)?;
+
+        let components = match *lowered {
             [] => Components::None,
             [component] => {
-                let span = ctx.ast_expressions.get_span(component);
-                let component = self.expression(component, ctx)?;
+                let span = ctx.ast_expressions.get_span(components[0]);
                 let ty_inner = super::resolve_inner!(ctx, component);
 
                 Components::One {
@@ -131,27 +138,16 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     ty_inner,
                 }
             }
-            [component, ref rest @ ..] => {
-                let span = ctx.ast_expressions.get_span(component);
-                let component = self.expression(component, ctx)?;
-
-                let components = std::iter::once(Ok(component))
-                    .chain(
-                        rest.iter()
-                            .map(|&component| self.expression(component, ctx)),
-                    )
-                    .collect::<Result<_, _>>()?;
-                let spans = std::iter::once(span)
-                    .chain(
-                        rest.iter()
-                            .map(|&component| ctx.ast_expressions.get_span(component)),
-                    )
+            [component, ..] => {
+                let spans = components
+                    .iter()
+                    .map(|&component| ctx.ast_expressions.get_span(component))
                     .collect();
 
                 let first_component_ty_inner = super::resolve_inner!(ctx, component);
 
                 Components::Many {
-                    components,
+                    components: lowered,
                     spans,
                     first_component_ty_inner,
                 }
@@ -498,6 +494,74 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         Ok(expr)
     }
 
+    /// Apply WGSL's automatic conversions to the lowered `components` of
+    /// a constructor expression.
+    ///
+    /// Components of a typed constructor are converted to the leaf type of
+    /// the type being built, or of the corresponding struct member. Single
+    /// components that are converted with an [`As`] expression are left
+    /// alone. Components of a partial constructor are converted to their
+    /// consensus type.
+    ///
+    /// Expressions appended at or after `start` are the components' own.
+    ///
+    /// [`As`]: crate::Expression::As
+    fn constructor_conversions(
+        constructor: &Constructor<Handle<crate::Type>>,
+        components: &mut [Handle<crate::Expression>],
+        start: usize,
+        ctx: &mut ExpressionContext<'source, '_, '_>,
+    ) -> Result<(), Error<'source>> {
+        match *constructor {
+            Constructor::Type(ty) => {
+                if let crate::TypeInner::Struct { ref members, .. } = ctx.module.types[ty].inner {
+                    let member_types: Vec<_> = members.iter().map(|member| member.ty).collect();
+                    for (component, member_ty) in components.iter_mut().zip(member_types) {
+                        *component =
+                            ctx.try_automatic_conversion_for_type(*component, member_ty)?;
+                        ctx.concretize_fresh_in_place(*component, start)?;
+                    }
+                    return Ok(());
+                }
+
+                let inner = &ctx.module.types[ty].inner;
+                let Some(goal) = Leaf::of_type(inner, &ctx.module.types) else {
+                    return Ok(());
+                };
+                if let [component] = *components {
+                    let is_splat = matches!(*inner, crate::TypeInner::Vector { .. })
+                        && matches!(
+                            *super::resolve_inner!(ctx, component),
+                            crate::TypeInner::Scalar { .. }
+                        );
+                    let is_array =
+                        matches!(ctx.module.types[ty].inner, crate::TypeInner::Array { .. });
+                    if !is_splat && !is_array {
+                        return Ok(());
+                    }
+                }
+                for component in components.iter_mut() {
+                    *component = ctx.try_automatic_conversion(*component, goal)?;
+                    ctx.concretize_fresh_in_place(*component, start)?;
+                }
+            }
+            Constructor::PartialMatrix { .. } => {
+                let goal = match ctx.automatic_conversion_consensus(components)? {
+                    Some(Leaf::AbstractInt) => Leaf::AbstractFloat,
+                    Some(goal) => goal,
+                    None => return Ok(()),
+                };
+                for component in components.iter_mut() {
+                    *component = ctx.try_automatic_conversion(*component, goal)?;
+                }
+            }
+            Constructor::PartialVector { .. } | Constructor::PartialArray => {
+                ctx.convert_to_consensus(components)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Build a [`Constructor`] for a WGSL construction expression.
     ///
     /// If `constructor` conveys enough information to determine which Naga [`Type`]
@@ -0,0 +1,538 @@
+//! WGSL's automatic conversions for abstract types.
+//!
+//! The WGSL front end represents values of the abstract types
+//! `AbstractInt` and `AbstractFloat` with [`Literal::AbstractInt`] and
+//! [`Literal::AbstractFloat`] leaves. Everywhere else in Naga, an abstract
+//! value is treated as if it had the type it would be given by default,
+//! `i32` or `f32`, so the types we resolve for abstract expressions, and
+//! the types of the `Compose` expressions that build them, are those
+//! default types.
+//!
+//! Converting an abstract value to its default type never needs new
+//! expressions: we only check that its leaves are representable, and
+//! [`Lowerer::lower`] replaces any abstract literals left in the module
+//! with their concrete equivalents once lowering is done. Conversions to
+//! other types go through the [`ConstantEvaluator`].
+//!
+//! [`Literal::AbstractInt`]: crate::Literal::AbstractInt
+//! [`Literal::AbstractFloat`]: crate::Literal::AbstractFloat
+//! [`Lowerer::lower`]: super::Lowerer::lower
+//! [`ConstantEvaluator`]: crate::proc::ConstantEvaluator
+
+use crate::front::wgsl::error::Error;
+use crate::front::wgsl::lower::{ExpressionContext, ExpressionContextType};
+use crate::proc::ConstantEvaluatorError;
+use crate::{Arena, Handle, Literal, ScalarKind};
+
+/// The scalar type of the leaves of a value: its own type if it is a
+/// scalar, or its components' if it is a vector, matrix or array.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Leaf {
+    AbstractInt,
+    AbstractFloat,
+    Concrete {
+        kind: ScalarKind,
+        width: crate::Bytes,
+    },
+}
+
+impl Leaf {
+    pub const U32: Self = Self::Concrete {
+        kind: ScalarKind::Uint,
+        width: 4,
+    };
+
+    /// Return the leaf scalar type of values of type `inner`, if it has one.
+    pub fn of_type(
+        inner: &crate::TypeInner,
+        types: &crate::UniqueArena<crate::Type>,
+    ) -> Option<Self> {
+        match *inner {
+            crate::TypeInner::Scalar { kind, width }
+            | crate::TypeInner::Vector { kind, width, .. } => Some(Self::Concrete { kind, width }),
+            crate::TypeInner::Matrix { width, .. } => Some(Self::Concrete {
+                kind: ScalarKind::Float,
+                width,
+            }),
+            crate::TypeInner::Array { base, .. } => Self::of_type(&types[base].inner, types),
+            _ => None,
+        }
+    }
+
+    /// The concrete type an abstract value of this leaf type is given by
+    /// default.
+    const fn concretize(self) -> Self {
+        match self {
+            Self::AbstractInt => Self::Concrete {
+                kind: ScalarKind::Sint,
+                width: 4,
+            },
+            Self::AbstractFloat => Self::Concrete {
+                kind: ScalarKind::Float,
+                width: 4,
+            },
+            concrete @ Self::Concrete { .. } => concrete,
+        }
+    }
+}
+
+/// Return the leaf type of `expr` if it is abstract.
+///
+/// Abstract values are always built from literals, so we just follow
+/// `expr` down to its first leaf. Constants are abstract if their
+/// initializers are.
+fn abstract_leaf(
+    expr: Handle<crate::Expression>,
+    expressions: &Arena<crate::Expression>,
+    module: &crate::Module,
+) -> Option<Leaf> {
+    match expressions[expr] {
+        crate::Expression::Literal(Literal::AbstractInt(_)) => Some(Leaf::AbstractInt),
+        crate::Expression::Literal(Literal::AbstractFloat(_)) => Some(Leaf::AbstractFloat),
+        crate::Expression::Compose { ty, ref components } => {
+            if let crate::TypeInner::Struct { .. } = module.types[ty].inner {
+                return None;
+            }
+            abstract_leaf(*components.first()?, expressions, module)
+        }
+        crate::Expression::Splat { value, .. } => abstract_leaf(value, expressions, module),
+        crate::Expression::Constant(c) => {
+            abstract_leaf(module.constants[c].init, &module.const_expressions, module)
+        }
+        _ => None,
+    }
+}
+
+/// Check that every abstract leaf of `expr` is representable in its
+/// default concrete type.
+fn check_concretizable(
+    expr: Handle<crate::Expression>,
+    expressions: &Arena<crate::Expression>,
+    module: &crate::Module,
+) -> Result<(), ConstantEvaluatorError> {
+    match expressions[expr] {
+        crate::Expression::Literal(Literal::AbstractInt(value))
+            if i32::try_from(value).is_err() =>
+        {
+            return Err(ConstantEvaluatorError::NotRepresentable {
+                value: value.to_string(),
+                to: "i32",
+            });
+        }
+        crate::Expression::Literal(Literal::AbstractFloat(value))
+            if !(value as f32).is_finite() =>
+        {
+            return Err(ConstantEvaluatorError::NotRepresentable {
+                value: format!("{value:?}"),
+                to: "f32",
+            });
+        }
+        crate::Expression::Compose { ref components, .. } => {
+            for &component in components {
+                check_concretizable(component, expressions, module)?;
+            }
+        }
+        crate::Expression::Splat { value, .. } => {
+            check_concretizable(value, expressions, module)?;
+        }
+        crate::Expression::Constant(c) => {
+            check_concretizable(module.constants[c].init, &module.const_expressions, module)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace every abstract literal in `expressions` with its default
+/// concrete equivalent.
+///
+/// Values that aren't representable can't be used by anything, since
+/// every use of an abstract value checks that, so they become zero.
+pub fn concretize_literals(expressions: &mut Arena<crate::Expression>) {
+    for (_, expr) in expressions.iter_mut() {
+        if let crate::Expression::Literal(ref mut literal) = *expr {
+            match *literal {
+                Literal::AbstractInt(value) => {
+                    *literal = Literal::I32(i32::try_from(value).unwrap_or(0));
+                }
+                Literal::AbstractFloat(value) => {
+                    let value = value as f32;
+                    *literal = Literal::F32(if value.is_finite() { value } else { 0.0 });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'source> ExpressionContext<'source, '_, '_> {
+    /// Return the number of expressions in this context's arena.
+    pub fn expression_count(&self) -> usize {
+        match self.expr_type {
+            ExpressionContextType::Runtime(ref rctx) => rctx.function.expressions.len(),
+            ExpressionContextType::Constant => self.module.const_expressions.len(),
+        }
+    }
+
+    /// Return the leaf type of `expr` if it is an abstract constant
+    /// expression.
+    pub fn abstract_leaf(&self, expr: Handle<crate::Expression>) -> Option<Leaf> {
+        match self.expr_type {
+            ExpressionContextType::Runtime(ref rctx) => {
+                if !rctx.expression_constness.is_const(expr) {
+                    return None;
+                }
+                abstract_leaf(expr, &rctx.function.expressions, self.module)
+            }
+            ExpressionContextType::Constant => {
+                abstract_leaf(expr, &self.module.const_expressions, self.module)
+            }
+        }
+    }
+
+    /// Check that `expr`'s abstract leaves, if it has any, are
+    /// representable in their default concrete types.
+    pub fn check_concretizable(
+        &self,
+        expr: Handle<crate::Expression>,
+    ) -> Result<(), Error<'source>> {
+        let expressions = match self.expr_type {
+            ExpressionContextType::Runtime(ref rctx) => {
+                if !rctx.expression_constness.is_const(expr) {
+                    return Ok(());
+                }
+                &rctx.function.expressions
+            }
+            ExpressionContextType::Constant => &self.module.const_expressions,
+        };
+        check_concretizable(expr, expressions, self.module)
+            .map_err(|err| Error::ConstantEvaluatorError(err, self.get_expression_span(expr)))
+    }
+
+    /// Apply WGSL's automatic conversions to `expr`, to give it the leaf
+    /// type `goal`.
+    ///
+    /// If `expr` is concrete, or WGSL doesn't convert its type to `goal`
+    /// automatically, return it unchanged, and let the caller's type
+    /// checks, or the validator, report the mismatch.
+    pub fn try_automatic_conversion(
+        &mut self,
+        expr: Handle<crate::Expression>,
+        goal: Leaf,
+    ) -> Result<Handle<crate::Expression>, Error<'source>> {
+        let Some(leaf) = self.abstract_leaf(expr) else {
+            return Ok(expr);
+        };
+        let span = self.get_expression_span(expr);
+        match (leaf, goal) {
+            (Leaf::AbstractInt, Leaf::AbstractFloat) => self
+                .as_const_evaluator()
+                .cast_abstract_int_to_float(expr, span)
+                .map_err(|err| Error::ConstantEvaluatorError(err, span)),
+            (
+                Leaf::AbstractInt,
+                Leaf::Concrete {
+                    kind: ScalarKind::Sint | ScalarKind::Uint | ScalarKind::Float,
+                    ..
+                },
+            )
+            | (
+                Leaf::AbstractFloat,
+                Leaf::Concrete {
+                    kind: ScalarKind::Float,
+                    ..
+                },
+            ) => {
+                if leaf.concretize() == goal {
+                    self.check_concretizable(expr)?;
+                    return Ok(expr);
+                }
+                let Leaf::Concrete { kind, width } = goal else {
+                    unreachable!()
+                };
+                self.as_const_evaluator()
+                    .cast_array(expr, kind, width, span)
+                    .map_err(|err| Error::ConstantEvaluatorError(err, span))
+            }
+            _ => Ok(expr),
+        }
+    }
+
+    /// Apply WGSL's automatic conversions to `expr`, to give it the type
+    /// `ty`, if `ty` has a leaf type.
+    pub fn try_automatic_conversion_for_type(
+        &mut self,
+        expr: Handle<crate::Expression>,
+        ty: Handle<crate::Type>,
+    ) -> Result<Handle<crate::Expression>, Error<'source>> {
+        match Leaf::of_type(&self.module.types[ty].inner, &self.module.types) {
+            Some(goal) => self.try_automatic_conversion(expr, goal),
+            None => Ok(expr),
+        }
+    }
+
+    /// Find the leaf type that all of `exprs` can be converted to.
+    ///
+    /// A concrete operand's type wins; otherwise, the result is
+    /// `AbstractFloat` if any operand is. Return `None` if `exprs` have
+    /// different concrete leaf types, or a type with no leaves.
+    pub fn automatic_conversion_consensus(
+        &mut self,
+        exprs: &[Handle<crate::Expression>],
+    ) -> Result<Option<Leaf>, Error<'source>> {
+        let mut consensus = None;
+        for &expr in exprs {
+            let leaf = match self.abstract_leaf(expr) {
+                Some(leaf) => leaf,
+                None => {
+                    let inner = super::resolve_inner!(self, expr);
+                    match Leaf::of_type(inner, &self.module.types) {
+                        Some(leaf) => leaf,
+                        None => return Ok(None),
+                    }
+                }
+            };
+            consensus = Some(match (consensus, leaf) {
+                (None, leaf) => leaf,
+                (Some(a), b) if a == b => a,
+                (Some(Leaf::AbstractInt), Leaf::AbstractFloat)
+                | (Some(Leaf::AbstractFloat), Leaf::AbstractInt) => Leaf::AbstractFloat,
+                (Some(Leaf::AbstractInt | Leaf::AbstractFloat), concrete)
+                | (Some(concrete), Leaf::AbstractInt | Leaf::AbstractFloat) => concrete,
+                (Some(Leaf::Concrete { .. }), Leaf::Concrete { .. }) => return Ok(None),
+            });
+        }
+        Ok(consensus)
+    }
+
+    /// Convert all of `exprs` to their [consensus] leaf type, if they have
+    /// one.
+    ///
+    /// [consensus]: Self::automatic_conversion_consensus
+    pub fn convert_to_consensus(
+        &mut self,
+        exprs: &mut [Handle<crate::Expression>],
+    ) -> Result<(), Error<'source>> {
+        if let Some(goal) = self.automatic_conversion_consensus(exprs)? {
+            for expr in exprs {
+                *expr = self.try_automatic_conversion(*expr, goal)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply WGSL's automatic conversions to `value`, to give it the type
+    /// that `pointer` points to.
+    ///
+    /// For pointers to atomics, convert to the atomic's scalar type.
+    pub fn try_automatic_conversion_for_pointee(
+        &mut self,
+        value: Handle<crate::Expression>,
+        pointer: Handle<crate::Expression>,
+    ) -> Result<Handle<crate::Expression>, Error<'source>> {
+        let goal = match *super::resolve_inner!(self, pointer) {
+            crate::TypeInner::Pointer { base, .. } => match self.module.types[base].inner {
+                crate::TypeInner::Atomic { kind, width } => Some(Leaf::Concrete { kind, width }),
+                ref inner => Leaf::of_type(inner, &self.module.types),
+            },
+            crate::TypeInner::ValuePointer { kind, width, .. } => {
+                Some(Leaf::Concrete { kind, width })
+            }
+            _ => None,
+        };
+        match goal {
+            Some(goal) => self.try_automatic_conversion(value, goal),
+            None => Ok(value),
+        }
+    }
+
+    /// Apply WGSL's automatic conversions to the operands of a binary
+    /// operator.
+    ///
+    /// The right operand of a shift is converted to `u32`; otherwise,
+    /// both operands are converted to their consensus type.
+    pub fn binary_op_conversions(
+        &mut self,
+        op: crate::BinaryOperator,
+        left: &mut Handle<crate::Expression>,
+        right: &mut Handle<crate::Expression>,
+    ) -> Result<(), Error<'source>> {
+        match op {
+            crate::BinaryOperator::ShiftLeft | crate::BinaryOperator::ShiftRight => {
+                *left = self.concretize(*left)?;
+                *right = self.try_automatic_conversion(*right, Leaf::U32)?;
+            }
+            _ => {
+                let mut operands = [*left, *right];
+                self.convert_to_consensus(&mut operands)?;
+                [*left, *right] = operands;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply WGSL's automatic conversions to the arguments of the math
+    /// function `fun`.
+    pub fn math_function_conversions(
+        &mut self,
+        fun: crate::MathFunction,
+        arg: &mut Handle<crate::Expression>,
+        arg1: &mut Option<Handle<crate::Expression>>,
+        arg2: &mut Option<Handle<crate::Expression>>,
+        arg3: &mut Option<Handle<crate::Expression>>,
+    ) -> Result<(), Error<'source>> {
+        use crate::MathFunction as Mf;
+
+        let to_u32 = |this: &mut Self,
+                      expr: &mut Option<Handle<crate::Expression>>|
+         -> Result<(), Error<'source>> {
+            if let Some(ref mut expr) = *expr {
+                *expr = this.try_automatic_conversion(*expr, Leaf::U32)?;
+            }
+            Ok(())
+        };
+
+        match fun {
+            Mf::Ldexp => {
+                *arg = self.concretize(*arg)?;
+                if let Some(ref mut exponent) = *arg1 {
+                    *exponent = self.concretize(*exponent)?;
+                }
+            }
+            Mf::ExtractBits => {
+                *arg = self.concretize(*arg)?;
+                to_u32(self, arg1)?;
+                to_u32(self, arg2)?;
+            }
+            Mf::InsertBits => {
+                if let Some(ref mut newbits) = *arg1 {
+                    let mut operands = [*arg, *newbits];
+                    self.convert_to_consensus(&mut operands)?;
+                    [*arg, *newbits] = operands;
+                }
+                to_u32(self, arg2)?;
+                to_u32(self, arg3)?;
+            }
+            Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                *arg = self.try_automatic_conversion(*arg, Leaf::U32)?;
+                to_u32(self, arg1)?;
+            }
+            Mf::Pack4xI8 | Mf::Pack4xI8Clamp | Mf::QuantizeToF16 => {
+                *arg = self.concretize(*arg)?;
+            }
+            Mf::Pack4xU8 | Mf::Pack4xU8Clamp => {
+                *arg = self.try_automatic_conversion(*arg, Leaf::U32)?;
+            }
+            Mf::Unpack2x16float
+            | Mf::Unpack2x16snorm
+            | Mf::Unpack2x16unorm
+            | Mf::Unpack4x8snorm
+            | Mf::Unpack4x8unorm
+            | Mf::Unpack4xI8
+            | Mf::Unpack4xU8 => {
+                *arg = self.try_automatic_conversion(*arg, Leaf::U32)?;
+            }
+            _ => {
+                let mut operands = Vec::with_capacity(4);
+                operands.push(*arg);
+                operands.extend(arg1.iter().chain(arg2.iter()).chain(arg3.iter()));
+                self.convert_to_consensus(&mut operands)?;
+                let mut operands = operands.into_iter();
+                *arg = operands.next().unwrap();
+                for slot in [arg1, arg2, arg3] {
+                    if let Some(ref mut expr) = *slot {
+                        *expr = operands.next().unwrap();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Make the initializer `init` of an abstract module-scope constant
+    /// available in this context's expression arena.
+    pub fn copy_const_expression(
+        &mut self,
+        init: Handle<crate::Expression>,
+    ) -> Result<Handle<crate::Expression>, Error<'source>> {
+        let span = self.module.const_expressions.get_span(init);
+        self.as_const_evaluator()
+            .copy_const_expression(init)
+            .map_err(|err| Error::ConstantEvaluatorError(err, span))
+    }
+
+    /// Give `expr` its default concrete type, if it is abstract.
+    pub fn concretize(
+        &mut self,
+        expr: Handle<crate::Expression>,
+    ) -> Result<Handle<crate::Expression>, Error<'source>> {
+        match self.abstract_leaf(expr) {
+            Some(leaf) => self.try_automatic_conversion(expr, leaf.concretize()),
+            None => Ok(expr),
+        }
+    }
+
+    /// Give `expr` its default concrete type in place, if it is abstract
+    /// and all its subexpressions were appended at or after `start`.
+    ///
+    /// This is for type constructors like `vec2<i32>(1, 2)`, whose
+    /// components keep their abstract leaves when converted to the
+    /// default type. Nothing else can refer to expressions we appended
+    /// while lowering the components, so we can safely make them concrete
+    /// in place; otherwise, later conversions would treat the constructed
+    /// value as abstract.
+    pub fn concretize_fresh_in_place(
+        &mut self,
+        expr: Handle<crate::Expression>,
+        start: usize,
+    ) -> Result<(), Error<'source>> {
+        if self.abstract_leaf(expr).is_none() {
+            return Ok(());
+        }
+        self.check_concretizable(expr)?;
+        let expressions = match self.expr_type {
+            ExpressionContextType::Runtime(ref mut rctx) => &mut rctx.function.expressions,
+            ExpressionContextType::Constant => &mut self.module.const_expressions,
+        };
+        let mut leaves = Vec::new();
+        if collect_fresh_literals(expr, expressions, start, &mut leaves) {
+            for leaf in leaves {
+                if let crate::Expression::Literal(ref mut literal) = expressions[leaf] {
+                    *literal = match *literal {
+                        Literal::AbstractInt(value) => Literal::I32(value as i32),
+                        Literal::AbstractFloat(value) => Literal::F32(value as f32),
+                        other => other,
+                    };
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Add the literals `expr` is built from to `leaves`, and return true if
+/// `expr` and all its subexpressions were appended at or after `start`.
+fn collect_fresh_literals(
+    expr: Handle<crate::Expression>,
+    expressions: &Arena<crate::Expression>,
+    start: usize,
+    leaves: &mut Vec<Handle<crate::Expression>>,
+) -> bool {
+    if expr.index() < start {
+        return false;
+    }
+    match expressions[expr] {
+        crate::Expression::Literal(_) => {
+            leaves.push(expr);
+            true
+        }
+        crate::Expression::ZeroValue(_) => true,
+        crate::Expression::Compose { ref components, .. } => components
+            .iter()
+            .all(|&component| collect_fresh_literals(component, expressions, start, leaves)),
+        crate::Expression::Splat { value, .. } => {
+            collect_fresh_literals(value, expressions, start, leaves)
+        }
+        _ => false,
+    }
+}
@@ -12,6 +12,9 @@ use crate::proc::{
 use crate::{Arena, FastHashMap, FastIndexMap, Handle, Span};
 
 mod construction;
+mod conversion;
+
+use conversion::Leaf;
 
 /// Resolves the inner type of a given expression.
 ///
@@ -357,7 +360,31 @@ impl<'source, 'temp, 'out> ExpressionContext<'source, 'temp, 'out> {
             // `expr` is not a constant expression. This is fine as
             // long as we're not building `Module::const_expressions`.
             Err(err) => match self.expr_type {
-                ExpressionContextType::Runtime(ref mut rctx) => {
+                ExpressionContextType::Runtime(_) => {
+                    // Any abstract operands of a runtime expression take
+                    // their default types. If evaluating `expr` failed
+                    // because of their values, that's an error even at
+                    // runtime.
+                    let mut operands = Vec::new();
+                    crate::proc::expression_operands(&expr, |operand| operands.push(operand));
+                    for operand in operands {
+                        if self.abstract_leaf(operand).is_none() {
+                            continue;
+                        }
+                        if let crate::proc::ConstantEvaluatorError::Overflow(_)
+                        | crate::proc::ConstantEvaluatorError::DivisionByZero
+                        | crate::proc::ConstantEvaluatorError::RemainderByZero
+                        | crate::proc::ConstantEvaluatorError::NotRepresentable { .. }
+                        | crate::proc::ConstantEvaluatorError::Literal(_) = err
+                        {
+                            return Err(Error::ConstantEvaluatorError(err, span));
+                        }
+                        self.check_concretizable(operand)?;
+                    }
+
+                    let ExpressionContextType::Runtime(ref mut rctx) = self.expr_type else {
+                        unreachable!()
+                    };
                     Ok(rctx.function.expressions.append(expr, span))
                 }
                 ExpressionContextType::Constant => Err(Error::ConstantEvaluatorError(err, span)),
@@ -444,6 +471,80 @@ impl<'source, 'temp, 'out> ExpressionContext<'source, 'temp, 'out> {
         }
     }
 
+    /// Clamp `coordinate` for `textureSampleBaseClampToEdge` on a
+    /// `texture_2d<f32>`.
+    ///
+    /// Produces `clamp(coordinate, half_texel, 1.0 - half_texel)`, where
+    /// `half_texel` is `0.5 / vec2<f32>(textureDimensions(image, 0))`.
+    fn clamp_to_edge(
+        &mut self,
+        image: Handle<crate::Expression>,
+        coordinate: Handle<crate::Expression>,
+        span: Span,
+    ) -> Result<Handle<crate::Expression>, Error<'source>> {
+        let zero =
+            self.append_expression(crate::Expression::Literal(crate::Literal::U32(0)), span)?;
+        let size = self.append_expression(
+            crate::Expression::ImageQuery {
+                image,
+                query: crate::ImageQuery::Size { level: Some(zero) },
+            },
+            span,
+        )?;
+        let size = self.append_expression(
+            crate::Expression::As {
+                expr: size,
+                kind: crate::ScalarKind::Float,
+                convert: Some(4),
+            },
+            span,
+        )?;
+        let half =
+            self.append_expression(crate::Expression::Literal(crate::Literal::F32(0.5)), span)?;
+        let half = self.append_expression(
+            crate::Expression::Splat {
+                size: crate::VectorSize::Bi,
+                value: half,
+            },
+            span,
+        )?;
+        let half_texel = self.append_expression(
+            crate::Expression::Binary {
+                op: crate::BinaryOperator::Divide,
+                left: half,
+                right: size,
+            },
+            span,
+        )?;
+        let one =
+            self.append_expression(crate::Expression::Literal(crate::Literal::F32(1.0)), span)?;
+        let one = self.append_expression(
+            crate::Expression::Splat {
+                size: crate::VectorSize::Bi,
+                value: one,
+            },
+            span,
+        )?;
+        let max = self.append_expression(
+            crate::Expression::Binary {
+                op: crate::BinaryOperator::Subtract,
+                left: one,
+                right: half_texel,
+            },
+            span,
+        )?;
+        self.append_expression(
+            crate::Expression::Math {
+                fun: crate::MathFunction::Clamp,
+                arg: coordinate,
+                arg1: Some(half_texel),
+                arg2: Some(max),
+                arg3: None,
+            },
+            span,
+        )
+    }
+
     /// Determine the type of `handle`, and add it to the module's arena.
     ///
     /// If you just need a `TypeInner` for `handle`'s type, use the
@@ -797,6 +898,15 @@ enum LoweredGlobalDecl {
     Function(Handle<crate::Function>),
     Var(Handle<crate::GlobalVariable>),
     Const(Handle<crate::Constant>),
+    /// A `const` declaration whose abstract value isn't representable in its
+    /// default concrete type, so it can't be a Naga [`Constant`].
+    ///
+    /// This holds the constant's value in [`Module::const_expressions`],
+    /// which we copy wherever the constant is used.
+    ///
+    /// [`Constant`]: crate::Constant
+    /// [`Module::const_expressions`]: crate::Module::const_expressions
+    AbstractConst(Handle<crate::Expression>),
     Type(Handle<crate::Type>),
     EntryPoint,
 }
@@ -811,7 +921,7 @@ enum Texture {
     SampleCompareLevel,
     SampleGrad,
     SampleLevel,
-    // SampleBaseClampToEdge,
+    SampleBaseClampToEdge,
 }
 
 impl Texture {
@@ -826,7 +936,7 @@ impl Texture {
             "textureSampleCompareLevel" => Self::SampleCompareLevel,
             "textureSampleGrad" => Self::SampleGrad,
             "textureSampleLevel" => Self::SampleLevel,
-            // "textureSampleBaseClampToEdge" => Some(Self::SampleBaseClampToEdge),
+            "textureSampleBaseClampToEdge" => Self::SampleBaseClampToEdge,
             _ => return None,
         })
     }
@@ -842,7 +952,7 @@ impl Texture {
             Self::SampleCompareLevel => 5,
             Self::SampleGrad => 6,
             Self::SampleLevel => 5,
-            // Self::SampleBaseClampToEdge => 3,
+            Self::SampleBaseClampToEdge => 3,
         }
     }
 }
@@ -888,7 +998,11 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
                     let init = v
                         .init
-                        .map(|init| self.expression(init, &mut ctx.as_const()))
+                        .map(|init| {
+                            let mut ectx = ctx.as_const();
+                            let init = self.expression(init, &mut ectx)?;
+                            ectx.try_automatic_conversion_for_type(init, ty)
+                        })
                         .transpose()?;
 
                     let binding = if let Some(ref binding) = v.binding {
@@ -915,14 +1029,29 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                         .insert(v.name.name, LoweredGlobalDecl::Var(handle));
                 }
                 ast::GlobalDeclKind::Const(ref c) => {
-                    let mut ectx = ctx.as_const();
-                    let init = self.expression(c.init, &mut ectx)?;
-                    let inferred_type = ectx.register_type(init)?;
-
                     let explicit_ty =
                         c.ty.map(|ty| self.resolve_ast_type(ty, &mut ctx))
                             .transpose()?;
 
+                    let mut ectx = ctx.as_const();
+                    let mut init = self.expression(c.init, &mut ectx)?;
+                    match explicit_ty {
+                        Some(explicit) => {
+                            init = ectx.try_automatic_conversion_for_type(init, explicit)?;
+                        }
+                        // A `const` without a type keeps its abstract value. If
+                        // that isn't representable in the default type we give
+                        // abstract values in Naga IR, we can't make a Naga
+                        // `Constant` of it.
+                        None if ectx.check_concretizable(init).is_err() => {
+                            ctx.globals
+                                .insert(c.name.name, LoweredGlobalDecl::AbstractConst(init));
+                            continue;
+                        }
+                        None => {}
+                    }
+                    let inferred_type = ectx.register_type(init)?;
+
                     if let Some(explicit) = explicit_ty {
                         if explicit != inferred_type {
                             let ty = &ctx.module.types[explicit];
@@ -971,6 +1100,8 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             }
         }
 
+        conversion::concretize_literals(&mut module.const_expressions);
+
         Ok(module)
     }
 
@@ -1040,6 +1171,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         };
         let mut body = self.block(&f.body, false, &mut stmt_ctx)?;
         ensure_block_returns(&mut body);
+        conversion::concretize_literals(&mut function.expressions);
 
         function.body = body;
         function.named_expressions = named_expressions
@@ -1107,8 +1239,16 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     let mut emitter = Emitter::default();
                     emitter.start(&ctx.function.expressions);
 
-                    let value =
-                        self.expression(l.init, &mut ctx.as_expression(block, &mut emitter))?;
+                    let explicit_ty =
+                        l.ty.map(|ty| self.resolve_ast_type(ty, &mut ctx.as_global()))
+                            .transpose()?;
+
+                    let mut ectx = ctx.as_expression(block, &mut emitter);
+                    let value = self.expression(l.init, &mut ectx)?;
+                    let value = match explicit_ty {
+                        Some(ty) => ectx.try_automatic_conversion_for_type(value, ty)?,
+                        None => ectx.concretize(value)?,
+                    };
 
                     // The WGSL spec says that any expression that refers to a
                     // `let`-bound variable is not a const expression. This
@@ -1117,10 +1257,6 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     // optimization.
                     ctx.expression_constness.force_non_const(value);
 
-                    let explicit_ty =
-                        l.ty.map(|ty| self.resolve_ast_type(ty, &mut ctx.as_global()))
-                            .transpose()?;
-
                     if let Some(ty) = explicit_ty {
                         let mut ctx = ctx.as_expression(block, &mut emitter);
                         let init_ty = ctx.register_type(value)?;
@@ -1147,17 +1283,22 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     let mut emitter = Emitter::default();
                     emitter.start(&ctx.function.expressions);
 
-                    let initializer = match v.init {
-                        Some(init) => Some(
-                            self.expression(init, &mut ctx.as_expression(block, &mut emitter))?,
-                        ),
-                        None => None,
-                    };
-
                     let explicit_ty =
                         v.ty.map(|ty| self.resolve_ast_type(ty, &mut ctx.as_global()))
                             .transpose()?;
 
+                    let initializer = match v.init {
+                        Some(init) => {
+                            let mut ectx = ctx.as_expression(block, &mut emitter);
+                            let init = self.expression(init, &mut ectx)?;
+                            Some(match explicit_ty {
+                                Some(ty) => ectx.try_automatic_conversion_for_type(init, ty)?,
+                                None => ectx.concretize(init)?,
+                            })
+                        }
+                        None => None,
+                    };
+
                     let ty = match (explicit_ty, initializer) {
                         (Some(explicit), Some(initializer)) => {
                             let mut ctx = ctx.as_expression(block, &mut emitter);
@@ -1258,6 +1399,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
                 let mut ectx = ctx.as_expression(block, &mut emitter);
                 let selector = self.expression(selector, &mut ectx)?;
+                let selector = ectx.concretize(selector)?;
 
                 let uint =
                     resolve_inner!(ectx, selector).scalar_kind() == Some(crate::ScalarKind::Uint);
@@ -1270,12 +1412,28 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                             value: match case.value {
                                 ast::SwitchValue::Expr(expr) => {
                                     let span = ctx.ast_expressions.get_span(expr);
-                                    let expr =
-                                        self.expression(expr, &mut ctx.as_global().as_const())?;
+                                    let mut global = ctx.as_global();
+                                    let mut ectx = global.as_const();
+                                    let expr = self.expression(expr, &mut ectx)?;
+                                    let expr = ectx.try_automatic_conversion(
+                                        expr,
+                                        Leaf::Concrete {
+                                            kind: if uint {
+                                                crate::ScalarKind::Uint
+                                            } else {
+                                                crate::ScalarKind::Sint
+                                            },
+                                            width: 4,
+                                        },
+                                    )?;
                                     match ctx.module.to_ctx().eval_expr_to_literal(expr) {
                                         Some(crate::Literal::I32(value)) if !uint => {
                                             crate::SwitchValue::I32(value)
                                         }
+                                        // We've checked that this fits in an `i32`.
+                                        Some(crate::Literal::AbstractInt(value)) if !uint => {
+                                            crate::SwitchValue::I32(value as i32)
+                                        }
                                         Some(crate::Literal::U32(value)) if uint => {
                                             crate::SwitchValue::U32(value)
                                         }
@@ -1315,14 +1473,52 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     break_if,
                 }
             }
+            ast::StatementKind::While {
+                condition,
+                ref body,
+            } => crate::Statement::Loop {
+                body: self.loop_body(Some(condition), body, ctx)?,
+                continuing: crate::Block::default(),
+                break_if: None,
+            },
+            ast::StatementKind::For {
+                ref init,
+                test,
+                ref update,
+                ref body,
+            } => {
+                if let Some(ref init) = *init {
+                    self.statement(init, block, is_inside_loop, ctx)?;
+                }
+
+                let body = self.loop_body(test, body, ctx)?;
+                let mut continuing = crate::Block::default();
+                if let Some(ref update) = *update {
+                    self.statement(update, &mut continuing, true, ctx)?;
+                }
+
+                crate::Statement::Loop {
+                    body,
+                    continuing,
+                    break_if: None,
+                }
+            }
             ast::StatementKind::Break => crate::Statement::Break,
             ast::StatementKind::Continue => crate::Statement::Continue,
             ast::StatementKind::Return { value } => {
                 let mut emitter = Emitter::default();
                 emitter.start(&ctx.function.expressions);
 
+                let result_ty = ctx.function.result.as_ref().map(|result| result.ty);
                 let value = value
-                    .map(|expr| self.expression(expr, &mut ctx.as_expression(block, &mut emitter)))
+                    .map(|expr| {
+                        let mut ectx = ctx.as_expression(block, &mut emitter);
+                        let value = self.expression(expr, &mut ectx)?;
+                        match result_ty {
+                            Some(ty) => ectx.try_automatic_conversion_for_type(value, ty),
+                            None => ectx.concretize(value),
+                        }
+                    })
                     .transpose()?;
                 block.extend(emitter.finish(&ctx.function.expressions));
 
@@ -1375,6 +1571,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     Some(op) => {
                         let mut ctx = ctx.as_expression(block, &mut emitter);
                         let mut left = ctx.apply_load_rule(target)?;
+                        ctx.binary_op_conversions(op, &mut left, &mut value)?;
                         ctx.binary_op_splat(op, &mut left, &mut value)?;
                         ctx.append_expression(
                             crate::Expression::Binary {
@@ -1385,7 +1582,9 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                             stmt.span,
                         )?
                     }
-                    None => value,
+                    None => ctx
+                        .as_expression(block, &mut emitter)
+                        .try_automatic_conversion_for_pointee(value, target_handle)?,
                 };
                 block.extend(emitter.finish(&ctx.function.expressions));
 
@@ -1469,6 +1668,42 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         Ok(())
     }
 
+    /// Lower the body of a `while` or `for` loop, which breaks out of the
+    /// loop first thing unless `condition` holds.
+    fn loop_body(
+        &mut self,
+        condition: Option<Handle<ast::Expression<'source>>>,
+        body: &ast::Block<'source>,
+        ctx: &mut StatementContext<'source, '_, '_>,
+    ) -> Result<crate::Block, Error<'source>> {
+        let mut block = crate::Block::default();
+
+        if let Some(condition) = condition {
+            let span = ctx.ast_expressions.get_span(condition);
+            let mut emitter = Emitter::default();
+            emitter.start(&ctx.function.expressions);
+            let condition =
+                self.expression(condition, &mut ctx.as_expression(&mut block, &mut emitter))?;
+            block.extend(emitter.finish(&ctx.function.expressions));
+
+            let mut reject = crate::Block::default();
+            reject.push(crate::Statement::Break, span);
+            block.push(
+                crate::Statement::If {
+                    condition,
+                    accept: crate::Block::default(),
+                    reject,
+                },
+                span,
+            );
+        }
+
+        let inner = self.block(body, true, ctx)?;
+        block.push(crate::Statement::Block(inner), body.span);
+
+        Ok(block)
+    }
+
     /// Lower `expr` and apply the Load Rule if possible.
     fn expression(
         &mut self,
@@ -1493,8 +1728,11 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     ast::Literal::Number(Number::F32(f)) => crate::Literal::F32(f),
                     ast::Literal::Number(Number::I32(i)) => crate::Literal::I32(i),
                     ast::Literal::Number(Number::U32(u)) => crate::Literal::U32(u),
-                    ast::Literal::Number(_) => {
-                        unreachable!("got abstract numeric type when not expected");
+                    ast::Literal::Number(Number::I64(i)) => crate::Literal::I64(i),
+                    ast::Literal::Number(Number::U64(u)) => crate::Literal::U64(u),
+                    ast::Literal::Number(Number::AbstractInt(i)) => crate::Literal::AbstractInt(i),
+                    ast::Literal::Number(Number::AbstractFloat(f)) => {
+                        crate::Literal::AbstractFloat(f)
                     }
                     ast::Literal::Bool(b) => crate::Literal::Bool(b),
                 };
@@ -1521,6 +1759,9 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     LoweredGlobalDecl::Const(handle) => {
                         Typed::Plain(crate::Expression::Constant(handle))
                     }
+                    LoweredGlobalDecl::AbstractConst(init) => {
+                        return Ok(Typed::Plain(ctx.copy_const_expression(init)?));
+                    }
                     _ => {
                         return Err(Error::Unexpected(span, ExpectedToken::Variable));
                     }
@@ -1568,6 +1809,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 // Load both operands.
                 let mut left = self.expression(left, ctx)?;
                 let mut right = self.expression(right, ctx)?;
+                ctx.binary_op_conversions(op, &mut left, &mut right)?;
                 ctx.binary_op_splat(op, &mut left, &mut right)?;
                 Typed::Plain(crate::Expression::Binary { op, left, right })
             }
@@ -1739,14 +1981,23 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 )?;
                 Ok(Some(handle))
             }
-            Some(&LoweredGlobalDecl::Const(_) | &LoweredGlobalDecl::Var(_)) => {
-                Err(Error::Unexpected(function.span, ExpectedToken::Function))
-            }
+            Some(
+                &LoweredGlobalDecl::Const(_)
+                | &LoweredGlobalDecl::AbstractConst(_)
+                | &LoweredGlobalDecl::Var(_),
+            ) => Err(Error::Unexpected(function.span, ExpectedToken::Function)),
             Some(&LoweredGlobalDecl::EntryPoint) => Err(Error::CalledEntryPoint(function.span)),
             Some(&LoweredGlobalDecl::Function(function)) => {
                 let arguments = arguments
                     .iter()
-                    .map(|&arg| self.expression(arg, ctx))
+                    .enumerate()
+                    .map(|(i, &arg)| {
+                        let arg = self.expression(arg, ctx)?;
+                        match ctx.module.functions[function].arguments.get(i) {
+                            Some(param) => ctx.try_automatic_conversion_for_type(arg, param.ty),
+                            None => Ok(arg),
+                        }
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
 
                 let has_result = ctx.module.functions[function].result.is_some();
@@ -1807,18 +2058,18 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     let expected = fun.argument_count() as _;
                     let mut args = ctx.prepare_args(arguments, expected, span);
 
-                    let arg = self.expression(args.next()?, ctx)?;
-                    let arg1 = args
+                    let mut arg = self.expression(args.next()?, ctx)?;
+                    let mut arg1 = args
                         .next()
                         .map(|x| self.expression(x, ctx))
                         .ok()
                         .transpose()?;
-                    let arg2 = args
+                    let mut arg2 = args
                         .next()
                         .map(|x| self.expression(x, ctx))
                         .ok()
                         .transpose()?;
-                    let arg3 = args
+                    let mut arg3 = args
                         .next()
                         .map(|x| self.expression(x, ctx))
                         .ok()
@@ -1826,6 +2077,8 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
                     args.finish()?;
 
+                    ctx.math_function_conversions(fun, &mut arg, &mut arg1, &mut arg2, &mut arg3)?;
+
                     if fun == crate::MathFunction::Modf || fun == crate::MathFunction::Frexp {
                         if let Some((size, width)) = match *resolve_inner!(ctx, arg) {
                             crate::TypeInner::Scalar { width, .. } => Some((None, width)),
@@ -1864,6 +2117,10 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
                             args.finish()?;
 
+                            let mut values = [reject, accept];
+                            ctx.convert_to_consensus(&mut values)?;
+                            let [reject, accept] = values;
+
                             crate::Expression::Select {
                                 reject,
                                 accept,
@@ -1888,6 +2145,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                             let mut args = ctx.prepare_args(arguments, 2, span);
                             let pointer = self.atomic_pointer(args.next()?, ctx)?;
                             let value = self.expression(args.next()?, ctx)?;
+                            let value = ctx.try_automatic_conversion_for_pointee(value, pointer)?;
                             args.finish()?;
 
                             let rctx = ctx.runtime_expression_ctx(span)?;
@@ -1968,10 +2226,13 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                             let pointer = self.atomic_pointer(args.next()?, ctx)?;
 
                             let compare = self.expression(args.next()?, ctx)?;
+                            let compare =
+                                ctx.try_automatic_conversion_for_pointee(compare, pointer)?;
 
                             let value = args.next()?;
                             let value_span = ctx.ast_expressions.get_span(value);
                             let value = self.expression(value, ctx)?;
+                            let value = ctx.try_automatic_conversion_for_pointee(value, pointer)?;
 
                             args.finish()?;
 
@@ -2021,6 +2282,14 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 .push(crate::Statement::Barrier(crate::Barrier::WORK_GROUP), span);
                             return Ok(None);
                         }
+                        "textureBarrier" => {
+                            ctx.prepare_args(arguments, 0, span).finish()?;
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .push(crate::Statement::Barrier(crate::Barrier::TEXTURE), span);
+                            return Ok(None);
+                        }
                         "workgroupUniformLoad" => {
                             let mut args = ctx.prepare_args(arguments, 1, span);
                             let expr = args.next()?;
@@ -2068,6 +2337,17 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 .transpose()?;
 
                             let value = self.expression(args.next()?, ctx)?;
+                            let value = match ctx.image_data(image, image_span)?.0 {
+                                crate::ImageClass::Storage { format, .. } => ctx
+                                    .try_automatic_conversion(
+                                        value,
+                                        Leaf::Concrete {
+                                            kind: format.into(),
+                                            width: 4,
+                                        },
+                                    )?,
+                                _ => value,
+                            };
 
                             args.finish()?;
 
@@ -2274,6 +2554,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
         let value = args.next()?;
         let value = self.expression(value, ctx)?;
+        let value = ctx.try_automatic_conversion_for_pointee(value, pointer)?;
         let ty = ctx.register_type(value)?;
 
         args.finish()?;
@@ -2361,9 +2642,34 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
         let sampler = self.expression(args.next()?, ctx)?;
 
-        let coordinate = self.expression(args.next()?, ctx)?;
+        let mut coordinate = self.expression(args.next()?, ctx)?;
 
         let (_, arrayed) = ctx.image_data(image, image_span)?;
+        if let Texture::SampleBaseClampToEdge = fun {
+            match *resolve_inner!(ctx, image) {
+                crate::TypeInner::Image {
+                    class: crate::ImageClass::External,
+                    ..
+                } => {}
+                crate::TypeInner::Image {
+                    dim: crate::ImageDimension::D2,
+                    arrayed: false,
+                    class:
+                        crate::ImageClass::Sampled {
+                            kind: crate::ScalarKind::Float,
+                            multi: false,
+                        },
+                } => {
+                    // External textures clamp in the back end (or in
+                    // `external_texture::lower`), but for ordinary textures
+                    // we have to keep the coordinates half a texel away from
+                    // the edge of mip level 0 ourselves.
+                    coordinate = ctx.clamp_to_edge(image, coordinate, span)?;
+                }
+                _ => return Err(Error::InvalidSampleBaseClampToEdgeImage(image_span)),
+            }
+        }
+
         let array_index = arrayed
             .then(|| self.expression(args.next()?, ctx))
             .transpose()?;
@@ -2397,13 +2703,17 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 let level = self.expression(args.next()?, ctx)?;
                 (crate::SampleLevel::Exact(level), None)
             }
+            Texture::SampleBaseClampToEdge => (crate::SampleLevel::Zero, None),
         };
 
-        let offset = args
-            .next()
-            .map(|arg| self.expression(arg, &mut ctx.as_const()))
-            .ok()
-            .transpose()?;
+        let offset = match fun {
+            Texture::SampleBaseClampToEdge => None,
+            _ => args
+                .next()
+                .map(|arg| self.expression(arg, &mut ctx.as_const()))
+                .ok()
+                .transpose()?,
+        };
 
         args.finish()?;
 
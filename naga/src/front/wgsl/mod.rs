@@ -5,6 +5,8 @@ Frontend for [WGSL][wgsl] (WebGPU Shading Language).
 */
 
 mod error;
+#[cfg(feature = "span")]
+mod format;
 mod index;
 mod lower;
 mod parse;
@@ -16,6 +18,8 @@ use crate::front::wgsl::parse::Parser;
 use thiserror::Error;
 
 pub use crate::front::wgsl::error::ParseError;
+#[cfg(feature = "span")]
+pub use crate::front::wgsl::format::format_str;
 use crate::front::wgsl::lower::Lowerer;
 
 pub struct Frontend {
@@ -100,7 +104,7 @@ impl crate::TypeInner {
     /// For example `vec3<f32>`.
     ///
     /// Note: The names of a `TypeInner::Struct` is not known. Therefore this method will simply return "struct" for them.
-    fn to_wgsl(&self, gctx: crate::proc::GlobalCtx) -> String {
+    pub fn to_wgsl(&self, gctx: crate::proc::GlobalCtx) -> String {
         use crate::TypeInner as Ti;
 
         match *self {
@@ -148,6 +152,10 @@ impl crate::TypeInner {
                 // TODO: Actually output the struct?
                 "struct".to_string()
             }
+            Ti::Image {
+                class: crate::ImageClass::External,
+                ..
+            } => "texture_external".to_string(),
             Ti::Image {
                 dim,
                 arrayed,
@@ -166,7 +174,8 @@ impl crate::TypeInner {
                     crate::ImageClass::Depth { multi: false } => "_depth",
                     crate::ImageClass::Depth { multi: true } => "_depth_multisampled",
                     crate::ImageClass::Sampled { multi: false, .. }
-                    | crate::ImageClass::Storage { .. } => "",
+                    | crate::ImageClass::Storage { .. }
+                    | crate::ImageClass::External => "",
                 };
 
                 let type_in_brackets = match class {
@@ -177,7 +186,9 @@ impl crate::TypeInner {
                         let element_type = Scalar { kind, width: 4 }.to_wgsl();
                         format!("<{element_type}>")
                     }
-                    crate::ImageClass::Depth { multi: _ } => String::new(),
+                    crate::ImageClass::Depth { multi: _ } | crate::ImageClass::External => {
+                        String::new()
+                    }
                     crate::ImageClass::Storage { format, access } => {
                         if access.contains(crate::StorageAccess::STORE) {
                             format!("<{},write>", format.to_wgsl())
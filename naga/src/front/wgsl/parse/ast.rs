@@ -256,6 +256,11 @@ pub enum Type<'a> {
 #[derive(Debug, Default)]
 pub struct Block<'a> {
     pub stmts: Vec<Statement<'a>>,
+
+    /// The span of the braces enclosing the block, or [`Span::UNDEFINED`]
+    /// for blocks that don't appear in the source, like the `reject` block of
+    /// an `if` without an `else`, or the one holding an `else if`.
+    pub span: Span,
 }
 
 #[derive(Debug)]
@@ -282,6 +287,31 @@ pub enum StatementKind<'a> {
         continuing: Block<'a>,
         break_if: Option<Handle<Expression<'a>>>,
     },
+
+    /// A `while` loop.
+    ///
+    /// This is lowered to a [`Loop`] whose body starts by breaking out of
+    /// the loop unless `condition` holds.
+    ///
+    /// [`Loop`]: StatementKind::Loop
+    While {
+        condition: Handle<Expression<'a>>,
+        body: Block<'a>,
+    },
+
+    /// A `for` loop.
+    ///
+    /// This is lowered to `init`, followed by a [`Loop`] whose body starts by
+    /// breaking out of the loop unless `test` holds, and whose continuing
+    /// block is `update`.
+    ///
+    /// [`Loop`]: StatementKind::Loop
+    For {
+        init: Option<Box<Statement<'a>>>,
+        test: Option<Handle<Expression<'a>>>,
+        update: Option<Box<Statement<'a>>>,
+        body: Block<'a>,
+    },
     Break,
     Continue,
     Return {
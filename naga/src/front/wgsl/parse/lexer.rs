@@ -199,6 +199,29 @@ fn is_word_part(c: char) -> bool {
     unicode_xid::UnicodeXID::is_xid_continue(c)
 }
 
+/// Return the spans of all the comments in `source`, in order.
+///
+/// The parser treats comments as trivia, so they don't appear in the AST.
+/// The formatter uses these to put them back in the output.
+pub(in crate::front::wgsl) fn comments(source: &str) -> Vec<Span> {
+    let mut comments = Vec::new();
+    let mut input = source;
+    loop {
+        let start = source.len() - input.len();
+        let (token, rest) = consume_token(input, false);
+        match token {
+            Token::End => return comments,
+            Token::Trivia if input.starts_with('/') => {
+                // Line comments take the line break that ends them along.
+                let text = input[..input.len() - rest.len()].trim_end();
+                comments.push(Span::from(start..start + text.len()));
+            }
+            _ => {}
+        }
+        input = rest;
+    }
+}
+
 #[derive(Clone)]
 pub(in crate::front::wgsl) struct Lexer<'a> {
     input: &'a str,
@@ -341,6 +364,7 @@ impl<'a> Lexer<'a> {
         let (peeked_token, rest) = self.peek_token_and_rest();
         if peeked_token.0 == what {
             self.input = rest;
+            self.last_end_offset = self.current_byte_offset();
             true
         } else {
             false
@@ -464,13 +488,13 @@ fn test_numbers() {
     sub_test(
         "0x123 0X123u 1u 123 0 0i 0x3f",
         &[
-            Token::Number(Ok(Number::I32(291))),
+            Token::Number(Ok(Number::AbstractInt(291))),
             Token::Number(Ok(Number::U32(291))),
             Token::Number(Ok(Number::U32(1))),
-            Token::Number(Ok(Number::I32(123))),
-            Token::Number(Ok(Number::I32(0))),
+            Token::Number(Ok(Number::AbstractInt(123))),
+            Token::Number(Ok(Number::AbstractInt(0))),
             Token::Number(Ok(Number::I32(0))),
-            Token::Number(Ok(Number::I32(63))),
+            Token::Number(Ok(Number::AbstractInt(63))),
         ],
     );
     // decimal floating point
@@ -478,17 +502,17 @@ fn test_numbers() {
         "0.e+4f 01. .01 12.34 .0f 0h 1e-3 0xa.fp+2 0x1P+4f 0X.3 0x3p+2h 0X1.fp-4 0x3.2p+2h",
         &[
             Token::Number(Ok(Number::F32(0.))),
-            Token::Number(Ok(Number::F32(1.))),
-            Token::Number(Ok(Number::F32(0.01))),
-            Token::Number(Ok(Number::F32(12.34))),
+            Token::Number(Ok(Number::AbstractFloat(1.))),
+            Token::Number(Ok(Number::AbstractFloat(0.01))),
+            Token::Number(Ok(Number::AbstractFloat(12.34))),
             Token::Number(Ok(Number::F32(0.))),
             Token::Number(Err(NumberError::UnimplementedF16)),
-            Token::Number(Ok(Number::F32(0.001))),
-            Token::Number(Ok(Number::F32(43.75))),
+            Token::Number(Ok(Number::AbstractFloat(0.001))),
+            Token::Number(Ok(Number::AbstractFloat(43.75))),
             Token::Number(Ok(Number::F32(16.))),
-            Token::Number(Ok(Number::F32(0.1875))),
+            Token::Number(Ok(Number::AbstractFloat(0.1875))),
             Token::Number(Err(NumberError::UnimplementedF16)),
-            Token::Number(Ok(Number::F32(0.12109375))),
+            Token::Number(Ok(Number::AbstractFloat(0.12109375))),
             Token::Number(Err(NumberError::UnimplementedF16)),
         ],
     );
@@ -516,6 +540,27 @@ fn test_numbers() {
         ],
     );
 
+    // min / max decimal signed long
+    sub_test(
+        "-9223372036854775808li 9223372036854775807li -9223372036854775809li 9223372036854775808li",
+        &[
+            Token::Number(Ok(Number::I64(i64::MIN))),
+            Token::Number(Ok(Number::I64(i64::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+    // min / max decimal unsigned long
+    sub_test(
+        "0lu 18446744073709551615lu -1lu 18446744073709551616lu",
+        &[
+            Token::Number(Ok(Number::U64(u64::MIN))),
+            Token::Number(Ok(Number::U64(u64::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+
     // min / max hexadecimal signed integer
     sub_test(
         "-0x80000000i 0x7FFFFFFFi -0x80000001i 0x80000000i",
@@ -537,6 +582,27 @@ fn test_numbers() {
         ],
     );
 
+    // min / max hexadecimal signed long
+    sub_test(
+        "-0x8000000000000000li 0x7FFFFFFFFFFFFFFFli -0x8000000000000001li 0x8000000000000000li",
+        &[
+            Token::Number(Ok(Number::I64(i64::MIN))),
+            Token::Number(Ok(Number::I64(i64::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+    // min / max hexadecimal unsigned long
+    sub_test(
+        "0x0lu 0xFFFFFFFFFFFFFFFFlu -0x1lu 0x10000000000000000lu",
+        &[
+            Token::Number(Ok(Number::U64(u64::MIN))),
+            Token::Number(Ok(Number::U64(u64::MAX))),
+            Token::Number(Err(NumberError::NotRepresentable)),
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+
     /// ≈ 2^-126 * 2^−23 (= 2^−149)
     const SMALLEST_POSITIVE_SUBNORMAL_F32: f32 = 1e-45;
     /// ≈ 2^-126 * (1 − 2^−23)
@@ -629,13 +695,16 @@ fn test_tokens() {
     sub_test("id123_OK", &[Token::Word("id123_OK")]);
     sub_test(
         "92No",
-        &[Token::Number(Ok(Number::I32(92))), Token::Word("No")],
+        &[
+            Token::Number(Ok(Number::AbstractInt(92))),
+            Token::Word("No"),
+        ],
     );
     sub_test(
         "2u3o",
         &[
             Token::Number(Ok(Number::U32(2))),
-            Token::Number(Ok(Number::I32(3))),
+            Token::Number(Ok(Number::AbstractInt(3))),
             Token::Word("o"),
         ],
     );
@@ -643,7 +712,7 @@ fn test_tokens() {
         "2.4f44po",
         &[
             Token::Number(Ok(Number::F32(2.4))),
-            Token::Number(Ok(Number::I32(44))),
+            Token::Number(Ok(Number::AbstractInt(44))),
             Token::Word("po"),
         ],
     );
@@ -684,7 +753,7 @@ fn test_variable_decl() {
             Token::Attribute,
             Token::Word("group"),
             Token::Paren('('),
-            Token::Number(Ok(Number::I32(0))),
+            Token::Number(Ok(Number::AbstractInt(0))),
             Token::Paren(')'),
             Token::Word("var"),
             Token::Paren('<'),
@@ -718,3 +787,13 @@ fn test_variable_decl() {
         ],
     );
 }
+
+#[test]
+fn test_comments() {
+    let source = "// line\nlet x = 1 / 2; /* block /* nested */ */ x\n// end";
+    let comments = comments(source)
+        .into_iter()
+        .map(|span| &source[span])
+        .collect::<Vec<_>>();
+    assert_eq!(comments, ["// line", "/* block /* nested */ */", "// end"]);
+}
@@ -491,6 +491,7 @@ impl Parser {
             | "texture_depth_cube"
             | "texture_depth_cube_array"
             | "texture_depth_multisampled_2d"
+            | "texture_external"
             | "texture_storage_1d"
             | "texture_storage_1d_array"
             | "texture_storage_2d"
@@ -1423,6 +1424,11 @@ impl Parser {
                 arrayed: false,
                 class: crate::ImageClass::Depth { multi: true },
             },
+            "texture_external" => ast::Type::Image {
+                dim: crate::ImageDimension::D2,
+                arrayed: false,
+                class: crate::ImageClass::External,
+            },
             "texture_storage_1d" => {
                 let (format, access) = lexer.next_format_generic()?;
                 ast::Type::Image {
@@ -1504,9 +1510,8 @@ impl Parser {
             }
         };
 
-        self.pop_rule_span(lexer);
-
-        let handle = ctx.types.append(ty, Span::UNDEFINED);
+        let span = self.pop_rule_span(lexer);
+        let handle = ctx.types.append(ty, span);
         Ok(handle)
     }
 
@@ -1843,38 +1848,10 @@ impl Parser {
                     "loop" => self.r#loop(lexer, ctx)?,
                     "while" => {
                         let _ = lexer.next();
-                        let mut body = ast::Block::default();
-
-                        let (condition, span) = lexer.capture_span(|lexer| {
-                            let condition = self.general_expression(lexer, ctx)?;
-                            Ok(condition)
-                        })?;
-                        let mut reject = ast::Block::default();
-                        reject.stmts.push(ast::Statement {
-                            kind: ast::StatementKind::Break,
-                            span,
-                        });
-
-                        body.stmts.push(ast::Statement {
-                            kind: ast::StatementKind::If {
-                                condition,
-                                accept: ast::Block::default(),
-                                reject,
-                            },
-                            span,
-                        });
-
-                        let (block, span) = self.block(lexer, ctx)?;
-                        body.stmts.push(ast::Statement {
-                            kind: ast::StatementKind::Block(block),
-                            span,
-                        });
+                        let condition = self.general_expression(lexer, ctx)?;
+                        let (body, _) = self.block(lexer, ctx)?;
 
-                        ast::StatementKind::Loop {
-                            body,
-                            continuing: ast::Block::default(),
-                            break_if: None,
-                        }
+                        ast::StatementKind::While { condition, body }
                     }
                     "for" => {
                         let _ = lexer.next();
@@ -1882,68 +1859,47 @@ impl Parser {
 
                         ctx.local_table.push_scope();
 
+                        let mut init = ast::Block::default();
                         if !lexer.skip(Token::Separator(';')) {
-                            let num_statements = block.stmts.len();
-                            let (_, span) = {
-                                let ctx = &mut *ctx;
-                                let block = &mut *block;
-                                lexer.capture_span(|lexer| self.statement(lexer, ctx, block))?
-                            };
-
-                            if block.stmts.len() != num_statements {
-                                match block.stmts.last().unwrap().kind {
-                                    ast::StatementKind::Call { .. }
-                                    | ast::StatementKind::Assign { .. }
-                                    | ast::StatementKind::LocalDecl(_) => {}
-                                    _ => return Err(Error::InvalidForInitializer(span)),
-                                }
+                            let (_, span) = lexer
+                                .capture_span(|lexer| self.statement(lexer, ctx, &mut init))?;
+
+                            match init.stmts.last() {
+                                None
+                                | Some(&ast::Statement {
+                                    kind:
+                                        ast::StatementKind::Call { .. }
+                                        | ast::StatementKind::Assign { .. }
+                                        | ast::StatementKind::LocalDecl(_),
+                                    ..
+                                }) => {}
+                                Some(_) => return Err(Error::InvalidForInitializer(span)),
                             }
                         };
 
-                        let mut body = ast::Block::default();
-                        if !lexer.skip(Token::Separator(';')) {
-                            let (condition, span) = lexer.capture_span(|lexer| {
-                                let condition = self.general_expression(lexer, ctx)?;
-                                lexer.expect(Token::Separator(';'))?;
-                                Ok(condition)
-                            })?;
-                            let mut reject = ast::Block::default();
-                            reject.stmts.push(ast::Statement {
-                                kind: ast::StatementKind::Break,
-                                span,
-                            });
-                            body.stmts.push(ast::Statement {
-                                kind: ast::StatementKind::If {
-                                    condition,
-                                    accept: ast::Block::default(),
-                                    reject,
-                                },
-                                span,
-                            });
+                        let test = if !lexer.skip(Token::Separator(';')) {
+                            let test = self.general_expression(lexer, ctx)?;
+                            lexer.expect(Token::Separator(';'))?;
+                            Some(test)
+                        } else {
+                            None
                         };
 
-                        let mut continuing = ast::Block::default();
+                        let mut update = ast::Block::default();
                         if !lexer.skip(Token::Paren(')')) {
-                            self.function_call_or_assignment_statement(
-                                lexer,
-                                ctx,
-                                &mut continuing,
-                            )?;
+                            self.function_call_or_assignment_statement(lexer, ctx, &mut update)?;
                             lexer.expect(Token::Paren(')'))?;
                         }
 
-                        let (block, span) = self.block(lexer, ctx)?;
-                        body.stmts.push(ast::Statement {
-                            kind: ast::StatementKind::Block(block),
-                            span,
-                        });
+                        let (body, _) = self.block(lexer, ctx)?;
 
                         ctx.local_table.pop_scope();
 
-                        ast::StatementKind::Loop {
+                        ast::StatementKind::For {
+                            init: init.stmts.pop().map(Box::new),
+                            test,
+                            update: update.stmts.pop().map(Box::new),
                             body,
-                            continuing,
-                            break_if: None,
                         }
                     }
                     "break" => {
@@ -2000,6 +1956,7 @@ impl Parser {
         let mut continuing = ast::Block::default();
         let mut break_if = None;
 
+        let start = lexer.start_byte_offset();
         lexer.expect(Token::Paren('{'))?;
 
         ctx.local_table.push_scope();
@@ -2010,6 +1967,7 @@ impl Parser {
                 // the last thing in the loop body
 
                 // Expect a opening brace to start the continuing block
+                let continuing_start = lexer.start_byte_offset();
                 lexer.expect(Token::Paren('{'))?;
                 loop {
                     if lexer.skip(Token::Word("break")) {
@@ -2042,6 +2000,7 @@ impl Parser {
                         self.statement(lexer, ctx, &mut continuing)?;
                     }
                 }
+                continuing.span = lexer.span_from(continuing_start);
                 // Since the continuing block must be the last part of the loop body,
                 // we expect to see a closing brace to end the loop body
                 lexer.expect(Token::Paren('}'))?;
@@ -2057,6 +2016,7 @@ impl Parser {
         }
 
         ctx.local_table.pop_scope();
+        body.span = lexer.span_from(start);
 
         Ok(ast::StatementKind::Loop {
             body,
@@ -2084,6 +2044,7 @@ impl Parser {
         ctx.local_table.pop_scope();
 
         let span = self.pop_rule_span(lexer);
+        block.span = span;
         Ok((block, span))
     }
 
@@ -2164,11 +2125,13 @@ impl Parser {
         };
 
         // do not use `self.block` here, since we must not push a new scope
+        let start = lexer.start_byte_offset();
         lexer.expect(Token::Paren('{'))?;
         let mut body = ast::Block::default();
         while !lexer.skip(Token::Paren('}')) {
             self.statement(lexer, &mut ctx, &mut body)?;
         }
+        body.span = lexer.span_from(start);
 
         ctx.local_table.pop_scope();
 
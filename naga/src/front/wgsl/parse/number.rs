@@ -3,7 +3,6 @@ use std::borrow::Cow;
 use crate::front::wgsl::error::NumberError;
 use crate::front::wgsl::parse::lexer::Token;
 
-/// When using this type assume no Abstract Int/Float for now
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Number {
     /// Abstract Int (-2^63 ≤ i < 2^63)
@@ -14,41 +13,19 @@ pub enum Number {
     I32(i32),
     /// Concrete u32
     U32(u32),
+    /// Concrete i64
+    I64(i64),
+    /// Concrete u64
+    U64(u64),
     /// Concrete f32
     F32(f32),
 }
 
-impl Number {
-    /// Convert abstract numbers to a plausible concrete counterpart.
-    ///
-    /// Return concrete numbers unchanged. If the conversion would be
-    /// lossy, return an error.
-    fn abstract_to_concrete(self) -> Result<Number, NumberError> {
-        match self {
-            Number::AbstractInt(num) => i32::try_from(num)
-                .map(Number::I32)
-                .map_err(|_| NumberError::NotRepresentable),
-            Number::AbstractFloat(num) => {
-                let num = num as f32;
-                if num.is_finite() {
-                    Ok(Number::F32(num))
-                } else {
-                    Err(NumberError::NotRepresentable)
-                }
-            }
-            num => Ok(num),
-        }
-    }
-}
-
 // TODO: when implementing Creation-Time Expressions, remove the ability to match the minus sign
 
 pub(in crate::front::wgsl) fn consume_number(input: &str) -> (Token<'_>, &str) {
     let (result, rest) = parse(input);
-    (
-        Token::Number(result.and_then(Number::abstract_to_concrete)),
-        rest,
-    )
+    (Token::Number(result), rest)
 }
 
 enum Kind {
@@ -59,6 +36,8 @@ enum Kind {
 enum IntKind {
     I32,
     U32,
+    I64,
+    U64,
 }
 
 enum FloatKind {
@@ -69,9 +48,9 @@ enum FloatKind {
 // The following regexes (from the WGSL spec) will be matched:
 
 // int_literal:
-// | / 0                                                                [iu]?   /
-// | / [1-9][0-9]*                                                      [iu]?   /
-// | / 0[xX][0-9a-fA-F]+                                                [iu]?   /
+// | / 0                                                                (l?[iu])?   /
+// | / [1-9][0-9]*                                                      (l?[iu])?   /
+// | / 0[xX][0-9a-fA-F]+                                                (l?[iu])?   /
 
 // decimal_float_literal:
 // | / 0                                                                [fh]    /
@@ -226,7 +205,13 @@ fn parse(input: &str) -> (Result<Number, NumberError>, &str) {
                     rest_to_str!(bytes),
                 )
             } else {
-                let kind = consume_map!(bytes, [b'i' => IntKind::I32, b'u' => IntKind::U32]);
+                let kind = if consume!(bytes, b'l', b'i') {
+                    Some(IntKind::I64)
+                } else if consume!(bytes, b'l', b'u') {
+                    Some(IntKind::U64)
+                } else {
+                    consume_map!(bytes, [b'i' => IntKind::I32, b'u' => IntKind::U32])
+                };
 
                 (
                     parse_hex_int(is_negative, digits, kind),
@@ -286,12 +271,18 @@ fn parse(input: &str) -> (Result<Number, NumberError>, &str) {
 
                 let digits_with_sign = general_extract.end(bytes);
 
-                let kind = consume_map!(bytes, [
+                let kind = if consume!(bytes, b'l', b'i') {
+                    Some(Kind::Int(IntKind::I64))
+                } else if consume!(bytes, b'l', b'u') {
+                    Some(Kind::Int(IntKind::U64))
+                } else {
+                    consume_map!(bytes, [
                     b'i' => Kind::Int(IntKind::I32),
                     b'u' => Kind::Int(IntKind::U32),
                     b'f' => Kind::Float(FloatKind::F32),
                     b'h' => Kind::Float(FloatKind::F16)
-                ]);
+                    ])
+                };
 
                 (
                     parse_dec(is_negative, digits_with_sign, kind),
@@ -439,5 +430,14 @@ fn parse_int(
             Ok(num) => Ok(Number::U32(num)),
             Err(e) => Err(map_err(e)),
         },
+        Some(IntKind::I64) => match i64::from_str_radix(input, radix) {
+            Ok(num) => Ok(Number::I64(num)),
+            Err(e) => Err(map_err(e)),
+        },
+        Some(IntKind::U64) if is_negative => Err(NumberError::NotRepresentable),
+        Some(IntKind::U64) => match u64::from_str_radix(input, radix) {
+            Ok(num) => Ok(Number::U64(num)),
+            Err(e) => Err(map_err(e)),
+        },
     }
 }
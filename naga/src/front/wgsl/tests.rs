@@ -73,13 +73,21 @@ fn parse_type_cast() {
     ",
     )
     .unwrap();
-    assert!(parse_str(
+    parse_str(
         "
         fn main() {
             let x: vec2<f32> = vec2<f32>(0);
         }
     ",
     )
+    .unwrap();
+    assert!(parse_str(
+        "
+        fn main() {
+            let x: vec2<f32> = vec2<f32>(0i);
+        }
+    ",
+    )
     .is_err());
 }
 
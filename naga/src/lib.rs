@@ -279,10 +279,18 @@ An override expression can be evaluated at pipeline creation time.
 mod arena;
 pub mod back;
 mod block;
+#[cfg(feature = "builder")]
+pub mod builder;
 #[cfg(feature = "compact")]
 pub mod compact;
+#[cfg(feature = "external-texture")]
+pub mod external_texture;
 pub mod front;
 pub mod keywords;
+#[cfg(feature = "link")]
+pub mod link;
+#[cfg(feature = "opt")]
+pub mod opt;
 pub mod proc;
 mod span;
 pub mod valid;
@@ -654,6 +662,20 @@ pub enum ImageClass {
         format: StorageFormat,
         access: StorageAccess,
     },
+    /// External image.
+    ///
+    /// An external image holds the frames of an external source, like a
+    /// video decoder, in one to three planes, which may be in a YUV format.
+    /// Sampling and loading it convert its texels to RGBA. Its dimension is
+    /// always [`D2`], and it is never arrayed.
+    ///
+    /// Only the WGSL back end accepts external images: the other back ends
+    /// expect them to be replaced by their planes first, as done by
+    /// [`external_texture::lower`].
+    ///
+    /// [`D2`]: ImageDimension::D2
+    /// [`external_texture::lower`]: crate::external_texture::lower
+    External,
 }
 
 /// A data type declared in the module.
@@ -861,7 +883,27 @@ pub enum Literal {
     F32(f32),
     U32(u32),
     I32(i32),
+    U64(u64),
+    I64(i64),
     Bool(bool),
+    /// A WGSL [AbstractInt], whose concrete type depends on how it is used.
+    ///
+    /// The WGSL front end uses these while evaluating constant expressions,
+    /// and converts any that remain to `i32` before returning the module, so
+    /// they never reach the validator or the back ends. Until then, the rest
+    /// of Naga treats an abstract literal as having the type it would be
+    /// given by default: `i32` here, `f32` for [`AbstractFloat`].
+    ///
+    /// [AbstractInt]: https://gpuweb.github.io/gpuweb/wgsl/#abstractint
+    /// [`AbstractFloat`]: Literal::AbstractFloat
+    AbstractInt(i64),
+    /// A WGSL [AbstractFloat]. See [`AbstractInt`] for how these are used.
+    ///
+    /// May not be NaN or infinity.
+    ///
+    /// [AbstractFloat]: https://gpuweb.github.io/gpuweb/wgsl/#abstractfloat
+    /// [`AbstractInt`]: Literal::AbstractInt
+    AbstractFloat(f64),
 }
 
 #[derive(Debug, PartialEq)]
@@ -1164,6 +1206,8 @@ pub enum MathFunction {
     Pow,
     // geometry
     Dot,
+    Dot4I8Packed,
+    Dot4U8Packed,
     Outer,
     Cross,
     Distance,
@@ -1183,6 +1227,7 @@ pub enum MathFunction {
     Inverse,
     Transpose,
     Determinant,
+    QuantizeToF16,
     // bits
     CountTrailingZeros,
     CountLeadingZeros,
@@ -1198,12 +1243,18 @@ pub enum MathFunction {
     Pack2x16snorm,
     Pack2x16unorm,
     Pack2x16float,
+    Pack4xI8,
+    Pack4xU8,
+    Pack4xI8Clamp,
+    Pack4xU8Clamp,
     // data unpacking
     Unpack4x8snorm,
     Unpack4x8unorm,
     Unpack2x16snorm,
     Unpack2x16unorm,
     Unpack2x16float,
+    Unpack4xI8,
+    Unpack4xU8,
 }
 
 /// Sampling modifier to control the level of detail.
@@ -1269,6 +1320,8 @@ bitflags::bitflags! {
         const STORAGE = 0x1;
         /// Barrier affects all `AddressSpace::WorkGroup` accesses.
         const WORK_GROUP = 0x2;
+        /// Barrier affects all storage texture accesses.
+        const TEXTURE = 0x4;
     }
 }
 
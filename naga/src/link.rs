@@ -0,0 +1,457 @@
+/*!
+Combining separately built [`Module`]s into one.
+
+A [`Linker`] accumulates modules, such as WGSL libraries parsed once and
+reused, or functions compiled from GLSL, into a single module that back
+ends can translate. Each module added must be self-contained, and valid
+on its own: calls and other references between modules aren't resolved.
+
+Items are merged as follows:
+
+- Types are deduplicated by the [`UniqueArena`] holding them, so a struct
+  declared identically by several modules appears once.
+
+- Constants with the same name, type and value are merged. Global
+  variables with the same name, address space and type are merged if they
+  are resources bound to the same slot, or push constants. Other global
+  variables are never merged, since that would make the modules share
+  their state.
+
+- Types, constants, global variables and functions whose names are
+  already used by a different item of the same kind are renamed, by
+  appending a number.
+
+- Entry points are never renamed, since pipelines refer to them by name.
+  Adding a module with an entry point whose name is already used is an
+  error, as is binding two different resources to the same slot.
+
+The linked module should be validated before it is used. Spans are kept,
+but refer to the source text of the module the item came from.
+
+[`Module`]: crate::Module
+[`UniqueArena`]: crate::UniqueArena
+*/
+
+use crate::{
+    arena::{Arena, Handle},
+    Block, Constant, Expression, FastHashSet, Function, GlobalVariable, Module, Statement, Type,
+    TypeInner,
+};
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum LinkError {
+    #[error("Entry point {0} is defined by more than one module")]
+    DuplicateEntryPoint(String),
+    #[error("Global variables {existing:?} and {new:?} are different, but both use {binding:?}")]
+    BindingConflict {
+        existing: Option<String>,
+        new: Option<String>,
+        binding: crate::ResourceBinding,
+    },
+}
+
+/// Combine `modules` into a single module.
+///
+/// See the [module-level documentation](self) for how items are merged.
+///
+/// # Panics
+///
+/// If any of `modules` has not passed validation, this may panic.
+pub fn link(modules: impl IntoIterator<Item = Module>) -> Result<Module, LinkError> {
+    let mut linker = Linker::new();
+    for module in modules {
+        linker.add(module)?;
+    }
+    Ok(linker.finish())
+}
+
+/// Accumulates modules into a single module.
+#[derive(Default)]
+pub struct Linker {
+    module: Module,
+    type_names: FastHashSet<String>,
+    constant_names: FastHashSet<String>,
+    global_names: FastHashSet<String>,
+    function_names: FastHashSet<String>,
+}
+
+/// Maps from the handles of the module being added to the handles of the
+/// linked module.
+struct Maps {
+    types: Vec<Handle<Type>>,
+    const_expressions: Vec<Handle<Expression>>,
+    constants: Vec<Handle<Constant>>,
+    global_variables: Vec<Handle<GlobalVariable>>,
+    functions: Vec<Handle<Function>>,
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The module linked so far.
+    pub const fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Add the items of `module` to the linked module.
+    ///
+    /// If this returns an error, some of `module`'s types may have been
+    /// added, but nothing else.
+    ///
+    /// # Panics
+    ///
+    /// If `module` has not passed validation, this may panic.
+    pub fn add(&mut self, module: Module) -> Result<(), LinkError> {
+        if let Some(ep) = module.entry_points.iter().find(|ep| {
+            self.module
+                .entry_points
+                .iter()
+                .any(|existing| existing.name == ep.name)
+        }) {
+            return Err(LinkError::DuplicateEntryPoint(ep.name.clone()));
+        }
+
+        let Module {
+            mut types,
+            special_types,
+            constants,
+            global_variables,
+            const_expressions,
+            functions,
+            entry_points,
+        } = module;
+
+        let mut maps = Maps {
+            types: Vec::with_capacity(types.len()),
+            const_expressions: Vec::with_capacity(const_expressions.len()),
+            constants: Vec::with_capacity(constants.len()),
+            global_variables: Vec::with_capacity(global_variables.len()),
+            functions: Vec::with_capacity(functions.len()),
+        };
+
+        // Types only refer to types that precede them.
+        for (_, ty, span) in types.drain_all() {
+            let handle = self.add_type(ty, span, &maps);
+            maps.types.push(handle);
+        }
+        self.add_special_types(special_types, &maps);
+
+        self.check_bindings(&global_variables, &maps)?;
+
+        // Constant expressions and constants refer to each other, but only
+        // to ones that precede them.
+        let const_expression_spans = spans(&const_expressions);
+        for (expr, span) in const_expressions
+            .into_inner()
+            .into_iter()
+            .zip(const_expression_spans)
+        {
+            let handle = self.add_const_expression(expr, span, &maps);
+            maps.const_expressions.push(handle);
+        }
+        let constant_spans = spans(&constants);
+        for (constant, span) in constants.into_inner().into_iter().zip(constant_spans) {
+            let handle = self.add_constant(constant, span, &maps);
+            maps.constants.push(handle);
+        }
+
+        let global_spans = spans(&global_variables);
+        for (global, span) in global_variables.into_inner().into_iter().zip(global_spans) {
+            let handle = self.add_global_variable(global, span, &maps);
+            maps.global_variables.push(handle);
+        }
+
+        // Functions only call functions that precede them.
+        let function_spans = spans(&functions);
+        for (mut function, span) in functions.into_inner().into_iter().zip(function_spans) {
+            maps.adjust_function(&mut function);
+            if let Some(ref mut name) = function.name {
+                rename(name, &mut self.function_names);
+            }
+            let handle = self.module.functions.append(function, span);
+            maps.functions.push(handle);
+        }
+
+        for mut ep in entry_points {
+            maps.adjust_function(&mut ep.function);
+            self.module.entry_points.push(ep);
+        }
+
+        Ok(())
+    }
+
+    /// Return the linked module.
+    pub fn finish(self) -> Module {
+        self.module
+    }
+
+    fn add_type(&mut self, ty: Type, span: crate::Span, maps: &Maps) -> Handle<Type> {
+        let inner = match ty.inner {
+            TypeInner::Pointer { base, space } => TypeInner::Pointer {
+                base: maps.types[base.index()],
+                space,
+            },
+            TypeInner::Array { base, size, stride } => TypeInner::Array {
+                base: maps.types[base.index()],
+                size,
+                stride,
+            },
+            TypeInner::BindingArray { base, size } => TypeInner::BindingArray {
+                base: maps.types[base.index()],
+                size,
+            },
+            TypeInner::Struct {
+                mut members,
+                span: struct_span,
+            } => {
+                for member in members.iter_mut() {
+                    member.ty = maps.types[member.ty.index()];
+                }
+                TypeInner::Struct {
+                    members,
+                    span: struct_span,
+                }
+            }
+            other => other,
+        };
+        let mut ty = Type {
+            name: ty.name,
+            inner,
+        };
+        if let Some(handle) = self.module.types.get(&ty) {
+            return handle;
+        }
+        if let Some(ref mut name) = ty.name {
+            rename(name, &mut self.type_names);
+        }
+        self.module.types.insert(ty, span)
+    }
+
+    fn add_special_types(&mut self, special_types: crate::SpecialTypes, maps: &Maps) {
+        let target = &mut self.module.special_types;
+        if let Some(ray_desc) = special_types.ray_desc {
+            target.ray_desc = Some(maps.types[ray_desc.index()]);
+        }
+        if let Some(ray_intersection) = special_types.ray_intersection {
+            target.ray_intersection = Some(maps.types[ray_intersection.index()]);
+        }
+        for (predeclared, handle) in special_types.predeclared_types {
+            target
+                .predeclared_types
+                .entry(predeclared)
+                .or_insert(maps.types[handle.index()]);
+        }
+    }
+
+    /// Check that the resources of `global_variables` bound to the same
+    /// slots as resources already linked are the same.
+    fn check_bindings(
+        &self,
+        global_variables: &Arena<GlobalVariable>,
+        maps: &Maps,
+    ) -> Result<(), LinkError> {
+        for (_, global) in global_variables.iter() {
+            let Some(ref binding) = global.binding else {
+                continue;
+            };
+            for (_, existing) in self.module.global_variables.iter() {
+                if existing.binding.as_ref() == Some(binding)
+                    && (existing.name != global.name
+                        || existing.space != global.space
+                        || existing.ty != maps.types[global.ty.index()])
+                {
+                    return Err(LinkError::BindingConflict {
+                        existing: existing.name.clone(),
+                        new: global.name.clone(),
+                        binding: binding.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_const_expression(
+        &mut self,
+        mut expr: Expression,
+        span: crate::Span,
+        maps: &Maps,
+    ) -> Handle<Expression> {
+        maps.adjust_expression(&mut expr);
+        match expr {
+            Expression::Compose {
+                ref mut components, ..
+            } => {
+                for component in components.iter_mut() {
+                    *component = maps.const_expressions[component.index()];
+                }
+            }
+            Expression::Splat { ref mut value, .. } => {
+                *value = maps.const_expressions[value.index()];
+            }
+            Expression::Literal(_) | Expression::Constant(_) | Expression::ZeroValue(_) => {}
+            _ => unreachable!("not a constant expression: {:?}", expr),
+        }
+        // Since operands are merged before their users, this merges equal
+        // constant expressions of any depth.
+        self.module
+            .const_expressions
+            .fetch_if_or_append(expr, span, |a, b| a == b)
+    }
+
+    fn add_constant(
+        &mut self,
+        mut constant: Constant,
+        span: crate::Span,
+        maps: &Maps,
+    ) -> Handle<Constant> {
+        constant.ty = maps.types[constant.ty.index()];
+        constant.init = maps.const_expressions[constant.init.index()];
+        if let Some(handle) = self
+            .module
+            .constants
+            .fetch_if(|existing| *existing == constant)
+        {
+            return handle;
+        }
+        if let Some(ref mut name) = constant.name {
+            rename(name, &mut self.constant_names);
+        }
+        self.module.constants.append(constant, span)
+    }
+
+    fn add_global_variable(
+        &mut self,
+        mut global: GlobalVariable,
+        span: crate::Span,
+        maps: &Maps,
+    ) -> Handle<GlobalVariable> {
+        global.ty = maps.types[global.ty.index()];
+        if let Some(ref mut init) = global.init {
+            *init = maps.const_expressions[init.index()];
+        }
+        let shared = global.binding.is_some() || global.space == crate::AddressSpace::PushConstant;
+        if shared {
+            if let Some(handle) = self
+                .module
+                .global_variables
+                .fetch_if(|existing| *existing == global)
+            {
+                return handle;
+            }
+        }
+        if let Some(ref mut name) = global.name {
+            rename(name, &mut self.global_names);
+        }
+        self.module.global_variables.append(global, span)
+    }
+}
+
+impl Maps {
+    /// Adjust the handles to module items in `expr`.
+    ///
+    /// This doesn't adjust operands, which are handles into the same arena
+    /// as `expr`.
+    fn adjust_expression(&self, expr: &mut Expression) {
+        match *expr {
+            Expression::Constant(ref mut constant) => {
+                *constant = self.constants[constant.index()];
+            }
+            Expression::ZeroValue(ref mut ty)
+            | Expression::Compose { ref mut ty, .. }
+            | Expression::AtomicResult { ref mut ty, .. }
+            | Expression::WorkGroupUniformLoadResult { ref mut ty } => {
+                *ty = self.types[ty.index()];
+            }
+            Expression::GlobalVariable(ref mut global) => {
+                *global = self.global_variables[global.index()];
+            }
+            Expression::CallResult(ref mut function) => {
+                *function = self.functions[function.index()];
+            }
+            Expression::ImageSample {
+                offset: Some(ref mut offset),
+                ..
+            } => {
+                *offset = self.const_expressions[offset.index()];
+            }
+            _ => {}
+        }
+    }
+
+    /// Adjust the handles to module items in `function`.
+    ///
+    /// Handles to the function's own expressions and local variables stay
+    /// the same, since their arenas are kept as they are.
+    fn adjust_function(&self, function: &mut Function) {
+        for argument in function.arguments.iter_mut() {
+            argument.ty = self.types[argument.ty.index()];
+        }
+        if let Some(ref mut result) = function.result {
+            result.ty = self.types[result.ty.index()];
+        }
+        for (_, local) in function.local_variables.iter_mut() {
+            local.ty = self.types[local.ty.index()];
+        }
+        for (_, expr) in function.expressions.iter_mut() {
+            self.adjust_expression(expr);
+        }
+        self.adjust_block(&mut function.body);
+    }
+
+    fn adjust_block(&self, block: &mut Block) {
+        for statement in block.iter_mut() {
+            match *statement {
+                Statement::Call {
+                    ref mut function, ..
+                } => *function = self.functions[function.index()],
+                Statement::Block(ref mut block) => self.adjust_block(block),
+                Statement::If {
+                    ref mut accept,
+                    ref mut reject,
+                    ..
+                } => {
+                    self.adjust_block(accept);
+                    self.adjust_block(reject);
+                }
+                Statement::Switch { ref mut cases, .. } => {
+                    for case in cases.iter_mut() {
+                        self.adjust_block(&mut case.body);
+                    }
+                }
+                Statement::Loop {
+                    ref mut body,
+                    ref mut continuing,
+                    ..
+                } => {
+                    self.adjust_block(body);
+                    self.adjust_block(continuing);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Make `name` unique among `names` by appending a number if needed, and
+/// add it to `names`.
+fn rename(name: &mut String, names: &mut FastHashSet<String>) {
+    if names.contains(name) {
+        let mut counter = 1;
+        while names.contains(&format!("{name}_{counter}")) {
+            counter += 1;
+        }
+        log::trace!("renaming {name} to {name}_{counter}");
+        *name = format!("{name}_{counter}");
+    }
+    names.insert(name.clone());
+}
+
+fn spans<T>(arena: &Arena<T>) -> Vec<crate::Span> {
+    arena
+        .iter()
+        .map(|(handle, _)| arena.get_span(handle))
+        .collect()
+}
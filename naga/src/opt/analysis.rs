@@ -0,0 +1,147 @@
+//! Which expressions, local variables and stores of a function matter.
+
+use super::visit;
+use crate::{Block, Expression, Function, Handle, LocalVariable, Statement};
+
+/// What the [`Rebuilder`] needs to know about a function before copying it.
+///
+/// [`Rebuilder`]: super::rebuild::Rebuilder
+pub struct Analysis {
+    /// Whether each expression contributes to a statement.
+    live: Vec<bool>,
+    /// Whether the value of each local variable is ever used.
+    ///
+    /// Stores to a variable that is never read can be dropped.
+    read: Vec<bool>,
+}
+
+impl Analysis {
+    /// Analyze `function`.
+    ///
+    /// If `dead_code` is false, every emitted expression and every store is
+    /// considered useful.
+    pub fn new(function: &Function, dead_code: bool) -> Self {
+        let expression_count = function.expressions.len();
+        let local_count = function.local_variables.len();
+        if !dead_code {
+            return Self {
+                live: vec![true; expression_count],
+                read: vec![true; local_count],
+            };
+        }
+
+        let mut analysis = Self {
+            live: vec![false; expression_count],
+            read: vec![false; local_count],
+        };
+        analysis.find_reads(function);
+
+        let mut worklist = Vec::new();
+        analysis.find_roots(function, &function.body, &mut worklist);
+        while let Some(handle) = worklist.pop() {
+            let expr = &function.expressions[handle];
+            visit::expression_operands(expr, |operand| {
+                if !analysis.live[operand.index()] {
+                    analysis.live[operand.index()] = true;
+                    worklist.push(operand);
+                }
+            });
+            if let Expression::LocalVariable(local) = *expr {
+                if let Some(init) = function.local_variables[local].init {
+                    if !analysis.live[init.index()] {
+                        analysis.live[init.index()] = true;
+                        worklist.push(init);
+                    }
+                }
+            }
+        }
+        analysis
+    }
+
+    /// Whether the expression `handle` needs to be kept.
+    pub fn is_live(&self, handle: Handle<Expression>) -> bool {
+        self.live[handle.index()]
+    }
+
+    /// Whether the value of the local variable `local` is used.
+    pub fn is_read(&self, local: Handle<LocalVariable>) -> bool {
+        self.read[local.index()]
+    }
+
+    /// Whether `statement` can be dropped because it only stores to a local
+    /// variable that is never read.
+    pub fn is_dead_store(&self, function: &Function, statement: &Statement) -> bool {
+        match *statement {
+            Statement::Store { pointer, .. } => {
+                root_local(function, pointer).map_or(false, |local| !self.read[local.index()])
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark the local variables whose values are used.
+    ///
+    /// A variable is read whenever a pointer into it is used for anything
+    /// else than storing to it, or deriving another pointer into it.
+    fn find_reads(&mut self, function: &Function) {
+        let mut mark = |operand: Handle<Expression>| {
+            if let Some(local) = root_local(function, operand) {
+                self.read[local.index()] = true;
+            }
+        };
+        for (_, expr) in function.expressions.iter() {
+            match *expr {
+                Expression::Access { index, .. } => mark(index),
+                Expression::AccessIndex { .. } => {}
+                _ => visit::expression_operands(expr, &mut mark),
+            }
+        }
+        visit::walk_statements(&function.body, &mut |statement| match *statement {
+            Statement::Store { value, .. } => mark(value),
+            _ => visit::statement_operands(statement, &mut mark),
+        });
+    }
+
+    /// Push the expressions used by the statements of `block` onto `worklist`.
+    fn find_roots(
+        &mut self,
+        function: &Function,
+        block: &Block,
+        worklist: &mut Vec<Handle<Expression>>,
+    ) {
+        for statement in block.iter() {
+            if !self.is_dead_store(function, statement) {
+                let live = &mut self.live;
+                let mut mark = |operand: Handle<Expression>| {
+                    if !live[operand.index()] {
+                        live[operand.index()] = true;
+                        worklist.push(operand);
+                    }
+                };
+                visit::statement_operands(statement, &mut mark);
+                if let Some(result) = visit::statement_result(statement) {
+                    mark(result);
+                }
+            }
+            visit::nested_blocks(statement, |block| {
+                self.find_roots(function, block, worklist)
+            });
+        }
+    }
+}
+
+/// Return the local variable that `pointer` points into, if any.
+pub fn root_local(
+    function: &Function,
+    mut pointer: Handle<Expression>,
+) -> Option<Handle<LocalVariable>> {
+    loop {
+        match function.expressions[pointer] {
+            Expression::Access { base, .. } | Expression::AccessIndex { base, .. } => {
+                pointer = base
+            }
+            Expression::LocalVariable(local) => return Some(local),
+            _ => return None,
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Moving loop-invariant expressions out of loops.
+
+use super::visit;
+use crate::{arena::Range, BinaryOperator, Block, Expression, Function, Handle, Span, Statement};
+
+/// Move the expressions emitted at the top level of loop bodies, or in plain
+/// blocks there, that only depend on values computed before the loop, to
+/// just before the loop.
+///
+/// Only pure expressions that can't fail are moved: loads, derivatives,
+/// image operations, divisions and the like stay where they are.
+///
+/// The moved expressions that the loop uses are given names, since back
+/// ends only store the values of named or repeatedly used expressions in
+/// variables, and write other expressions out again where they are used.
+pub fn hoist_loop_invariants(function: &mut Function) {
+    let mut used = Vec::new();
+    hoist_block(&function.expressions, &mut function.body, &mut used);
+    for handle in used {
+        if !function.named_expressions.contains_key(&handle) {
+            function
+                .named_expressions
+                .insert(handle, "invariant".to_string());
+        }
+    }
+}
+
+/// Hoist the invariants of the loops in `block`, and push the ones that
+/// should be named onto `used`.
+fn hoist_block(
+    expressions: &crate::Arena<Expression>,
+    block: &mut Block,
+    used: &mut Vec<Handle<Expression>>,
+) {
+    let mut index = 0;
+    while index < block.len() {
+        let mut hoisted = Block::new();
+        match block[index] {
+            Statement::Block(ref mut inner) => hoist_block(expressions, inner, used),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                hoist_block(expressions, accept, used);
+                hoist_block(expressions, reject, used);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    hoist_block(expressions, &mut case.body, used);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                break_if,
+            } => {
+                // Inner loops first, so that their invariants can move on
+                // out of this loop.
+                hoist_block(expressions, body, used);
+                hoist_block(expressions, continuing, used);
+                hoisted = hoist_loop(expressions, body, continuing, break_if, used);
+            }
+            _ => {}
+        }
+        let count = hoisted.len();
+        block.splice(index..index, hoisted);
+        index += count + 1;
+    }
+}
+
+/// Remove the invariant expressions from the `Emit` statements at the top
+/// level of `body`, and return `Emit` statements for them.
+fn hoist_loop(
+    expressions: &crate::Arena<Expression>,
+    body: &mut Block,
+    continuing: &Block,
+    break_if: Option<Handle<Expression>>,
+    used: &mut Vec<Handle<Expression>>,
+) -> Block {
+    // Expressions whose values are computed inside the loop.
+    let mut inside = vec![false; expressions.len()];
+    let mut mark = |statement: &Statement| {
+        if let Statement::Emit(ref range) = *statement {
+            for handle in range.clone() {
+                inside[handle.index()] = true;
+            }
+        }
+        if let Some(result) = visit::statement_result(statement) {
+            inside[result.index()] = true;
+        }
+    };
+    visit::walk_statements(body, &mut mark);
+    visit::walk_statements(continuing, &mut mark);
+
+    let mut hoisted = Vec::new();
+    find_invariants(expressions, body, &mut inside, &mut hoisted);
+    if hoisted.is_empty() {
+        return Block::new();
+    }
+    remove_hoisted(body, &hoisted);
+
+    // Find the hoisted expressions that the loop uses directly. Pointers
+    // can't be stored in variables, so they are left unnamed.
+    let mut is_used = vec![false; expressions.len()];
+    let mut mark = |operand: Handle<Expression>| is_used[operand.index()] = true;
+    let mut mark_statement = |statement: &Statement| match *statement {
+        Statement::Emit(ref range) => {
+            for handle in range.clone() {
+                visit::expression_operands(&expressions[handle], &mut mark);
+            }
+        }
+        _ => visit::statement_operands(statement, &mut mark),
+    };
+    visit::walk_statements(body, &mut mark_statement);
+    visit::walk_statements(continuing, &mut mark_statement);
+    break_if.into_iter().for_each(&mut mark);
+    used.extend(hoisted.iter().copied().filter(|&handle| {
+        is_used[handle.index()]
+            && !matches!(
+                expressions[handle],
+                Expression::Access { .. } | Expression::AccessIndex { .. }
+            )
+    }));
+
+    let mut emits = Block::new();
+    for range in contiguous_ranges(hoisted.into_iter()) {
+        let mut span = Span::default();
+        for handle in range.clone() {
+            span.subsume(expressions.get_span(handle));
+        }
+        emits.push(Statement::Emit(range), span);
+    }
+    emits
+}
+
+/// Push the invariant expressions emitted by `block` onto `hoisted`.
+///
+/// Only the statements that are executed whenever `block` is are considered.
+fn find_invariants(
+    expressions: &crate::Arena<Expression>,
+    block: &Block,
+    inside: &mut [bool],
+    hoisted: &mut Vec<Handle<Expression>>,
+) {
+    for statement in block.iter() {
+        match *statement {
+            Statement::Emit(ref range) => {
+                for handle in range.clone() {
+                    let expr = &expressions[handle];
+                    let mut invariant = is_hoistable(expr);
+                    visit::expression_operands(expr, |operand| {
+                        invariant &= !inside[operand.index()];
+                    });
+                    if invariant {
+                        inside[handle.index()] = false;
+                        hoisted.push(handle);
+                    }
+                }
+            }
+            Statement::Block(ref block) => find_invariants(expressions, block, inside, hoisted),
+            _ => {}
+        }
+    }
+}
+
+/// Remove the `hoisted` expressions from the `Emit` statements found by
+/// [`find_invariants`].
+fn remove_hoisted(block: &mut Block, hoisted: &[Handle<Expression>]) {
+    let mut new_block = Block::with_capacity(block.len());
+    for (statement, span) in block.span_iter_mut() {
+        let span = span.map_or(Span::default(), |span| *span);
+        match *statement {
+            Statement::Emit(ref range) => {
+                let remaining = range.clone().filter(|handle| !hoisted.contains(handle));
+                for range in contiguous_ranges(remaining) {
+                    new_block.push(Statement::Emit(range), span);
+                }
+            }
+            Statement::Block(ref mut inner) => {
+                remove_hoisted(inner, hoisted);
+                new_block.push(Statement::Block(std::mem::take(inner)), span);
+            }
+            _ => new_block.push(std::mem::replace(statement, Statement::Kill), span),
+        }
+    }
+    *block = new_block;
+}
+
+/// Whether `expr` can be evaluated before the loop without changing the
+/// meaning of the program.
+const fn is_hoistable(expr: &Expression) -> bool {
+    match *expr {
+        Expression::Binary {
+            op: BinaryOperator::Divide | BinaryOperator::Modulo,
+            ..
+        } => false,
+        Expression::Compose { .. }
+        | Expression::Access { .. }
+        | Expression::AccessIndex { .. }
+        | Expression::Splat { .. }
+        | Expression::Swizzle { .. }
+        | Expression::Unary { .. }
+        | Expression::Binary { .. }
+        | Expression::Select { .. }
+        | Expression::Relational { .. }
+        | Expression::Math { .. }
+        | Expression::As { .. } => true,
+        _ => false,
+    }
+}
+
+/// Group increasing `handles` into ranges of consecutive handles.
+fn contiguous_ranges(handles: impl Iterator<Item = Handle<Expression>>) -> Vec<Range<Expression>> {
+    let mut ranges = Vec::new();
+    let mut current: Option<(Handle<Expression>, Handle<Expression>)> = None;
+    for handle in handles {
+        current = match current {
+            Some((first, last)) if last.index() + 1 == handle.index() => Some((first, handle)),
+            Some((first, last)) => {
+                ranges.push(Range::new_from_bounds(first, last));
+                Some((handle, handle))
+            }
+            None => Some((handle, handle)),
+        };
+    }
+    if let Some((first, last)) = current {
+        ranges.push(Range::new_from_bounds(first, last));
+    }
+    ranges
+}
@@ -0,0 +1,212 @@
+/*!
+Optimizations of [`Module`]s, from IR to IR.
+
+Back ends translate the IR nearly statement by statement, so every
+temporary and every helper function written in the source ends up in their
+output. For large shaders, this makes the output and the time drivers spend
+compiling it needlessly large, especially where driver optimizers are weak,
+like on OpenGL ES.
+
+[`optimize`] applies the passes enabled in [`Options`] to every function
+and entry point of a module:
+
+- Constant folding evaluates the expressions whose operands are all
+  constant, using the same rules as the WGSL front end.
+
+- Dead code elimination drops the expressions whose values are never used,
+  and the stores to local variables that are never read.
+
+- Common subexpression elimination reuses an equivalent expression emitted
+  earlier by the same [`Emit`] statement, instead of evaluating it again.
+
+- Inlining replaces calls to small functions with a copy of their bodies.
+
+- Loop-invariant hoisting moves the expressions that compute the same value
+  on every iteration of a loop to before the loop.
+
+Passes enable each other: inlining exposes constant arguments to folding,
+and duplicated expressions to elimination. The result is a functionally
+identical module, which is valid if the original module was.
+
+[`Module`]: crate::Module
+[`Emit`]: crate::Statement::Emit
+*/
+
+mod analysis;
+mod hoist;
+mod rebuild;
+mod visit;
+
+use crate::{arena::Arena, Function, Handle, Statement};
+
+/// Which optimizations [`optimize`] applies.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Evaluate expressions with constant operands at compile time.
+    pub fold_constants: bool,
+    /// Remove unused expressions and stores to variables that are never
+    /// read.
+    pub eliminate_dead_code: bool,
+    /// Reuse identical expressions within an `Emit` statement.
+    pub eliminate_common_subexpressions: bool,
+    /// Inline the functions whose bodies have at most this many
+    /// expressions and statements, as well as the functions that are
+    /// called only once. Zero disables inlining.
+    ///
+    /// Functions that return from the middle of their bodies, rather than
+    /// only at their ends, are never inlined.
+    pub inline_size_limit: usize,
+    /// Move loop-invariant expressions out of loops.
+    pub hoist_loop_invariants: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            fold_constants: true,
+            eliminate_dead_code: true,
+            eliminate_common_subexpressions: true,
+            inline_size_limit: 32,
+            hoist_loop_invariants: true,
+        }
+    }
+}
+
+impl Options {
+    /// Options that don't apply any optimization.
+    ///
+    /// Enable the passes you want on top of this.
+    pub const fn none() -> Self {
+        Options {
+            fold_constants: false,
+            eliminate_dead_code: false,
+            eliminate_common_subexpressions: false,
+            inline_size_limit: 0,
+            hoist_loop_invariants: false,
+        }
+    }
+}
+
+/// Optimize every function and entry point of `module`.
+///
+/// Functions that are no longer called once all their calls have been
+/// inlined are removed from the module. Other functions are kept, even if
+/// they are not called.
+///
+/// # Panics
+///
+/// If `module` has not passed validation, this may panic.
+pub fn optimize(module: &mut crate::Module, options: &Options) {
+    let mut functions = std::mem::take(&mut module.functions);
+    let calls = count_calls(&functions, &module.entry_points);
+
+    // Callees always come before their callers in the arena, so they have
+    // been optimized, and possibly had calls inlined themselves, by the
+    // time their callers decide whether to inline them.
+    let handles = functions
+        .iter()
+        .map(|(handle, _)| handle)
+        .collect::<Vec<_>>();
+    let mut inline = vec![false; functions.len()];
+    for (index, &handle) in handles.iter().enumerate() {
+        log::trace!("optimizing function {:?}", functions[handle].name);
+        let new = rebuild::Rebuilder::new(module, &functions, options, &inline)
+            .rebuild(&functions[handle]);
+        functions[handle] = new;
+        if options.hoist_loop_invariants {
+            hoist::hoist_loop_invariants(&mut functions[handle]);
+        }
+        inline[index] = should_inline(&functions[handle], calls[index], options);
+    }
+
+    let mut entry_points = std::mem::take(&mut module.entry_points);
+    for entry_point in entry_points.iter_mut() {
+        log::trace!("optimizing entry point {:?}", entry_point.name);
+        let new = rebuild::Rebuilder::new(module, &functions, options, &inline)
+            .rebuild(&entry_point.function);
+        entry_point.function = new;
+        if options.hoist_loop_invariants {
+            hoist::hoist_loop_invariants(&mut entry_point.function);
+        }
+    }
+
+    // Remove the functions that were called before inlining, but aren't
+    // anymore.
+    let remaining_calls = count_calls(&functions, &entry_points);
+    let spans = handles
+        .iter()
+        .map(|&handle| functions.get_span(handle))
+        .collect::<Vec<_>>();
+    let mut new_handles = Vec::with_capacity(functions.len());
+    let mut new_functions = Arena::new();
+    for (index, (_, function)) in functions.iter_mut().enumerate() {
+        if calls[index] > 0 && remaining_calls[index] == 0 {
+            log::trace!("removing inlined function {:?}", function.name);
+            new_handles.push(None);
+        } else {
+            let new = new_functions.append(std::mem::take(function), spans[index]);
+            new_handles.push(Some(new));
+        }
+    }
+    let adjust = |handle: &mut Handle<Function>| {
+        *handle = new_handles[handle.index()].expect("Call to a removed function");
+    };
+    for (_, function) in new_functions.iter_mut() {
+        adjust_function_handles(function, adjust);
+    }
+    for entry_point in entry_points.iter_mut() {
+        adjust_function_handles(&mut entry_point.function, adjust);
+    }
+
+    module.functions = new_functions;
+    module.entry_points = entry_points;
+}
+
+/// Count the calls to each function of `functions`.
+fn count_calls(functions: &Arena<Function>, entry_points: &[crate::EntryPoint]) -> Vec<usize> {
+    let mut calls = vec![0; functions.len()];
+    let bodies = functions.iter().map(|(_, function)| &function.body).chain(
+        entry_points
+            .iter()
+            .map(|entry_point| &entry_point.function.body),
+    );
+    for body in bodies {
+        visit::walk_statements(body, &mut |statement| {
+            if let Statement::Call { function, .. } = *statement {
+                calls[function.index()] += 1;
+            }
+        });
+    }
+    calls
+}
+
+/// Decide whether calls to `function` should be inlined.
+fn should_inline(function: &Function, calls: usize, options: &Options) -> bool {
+    if options.inline_size_limit == 0 || calls == 0 {
+        return false;
+    }
+    if !rebuild::returns_only_at_end(&function.body, true) {
+        return false;
+    }
+    let mut statements = 0;
+    visit::walk_statements(&function.body, &mut |_| statements += 1);
+    calls == 1 || function.expressions.len() + statements <= options.inline_size_limit
+}
+
+/// Apply `adjust` to every function handle in `function`.
+fn adjust_function_handles(function: &mut Function, mut adjust: impl FnMut(&mut Handle<Function>)) {
+    for (_, expr) in function.expressions.iter_mut() {
+        if let crate::Expression::CallResult(ref mut callee) = *expr {
+            adjust(callee);
+        }
+    }
+    visit::walk_statements_mut(&mut function.body, &mut |statement| {
+        if let Statement::Call {
+            function: ref mut callee,
+            ..
+        } = *statement
+        {
+            adjust(callee);
+        }
+    });
+}
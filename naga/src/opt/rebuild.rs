@@ -0,0 +1,643 @@
+//! Copying a function into fresh arenas, improving it along the way.
+//!
+//! Rather than editing a function's expression arena in place, which would
+//! require adjusting every handle after each removal or insertion, the
+//! [`Rebuilder`] walks the function's statements in order and appends to a
+//! new arena each expression that is still needed, right when its `Emit`
+//! statement is reached. This naturally:
+//!
+//! - drops expressions and stores that don't contribute to any statement,
+//!
+//! - folds expressions whose operands are all constant, using the same
+//!   [`ConstantEvaluator`] as the front ends,
+//!
+//! - reuses an equivalent expression emitted earlier by the same `Emit`
+//!   statement, instead of appending a duplicate, and
+//!
+//! - replaces calls to small functions with a copy of their bodies, since
+//!   the callee's expressions can be appended right where the call was.
+//!
+//! Since expressions are appended in the order they are used, the new arena
+//! still has every expression after its operands.
+
+use super::{analysis::Analysis, visit, Options};
+use crate::{
+    arena::Arena,
+    proc::{ConstantEvaluator, Emitter, ExpressionConstnessTracker},
+    Block, Expression, Function, Handle, LocalVariable, NamedExpressions, Span, Statement,
+    SwitchCase,
+};
+
+/// Where the values of `Return` statements go.
+enum Returns {
+    /// The function being rebuilt: `Return` statements are copied.
+    Keep,
+    /// An inlined function whose only value-returning `Return` is its last
+    /// statement. Holds the value once it has been reached.
+    Direct(Option<Handle<Expression>>),
+    /// An inlined function whose return values are stored in a local
+    /// variable of the caller.
+    Local(Handle<LocalVariable>),
+}
+
+/// A function being copied into the [`Rebuilder`]'s arenas.
+struct Source<'a> {
+    function: &'a Function,
+    analysis: Analysis,
+    /// The new handle of each expression of `function`, once copied.
+    expressions: Vec<Option<Handle<Expression>>>,
+    /// The new handle of each local variable of `function`, once copied.
+    local_variables: Vec<Option<Handle<LocalVariable>>>,
+    /// Whether the local variables are initialized by stores, rather than
+    /// by their declarations.
+    store_initializers: bool,
+    returns: Returns,
+}
+
+impl<'a> Source<'a> {
+    fn new(function: &'a Function, options: &Options, returns: Returns) -> Self {
+        Source {
+            function,
+            analysis: Analysis::new(function, options.eliminate_dead_code),
+            expressions: vec![None; function.expressions.len()],
+            local_variables: vec![None; function.local_variables.len()],
+            store_initializers: false,
+            returns,
+        }
+    }
+}
+
+/// Builder of the optimized copy of a single function.
+pub struct Rebuilder<'a> {
+    /// The module, for its types and constants.
+    ///
+    /// Its function arena has been moved into `functions`.
+    module: &'a mut crate::Module,
+    functions: &'a Arena<Function>,
+    options: &'a Options,
+    /// Whether calls to each function of `functions` should be inlined.
+    inline: &'a [bool],
+
+    expressions: Arena<Expression>,
+    local_variables: Arena<LocalVariable>,
+    named_expressions: NamedExpressions,
+    constness: ExpressionConstnessTracker,
+    emitter: Emitter,
+    /// Number of loops around the current statement.
+    loop_depth: usize,
+}
+
+impl<'a> Rebuilder<'a> {
+    pub fn new(
+        module: &'a mut crate::Module,
+        functions: &'a Arena<Function>,
+        options: &'a Options,
+        inline: &'a [bool],
+    ) -> Self {
+        Rebuilder {
+            module,
+            functions,
+            options,
+            inline,
+            expressions: Arena::new(),
+            local_variables: Arena::new(),
+            named_expressions: NamedExpressions::default(),
+            constness: ExpressionConstnessTracker::new(),
+            emitter: Emitter::default(),
+            loop_depth: 0,
+        }
+    }
+
+    /// Return an optimized copy of `function`.
+    pub fn rebuild(mut self, function: &Function) -> Function {
+        let mut source = Source::new(function, self.options, Returns::Keep);
+        if !self.options.eliminate_dead_code {
+            for (local, _) in function.local_variables.iter() {
+                self.local_variable(&mut source, local);
+            }
+        }
+        let body = self.block(&mut source, &function.body);
+        self.finish_local_variables(&mut source);
+
+        Function {
+            name: function.name.clone(),
+            arguments: function.arguments.clone(),
+            result: function.result.clone(),
+            local_variables: self.local_variables,
+            expressions: self.expressions,
+            named_expressions: self.named_expressions,
+            body,
+        }
+    }
+
+    fn block(&mut self, source: &mut Source, block: &Block) -> Block {
+        let mut out = Block::with_capacity(block.len());
+        for (statement, span) in block.span_iter() {
+            self.statement(source, statement, *span, &mut out);
+        }
+        out
+    }
+
+    fn statement(
+        &mut self,
+        source: &mut Source,
+        statement: &Statement,
+        span: Span,
+        out: &mut Block,
+    ) {
+        use Statement as S;
+        let statement = match *statement {
+            S::Emit(ref range) => {
+                // Candidates for reuse, with their operands already adjusted.
+                let mut emitted = Vec::new();
+                self.emitter.start(&self.expressions);
+                for handle in range.clone() {
+                    // Initializers of inlined local variables may have been
+                    // emitted at the start of the inlined body already.
+                    if source.analysis.is_live(handle)
+                        && source.expressions[handle.index()].is_none()
+                    {
+                        self.emit(source, handle, &mut emitted, out);
+                    }
+                }
+                out.extend(self.emitter.finish(&self.expressions));
+                return;
+            }
+            S::Block(ref block) => S::Block(self.block(source, block)),
+            S::If {
+                condition,
+                ref accept,
+                ref reject,
+            } => S::If {
+                condition: self.expression(source, condition, out),
+                accept: self.block(source, accept),
+                reject: self.block(source, reject),
+            },
+            S::Switch {
+                selector,
+                ref cases,
+            } => S::Switch {
+                selector: self.expression(source, selector, out),
+                cases: cases
+                    .iter()
+                    .map(|case| SwitchCase {
+                        value: case.value,
+                        body: self.block(source, &case.body),
+                        fall_through: case.fall_through,
+                    })
+                    .collect(),
+            },
+            S::Loop {
+                ref body,
+                ref continuing,
+                break_if,
+            } => {
+                self.loop_depth += 1;
+                let body = self.block(source, body);
+                let continuing = self.block(source, continuing);
+                self.loop_depth -= 1;
+                S::Loop {
+                    body,
+                    continuing,
+                    break_if: break_if.map(|condition| self.expression(source, condition, out)),
+                }
+            }
+            S::Break => S::Break,
+            S::Continue => S::Continue,
+            S::Kill => S::Kill,
+            S::Barrier(barrier) => S::Barrier(barrier),
+            S::Return { value } => {
+                let value = value.map(|value| self.expression(source, value, out));
+                match source.returns {
+                    Returns::Keep => S::Return { value },
+                    Returns::Direct(ref mut result) => {
+                        *result = value;
+                        return;
+                    }
+                    Returns::Local(local) => match value {
+                        Some(value) => S::Store {
+                            pointer: self.append(Expression::LocalVariable(local), span, out),
+                            value,
+                        },
+                        None => return,
+                    },
+                }
+            }
+            S::Store { pointer, value } => {
+                if source.analysis.is_dead_store(source.function, statement) {
+                    return;
+                }
+                S::Store {
+                    pointer: self.expression(source, pointer, out),
+                    value: self.expression(source, value, out),
+                }
+            }
+            S::ImageStore {
+                image,
+                coordinate,
+                array_index,
+                value,
+            } => S::ImageStore {
+                image: self.expression(source, image, out),
+                coordinate: self.expression(source, coordinate, out),
+                array_index: array_index.map(|index| self.expression(source, index, out)),
+                value: self.expression(source, value, out),
+            },
+            S::Atomic {
+                pointer,
+                fun,
+                value,
+                result,
+            } => S::Atomic {
+                pointer: self.expression(source, pointer, out),
+                fun: match fun {
+                    crate::AtomicFunction::Exchange { compare } => {
+                        crate::AtomicFunction::Exchange {
+                            compare: compare.map(|compare| self.expression(source, compare, out)),
+                        }
+                    }
+                    other => other,
+                },
+                value: self.expression(source, value, out),
+                result: self.result(source, result, out),
+            },
+            S::WorkGroupUniformLoad { pointer, result } => S::WorkGroupUniformLoad {
+                pointer: self.expression(source, pointer, out),
+                result: self.result(source, result, out),
+            },
+            S::Call {
+                function,
+                ref arguments,
+                result,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|&argument| self.expression(source, argument, out))
+                    .collect();
+                if self.inline[function.index()] {
+                    self.inline_call(source, function, arguments, result, span, out);
+                    return;
+                }
+                S::Call {
+                    function,
+                    arguments,
+                    result: result.map(|result| self.result(source, result, out)),
+                }
+            }
+            S::RayQuery { query, ref fun } => S::RayQuery {
+                query: self.expression(source, query, out),
+                fun: match *fun {
+                    crate::RayQueryFunction::Initialize {
+                        acceleration_structure,
+                        descriptor,
+                    } => crate::RayQueryFunction::Initialize {
+                        acceleration_structure: self.expression(
+                            source,
+                            acceleration_structure,
+                            out,
+                        ),
+                        descriptor: self.expression(source, descriptor, out),
+                    },
+                    crate::RayQueryFunction::Proceed { result } => {
+                        crate::RayQueryFunction::Proceed {
+                            result: self.result(source, result, out),
+                        }
+                    }
+                    crate::RayQueryFunction::Terminate => crate::RayQueryFunction::Terminate,
+                },
+            },
+        };
+        out.push(statement, span);
+    }
+
+    /// Copy the expression `handle` of an `Emit` statement.
+    ///
+    /// If an equivalent expression is in `emitted`, reuse it instead.
+    fn emit(
+        &mut self,
+        source: &mut Source,
+        handle: Handle<Expression>,
+        emitted: &mut Vec<(Expression, Handle<Expression>)>,
+        out: &mut Block,
+    ) {
+        let function = source.function;
+        let span = function.expressions.get_span(handle);
+        let mut expr = function.expressions[handle].clone();
+        visit::map_expression_operands(&mut expr, |operand| self.expression(source, operand, out));
+
+        let new = match emitted.iter().find(|&&(ref other, _)| *other == expr) {
+            Some(&(_, new)) => new,
+            None => {
+                let new = match self.fold(&expr, span, out) {
+                    Some(new) => new,
+                    None => self.append(expr.clone(), span, out),
+                };
+                if self.options.eliminate_common_subexpressions {
+                    emitted.push((expr, new));
+                }
+                new
+            }
+        };
+        source.expressions[handle.index()] = Some(new);
+        self.name(source, handle, new);
+    }
+
+    /// Try to evaluate `expr` at compile time.
+    fn fold(
+        &mut self,
+        expr: &Expression,
+        span: Span,
+        out: &mut Block,
+    ) -> Option<Handle<Expression>> {
+        if !self.options.fold_constants {
+            return None;
+        }
+        match *expr {
+            Expression::Compose { .. }
+            | Expression::Splat { .. }
+            | Expression::Swizzle { .. }
+            | Expression::Access { .. }
+            | Expression::AccessIndex { .. }
+            | Expression::Unary { .. }
+            | Expression::Binary { .. }
+            | Expression::Math { .. }
+            | Expression::As { .. } => {}
+            _ => return None,
+        }
+        let mut constant = true;
+        visit::expression_operands(expr, |operand| {
+            constant &= self.constness.is_const(operand);
+        });
+        if !constant {
+            return None;
+        }
+
+        let mut evaluator = ConstantEvaluator::for_wgsl_function(
+            self.module,
+            &mut self.expressions,
+            &mut self.constness,
+            &mut self.emitter,
+            out,
+        );
+        // Expressions that can't be evaluated, like integer divisions by
+        // zero, are simply left for the run time.
+        evaluator.try_eval_and_append(expr, span).ok()
+    }
+
+    /// Return the new handle of the expression `handle` of the source.
+    ///
+    /// Expressions that are not emitted, like literals, arguments and
+    /// pointers to variables, are appended the first time they are used.
+    fn expression(
+        &mut self,
+        source: &mut Source,
+        handle: Handle<Expression>,
+        out: &mut Block,
+    ) -> Handle<Expression> {
+        if let Some(new) = source.expressions[handle.index()] {
+            return new;
+        }
+        let function = source.function;
+        let span = function.expressions.get_span(handle);
+        let new = match function.expressions[handle] {
+            Expression::LocalVariable(local) => {
+                let local = self.local_variable(source, local);
+                self.append(Expression::LocalVariable(local), span, out)
+            }
+            ref expr if expr.needs_pre_emit() => self.append(expr.clone(), span, out),
+            ref expr => {
+                // Only the initializers of local variables are used without
+                // being emitted first.
+                let mut expr = expr.clone();
+                visit::map_expression_operands(&mut expr, |operand| {
+                    self.expression(source, operand, out)
+                });
+                self.append(expr, span, out)
+            }
+        };
+        source.expressions[handle.index()] = Some(new);
+        self.name(source, handle, new);
+        new
+    }
+
+    /// Copy the expression `handle`, produced by a statement.
+    fn result(
+        &mut self,
+        source: &mut Source,
+        handle: Handle<Expression>,
+        out: &mut Block,
+    ) -> Handle<Expression> {
+        let function = source.function;
+        let span = function.expressions.get_span(handle);
+        let new = self.append(function.expressions[handle].clone(), span, out);
+        source.expressions[handle.index()] = Some(new);
+        self.name(source, handle, new);
+        new
+    }
+
+    fn append(&mut self, expr: Expression, span: Span, out: &mut Block) -> Handle<Expression> {
+        let constant = match expr {
+            Expression::Literal(_) | Expression::ZeroValue(_) | Expression::Constant(_) => true,
+            Expression::Compose { ref components, .. } => components
+                .iter()
+                .all(|&component| self.constness.is_const(component)),
+            Expression::Splat { value, .. } => self.constness.is_const(value),
+            _ => false,
+        };
+
+        // Expressions that don't need to be emitted can't be part of an
+        // `Emit` range, so interrupt the current one, if any.
+        let interrupt = expr.needs_pre_emit() && self.emitter.is_running();
+        if interrupt {
+            out.extend(self.emitter.finish(&self.expressions));
+        }
+        let handle = self.expressions.append(expr, span);
+        if interrupt {
+            self.emitter.start(&self.expressions);
+        }
+
+        if constant {
+            self.constness.insert(handle);
+        }
+        handle
+    }
+
+    /// Give `new` the name of the source's expression `old`, if it has one and
+    /// `new` doesn't.
+    fn name(&mut self, source: &Source, old: Handle<Expression>, new: Handle<Expression>) {
+        if let Some(name) = source.function.named_expressions.get(&old) {
+            self.named_expressions
+                .entry(new)
+                .or_insert_with(|| name.clone());
+        }
+    }
+
+    fn local_variable(
+        &mut self,
+        source: &mut Source,
+        local: Handle<LocalVariable>,
+    ) -> Handle<LocalVariable> {
+        if let Some(new) = source.local_variables[local.index()] {
+            return new;
+        }
+        let function = source.function;
+        let variable = &function.local_variables[local];
+        let new = self.local_variables.append(
+            LocalVariable {
+                name: variable.name.clone(),
+                ty: variable.ty,
+                // Set by `finish_local_variables`, once the initializer
+                // has been emitted.
+                init: None,
+            },
+            function.local_variables.get_span(local),
+        );
+        source.local_variables[local.index()] = Some(new);
+        new
+    }
+
+    /// Set the initializers of the local variables copied from `source`.
+    fn finish_local_variables(&mut self, source: &mut Source) {
+        if source.store_initializers {
+            return;
+        }
+        let function = source.function;
+        let mut out = Block::new();
+        for (local, variable) in function.local_variables.iter() {
+            if let (Some(new), Some(init)) = (source.local_variables[local.index()], variable.init)
+            {
+                let init = self.expression(source, init, &mut out);
+                self.local_variables[new].init = Some(init);
+            }
+        }
+        debug_assert!(out.is_empty());
+    }
+
+    /// Store the initial values of the local variables of `source` that are
+    /// read.
+    fn store_initializers(&mut self, source: &mut Source, span: Span, out: &mut Block) {
+        let function = source.function;
+        for (local, variable) in function.local_variables.iter() {
+            if !source.analysis.is_read(local) {
+                continue;
+            }
+            let value = match variable.init {
+                Some(init) => {
+                    self.emitter.start(&self.expressions);
+                    let value = self.expression(source, init, out);
+                    out.extend(self.emitter.finish(&self.expressions));
+                    value
+                }
+                None => self.append(Expression::ZeroValue(variable.ty), span, out),
+            };
+            let local = self.local_variable(source, local);
+            let pointer = self.append(Expression::LocalVariable(local), span, out);
+            out.push(Statement::Store { pointer, value }, span);
+        }
+    }
+
+    /// Copy the body of `function` in place of a call to it.
+    fn inline_call(
+        &mut self,
+        caller: &mut Source,
+        function: Handle<Function>,
+        arguments: Vec<Handle<Expression>>,
+        result: Option<Handle<Expression>>,
+        span: Span,
+        out: &mut Block,
+    ) {
+        let callee = &self.functions[function];
+        let returns = match callee.result {
+            Some(ref callee_result) if result.is_some() && !returns_directly(callee) => {
+                let local = self.local_variables.append(
+                    LocalVariable {
+                        name: callee.name.clone(),
+                        ty: callee_result.ty,
+                        init: None,
+                    },
+                    span,
+                );
+                Returns::Local(local)
+            }
+            _ => Returns::Direct(None),
+        };
+
+        let mut source = Source::new(callee, self.options, returns);
+        for (handle, expr) in callee.expressions.iter() {
+            if let Expression::FunctionArgument(index) = *expr {
+                source.expressions[handle.index()] = Some(arguments[index as usize]);
+            }
+        }
+        if self.loop_depth > 0 {
+            // Each call starts with freshly initialized local variables,
+            // while the variables of the caller are initialized only once.
+            source.store_initializers = true;
+            self.store_initializers(&mut source, span, out);
+        }
+        let body = self.block(&mut source, &callee.body);
+        out.extend_block(body);
+        self.finish_local_variables(&mut source);
+
+        if let Some(result) = result {
+            let value = match source.returns {
+                Returns::Direct(value) => value.expect("Inlined function returns no value"),
+                Returns::Local(local) => {
+                    let pointer = self.append(Expression::LocalVariable(local), span, out);
+                    self.emitter.start(&self.expressions);
+                    let value = self.expressions.append(Expression::Load { pointer }, span);
+                    out.extend(self.emitter.finish(&self.expressions));
+                    value
+                }
+                Returns::Keep => unreachable!(),
+            };
+            caller.expressions[result.index()] = Some(value);
+            self.name(caller, result, value);
+        }
+    }
+}
+
+/// Whether the only `Return` statement of `function` with a value is its last
+/// statement.
+fn returns_directly(function: &Function) -> bool {
+    let mut count = 0;
+    visit::walk_statements(&function.body, &mut |statement| {
+        if let Statement::Return { value: Some(_) } = *statement {
+            count += 1;
+        }
+    });
+    count == 1
+        && matches!(
+            function.body.last(),
+            Some(&Statement::Return { value: Some(_) })
+        )
+}
+
+/// Whether every `Return` statement of `block` is the last statement to be
+/// executed when `block` is the body of a function.
+///
+/// Inlining replaces such `Return` statements with nothing, or with a store
+/// to the variable holding the result, since the end of the copied body is
+/// reached right after them.
+pub fn returns_only_at_end(block: &Block, at_end: bool) -> bool {
+    block.iter().enumerate().all(|(index, statement)| {
+        let last = at_end && index + 1 == block.len();
+        match *statement {
+            Statement::Return { .. } => last,
+            Statement::Block(ref block) => returns_only_at_end(block, last),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => returns_only_at_end(accept, last) && returns_only_at_end(reject, last),
+            Statement::Switch { ref cases, .. } => cases
+                .iter()
+                .all(|case| returns_only_at_end(&case.body, false)),
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => returns_only_at_end(body, false) && returns_only_at_end(continuing, false),
+            _ => true,
+        }
+    })
+}
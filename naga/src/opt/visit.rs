@@ -0,0 +1,148 @@
+//! Enumerating the expression operands of expressions and statements.
+
+use crate::{Expression, Handle, RayQueryFunction, Statement};
+
+pub use crate::proc::{expression_operands, map_expression_operands};
+
+/// Call `f` on every expression that `statement` uses as an operand.
+///
+/// This doesn't include the expressions that `statement` emits or
+/// produces as a result, nor the operands of nested blocks.
+pub fn statement_operands(statement: &Statement, mut f: impl FnMut(Handle<Expression>)) {
+    match *statement {
+        Statement::Emit(_)
+        | Statement::Block(_)
+        | Statement::Break
+        | Statement::Continue
+        | Statement::Kill
+        | Statement::Barrier(_) => {}
+        Statement::If { condition, .. } => f(condition),
+        Statement::Switch { selector, .. } => f(selector),
+        Statement::Loop { break_if, .. } => break_if.into_iter().for_each(f),
+        Statement::Return { value } => value.into_iter().for_each(f),
+        Statement::Store { pointer, value } => {
+            f(pointer);
+            f(value);
+        }
+        Statement::ImageStore {
+            image,
+            coordinate,
+            array_index,
+            value,
+        } => {
+            f(image);
+            f(coordinate);
+            array_index.into_iter().for_each(&mut f);
+            f(value);
+        }
+        Statement::Atomic {
+            pointer,
+            ref fun,
+            value,
+            result: _,
+        } => {
+            f(pointer);
+            if let crate::AtomicFunction::Exchange {
+                compare: Some(compare),
+            } = *fun
+            {
+                f(compare);
+            }
+            f(value);
+        }
+        Statement::WorkGroupUniformLoad { pointer, result: _ } => f(pointer),
+        Statement::Call { ref arguments, .. } => arguments.iter().copied().for_each(f),
+        Statement::RayQuery { query, ref fun } => {
+            f(query);
+            if let RayQueryFunction::Initialize {
+                acceleration_structure,
+                descriptor,
+            } = *fun
+            {
+                f(acceleration_structure);
+                f(descriptor);
+            }
+        }
+    }
+}
+
+/// Return the expression that `statement` produces as a result, if any.
+pub const fn statement_result(statement: &Statement) -> Option<Handle<Expression>> {
+    match *statement {
+        Statement::Atomic { result, .. } | Statement::WorkGroupUniformLoad { result, .. } => {
+            Some(result)
+        }
+        Statement::Call { result, .. } => result,
+        Statement::RayQuery {
+            fun: RayQueryFunction::Proceed { result },
+            ..
+        } => Some(result),
+        _ => None,
+    }
+}
+
+/// Call `f` on every block nested directly in `statement`.
+pub fn nested_blocks(statement: &Statement, mut f: impl FnMut(&crate::Block)) {
+    match *statement {
+        Statement::Block(ref block) => f(block),
+        Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => {
+            f(accept);
+            f(reject);
+        }
+        Statement::Switch { ref cases, .. } => cases.iter().for_each(|case| f(&case.body)),
+        Statement::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => {
+            f(body);
+            f(continuing);
+        }
+        _ => {}
+    }
+}
+
+/// Call `f` on every statement of `block`, including nested ones, before
+/// the statements nested in them.
+pub fn walk_statements(block: &crate::Block, f: &mut impl FnMut(&Statement)) {
+    for statement in block.iter() {
+        f(statement);
+        nested_blocks(statement, |block| walk_statements(block, f));
+    }
+}
+
+/// Call `f` on every statement of `block`, including nested ones.
+pub fn walk_statements_mut(block: &mut crate::Block, f: &mut impl FnMut(&mut Statement)) {
+    for statement in block.iter_mut() {
+        f(statement);
+        match *statement {
+            Statement::Block(ref mut block) => walk_statements_mut(block, f),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                walk_statements_mut(accept, f);
+                walk_statements_mut(reject, f);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    walk_statements_mut(&mut case.body, f);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                walk_statements_mut(body, f);
+                walk_statements_mut(continuing, f);
+            }
+            _ => {}
+        }
+    }
+}
@@ -78,7 +78,7 @@ impl ExpressionConstnessTracker {
         self.inner.remove(value.index());
     }
 
-    fn insert(&mut self, value: Handle<Expression>) {
+    pub(crate) fn insert(&mut self, value: Handle<Expression>) {
         self.inner.insert(value.index());
     }
 
@@ -173,6 +173,10 @@ pub enum ConstantEvaluatorError {
     RemainderByZero,
     #[error("RHS of shift operation is greater than or equal to 32")]
     ShiftedMoreThan32Bits,
+    #[error("RHS of shift operation is greater than or equal to 64")]
+    ShiftedMoreThan64Bits,
+    #[error("{value} is not representable as {to}")]
+    NotRepresentable { value: String, to: &'static str },
     #[error(transparent)]
     Literal(#[from] crate::valid::LiteralError),
 }
@@ -579,6 +583,16 @@ impl<'a> ConstantEvaluator<'a> {
         match fun {
             crate::MathFunction::Pow => self.math_pow(arg, arg1.unwrap(), span),
             crate::MathFunction::Clamp => self.math_clamp(arg, arg1.unwrap(), arg2.unwrap(), span),
+            crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                self.math_dot4_packed(fun, arg, arg1.unwrap(), span)
+            }
+            crate::MathFunction::Pack4xI8
+            | crate::MathFunction::Pack4xU8
+            | crate::MathFunction::Pack4xI8Clamp
+            | crate::MathFunction::Pack4xU8Clamp => self.math_pack4x8(fun, arg, span),
+            crate::MathFunction::Unpack4xI8 | crate::MathFunction::Unpack4xU8 => {
+                self.math_unpack4x8(fun, arg, span)
+            }
             fun => Err(ConstantEvaluatorError::NotImplemented(format!(
                 "{fun:?} built-in function"
             ))),
@@ -595,9 +609,13 @@ impl<'a> ConstantEvaluator<'a> {
         let e2 = self.eval_zero_value_and_splat(e2, span)?;
 
         let expr = match (&self.expressions[e1], &self.expressions[e2]) {
-            (&Expression::Literal(Literal::F32(a)), &Expression::Literal(Literal::F32(b))) => {
-                Expression::Literal(Literal::F32(a.powf(b)))
-            }
+            (&Expression::Literal(a), &Expression::Literal(b)) => match unify_abstract(a, b)? {
+                (Literal::F32(a), Literal::F32(b)) => Expression::Literal(Literal::F32(a.powf(b))),
+                (Literal::AbstractFloat(a), Literal::AbstractFloat(b)) => {
+                    Expression::Literal(Literal::AbstractFloat(a.powf(b)))
+                }
+                _ => return Err(ConstantEvaluatorError::InvalidMathArg),
+            },
             (
                 &Expression::Compose {
                     components: ref src_components0,
@@ -648,6 +666,116 @@ impl<'a> ConstantEvaluator<'a> {
         self.register_evaluated_expr(expr, span)
     }
 
+    /// Return the value of the `u32` literal `expr`.
+    fn u32_literal(
+        &mut self,
+        expr: Handle<Expression>,
+        span: Span,
+    ) -> Result<u32, ConstantEvaluatorError> {
+        let expr = self.eval_zero_value_and_splat(expr, span)?;
+        match self.expressions[expr] {
+            Expression::Literal(Literal::U32(value)) => Ok(value),
+            _ => Err(ConstantEvaluatorError::InvalidMathArg),
+        }
+    }
+
+    fn math_dot4_packed(
+        &mut self,
+        fun: crate::MathFunction,
+        e1: Handle<Expression>,
+        e2: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let a = self.u32_literal(e1, span)?.to_le_bytes();
+        let b = self.u32_literal(e2, span)?.to_le_bytes();
+
+        let literal = if fun == crate::MathFunction::Dot4I8Packed {
+            Literal::I32(
+                a.iter()
+                    .zip(b)
+                    .map(|(&a, b)| a as i8 as i32 * b as i8 as i32)
+                    .sum(),
+            )
+        } else {
+            Literal::U32(a.iter().zip(b).map(|(&a, b)| a as u32 * b as u32).sum())
+        };
+        self.register_evaluated_expr(Expression::Literal(literal), span)
+    }
+
+    fn math_pack4x8(
+        &mut self,
+        fun: crate::MathFunction,
+        e: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let e = self.eval_zero_value_and_splat(e, span)?;
+        let (ty, src_components) = match self.expressions[e] {
+            Expression::Compose { ty, ref components } => (ty, components.clone()),
+            _ => return Err(ConstantEvaluatorError::InvalidMathArg),
+        };
+        let components: Vec<_> =
+            crate::proc::flatten_compose(ty, &src_components, self.expressions, self.types)
+                .collect();
+
+        let mut bytes = [0u8; 4];
+        for (byte, component) in bytes.iter_mut().zip(components) {
+            let component = self.eval_zero_value_and_splat(component, span)?;
+            // The WGSL front end leaves arguments that are converted to their
+            // default concrete type abstract, so accept `AbstractInt` as `i32`.
+            let value = match self.expressions[component] {
+                Expression::Literal(Literal::I32(value)) => value as i64,
+                Expression::Literal(Literal::AbstractInt(value)) => value,
+                Expression::Literal(Literal::U32(value)) => value as i64,
+                _ => return Err(ConstantEvaluatorError::InvalidMathArg),
+            };
+            *byte = match fun {
+                crate::MathFunction::Pack4xI8Clamp => {
+                    value.clamp(i8::MIN as i64, i8::MAX as i64) as u8
+                }
+                crate::MathFunction::Pack4xU8Clamp => value.min(u8::MAX as i64) as u8,
+                _ => value as u8,
+            };
+        }
+
+        let expr = Expression::Literal(Literal::U32(u32::from_le_bytes(bytes)));
+        self.register_evaluated_expr(expr, span)
+    }
+
+    fn math_unpack4x8(
+        &mut self,
+        fun: crate::MathFunction,
+        e: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let bytes = self.u32_literal(e, span)?.to_le_bytes();
+
+        let kind = if fun == crate::MathFunction::Unpack4xI8 {
+            ScalarKind::Sint
+        } else {
+            ScalarKind::Uint
+        };
+        let mut components = Vec::with_capacity(bytes.len());
+        for byte in bytes {
+            let literal = match kind {
+                ScalarKind::Sint => Literal::I32(byte as i8 as i32),
+                _ => Literal::U32(byte as u32),
+            };
+            components.push(self.register_evaluated_expr(Expression::Literal(literal), span)?);
+        }
+        let ty = self.types.insert(
+            Type {
+                name: None,
+                inner: TypeInner::Vector {
+                    size: crate::VectorSize::Quad,
+                    kind,
+                    width: 4,
+                },
+            },
+            span,
+        );
+        self.register_evaluated_expr(Expression::Compose { ty, components }, span)
+    }
+
     fn math_clamp(
         &mut self,
         e: Handle<Expression>,
@@ -665,6 +793,9 @@ impl<'a> ConstantEvaluator<'a> {
             &self.expressions[high],
         ) {
             (&Expression::Literal(e), &Expression::Literal(low), &Expression::Literal(high)) => {
+                let (e, low) = unify_abstract(e, low)?;
+                let (e, high) = unify_abstract(e, high)?;
+                let (low, high) = unify_abstract(low, high)?;
                 let literal = match (e, low, high) {
                     (Literal::I32(e), Literal::I32(low), Literal::I32(high)) => {
                         if low > high {
@@ -680,6 +811,20 @@ impl<'a> ConstantEvaluator<'a> {
                             Literal::U32(e.clamp(low, high))
                         }
                     }
+                    (Literal::I64(e), Literal::I64(low), Literal::I64(high)) => {
+                        if low > high {
+                            return Err(ConstantEvaluatorError::InvalidClamp);
+                        } else {
+                            Literal::I64(e.clamp(low, high))
+                        }
+                    }
+                    (Literal::U64(e), Literal::U64(low), Literal::U64(high)) => {
+                        if low > high {
+                            return Err(ConstantEvaluatorError::InvalidClamp);
+                        } else {
+                            Literal::U64(e.clamp(low, high))
+                        }
+                    }
                     (Literal::F32(e), Literal::F32(low), Literal::F32(high)) => {
                         if low > high {
                             return Err(ConstantEvaluatorError::InvalidClamp);
@@ -687,6 +832,28 @@ impl<'a> ConstantEvaluator<'a> {
                             Literal::F32(e.clamp(low, high))
                         }
                     }
+                    (
+                        Literal::AbstractInt(e),
+                        Literal::AbstractInt(low),
+                        Literal::AbstractInt(high),
+                    ) => {
+                        if low > high {
+                            return Err(ConstantEvaluatorError::InvalidClamp);
+                        } else {
+                            Literal::AbstractInt(e.clamp(low, high))
+                        }
+                    }
+                    (
+                        Literal::AbstractFloat(e),
+                        Literal::AbstractFloat(low),
+                        Literal::AbstractFloat(high),
+                    ) => {
+                        if low > high {
+                            return Err(ConstantEvaluatorError::InvalidClamp);
+                        } else {
+                            Literal::AbstractFloat(e.clamp(low, high))
+                        }
+                    }
                     _ => return Err(ConstantEvaluatorError::InvalidMathArg),
                 };
                 Expression::Literal(literal)
@@ -840,6 +1007,7 @@ impl<'a> ConstantEvaluator<'a> {
                 Ok(0)
             }
             Expression::Literal(Literal::U32(index)) => Ok(index as usize),
+            Expression::Literal(Literal::AbstractInt(index)) if index >= 0 => Ok(index as usize),
             _ => Err(ConstantEvaluatorError::InvalidAccessIndexTy),
         }
     }
@@ -957,38 +1125,7 @@ impl<'a> ConstantEvaluator<'a> {
 
         let expr = match self.expressions[expr] {
             Expression::Literal(literal) => {
-                let literal = match (kind, target_width) {
-                    (ScalarKind::Sint, 4) => Literal::I32(match literal {
-                        Literal::I32(v) => v,
-                        Literal::U32(v) => v as i32,
-                        Literal::F32(v) => v as i32,
-                        Literal::Bool(v) => v as i32,
-                        Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
-                    }),
-                    (ScalarKind::Uint, 4) => Literal::U32(match literal {
-                        Literal::I32(v) => v as u32,
-                        Literal::U32(v) => v,
-                        Literal::F32(v) => v as u32,
-                        Literal::Bool(v) => v as u32,
-                        Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
-                    }),
-                    (ScalarKind::Float, 4) => Literal::F32(match literal {
-                        Literal::I32(v) => v as f32,
-                        Literal::U32(v) => v as f32,
-                        Literal::F32(v) => v,
-                        Literal::Bool(v) => v as u32 as f32,
-                        Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
-                    }),
-                    (ScalarKind::Bool, crate::BOOL_WIDTH) => Literal::Bool(match literal {
-                        Literal::I32(v) => v != 0,
-                        Literal::U32(v) => v != 0,
-                        Literal::F32(v) => v != 0.0,
-                        Literal::Bool(v) => v,
-                        Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
-                    }),
-                    _ => return Err(ConstantEvaluatorError::InvalidCastArg),
-                };
-                Expression::Literal(literal)
+                Expression::Literal(cast_literal(literal, kind, target_width)?)
             }
             Expression::Compose {
                 ty,
@@ -1029,6 +1166,103 @@ impl<'a> ConstantEvaluator<'a> {
         self.register_evaluated_expr(expr, span)
     }
 
+    /// Convert the scalar leaves of `expr` to `kind` and `target_width`,
+    /// like [`cast`], but also accept arrays.
+    ///
+    /// This is used for WGSL's automatic conversions, which apply to arrays
+    /// of abstract values as well as to vectors and matrices.
+    ///
+    /// [`cast`]: ConstantEvaluator::cast
+    pub fn cast_array(
+        &mut self,
+        expr: Handle<Expression>,
+        kind: ScalarKind,
+        target_width: crate::Bytes,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let expr = self.check_and_get(expr)?;
+        self.convert_leaves(expr, kind, target_width, span, &mut |literal| {
+            cast_literal(literal, kind, target_width)
+        })
+    }
+
+    /// Convert the [`AbstractInt`] leaves of `expr` to [`AbstractFloat`].
+    ///
+    /// [`AbstractInt`]: Literal::AbstractInt
+    /// [`AbstractFloat`]: Literal::AbstractFloat
+    pub fn cast_abstract_int_to_float(
+        &mut self,
+        expr: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let expr = self.check_and_get(expr)?;
+        self.convert_leaves(expr, ScalarKind::Float, 4, span, &mut |literal| {
+            Ok(match literal {
+                Literal::AbstractInt(v) => Literal::AbstractFloat(v as f64),
+                other => other,
+            })
+        })
+    }
+
+    /// Rebuild `expr` with `convert` applied to each of its scalar leaves.
+    ///
+    /// The types of the rebuilt vectors, matrices and arrays use `kind` and
+    /// `width` as their scalar. Since all the scalars we convert between are
+    /// four bytes wide, arrays keep their stride.
+    fn convert_leaves(
+        &mut self,
+        expr: Handle<Expression>,
+        kind: ScalarKind,
+        width: crate::Bytes,
+        span: Span,
+        convert: &mut dyn FnMut(Literal) -> Result<Literal, ConstantEvaluatorError>,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let expr = self.eval_zero_value_and_splat(expr, span)?;
+
+        let expr = match self.expressions[expr] {
+            Expression::Literal(literal) => Expression::Literal(convert(literal)?),
+            Expression::Compose {
+                ty,
+                components: ref src_components,
+            } => {
+                let mut components = src_components.clone();
+                for component in &mut components {
+                    *component = self.convert_leaves(*component, kind, width, span, convert)?;
+                }
+
+                let inner = match self.types[ty].inner {
+                    TypeInner::Vector { size, .. } => TypeInner::Vector { size, kind, width },
+                    TypeInner::Matrix { columns, rows, .. } => TypeInner::Matrix {
+                        columns,
+                        rows,
+                        width,
+                    },
+                    TypeInner::Array { size, stride, .. } => {
+                        let base = match self.expressions[components[0]] {
+                            Expression::Compose { ty, .. } => ty,
+                            Expression::Literal(literal) => self.types.insert(
+                                Type {
+                                    name: None,
+                                    inner: literal.ty_inner(),
+                                },
+                                span,
+                            ),
+                            _ => return Err(ConstantEvaluatorError::InvalidCastArg),
+                        };
+                        TypeInner::Array { base, size, stride }
+                    }
+                    _ => return Err(ConstantEvaluatorError::InvalidCastArg),
+                };
+                let ty = self.types.insert(Type { name: None, inner }, span);
+
+                Expression::Compose { ty, components }
+            }
+            _ => return Err(ConstantEvaluatorError::InvalidCastArg),
+        };
+
+        self.register_evaluated_expr(expr, span)
+    }
+
     fn unary_op(
         &mut self,
         op: UnaryOperator,
@@ -1042,6 +1276,15 @@ impl<'a> ConstantEvaluator<'a> {
                 UnaryOperator::Negate => match value {
                     Literal::I32(v) => Literal::I32(-v),
                     Literal::F32(v) => Literal::F32(-v),
+                    Literal::I64(v) => Literal::I64(
+                        v.checked_neg()
+                            .ok_or_else(|| ConstantEvaluatorError::Overflow("negation".into()))?,
+                    ),
+                    Literal::AbstractInt(v) => Literal::AbstractInt(
+                        v.checked_neg()
+                            .ok_or_else(|| ConstantEvaluatorError::Overflow("negation".into()))?,
+                    ),
+                    Literal::AbstractFloat(v) => Literal::AbstractFloat(-v),
                     _ => return Err(ConstantEvaluatorError::InvalidUnaryOpArg),
                 },
                 UnaryOperator::LogicalNot => match value {
@@ -1051,6 +1294,9 @@ impl<'a> ConstantEvaluator<'a> {
                 UnaryOperator::BitwiseNot => match value {
                     Literal::I32(v) => Literal::I32(!v),
                     Literal::U32(v) => Literal::U32(!v),
+                    Literal::I64(v) => Literal::I64(!v),
+                    Literal::U64(v) => Literal::U64(!v),
+                    Literal::AbstractInt(v) => Literal::AbstractInt(!v),
                     _ => return Err(ConstantEvaluatorError::InvalidUnaryOpArg),
                 },
             }),
@@ -1088,6 +1334,16 @@ impl<'a> ConstantEvaluator<'a> {
 
         let expr = match (&self.expressions[left], &self.expressions[right]) {
             (&Expression::Literal(left_value), &Expression::Literal(right_value)) => {
+                let (left_value, right_value) = match op {
+                    // The shift amount is always a `u32`.
+                    BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => match right_value {
+                        Literal::AbstractInt(_) => {
+                            (left_value, cast_literal(right_value, ScalarKind::Uint, 4)?)
+                        }
+                        _ => (left_value, right_value),
+                    },
+                    _ => unify_abstract(left_value, right_value)?,
+                };
                 let literal = match op {
                     BinaryOperator::Equal => Literal::Bool(left_value == right_value),
                     BinaryOperator::NotEqual => Literal::Bool(left_value != right_value),
@@ -1162,6 +1418,74 @@ impl<'a> ConstantEvaluator<'a> {
                                 .ok_or(ConstantEvaluatorError::ShiftedMoreThan32Bits)?,
                             _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
                         }),
+                        (Literal::I64(a), Literal::I64(b)) => Literal::I64(match op {
+                            BinaryOperator::Add => a.checked_add(b).ok_or_else(|| {
+                                ConstantEvaluatorError::Overflow("addition".into())
+                            })?,
+                            BinaryOperator::Subtract => a.checked_sub(b).ok_or_else(|| {
+                                ConstantEvaluatorError::Overflow("subtraction".into())
+                            })?,
+                            BinaryOperator::Multiply => a.checked_mul(b).ok_or_else(|| {
+                                ConstantEvaluatorError::Overflow("multiplication".into())
+                            })?,
+                            BinaryOperator::Divide => a.checked_div(b).ok_or_else(|| {
+                                if b == 0 {
+                                    ConstantEvaluatorError::DivisionByZero
+                                } else {
+                                    ConstantEvaluatorError::Overflow("division".into())
+                                }
+                            })?,
+                            BinaryOperator::Modulo => a.checked_rem(b).ok_or_else(|| {
+                                if b == 0 {
+                                    ConstantEvaluatorError::RemainderByZero
+                                } else {
+                                    ConstantEvaluatorError::Overflow("remainder".into())
+                                }
+                            })?,
+                            BinaryOperator::And => a & b,
+                            BinaryOperator::ExclusiveOr => a ^ b,
+                            BinaryOperator::InclusiveOr => a | b,
+                            _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                        }),
+                        (Literal::I64(a), Literal::U32(b)) => Literal::I64(match op {
+                            BinaryOperator::ShiftLeft => a
+                                .checked_shl(b)
+                                .ok_or(ConstantEvaluatorError::ShiftedMoreThan64Bits)?,
+                            BinaryOperator::ShiftRight => a
+                                .checked_shr(b)
+                                .ok_or(ConstantEvaluatorError::ShiftedMoreThan64Bits)?,
+                            _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                        }),
+                        (Literal::U64(a), Literal::U64(b)) => Literal::U64(match op {
+                            BinaryOperator::Add => a.checked_add(b).ok_or_else(|| {
+                                ConstantEvaluatorError::Overflow("addition".into())
+                            })?,
+                            BinaryOperator::Subtract => a.checked_sub(b).ok_or_else(|| {
+                                ConstantEvaluatorError::Overflow("subtraction".into())
+                            })?,
+                            BinaryOperator::Multiply => a.checked_mul(b).ok_or_else(|| {
+                                ConstantEvaluatorError::Overflow("multiplication".into())
+                            })?,
+                            BinaryOperator::Divide => a
+                                .checked_div(b)
+                                .ok_or(ConstantEvaluatorError::DivisionByZero)?,
+                            BinaryOperator::Modulo => a
+                                .checked_rem(b)
+                                .ok_or(ConstantEvaluatorError::RemainderByZero)?,
+                            BinaryOperator::And => a & b,
+                            BinaryOperator::ExclusiveOr => a ^ b,
+                            BinaryOperator::InclusiveOr => a | b,
+                            _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                        }),
+                        (Literal::U64(a), Literal::U32(b)) => Literal::U64(match op {
+                            BinaryOperator::ShiftLeft => a
+                                .checked_shl(b)
+                                .ok_or(ConstantEvaluatorError::ShiftedMoreThan64Bits)?,
+                            BinaryOperator::ShiftRight => a
+                                .checked_shr(b)
+                                .ok_or(ConstantEvaluatorError::ShiftedMoreThan64Bits)?,
+                            _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                        }),
                         (Literal::F32(a), Literal::F32(b)) => Literal::F32(match op {
                             BinaryOperator::Add => a + b,
                             BinaryOperator::Subtract => a - b,
@@ -1170,6 +1494,65 @@ impl<'a> ConstantEvaluator<'a> {
                             BinaryOperator::Modulo => a % b,
                             _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
                         }),
+                        (Literal::AbstractInt(a), Literal::AbstractInt(b)) => {
+                            Literal::AbstractInt(match op {
+                                BinaryOperator::Add => a.checked_add(b).ok_or_else(|| {
+                                    ConstantEvaluatorError::Overflow("addition".into())
+                                })?,
+                                BinaryOperator::Subtract => a.checked_sub(b).ok_or_else(|| {
+                                    ConstantEvaluatorError::Overflow("subtraction".into())
+                                })?,
+                                BinaryOperator::Multiply => a.checked_mul(b).ok_or_else(|| {
+                                    ConstantEvaluatorError::Overflow("multiplication".into())
+                                })?,
+                                BinaryOperator::Divide => a.checked_div(b).ok_or_else(|| {
+                                    if b == 0 {
+                                        ConstantEvaluatorError::DivisionByZero
+                                    } else {
+                                        ConstantEvaluatorError::Overflow("division".into())
+                                    }
+                                })?,
+                                BinaryOperator::Modulo => a.checked_rem(b).ok_or_else(|| {
+                                    if b == 0 {
+                                        ConstantEvaluatorError::RemainderByZero
+                                    } else {
+                                        ConstantEvaluatorError::Overflow("remainder".into())
+                                    }
+                                })?,
+                                BinaryOperator::And => a & b,
+                                BinaryOperator::ExclusiveOr => a ^ b,
+                                BinaryOperator::InclusiveOr => a | b,
+                                _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                            })
+                        }
+                        (Literal::AbstractInt(a), Literal::U32(b)) => {
+                            Literal::AbstractInt(match op {
+                                // Shifting an abstract integer left must not
+                                // lose any bits, including the sign.
+                                BinaryOperator::ShiftLeft => a
+                                    .checked_shl(b)
+                                    .filter(|shifted| shifted >> b == a)
+                                    .ok_or_else(|| {
+                                        ConstantEvaluatorError::Overflow("left shift".into())
+                                    })?,
+                                BinaryOperator::ShiftRight => {
+                                    a.checked_shr(b).ok_or_else(|| {
+                                        ConstantEvaluatorError::Overflow("right shift".into())
+                                    })?
+                                }
+                                _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                            })
+                        }
+                        (Literal::AbstractFloat(a), Literal::AbstractFloat(b)) => {
+                            Literal::AbstractFloat(match op {
+                                BinaryOperator::Add => a + b,
+                                BinaryOperator::Subtract => a - b,
+                                BinaryOperator::Multiply => a * b,
+                                BinaryOperator::Divide => a / b,
+                                BinaryOperator::Modulo => a % b,
+                                _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                            })
+                        }
                         (Literal::Bool(a), Literal::Bool(b)) => Literal::Bool(match op {
                             BinaryOperator::LogicalAnd => a && b,
                             BinaryOperator::LogicalOr => a || b,
@@ -1212,6 +1595,22 @@ impl<'a> ConstantEvaluator<'a> {
         self.register_evaluated_expr(expr, span)
     }
 
+    /// Make the module constant expression `expr` available in the arena
+    /// `self` contributes to.
+    ///
+    /// If `self` is building a function's expression arena, deep-copy
+    /// `expr` into it. Otherwise, `expr` is already in the right arena.
+    pub fn copy_const_expression(
+        &mut self,
+        expr: Handle<Expression>,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        if let Some(ref function_local_data) = self.function_local_data {
+            self.copy_from(expr, function_local_data.const_expressions)
+        } else {
+            Ok(expr)
+        }
+    }
+
     /// Deep copy `expr` from `expressions` into `self.expressions`.
     ///
     /// Return the root of the new copy.
@@ -1285,6 +1684,172 @@ impl<'a> ConstantEvaluator<'a> {
     }
 }
 
+/// Convert `literal` to a scalar of the given `kind` and `width`.
+///
+/// Concrete values are converted the way the target languages would.
+/// Abstract values must be representable in the target type.
+fn cast_literal(
+    literal: Literal,
+    kind: ScalarKind,
+    width: crate::Bytes,
+) -> Result<Literal, ConstantEvaluatorError> {
+    let not_representable = |to| ConstantEvaluatorError::NotRepresentable {
+        value: match literal {
+            Literal::AbstractInt(v) => v.to_string(),
+            Literal::AbstractFloat(v) => format!("{v:?}"),
+            _ => unreachable!(),
+        },
+        to,
+    };
+    Ok(match (kind, width) {
+        (ScalarKind::Sint, 4) => Literal::I32(match literal {
+            Literal::I32(v) => v,
+            Literal::U32(v) => v as i32,
+            Literal::I64(v) => v as i32,
+            Literal::U64(v) => v as i32,
+            Literal::F32(v) => v as i32,
+            Literal::Bool(v) => v as i32,
+            Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
+            Literal::AbstractInt(v) => i32::try_from(v).map_err(|_| not_representable("i32"))?,
+            Literal::AbstractFloat(v) => {
+                let v = v.trunc();
+                if v < i32::MIN as f64 || v > i32::MAX as f64 {
+                    return Err(not_representable("i32"));
+                }
+                v as i32
+            }
+        }),
+        (ScalarKind::Uint, 4) => Literal::U32(match literal {
+            Literal::I32(v) => v as u32,
+            Literal::U32(v) => v,
+            Literal::I64(v) => v as u32,
+            Literal::U64(v) => v as u32,
+            Literal::F32(v) => v as u32,
+            Literal::Bool(v) => v as u32,
+            Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
+            Literal::AbstractInt(v) => u32::try_from(v).map_err(|_| not_representable("u32"))?,
+            Literal::AbstractFloat(v) => {
+                let v = v.trunc();
+                if v < 0.0 || v > u32::MAX as f64 {
+                    return Err(not_representable("u32"));
+                }
+                v as u32
+            }
+        }),
+        (ScalarKind::Sint, 8) => Literal::I64(match literal {
+            Literal::I32(v) => v as i64,
+            Literal::U32(v) => v as i64,
+            Literal::I64(v) => v,
+            Literal::U64(v) => v as i64,
+            Literal::F32(v) => v as i64,
+            Literal::Bool(v) => v as i64,
+            Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
+            Literal::AbstractInt(v) => v,
+            Literal::AbstractFloat(v) => {
+                let v = v.trunc();
+                if v < i64::MIN as f64 || v >= i64::MAX as f64 {
+                    return Err(not_representable("i64"));
+                }
+                v as i64
+            }
+        }),
+        (ScalarKind::Uint, 8) => Literal::U64(match literal {
+            Literal::I32(v) => v as u64,
+            Literal::U32(v) => v as u64,
+            Literal::I64(v) => v as u64,
+            Literal::U64(v) => v,
+            Literal::F32(v) => v as u64,
+            Literal::Bool(v) => v as u64,
+            Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
+            Literal::AbstractInt(v) => u64::try_from(v).map_err(|_| not_representable("u64"))?,
+            Literal::AbstractFloat(v) => {
+                let v = v.trunc();
+                if v < 0.0 || v >= u64::MAX as f64 {
+                    return Err(not_representable("u64"));
+                }
+                v as u64
+            }
+        }),
+        (ScalarKind::Float, 4) => Literal::F32(match literal {
+            Literal::I32(v) => v as f32,
+            Literal::U32(v) => v as f32,
+            Literal::I64(v) => v as f32,
+            Literal::U64(v) => v as f32,
+            Literal::F32(v) => v,
+            Literal::Bool(v) => v as u32 as f32,
+            Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
+            Literal::AbstractInt(v) => v as f32,
+            Literal::AbstractFloat(v) => {
+                let v = v as f32;
+                if !v.is_finite() {
+                    return Err(not_representable("f32"));
+                }
+                v
+            }
+        }),
+        (ScalarKind::Float, 8) => Literal::F64(match literal {
+            Literal::AbstractInt(v) => v as f64,
+            Literal::AbstractFloat(v) => v,
+            _ => return Err(ConstantEvaluatorError::InvalidCastArg),
+        }),
+        (ScalarKind::Bool, crate::BOOL_WIDTH) => Literal::Bool(match literal {
+            Literal::I32(v) => v != 0,
+            Literal::U32(v) => v != 0,
+            Literal::I64(v) => v != 0,
+            Literal::U64(v) => v != 0,
+            Literal::F32(v) => v != 0.0,
+            Literal::Bool(v) => v,
+            Literal::F64(_) => return Err(ConstantEvaluatorError::InvalidCastArg),
+            Literal::AbstractInt(v) => v != 0,
+            Literal::AbstractFloat(v) => v != 0.0,
+        }),
+        _ => return Err(ConstantEvaluatorError::InvalidCastArg),
+    })
+}
+
+/// Apply WGSL's automatic conversions to a pair of operands.
+///
+/// If one operand is abstract and the other is concrete, convert the
+/// abstract operand to the concrete operand's type, if WGSL allows that
+/// conversion. If both are abstract, but only one is an [`AbstractFloat`],
+/// convert the other to [`AbstractFloat`] too. Return any other pair
+/// unchanged.
+///
+/// [`AbstractFloat`]: Literal::AbstractFloat
+fn unify_abstract(a: Literal, b: Literal) -> Result<(Literal, Literal), ConstantEvaluatorError> {
+    /// Return true if WGSL automatically converts `from` to the type of `to`.
+    const fn converts_to(from: Literal, to: Literal) -> bool {
+        match (from, to) {
+            (Literal::AbstractInt(_), Literal::AbstractFloat(_)) => true,
+            (Literal::AbstractInt(_), _) => {
+                matches!(
+                    to.scalar_kind(),
+                    ScalarKind::Sint | ScalarKind::Uint | ScalarKind::Float
+                ) && !matches!(to, Literal::AbstractInt(_))
+            }
+            (Literal::AbstractFloat(_), _) => matches!(to, Literal::F32(_) | Literal::F64(_)),
+            _ => false,
+        }
+    }
+
+    fn convert(from: Literal, to: Literal) -> Result<Literal, ConstantEvaluatorError> {
+        match (from, to) {
+            (Literal::AbstractInt(v), Literal::AbstractFloat(_)) => {
+                Ok(Literal::AbstractFloat(v as f64))
+            }
+            _ => cast_literal(from, to.scalar_kind(), to.width()),
+        }
+    }
+
+    Ok(if converts_to(a, b) {
+        (convert(a, b)?, b)
+    } else if converts_to(b, a) {
+        (a, convert(b, a)?)
+    } else {
+        (a, b)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::vec;
@@ -7,6 +7,7 @@ mod emitter;
 pub mod index;
 mod layouter;
 mod namer;
+mod specialize;
 mod terminator;
 mod typifier;
 
@@ -17,6 +18,9 @@ pub use emitter::Emitter;
 pub use index::{BoundsCheckPolicies, BoundsCheckPolicy, IndexableLength, IndexableLengthError};
 pub use layouter::{Alignment, LayoutError, LayoutErrorInner, Layouter, TypeLayout};
 pub use namer::{EntryPointIndex, NameKey, Namer};
+pub use specialize::{
+    find_unspecialized_pointer_argument, needs_pointer_specialization, specialize_pointer_arguments,
+};
 pub use terminator::ensure_block_returns;
 pub use typifier::{ResolveContext, ResolveError, TypeResolution};
 
@@ -84,7 +88,11 @@ impl PartialEq for crate::Literal {
             (Self::F32(a), Self::F32(b)) => a.to_bits() == b.to_bits(),
             (Self::U32(a), Self::U32(b)) => a == b,
             (Self::I32(a), Self::I32(b)) => a == b,
+            (Self::U64(a), Self::U64(b)) => a == b,
+            (Self::I64(a), Self::I64(b)) => a == b,
             (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::AbstractInt(a), Self::AbstractInt(b)) => a == b,
+            (Self::AbstractFloat(a), Self::AbstractFloat(b)) => a.to_bits() == b.to_bits(),
             _ => false,
         }
     }
@@ -113,6 +121,22 @@ impl std::hash::Hash for crate::Literal {
                 hasher.write_u8(4);
                 v.hash(hasher);
             }
+            Self::AbstractInt(v) => {
+                hasher.write_u8(5);
+                v.hash(hasher);
+            }
+            Self::AbstractFloat(v) => {
+                hasher.write_u8(6);
+                v.to_bits().hash(hasher);
+            }
+            Self::U64(v) => {
+                hasher.write_u8(7);
+                v.hash(hasher);
+            }
+            Self::I64(v) => {
+                hasher.write_u8(8);
+                v.hash(hasher);
+            }
         }
     }
 }
@@ -124,6 +148,8 @@ impl crate::Literal {
             (value, crate::ScalarKind::Float, 4) => Some(Self::F32(value as _)),
             (value, crate::ScalarKind::Uint, 4) => Some(Self::U32(value as _)),
             (value, crate::ScalarKind::Sint, 4) => Some(Self::I32(value as _)),
+            (value, crate::ScalarKind::Uint, 8) => Some(Self::U64(value as _)),
+            (value, crate::ScalarKind::Sint, 8) => Some(Self::I64(value as _)),
             (1, crate::ScalarKind::Bool, 4) => Some(Self::Bool(true)),
             (0, crate::ScalarKind::Bool, 4) => Some(Self::Bool(false)),
             _ => None,
@@ -140,16 +166,18 @@ impl crate::Literal {
 
     pub const fn width(&self) -> crate::Bytes {
         match *self {
-            Self::F64(_) => 8,
+            Self::F64(_) | Self::U64(_) | Self::I64(_) => 8,
             Self::F32(_) | Self::U32(_) | Self::I32(_) => 4,
             Self::Bool(_) => 1,
+            // Abstract literals have the width of their default concretization.
+            Self::AbstractInt(_) | Self::AbstractFloat(_) => 4,
         }
     }
     pub const fn scalar_kind(&self) -> crate::ScalarKind {
         match *self {
-            Self::F64(_) | Self::F32(_) => crate::ScalarKind::Float,
-            Self::U32(_) => crate::ScalarKind::Uint,
-            Self::I32(_) => crate::ScalarKind::Sint,
+            Self::F64(_) | Self::F32(_) | Self::AbstractFloat(_) => crate::ScalarKind::Float,
+            Self::U32(_) | Self::U64(_) => crate::ScalarKind::Uint,
+            Self::I32(_) | Self::I64(_) | Self::AbstractInt(_) => crate::ScalarKind::Sint,
             Self::Bool(_) => crate::ScalarKind::Bool,
         }
     }
@@ -394,6 +422,8 @@ impl super::MathFunction {
             Self::Pow => 2,
             // geometry
             Self::Dot => 2,
+            Self::Dot4I8Packed => 2,
+            Self::Dot4U8Packed => 2,
             Self::Outer => 2,
             Self::Cross => 2,
             Self::Distance => 2,
@@ -413,6 +443,7 @@ impl super::MathFunction {
             Self::Inverse => 1,
             Self::Transpose => 1,
             Self::Determinant => 1,
+            Self::QuantizeToF16 => 1,
             // bits
             Self::CountTrailingZeros => 1,
             Self::CountLeadingZeros => 1,
@@ -428,12 +459,18 @@ impl super::MathFunction {
             Self::Pack2x16snorm => 1,
             Self::Pack2x16unorm => 1,
             Self::Pack2x16float => 1,
+            Self::Pack4xI8 => 1,
+            Self::Pack4xU8 => 1,
+            Self::Pack4xI8Clamp => 1,
+            Self::Pack4xU8Clamp => 1,
             // data unpacking
             Self::Unpack4x8snorm => 1,
             Self::Unpack4x8unorm => 1,
             Self::Unpack2x16snorm => 1,
             Self::Unpack2x16unorm => 1,
             Self::Unpack2x16float => 1,
+            Self::Unpack4xI8 => 1,
+            Self::Unpack4xU8 => 1,
         }
     }
 }
@@ -547,14 +584,14 @@ impl super::ImageClass {
     pub const fn is_multisampled(self) -> bool {
         match self {
             crate::ImageClass::Sampled { multi, .. } | crate::ImageClass::Depth { multi } => multi,
-            crate::ImageClass::Storage { .. } => false,
+            crate::ImageClass::Storage { .. } | crate::ImageClass::External => false,
         }
     }
 
     pub const fn is_mipmapped(self) -> bool {
         match self {
             crate::ImageClass::Sampled { multi, .. } | crate::ImageClass::Depth { multi } => !multi,
-            crate::ImageClass::Storage { .. } => false,
+            crate::ImageClass::Storage { .. } | crate::ImageClass::External => false,
         }
     }
 }
@@ -603,6 +640,13 @@ impl GlobalCtx<'_> {
             Some(crate::Literal::I32(value)) => {
                 value.try_into().map_err(|_| U32EvalError::Negative)
             }
+            Some(crate::Literal::AbstractInt(value)) => value.try_into().map_err(|_| {
+                if value < 0 {
+                    U32EvalError::Negative
+                } else {
+                    U32EvalError::NonConst
+                }
+            }),
             _ => Err(U32EvalError::NonConst),
         }
     }
@@ -698,6 +742,232 @@ pub fn flatten_compose<'arenas>(
         .cloned()
 }
 
+/// Call `f` on every expression that `expr` uses as an operand.
+pub fn expression_operands(
+    expr: &crate::Expression,
+    mut f: impl FnMut(crate::Handle<crate::Expression>),
+) {
+    match *expr {
+        crate::Expression::Literal(_)
+        | crate::Expression::Constant(_)
+        | crate::Expression::ZeroValue(_)
+        | crate::Expression::FunctionArgument(_)
+        | crate::Expression::GlobalVariable(_)
+        | crate::Expression::LocalVariable(_)
+        | crate::Expression::CallResult(_)
+        | crate::Expression::AtomicResult { .. }
+        | crate::Expression::WorkGroupUniformLoadResult { .. }
+        | crate::Expression::RayQueryProceedResult => {}
+        crate::Expression::Compose { ref components, .. } => components.iter().copied().for_each(f),
+        crate::Expression::Access { base, index } => {
+            f(base);
+            f(index);
+        }
+        crate::Expression::AccessIndex { base, .. } => f(base),
+        crate::Expression::Splat { value, .. } => f(value),
+        crate::Expression::Swizzle { vector, .. } => f(vector),
+        crate::Expression::Load { pointer } => f(pointer),
+        crate::Expression::ImageSample {
+            image,
+            sampler,
+            coordinate,
+            array_index,
+            offset: _,
+            level,
+            depth_ref,
+            gather: _,
+        } => {
+            f(image);
+            f(sampler);
+            f(coordinate);
+            array_index.into_iter().for_each(&mut f);
+            match level {
+                crate::SampleLevel::Auto | crate::SampleLevel::Zero => {}
+                crate::SampleLevel::Exact(h) | crate::SampleLevel::Bias(h) => f(h),
+                crate::SampleLevel::Gradient { x, y } => {
+                    f(x);
+                    f(y);
+                }
+            }
+            depth_ref.into_iter().for_each(f);
+        }
+        crate::Expression::ImageLoad {
+            image,
+            coordinate,
+            array_index,
+            sample,
+            level,
+        } => {
+            f(image);
+            f(coordinate);
+            array_index.into_iter().for_each(&mut f);
+            sample.into_iter().for_each(&mut f);
+            level.into_iter().for_each(f);
+        }
+        crate::Expression::ImageQuery { image, query } => {
+            f(image);
+            if let crate::ImageQuery::Size { level: Some(level) } = query {
+                f(level);
+            }
+        }
+        crate::Expression::Unary { expr, .. } => f(expr),
+        crate::Expression::Binary { left, right, .. } => {
+            f(left);
+            f(right);
+        }
+        crate::Expression::Select {
+            condition,
+            accept,
+            reject,
+        } => {
+            f(condition);
+            f(accept);
+            f(reject);
+        }
+        crate::Expression::Derivative { expr, .. } => f(expr),
+        crate::Expression::Relational { argument, .. } => f(argument),
+        crate::Expression::Math {
+            arg,
+            arg1,
+            arg2,
+            arg3,
+            ..
+        } => {
+            f(arg);
+            arg1.into_iter().chain(arg2).chain(arg3).for_each(f);
+        }
+        crate::Expression::As { expr, .. } => f(expr),
+        crate::Expression::ArrayLength(expr) => f(expr),
+        crate::Expression::RayQueryGetIntersection { query, .. } => f(query),
+    }
+}
+
+/// Replace every expression that `expr` uses as an operand with `f` of it.
+pub fn map_expression_operands(
+    expr: &mut crate::Expression,
+    mut f: impl FnMut(crate::Handle<crate::Expression>) -> crate::Handle<crate::Expression>,
+) {
+    use crate::{Expression, SampleLevel};
+
+    let mut map = |handle: &mut crate::Handle<Expression>| *handle = f(*handle);
+    match *expr {
+        Expression::Literal(_)
+        | Expression::Constant(_)
+        | Expression::ZeroValue(_)
+        | Expression::FunctionArgument(_)
+        | Expression::GlobalVariable(_)
+        | Expression::LocalVariable(_)
+        | Expression::CallResult(_)
+        | Expression::AtomicResult { .. }
+        | Expression::WorkGroupUniformLoadResult { .. }
+        | Expression::RayQueryProceedResult => {}
+        Expression::Compose {
+            ref mut components, ..
+        } => components.iter_mut().for_each(map),
+        Expression::Access {
+            ref mut base,
+            ref mut index,
+        } => {
+            map(base);
+            map(index);
+        }
+        Expression::AccessIndex { ref mut base, .. } => map(base),
+        Expression::Splat { ref mut value, .. } => map(value),
+        Expression::Swizzle { ref mut vector, .. } => map(vector),
+        Expression::Load { ref mut pointer } => map(pointer),
+        Expression::ImageSample {
+            ref mut image,
+            ref mut sampler,
+            ref mut coordinate,
+            ref mut array_index,
+            offset: _,
+            ref mut level,
+            ref mut depth_ref,
+            gather: _,
+        } => {
+            map(image);
+            map(sampler);
+            map(coordinate);
+            array_index.iter_mut().for_each(&mut map);
+            match *level {
+                SampleLevel::Auto | SampleLevel::Zero => {}
+                SampleLevel::Exact(ref mut h) | SampleLevel::Bias(ref mut h) => map(h),
+                SampleLevel::Gradient {
+                    ref mut x,
+                    ref mut y,
+                } => {
+                    map(x);
+                    map(y);
+                }
+            }
+            depth_ref.iter_mut().for_each(map);
+        }
+        Expression::ImageLoad {
+            ref mut image,
+            ref mut coordinate,
+            ref mut array_index,
+            ref mut sample,
+            ref mut level,
+        } => {
+            map(image);
+            map(coordinate);
+            array_index.iter_mut().for_each(&mut map);
+            sample.iter_mut().for_each(&mut map);
+            level.iter_mut().for_each(map);
+        }
+        Expression::ImageQuery {
+            ref mut image,
+            ref mut query,
+        } => {
+            map(image);
+            if let crate::ImageQuery::Size {
+                level: Some(ref mut level),
+            } = *query
+            {
+                map(level);
+            }
+        }
+        Expression::Unary { ref mut expr, .. } => map(expr),
+        Expression::Binary {
+            ref mut left,
+            ref mut right,
+            ..
+        } => {
+            map(left);
+            map(right);
+        }
+        Expression::Select {
+            ref mut condition,
+            ref mut accept,
+            ref mut reject,
+        } => {
+            map(condition);
+            map(accept);
+            map(reject);
+        }
+        Expression::Derivative { ref mut expr, .. } => map(expr),
+        Expression::Relational {
+            ref mut argument, ..
+        } => map(argument),
+        Expression::Math {
+            ref mut arg,
+            ref mut arg1,
+            ref mut arg2,
+            ref mut arg3,
+            ..
+        } => {
+            map(arg);
+            arg1.iter_mut()
+                .chain(arg2.iter_mut())
+                .chain(arg3.iter_mut())
+                .for_each(map);
+        }
+        Expression::As { ref mut expr, .. } => map(expr),
+        Expression::ArrayLength(ref mut expr) => map(expr),
+        Expression::RayQueryGetIntersection { ref mut query, .. } => map(query),
+    }
+}
+
 #[test]
 fn test_matrix_size() {
     let module = crate::Module::default();
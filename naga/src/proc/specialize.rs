@@ -0,0 +1,587 @@
+/*!
+Specializing functions for the pointers passed to them.
+
+WGSL's `unrestricted_pointer_parameters` feature lets functions take
+pointers into the `storage`, `uniform` and `workgroup` address spaces, to
+whole global variables or to parts of them. HLSL and GLSL have no way to
+pass such pointers, and SPIR-V can't pass them without variable pointers,
+so [`specialize_pointer_arguments`] replaces each such function with one
+clone per global variable and access path it is called with:
+
+```wgsl
+var<storage, read_write> buffers: array<array<u32, 4>, 8>;
+
+fn clear(p: ptr<storage, array<u32, 4>, read_write>) { (*p)[0] = 0u; }
+
+fn main(i: u32) { clear(&buffers[i]); }
+```
+
+becomes, in effect:
+
+```wgsl
+fn clear_buffers(p_index0: u32) { buffers[p_index0][0] = 0u; }
+
+fn main(i: u32) { clear_buffers(i); }
+```
+
+Constant indices are built into the clone. Each dynamic index becomes a
+new argument, in place of the pointer.
+*/
+
+use crate::{
+    arena::{Arena, Handle},
+    front::Typifier,
+    proc::{Emitter, ResolveContext},
+    AddressSpace, Block, Expression, FastHashMap, Function, FunctionArgument, GlobalVariable,
+    Module, Span, Statement, Type,
+};
+
+/// Return true if pointers in `space` can only be passed to functions
+/// after [`specialize_pointer_arguments`].
+pub const fn needs_pointer_specialization(space: AddressSpace) -> bool {
+    matches!(
+        space,
+        AddressSpace::Uniform | AddressSpace::Storage { .. } | AddressSpace::WorkGroup
+    )
+}
+
+/// Find the first function of `module` that takes a pointer that
+/// [`specialize_pointer_arguments`] would remove.
+///
+/// Return the function and the index of the argument.
+pub fn find_unspecialized_pointer_argument(module: &Module) -> Option<(Handle<Function>, usize)> {
+    module.functions.iter().find_map(|(handle, function)| {
+        pointer_arguments(module, function)
+            .first()
+            .map(|&index| (handle, index))
+    })
+}
+
+/// Replace the functions of `module` that take pointers into the
+/// `storage`, `uniform` and `workgroup` address spaces with clones
+/// specialized for each pointer they are called with.
+///
+/// The original functions are removed, and every call is redirected to
+/// the clone for its arguments. Clones are named after the original
+/// function and the global variables they access. Handles to the other
+/// functions may change.
+///
+/// # Panics
+///
+/// This may panic if `module` hasn't been validated.
+pub fn specialize_pointer_arguments(module: &mut Module) {
+    let originals = std::mem::take(&mut module.functions);
+    let specialized = originals
+        .iter()
+        .map(|(_, function)| pointer_arguments(module, function))
+        .collect::<Vec<_>>();
+    if specialized.iter().all(Vec::is_empty) {
+        module.functions = originals;
+        return;
+    }
+
+    let mut specializer = Specializer {
+        module,
+        originals: &originals,
+        specialized: &specialized,
+        new_handles: vec![None; originals.len()],
+        instances: FastHashMap::default(),
+    };
+
+    // Functions taking specialized pointers are only reachable through
+    // their clones, which are added as they are called.
+    for (handle, function) in originals.iter() {
+        if !specialized[handle.index()].is_empty() {
+            continue;
+        }
+        let mut function = specialize(function, &[], specializer.module);
+        specializer.adjust_calls(&mut function);
+        let new_handle = specializer
+            .module
+            .functions
+            .append(function, originals.get_span(handle));
+        specializer.new_handles[handle.index()] = Some(new_handle);
+    }
+
+    let mut entry_points = std::mem::take(&mut specializer.module.entry_points);
+    for entry_point in entry_points.iter_mut() {
+        specializer.adjust_calls(&mut entry_point.function);
+    }
+    specializer.module.entry_points = entry_points;
+}
+
+/// Return the indices of the arguments of `function` that are pointers
+/// needing specialization.
+fn pointer_arguments(module: &Module, function: &Function) -> Vec<usize> {
+    function
+        .arguments
+        .iter()
+        .enumerate()
+        .filter(|&(_, argument)| {
+            module.types[argument.ty]
+                .inner
+                .pointer_space()
+                .map_or(false, needs_pointer_specialization)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// A step of the access chain from a global variable to a pointer argument.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Step {
+    /// An [`AccessIndex`](Expression::AccessIndex) with this index.
+    Static(u32),
+    /// An [`Access`](Expression::Access), whose index has this type and is
+    /// passed as a new argument.
+    Dynamic(Handle<Type>),
+}
+
+/// The pointer a clone is specialized for, in place of one argument.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct PointerArgument {
+    /// The index of the argument in the original function.
+    index: usize,
+    global: Handle<GlobalVariable>,
+    steps: Vec<Step>,
+}
+
+/// A function, and the pointers it is specialized for.
+type Instance = (Handle<Function>, Vec<PointerArgument>);
+
+struct Specializer<'a> {
+    module: &'a mut Module,
+
+    /// The functions of the module before specialization.
+    originals: &'a Arena<Function>,
+
+    /// For each original function, the indices of its pointer arguments.
+    specialized: &'a [Vec<usize>],
+
+    /// The new handles of the original functions that are kept.
+    new_handles: Vec<Option<Handle<Function>>>,
+
+    /// The clones built so far.
+    instances: FastHashMap<Instance, Handle<Function>>,
+}
+
+impl Specializer<'_> {
+    /// Redirect the calls of `function`, which still refer to the original
+    /// functions, to their new handles or to specialized clones.
+    fn adjust_calls(&mut self, function: &mut Function) {
+        let mut typifier = Typifier::new();
+        let mut results = Vec::new();
+        let Function {
+            ref arguments,
+            ref local_variables,
+            ref mut expressions,
+            ref mut body,
+            ..
+        } = *function;
+        let caller = Caller {
+            arguments,
+            local_variables,
+            expressions,
+        };
+        self.adjust_block(body, &caller, &mut typifier, &mut results);
+
+        for (result, callee) in results {
+            expressions[result] = Expression::CallResult(callee);
+        }
+    }
+
+    fn adjust_block(
+        &mut self,
+        block: &mut Block,
+        caller: &Caller,
+        typifier: &mut Typifier,
+        results: &mut Vec<(Handle<Expression>, Handle<Function>)>,
+    ) {
+        for statement in block.iter_mut() {
+            match *statement {
+                Statement::Block(ref mut block) => {
+                    self.adjust_block(block, caller, typifier, results)
+                }
+                Statement::If {
+                    ref mut accept,
+                    ref mut reject,
+                    ..
+                } => {
+                    self.adjust_block(accept, caller, typifier, results);
+                    self.adjust_block(reject, caller, typifier, results);
+                }
+                Statement::Switch { ref mut cases, .. } => {
+                    for case in cases.iter_mut() {
+                        self.adjust_block(&mut case.body, caller, typifier, results);
+                    }
+                }
+                Statement::Loop {
+                    ref mut body,
+                    ref mut continuing,
+                    ..
+                } => {
+                    self.adjust_block(body, caller, typifier, results);
+                    self.adjust_block(continuing, caller, typifier, results);
+                }
+                Statement::Call {
+                    ref mut function,
+                    ref mut arguments,
+                    result,
+                } => {
+                    let callee = *function;
+                    *function = if self.specialized[callee.index()].is_empty() {
+                        self.new_handles[callee.index()]
+                            .expect("callees must come before their callers")
+                    } else {
+                        let (instance, new_arguments) =
+                            self.instance_for_call(callee, arguments, caller, typifier);
+                        *arguments = new_arguments;
+                        self.instance(instance)
+                    };
+                    if let Some(result) = result {
+                        results.push((result, *function));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Return the instance of `callee` that a call with `arguments`
+    /// needs, and the arguments to pass to it.
+    fn instance_for_call(
+        &mut self,
+        callee: Handle<Function>,
+        arguments: &[Handle<Expression>],
+        caller: &Caller,
+        typifier: &mut Typifier,
+    ) -> (Instance, Vec<Handle<Expression>>) {
+        let mut pointers = Vec::new();
+        let mut new_arguments = Vec::with_capacity(arguments.len());
+        for (index, &argument) in arguments.iter().enumerate() {
+            if !self.specialized[callee.index()].contains(&index) {
+                new_arguments.push(argument);
+                continue;
+            }
+
+            let mut steps = Vec::new();
+            let mut indices = Vec::new();
+            let mut pointer = argument;
+            let global = loop {
+                match caller.expressions[pointer] {
+                    Expression::Access { base, index } => {
+                        steps.push(Step::Dynamic(self.index_type(index, caller, typifier)));
+                        indices.push(index);
+                        pointer = base;
+                    }
+                    Expression::AccessIndex { base, index } => {
+                        steps.push(Step::Static(index));
+                        pointer = base;
+                    }
+                    Expression::GlobalVariable(global) => break global,
+                    ref other => unreachable!("pointer argument built from {other:?}"),
+                }
+            };
+            steps.reverse();
+            indices.reverse();
+            new_arguments.extend(indices);
+            pointers.push(PointerArgument {
+                index,
+                global,
+                steps,
+            });
+        }
+        ((callee, pointers), new_arguments)
+    }
+
+    fn index_type(
+        &mut self,
+        index: Handle<Expression>,
+        caller: &Caller,
+        typifier: &mut Typifier,
+    ) -> Handle<Type> {
+        let resolve_ctx = ResolveContext {
+            // Calls still refer to the original functions.
+            functions: self.originals,
+            ..ResolveContext::with_locals(self.module, caller.local_variables, caller.arguments)
+        };
+        typifier
+            .grow(index, caller.expressions, &resolve_ctx)
+            .expect("module should be valid");
+        typifier.register_type(index, &mut self.module.types)
+    }
+
+    /// Return the handle of the clone for `instance`, building it if needed.
+    fn instance(&mut self, instance: Instance) -> Handle<Function> {
+        if let Some(&handle) = self.instances.get(&instance) {
+            return handle;
+        }
+
+        let originals = self.originals;
+        let (callee, ref pointers) = instance;
+        let mut function = specialize(&originals[callee], pointers, self.module);
+        // Clones of the functions this one calls are added first.
+        self.adjust_calls(&mut function);
+        let handle = self
+            .module
+            .functions
+            .append(function, originals.get_span(callee));
+        self.instances.insert(instance, handle);
+        handle
+    }
+}
+
+/// The parts of a caller needed to resolve the types of its expressions.
+struct Caller<'a> {
+    arguments: &'a [FunctionArgument],
+    local_variables: &'a Arena<crate::LocalVariable>,
+    expressions: &'a Arena<Expression>,
+}
+
+/// Return a copy of `original` with the arguments in `pointers` replaced
+/// by the access chains they describe.
+///
+/// With no `pointers`, this just copies `original`, which needn't be
+/// `Clone`.
+fn specialize(original: &Function, pointers: &[PointerArgument], module: &Module) -> Function {
+    enum Link {
+        Static(u32),
+        Dynamic(Handle<Expression>),
+    }
+
+    let mut arguments = Vec::with_capacity(original.arguments.len());
+    let mut expressions = Arena::new();
+    let mut argument_map = vec![None; original.arguments.len()];
+    let mut chains = Vec::new();
+    for (index, argument) in original.arguments.iter().enumerate() {
+        let Some(pointer) = pointers.iter().find(|pointer| pointer.index == index) else {
+            argument_map[index] = Some(arguments.len() as u32);
+            arguments.push(argument.clone());
+            continue;
+        };
+        let root = expressions.append(Expression::GlobalVariable(pointer.global), Span::UNDEFINED);
+        let mut links = Vec::with_capacity(pointer.steps.len());
+        let mut dynamic_count = 0;
+        for step in pointer.steps.iter() {
+            links.push(match *step {
+                Step::Static(index) => Link::Static(index),
+                Step::Dynamic(ty) => {
+                    let new_index = arguments.len() as u32;
+                    let k = dynamic_count;
+                    dynamic_count += 1;
+                    arguments.push(FunctionArgument {
+                        name: argument
+                            .name
+                            .as_ref()
+                            .map(|name| format!("{name}_index{k}")),
+                        ty,
+                        binding: None,
+                    });
+                    Link::Dynamic(
+                        expressions
+                            .append(Expression::FunctionArgument(new_index), Span::UNDEFINED),
+                    )
+                }
+            });
+        }
+        chains.push((index, root, links));
+    }
+
+    // Build the access chains at the start of the body.
+    let mut body = Block::new();
+    let mut emitter = Emitter::default();
+    emitter.start(&expressions);
+    let mut pointer_map = vec![None; original.arguments.len()];
+    for (index, root, links) in chains {
+        let mut pointer = root;
+        for link in links {
+            let expression = match link {
+                Link::Static(index) => Expression::AccessIndex {
+                    base: pointer,
+                    index,
+                },
+                Link::Dynamic(index) => Expression::Access {
+                    base: pointer,
+                    index,
+                },
+            };
+            pointer = expressions.append(expression, Span::UNDEFINED);
+        }
+        pointer_map[index] = Some(pointer);
+    }
+    body.extend(emitter.finish(&expressions));
+
+    // Copy the original expressions, using the chains for the pointers.
+    let mut map = Vec::with_capacity(original.expressions.len());
+    for (handle, expression) in original.expressions.iter() {
+        let new_handle = match *expression {
+            Expression::FunctionArgument(index) => match pointer_map[index as usize] {
+                Some(pointer) => pointer,
+                None => expressions.append(
+                    Expression::FunctionArgument(argument_map[index as usize].unwrap()),
+                    original.expressions.get_span(handle),
+                ),
+            },
+            ref expression => {
+                let mut expression = expression.clone();
+                super::map_expression_operands(&mut expression, |operand| map[operand.index()]);
+                expressions.append(expression, original.expressions.get_span(handle))
+            }
+        };
+        map.push(new_handle);
+    }
+
+    let mut original_body = original.body.clone();
+    adjust_block(&mut original_body, &map);
+    body.extend_block(original_body);
+
+    let mut local_variables = Arena::new();
+    for (handle, local) in original.local_variables.iter() {
+        local_variables.append(
+            crate::LocalVariable {
+                name: local.name.clone(),
+                ty: local.ty,
+                init: local.init.map(|init| map[init.index()]),
+            },
+            original.local_variables.get_span(handle),
+        );
+    }
+
+    let name = original.name.as_ref().map(|name| {
+        let mut name = name.clone();
+        for pointer in pointers {
+            name.push('_');
+            name.push_str(
+                module.global_variables[pointer.global]
+                    .name
+                    .as_deref()
+                    .unwrap_or("global"),
+            );
+        }
+        name
+    });
+
+    Function {
+        name,
+        arguments,
+        result: original.result.clone(),
+        local_variables,
+        expressions,
+        named_expressions: original
+            .named_expressions
+            .iter()
+            .map(|(&handle, name)| (map[handle.index()], name.clone()))
+            .collect(),
+        body,
+    }
+}
+
+/// Replace every expression handle in `block` with its entry in `map`.
+fn adjust_block(block: &mut Block, map: &[Handle<Expression>]) {
+    let adjust = |handle: &mut Handle<Expression>| *handle = map[handle.index()];
+    for statement in block.iter_mut() {
+        match *statement {
+            Statement::Emit(ref mut range) => {
+                if let Some((first, last)) = range.first_and_last() {
+                    *range = crate::Range::new_from_bounds(map[first.index()], map[last.index()]);
+                }
+            }
+            Statement::Block(ref mut block) => adjust_block(block, map),
+            Statement::If {
+                ref mut condition,
+                ref mut accept,
+                ref mut reject,
+            } => {
+                adjust(condition);
+                adjust_block(accept, map);
+                adjust_block(reject, map);
+            }
+            Statement::Switch {
+                ref mut selector,
+                ref mut cases,
+            } => {
+                adjust(selector);
+                for case in cases.iter_mut() {
+                    adjust_block(&mut case.body, map);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ref mut break_if,
+            } => {
+                adjust_block(body, map);
+                adjust_block(continuing, map);
+                break_if.iter_mut().for_each(adjust);
+            }
+            Statement::Break | Statement::Continue | Statement::Kill | Statement::Barrier(_) => {}
+            Statement::Return { ref mut value } => value.iter_mut().for_each(adjust),
+            Statement::Store {
+                ref mut pointer,
+                ref mut value,
+            } => {
+                adjust(pointer);
+                adjust(value);
+            }
+            Statement::ImageStore {
+                ref mut image,
+                ref mut coordinate,
+                ref mut array_index,
+                ref mut value,
+            } => {
+                adjust(image);
+                adjust(coordinate);
+                array_index.iter_mut().for_each(adjust);
+                adjust(value);
+            }
+            Statement::Atomic {
+                ref mut pointer,
+                ref mut fun,
+                ref mut value,
+                ref mut result,
+            } => {
+                adjust(pointer);
+                if let crate::AtomicFunction::Exchange {
+                    compare: Some(ref mut compare),
+                } = *fun
+                {
+                    adjust(compare);
+                }
+                adjust(value);
+                adjust(result);
+            }
+            Statement::WorkGroupUniformLoad {
+                ref mut pointer,
+                ref mut result,
+            } => {
+                adjust(pointer);
+                adjust(result);
+            }
+            Statement::Call {
+                ref mut arguments,
+                ref mut result,
+                ..
+            } => {
+                arguments.iter_mut().for_each(adjust);
+                result.iter_mut().for_each(adjust);
+            }
+            Statement::RayQuery {
+                ref mut query,
+                ref mut fun,
+            } => {
+                adjust(query);
+                match *fun {
+                    crate::RayQueryFunction::Initialize {
+                        ref mut acceleration_structure,
+                        ref mut descriptor,
+                    } => {
+                        adjust(acceleration_structure);
+                        adjust(descriptor);
+                    }
+                    crate::RayQueryFunction::Proceed { ref mut result } => adjust(result),
+                    crate::RayQueryFunction::Terminate => {}
+                }
+            }
+        }
+    }
+}
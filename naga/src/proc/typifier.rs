@@ -519,6 +519,11 @@ impl<'a> ResolveContext<'a> {
                         width: 4,
                         size: crate::VectorSize::Quad,
                     },
+                    crate::ImageClass::External => Ti::Vector {
+                        kind: crate::ScalarKind::Float,
+                        width: 4,
+                        size: crate::VectorSize::Quad,
+                    },
                 }),
                 ref other => {
                     log::error!("Image type {:?}", other);
@@ -749,12 +754,14 @@ impl<'a> ResolveContext<'a> {
                                 format!("{fun:?}({other:?}, _)")
                             )),
                     },
+                    Mf::Dot4I8Packed => TypeResolution::Value(Ti::Scalar { kind: crate::ScalarKind::Sint, width: 4 }),
+                    Mf::Dot4U8Packed => TypeResolution::Value(Ti::Scalar { kind: crate::ScalarKind::Uint, width: 4 }),
                     Mf::Outer => {
                         let arg1 = arg1.ok_or_else(|| ResolveError::IncompatibleOperands(
                             format!("{fun:?}(_, None)")
                         ))?;
                         match (res_arg.inner_with(types), past(arg1)?.inner_with(types)) {
-                            (&Ti::Vector {kind: _, size: columns,width}, &Ti::Vector{ size: rows, .. }) => TypeResolution::Value(Ti::Matrix { columns, rows, width }),
+                            (&Ti::Vector {kind: _, size: rows,width}, &Ti::Vector{ size: columns, .. }) => TypeResolution::Value(Ti::Matrix { columns, rows, width }),
                             (left, right) =>
                                 return Err(ResolveError::IncompatibleOperands(
                                     format!("{fun:?}({left:?}, {right:?})")
@@ -781,7 +788,8 @@ impl<'a> ResolveContext<'a> {
                     Mf::Step |
                     Mf::SmoothStep |
                     Mf::Sqrt |
-                    Mf::InverseSqrt => res_arg.clone(),
+                    Mf::InverseSqrt |
+                    Mf::QuantizeToF16 => res_arg.clone(),
                     Mf::Transpose => match *res_arg.inner_with(types) {
                         Ti::Matrix {
                             columns,
@@ -841,13 +849,19 @@ impl<'a> ResolveContext<'a> {
                     Mf::Pack4x8unorm |
                     Mf::Pack2x16snorm |
                     Mf::Pack2x16unorm |
-                    Mf::Pack2x16float => TypeResolution::Value(Ti::Scalar { kind: crate::ScalarKind::Uint, width: 4 }),
+                    Mf::Pack2x16float |
+                    Mf::Pack4xI8 |
+                    Mf::Pack4xU8 |
+                    Mf::Pack4xI8Clamp |
+                    Mf::Pack4xU8Clamp => TypeResolution::Value(Ti::Scalar { kind: crate::ScalarKind::Uint, width: 4 }),
                     // data unpacking
                     Mf::Unpack4x8snorm |
                     Mf::Unpack4x8unorm => TypeResolution::Value(Ti::Vector { size: crate::VectorSize::Quad, kind: crate::ScalarKind::Float, width: 4 }),
                     Mf::Unpack2x16snorm |
                     Mf::Unpack2x16unorm |
                     Mf::Unpack2x16float => TypeResolution::Value(Ti::Vector { size: crate::VectorSize::Bi, kind: crate::ScalarKind::Float, width: 4 }),
+                    Mf::Unpack4xI8 => TypeResolution::Value(Ti::Vector { size: crate::VectorSize::Quad, kind: crate::ScalarKind::Sint, width: 4 }),
+                    Mf::Unpack4xU8 => TypeResolution::Value(Ti::Vector { size: crate::VectorSize::Quad, kind: crate::ScalarKind::Uint, width: 4 }),
                 }
             }
             crate::Expression::As {
@@ -107,15 +107,17 @@ impl FunctionUniformity {
         }
     }
 
-    /// Returns a disruptor based on the stored exit flags, if any.
-    const fn exit_disruptor(&self) -> Option<UniformityDisruptor> {
-        if self.exit.contains(ExitFlags::MAY_RETURN) {
-            Some(UniformityDisruptor::Return)
+    /// Returns a disruption based on the stored exit flags, if any,
+    /// attributed to the statement at `site`.
+    const fn exit_disruption(&self, site: crate::Span) -> Option<Disruption> {
+        let cause = if self.exit.contains(ExitFlags::MAY_RETURN) {
+            UniformityDisruptor::Return
         } else if self.exit.contains(ExitFlags::MAY_KILL) {
-            Some(UniformityDisruptor::Discard)
+            UniformityDisruptor::Discard
         } else {
-            None
-        }
+            return None;
+        };
+        Some(Disruption { cause, site })
     }
 }
 
@@ -310,6 +312,48 @@ pub enum UniformityDisruptor {
     Discard,
 }
 
+/// A [`UniformityDisruptor`] together with the statement or expression that
+/// made control flow non-uniform, used to explain uniformity errors.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(feature = "validate"), allow(dead_code))]
+struct Disruption {
+    cause: UniformityDisruptor,
+    /// The condition of the branch, or the statement that may exit early.
+    site: crate::Span,
+}
+
+#[cfg(feature = "validate")]
+impl Disruption {
+    /// Attach the chain of spans explaining this disruption to `error`:
+    /// where the non-uniform value originates, and where control flow
+    /// starts depending on it.
+    fn explain<E>(
+        &self,
+        error: WithSpan<E>,
+        expression_arena: &Arena<crate::Expression>,
+    ) -> WithSpan<E> {
+        match self.cause {
+            UniformityDisruptor::Expression(origin) => error
+                .with_span(
+                    expression_arena.get_span(origin),
+                    "the non-uniform value originates here",
+                )
+                .with_span(
+                    self.site,
+                    "control flow depends on the non-uniform value here",
+                ),
+            UniformityDisruptor::Return => error.with_span(
+                self.site,
+                "this may return early, making the rest of the function non-uniform",
+            ),
+            UniformityDisruptor::Discard => error.with_span(
+                self.site,
+                "this may discard, making the rest of the entry point non-uniform",
+            ),
+        }
+    }
+}
+
 impl FunctionInfo {
     /// Adds a value-type reference to an expression.
     #[must_use]
@@ -766,13 +810,13 @@ impl FunctionInfo {
         &mut self,
         statements: &crate::Block,
         other_functions: &[FunctionInfo],
-        mut disruptor: Option<UniformityDisruptor>,
+        mut disruptor: Option<Disruption>,
         expression_arena: &Arena<crate::Expression>,
     ) -> Result<FunctionUniformity, WithSpan<FunctionError>> {
         use crate::Statement as S;
 
         let mut combined_uniformity = FunctionUniformity::new();
-        for statement in statements {
+        for (statement, &span) in statements.span_iter() {
             let uniformity = match *statement {
                 S::Emit(ref range) => {
                     let mut requirements = UniformityRequirements::empty();
@@ -784,9 +828,14 @@ impl FunctionInfo {
                             .contains(super::ValidationFlags::CONTROL_FLOW_UNIFORMITY)
                             && !req.is_empty()
                         {
-                            if let Some(cause) = disruptor {
-                                return Err(FunctionError::NonUniformControlFlow(req, expr, cause)
-                                    .with_span_handle(expr, expression_arena));
+                            if let Some(disruption) = disruptor {
+                                let error = FunctionError::NonUniformControlFlow(
+                                    req,
+                                    expr,
+                                    disruption.cause,
+                                )
+                                .with_span_handle(expr, expression_arena);
+                                return Err(disruption.explain(error, expression_arena));
                             }
                         }
                         requirements |= req;
@@ -853,8 +902,10 @@ impl FunctionInfo {
                     ref reject,
                 } => {
                     let condition_nur = self.add_ref(condition);
-                    let branch_disruptor =
-                        disruptor.or(condition_nur.map(UniformityDisruptor::Expression));
+                    let branch_disruptor = disruptor.or(condition_nur.map(|origin| Disruption {
+                        cause: UniformityDisruptor::Expression(origin),
+                        site: expression_arena.get_span(condition),
+                    }));
                     let accept_uniformity = self.process_block(
                         accept,
                         other_functions,
@@ -874,8 +925,10 @@ impl FunctionInfo {
                     ref cases,
                 } => {
                     let selector_nur = self.add_ref(selector);
-                    let branch_disruptor =
-                        disruptor.or(selector_nur.map(UniformityDisruptor::Expression));
+                    let branch_disruptor = disruptor.or(selector_nur.map(|origin| Disruption {
+                        cause: UniformityDisruptor::Expression(origin),
+                        site: expression_arena.get_span(selector),
+                    }));
                     let mut uniformity = FunctionUniformity::new();
                     let mut case_disruptor = branch_disruptor;
                     for case in cases.iter() {
@@ -886,7 +939,7 @@ impl FunctionInfo {
                             expression_arena,
                         )?;
                         case_disruptor = if case.fall_through {
-                            case_disruptor.or(case_uniformity.exit_disruptor())
+                            case_disruptor.or(case_uniformity.exit_disruption(span))
                         } else {
                             branch_disruptor
                         };
@@ -901,7 +954,7 @@ impl FunctionInfo {
                 } => {
                     let body_uniformity =
                         self.process_block(body, other_functions, disruptor, expression_arena)?;
-                    let continuing_disruptor = disruptor.or(body_uniformity.exit_disruptor());
+                    let continuing_disruptor = disruptor.or(body_uniformity.exit_disruption(span));
                     let continuing_uniformity = self.process_block(
                         continuing,
                         other_functions,
@@ -952,7 +1005,20 @@ impl FunctionInfo {
                     result: _,
                 } => {
                     for &argument in arguments {
-                        let _ = self.add_ref(argument);
+                        // The callee may write through pointers into writable buffers.
+                        // Pointer expressions always resolve to type values.
+                        let global_use = match self.expressions[argument.index()].ty {
+                            TypeResolution::Value(ref inner) => match inner.pointer_space() {
+                                Some(crate::AddressSpace::Storage { access })
+                                    if access.contains(crate::StorageAccess::STORE) =>
+                                {
+                                    GlobalUse::READ | GlobalUse::WRITE
+                                }
+                                _ => GlobalUse::READ,
+                            },
+                            TypeResolution::Handle(_) => GlobalUse::READ,
+                        };
+                        let _ = self.add_ref_impl(argument, global_use);
                     }
                     let info = &other_functions[function.index()];
                     //Note: the result is validated by the Validator, not here
@@ -985,7 +1051,7 @@ impl FunctionInfo {
                 }
             };
 
-            disruptor = disruptor.or(uniformity.exit_disruptor());
+            disruptor = disruptor.or(uniformity.exit_disruption(span));
             combined_uniformity = combined_uniformity | uniformity;
         }
         Ok(combined_uniformity)
@@ -1242,7 +1308,10 @@ fn uniform_control_flow() {
         info.process_block(
             &vec![stmt_emit3, stmt_return_non_uniform].into(),
             &[],
-            Some(UniformityDisruptor::Return),
+            Some(Disruption {
+                cause: UniformityDisruptor::Return,
+                site: crate::Span::UNDEFINED,
+            }),
             &expressions
         ),
         Ok(FunctionUniformity {
@@ -1269,7 +1338,10 @@ fn uniform_control_flow() {
         info.process_block(
             &vec![stmt_emit4, stmt_assign, stmt_kill, stmt_return_pointer].into(),
             &[],
-            Some(UniformityDisruptor::Discard),
+            Some(Disruption {
+                cause: UniformityDisruptor::Discard,
+                site: crate::Span::UNDEFINED,
+            }),
             &expressions
         ),
         Ok(FunctionUniformity {
@@ -1282,3 +1354,127 @@ fn uniform_control_flow() {
     );
     assert_eq!(info[non_uniform_global], GlobalUse::READ | GlobalUse::WRITE);
 }
+
+#[test]
+#[cfg(all(feature = "validate", feature = "span"))]
+fn non_uniform_control_flow_explanation() {
+    use crate::{Expression as E, Span, Statement as S};
+
+    let mut type_arena = crate::UniqueArena::new();
+    let ty = type_arena.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Vector {
+                size: crate::VectorSize::Bi,
+                kind: crate::ScalarKind::Float,
+                width: 4,
+            },
+        },
+        Default::default(),
+    );
+    let mut global_var_arena = Arena::new();
+    let non_uniform_global = global_var_arena.append(
+        crate::GlobalVariable {
+            name: None,
+            init: None,
+            ty,
+            space: crate::AddressSpace::Handle,
+            binding: None,
+        },
+        Default::default(),
+    );
+
+    let mut expressions = Arena::new();
+    let origin_expr = expressions.append(E::GlobalVariable(non_uniform_global), Span::new(0, 6));
+    let condition_expr = expressions.append(
+        E::AccessIndex {
+            base: origin_expr,
+            index: 0,
+        },
+        Span::new(10, 20),
+    );
+    let emit_range_condition = expressions.range_from(0);
+    let constant_expr = expressions.append(E::Literal(crate::Literal::F32(0.0)), Span::new(30, 33));
+    let derivative_expr = expressions.append(
+        E::Derivative {
+            axis: crate::DerivativeAxis::X,
+            ctrl: crate::DerivativeControl::None,
+            expr: constant_expr,
+        },
+        Span::new(30, 40),
+    );
+    let emit_range_derivative = expressions.range_from(2);
+
+    let mut info = FunctionInfo {
+        flags: ValidationFlags::all(),
+        available_stages: ShaderStages::all(),
+        uniformity: Uniformity::new(),
+        may_kill: false,
+        sampling_set: crate::FastHashSet::default(),
+        global_uses: vec![GlobalUse::empty(); global_var_arena.len()].into_boxed_slice(),
+        expressions: vec![ExpressionInfo::new(); expressions.len()].into_boxed_slice(),
+        sampling: crate::FastHashSet::default(),
+        dual_source_blending: false,
+    };
+    let resolve_context = ResolveContext {
+        constants: &Arena::new(),
+        types: &type_arena,
+        special_types: &crate::SpecialTypes::default(),
+        global_vars: &global_var_arena,
+        local_vars: &Arena::new(),
+        functions: &Arena::new(),
+        arguments: &[],
+    };
+    for (handle, _) in expressions.iter() {
+        info.process_expression(
+            handle,
+            &expressions,
+            &[],
+            &resolve_context,
+            super::Capabilities::empty(),
+        )
+        .unwrap();
+    }
+    // Derivatives may not require uniformity in this configuration,
+    // so require it explicitly.
+    info.expressions[derivative_expr.index()]
+        .uniformity
+        .requirements = UniformityRequirements::WORK_GROUP_BARRIER;
+
+    let block = vec![
+        S::Emit(emit_range_condition),
+        S::If {
+            condition: condition_expr,
+            accept: vec![S::Emit(emit_range_derivative)].into(),
+            reject: crate::Block::new(),
+        },
+    ]
+    .into();
+    let error = info
+        .process_block(&block, &[], None, &expressions)
+        .unwrap_err();
+    assert_eq!(
+        error.as_inner(),
+        &FunctionError::NonUniformControlFlow(
+            UniformityRequirements::WORK_GROUP_BARRIER,
+            derivative_expr,
+            UniformityDisruptor::Expression(origin_expr),
+        ),
+    );
+    let spans = error
+        .spans()
+        .map(|&(span, ref description)| (span, description.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(spans.len(), 3);
+    assert_eq!(spans[0].0, Span::new(30, 40));
+    assert_eq!(
+        spans[1..],
+        [
+            (Span::new(0, 6), "the non-uniform value originates here"),
+            (
+                Span::new(10, 20),
+                "control flow depends on the non-uniform value here"
+            ),
+        ]
+    );
+}
@@ -153,6 +153,8 @@ pub enum LiteralError {
     Infinity,
     #[error(transparent)]
     Width(#[from] super::r#type::WidthError),
+    #[error("Abstract literals should have been converted to concrete types by the front end")]
+    Abstract,
 }
 
 #[cfg(feature = "validate")]
@@ -435,6 +437,14 @@ impl super::Validator {
                         multi: false,
                     } if gather.is_some() => false,
                     crate::ImageClass::Depth { multi: false } => true,
+                    // External images can only be sampled at the base level.
+                    crate::ImageClass::External
+                        if gather.is_none()
+                            && offset.is_none()
+                            && matches!(level, crate::SampleLevel::Zero) =>
+                    {
+                        false
+                    }
                     _ => return Err(ExpressionError::InvalidImageClass(class)),
                 };
                 if comparison != depth_ref.is_some() || (comparison && !image_depth) {
@@ -1140,6 +1150,32 @@ impl super::Validator {
                             ));
                         }
                     }
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let arg1_ty = match (arg1_ty, arg2_ty, arg3_ty) {
+                            (Some(ty1), None, None) => ty1,
+                            _ => return Err(ExpressionError::WrongArgumentCount(fun)),
+                        };
+                        match *arg_ty {
+                            Ti::Scalar {
+                                kind: Sk::Uint,
+                                width: 4,
+                            } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                        match *arg1_ty {
+                            Ti::Scalar {
+                                kind: Sk::Uint,
+                                width: 4,
+                            } => {}
+                            _ => {
+                                return Err(ExpressionError::InvalidArgumentType(
+                                    fun,
+                                    1,
+                                    arg1.unwrap(),
+                                ))
+                            }
+                        }
+                    }
                     Mf::Outer | Mf::Cross | Mf::Reflect => {
                         let arg1_ty = match (arg1_ty, arg2_ty, arg3_ty) {
                             (Some(ty1), None, None) => ty1,
@@ -1301,6 +1337,23 @@ impl super::Validator {
                             _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
                         }
                     }
+                    Mf::QuantizeToF16 => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        match *arg_ty {
+                            Ti::Scalar {
+                                kind: Sk::Float,
+                                width: 4,
+                            }
+                            | Ti::Vector {
+                                kind: Sk::Float,
+                                width: 4,
+                                ..
+                            } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
                     Mf::CountTrailingZeros
                     | Mf::CountLeadingZeros
                     | Mf::CountOneBits
@@ -1429,6 +1482,44 @@ impl super::Validator {
                             _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
                         }
                     }
+                    Mf::Pack4xI8 | Mf::Pack4xI8Clamp => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        match *arg_ty {
+                            Ti::Vector {
+                                size: crate::VectorSize::Quad,
+                                kind: Sk::Sint,
+                                width: 4,
+                            } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
+                    Mf::Pack4xU8 | Mf::Pack4xU8Clamp => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        match *arg_ty {
+                            Ti::Vector {
+                                size: crate::VectorSize::Quad,
+                                kind: Sk::Uint,
+                                width: 4,
+                            } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
+                    Mf::Unpack4xI8 | Mf::Unpack4xU8 => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        match *arg_ty {
+                            Ti::Scalar {
+                                kind: Sk::Uint,
+                                width: 4,
+                            } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
                     Mf::Unpack2x16float
                     | Mf::Unpack2x16snorm
                     | Mf::Unpack2x16unorm
@@ -1569,6 +1660,9 @@ impl super::Validator {
     }
 
     pub fn validate_literal(&self, literal: crate::Literal) -> Result<(), LiteralError> {
+        if let crate::Literal::AbstractInt(_) | crate::Literal::AbstractFloat(_) = literal {
+            return Err(LiteralError::Abstract);
+        }
         let kind = literal.scalar_kind();
         let width = literal.width();
         self.check_width(kind, width)?;
@@ -1582,6 +1676,7 @@ pub fn check_literal_value(literal: crate::Literal) -> Result<(), LiteralError>
     let is_nan = match literal {
         crate::Literal::F64(v) => v.is_nan(),
         crate::Literal::F32(v) => v.is_nan(),
+        crate::Literal::AbstractFloat(v) => v.is_nan(),
         _ => false,
     };
     if is_nan {
@@ -1591,6 +1686,7 @@ pub fn check_literal_value(literal: crate::Literal) -> Result<(), LiteralError>
     let is_infinite = match literal {
         crate::Literal::F64(v) => v.is_infinite(),
         crate::Literal::F32(v) => v.is_infinite(),
+        crate::Literal::AbstractFloat(v) => v.is_infinite(),
         _ => false,
     };
     if is_infinite {
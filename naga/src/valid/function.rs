@@ -38,6 +38,11 @@ pub enum CallError {
     },
     #[error("The emitted expression doesn't match the call")]
     ExpressionMismatch(Option<Handle<crate::Expression>>),
+    #[error("Argument {index} value {seen_expression:?} is a pointer that doesn't lead to a global variable or an argument")]
+    PointerArgumentRoot {
+        index: usize,
+        seen_expression: Handle<crate::Expression>,
+    },
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -49,6 +54,10 @@ pub enum AtomicError {
     InvalidOperand(Handle<crate::Expression>),
     #[error("Result type for {0:?} doesn't match the statement")]
     ResultTypeMismatch(Handle<crate::Expression>),
+    #[error("Atomic function {0:?} is not supported on 64-bit integers")]
+    Unsupported64BitFunction(crate::AtomicFunction),
+    #[error("64-bit atomics are only supported in storage buffers, not in {0:?}")]
+    Unsupported64BitAddressSpace(crate::AddressSpace),
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -295,6 +304,27 @@ impl super::Validator {
                 }
                 .with_span_handle(expr, context.expressions));
             }
+            if arg_inner
+                .pointer_space()
+                .map_or(false, crate::proc::needs_pointer_specialization)
+            {
+                let mut pointer = expr;
+                loop {
+                    match context.expressions[pointer] {
+                        crate::Expression::Access { base, .. }
+                        | crate::Expression::AccessIndex { base, .. } => pointer = base,
+                        crate::Expression::GlobalVariable(_)
+                        | crate::Expression::FunctionArgument(_) => break,
+                        _ => {
+                            return Err(CallError::PointerArgumentRoot {
+                                index,
+                                seen_expression: expr,
+                            }
+                            .with_span_handle(expr, context.expressions))
+                        }
+                    }
+                }
+            }
         }
 
         if let Some(expr) = result {
@@ -345,9 +375,9 @@ impl super::Validator {
         context: &BlockContext,
     ) -> Result<(), WithSpan<FunctionError>> {
         let pointer_inner = context.resolve_type(pointer, &self.valid_expression_set)?;
-        let (ptr_kind, ptr_width) = match *pointer_inner {
-            crate::TypeInner::Pointer { base, .. } => match context.types[base].inner {
-                crate::TypeInner::Atomic { kind, width } => (kind, width),
+        let (ptr_kind, ptr_width, ptr_space) = match *pointer_inner {
+            crate::TypeInner::Pointer { base, space } => match context.types[base].inner {
+                crate::TypeInner::Atomic { kind, width } => (kind, width, space),
                 ref other => {
                     log::error!("Atomic pointer to type {:?}", other);
                     return Err(AtomicError::InvalidPointer(pointer)
@@ -363,6 +393,24 @@ impl super::Validator {
             }
         };
 
+        // 64-bit atomics are only required to support `min` and `max`
+        // on storage buffers.
+        if ptr_width == 8 {
+            match *fun {
+                crate::AtomicFunction::Min | crate::AtomicFunction::Max => {}
+                other => {
+                    return Err(AtomicError::Unsupported64BitFunction(other)
+                        .with_span_handle(pointer, context.expressions)
+                        .into_other());
+                }
+            }
+            if !matches!(ptr_space, crate::AddressSpace::Storage { .. }) {
+                return Err(AtomicError::Unsupported64BitAddressSpace(ptr_space)
+                    .with_span_handle(pointer, context.expressions)
+                    .into_other());
+            }
+        }
+
         let value_inner = context.resolve_type(value, &self.valid_expression_set)?;
         match *value_inner {
             crate::TypeInner::Scalar { width, kind } if kind == ptr_kind && width == ptr_width => {}
@@ -1001,7 +1049,14 @@ impl super::Validator {
         #[cfg(feature = "validate")]
         for (index, argument) in fun.arguments.iter().enumerate() {
             match module.types[argument.ty].inner.pointer_space() {
-                Some(crate::AddressSpace::Private | crate::AddressSpace::Function) | None => {}
+                Some(
+                    crate::AddressSpace::Private
+                    | crate::AddressSpace::Function
+                    | crate::AddressSpace::Uniform
+                    | crate::AddressSpace::Storage { .. }
+                    | crate::AddressSpace::WorkGroup,
+                )
+                | None => {}
                 Some(other) => {
                     return Err(FunctionError::InvalidArgumentPointerSpace {
                         index,
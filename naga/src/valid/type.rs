@@ -124,6 +124,8 @@ pub enum TypeError {
     },
     #[error("Structure types must have at least one member")]
     EmptyStruct,
+    #[error("External images must be two-dimensional and not arrayed")]
+    InvalidExternalImage,
     #[error(transparent)]
     WidthError(#[from] WidthError),
 }
@@ -183,10 +185,10 @@ fn check_member_layout(
 const fn ptr_space_argument_flag(space: crate::AddressSpace) -> TypeFlags {
     use crate::AddressSpace as As;
     match space {
-        As::Function | As::Private => TypeFlags::ARGUMENT,
-        As::Uniform | As::Storage { .. } | As::Handle | As::PushConstant | As::WorkGroup => {
-            TypeFlags::empty()
+        As::Function | As::Private | As::Uniform | As::Storage { .. } | As::WorkGroup => {
+            TypeFlags::ARGUMENT
         }
+        As::Handle | As::PushConstant => TypeFlags::empty(),
     }
 }
 
@@ -244,7 +246,19 @@ impl super::Validator {
                     width == 4
                 }
             }
-            crate::ScalarKind::Sint | crate::ScalarKind::Uint => width == 4,
+            crate::ScalarKind::Sint | crate::ScalarKind::Uint => {
+                if width == 8 {
+                    if !self.capabilities.contains(Capabilities::SHADER_INT64) {
+                        return Err(WidthError::MissingCapability {
+                            name: "i64",
+                            flag: "SHADER_INT64",
+                        });
+                    }
+                    true
+                } else {
+                    width == 4
+                }
+            }
         };
         if good {
             Ok(())
@@ -320,7 +334,22 @@ impl super::Validator {
             Ti::Atomic { kind, width } => {
                 let good = match kind {
                     crate::ScalarKind::Bool | crate::ScalarKind::Float => false,
-                    crate::ScalarKind::Sint | crate::ScalarKind::Uint => width == 4,
+                    crate::ScalarKind::Sint | crate::ScalarKind::Uint => {
+                        if width == 8 {
+                            if !self
+                                .capabilities
+                                .contains(Capabilities::SHADER_INT64_ATOMIC_MIN_MAX)
+                            {
+                                return Err(TypeError::WidthError(WidthError::MissingCapability {
+                                    name: "64-bit atomic",
+                                    flag: "SHADER_INT64_ATOMIC_MIN_MAX",
+                                }));
+                            }
+                            true
+                        } else {
+                            width == 4
+                        }
+                    }
                 };
                 if !good {
                     return Err(TypeError::InvalidAtomicWidth(kind, width));
@@ -582,8 +611,13 @@ impl super::Validator {
             Ti::Image {
                 dim,
                 arrayed,
-                class: _,
+                class,
             } => {
+                if class == crate::ImageClass::External
+                    && (dim != crate::ImageDimension::D2 || arrayed)
+                {
+                    return Err(TypeError::InvalidExternalImage);
+                }
                 if arrayed && matches!(dim, crate::ImageDimension::Cube) {
                     self.require_type_capability(Capabilities::CUBE_ARRAY_TEXTURES)?;
                 }
@@ -0,0 +1,223 @@
+/*!
+Tests for building modules with `naga::builder`.
+*/
+
+#![cfg(all(feature = "builder", feature = "validate", feature = "wgsl-out"))]
+
+use naga::{
+    builder::{BuildError, ModuleBuilder},
+    valid, AddressSpace, ArraySize, BinaryOperator, Expression, Literal, ScalarKind, ShaderStage,
+    Statement, TypeInner,
+};
+
+fn validate_and_write(module: &naga::Module) -> String {
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(module)
+        .unwrap_or_else(|e| panic!("built module is invalid: {:?}", e.into_inner()));
+    naga::back::wgsl::write_string(module, &info, naga::back::wgsl::WriterFlags::empty()).unwrap()
+}
+
+fn float(builder: &mut ModuleBuilder) -> naga::Handle<naga::Type> {
+    builder.ty(TypeInner::Scalar {
+        kind: ScalarKind::Float,
+        width: 4,
+    })
+}
+
+fn uint(builder: &mut ModuleBuilder) -> naga::Handle<naga::Type> {
+    builder.ty(TypeInner::Scalar {
+        kind: ScalarKind::Uint,
+        width: 4,
+    })
+}
+
+#[test]
+fn struct_layout() {
+    let mut builder = ModuleBuilder::new();
+    let float = float(&mut builder);
+    let vec3 = builder.ty(TypeInner::Vector {
+        size: naga::VectorSize::Tri,
+        kind: ScalarKind::Float,
+        width: 4,
+    });
+    let light = builder
+        .struct_ty("Light", &[("intensity", float), ("color", vec3)])
+        .unwrap();
+    match builder.module().types[light].inner {
+        TypeInner::Struct { ref members, span } => {
+            assert_eq!(members[0].offset, 0);
+            assert_eq!(members[1].offset, 16);
+            assert_eq!(span, 32);
+        }
+        _ => unreachable!(),
+    }
+    let array = builder.array_ty(light, ArraySize::Dynamic).unwrap();
+    match builder.module().types[array].inner {
+        TypeInner::Array { stride, .. } => assert_eq!(stride, 32),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn constant_folding() {
+    let mut builder = ModuleBuilder::new();
+    let float = float(&mut builder);
+    let two = builder
+        .const_expr(Expression::Literal(Literal::F32(2.0)))
+        .unwrap();
+    let scale = builder.constant("SCALE", two);
+    builder
+        .function("scaled", |f| {
+            f.result(float, None);
+            let scale = f.constant(scale)?;
+            let three = f.literal(Literal::F32(3.0))?;
+            let product = f.binary(BinaryOperator::Multiply, scale, three)?;
+            assert!(matches!(
+                *f.type_of(product),
+                TypeInner::Scalar {
+                    kind: ScalarKind::Float,
+                    ..
+                }
+            ));
+            f.return_(Some(product));
+            Ok(())
+        })
+        .unwrap();
+    let module = builder.finish();
+    validate_and_write(&module);
+
+    let (_, function) = module.functions.iter().next().unwrap();
+    assert!(function.expressions.iter().any(
+        |(_, expr)| matches!(*expr, Expression::Literal(Literal::F32(value)) if value == 6.0)
+    ));
+    assert!(!function
+        .expressions
+        .iter()
+        .any(|(_, expr)| matches!(*expr, Expression::Binary { .. })));
+}
+
+#[test]
+fn compute_shader() {
+    let mut builder = ModuleBuilder::new();
+    let float = float(&mut builder);
+    let uint = uint(&mut builder);
+    let vec3u = builder.ty(TypeInner::Vector {
+        size: naga::VectorSize::Tri,
+        kind: ScalarKind::Uint,
+        width: 4,
+    });
+    let array = builder.array_ty(float, ArraySize::Dynamic).unwrap();
+    let data = builder.global(naga::GlobalVariable {
+        name: Some("data".to_string()),
+        space: AddressSpace::Storage {
+            access: naga::StorageAccess::LOAD | naga::StorageAccess::STORE,
+        },
+        binding: Some(naga::ResourceBinding {
+            group: 0,
+            binding: 0,
+        }),
+        ty: array,
+        init: None,
+    });
+
+    let double = builder
+        .function("double", |f| {
+            let x = f.argument("x", float, None)?;
+            f.result(float, None);
+            let sum = f.binary(BinaryOperator::Add, x, x)?;
+            f.return_(Some(sum));
+            Ok(())
+        })
+        .unwrap();
+
+    builder
+        .entry_point("main", ShaderStage::Compute, [64, 1, 1], |f| {
+            let id = f.argument(
+                "id",
+                vec3u,
+                Some(naga::Binding::BuiltIn(naga::BuiltIn::GlobalInvocationId)),
+            )?;
+            let index = f.access_index(id, 0)?;
+            let data = f.global(data)?;
+            let element = f.access(data, index)?;
+
+            // Double the element until it is at least 100, at most 4 times.
+            let zero = f.literal(Literal::U32(0))?;
+            let count = f.local("count", uint, Some(zero))?;
+            f.loop_(
+                |f| {
+                    let value = f.load(element)?;
+                    let hundred = f.literal(Literal::F32(100.0))?;
+                    let big = f.binary(BinaryOperator::GreaterEqual, value, hundred)?;
+                    f.if_(
+                        big,
+                        |f| {
+                            f.break_();
+                            Ok(())
+                        },
+                        |_| Ok(()),
+                    )?;
+                    let doubled = f.call(double, vec![value])?.unwrap();
+                    f.store(element, doubled);
+                    Ok(())
+                },
+                |f| {
+                    let current = f.load(count)?;
+                    let one = f.literal(Literal::U32(1))?;
+                    let next = f.binary(BinaryOperator::Add, current, one)?;
+                    f.store(count, next);
+                    let four = f.literal(Literal::U32(4))?;
+                    let done = f.binary(BinaryOperator::GreaterEqual, next, four)?;
+                    Ok(Some(done))
+                },
+            )?;
+
+            // A local initialized with a runtime value.
+            let value = f.load(element)?;
+            let copy = f.local("copy", float, Some(value))?;
+            let copied = f.load(copy)?;
+            f.switch(
+                index,
+                &[naga::SwitchValue::U32(0), naga::SwitchValue::Default],
+                |f, value| {
+                    if value == naga::SwitchValue::U32(0) {
+                        f.store(element, copied);
+                    }
+                    Ok(())
+                },
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+    let module = builder.finish();
+    let wgsl = validate_and_write(&module);
+    assert!(wgsl.contains("fn double("));
+    assert!(wgsl.contains("loop {"));
+    assert!(wgsl.contains("switch"));
+
+    let function = &module.entry_points[0].function;
+    let (_, copy) = function.local_variables.iter().nth(1).unwrap();
+    assert_eq!(copy.init, None);
+    assert!(function.body.iter().any(|statement| matches!(
+        *statement,
+        Statement::Loop {
+            break_if: Some(_),
+            ..
+        }
+    )));
+}
+
+#[test]
+fn resolve_error() {
+    let mut builder = ModuleBuilder::new();
+    let float = float(&mut builder);
+    let error = builder
+        .function("bad", |f| {
+            let x = f.argument("x", float, None)?;
+            f.access_index(x, 0)?;
+            Ok(())
+        })
+        .unwrap_err();
+    assert!(matches!(error, BuildError::Resolve(_)));
+}
@@ -0,0 +1,156 @@
+/*!
+Tests for module compaction and entry point extraction.
+*/
+
+#![cfg(all(feature = "compact", feature = "validate", feature = "wgsl-in"))]
+
+use naga::{compact, valid, ShaderStage};
+
+const SOURCE: &str = "
+    struct Light {
+        color: vec3<f32>,
+        intensity: f32,
+    }
+
+    struct Particle {
+        position: vec2<f32>,
+        velocity: vec2<f32>,
+    }
+
+    const GRAVITY = vec2<f32>(0.0, -9.8);
+    const AMBIENT = 0.1;
+
+    @group(0) @binding(0) var<uniform> light: Light;
+    @group(0) @binding(1) var<storage, read_write> particles: array<Particle>;
+    @group(0) @binding(2) var t: texture_2d<f32>;
+    @group(0) @binding(3) var s: sampler;
+
+    fn shade(color: vec3<f32>) -> vec3<f32> {
+        return color * light.color * light.intensity + AMBIENT;
+    }
+
+    fn integrate(p: Particle, dt: f32) -> Particle {
+        let velocity = p.velocity + GRAVITY * dt;
+        return Particle(p.position + velocity * dt, velocity);
+    }
+
+    fn step(index: u32) {
+        particles[index] = integrate(particles[index], 0.016);
+    }
+
+    @vertex
+    fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+        return vec4<f32>(particles[index].position, 0.0, 1.0);
+    }
+
+    @fragment
+    fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+        return vec4<f32>(shade(textureSample(t, s, uv).rgb), 1.0);
+    }
+
+    @compute @workgroup_size(64)
+    fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+        step(id.x);
+    }
+";
+
+fn extract(stage: ShaderStage, name: &str) -> naga::Module {
+    let mut module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .unwrap();
+    assert!(compact::extract_entry_point(&mut module, stage, name));
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .unwrap_or_else(|e| panic!("extracted module is invalid: {:?}", e.into_inner()));
+    module
+}
+
+fn global_names(module: &naga::Module) -> Vec<&str> {
+    module
+        .global_variables
+        .iter()
+        .filter_map(|(_, global)| global.name.as_deref())
+        .collect()
+}
+
+fn function_names(module: &naga::Module) -> Vec<&str> {
+    module
+        .functions
+        .iter()
+        .filter_map(|(_, function)| function.name.as_deref())
+        .collect()
+}
+
+fn constant_names(module: &naga::Module) -> Vec<&str> {
+    module
+        .constants
+        .iter()
+        .filter_map(|(_, constant)| constant.name.as_deref())
+        .collect()
+}
+
+fn has_struct(module: &naga::Module, name: &str) -> bool {
+    module
+        .types
+        .iter()
+        .any(|(_, ty)| ty.name.as_deref() == Some(name))
+}
+
+#[test]
+fn extract_vertex() {
+    let module = extract(ShaderStage::Vertex, "vs_main");
+    assert_eq!(module.entry_points.len(), 1);
+    assert_eq!(module.entry_points[0].name, "vs_main");
+    assert_eq!(global_names(&module), ["particles"]);
+    assert!(function_names(&module).is_empty());
+    assert!(constant_names(&module).is_empty());
+    assert!(has_struct(&module, "Particle"));
+    assert!(!has_struct(&module, "Light"));
+}
+
+#[test]
+fn extract_fragment() {
+    let module = extract(ShaderStage::Fragment, "fs_main");
+    assert_eq!(global_names(&module), ["light", "t", "s"]);
+    assert_eq!(function_names(&module), ["shade"]);
+    // The front end evaluates `AMBIENT` where it is used.
+    assert!(constant_names(&module).is_empty());
+    assert!(has_struct(&module, "Light"));
+    assert!(!has_struct(&module, "Particle"));
+}
+
+#[test]
+fn extract_compute_through_calls() {
+    let module = extract(ShaderStage::Compute, "cs_main");
+    assert_eq!(global_names(&module), ["particles"]);
+    assert_eq!(function_names(&module), ["integrate", "step"]);
+    assert_eq!(constant_names(&module), ["GRAVITY"]);
+    assert!(!has_struct(&module, "Light"));
+}
+
+#[test]
+fn extract_missing() {
+    let mut module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+    let globals = module.global_variables.len();
+    assert!(!compact::extract_entry_point(
+        &mut module,
+        ShaderStage::Vertex,
+        "fs_main"
+    ));
+    assert_eq!(module.entry_points.len(), 3);
+    assert_eq!(module.global_variables.len(), globals);
+}
+
+#[test]
+fn compact_keeps_everything_named() {
+    let mut module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+    compact::compact(&mut module);
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .unwrap();
+    assert_eq!(module.entry_points.len(), 3);
+    assert_eq!(global_names(&module), ["light", "particles", "t", "s"]);
+    assert_eq!(function_names(&module), ["shade", "integrate", "step"]);
+    assert_eq!(constant_names(&module), ["GRAVITY", "AMBIENT"]);
+}
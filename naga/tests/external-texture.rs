@@ -0,0 +1,181 @@
+/*!
+Tests for lowering external textures.
+*/
+
+#![cfg(all(
+    feature = "external-texture",
+    feature = "validate",
+    feature = "wgsl-in",
+    feature = "wgsl-out"
+))]
+
+use naga::{
+    external_texture::{self, BindingMap, ExternalTextureBindings, LowerError},
+    valid, AddressSpace, ImageClass, ResourceBinding, TypeInner,
+};
+
+fn parse(source: &str) -> naga::Module {
+    naga::front::wgsl::parse_str(source).unwrap_or_else(|e| {
+        panic!(
+            "expected WGSL to parse successfully:\n{}",
+            e.emit_to_string(source)
+        )
+    })
+}
+
+fn validate(module: &naga::Module) -> valid::ModuleInfo {
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(module)
+        .unwrap_or_else(|e| panic!("module is invalid: {:?}", e.into_inner()))
+}
+
+fn consecutive(bindings: &[(u32, u32)]) -> BindingMap {
+    bindings
+        .iter()
+        .map(|&(group, binding)| {
+            let binding = ResourceBinding { group, binding };
+            let planes = ExternalTextureBindings::consecutive(&binding);
+            (binding, planes)
+        })
+        .collect()
+}
+
+/// Lower `source`, and check that the result is valid, and still valid
+/// after a round trip through WGSL.
+fn lower(source: &str, bindings: &BindingMap) -> Result<naga::Module, LowerError> {
+    let mut module = parse(source);
+    validate(&module);
+    external_texture::lower(&mut module, bindings)?;
+    let info = validate(&module);
+    let wgsl =
+        naga::back::wgsl::write_string(&module, &info, naga::back::wgsl::WriterFlags::empty())
+            .unwrap();
+    validate(&parse(&wgsl));
+    Ok(module)
+}
+
+const SOURCE: &str = "
+    @group(0) @binding(0) var video: texture_external;
+    @group(1) @binding(0) var samp: sampler;
+
+    fn sample(coords: vec2<f32>) -> vec4<f32> {
+        return textureSampleBaseClampToEdge(video, samp, coords);
+    }
+
+    @fragment
+    fn main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+        let coords = position.xy / vec2<f32>(textureDimensions(video));
+        let texel = textureLoad(video, vec2<i32>(position.xy));
+        if position.x > 1.0 {
+            return sample(coords) + texel;
+        }
+        return textureLoad(video, vec2<u32>(position.xy));
+    }
+";
+
+#[test]
+fn planes_and_params() {
+    let module = lower(SOURCE, &consecutive(&[(0, 0)])).unwrap();
+
+    assert!(!module.types.iter().any(|(_, ty)| matches!(
+        ty.inner,
+        TypeInner::Image {
+            class: ImageClass::External,
+            ..
+        }
+    )));
+
+    let mut bindings = module
+        .global_variables
+        .iter()
+        .filter_map(|(_, global)| {
+            let binding = global.binding.as_ref()?;
+            Some((binding.group, binding.binding, global.space))
+        })
+        .collect::<Vec<_>>();
+    bindings.sort_by_key(|&(group, binding, _)| (group, binding));
+    assert_eq!(
+        bindings,
+        [
+            (0, 0, AddressSpace::Handle),
+            (0, 1, AddressSpace::Handle),
+            (0, 2, AddressSpace::Handle),
+            (0, 3, AddressSpace::Uniform),
+            (1, 0, AddressSpace::Handle),
+        ]
+    );
+}
+
+#[test]
+fn helpers_precede_callers() {
+    let module = lower(SOURCE, &consecutive(&[(0, 0)])).unwrap();
+    let names = module
+        .functions
+        .iter()
+        .map(|(_, function)| function.name.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["video_sample", "video_load", "video_load", "sample"]
+    );
+}
+
+#[test]
+fn without_external_textures() {
+    let source = "
+        @group(0) @binding(0) var t: texture_2d<f32>;
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return textureLoad(t, vec2<i32>(0), 0);
+        }
+    ";
+    let module = lower(source, &BindingMap::new()).unwrap();
+    assert_eq!(module.global_variables.len(), 1);
+    assert_eq!(module.functions.len(), 0);
+}
+
+#[test]
+fn missing_bindings() {
+    let error = lower(SOURCE, &consecutive(&[(1, 0)])).unwrap_err();
+    assert!(matches!(
+        error,
+        LowerError::MissingBindings(ResourceBinding {
+            group: 0,
+            binding: 0
+        })
+    ));
+}
+
+#[test]
+fn external_texture_argument() {
+    let source = "
+        @group(0) @binding(0) var video: texture_external;
+
+        fn load(t: texture_external) -> vec4<f32> {
+            return textureLoad(t, vec2<i32>(0));
+        }
+
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return load(video);
+        }
+    ";
+    let error = lower(source, &consecutive(&[(0, 0)])).unwrap_err();
+    assert!(matches!(
+        error,
+        LowerError::ExternalTextureArgument(Some(ref name)) if name == "load"
+    ));
+}
+
+#[cfg(feature = "spv-out")]
+#[test]
+fn back_ends_accept_lowered_module() {
+    let mut module = parse(SOURCE);
+    let info = validate(&module);
+    assert!(naga::back::spv::write_vec(&module, &info, &Default::default(), None).is_err());
+
+    external_texture::lower(&mut module, &consecutive(&[(0, 0)])).unwrap();
+    let info = validate(&module);
+    naga::back::spv::write_vec(&module, &info, &Default::default(), None).unwrap();
+}
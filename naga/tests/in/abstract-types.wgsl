@@ -0,0 +1,49 @@
+// Tests for WGSL abstract types: AbstractInt and AbstractFloat.
+
+const aint = 1;
+const afloat = 2.0;
+const big = 5000000000;
+const small = big - 4999999999;
+
+const xu: u32 = 4;
+const xf: f32 = 1;
+const xvu: vec2<u32> = vec2(1, 2);
+const xvf = vec2(1, 2.5);
+const xvfc: vec2<f32> = vec2(aint, 2);
+const xm = mat2x2(1, 2, 3, 4);
+const xau: array<u32, 3> = array(1, 2, 3);
+const xaf = array(1, 2.0);
+const xshl = 1 << 3;
+const xmixed = xu + 3;
+const xfmul = 1.5 * 2;
+
+struct S {
+    a: u32,
+    b: f32,
+    c: vec2<f32>,
+}
+
+var<private> pu: u32 = 7;
+var<private> pf: f32 = 1;
+
+fn takes(x: u32, y: f32) -> f32 {
+    return y + 1;
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    let s = S(1, 2, vec2(3, 4));
+    var b: u32 = 8;
+    b += 1;
+    b = b << 2;
+    let c = takes(3, 4);
+    let d = max(pf, 3);
+    let e = select(0u, 1, true);
+    let f = vec3<f32>(1, 2, 3);
+    let g = vec3<u32>(1);
+    let h = small + xshl;
+    switch b {
+        case 1, 2: {}
+        default: {}
+    }
+}
@@ -0,0 +1,20 @@
+@group(0) @binding(0)
+var video: texture_external;
+@group(0) @binding(1)
+var image: texture_2d<f32>;
+@group(0) @binding(2)
+var samp: sampler;
+
+fn sample_video(coords: vec2<f32>) -> vec4<f32> {
+    return textureSampleBaseClampToEdge(video, samp, coords);
+}
+
+@fragment
+fn main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = textureDimensions(video);
+    let coords = position.xy / vec2<f32>(size);
+    let texel = textureLoad(video, vec2<i32>(position.xy));
+    let unsigned_texel = textureLoad(video, vec2<u32>(position.xy));
+    let clamped = textureSampleBaseClampToEdge(image, samp, coords);
+    return sample_video(coords) + texel + unsigned_texel + clamped;
+}
@@ -0,0 +1,11 @@
+// Uses the native packed dot products of HLSL shader model 6.4.
+
+@group(0) @binding(0)
+var<storage, read_write> out: array<u32>;
+
+@compute @workgroup_size(1)
+fn main() {
+    let a = out[0];
+    let b = out[1];
+    out[2] = bitcast<u32>(dot4I8Packed(a, b)) + dot4U8Packed(a, b);
+}
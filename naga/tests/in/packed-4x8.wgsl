@@ -0,0 +1,25 @@
+const packed_dot = dot4I8Packed(0xFF010203u, 0x04050607u);
+const packed_vector = pack4xI8Clamp(vec4(-200, -1, 1, 200));
+const unpacked_vector = unpack4xU8(0x80FF0102u);
+
+@group(0) @binding(0)
+var<storage, read_write> out: array<u32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let a = out[0];
+    let b = out[1];
+
+    let sdot = dot4I8Packed(a, b);
+    let udot = dot4U8Packed(a, b);
+
+    let signed_bytes = unpack4xI8(a);
+    let unsigned_bytes = unpack4xU8(b);
+
+    out[2] = pack4xI8(signed_bytes);
+    out[3] = pack4xU8(unsigned_bytes);
+    out[4] = pack4xI8Clamp(signed_bytes * 2);
+    out[5] = pack4xU8Clamp(unsigned_bytes * 2u);
+    out[6] = bitcast<u32>(sdot) + udot;
+    out[7] = bitcast<u32>(packed_dot) + packed_vector + unpacked_vector.w;
+}
@@ -0,0 +1,55 @@
+// Functions taking pointers into the storage, uniform and workgroup
+// address spaces.
+
+struct Particle {
+    position: vec4<f32>,
+    velocity: vec4<f32>,
+}
+
+struct Particles {
+    count: u32,
+    items: array<Particle>,
+}
+
+struct Params {
+    gravity: vec4<f32>,
+    scales: array<vec4<f32>, 4>,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> particles: Particles;
+@group(0) @binding(1)
+var<uniform> params: Params;
+@group(0) @binding(2)
+var<storage, read_write> totals: array<atomic<u32>, 8>;
+
+var<workgroup> scratch: array<vec4<f32>, 64>;
+
+fn step(particle: ptr<storage, Particle, read_write>, gravity: ptr<uniform, vec4<f32>>) {
+    (*particle).velocity += *gravity;
+    (*particle).position += (*particle).velocity;
+}
+
+fn scaled(scale: ptr<uniform, vec4<f32>>, value: vec4<f32>) -> vec4<f32> {
+    return value * *scale;
+}
+
+fn stash(slot: ptr<workgroup, vec4<f32>>, particle: ptr<storage, Particle, read_write>, scale: ptr<uniform, vec4<f32>>) {
+    *slot = scaled(scale, (*particle).position);
+}
+
+fn count(total: ptr<storage, atomic<u32>, read_write>) {
+    atomicAdd(total, 1u);
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(local_invocation_index) index: u32) {
+    if id.x >= particles.count {
+        return;
+    }
+    step(&particles.items[id.x], &params.gravity);
+    stash(&scratch[index], &particles.items[id.x], &params.scales[id.x % 4u]);
+    stash(&scratch[63], &particles.items[0], &params.scales[1]);
+    count(&totals[id.x % 8u]);
+    workgroupBarrier();
+}
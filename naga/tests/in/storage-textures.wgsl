@@ -0,0 +1,40 @@
+@group(0) @binding(0)
+var s_r_r: texture_storage_2d<r32float, read>;
+@group(0) @binding(1)
+var s_r_rg: texture_storage_2d<rgba8unorm, read>;
+@group(0) @binding(2)
+var s_rw_r: texture_storage_2d<r32float, read_write>;
+@group(0) @binding(3)
+var s_rw_i: texture_storage_2d<r32sint, read_write>;
+@group(0) @binding(4)
+var s_rw_u: texture_storage_2d_array<r32uint, read_write>;
+@group(0) @binding(5)
+var s_w_r: texture_storage_2d<rgba16float, write>;
+
+@compute @workgroup_size(8, 8)
+fn csLoad(@builtin(global_invocation_id) id: vec3<u32>) {
+    let coords = vec2<i32>(id.xy);
+    let r = textureLoad(s_r_r, coords);
+    let rgba = textureLoad(s_r_rg, coords);
+    textureStore(s_w_r, coords, rgba * r.x);
+}
+
+@compute @workgroup_size(8, 8)
+fn csReadWrite(@builtin(global_invocation_id) id: vec3<u32>) {
+    let coords = vec2<i32>(id.xy);
+    let f = textureLoad(s_rw_r, coords);
+    textureStore(s_rw_r, coords, f * 2.0);
+    let i = textureLoad(s_rw_i, coords);
+    textureStore(s_rw_i, coords, i + vec4<i32>(1));
+    textureBarrier();
+    let u = textureLoad(s_rw_u, coords, 0);
+    textureStore(s_rw_u, coords, 1, u);
+}
+
+@fragment
+fn fsReadWrite(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(position.xy);
+    let f = textureLoad(s_rw_r, coords);
+    textureStore(s_rw_r, coords, f + 1.0);
+    return textureLoad(s_r_rg, coords);
+}
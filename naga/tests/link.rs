@@ -0,0 +1,196 @@
+/*!
+Tests for linking modules together.
+*/
+
+#![cfg(all(feature = "link", feature = "validate", feature = "wgsl-in"))]
+
+use naga::{link, valid};
+
+fn parse(source: &str) -> naga::Module {
+    naga::front::wgsl::parse_str(source).unwrap_or_else(|e| {
+        panic!(
+            "expected WGSL to parse successfully:\n{}",
+            e.emit_to_string(source)
+        )
+    })
+}
+
+fn link_valid(sources: &[&str]) -> naga::Module {
+    let module = link::link(sources.iter().map(|source| parse(source))).unwrap();
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .unwrap_or_else(|e| panic!("linked module is invalid: {:?}", e.into_inner()));
+    module
+}
+
+const CAMERA: &str = "
+    struct Camera {
+        view_proj: mat4x4<f32>,
+        position: vec3<f32>,
+    }
+
+    @group(0) @binding(0) var<uniform> camera: Camera;
+";
+
+#[test]
+fn shared_declarations() {
+    let vertex = format!(
+        "{CAMERA}
+        const SCALE = 2.0;
+
+        fn transform(p: vec3<f32>) -> vec4<f32> {{
+            return camera.view_proj * vec4<f32>(p * SCALE, 1.0);
+        }}
+
+        @vertex
+        fn vs_main(@location(0) p: vec3<f32>) -> @builtin(position) vec4<f32> {{
+            return transform(p);
+        }}
+        "
+    );
+    let fragment = format!(
+        "{CAMERA}
+        const SCALE = 2.0;
+
+        @fragment
+        fn fs_main(@location(0) p: vec3<f32>) -> @location(0) vec4<f32> {{
+            return vec4<f32>(distance(p, camera.position) * SCALE);
+        }}
+        "
+    );
+    let module = link_valid(&[&vertex, &fragment]);
+
+    assert_eq!(module.entry_points.len(), 2);
+    assert_eq!(module.global_variables.len(), 1);
+    assert_eq!(
+        module
+            .types
+            .iter()
+            .filter(|&(_, ty)| ty.name.as_deref() == Some("Camera"))
+            .count(),
+        1
+    );
+    assert_eq!(module.constants.len(), 1);
+}
+
+#[test]
+fn renamed_functions() {
+    let a = "
+        fn helper(x: f32) -> f32 {
+            return x * 2.0;
+        }
+
+        @fragment
+        fn fs_a(@location(0) x: f32) -> @location(0) vec4<f32> {
+            return vec4<f32>(helper(x));
+        }
+    ";
+    let b = "
+        fn helper(x: f32) -> f32 {
+            return x + 1.0;
+        }
+
+        fn twice(x: f32) -> f32 {
+            return helper(helper(x));
+        }
+
+        @fragment
+        fn fs_b(@location(0) x: f32) -> @location(0) vec4<f32> {
+            return vec4<f32>(twice(x));
+        }
+    ";
+    let module = link_valid(&[a, b]);
+
+    let names = module
+        .functions
+        .iter()
+        .map(|(_, function)| function.name.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["helper", "helper_1", "twice"]);
+
+    // `twice` still calls the helper from its own module.
+    let (twice, _) = module.functions.iter().nth(2).unwrap();
+    let (helper_1, _) = module.functions.iter().nth(1).unwrap();
+    for statement in module.functions[twice].body.iter() {
+        if let naga::Statement::Call { function, .. } = *statement {
+            assert_eq!(function, helper_1);
+        }
+    }
+}
+
+#[test]
+fn private_globals_are_not_shared() {
+    let a = "
+        var<private> counter: i32;
+
+        @compute @workgroup_size(1)
+        fn a() {
+            counter += 1;
+        }
+    ";
+    let b = "
+        var<private> counter: i32;
+
+        @compute @workgroup_size(1)
+        fn b() {
+            counter -= 1;
+        }
+    ";
+    let module = link_valid(&[a, b]);
+    let names = module
+        .global_variables
+        .iter()
+        .map(|(_, global)| global.name.as_deref().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["counter", "counter_1"]);
+}
+
+#[test]
+fn different_structs_with_the_same_name() {
+    let a = "
+        struct Light { color: vec3<f32> }
+        @group(0) @binding(0) var<uniform> a_light: Light;
+
+        @fragment
+        fn fs_a() -> @location(0) vec4<f32> {
+            return vec4<f32>(a_light.color, 1.0);
+        }
+    ";
+    let b = "
+        struct Light { direction: vec3<f32>, intensity: f32 }
+        @group(0) @binding(1) var<uniform> b_light: Light;
+
+        @fragment
+        fn fs_b() -> @location(0) vec4<f32> {
+            return vec4<f32>(b_light.direction * b_light.intensity, 1.0);
+        }
+    ";
+    let module = link_valid(&[a, b]);
+    let mut names = module
+        .types
+        .iter()
+        .filter_map(|(_, ty)| ty.name.as_deref())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["Light", "Light_1"]);
+}
+
+#[test]
+fn duplicate_entry_point() {
+    let source = "
+        @fragment
+        fn main() -> @location(0) vec4<f32> {
+            return vec4<f32>(1.0);
+        }
+    ";
+    let error = link::link([parse(source), parse(source)]).unwrap_err();
+    assert!(matches!(error, link::LinkError::DuplicateEntryPoint(ref name) if name == "main"));
+}
+
+#[test]
+fn binding_conflict() {
+    let a = "@group(0) @binding(0) var<uniform> a: vec4<f32>;";
+    let b = "@group(0) @binding(0) var<uniform> b: vec4<f32>;";
+    let error = link::link([parse(a), parse(b)]).unwrap_err();
+    assert!(matches!(error, link::LinkError::BindingConflict { .. }));
+}
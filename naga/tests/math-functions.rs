@@ -0,0 +1,338 @@
+/*!
+Tests that every `MathFunction` can be written by every back end.
+*/
+
+#![cfg(all(
+    feature = "validate",
+    feature = "spv-in",
+    feature = "spv-out",
+    feature = "msl-out",
+    feature = "glsl-out",
+    feature = "hlsl-out",
+    feature = "wgsl-in",
+    feature = "wgsl-out"
+))]
+
+use naga::{
+    back, front, valid, Block, EntryPoint, Expression, Function, LocalVariable, MathFunction,
+    Module, ScalarKind, ShaderStage, Span, Statement, Type, TypeInner, VectorSize,
+};
+
+const ALL: &[MathFunction] = {
+    use MathFunction as Mf;
+    &[
+        Mf::Abs,
+        Mf::Min,
+        Mf::Max,
+        Mf::Clamp,
+        Mf::Saturate,
+        Mf::Cos,
+        Mf::Cosh,
+        Mf::Sin,
+        Mf::Sinh,
+        Mf::Tan,
+        Mf::Tanh,
+        Mf::Acos,
+        Mf::Asin,
+        Mf::Atan,
+        Mf::Atan2,
+        Mf::Asinh,
+        Mf::Acosh,
+        Mf::Atanh,
+        Mf::Radians,
+        Mf::Degrees,
+        Mf::Ceil,
+        Mf::Floor,
+        Mf::Round,
+        Mf::Fract,
+        Mf::Trunc,
+        Mf::Modf,
+        Mf::Frexp,
+        Mf::Ldexp,
+        Mf::Exp,
+        Mf::Exp2,
+        Mf::Log,
+        Mf::Log2,
+        Mf::Pow,
+        Mf::Dot,
+        Mf::Dot4I8Packed,
+        Mf::Dot4U8Packed,
+        Mf::Outer,
+        Mf::Cross,
+        Mf::Distance,
+        Mf::Length,
+        Mf::Normalize,
+        Mf::FaceForward,
+        Mf::Reflect,
+        Mf::Refract,
+        Mf::Sign,
+        Mf::Fma,
+        Mf::Mix,
+        Mf::Step,
+        Mf::SmoothStep,
+        Mf::Sqrt,
+        Mf::InverseSqrt,
+        Mf::Inverse,
+        Mf::Transpose,
+        Mf::Determinant,
+        Mf::QuantizeToF16,
+        Mf::CountTrailingZeros,
+        Mf::CountLeadingZeros,
+        Mf::CountOneBits,
+        Mf::ReverseBits,
+        Mf::ExtractBits,
+        Mf::InsertBits,
+        Mf::FindLsb,
+        Mf::FindMsb,
+        Mf::Pack4x8snorm,
+        Mf::Pack4x8unorm,
+        Mf::Pack2x16snorm,
+        Mf::Pack2x16unorm,
+        Mf::Pack2x16float,
+        Mf::Pack4xI8,
+        Mf::Pack4xU8,
+        Mf::Pack4xI8Clamp,
+        Mf::Pack4xU8Clamp,
+        Mf::Unpack4x8snorm,
+        Mf::Unpack4x8unorm,
+        Mf::Unpack2x16snorm,
+        Mf::Unpack2x16unorm,
+        Mf::Unpack2x16float,
+        Mf::Unpack4xI8,
+        Mf::Unpack4xU8,
+    ]
+};
+
+const fn scalar(kind: ScalarKind) -> TypeInner {
+    TypeInner::Scalar { kind, width: 4 }
+}
+
+const fn vector(size: VectorSize, kind: ScalarKind) -> TypeInner {
+    TypeInner::Vector {
+        size,
+        kind,
+        width: 4,
+    }
+}
+
+const fn matrix(columns: VectorSize, rows: VectorSize) -> TypeInner {
+    TypeInner::Matrix {
+        columns,
+        rows,
+        width: 4,
+    }
+}
+
+/// The types of arguments to pass to `fun`.
+///
+/// This match is exhaustive, so that adding a `MathFunction` leads here.
+fn arguments(fun: MathFunction) -> Vec<TypeInner> {
+    use MathFunction as Mf;
+    use ScalarKind::{Float, Sint, Uint};
+    use VectorSize::{Bi, Quad, Tri};
+
+    let float = scalar(Float);
+    let vec3 = vector(Tri, Float);
+    match fun {
+        Mf::Abs
+        | Mf::Saturate
+        | Mf::Cos
+        | Mf::Cosh
+        | Mf::Sin
+        | Mf::Sinh
+        | Mf::Tan
+        | Mf::Tanh
+        | Mf::Acos
+        | Mf::Asin
+        | Mf::Atan
+        | Mf::Asinh
+        | Mf::Acosh
+        | Mf::Atanh
+        | Mf::Ceil
+        | Mf::Floor
+        | Mf::Round
+        | Mf::Fract
+        | Mf::Trunc
+        | Mf::Modf
+        | Mf::Frexp
+        | Mf::Exp
+        | Mf::Exp2
+        | Mf::Log
+        | Mf::Log2
+        | Mf::Sign
+        | Mf::Sqrt
+        | Mf::InverseSqrt => vec![float],
+        Mf::Min | Mf::Max | Mf::Atan2 | Mf::Pow | Mf::Step => vec![float; 2],
+        Mf::Clamp | Mf::Fma | Mf::Mix | Mf::SmoothStep => vec![float; 3],
+        Mf::Radians | Mf::Degrees | Mf::Length | Mf::Normalize | Mf::QuantizeToF16 => vec![vec3],
+        Mf::Dot | Mf::Outer | Mf::Cross | Mf::Distance | Mf::Reflect => vec![vec3; 2],
+        Mf::FaceForward => vec![vec3; 3],
+        Mf::Refract => vec![vec3.clone(), vec3, float],
+        Mf::Ldexp => vec![float, scalar(Sint)],
+        Mf::Inverse => vec![matrix(Quad, Quad)],
+        Mf::Transpose => vec![matrix(Tri, Bi)],
+        Mf::Determinant => vec![matrix(Tri, Tri)],
+        Mf::Dot4I8Packed | Mf::Dot4U8Packed => vec![scalar(Uint); 2],
+        Mf::CountTrailingZeros
+        | Mf::CountLeadingZeros
+        | Mf::CountOneBits
+        | Mf::ReverseBits
+        | Mf::FindLsb
+        | Mf::FindMsb => vec![scalar(Sint)],
+        Mf::ExtractBits => vec![scalar(Sint), scalar(Uint), scalar(Uint)],
+        Mf::InsertBits => vec![scalar(Sint), scalar(Sint), scalar(Uint), scalar(Uint)],
+        Mf::Pack4x8snorm | Mf::Pack4x8unorm => vec![vector(Quad, Float)],
+        Mf::Pack2x16snorm | Mf::Pack2x16unorm | Mf::Pack2x16float => vec![vector(Bi, Float)],
+        Mf::Pack4xI8 | Mf::Pack4xI8Clamp => vec![vector(Quad, Sint)],
+        Mf::Pack4xU8 | Mf::Pack4xU8Clamp => vec![vector(Quad, Uint)],
+        Mf::Unpack4x8snorm
+        | Mf::Unpack4x8unorm
+        | Mf::Unpack2x16snorm
+        | Mf::Unpack2x16unorm
+        | Mf::Unpack2x16float
+        | Mf::Unpack4xI8
+        | Mf::Unpack4xU8 => vec![scalar(Uint)],
+    }
+}
+
+/// Build a compute entry point that applies `fun` to loads of local
+/// variables, naming the result so that every back end writes it.
+fn module(fun: MathFunction) -> Module {
+    let mut module = Module::default();
+    let mut function = Function::default();
+
+    let arguments = arguments(fun);
+    assert_eq!(arguments.len(), fun.argument_count(), "{fun:?}");
+    let first = arguments[0].clone();
+
+    let mut loads = Vec::new();
+    for inner in arguments {
+        let ty = module
+            .types
+            .insert(Type { name: None, inner }, Span::UNDEFINED);
+        let var = function.local_variables.append(
+            LocalVariable {
+                name: None,
+                ty,
+                init: None,
+            },
+            Span::UNDEFINED,
+        );
+        let pointer = function
+            .expressions
+            .append(Expression::LocalVariable(var), Span::UNDEFINED);
+        loads.push(pointer);
+    }
+
+    let start = function.expressions.len();
+    for pointer in loads.iter_mut() {
+        *pointer = function
+            .expressions
+            .append(Expression::Load { pointer: *pointer }, Span::UNDEFINED);
+    }
+    let result = function.expressions.append(
+        Expression::Math {
+            fun,
+            arg: loads[0],
+            arg1: loads.get(1).copied(),
+            arg2: loads.get(2).copied(),
+            arg3: loads.get(3).copied(),
+        },
+        Span::UNDEFINED,
+    );
+    function
+        .named_expressions
+        .insert(result, "result".to_string());
+    function.body = Block::from_vec(vec![Statement::Emit(
+        function.expressions.range_from(start),
+    )]);
+
+    let (size, width) = match first {
+        TypeInner::Scalar { width, .. } => (None, width),
+        TypeInner::Vector { size, width, .. } => (Some(size), width),
+        _ => (None, 4),
+    };
+    match fun {
+        MathFunction::Modf => {
+            module.generate_predeclared_type(naga::PredeclaredType::ModfResult { size, width });
+        }
+        MathFunction::Frexp => {
+            module.generate_predeclared_type(naga::PredeclaredType::FrexpResult { size, width });
+        }
+        _ => {}
+    }
+
+    module.entry_points.push(EntryPoint {
+        name: "main".to_string(),
+        stage: ShaderStage::Compute,
+        early_depth_test: None,
+        workgroup_size: [1, 1, 1],
+        function,
+    });
+    module
+}
+
+fn validate(module: &Module) -> Result<valid::ModuleInfo, String> {
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(module)
+        .map_err(|e| format!("{:?}", e.into_inner()))
+}
+
+#[test]
+fn every_math_function_in_every_back_end() {
+    for &fun in ALL {
+        let module = module(fun);
+        let info = validate(&module).unwrap_or_else(|e| panic!("{fun:?} is invalid: {e}"));
+
+        let spv = back::spv::write_vec(&module, &info, &back::spv::Options::default(), None)
+            .unwrap_or_else(|e| panic!("{fun:?} failed in SPIR-V: {e}"));
+        let bytes: Vec<u8> = spv.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let module_from_spv = front::spv::parse_u8_slice(&bytes, &Default::default())
+            .unwrap_or_else(|e| panic!("{fun:?} failed to parse back from SPIR-V: {e}"));
+        // The writer clamps signed trailing zero counts with `UMin` on a mix of
+        // signed and unsigned operands, which the front end reads back as an
+        // invalid `Min`.
+        if fun != MathFunction::CountTrailingZeros {
+            validate(&module_from_spv)
+                .unwrap_or_else(|e| panic!("{fun:?} is invalid after SPIR-V: {e}"));
+        }
+
+        let msl_options = back::msl::Options {
+            lang_version: (2, 4),
+            ..Default::default()
+        };
+        back::msl::write_string(&module, &info, &msl_options, &Default::default())
+            .unwrap_or_else(|e| panic!("{fun:?} failed in MSL: {e}"));
+
+        let mut glsl = String::new();
+        back::glsl::Writer::new(
+            &mut glsl,
+            &module,
+            &info,
+            &back::glsl::Options {
+                version: back::glsl::Version::Desktop(450),
+                ..Default::default()
+            },
+            &back::glsl::PipelineOptions {
+                shader_stage: ShaderStage::Compute,
+                entry_point: "main".to_string(),
+                multiview: None,
+            },
+            Default::default(),
+        )
+        .and_then(|mut writer| writer.write())
+        .unwrap_or_else(|e| panic!("{fun:?} failed in GLSL: {e}"));
+
+        let mut hlsl = String::new();
+        back::hlsl::Writer::new(&mut hlsl, &Default::default())
+            .write(&module, &info)
+            .unwrap_or_else(|e| panic!("{fun:?} failed in HLSL: {e}"));
+
+        let wgsl = back::wgsl::write_string(&module, &info, back::wgsl::WriterFlags::empty())
+            .unwrap_or_else(|e| panic!("{fun:?} failed in WGSL: {e}"));
+        let module_from_wgsl = front::wgsl::parse_str(&wgsl)
+            .unwrap_or_else(|e| panic!("{fun:?} failed to parse back from WGSL: {e}\n{wgsl}"));
+        validate(&module_from_wgsl)
+            .unwrap_or_else(|e| panic!("{fun:?} is invalid after WGSL: {e}\n{wgsl}"));
+    }
+}
@@ -0,0 +1,295 @@
+/*!
+Tests for the IR optimizations.
+*/
+
+#![cfg(all(
+    feature = "opt",
+    feature = "validate",
+    feature = "wgsl-in",
+    feature = "wgsl-out"
+))]
+
+use naga::{opt, valid, Expression, Statement};
+
+fn parse(source: &str) -> naga::Module {
+    naga::front::wgsl::parse_str(source).unwrap_or_else(|e| {
+        panic!(
+            "expected WGSL to parse successfully:\n{}",
+            e.emit_to_string(source)
+        )
+    })
+}
+
+fn validate(module: &naga::Module) -> Result<valid::ModuleInfo, String> {
+    valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(module)
+        .map_err(|e| format!("{:?}", e.into_inner()))
+}
+
+fn write(
+    module: &naga::Module,
+    info: &valid::ModuleInfo,
+) -> Result<String, naga::back::wgsl::Error> {
+    naga::back::wgsl::write_string(module, info, naga::back::wgsl::WriterFlags::empty())
+}
+
+/// Optimize `source`, and check that the result is valid, and still valid
+/// after a round trip through WGSL, if the WGSL back end supports it.
+fn optimize(source: &str, options: &opt::Options) -> naga::Module {
+    let mut module = parse(source);
+    let info = validate(&module).expect("input is invalid");
+    let round_trip = write(&module, &info).is_ok();
+    opt::optimize(&mut module, options);
+    let info = validate(&module).unwrap_or_else(|e| panic!("optimized module is invalid: {e}"));
+
+    if round_trip {
+        let wgsl = write(&module, &info).expect("failed to write optimized module");
+        validate(&parse(&wgsl)).unwrap_or_else(|e| {
+            panic!("optimized module is invalid after a round trip: {e}\n{wgsl}")
+        });
+    }
+    module
+}
+
+fn entry_point(module: &naga::Module) -> &naga::Function {
+    &module.entry_points[0].function
+}
+
+fn count_expressions(function: &naga::Function, f: impl Fn(&Expression) -> bool) -> usize {
+    function
+        .expressions
+        .iter()
+        .filter(|&(_, expr)| f(expr))
+        .count()
+}
+
+/// All the passes, each on its own and together, on every snapshot input.
+#[test]
+fn snapshot_inputs() {
+    let mut configurations = vec![opt::Options::none(), opt::Options::default()];
+    for index in 0..5 {
+        let mut options = opt::Options::none();
+        match index {
+            0 => options.fold_constants = true,
+            1 => options.eliminate_dead_code = true,
+            2 => options.eliminate_common_subexpressions = true,
+            3 => options.inline_size_limit = usize::MAX,
+            _ => options.hoist_loop_invariants = true,
+        }
+        configurations.push(options);
+    }
+
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/in");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|e| e.to_str()) != Some("wgsl") {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        if validate(&parse(&source)).is_err() {
+            continue;
+        }
+        for options in configurations.iter() {
+            println!("{} with {:?}", path.display(), options);
+            optimize(&source, options);
+        }
+    }
+}
+
+#[test]
+fn dead_code() {
+    let module = optimize(
+        "
+        @fragment
+        fn main(@location(0) a: f32) -> @location(0) f32 {
+            let unused = a * 2.0;
+            var only_stored: f32;
+            only_stored = a + 1.0;
+            return a;
+        }
+        ",
+        &opt::Options::default(),
+    );
+    let function = entry_point(&module);
+    assert!(function.local_variables.is_empty());
+    assert_eq!(
+        count_expressions(function, |e| matches!(*e, Expression::Binary { .. })),
+        0
+    );
+    assert!(!function
+        .named_expressions
+        .values()
+        .any(|name| name == "unused"));
+}
+
+#[test]
+fn common_subexpressions() {
+    let module = optimize(
+        "
+        @fragment
+        fn main(@location(0) a: f32, @location(1) b: f32) -> @location(0) f32 {
+            return a * b + a * b;
+        }
+        ",
+        &opt::Options::default(),
+    );
+    assert_eq!(
+        count_expressions(entry_point(&module), |e| matches!(
+            *e,
+            Expression::Binary {
+                op: naga::BinaryOperator::Multiply,
+                ..
+            }
+        )),
+        1
+    );
+}
+
+#[test]
+fn inlining_and_folding() {
+    let module = optimize(
+        "
+        fn add(a: f32, b: f32) -> f32 {
+            return a + b;
+        }
+
+        @fragment
+        fn main() -> @location(0) f32 {
+            return add(1.0, 2.0);
+        }
+        ",
+        &opt::Options::default(),
+    );
+    assert!(module.functions.is_empty());
+    let function = entry_point(&module);
+    let mut calls = 0;
+    for statement in function.body.iter() {
+        if let Statement::Call { .. } = *statement {
+            calls += 1;
+        }
+    }
+    assert_eq!(calls, 0);
+    assert_eq!(
+        count_expressions(function, |e| matches!(
+            *e,
+            Expression::Literal(naga::Literal::F32(value)) if value == 3.0
+        )),
+        1
+    );
+}
+
+#[test]
+fn inlining_size_limit() {
+    let source = "
+        fn twice(a: f32) -> f32 {
+            return a * 2.0;
+        }
+
+        @fragment
+        fn main(@location(0) a: f32) -> @location(0) f32 {
+            return twice(a) + twice(a + 1.0);
+        }
+    ";
+    let module = optimize(source, &opt::Options::default());
+    assert!(module.functions.is_empty());
+
+    let options = opt::Options {
+        inline_size_limit: 1,
+        ..opt::Options::default()
+    };
+    let module = optimize(source, &options);
+    assert_eq!(module.functions.len(), 1);
+}
+
+#[test]
+fn inlining_tail_returns_in_loop() {
+    let module = optimize(
+        "
+        fn step(x: i32) -> i32 {
+            var acc = 1;
+            acc += x;
+            if acc > 10 {
+                return acc;
+            } else {
+                return 0;
+            }
+        }
+
+        @compute @workgroup_size(1)
+        fn main() {
+            var total = 0;
+            for (var i = 0; i < 4; i++) {
+                total += step(i);
+            }
+        }
+        ",
+        &opt::Options::default(),
+    );
+    assert!(module.functions.is_empty());
+}
+
+#[test]
+fn no_inlining_of_early_returns() {
+    let module = optimize(
+        "
+        fn f(x: i32) -> i32 {
+            loop {
+                if x > 0 {
+                    return x;
+                }
+                break;
+            }
+            return 0;
+        }
+
+        @compute @workgroup_size(1)
+        fn main() {
+            _ = f(1);
+        }
+        ",
+        &opt::Options::default(),
+    );
+    assert_eq!(module.functions.len(), 1);
+}
+
+#[test]
+fn loop_invariants() {
+    let module = optimize(
+        "
+        @group(0) @binding(0) var<storage, read_write> data: array<f32, 16>;
+
+        @compute @workgroup_size(1)
+        fn main(@builtin(local_invocation_index) index: u32) {
+            let scale = f32(index);
+            var i = 0;
+            loop {
+                if i >= 16 {
+                    break;
+                }
+                data[i] = data[i] * (scale + 1.0);
+                continuing {
+                    i++;
+                }
+            }
+        }
+        ",
+        &opt::Options::default(),
+    );
+    let function = entry_point(&module);
+    // The addition is emitted before the loop.
+    let mut emitted_before_loop = Vec::new();
+    for statement in function.body.iter() {
+        match *statement {
+            Statement::Emit(ref range) => emitted_before_loop.extend(range.clone()),
+            Statement::Loop { .. } => break,
+            _ => {}
+        }
+    }
+    assert!(emitted_before_loop.iter().any(|&handle| matches!(
+        function.expressions[handle],
+        Expression::Binary {
+            op: naga::BinaryOperator::Add,
+            ..
+        }
+    )));
+}
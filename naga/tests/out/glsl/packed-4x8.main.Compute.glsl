@@ -0,0 +1,33 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+const int packed_dot = 34;
+const uint packed_vector = 2130837376u;
+const uvec4 unpacked_vector = uvec4(2u, 1u, 255u, 128u);
+
+layout(std430) buffer type_3_block_0Compute { uint _group_0_binding_0_cs[]; };
+
+
+void main() {
+    uvec3 id = gl_GlobalInvocationID;
+    uint a = _group_0_binding_0_cs[0];
+    uint b = _group_0_binding_0_cs[1];
+    int sdot = ((int(a) << 24 >> 24) * (int(b) << 24 >> 24) + (int(a) << 16 >> 24) * (int(b) << 16 >> 24) + (int(a) << 8 >> 24) * (int(b) << 8 >> 24) + (int(a) >> 24) * (int(b) >> 24));
+    uint udot = ((a & 0xFFu) * (b & 0xFFu) + (a >> 8 & 0xFFu) * (b >> 8 & 0xFFu) + (a >> 16 & 0xFFu) * (b >> 16 & 0xFFu) + (a >> 24) * (b >> 24));
+    ivec4 signed_bytes = ivec4((int(a) << 24 >> 24), (int(a) << 16 >> 24), (int(a) << 8 >> 24), (int(a) >> 24));
+    uvec4 unsigned_bytes = uvec4((b & 0xFFu), (b >> 8 & 0xFFu), (b >> 16 & 0xFFu), (b >> 24));
+    _group_0_binding_0_cs[2] = uint((signed_bytes.x & 0xFF) | (signed_bytes.y & 0xFF) << 8 | (signed_bytes.z & 0xFF) << 16 | (signed_bytes.w & 0xFF) << 24);
+    _group_0_binding_0_cs[3] = ((unsigned_bytes.x & 0xFFu) | (unsigned_bytes.y & 0xFFu) << 8 | (unsigned_bytes.z & 0xFFu) << 16 | (unsigned_bytes.w & 0xFFu) << 24);
+    ivec4 _e20 = (signed_bytes * 2);
+    _group_0_binding_0_cs[4] = uint((clamp(_e20.x, -128, 127) & 0xFF) | (clamp(_e20.y, -128, 127) & 0xFF) << 8 | (clamp(_e20.z, -128, 127) & 0xFF) << 16 | (clamp(_e20.w, -128, 127) & 0xFF) << 24);
+    uvec4 _e25 = (unsigned_bytes * 2u);
+    _group_0_binding_0_cs[5] = ((clamp(_e25.x, 0u, 255u) & 0xFFu) | (clamp(_e25.y, 0u, 255u) & 0xFFu) << 8 | (clamp(_e25.z, 0u, 255u) & 0xFFu) << 16 | (clamp(_e25.w, 0u, 255u) & 0xFFu) << 24);
+    _group_0_binding_0_cs[6] = (uint(sdot) + udot);
+    _group_0_binding_0_cs[7] = ((uint(packed_dot) + packed_vector) + 128u);
+    return;
+}
+
@@ -0,0 +1,89 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+struct Particle {
+    vec4 position;
+    vec4 velocity;
+};
+struct Params {
+    vec4 gravity;
+    vec4 scales[4];
+};
+layout(std430) buffer Particles_block_0Compute {
+    uint count;
+    Particle items[];
+} _group_0_binding_0_cs;
+
+uniform Params_block_1Compute { Params _group_0_binding_1_cs; };
+
+layout(std430) buffer type_5_block_2Compute { uint _group_0_binding_2_cs[8]; };
+
+shared vec4 scratch[64];
+
+
+void step_particles_params(uint particle_index0_) {
+    vec4 _e7 = _group_0_binding_1_cs.gravity;
+    vec4 _e8 = _group_0_binding_0_cs.items[particle_index0_].velocity;
+    _group_0_binding_0_cs.items[particle_index0_].velocity = (_e8 + _e7);
+    vec4 _e12 = _group_0_binding_0_cs.items[particle_index0_].velocity;
+    vec4 _e13 = _group_0_binding_0_cs.items[particle_index0_].position;
+    _group_0_binding_0_cs.items[particle_index0_].position = (_e13 + _e12);
+    return;
+}
+
+vec4 scaled_params(uint scale_index0_, vec4 value) {
+    vec4 _e5 = _group_0_binding_1_cs.scales[scale_index0_];
+    return (value * _e5);
+}
+
+void stash_scratch_particles_params(uint slot_index0_, uint particle_index0_1, uint scale_index0_1) {
+    vec4 _e12 = _group_0_binding_0_cs.items[particle_index0_1].position;
+    vec4 _e13 = scaled_params(scale_index0_1, _e12);
+    scratch[slot_index0_] = _e13;
+    return;
+}
+
+vec4 scaled_params_1(vec4 value_1) {
+    vec4 _e4 = _group_0_binding_1_cs.scales[1];
+    return (value_1 * _e4);
+}
+
+void stash_scratch_particles_params_1() {
+    vec4 _e9 = _group_0_binding_0_cs.items[0].position;
+    vec4 _e10 = scaled_params_1(_e9);
+    scratch[63] = _e10;
+    return;
+}
+
+void count_totals(uint total_index0_) {
+    uint _e4 = atomicAdd(_group_0_binding_2_cs[total_index0_], 1u);
+    return;
+}
+
+void main() {
+    if (gl_LocalInvocationID == uvec3(0u)) {
+        scratch = vec4[64](vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0), vec4(0.0));
+    }
+    memoryBarrierShared();
+    barrier();
+    uvec3 id = gl_GlobalInvocationID;
+    uint index = gl_LocalInvocationIndex;
+    uint _e5 = _group_0_binding_0_cs.count;
+    if ((id.x >= _e5)) {
+        return;
+    }
+    step_particles_params(id.x);
+    uint _e23 = (id.x % 4u);
+    stash_scratch_particles_params(index, id.x, _e23);
+    stash_scratch_particles_params_1();
+    uint _e36 = (id.x % 8u);
+    count_totals(_e36);
+    memoryBarrierShared();
+    barrier();
+    return;
+}
+
@@ -0,0 +1,23 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+layout(r32f) readonly uniform highp image2D _group_0_binding_0_cs;
+
+layout(rgba8) readonly uniform highp image2D _group_0_binding_1_cs;
+
+layout(rgba16f) writeonly uniform highp image2D _group_0_binding_5_cs;
+
+
+void main() {
+    uvec3 id = gl_GlobalInvocationID;
+    ivec2 coords = ivec2(id.xy);
+    vec4 r = imageLoad(_group_0_binding_0_cs, coords);
+    vec4 rgba = imageLoad(_group_0_binding_1_cs, coords);
+    imageStore(_group_0_binding_5_cs, coords, (rgba * r.x));
+    return;
+}
+
@@ -0,0 +1,28 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+layout(r32f) uniform highp image2D _group_0_binding_2_cs;
+
+layout(r32i) uniform highp iimage2D _group_0_binding_3_cs;
+
+layout(r32ui) uniform highp uimage2DArray _group_0_binding_4_cs;
+
+
+void main() {
+    uvec3 id_1 = gl_GlobalInvocationID;
+    ivec2 coords = ivec2(id_1.xy);
+    vec4 f = imageLoad(_group_0_binding_2_cs, coords);
+    imageStore(_group_0_binding_2_cs, coords, (f * 2.0));
+    ivec4 i = imageLoad(_group_0_binding_3_cs, coords);
+    imageStore(_group_0_binding_3_cs, coords, (i + ivec4(1)));
+    memoryBarrierImage();
+    barrier();
+    uvec4 u = imageLoad(_group_0_binding_4_cs, ivec3(coords, 0));
+    imageStore(_group_0_binding_4_cs, ivec3(coords, 1), u);
+    return;
+}
+
@@ -0,0 +1,21 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(rgba8) readonly uniform highp image2D _group_0_binding_1_fs;
+
+layout(r32f) uniform highp image2D _group_0_binding_2_fs;
+
+layout(location = 0) out vec4 _fs2p_location0;
+
+void main() {
+    vec4 position = gl_FragCoord;
+    ivec2 coords = ivec2(position.xy);
+    vec4 f = imageLoad(_group_0_binding_2_fs, coords);
+    imageStore(_group_0_binding_2_fs, coords, (f + vec4(1.0)));
+    vec4 _e10 = imageLoad(_group_0_binding_1_fs, coords);
+    _fs2p_location0 = _e10;
+    return;
+}
+
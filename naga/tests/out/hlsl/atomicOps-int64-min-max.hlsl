@@ -0,0 +1,30 @@
+struct NagaConstants {
+    int base_vertex;
+    int base_instance;
+    uint other;
+};
+ConstantBuffer<NagaConstants> _NagaConstants: register(b0, space1);
+
+struct Struct {
+    uint64_t atomic_scalar;
+    uint64_t atomic_arr[2];
+};
+
+RWByteAddressBuffer storage_atomic_scalar : register(u0);
+RWByteAddressBuffer storage_atomic_arr : register(u1);
+RWByteAddressBuffer storage_struct : register(u2);
+
+[numthreads(2, 1, 1)]
+void cs_main(uint3 id : SV_GroupThreadID)
+{
+    uint64_t _e3; storage_atomic_scalar.InterlockedMax64(0, 1uL, _e3);
+    uint64_t _e7; storage_atomic_arr.InterlockedMax64(8, 1uL, _e7);
+    uint64_t _e11; storage_struct.InterlockedMax64(0, 1uL, _e11);
+    uint64_t _e16; storage_struct.InterlockedMax64(8+8, 1uL, _e16);
+    GroupMemoryBarrierWithGroupSync();
+    uint64_t _e19; storage_atomic_scalar.InterlockedMin64(0, 1uL, _e19);
+    uint64_t _e23; storage_atomic_arr.InterlockedMin64(8, 1uL, _e23);
+    uint64_t _e27; storage_struct.InterlockedMin64(0, 1uL, _e27);
+    uint64_t _e32; storage_struct.InterlockedMin64(8+8, 1uL, _e32);
+    return;
+}
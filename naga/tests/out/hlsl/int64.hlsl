@@ -0,0 +1,216 @@
+struct NagaConstants {
+    int base_vertex;
+    int base_instance;
+    uint other;
+};
+ConstantBuffer<NagaConstants> _NagaConstants: register(b0, space1);
+
+struct UniformCompatible {
+    uint val_u32_;
+    int val_i32_;
+    float val_f32_;
+    int _pad3_0;
+    uint64_t val_u64_;
+    int _pad4_0;
+    int _pad4_1;
+    uint64_t2 val_u64_2_;
+    int _pad5_0;
+    int _pad5_1;
+    int _pad5_2;
+    int _pad5_3;
+    uint64_t3 val_u64_3_;
+    int _pad6_0;
+    int _pad6_1;
+    uint64_t4 val_u64_4_;
+    int64_t val_i64_;
+    int _pad8_0;
+    int _pad8_1;
+    int64_t2 val_i64_2_;
+    int64_t3 val_i64_3_;
+    int _pad10_0;
+    int _pad10_1;
+    int64_t4 val_i64_4_;
+    uint64_t final_value;
+    int _end_pad_0;
+    int _end_pad_1;
+    int _end_pad_2;
+    int _end_pad_3;
+    int _end_pad_4;
+    int _end_pad_5;
+};
+
+struct StorageCompatible {
+    uint64_t val_u64_array_2_[2];
+    int64_t val_i64_array_2_[2];
+};
+
+static const uint64_t constant_variable = 20uL;
+
+static int64_t private_variable = 1L;
+cbuffer input_uniform : register(b0) { UniformCompatible input_uniform; }
+ByteAddressBuffer input_storage : register(t1);
+ByteAddressBuffer input_arrays : register(t2);
+RWByteAddressBuffer output : register(u3);
+RWByteAddressBuffer output_arrays : register(u4);
+
+typedef int64_t ret_Constructarray2_int64_t_[2];
+ret_Constructarray2_int64_t_ Constructarray2_int64_t_(int64_t arg0, int64_t arg1) {
+    int64_t ret[2] = { arg0, arg1 };
+    return ret;
+}
+
+int64_t int64_function(int64_t x)
+{
+    int64_t val = 20L;
+
+    int64_t _expr4 = val;
+    val = (_expr4 + -1002003004004975L);
+    int64_t _expr6 = val;
+    int64_t _expr9 = val;
+    val = (_expr9 + (_expr6 + 5L));
+    uint _expr13 = input_uniform.val_u32_;
+    int64_t _expr14 = val;
+    int64_t _expr18 = val;
+    val = (_expr18 + int64_t((_expr13 + uint(_expr14))));
+    int _expr22 = input_uniform.val_i32_;
+    int64_t _expr23 = val;
+    int64_t _expr27 = val;
+    val = (_expr27 + int64_t((_expr22 + int(_expr23))));
+    float _expr31 = input_uniform.val_f32_;
+    int64_t _expr32 = val;
+    int64_t _expr36 = val;
+    val = (_expr36 + int64_t((_expr31 + float(_expr32))));
+    int64_t _expr40 = input_uniform.val_i64_;
+    int64_t _expr43 = val;
+    val = (_expr43 + (_expr40).xxx.z);
+    uint64_t _expr47 = input_uniform.val_u64_;
+    int64_t _expr49 = val;
+    val = (_expr49 + int64_t(_expr47));
+    int64_t _expr55 = input_uniform.val_i64_;
+    int64_t _expr58 = input_storage.Load<int64_t>(128);
+    output.Store<int64_t>(128, (_expr55 + _expr58));
+    int64_t2 _expr64 = input_uniform.val_i64_2_;
+    int64_t2 _expr67 = input_storage.Load<int64_t2>(144);
+    output.Store<int64_t2>(144, (_expr64 + _expr67));
+    int64_t3 _expr73 = input_uniform.val_i64_3_;
+    int64_t3 _expr76 = input_storage.Load<int64_t3>(160);
+    output.Store<int64_t3>(160, (_expr73 + _expr76));
+    int64_t4 _expr82 = input_uniform.val_i64_4_;
+    int64_t4 _expr85 = input_storage.Load<int64_t4>(192);
+    output.Store<int64_t4>(192, (_expr82 + _expr85));
+    int64_t _expr91[2] = Constructarray2_int64_t_(input_arrays.Load<int64_t>(16+0), input_arrays.Load<int64_t>(16+8));
+    {
+        int64_t _value2[2] = _expr91;
+        output_arrays.Store<int64_t>(16+0, _value2[0]);
+        output_arrays.Store<int64_t>(16+8, _value2[1]);
+    }
+    int64_t _expr92 = val;
+    int64_t _expr94 = val;
+    val = (_expr94 + abs(_expr92));
+    int64_t _expr96 = val;
+    int64_t _expr97 = val;
+    int64_t _expr98 = val;
+    int64_t _expr100 = val;
+    val = (_expr100 + clamp(_expr96, _expr97, _expr98));
+    int64_t _expr102 = val;
+    int64_t _expr104 = val;
+    int64_t _expr107 = val;
+    val = (_expr107 + dot((_expr102).xx, (_expr104).xx));
+    int64_t _expr109 = val;
+    int64_t _expr110 = val;
+    int64_t _expr112 = val;
+    val = (_expr112 + max(_expr109, _expr110));
+    int64_t _expr114 = val;
+    int64_t _expr115 = val;
+    int64_t _expr117 = val;
+    val = (_expr117 + min(_expr114, _expr115));
+    int64_t _expr119 = val;
+    int64_t _expr121 = val;
+    val = (_expr121 + sign(_expr119));
+    int64_t _expr123 = val;
+    return _expr123;
+}
+
+typedef uint64_t ret_Constructarray2_uint64_t_[2];
+ret_Constructarray2_uint64_t_ Constructarray2_uint64_t_(uint64_t arg0, uint64_t arg1) {
+    uint64_t ret[2] = { arg0, arg1 };
+    return ret;
+}
+
+uint64_t uint64_function(uint64_t x_1)
+{
+    uint64_t val_1 = 20uL;
+
+    uint64_t _expr4 = val_1;
+    val_1 = (_expr4 + 1002003004005037uL);
+    uint64_t _expr6 = val_1;
+    uint64_t _expr9 = val_1;
+    val_1 = (_expr9 + (_expr6 + 5uL));
+    uint _expr13 = input_uniform.val_u32_;
+    uint64_t _expr14 = val_1;
+    uint64_t _expr18 = val_1;
+    val_1 = (_expr18 + uint64_t((_expr13 + uint(_expr14))));
+    int _expr22 = input_uniform.val_i32_;
+    uint64_t _expr23 = val_1;
+    uint64_t _expr27 = val_1;
+    val_1 = (_expr27 + uint64_t((_expr22 + int(_expr23))));
+    float _expr31 = input_uniform.val_f32_;
+    uint64_t _expr32 = val_1;
+    uint64_t _expr36 = val_1;
+    val_1 = (_expr36 + uint64_t((_expr31 + float(_expr32))));
+    uint64_t _expr40 = input_uniform.val_u64_;
+    uint64_t _expr43 = val_1;
+    val_1 = (_expr43 + (_expr40).xxx.z);
+    int64_t _expr47 = input_uniform.val_i64_;
+    uint64_t _expr49 = val_1;
+    val_1 = (_expr49 + uint64_t(_expr47));
+    uint64_t _expr55 = input_uniform.val_u64_;
+    uint64_t _expr58 = input_storage.Load<uint64_t>(16);
+    output.Store<uint64_t>(16, (_expr55 + _expr58));
+    uint64_t2 _expr64 = input_uniform.val_u64_2_;
+    uint64_t2 _expr67 = input_storage.Load<uint64_t2>(32);
+    output.Store<uint64_t2>(32, (_expr64 + _expr67));
+    uint64_t3 _expr73 = input_uniform.val_u64_3_;
+    uint64_t3 _expr76 = input_storage.Load<uint64_t3>(64);
+    output.Store<uint64_t3>(64, (_expr73 + _expr76));
+    uint64_t4 _expr82 = input_uniform.val_u64_4_;
+    uint64_t4 _expr85 = input_storage.Load<uint64_t4>(96);
+    output.Store<uint64_t4>(96, (_expr82 + _expr85));
+    uint64_t _expr91[2] = Constructarray2_uint64_t_(input_arrays.Load<uint64_t>(0+0), input_arrays.Load<uint64_t>(0+8));
+    {
+        uint64_t _value2[2] = _expr91;
+        output_arrays.Store<uint64_t>(0+0, _value2[0]);
+        output_arrays.Store<uint64_t>(0+8, _value2[1]);
+    }
+    uint64_t _expr92 = val_1;
+    uint64_t _expr94 = val_1;
+    val_1 = (_expr94 + abs(_expr92));
+    uint64_t _expr96 = val_1;
+    uint64_t _expr97 = val_1;
+    uint64_t _expr98 = val_1;
+    uint64_t _expr100 = val_1;
+    val_1 = (_expr100 + clamp(_expr96, _expr97, _expr98));
+    uint64_t _expr102 = val_1;
+    uint64_t _expr104 = val_1;
+    uint64_t _expr107 = val_1;
+    val_1 = (_expr107 + dot((_expr102).xx, (_expr104).xx));
+    uint64_t _expr109 = val_1;
+    uint64_t _expr110 = val_1;
+    uint64_t _expr112 = val_1;
+    val_1 = (_expr112 + max(_expr109, _expr110));
+    uint64_t _expr114 = val_1;
+    uint64_t _expr115 = val_1;
+    uint64_t _expr117 = val_1;
+    val_1 = (_expr117 + min(_expr114, _expr115));
+    uint64_t _expr119 = val_1;
+    return _expr119;
+}
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    const uint64_t _e3 = uint64_function(67uL);
+    const int64_t _e5 = int64_function(60L);
+    output.Store<uint64_t>(224, (_e3 + uint64_t(_e5)));
+    return;
+}
@@ -0,0 +1,17 @@
+struct NagaConstants {
+    int base_vertex;
+    int base_instance;
+    uint other;
+};
+ConstantBuffer<NagaConstants> _NagaConstants: register(b0, space1);
+
+RWByteAddressBuffer out_ : register(u0);
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    uint a = asuint(out_.Load(0));
+    uint b = asuint(out_.Load(4));
+    out_.Store(8, asuint((asuint(dot4add_i8packed(a, b, 0)) + dot4add_u8packed(a, b, 0u))));
+    return;
+}
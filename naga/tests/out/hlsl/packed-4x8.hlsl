@@ -0,0 +1,25 @@
+static const int packed_dot = 34;
+static const uint packed_vector = 2130837376u;
+static const uint4 unpacked_vector = uint4(2u, 1u, 255u, 128u);
+
+RWByteAddressBuffer out_ : register(u0);
+
+[numthreads(1, 1, 1)]
+void main(uint3 id : SV_DispatchThreadID)
+{
+    uint a = asuint(out_.Load(0));
+    uint b = asuint(out_.Load(4));
+    int sdot = dot((int4(a << 24, a << 16, a << 8, a) >> 24), (int4(b << 24, b << 16, b << 8, b) >> 24));
+    uint udot = dot((uint4(a, a >> 8, a >> 16, a >> 24) & 0xFF), (uint4(b, b >> 8, b >> 16, b >> 24) & 0xFF));
+    int4 signed_bytes = (int4(a << 24, a << 16, a << 8, a) >> 24);
+    uint4 unsigned_bytes = (uint4(b, b >> 8, b >> 16, b >> 24) & 0xFF);
+    out_.Store(8, asuint(asuint((signed_bytes[0] & 0xFF) | (signed_bytes[1] & 0xFF) << 8 | (signed_bytes[2] & 0xFF) << 16 | (signed_bytes[3] & 0xFF) << 24)));
+    out_.Store(12, asuint(((unsigned_bytes[0] & 0xFF) | (unsigned_bytes[1] & 0xFF) << 8 | (unsigned_bytes[2] & 0xFF) << 16 | (unsigned_bytes[3] & 0xFF) << 24)));
+    int4 _expr20 = (signed_bytes * 2);
+    out_.Store(16, asuint(asuint((clamp(_expr20[0], -128, 127) & 0xFF) | (clamp(_expr20[1], -128, 127) & 0xFF) << 8 | (clamp(_expr20[2], -128, 127) & 0xFF) << 16 | (clamp(_expr20[3], -128, 127) & 0xFF) << 24)));
+    uint4 _expr25 = (unsigned_bytes * 2u);
+    out_.Store(20, asuint(((min(_expr25[0], 255u) & 0xFF) | (min(_expr25[1], 255u) & 0xFF) << 8 | (min(_expr25[2], 255u) & 0xFF) << 16 | (min(_expr25[3], 255u) & 0xFF) << 24)));
+    out_.Store(24, asuint((asuint(sdot) + udot)));
+    out_.Store(28, asuint(((asuint(packed_dot) + packed_vector) + 128u)));
+    return;
+}
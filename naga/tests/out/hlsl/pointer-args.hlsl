@@ -0,0 +1,80 @@
+struct Particle {
+    float4 position;
+    float4 velocity;
+};
+
+struct Params {
+    float4 gravity;
+    float4 scales[4];
+};
+
+RWByteAddressBuffer particles : register(u0);
+cbuffer params : register(b1) { Params params; }
+RWByteAddressBuffer totals : register(u2);
+groupshared float4 scratch[64];
+
+void step_particles_params(uint particle_index0_)
+{
+    float4 _expr7 = params.gravity;
+    float4 _expr8 = asfloat(particles.Load4(16+particle_index0_*32+16));
+    particles.Store4(16+particle_index0_*32+16, asuint((_expr8 + _expr7)));
+    float4 _expr12 = asfloat(particles.Load4(16+particle_index0_*32+16));
+    float4 _expr13 = asfloat(particles.Load4(0+particle_index0_*32+16));
+    particles.Store4(0+particle_index0_*32+16, asuint((_expr13 + _expr12)));
+    return;
+}
+
+float4 scaled_params(uint scale_index0_, float4 value)
+{
+    float4 _expr5 = params.scales[scale_index0_];
+    return (value * _expr5);
+}
+
+void stash_scratch_particles_params(uint slot_index0_, uint particle_index0_1, uint scale_index0_1)
+{
+    float4 _expr12 = asfloat(particles.Load4(0+particle_index0_1*32+16));
+    const float4 _e13 = scaled_params(scale_index0_1, _expr12);
+    scratch[slot_index0_] = _e13;
+    return;
+}
+
+float4 scaled_params_1(float4 value_1)
+{
+    float4 _expr4 = params.scales[1];
+    return (value_1 * _expr4);
+}
+
+void stash_scratch_particles_params_1()
+{
+    float4 _expr9 = asfloat(particles.Load4(0+0+16));
+    const float4 _e10 = scaled_params_1(_expr9);
+    scratch[63] = _e10;
+    return;
+}
+
+void count_totals(uint total_index0_)
+{
+    uint _e4; totals.InterlockedAdd(total_index0_*4, 1u, _e4);
+    return;
+}
+
+[numthreads(64, 1, 1)]
+void main(uint3 id : SV_DispatchThreadID, uint index : SV_GroupIndex, uint3 __local_invocation_id : SV_GroupThreadID)
+{
+    if (all(__local_invocation_id == uint3(0u, 0u, 0u))) {
+        scratch = (float4[64])0;
+    }
+    GroupMemoryBarrierWithGroupSync();
+    uint _expr5 = asuint(particles.Load(0));
+    if ((id.x >= _expr5)) {
+        return;
+    }
+    step_particles_params(id.x);
+    uint _expr23 = (id.x % 4u);
+    stash_scratch_particles_params(index, id.x, _expr23);
+    stash_scratch_particles_params_1();
+    uint _expr36 = (id.x % 8u);
+    count_totals(_expr36);
+    GroupMemoryBarrierWithGroupSync();
+    return;
+}
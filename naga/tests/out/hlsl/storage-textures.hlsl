@@ -0,0 +1,44 @@
+RWTexture2D<float4> s_r_r : register(u0);
+RWTexture2D<unorm float4> s_r_rg : register(u1);
+RWTexture2D<float4> s_rw_r : register(u2);
+RWTexture2D<int4> s_rw_i : register(u3);
+RWTexture2DArray<uint4> s_rw_u : register(u4);
+RWTexture2D<float4> s_w_r : register(u5);
+
+struct FragmentInput_fsReadWrite {
+    float4 position_1 : SV_Position;
+};
+
+[numthreads(8, 8, 1)]
+void csLoad(uint3 id : SV_DispatchThreadID)
+{
+    int2 coords = int2(id.xy);
+    float4 r = s_r_r.Load(coords);
+    float4 rgba = s_r_rg.Load(coords);
+    s_w_r[coords] = (rgba * r.x);
+    return;
+}
+
+[numthreads(8, 8, 1)]
+void csReadWrite(uint3 id_1 : SV_DispatchThreadID)
+{
+    int2 coords_1 = int2(id_1.xy);
+    float4 f = s_rw_r.Load(coords_1);
+    s_rw_r[coords_1] = (f * 2.0);
+    int4 i = s_rw_i.Load(coords_1);
+    s_rw_i[coords_1] = (i + (1).xxxx);
+    DeviceMemoryBarrierWithGroupSync();
+    uint4 u = s_rw_u.Load(int3(coords_1, 0));
+    s_rw_u[int3(coords_1, 1)] = u;
+    return;
+}
+
+float4 fsReadWrite(FragmentInput_fsReadWrite fragmentinput_fsreadwrite) : SV_Target0
+{
+    float4 position = fragmentinput_fsreadwrite.position_1;
+    int2 coords_2 = int2(position.xy);
+    float4 f_1 = s_rw_r.Load(coords_2);
+    s_rw_r[coords_2] = (f_1 + (1.0).xxxx);
+    float4 _expr10 = s_r_rg.Load(coords_2);
+    return _expr10;
+}
@@ -0,0 +1,53 @@
+struct S {
+    a: u32,
+    b: f32,
+    c: vec2<f32>,
+}
+
+const aint: i32 = 1;
+const afloat: f32 = 2.0;
+const small: i32 = 1;
+const xu: u32 = 4u;
+const xf: f32 = 1.0;
+const xvu: vec2<u32> = vec2<u32>(1u, 2u);
+const xvf: vec2<f32> = vec2<f32>(1.0, 2.5);
+const xvfc: vec2<f32> = vec2<f32>(1.0, 2.0);
+const xm: mat2x2<f32> = mat2x2<f32>(vec2<f32>(1.0, 2.0), vec2<f32>(3.0, 4.0));
+const xau: array<u32, 3> = array<u32, 3>(1u, 2u, 3u);
+const xaf: array<f32, 2> = array<f32, 2>(1.0, 2.0);
+const xshl: i32 = 8;
+const xmixed: u32 = 7u;
+const xfmul: f32 = 3.0;
+
+var<private> pu: u32 = 7u;
+var<private> pf: f32 = 1.0;
+
+fn takes(x: u32, y: f32) -> f32 {
+    return (y + 1.0);
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    var b: u32 = 8u;
+
+    let s = S(1u, 2.0, vec2<f32>(3.0, 4.0));
+    let _e8 = b;
+    b = (_e8 + 1u);
+    let _e11 = b;
+    b = (_e11 << 2u);
+    let _e16 = takes(3u, 4.0);
+    let _e18 = pf;
+    let d = max(_e18, 3.0);
+    let e = select(0u, 1u, true);
+    let f = vec3<f32>(1.0, 2.0, 3.0);
+    let g = vec3(1u);
+    let _e32 = b;
+    switch _e32 {
+        case 1u, 2u: {
+            return;
+        }
+        default: {
+            return;
+        }
+    }
+}
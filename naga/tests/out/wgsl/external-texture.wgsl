@@ -0,0 +1,24 @@
+@group(0) @binding(0) 
+var video: texture_external;
+@group(0) @binding(1) 
+var image: texture_2d<f32>;
+@group(0) @binding(2) 
+var samp: sampler;
+
+fn sample_video(coords: vec2<f32>) -> vec4<f32> {
+    let _e3 = textureSampleBaseClampToEdge(video, samp, coords);
+    return _e3;
+}
+
+@fragment 
+fn main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = textureDimensions(video);
+    let coords_1 = (position.xy / vec2<f32>(size));
+    let texel = textureLoad(video, vec2<i32>(position.xy));
+    let unsigned_texel = textureLoad(video, vec2<u32>(position.xy));
+    let _e17 = textureDimensions(image, 0u);
+    let _e21 = (vec2(0.5) / vec2<f32>(_e17));
+    let clamped = textureSampleLevel(image, samp, clamp(coords_1, _e21, (vec2(1.0) - _e21)), 0.0);
+    let _e27 = sample_video(coords_1);
+    return (((_e27 + texel) + unsigned_texel) + clamped);
+}
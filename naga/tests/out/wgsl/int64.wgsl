@@ -0,0 +1,172 @@
+struct UniformCompatible {
+    val_u32_: u32,
+    val_i32_: i32,
+    val_f32_: f32,
+    val_u64_: u64,
+    val_u64_2_: vec2<u64>,
+    val_u64_3_: vec3<u64>,
+    val_u64_4_: vec4<u64>,
+    val_i64_: i64,
+    val_i64_2_: vec2<i64>,
+    val_i64_3_: vec3<i64>,
+    val_i64_4_: vec4<i64>,
+    final_value: u64,
+}
+
+struct StorageCompatible {
+    val_u64_array_2_: array<u64, 2>,
+    val_i64_array_2_: array<i64, 2>,
+}
+
+const constant_variable: u64 = 20lu;
+
+var<private> private_variable: i64 = 1li;
+@group(0) @binding(0) 
+var<uniform> input_uniform: UniformCompatible;
+@group(0) @binding(1) 
+var<storage> input_storage: UniformCompatible;
+@group(0) @binding(2) 
+var<storage> input_arrays: StorageCompatible;
+@group(0) @binding(3) 
+var<storage, read_write> output: UniformCompatible;
+@group(0) @binding(4) 
+var<storage, read_write> output_arrays: StorageCompatible;
+
+fn int64_function(x: i64) -> i64 {
+    var val: i64 = 20li;
+
+    let _e4 = val;
+    val = (_e4 + -1002003004004975li);
+    let _e6 = val;
+    let _e9 = val;
+    val = (_e9 + (_e6 + 5li));
+    let _e13 = input_uniform.val_u32_;
+    let _e14 = val;
+    let _e18 = val;
+    val = (_e18 + i64((_e13 + u32(_e14))));
+    let _e22 = input_uniform.val_i32_;
+    let _e23 = val;
+    let _e27 = val;
+    val = (_e27 + i64((_e22 + i32(_e23))));
+    let _e31 = input_uniform.val_f32_;
+    let _e32 = val;
+    let _e36 = val;
+    val = (_e36 + i64((_e31 + f32(_e32))));
+    let _e40 = input_uniform.val_i64_;
+    let _e43 = val;
+    val = (_e43 + vec3(_e40).z);
+    let _e47 = input_uniform.val_u64_;
+    let _e49 = val;
+    val = (_e49 + bitcast<i64>(_e47));
+    let _e55 = input_uniform.val_i64_;
+    let _e58 = input_storage.val_i64_;
+    output.val_i64_ = (_e55 + _e58);
+    let _e64 = input_uniform.val_i64_2_;
+    let _e67 = input_storage.val_i64_2_;
+    output.val_i64_2_ = (_e64 + _e67);
+    let _e73 = input_uniform.val_i64_3_;
+    let _e76 = input_storage.val_i64_3_;
+    output.val_i64_3_ = (_e73 + _e76);
+    let _e82 = input_uniform.val_i64_4_;
+    let _e85 = input_storage.val_i64_4_;
+    output.val_i64_4_ = (_e82 + _e85);
+    let _e91 = input_arrays.val_i64_array_2_;
+    output_arrays.val_i64_array_2_ = _e91;
+    let _e92 = val;
+    let _e94 = val;
+    val = (_e94 + abs(_e92));
+    let _e96 = val;
+    let _e97 = val;
+    let _e98 = val;
+    let _e100 = val;
+    val = (_e100 + clamp(_e96, _e97, _e98));
+    let _e102 = val;
+    let _e104 = val;
+    let _e107 = val;
+    val = (_e107 + dot(vec2(_e102), vec2(_e104)));
+    let _e109 = val;
+    let _e110 = val;
+    let _e112 = val;
+    val = (_e112 + max(_e109, _e110));
+    let _e114 = val;
+    let _e115 = val;
+    let _e117 = val;
+    val = (_e117 + min(_e114, _e115));
+    let _e119 = val;
+    let _e121 = val;
+    val = (_e121 + sign(_e119));
+    let _e123 = val;
+    return _e123;
+}
+
+fn uint64_function(x_1: u64) -> u64 {
+    var val_1: u64 = 20lu;
+
+    let _e4 = val_1;
+    val_1 = (_e4 + 1002003004005037lu);
+    let _e6 = val_1;
+    let _e9 = val_1;
+    val_1 = (_e9 + (_e6 + 5lu));
+    let _e13 = input_uniform.val_u32_;
+    let _e14 = val_1;
+    let _e18 = val_1;
+    val_1 = (_e18 + u64((_e13 + u32(_e14))));
+    let _e22 = input_uniform.val_i32_;
+    let _e23 = val_1;
+    let _e27 = val_1;
+    val_1 = (_e27 + u64((_e22 + i32(_e23))));
+    let _e31 = input_uniform.val_f32_;
+    let _e32 = val_1;
+    let _e36 = val_1;
+    val_1 = (_e36 + u64((_e31 + f32(_e32))));
+    let _e40 = input_uniform.val_u64_;
+    let _e43 = val_1;
+    val_1 = (_e43 + vec3(_e40).z);
+    let _e47 = input_uniform.val_i64_;
+    let _e49 = val_1;
+    val_1 = (_e49 + bitcast<u64>(_e47));
+    let _e55 = input_uniform.val_u64_;
+    let _e58 = input_storage.val_u64_;
+    output.val_u64_ = (_e55 + _e58);
+    let _e64 = input_uniform.val_u64_2_;
+    let _e67 = input_storage.val_u64_2_;
+    output.val_u64_2_ = (_e64 + _e67);
+    let _e73 = input_uniform.val_u64_3_;
+    let _e76 = input_storage.val_u64_3_;
+    output.val_u64_3_ = (_e73 + _e76);
+    let _e82 = input_uniform.val_u64_4_;
+    let _e85 = input_storage.val_u64_4_;
+    output.val_u64_4_ = (_e82 + _e85);
+    let _e91 = input_arrays.val_u64_array_2_;
+    output_arrays.val_u64_array_2_ = _e91;
+    let _e92 = val_1;
+    let _e94 = val_1;
+    val_1 = (_e94 + abs(_e92));
+    let _e96 = val_1;
+    let _e97 = val_1;
+    let _e98 = val_1;
+    let _e100 = val_1;
+    val_1 = (_e100 + clamp(_e96, _e97, _e98));
+    let _e102 = val_1;
+    let _e104 = val_1;
+    let _e107 = val_1;
+    val_1 = (_e107 + dot(vec2(_e102), vec2(_e104)));
+    let _e109 = val_1;
+    let _e110 = val_1;
+    let _e112 = val_1;
+    val_1 = (_e112 + max(_e109, _e110));
+    let _e114 = val_1;
+    let _e115 = val_1;
+    let _e117 = val_1;
+    val_1 = (_e117 + min(_e114, _e115));
+    let _e119 = val_1;
+    return _e119;
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    let _e3 = uint64_function(67lu);
+    let _e5 = int64_function(60li);
+    output.final_value = (_e3 + bitcast<u64>(_e5));
+    return;
+}
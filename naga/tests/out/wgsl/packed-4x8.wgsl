@@ -0,0 +1,23 @@
+const packed_dot: i32 = 34;
+const packed_vector: u32 = 2130837376u;
+const unpacked_vector: vec4<u32> = vec4<u32>(2u, 1u, 255u, 128u);
+
+@group(0) @binding(0) 
+var<storage, read_write> out: array<u32>;
+
+@compute @workgroup_size(1, 1, 1) 
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let a = out[0];
+    let b = out[1];
+    let sdot = dot4I8Packed(a, b);
+    let udot = dot4U8Packed(a, b);
+    let signed_bytes = unpack4xI8(a);
+    let unsigned_bytes = unpack4xU8(b);
+    out[2] = pack4xI8(signed_bytes);
+    out[3] = pack4xU8(unsigned_bytes);
+    out[4] = pack4xI8Clamp((signed_bytes * 2));
+    out[5] = pack4xU8Clamp((unsigned_bytes * 2u));
+    out[6] = (bitcast<u32>(sdot) + udot);
+    out[7] = ((bitcast<u32>(packed_dot) + packed_vector) + 128u);
+    return;
+}
@@ -0,0 +1,63 @@
+struct Particle {
+    position: vec4<f32>,
+    velocity: vec4<f32>,
+}
+
+struct Particles {
+    count: u32,
+    items: array<Particle>,
+}
+
+struct Params {
+    gravity: vec4<f32>,
+    scales: array<vec4<f32>, 4>,
+}
+
+@group(0) @binding(0) 
+var<storage, read_write> particles: Particles;
+@group(0) @binding(1) 
+var<uniform> params: Params;
+@group(0) @binding(2) 
+var<storage, read_write> totals: array<atomic<u32>, 8>;
+var<workgroup> scratch: array<vec4<f32>, 64>;
+
+fn step(particle: ptr<storage, Particle, read_write>, gravity: ptr<uniform, vec4<f32>>) {
+    let _e3 = (*gravity);
+    let _e4 = (*particle).velocity;
+    (*particle).velocity = (_e4 + _e3);
+    let _e8 = (*particle).velocity;
+    let _e9 = (*particle).position;
+    (*particle).position = (_e9 + _e8);
+    return;
+}
+
+fn scaled(scale: ptr<uniform, vec4<f32>>, value: vec4<f32>) -> vec4<f32> {
+    let _e2 = (*scale);
+    return (value * _e2);
+}
+
+fn stash(slot: ptr<workgroup, vec4<f32>>, particle_1: ptr<storage, Particle, read_write>, scale_1: ptr<uniform, vec4<f32>>) {
+    let _e4 = (*particle_1).position;
+    let _e5 = scaled(scale_1, _e4);
+    (*slot) = _e5;
+    return;
+}
+
+fn count(total: ptr<storage, atomic<u32>, read_write>) {
+    let _e2 = atomicAdd(total, 1u);
+    return;
+}
+
+@compute @workgroup_size(64, 1, 1) 
+fn main(@builtin(global_invocation_id) id: vec3<u32>, @builtin(local_invocation_index) index: u32) {
+    let _e5 = particles.count;
+    if (id.x >= _e5) {
+        return;
+    }
+    step((&particles.items[id.x]), (&params.gravity));
+    stash((&scratch[index]), (&particles.items[id.x]), (&params.scales[(id.x % 4u)]));
+    stash((&scratch[63]), (&particles.items[0]), (&params.scales[1]));
+    count((&totals[(id.x % 8u)]));
+    workgroupBarrier();
+    return;
+}
@@ -0,0 +1,43 @@
+@group(0) @binding(0) 
+var s_r_r: texture_storage_2d<r32float,read>;
+@group(0) @binding(1) 
+var s_r_rg: texture_storage_2d<rgba8unorm,read>;
+@group(0) @binding(2) 
+var s_rw_r: texture_storage_2d<r32float,read_write>;
+@group(0) @binding(3) 
+var s_rw_i: texture_storage_2d<r32sint,read_write>;
+@group(0) @binding(4) 
+var s_rw_u: texture_storage_2d_array<r32uint,read_write>;
+@group(0) @binding(5) 
+var s_w_r: texture_storage_2d<rgba16float,write>;
+
+@compute @workgroup_size(8, 8, 1) 
+fn csLoad(@builtin(global_invocation_id) id: vec3<u32>) {
+    let coords = vec2<i32>(id.xy);
+    let r = textureLoad(s_r_r, coords);
+    let rgba = textureLoad(s_r_rg, coords);
+    textureStore(s_w_r, coords, (rgba * r.x));
+    return;
+}
+
+@compute @workgroup_size(8, 8, 1) 
+fn csReadWrite(@builtin(global_invocation_id) id_1: vec3<u32>) {
+    let coords_1 = vec2<i32>(id_1.xy);
+    let f = textureLoad(s_rw_r, coords_1);
+    textureStore(s_rw_r, coords_1, (f * 2.0));
+    let i = textureLoad(s_rw_i, coords_1);
+    textureStore(s_rw_i, coords_1, (i + vec4(1)));
+    textureBarrier();
+    let u = textureLoad(s_rw_u, coords_1, 0);
+    textureStore(s_rw_u, coords_1, 1, u);
+    return;
+}
+
+@fragment 
+fn fsReadWrite(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let coords_2 = vec2<i32>(position.xy);
+    let f_1 = textureLoad(s_rw_r, coords_2);
+    textureStore(s_rw_r, coords_2, (f_1 + vec4(1.0)));
+    let _e10 = textureLoad(s_r_rg, coords_2);
+    return _e10;
+}
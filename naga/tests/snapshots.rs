@@ -309,6 +309,25 @@ fn check_targets(
         }
     }
 
+    // The SPIR-V, GLSL and HLSL back ends need functions taking pointers
+    // into some address spaces to be specialized first.
+    let specialized = naga::proc::find_unspecialized_pointer_argument(module).map(|_| {
+        let mut specialized = module.clone();
+        naga::proc::specialize_pointer_arguments(&mut specialized);
+        let info = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), capabilities)
+            .validate(&specialized)
+            .unwrap_or_else(|_| {
+                panic!(
+                    "Specialized module validation failed on test '{}'",
+                    name.display()
+                )
+            });
+        (specialized, info)
+    });
+    let (specialized_module, specialized_info) = specialized
+        .as_ref()
+        .map_or((&*module, &info), |(module, info)| (module, info));
+
     #[cfg(all(feature = "deserialize", feature = "spv-out"))]
     {
         let debug_info = if cfg!(feature = "span") {
@@ -323,8 +342,8 @@ fn check_targets(
         if targets.contains(Targets::SPIRV) {
             write_output_spv(
                 input,
-                module,
-                &info,
+                specialized_module,
+                specialized_info,
                 debug_info,
                 &params.spv,
                 params.bounds_check_policies,
@@ -347,14 +366,14 @@ fn check_targets(
     #[cfg(all(feature = "deserialize", feature = "glsl-out"))]
     {
         if targets.contains(Targets::GLSL) {
-            for ep in module.entry_points.iter() {
+            for ep in specialized_module.entry_points.iter() {
                 if params.glsl_exclude_list.contains(&ep.name) {
                     continue;
                 }
                 write_output_glsl(
                     input,
-                    module,
-                    &info,
+                    specialized_module,
+                    specialized_info,
                     ep.stage,
                     &ep.name,
                     &params.glsl,
@@ -374,7 +393,7 @@ fn check_targets(
     #[cfg(all(feature = "deserialize", feature = "hlsl-out"))]
     {
         if targets.contains(Targets::HLSL) {
-            write_output_hlsl(input, module, &info, &params.hlsl);
+            write_output_hlsl(input, specialized_module, specialized_info, &params.hlsl);
         }
     }
     #[cfg(all(feature = "deserialize", feature = "wgsl-out"))]
@@ -755,6 +774,29 @@ fn convert_wgsl() {
         ("lexical-scopes", Targets::WGSL),
         ("type-alias", Targets::WGSL),
         ("module-scope", Targets::WGSL),
+        ("abstract-types", Targets::METAL | Targets::WGSL),
+        (
+            "int64",
+            Targets::SPIRV | Targets::HLSL | Targets::METAL | Targets::WGSL,
+        ),
+        (
+            "atomicOps-int64-min-max",
+            Targets::SPIRV | Targets::HLSL | Targets::METAL | Targets::WGSL,
+        ),
+        ("external-texture", Targets::WGSL),
+        (
+            "packed-4x8",
+            Targets::SPIRV | Targets::GLSL | Targets::HLSL | Targets::METAL | Targets::WGSL,
+        ),
+        ("packed-4x8-native", Targets::HLSL),
+        (
+            "storage-textures",
+            Targets::GLSL | Targets::HLSL | Targets::METAL | Targets::WGSL,
+        ),
+        (
+            "pointer-args",
+            Targets::SPIRV | Targets::GLSL | Targets::HLSL | Targets::METAL | Targets::WGSL,
+        ),
         (
             "workgroup-var-init",
             Targets::WGSL | Targets::GLSL | Targets::SPIRV | Targets::HLSL | Targets::METAL,
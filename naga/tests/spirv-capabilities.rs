@@ -7,6 +7,13 @@ Test SPIR-V backend capability checks.
 use spirv::Capability as Ca;
 
 fn capabilities_used(source: &str) -> naga::FastIndexSet<Ca> {
+    capabilities_used_with(&naga::back::spv::Options::default(), source)
+}
+
+fn capabilities_used_with(
+    options: &naga::back::spv::Options,
+    source: &str,
+) -> naga::FastIndexSet<Ca> {
     use naga::back::spv;
     use naga::valid;
 
@@ -22,7 +29,7 @@ fn capabilities_used(source: &str) -> naga::FastIndexSet<Ca> {
         .expect("validation failed");
 
     let mut words = vec![];
-    let mut writer = spv::Writer::new(&spv::Options::default()).unwrap();
+    let mut writer = spv::Writer::new(options).unwrap();
     writer
         .write(&module, &info, None, &None, &mut words)
         .unwrap();
@@ -176,3 +183,57 @@ fn storage_image_formats() {
         "#,
     );
 }
+
+#[test]
+fn int64() {
+    require_and_forbid(
+        &[Ca::Int64],
+        &[Ca::Int64Atomics],
+        "fn f(x: u64) -> i64 { return i64(x) + 1li; }",
+    );
+}
+
+#[test]
+fn int64_atomics() {
+    require(
+        &[Ca::Int64, Ca::Int64Atomics],
+        r#"
+            @group(0) @binding(0)
+            var<storage, read_write> counter: atomic<u64>;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                atomicMax(&counter, 1lu);
+            }
+        "#,
+    );
+}
+
+#[test]
+fn packed_dot_product() {
+    use naga::back::spv;
+
+    let source =
+        "fn f(a: u32, b: u32) -> i32 { return dot4I8Packed(a, b) + i32(dot4U8Packed(a, b)); }";
+    let native = [Ca::DotProduct, Ca::DotProductInput4x8BitPacked];
+
+    // SPIR-V 1.0 falls back to unpacking the bytes.
+    let caps_used = capabilities_used(source);
+    assert!(native.iter().all(|cap| !caps_used.contains(cap)));
+
+    // SPIR-V 1.6 has `OpSDot` and `OpUDot` in core.
+    let options = spv::Options {
+        lang_version: (1, 6),
+        ..spv::Options::default()
+    };
+    let caps_used = capabilities_used_with(&options, source);
+    assert!(native.iter().all(|cap| caps_used.contains(cap)));
+
+    // Older versions can use them through `SPV_KHR_integer_dot_product`.
+    let options = spv::Options {
+        capabilities: Some(native.iter().cloned().chain([Ca::Shader]).collect()),
+        ..spv::Options::default()
+    };
+    let caps_used = capabilities_used_with(&options, source);
+    assert!(native.iter().all(|cap| caps_used.contains(cap)));
+}
@@ -129,6 +129,28 @@ fn bad_texture() {
     );
 }
 
+#[test]
+fn bad_sample_base_clamp_to_edge() {
+    check(
+        r#"
+            @group(0) @binding(0) var sampler1 : sampler;
+            @group(0) @binding(1) var texture : texture_2d_array<f32>;
+
+            @fragment
+            fn main() -> @location(0) vec4<f32> {
+                return textureSampleBaseClampToEdge(texture, sampler1, vec2<f32>(0.0));
+            }
+        "#,
+        r#"error: `textureSampleBaseClampToEdge` expects a `texture_2d<f32>` or `texture_external`, but found 'texture'
+  ┌─ wgsl:7:53
+  │
+7 │                 return textureSampleBaseClampToEdge(texture, sampler1, vec2<f32>(0.0));
+  │                                                     ^^^^^^^ invalid texture type
+
+"#,
+    );
+}
+
 #[test]
 fn bad_type_cast() {
     check(
@@ -1065,41 +1087,23 @@ fn invalid_functions() {
         })
     }
 
-    // Pointers of these address spaces cannot be passed as arguments.
+    // Pointers of these address spaces can be passed as arguments.
     check_validation! {
-        "fn unacceptable_ptr_space(arg: ptr<storage, array<f32>>) { }":
-        Err(naga::valid::ValidationError::Function {
-            name: function_name,
-            source: naga::valid::FunctionError::InvalidArgumentPointerSpace {
-                index: 0,
-                name: argument_name,
-                space: naga::AddressSpace::Storage { .. },
-            },
-            ..
-        })
-        if function_name == "unacceptable_ptr_space" && argument_name == "arg"
-    }
-    check_validation! {
-        "fn unacceptable_ptr_space(arg: ptr<uniform, f32>) { }":
-        Err(naga::valid::ValidationError::Function {
-            name: function_name,
-            source: naga::valid::FunctionError::InvalidArgumentPointerSpace {
-                index: 0,
-                name: argument_name,
-                space: naga::AddressSpace::Uniform,
-            },
-            ..
-        })
-        if function_name == "unacceptable_ptr_space" && argument_name == "arg"
+        "fn acceptable_ptr_space(arg: ptr<storage, array<f32>>) { }",
+        "fn acceptable_ptr_space(arg: ptr<uniform, f32>) { }",
+        "fn acceptable_ptr_space(arg: ptr<workgroup, f32>) { }":
+        Ok(_)
     }
+
+    // Pointers of this address space cannot be passed as arguments.
     check_validation! {
-        "fn unacceptable_ptr_space(arg: ptr<workgroup, f32>) { }":
+        "fn unacceptable_ptr_space(arg: ptr<push_constant, f32>) { }":
         Err(naga::valid::ValidationError::Function {
             name: function_name,
             source: naga::valid::FunctionError::InvalidArgumentPointerSpace {
                 index: 0,
                 name: argument_name,
-                space: naga::AddressSpace::WorkGroup,
+                space: naga::AddressSpace::PushConstant,
             },
             ..
         })
@@ -1869,15 +1873,15 @@ fn switch_signed_unsigned_mismatch() {
         "
         fn x(y: u32) {
 	        switch y {
-		        case 1: {}
+		        case 1i: {}
 	        }
         }
         ",
         r###"error: invalid switch value
   ┌─ wgsl:4:16
   │
-4 │                 case 1: {}
-  │                      ^ expected unsigned integer
+4 │                 case 1i: {}
+  │                      ^^ expected unsigned integer
   │
   = note: suffix the integer with a `u`: '1u'
 
@@ -1992,3 +1996,173 @@ fn binding_array_non_struct() {
         })
     }
 }
+
+#[test]
+fn abstract_int_not_representable() {
+    check(
+        "var<private> x: u32 = -1;",
+        r###"error: -1 is not representable as u32
+  ┌─ wgsl:1:23
+  │
+1 │ var<private> x: u32 = -1;
+  │                       ^^ see msg
+
+"###,
+    );
+
+    check(
+        "fn f() { let y = 1 << 40; }",
+        r###"error: 1099511627776 is not representable as i32
+  ┌─ wgsl:1:18
+  │
+1 │ fn f() { let y = 1 << 40; }
+  │                  ^^^^^^^ see msg
+
+"###,
+    );
+}
+
+#[test]
+fn abstract_float_not_representable() {
+    check(
+        "const x = 1e40; const y: f32 = x;",
+        r###"error: 1e40 is not representable as f32
+  ┌─ wgsl:1:11
+  │
+1 │ const x = 1e40; const y: f32 = x;
+  │           ^^^^ see msg
+
+"###,
+    );
+}
+
+#[test]
+fn abstract_int_overflow() {
+    check(
+        "const x = 9223372036854775807 + 1;",
+        r###"error: addition operation overflowed
+  ┌─ wgsl:1:11
+  │
+1 │ const x = 9223372036854775807 + 1;
+  │           ^^^^^^^^^^^^^^^^^^^^^^^ see msg
+
+"###,
+    );
+}
+
+#[test]
+fn int64_requires_capability() {
+    check_validation! {
+        "var<private> x: i64;",
+        "var<private> x: vec2<u64>;":
+        Err(naga::valid::ValidationError::Type {
+            source: naga::valid::TypeError::WidthError(
+                naga::valid::WidthError::MissingCapability { flag: "SHADER_INT64", .. }
+            ),
+            ..
+        })
+    }
+}
+
+#[test]
+fn int64_atomics() {
+    fn validate(source: &str) -> Result<naga::valid::ModuleInfo, naga::valid::ValidationError> {
+        let module = naga::front::wgsl::parse_str(source).unwrap();
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::SHADER_INT64
+                | naga::valid::Capabilities::SHADER_INT64_ATOMIC_MIN_MAX,
+        )
+        .validate(&module)
+        .map_err(|e| e.into_inner())
+    }
+
+    assert!(validate(
+        "
+        @group(0) @binding(0)
+        var<storage, read_write> counter: atomic<u64>;
+
+        fn f() {
+            atomicMin(&counter, 1lu);
+        }
+        "
+    )
+    .is_ok());
+
+    assert!(matches!(
+        validate(
+            "
+            @group(0) @binding(0)
+            var<storage, read_write> counter: atomic<u64>;
+
+            fn f() {
+                atomicAdd(&counter, 1lu);
+            }
+            "
+        ),
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidAtomic(
+                naga::valid::AtomicError::Unsupported64BitFunction(naga::AtomicFunction::Add)
+            ),
+            ..
+        })
+    ));
+
+    assert!(matches!(
+        validate(
+            "
+            var<workgroup> counter: atomic<u64>;
+
+            fn f() {
+                atomicMax(&counter, 1lu);
+            }
+            "
+        ),
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidAtomic(
+                naga::valid::AtomicError::Unsupported64BitAddressSpace(
+                    naga::AddressSpace::WorkGroup
+                )
+            ),
+            ..
+        })
+    ));
+
+    check_validation! {
+        "var<workgroup> counter: atomic<u64>;":
+        Err(naga::valid::ValidationError::Type {
+            source: naga::valid::TypeError::WidthError(
+                naga::valid::WidthError::MissingCapability { .. }
+            ),
+            ..
+        })
+    }
+}
+
+#[test]
+fn packed_4x8_argument_types() {
+    check_validation! {
+        "
+        fn f(v: vec4<u32>) -> u32 {
+            return pack4xI8(v);
+        }
+        ",
+        "
+        fn f(v: vec4<i32>) -> u32 {
+            return pack4xU8Clamp(v);
+        }
+        ",
+        "
+        fn f(v: vec2<u32>) -> i32 {
+            return dot4I8Packed(v.x, bitcast<i32>(v.y));
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::Expression {
+                source: naga::valid::ExpressionError::InvalidArgumentType(..),
+                ..
+            },
+            ..
+        })
+    }
+}
@@ -0,0 +1,222 @@
+/*!
+Tests for the WGSL formatter.
+*/
+#![cfg(all(feature = "wgsl-in", feature = "span"))]
+
+use naga::front::wgsl::format_str;
+
+fn check(input: &str, expected: &str) {
+    let output = format_str(input).unwrap_or_else(|e| panic!("{}", e.emit_to_string(input)));
+    if output != expected {
+        for diff in diff::lines(expected, &output) {
+            match diff {
+                diff::Result::Left(l) => println!("-{l}"),
+                diff::Result::Both(l, _) => println!(" {l}"),
+                diff::Result::Right(r) => println!("+{r}"),
+            }
+        }
+        panic!("unexpected formatting");
+    }
+    assert_eq!(format_str(&output).unwrap(), output, "not idempotent");
+}
+
+#[test]
+fn declarations() {
+    check(
+        "
+// Lights.
+struct Light{color:vec3f,   @align(16) intensity : f32};
+
+
+@group(0)@binding( 0 )var<uniform>light:Light; // the only one
+@group(0) @binding(1)
+var<storage,read_write> out: array<vec4<f32> >;
+const SCALE:f32=2.0;alias Color=vec3<f32>;
+",
+        "\
+// Lights.
+struct Light {
+    color: vec3f,
+    @align(16) intensity: f32,
+}
+
+@group(0) @binding(0) var<uniform> light: Light; // the only one
+@group(0) @binding(1)
+var<storage, read_write> out: array<vec4<f32>>;
+const SCALE: f32 = 2.0;
+alias Color = vec3<f32>;
+",
+    );
+}
+
+#[test]
+fn functions() {
+    check(
+        "
+@fragment
+// The entry point.
+fn main(@builtin(position) position:vec4<f32>,@location(0)uv:vec2f)->@location(0) vec4<f32>{
+let x=(uv.x+1.0)*0.5;var y=-uv.y; // flipped
+if x>0.5{return vec4(1.0);}else if (x < 0.25) {discard;}else{}
+
+
+for(var i=0;i<4;i++){y+=f32(i);}
+while y>0.0{y-=1.0;}
+switch u32(x) {case 0u,1u:{} default{y = 0.0;}}
+loop { if y < 0.0 { break; } continuing { y -= 1.0; break if y<-1.0; } }
+return vec4(x,y,-(-y),1.0);}
+fn helper()->void{}
+",
+        "\
+@fragment
+// The entry point.
+fn main(@builtin(position) position: vec4<f32>, @location(0) uv: vec2f) -> @location(0) vec4<f32> {
+    let x = (uv.x + 1.0) * 0.5;
+    var y = -uv.y; // flipped
+    if x > 0.5 {
+        return vec4(1.0);
+    } else if (x < 0.25) {
+        discard;
+    } else {}
+
+    for (var i = 0; i < 4; i++) {
+        y += f32(i);
+    }
+    while y > 0.0 {
+        y -= 1.0;
+    }
+    switch u32(x) {
+        case 0u, 1u {}
+        default {
+            y = 0.0;
+        }
+    }
+    loop {
+        if y < 0.0 {
+            break;
+        }
+        continuing {
+            y -= 1.0;
+            break if y < -1.0;
+        }
+    }
+    return vec4(x, y, -(-y), 1.0);
+}
+fn helper() -> void {}
+",
+    );
+}
+
+#[test]
+fn split_arguments() {
+    check(
+        "
+fn f() {
+    let m = mat3x3<f32>(
+        1.0, 0.0, 0.0, // first column
+        0.0, 1.0, 0.0,
+        0.0, 0.0, 1.0);
+    let long = some_function_with_a_long_name(first_argument_value, second_argument_value, third_value);
+    let short = max(
+        a, b);
+}
+",
+        "\
+fn f() {
+    let m = mat3x3<f32>(
+        1.0,
+        0.0,
+        0.0, // first column
+        0.0,
+        1.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+    );
+    let long = some_function_with_a_long_name(
+        first_argument_value,
+        second_argument_value,
+        third_value,
+    );
+    let short = max(
+        a,
+        b,
+    );
+}
+",
+    );
+}
+
+#[test]
+fn comments() {
+    check(
+        "/* header */
+fn f() { // opening
+    // leading
+    let a = 1 + /* inside */ 2;
+
+    /* before return */ return;
+    // at the end
+}
+// trailing
+",
+        "\
+/* header */
+fn f() { // opening
+    // leading
+    let a = 1 + 2;
+    /* inside */
+
+    /* before return */
+    return;
+    // at the end
+}
+// trailing
+",
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    assert!(format_str("fn f() { let x = ; }").is_err());
+}
+
+/// Formatting the shaders in `tests/in` doesn't change what they mean.
+#[cfg(all(feature = "validate", feature = "wgsl-out"))]
+#[test]
+fn snapshot_inputs() {
+    use naga::{back::wgsl, valid};
+
+    fn to_wgsl(source: &str) -> Option<String> {
+        let module = naga::front::wgsl::parse_str(source).ok()?;
+        let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+            .validate(&module)
+            .ok()?;
+        wgsl::write_string(&module, &info, wgsl::WriterFlags::empty()).ok()
+    }
+
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/in");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("wgsl") {
+            continue;
+        }
+
+        let source = std::fs::read_to_string(&path).unwrap();
+        let formatted = format_str(&source)
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e.emit_to_string(&source)));
+        assert_eq!(
+            format_str(&formatted).unwrap(),
+            formatted,
+            "formatting {} is not idempotent",
+            path.display()
+        );
+        assert_eq!(
+            to_wgsl(&formatted),
+            to_wgsl(&source),
+            "formatting {} changed its meaning",
+            path.display()
+        );
+    }
+}
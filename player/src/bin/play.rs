@@ -1,15 +1,192 @@
 /*! This is a player for WebGPU traces.
 !*/
 
+#[cfg(not(target_arch = "wasm32"))]
+const HELP: &str = "\
+Usage: play [OPTIONS] <TRACE>
+       play codegen <TRACE> <OUT_DIR>
+       play minimize [OPTIONS] <PREDICATE> <TRACE> <OUTPUT>
+       play stats <TRACE>
+       play diff <TRACE> <TRACE>
+
+Replays a trace directory, or a binary `.wgputrace` trace file.
+
+Options:
+  -h, --help               Print this help message.
+  -b, --backend <NAME>     Replay on this backend instead of the recorded one:
+                           vulkan, metal, dx12, dx11 or gl.
+  -f, --frame <N>          Stop at the N-th presented frame, counting from 1,
+                           before it is presented.
+  -d, --dump-dir <PATH>    Directory to dump resources into. Defaults to \"dump\".
+      --dump-all           Dump every live buffer and texture when stopping.
+      --dump-buffer <ID>   Dump the buffer with this ID index. Can be repeated.
+      --dump-texture <ID>  Dump the texture with this ID index. Can be repeated.
+
+Resources are dumped when stopping at the requested frame, or at the end of
+the trace. Without a window, surface textures are replaced by offscreen
+textures, which can be dumped as well. Only the first mip level of textures
+is dumped, one file per array layer, with separate files for the depth and
+stencil aspects. The depth of `Depth24Plus` formats can't be dumped.
+
+The `codegen` command writes a Cargo package into OUT_DIR, with a program
+that reproduces the trace using the public `wgpu` API. The package depends
+on the `wgpu` and `naga` crates of the checkout the player was built from.
+
+The `minimize` command removes actions and commands from the trace for as
+long as the replay keeps matching the predicate, and writes the reduced trace
+to OUTPUT. The trace is replayed in a new process for every attempt.
+
+Predicates:
+      --error <TEXT>       The replay fails with a message containing TEXT.
+      --panic              The replay fails.
+      --texture <ID>       The texture with this ID index is dumped at the end
+      --reference <FILE>   of the replay, and differs from FILE.
+
+The `stats` command prints a summary of the trace: the number of actions and
+commands of each kind, the bytes uploaded, the objects alive, the work of
+every frame, and the shader modules. The `diff` command prints where the
+summaries of two traces differ. Neither of them needs a GPU.
+";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn exit_with_help() -> ! {
+    eprintln!("{HELP}");
+    std::process::exit(101);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn invalid_argument(error: pico_args::Error) -> ! {
+    eprintln!("{error}");
+    exit_with_help();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_backend(name: &str) -> Result<wgt::Backend, String> {
+    Ok(match name.to_lowercase().as_str() {
+        "vulkan" | "vk" => wgt::Backend::Vulkan,
+        "metal" | "mtl" => wgt::Backend::Metal,
+        "dx12" | "d3d12" => wgt::Backend::Dx12,
+        "dx11" | "d3d11" => wgt::Backend::Dx11,
+        "gl" | "gles" | "opengl" => wgt::Backend::Gl,
+        _ => return Err(format!("unknown backend {name:?}")),
+    })
+}
+
+/// What a reduced trace has to reproduce.
+#[cfg(not(target_arch = "wasm32"))]
+enum Predicate {
+    Error(String),
+    Panic,
+    Texture {
+        index: u32,
+        reference: std::path::PathBuf,
+    },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn minimize(mut args: pico_args::Arguments) {
+    use std::{path::PathBuf, process::Command};
+
+    let backend: Option<String> = args
+        .opt_value_from_str(["-b", "--backend"])
+        .unwrap_or_else(|e| invalid_argument(e));
+    let error: Option<String> = args
+        .opt_value_from_str("--error")
+        .unwrap_or_else(|e| invalid_argument(e));
+    let panic = args.contains("--panic");
+    let texture: Option<u32> = args
+        .opt_value_from_str("--texture")
+        .unwrap_or_else(|e| invalid_argument(e));
+    let reference: Option<PathBuf> = args
+        .opt_value_from_str("--reference")
+        .unwrap_or_else(|e| invalid_argument(e));
+    let predicate = match (error, panic, texture, reference) {
+        (Some(text), false, None, None) => Predicate::Error(text),
+        (None, true, None, None) => Predicate::Panic,
+        (None, false, Some(index), Some(reference)) => Predicate::Texture { index, reference },
+        _ => exit_with_help(),
+    };
+    let (path, output): (PathBuf, PathBuf) = match (args.free_from_str(), args.free_from_str()) {
+        (Ok(path), Ok(output)) => (path, output),
+        _ => exit_with_help(),
+    };
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        eprintln!("Unknown argument(s): {remaining:?}");
+        exit_with_help();
+    }
+
+    log::info!("Loading trace '{:?}'", path);
+    let (actions, data_source) = wgc::device::trace::load(&path, None).unwrap();
+    log::info!("Found {} actions", actions.len());
+
+    let work_dir = std::env::temp_dir().join(format!("wgpu-minimize-{}", std::process::id()));
+    let candidate_dir = work_dir.join("trace");
+    let dump_dir = work_dir.join("dump");
+    let reproduces = |actions: &[wgc::device::trace::Action<'static>]| {
+        let _ = std::fs::remove_dir_all(&work_dir);
+        player::minimize::write(actions, &data_source, &candidate_dir).unwrap();
+
+        let mut replay = Command::new(std::env::current_exe().unwrap());
+        if let Some(ref backend) = backend {
+            replay.args(["--backend", backend]);
+        }
+        if let Predicate::Texture { index, .. } = predicate {
+            replay.arg("--dump-texture").arg(index.to_string());
+            replay.arg("--dump-dir").arg(&dump_dir);
+        }
+        let result = replay.arg(&candidate_dir).output().unwrap();
+
+        match predicate {
+            Predicate::Error(ref text) => {
+                !result.status.success()
+                    && (String::from_utf8_lossy(&result.stderr).contains(text.as_str())
+                        || String::from_utf8_lossy(&result.stdout).contains(text.as_str()))
+            }
+            Predicate::Panic => !result.status.success(),
+            Predicate::Texture {
+                index,
+                ref reference,
+            } => {
+                let prefix = format!("texture-{index}-layer0.");
+                let dumped = std::fs::read_dir(&dump_dir)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .find(|entry| entry.file_name().to_string_lossy().starts_with(&prefix));
+                result.status.success()
+                    && dumped.is_some_and(|entry| {
+                        std::fs::read(entry.path()).ok() != std::fs::read(reference).ok()
+                    })
+            }
+        }
+    };
+
+    if !reproduces(&actions) {
+        eprintln!("The full trace doesn't match the predicate");
+        std::process::exit(1);
+    }
+    let length = actions.len();
+    let actions = player::minimize::minimize(actions, reproduces);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    player::minimize::write(&actions, &data_source, &output).unwrap();
+    println!(
+        "Reduced the trace from {} to {} actions, written to {:?}",
+        length,
+        actions.len(),
+        output
+    );
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
-    use player::{GlobalPlay as _, IdentityPassThroughFactory};
+    use player::{
+        dump::{DumpSelection, Dumper},
+        GlobalPlay as _, IdentityPassThroughFactory,
+    };
     use wgc::{device::trace, gfx_select};
 
-    use std::{
-        fs,
-        path::{Path, PathBuf},
-    };
+    use std::path::PathBuf;
 
     #[cfg(feature = "winit")]
     use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
@@ -23,20 +200,102 @@ fn main() {
 
     env_logger::init();
 
-    //TODO: setting for the backend bits
-    //TODO: setting for the target frame, or controls
-
-    let dir = match std::env::args().nth(1) {
-        Some(arg) if Path::new(&arg).is_dir() => PathBuf::from(arg),
-        _ => panic!("Provide the dir path as the parameter"),
+    let mut raw_args: Vec<_> = std::env::args_os().skip(1).collect();
+    let command = raw_args
+        .first()
+        .and_then(|arg| arg.to_str())
+        .filter(|&arg| matches!(arg, "codegen" | "minimize" | "stats" | "diff"))
+        .map(str::to_string);
+    if command.is_some() {
+        raw_args.remove(0);
+    }
+    let mut args = pico_args::Arguments::from_vec(raw_args);
+    if args.contains(["-h", "--help"]) {
+        exit_with_help();
+    }
+    if command.as_deref() == Some("minimize") {
+        minimize(args);
+        return;
+    }
+    if command.as_deref() == Some("stats") {
+        let path: PathBuf = args.free_from_str().unwrap_or_else(|_| exit_with_help());
+        let (actions, _) = trace::load(&path, None).unwrap();
+        print!("{}", player::stats::Stats::new(&actions));
+        return;
+    }
+    if command.as_deref() == Some("diff") {
+        let (before, after): (PathBuf, PathBuf) = match (args.free_from_str(), args.free_from_str())
+        {
+            (Ok(before), Ok(after)) => (before, after),
+            _ => exit_with_help(),
+        };
+        let (before, _) = trace::load(&before, None).unwrap();
+        let (after, _) = trace::load(&after, None).unwrap();
+        let (before, after) = (
+            player::stats::Stats::new(&before),
+            player::stats::Stats::new(&after),
+        );
+        print!(
+            "{}",
+            player::stats::Diff {
+                before: &before,
+                after: &after,
+            }
+        );
+        return;
+    }
+    if command.as_deref() == Some("codegen") {
+        let (path, out_dir): (PathBuf, PathBuf) = match (args.free_from_str(), args.free_from_str())
+        {
+            (Ok(path), Ok(out_dir)) => (path, out_dir),
+            _ => exit_with_help(),
+        };
+        log::info!("Loading trace '{:?}'", path);
+        let (actions, data_source) = trace::load(&path, None).unwrap();
+        player::codegen::generate(&actions, &data_source, &out_dir).unwrap();
+        log::info!("Generated a program in '{:?}'", out_dir);
+        return;
+    }
+    let backend_override: Option<wgt::Backend> = args
+        .opt_value_from_fn(["-b", "--backend"], parse_backend)
+        .unwrap_or_else(|e| invalid_argument(e));
+    let stop_frame: Option<u32> = args
+        .opt_value_from_str(["-f", "--frame"])
+        .unwrap_or_else(|e| invalid_argument(e));
+    let dump_dir: PathBuf = args
+        .opt_value_from_str(["-d", "--dump-dir"])
+        .unwrap_or_else(|e| invalid_argument(e))
+        .unwrap_or_else(|| PathBuf::from("dump"));
+    let selection = DumpSelection {
+        all: args.contains("--dump-all"),
+        buffers: args
+            .values_from_str("--dump-buffer")
+            .unwrap_or_else(|e| invalid_argument(e)),
+        textures: args
+            .values_from_str("--dump-texture")
+            .unwrap_or_else(|e| invalid_argument(e)),
     };
+    let path: PathBuf = match args.free_from_str() {
+        Ok(path) => path,
+        Err(_) => exit_with_help(),
+    };
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        eprintln!("Unknown argument(s): {remaining:?}");
+        exit_with_help();
+    }
 
-    log::info!("Loading trace '{:?}'", dir);
-    let file = fs::File::open(dir.join(trace::FILE_NAME)).unwrap();
-    let mut actions: Vec<trace::Action> = ron::de::from_reader(file).unwrap();
+    log::info!("Loading trace '{:?}'", path);
+    let (mut actions, data_source) = trace::load(&path, backend_override).unwrap();
     actions.reverse(); // allows us to pop from the top
     log::info!("Found {} actions", actions.len());
 
+    let mut dumper = if selection.is_empty() {
+        None
+    } else {
+        Some(Dumper::new(&dump_dir, selection, &actions))
+    };
+
     #[cfg(feature = "winit")]
     let event_loop = {
         log::info!("Creating a window");
@@ -67,6 +326,7 @@ fn main() {
 
     let device = match actions.pop() {
         Some(trace::Action::Init { desc, backend }) => {
+            let backend = backend_override.unwrap_or(backend);
             log::info!("Initializing the device for backend: {:?}", backend);
             let adapter = global
                 .request_adapter(
@@ -105,10 +365,77 @@ fn main() {
     log::info!("Executing actions");
     #[cfg(not(feature = "winit"))]
     {
+        use std::collections::HashMap;
+
         gfx_select!(device => global.device_start_capture(device));
 
-        while let Some(action) = actions.pop() {
-            gfx_select!(device => global.process(device, action, &dir, &mut command_buffer_id_manager));
+        // Without a window, surface textures are replaced by offscreen ones.
+        let mut surface_configs = HashMap::new();
+        let mut surface_textures = HashMap::new();
+        let mut frame_count = 0;
+        while let Some(mut action) = actions.pop() {
+            if let Some(ref mut dumper) = dumper {
+                dumper.prepare(&mut action);
+            }
+            match action {
+                trace::Action::ConfigureSurface(surface_id, config) => {
+                    log::info!("Configuring the offscreen surface");
+                    surface_configs.insert(surface_id, config);
+                }
+                trace::Action::GetSurfaceTexture { id, parent_id } => {
+                    let config: &wgt::SurfaceConfiguration<Vec<wgt::TextureFormat>> =
+                        &surface_configs[&parent_id];
+                    let desc = wgc::resource::TextureDescriptor {
+                        label: Some("(player) surface texture".into()),
+                        size: wgt::Extent3d {
+                            width: config.width,
+                            height: config.height,
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: wgt::TextureDimension::D2,
+                        format: config.format,
+                        usage: config.usage | wgt::TextureUsages::COPY_SRC,
+                        view_formats: config.view_formats.clone(),
+                    };
+                    gfx_select!(device => global.device_maintain_ids(device)).unwrap();
+                    let (_, error) =
+                        gfx_select!(device => global.device_create_texture(device, &desc, id));
+                    if let Some(e) = error {
+                        panic!("{e}");
+                    }
+                    if let Some(ref mut dumper) = dumper {
+                        dumper.add_texture(id, &desc);
+                    }
+                    surface_textures.insert(parent_id, id);
+                }
+                trace::Action::Present(surface_id)
+                | trace::Action::DiscardSurfaceTexture(surface_id) => {
+                    if let trace::Action::Present(_) = action {
+                        frame_count += 1;
+                        log::debug!("Presenting frame {}", frame_count);
+                        if stop_frame == Some(frame_count) {
+                            println!("Stopped at frame {}", frame_count);
+                            break;
+                        }
+                    }
+                    if let Some(texture) = surface_textures.remove(&surface_id) {
+                        if let Some(ref mut dumper) = dumper {
+                            dumper.remove_texture(texture);
+                        }
+                        gfx_select!(device => global.texture_drop(texture, false));
+                    }
+                }
+                action => {
+                    gfx_select!(device => global.process(device, action, &data_source, &mut command_buffer_id_manager));
+                }
+            }
+        }
+
+        if let Some(ref mut dumper) = dumper {
+            gfx_select!(device => dumper.dump(&global, device, &mut command_buffer_id_manager))
+                .unwrap();
         }
 
         gfx_select!(device => global.device_stop_capture(device));
@@ -153,6 +480,16 @@ fn main() {
                         Some(trace::Action::Present(id)) => {
                             frame_count += 1;
                             log::debug!("Presenting frame {}", frame_count);
+                            if stop_frame == Some(frame_count) {
+                                println!("Stopped at frame {}", frame_count);
+                                if let Some(ref mut dumper) = dumper {
+                                    gfx_select!(device => dumper.dump(&global, device, &mut command_buffer_id_manager))
+                                        .unwrap();
+                                }
+                                done = true;
+                                target.exit();
+                                return;
+                            }
                             gfx_select!(device => global.surface_present(id)).unwrap();
                                 target.exit();
                         }
@@ -161,12 +498,19 @@ fn main() {
                             gfx_select!(device => global.surface_texture_discard(id)).unwrap();
                                 target.exit();
                         }
-                        Some(action) => {
-                            gfx_select!(device => global.process(device, action, &dir, &mut command_buffer_id_manager));
+                        Some(mut action) => {
+                            if let Some(ref mut dumper) = dumper {
+                                dumper.prepare(&mut action);
+                            }
+                            gfx_select!(device => global.process(device, action, &data_source, &mut command_buffer_id_manager));
                         }
                         None => {
                             if !done {
                                 println!("Finished the end at frame {}", frame_count);
+                                if let Some(ref mut dumper) = dumper {
+                                    gfx_select!(device => dumper.dump(&global, device, &mut command_buffer_id_manager))
+                                        .unwrap();
+                                }
                                 done = true;
                             }
                                 target.exit();
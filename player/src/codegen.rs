@@ -0,0 +1,1586 @@
+//! Generating a standalone Rust program from a trace.
+//!
+//! The generated program replays the actions of a trace with the public
+//! `wgpu` API, so that a sequence of calls can be reproduced without the
+//! application that recorded it. It is written as a Cargo package:
+//! - `Cargo.toml` depends on `wgpu` and `naga` by path, in the checkout the
+//!   player was built from, so the package only builds next to that checkout.
+//! - `src/main.rs` holds the calls, with one variable per traced object.
+//! - `data/` holds the shader sources and the buffer and texture contents,
+//!   embedded with `include_bytes!` and `include_str!`.
+//!
+//! The program doesn't open a window: surface textures are replaced by
+//! offscreen textures of the configured size and format. Objects that the
+//! public API can't express are skipped, with a comment in the code.
+
+use wgc::{
+    command::{self, BasePass, ComputeCommand, RenderCommand},
+    device::trace,
+    id::{self, TypedId as _},
+};
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::{Debug, Write as _},
+    fs, io,
+    path::Path,
+};
+
+/// Write a Cargo package reproducing `actions` into `out_dir`.
+///
+/// `actions` must start with [`trace::Action::Init`].
+pub fn generate(
+    actions: &[trace::Action],
+    data_source: &trace::DataSource,
+    out_dir: &Path,
+) -> io::Result<()> {
+    let mut gen = Generator::default();
+    gen.line("async fn run() {");
+    gen.indent += 1;
+    for action in actions {
+        gen.action(action);
+    }
+    gen.line("device.poll(wgpu::Maintain::Wait);");
+    gen.indent -= 1;
+    gen.line("}");
+
+    fs::create_dir_all(out_dir.join("src"))?;
+    fs::create_dir_all(out_dir.join("data"))?;
+    for name in &gen.data_files {
+        fs::write(out_dir.join("data").join(name), data_source.read(name)?)?;
+    }
+    fs::write(out_dir.join("Cargo.toml"), gen.manifest())?;
+    fs::write(out_dir.join("src").join("main.rs"), gen.main())?;
+    if gen.skipped != 0 {
+        log::warn!(
+            "{} actions or commands could not be expressed with the wgpu API",
+            gen.skipped
+        );
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct Generator {
+    code: String,
+    indent: usize,
+    /// Data files to copy next to the program.
+    data_files: BTreeSet<String>,
+    /// Extra features of `wgpu` needed by the program.
+    wgpu_features: BTreeSet<&'static str>,
+    /// Whether naga modules are deserialized from RON.
+    naga_ron: bool,
+    /// Variables of the objects that currently exist.
+    variables: HashSet<String>,
+    surface_configs: HashMap<id::SurfaceId, wgt::SurfaceConfiguration<Vec<wgt::TextureFormat>>>,
+    surface_textures: HashMap<id::SurfaceId, id::TextureId>,
+    /// Buffers created mapped, which haven't been unmapped yet.
+    mapped_at_creation: HashSet<id::BufferId>,
+    /// Number of actions and commands that were skipped.
+    skipped: usize,
+}
+
+/// The path of the workspace crate `name`, as a TOML string.
+///
+/// The generated program uses the crates of the checkout the player was built
+/// from, since it may need APIs that aren't released yet.
+fn workspace_path(name: &str) -> String {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap_or_else(|| Path::new(".."));
+    format!("{:?}", root.join(name).display().to_string())
+}
+
+/// The name of the variable holding the object `id`.
+fn var<T>(kind: &str, id: id::Id<T>) -> String {
+    let (index, epoch, _) = id.unzip();
+    format!("{kind}_{index}_{epoch}")
+}
+
+fn buffer(id: id::BufferId) -> String {
+    var("buffer", id)
+}
+
+fn texture(id: id::TextureId) -> String {
+    var("texture", id)
+}
+
+fn view(id: id::TextureViewId) -> String {
+    var("view", id)
+}
+
+fn query_set(id: id::QuerySetId) -> String {
+    var("query_set", id)
+}
+
+fn label(label: &wgc::Label) -> String {
+    match *label {
+        Some(ref label) => format!("Some({:?})", label),
+        None => "None".to_string(),
+    }
+}
+
+fn option<T>(value: Option<T>, fun: impl FnOnce(T) -> String) -> String {
+    match value {
+        Some(value) => format!("Some({})", fun(value)),
+        None => "None".to_string(),
+    }
+}
+
+/// A unit variant of the `wgpu` enum `path`.
+fn unit(path: &str, value: impl Debug) -> String {
+    format!("wgpu::{path}::{value:?}")
+}
+
+/// A struct of `wgpu` that only has numbers, which `Debug` prints as Rust.
+fn plain(value: impl Debug) -> String {
+    format!("wgpu::{value:?}")
+}
+
+macro_rules! flags {
+    ($path:literal, $value:expr) => {{
+        let value = $value;
+        if value.is_empty() {
+            format!("wgpu::{}::empty()", $path)
+        } else {
+            value
+                .iter_names()
+                .map(|(name, _)| format!("wgpu::{}::{}", $path, name))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        }
+    }};
+}
+
+fn float32(value: f32) -> String {
+    if value.is_finite() {
+        format!("{value:?}")
+    } else if value.is_nan() {
+        "f32::NAN".to_string()
+    } else if value > 0.0 {
+        "f32::INFINITY".to_string()
+    } else {
+        "f32::NEG_INFINITY".to_string()
+    }
+}
+
+fn float64(value: f64) -> String {
+    if value.is_finite() {
+        format!("{value:?}")
+    } else if value.is_nan() {
+        "f64::NAN".to_string()
+    } else if value > 0.0 {
+        "f64::INFINITY".to_string()
+    } else {
+        "f64::NEG_INFINITY".to_string()
+    }
+}
+
+fn color(color: wgt::Color) -> String {
+    format!(
+        "wgpu::Color {{ r: {}, g: {}, b: {}, a: {} }}",
+        float64(color.r),
+        float64(color.g),
+        float64(color.b),
+        float64(color.a)
+    )
+}
+
+fn texture_format(format: wgt::TextureFormat) -> String {
+    match format {
+        wgt::TextureFormat::Astc { block, channel } => format!(
+            "wgpu::TextureFormat::Astc {{ block: {}, channel: {} }}",
+            unit("AstcBlock", block),
+            unit("AstcChannel", channel)
+        ),
+        _ => unit("TextureFormat", format),
+    }
+}
+
+fn texture_formats(formats: &[wgt::TextureFormat]) -> String {
+    let formats = formats
+        .iter()
+        .map(|&f| texture_format(f))
+        .collect::<Vec<_>>();
+    format!("&[{}]", formats.join(", "))
+}
+
+fn size64(size: Option<wgt::BufferSize>) -> String {
+    option(size, |size| {
+        format!("wgpu::BufferSize::new({}).unwrap()", size.get())
+    })
+}
+
+fn slice(id: id::BufferId, offset: wgt::BufferAddress, size: Option<wgt::BufferSize>) -> String {
+    match size {
+        Some(size) => format!("{}.slice({}..{})", buffer(id), offset, offset + size.get()),
+        None => format!("{}.slice({}..)", buffer(id), offset),
+    }
+}
+
+fn bytes(data: &[u8]) -> String {
+    let data = data.iter().map(|b| b.to_string()).collect::<Vec<_>>();
+    format!("&[{}]", data.join(", "))
+}
+
+fn subresource_range(range: &wgt::ImageSubresourceRange) -> String {
+    format!(
+        "wgpu::ImageSubresourceRange {{ aspect: {}, base_mip_level: {}, mip_level_count: {:?}, base_array_layer: {}, array_layer_count: {:?} }}",
+        unit("TextureAspect", range.aspect),
+        range.base_mip_level,
+        range.mip_level_count,
+        range.base_array_layer,
+        range.array_layer_count,
+    )
+}
+
+fn image_copy_texture(copy: &command::ImageCopyTexture) -> String {
+    format!(
+        "wgpu::ImageCopyTexture {{ texture: &{}, mip_level: {}, origin: {}, aspect: {} }}",
+        texture(copy.texture),
+        copy.mip_level,
+        plain(copy.origin),
+        unit("TextureAspect", copy.aspect),
+    )
+}
+
+fn image_copy_buffer(copy: &command::ImageCopyBuffer) -> String {
+    format!(
+        "wgpu::ImageCopyBuffer {{ buffer: &{}, layout: {} }}",
+        buffer(copy.buffer),
+        plain(copy.layout),
+    )
+}
+
+fn binding_type(ty: &wgt::BindingType) -> String {
+    match *ty {
+        wgt::BindingType::Buffer {
+            ty,
+            has_dynamic_offset,
+            min_binding_size,
+        } => {
+            let ty = match ty {
+                wgt::BufferBindingType::Uniform => "wgpu::BufferBindingType::Uniform".to_string(),
+                wgt::BufferBindingType::Storage { read_only } => {
+                    format!("wgpu::BufferBindingType::Storage {{ read_only: {read_only} }}")
+                }
+            };
+            format!(
+                "wgpu::BindingType::Buffer {{ ty: {}, has_dynamic_offset: {}, min_binding_size: {} }}",
+                ty,
+                has_dynamic_offset,
+                size64(min_binding_size)
+            )
+        }
+        wgt::BindingType::Sampler(ty) => format!(
+            "wgpu::BindingType::Sampler({})",
+            unit("SamplerBindingType", ty)
+        ),
+        wgt::BindingType::Texture {
+            sample_type,
+            view_dimension,
+            multisampled,
+        } => {
+            let sample_type = match sample_type {
+                wgt::TextureSampleType::Float { filterable } => {
+                    format!("wgpu::TextureSampleType::Float {{ filterable: {filterable} }}")
+                }
+                other => unit("TextureSampleType", other),
+            };
+            format!(
+                "wgpu::BindingType::Texture {{ sample_type: {}, view_dimension: {}, multisampled: {} }}",
+                sample_type,
+                unit("TextureViewDimension", view_dimension),
+                multisampled
+            )
+        }
+        wgt::BindingType::StorageTexture {
+            access,
+            format,
+            view_dimension,
+        } => format!(
+            "wgpu::BindingType::StorageTexture {{ access: {}, format: {}, view_dimension: {} }}",
+            unit("StorageTextureAccess", access),
+            texture_format(format),
+            unit("TextureViewDimension", view_dimension)
+        ),
+        wgt::BindingType::ExternalTexture => "wgpu::BindingType::ExternalTexture".to_string(),
+    }
+}
+
+fn stencil_face(face: &wgt::StencilFaceState) -> String {
+    format!(
+        "wgpu::StencilFaceState {{ compare: {}, fail_op: {}, depth_fail_op: {}, pass_op: {} }}",
+        unit("CompareFunction", face.compare),
+        unit("StencilOperation", face.fail_op),
+        unit("StencilOperation", face.depth_fail_op),
+        unit("StencilOperation", face.pass_op),
+    )
+}
+
+fn depth_stencil(state: &wgt::DepthStencilState) -> String {
+    format!(
+        "wgpu::DepthStencilState {{ format: {}, depth_write_enabled: {}, depth_compare: {}, stencil: wgpu::StencilState {{ front: {}, back: {}, read_mask: {}, write_mask: {} }}, bias: wgpu::DepthBiasState {{ constant: {}, slope_scale: {}, clamp: {} }} }}",
+        texture_format(state.format),
+        state.depth_write_enabled,
+        unit("CompareFunction", state.depth_compare),
+        stencil_face(&state.stencil.front),
+        stencil_face(&state.stencil.back),
+        state.stencil.read_mask,
+        state.stencil.write_mask,
+        state.bias.constant,
+        float32(state.bias.slope_scale),
+        float32(state.bias.clamp),
+    )
+}
+
+fn blend_component(component: &wgt::BlendComponent) -> String {
+    format!(
+        "wgpu::BlendComponent {{ src_factor: {}, dst_factor: {}, operation: {} }}",
+        unit("BlendFactor", component.src_factor),
+        unit("BlendFactor", component.dst_factor),
+        unit("BlendOperation", component.operation),
+    )
+}
+
+fn color_target(target: &wgt::ColorTargetState) -> String {
+    format!(
+        "wgpu::ColorTargetState {{ format: {}, blend: {}, write_mask: {} }}",
+        texture_format(target.format),
+        option(target.blend, |blend| format!(
+            "wgpu::BlendState {{ color: {}, alpha: {} }}",
+            blend_component(&blend.color),
+            blend_component(&blend.alpha)
+        )),
+        flags!("ColorWrites", target.write_mask),
+    )
+}
+
+fn primitive(state: &wgt::PrimitiveState) -> String {
+    format!(
+        "wgpu::PrimitiveState {{ topology: {}, strip_index_format: {}, front_face: {}, cull_mode: {}, unclipped_depth: {}, polygon_mode: {}, conservative: {} }}",
+        unit("PrimitiveTopology", state.topology),
+        option(state.strip_index_format, |f| unit("IndexFormat", f)),
+        unit("FrontFace", state.front_face),
+        option(state.cull_mode, |f| unit("Face", f)),
+        state.unclipped_depth,
+        unit("PolygonMode", state.polygon_mode),
+        state.conservative,
+    )
+}
+
+impl Generator {
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.indent {
+            self.code.push_str("    ");
+        }
+        self.code.push_str(line);
+        self.code.push('\n');
+    }
+
+    fn skip(&mut self, what: impl Debug) {
+        self.skipped += 1;
+        let what = format!("{what:?}").replace('\n', " ");
+        self.line(&format!("// Unsupported: {what}"));
+    }
+
+    fn declare(&mut self, name: &str, value: &str) {
+        self.variables.insert(name.to_string());
+        self.line(&format!("let {name} = {value};"));
+    }
+
+    fn drop_var(&mut self, name: String) {
+        if self.variables.remove(&name) {
+            self.line(&format!("drop({name});"));
+        }
+    }
+
+    /// Embed the data file `name`, as bytes or as text.
+    fn data(&mut self, name: &str, text: bool) -> String {
+        self.data_files.insert(name.to_string());
+        let kind = if text { "str" } else { "bytes" };
+        format!("include_{kind}!(\"../data/{name}\")")
+    }
+
+    fn manifest(&self) -> String {
+        let mut manifest = String::from(
+            "[package]\n\
+             name = \"wgpu-trace-reproducer\"\n\
+             version = \"0.1.0\"\n\
+             edition = \"2021\"\n\
+             publish = false\n\n\
+             [dependencies]\n\
+             env_logger = \"0.10\"\n\
+             pollster = \"0.3\"\n",
+        );
+        let features = self
+            .wgpu_features
+            .iter()
+            .map(|f| format!("{f:?}"))
+            .collect::<Vec<_>>();
+        let _ = writeln!(
+            manifest,
+            "wgpu = {{ path = {}, features = [{}] }}",
+            workspace_path("wgpu"),
+            features.join(", ")
+        );
+        if self.naga_ron {
+            let _ = writeln!(
+                manifest,
+                "naga = {{ path = {}, features = [\"deserialize\"] }}\nron = \"0.8\"",
+                workspace_path("naga"),
+            );
+        }
+        manifest
+    }
+
+    fn main(&self) -> String {
+        let mut main = String::from(
+            "//! Generated from a wgpu trace by `play codegen`.\n\
+             #![allow(unused, clippy::all)]\n\n\
+             fn main() {\n    \
+                 env_logger::init();\n    \
+                 pollster::block_on(run());\n\
+             }\n\n",
+        );
+        main.push_str(&self.code);
+        main
+    }
+
+    fn action(&mut self, action: &trace::Action) {
+        use trace::Action as A;
+        match *action {
+            A::Init { ref desc, backend } => {
+                let backends = match backend {
+                    wgt::Backend::Vulkan => "wgpu::Backends::VULKAN",
+                    wgt::Backend::Metal => "wgpu::Backends::METAL",
+                    wgt::Backend::Dx12 => "wgpu::Backends::DX12",
+                    wgt::Backend::Dx11 => "wgpu::Backends::DX11",
+                    wgt::Backend::Gl => "wgpu::Backends::GL",
+                    _ => "wgpu::Backends::all()",
+                };
+                self.line("let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {");
+                self.line(&format!(
+                    "    backends: wgpu::util::backend_bits_from_env().unwrap_or({backends}),"
+                ));
+                self.line("    ..Default::default()");
+                self.line("});");
+                self.line("let adapter = instance");
+                self.line("    .request_adapter(&wgpu::RequestAdapterOptions::default())");
+                self.line("    .await");
+                self.line("    .expect(\"Unable to find an adapter\");");
+                self.line("let (device, queue) = adapter");
+                self.line("    .request_device(");
+                self.line("        &wgpu::DeviceDescriptor {");
+                self.line(&format!("            label: {},", label(&desc.label)));
+                self.line(&format!(
+                    "            features: {},",
+                    flags!("Features", desc.features)
+                ));
+                self.line(&format!("            limits: {},", plain(&desc.limits)));
+                self.line("        },");
+                self.line("        None,");
+                self.line("    )");
+                self.line("    .await");
+                self.line("    .expect(\"Unable to create the device\");");
+            }
+            A::ConfigureSurface(surface_id, ref config) => {
+                self.line("// The surface is replaced by offscreen textures.");
+                self.surface_configs.insert(surface_id, config.clone());
+            }
+            A::CreateBuffer(id, ref desc) => {
+                if desc.mapped_at_creation {
+                    self.mapped_at_creation.insert(id);
+                }
+                self.declare(
+                    &buffer(id),
+                    &format!(
+                        "device.create_buffer(&wgpu::BufferDescriptor {{ label: {}, size: {}, usage: {}, mapped_at_creation: {} }})",
+                        label(&desc.label),
+                        desc.size,
+                        flags!("BufferUsages", desc.usage),
+                        desc.mapped_at_creation,
+                    ),
+                );
+            }
+            A::FreeBuffer(id) => {
+                if self.variables.contains(&buffer(id)) {
+                    self.line(&format!("{}.destroy();", buffer(id)));
+                }
+            }
+            A::DestroyBuffer(id) => {
+                self.mapped_at_creation.remove(&id);
+                self.drop_var(buffer(id));
+            }
+            A::CreateTexture(id, ref desc) => {
+                self.declare(
+                    &texture(id),
+                    &format!(
+                        "device.create_texture(&wgpu::TextureDescriptor {{ label: {}, size: {}, mip_level_count: {}, sample_count: {}, dimension: {}, format: {}, usage: {}, view_formats: {} }})",
+                        label(&desc.label),
+                        plain(desc.size),
+                        desc.mip_level_count,
+                        desc.sample_count,
+                        unit("TextureDimension", desc.dimension),
+                        texture_format(desc.format),
+                        flags!("TextureUsages", desc.usage),
+                        texture_formats(&desc.view_formats),
+                    ),
+                );
+            }
+            A::FreeTexture(id) => {
+                if self.variables.contains(&texture(id)) {
+                    self.line(&format!("{}.destroy();", texture(id)));
+                }
+            }
+            A::DestroyTexture(id) => self.drop_var(texture(id)),
+            A::CreateTextureView {
+                id,
+                parent_id,
+                ref desc,
+            } => {
+                self.declare(
+                    &view(id),
+                    &format!(
+                        "{}.create_view(&wgpu::TextureViewDescriptor {{ label: {}, format: {}, dimension: {}, aspect: {}, base_mip_level: {}, mip_level_count: {:?}, base_array_layer: {}, array_layer_count: {:?} }})",
+                        texture(parent_id),
+                        label(&desc.label),
+                        option(desc.format, texture_format),
+                        option(desc.dimension, |d| unit("TextureViewDimension", d)),
+                        unit("TextureAspect", desc.range.aspect),
+                        desc.range.base_mip_level,
+                        desc.range.mip_level_count,
+                        desc.range.base_array_layer,
+                        desc.range.array_layer_count,
+                    ),
+                );
+            }
+            A::DestroyTextureView(id) => self.drop_var(view(id)),
+            A::CreateSampler(id, ref desc) => {
+                self.declare(
+                    &var("sampler", id),
+                    &format!(
+                        "device.create_sampler(&wgpu::SamplerDescriptor {{ label: {}, address_mode_u: {}, address_mode_v: {}, address_mode_w: {}, mag_filter: {}, min_filter: {}, mipmap_filter: {}, lod_min_clamp: {}, lod_max_clamp: {}, compare: {}, anisotropy_clamp: {}, border_color: {} }})",
+                        label(&desc.label),
+                        unit("AddressMode", desc.address_modes[0]),
+                        unit("AddressMode", desc.address_modes[1]),
+                        unit("AddressMode", desc.address_modes[2]),
+                        unit("FilterMode", desc.mag_filter),
+                        unit("FilterMode", desc.min_filter),
+                        unit("FilterMode", desc.mipmap_filter),
+                        float32(desc.lod_min_clamp),
+                        float32(desc.lod_max_clamp),
+                        option(desc.compare, |c| unit("CompareFunction", c)),
+                        desc.anisotropy_clamp,
+                        option(desc.border_color, |c| unit("SamplerBorderColor", c)),
+                    ),
+                );
+            }
+            A::DestroySampler(id) => self.drop_var(var("sampler", id)),
+            A::CreateExternalTexture {
+                id,
+                ref desc,
+                ref planes,
+            } => {
+                self.declare(
+                    &var("external_texture", id),
+                    &format!(
+                        "device.create_external_texture(&wgpu::ExternalTextureDescriptor {{ label: {}, format: {}, yuv_conversion_matrix: [{}] }}, &[{}])",
+                        label(&desc.label),
+                        unit("ExternalTextureFormat", desc.format),
+                        desc.yuv_conversion_matrix
+                            .iter()
+                            .map(|&value| float32(value))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        planes
+                            .iter()
+                            .map(|&id| format!("&{}", view(id)))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                );
+            }
+            A::DestroyExternalTexture(id) => self.drop_var(var("external_texture", id)),
+            A::GetSurfaceTexture { id, parent_id } => {
+                let config = match self.surface_configs.get(&parent_id) {
+                    Some(config) => config.clone(),
+                    None => return self.skip(action),
+                };
+                self.surface_textures.insert(parent_id, id);
+                self.declare(
+                    &texture(id),
+                    &format!(
+                        "device.create_texture(&wgpu::TextureDescriptor {{ label: Some(\"surface texture\"), size: wgpu::Extent3d {{ width: {}, height: {}, depth_or_array_layers: 1 }}, mip_level_count: 1, sample_count: 1, dimension: wgpu::TextureDimension::D2, format: {}, usage: {}, view_formats: {} }})",
+                        config.width,
+                        config.height,
+                        texture_format(config.format),
+                        flags!("TextureUsages", config.usage),
+                        texture_formats(&config.view_formats),
+                    ),
+                );
+            }
+            A::Present(surface_id) | A::DiscardSurfaceTexture(surface_id) => {
+                if let A::Present(_) = *action {
+                    self.line("// Present");
+                }
+                if let Some(id) = self.surface_textures.remove(&surface_id) {
+                    self.drop_var(texture(id));
+                }
+            }
+            A::CreateBindGroupLayout(id, ref desc) => {
+                let entries = desc
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "wgpu::BindGroupLayoutEntry {{ binding: {}, visibility: {}, ty: {}, count: {} }}",
+                            entry.binding,
+                            flags!("ShaderStages", entry.visibility),
+                            binding_type(&entry.ty),
+                            option(entry.count, |c| format!(
+                                "std::num::NonZeroU32::new({}).unwrap()",
+                                c.get()
+                            )),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                self.declare(
+                    &var("bind_group_layout", id),
+                    &format!(
+                        "device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {{ label: {}, entries: &[{}] }})",
+                        label(&desc.label),
+                        entries.join(", "),
+                    ),
+                );
+            }
+            A::DestroyBindGroupLayout(id) => self.drop_var(var("bind_group_layout", id)),
+            A::CreatePipelineLayout(id, ref desc) => {
+                let layouts = desc
+                    .bind_group_layouts
+                    .iter()
+                    .map(|&id| format!("&{}", var("bind_group_layout", id)))
+                    .collect::<Vec<_>>();
+                let ranges = desc
+                    .push_constant_ranges
+                    .iter()
+                    .map(|range| {
+                        format!(
+                            "wgpu::PushConstantRange {{ stages: {}, range: {:?} }}",
+                            flags!("ShaderStages", range.stages),
+                            range.range,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                self.declare(
+                    &var("pipeline_layout", id),
+                    &format!(
+                        "device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {{ label: {}, bind_group_layouts: &[{}], push_constant_ranges: &[{}] }})",
+                        label(&desc.label),
+                        layouts.join(", "),
+                        ranges.join(", "),
+                    ),
+                );
+            }
+            A::DestroyPipelineLayout(id) => self.drop_var(var("pipeline_layout", id)),
+            A::CreateBindGroup(id, ref desc) => {
+                let binding = |b: &wgc::binding_model::BufferBinding| {
+                    format!(
+                        "wgpu::BufferBinding {{ buffer: &{}, offset: {}, size: {} }}",
+                        buffer(b.buffer_id),
+                        b.offset,
+                        size64(b.size),
+                    )
+                };
+                let list = |items: Vec<String>| format!("&[{}]", items.join(", "));
+                let entries = desc
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        use wgc::binding_model::BindingResource as Br;
+                        let resource = match entry.resource {
+                            Br::Buffer(ref b) => {
+                                format!("wgpu::BindingResource::Buffer({})", binding(b))
+                            }
+                            Br::BufferArray(ref bs) => format!(
+                                "wgpu::BindingResource::BufferArray({})",
+                                list(bs.iter().map(binding).collect())
+                            ),
+                            Br::Sampler(id) => {
+                                format!("wgpu::BindingResource::Sampler(&{})", var("sampler", id))
+                            }
+                            Br::SamplerArray(ref ids) => format!(
+                                "wgpu::BindingResource::SamplerArray({})",
+                                list(
+                                    ids.iter()
+                                        .map(|&id| format!("&{}", var("sampler", id)))
+                                        .collect()
+                                )
+                            ),
+                            Br::TextureView(id) => {
+                                format!("wgpu::BindingResource::TextureView(&{})", view(id))
+                            }
+                            Br::TextureViewArray(ref ids) => format!(
+                                "wgpu::BindingResource::TextureViewArray({})",
+                                list(ids.iter().map(|&id| format!("&{}", view(id))).collect())
+                            ),
+                            Br::ExternalTexture(id) => format!(
+                                "wgpu::BindingResource::ExternalTexture(&{})",
+                                var("external_texture", id)
+                            ),
+                        };
+                        format!(
+                            "wgpu::BindGroupEntry {{ binding: {}, resource: {} }}",
+                            entry.binding, resource
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                self.declare(
+                    &var("bind_group", id),
+                    &format!(
+                        "device.create_bind_group(&wgpu::BindGroupDescriptor {{ label: {}, layout: &{}, entries: &[{}] }})",
+                        label(&desc.label),
+                        var("bind_group_layout", desc.layout),
+                        entries.join(", "),
+                    ),
+                );
+            }
+            A::DestroyBindGroup(id) => self.drop_var(var("bind_group", id)),
+            A::CreateShaderModule {
+                id,
+                ref desc,
+                ref data,
+            } => {
+                let source = if data.ends_with(".wgsl") {
+                    format!("wgpu::ShaderSource::Wgsl({}.into())", self.data(data, true))
+                } else if data.ends_with(".spv") {
+                    self.wgpu_features.insert("spirv");
+                    format!("wgpu::util::make_spirv({})", self.data(data, false))
+                } else if data.ends_with(".ron") {
+                    self.wgpu_features.insert("naga");
+                    self.naga_ron = true;
+                    format!(
+                        "wgpu::ShaderSource::Naga(std::borrow::Cow::Owned(ron::de::from_str({}).unwrap()))",
+                        self.data(data, true)
+                    )
+                } else {
+                    return self.skip(action);
+                };
+                let call = if desc.shader_bound_checks.runtime_checks() {
+                    "device.create_shader_module"
+                } else {
+                    "unsafe { device.create_shader_module_unchecked"
+                };
+                let end = if call.starts_with("unsafe") { " }" } else { "" };
+                self.declare(
+                    &var("shader", id),
+                    &format!(
+                        "{}(wgpu::ShaderModuleDescriptor {{ label: {}, source: {} }}){}",
+                        call,
+                        label(&desc.label),
+                        source,
+                        end,
+                    ),
+                );
+            }
+            A::DestroyShaderModule(id) => self.drop_var(var("shader", id)),
+            A::CreateComputePipeline {
+                id,
+                ref desc,
+                ref implicit_context,
+            } => {
+                let name = var("compute_pipeline", id);
+                self.declare(
+                    &name,
+                    &format!(
+                        "device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {{ label: {}, layout: {}, module: &{}, entry_point: {:?} }})",
+                        label(&desc.label),
+                        option(desc.layout, |id| format!("&{}", var("pipeline_layout", id))),
+                        var("shader", desc.stage.module),
+                        desc.stage.entry_point,
+                    ),
+                );
+                self.implicit_layouts(&name, implicit_context);
+            }
+            A::DestroyComputePipeline(id) => self.drop_var(var("compute_pipeline", id)),
+            A::CreateRenderPipeline {
+                id,
+                ref desc,
+                ref implicit_context,
+            } => {
+                let buffers = desc
+                    .vertex
+                    .buffers
+                    .iter()
+                    .map(|layout| {
+                        let attributes = layout
+                            .attributes
+                            .iter()
+                            .map(|a| {
+                                format!(
+                                    "wgpu::VertexAttribute {{ format: {}, offset: {}, shader_location: {} }}",
+                                    unit("VertexFormat", a.format),
+                                    a.offset,
+                                    a.shader_location
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        format!(
+                            "wgpu::VertexBufferLayout {{ array_stride: {}, step_mode: {}, attributes: &[{}] }}",
+                            layout.array_stride,
+                            unit("VertexStepMode", layout.step_mode),
+                            attributes.join(", ")
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let fragment = option(desc.fragment.as_ref(), |fragment| {
+                    let targets = fragment
+                        .targets
+                        .iter()
+                        .map(|target| option(target.as_ref(), color_target))
+                        .collect::<Vec<_>>();
+                    format!(
+                        "wgpu::FragmentState {{ module: &{}, entry_point: {:?}, targets: &[{}] }}",
+                        var("shader", fragment.stage.module),
+                        fragment.stage.entry_point,
+                        targets.join(", ")
+                    )
+                });
+                let name = var("render_pipeline", id);
+                self.declare(
+                    &name,
+                    &format!(
+                        "device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {{ label: {}, layout: {}, vertex: wgpu::VertexState {{ module: &{}, entry_point: {:?}, buffers: &[{}] }}, primitive: {}, depth_stencil: {}, multisample: {}, fragment: {}, multiview: {} }})",
+                        label(&desc.label),
+                        option(desc.layout, |id| format!("&{}", var("pipeline_layout", id))),
+                        var("shader", desc.vertex.stage.module),
+                        desc.vertex.stage.entry_point,
+                        buffers.join(", "),
+                        primitive(&desc.primitive),
+                        option(desc.depth_stencil.as_ref(), depth_stencil),
+                        plain(desc.multisample),
+                        fragment,
+                        option(desc.multiview, |v| format!(
+                            "std::num::NonZeroU32::new({}).unwrap()",
+                            v.get()
+                        )),
+                    ),
+                );
+                self.implicit_layouts(&name, implicit_context);
+            }
+            A::DestroyRenderPipeline(id) => self.drop_var(var("render_pipeline", id)),
+            A::CreateRenderBundle {
+                id,
+                ref desc,
+                ref base,
+            } => {
+                let color_formats = desc
+                    .color_formats
+                    .iter()
+                    .map(|&f| option(f, texture_format))
+                    .collect::<Vec<_>>();
+                let depth_stencil = option(desc.depth_stencil, |ds| {
+                    format!(
+                        "wgpu::RenderBundleDepthStencil {{ format: {}, depth_read_only: {}, stencil_read_only: {} }}",
+                        texture_format(ds.format),
+                        ds.depth_read_only,
+                        ds.stencil_read_only
+                    )
+                });
+                let name = var("render_bundle", id);
+                self.variables.insert(name.clone());
+                self.line(&format!("let {name} = {{"));
+                self.indent += 1;
+                self.line(&format!(
+                    "let mut bundle = device.create_render_bundle_encoder(&wgpu::RenderBundleEncoderDescriptor {{ label: {}, color_formats: &[{}], depth_stencil: {}, sample_count: {}, multiview: {} }});",
+                    label(&desc.label),
+                    color_formats.join(", "),
+                    depth_stencil,
+                    desc.sample_count,
+                    option(desc.multiview, |v| format!(
+                        "std::num::NonZeroU32::new({}).unwrap()",
+                        v.get()
+                    )),
+                ));
+                self.render_commands("bundle", base, true);
+                self.line(&format!(
+                    "bundle.finish(&wgpu::RenderBundleDescriptor {{ label: {} }})",
+                    label(&desc.label)
+                ));
+                self.indent -= 1;
+                self.line("};");
+            }
+            A::DestroyRenderBundle(id) => self.drop_var(var("render_bundle", id)),
+            A::CreateComputeBundle {
+                id,
+                ref desc,
+                ref base,
+            } => {
+                let name = var("compute_bundle", id);
+                self.variables.insert(name.clone());
+                self.line(&format!("let {name} = {{"));
+                self.indent += 1;
+                self.line(&format!(
+                    "let mut bundle = device.create_compute_bundle_encoder(&wgpu::ComputeBundleEncoderDescriptor {{ label: {} }});",
+                    label(&desc.label),
+                ));
+                self.compute_commands("bundle", base, true);
+                self.line(&format!(
+                    "bundle.finish(&wgpu::ComputeBundleDescriptor {{ label: {} }})",
+                    label(&desc.label)
+                ));
+                self.indent -= 1;
+                self.line("};");
+            }
+            A::DestroyComputeBundle(id) => self.drop_var(var("compute_bundle", id)),
+            A::CreateQuerySet { id, ref desc } => {
+                let ty = match desc.ty {
+                    wgt::QueryType::PipelineStatistics(types) => format!(
+                        "wgpu::QueryType::PipelineStatistics({})",
+                        flags!("PipelineStatisticsTypes", types)
+                    ),
+                    other => unit("QueryType", other),
+                };
+                self.declare(
+                    &query_set(id),
+                    &format!(
+                        "device.create_query_set(&wgpu::QuerySetDescriptor {{ label: {}, ty: {}, count: {} }})",
+                        label(&desc.label),
+                        ty,
+                        desc.count,
+                    ),
+                );
+            }
+            A::DestroyQuerySet(id) => self.drop_var(query_set(id)),
+            A::WriteBuffer {
+                id,
+                ref data,
+                ref range,
+                queued: true,
+            } => {
+                let bytes = self.data(data, false);
+                self.line(&format!(
+                    "queue.write_buffer(&{}, {}, {});",
+                    buffer(id),
+                    range.start,
+                    bytes
+                ));
+            }
+            A::WriteBuffer {
+                id,
+                ref data,
+                ref range,
+                queued: false,
+            }
+            | A::UnmapBuffer {
+                id,
+                ref data,
+                ref range,
+            } => {
+                let bytes = self.data(data, false);
+                let size = range.end - range.start;
+                if self.mapped_at_creation.remove(&id) {
+                    self.line(&format!(
+                        "{}.slice({:?}).get_mapped_range_mut().copy_from_slice(&{}[..{}]);",
+                        buffer(id),
+                        range,
+                        bytes,
+                        size
+                    ));
+                } else {
+                    self.line("{");
+                    self.line(&format!(
+                        "    let slice = {}.slice({:?});",
+                        buffer(id),
+                        range
+                    ));
+                    self.line("    slice.map_async(wgpu::MapMode::Write, |_| ());");
+                    self.line("    device.poll(wgpu::Maintain::Wait);");
+                    self.line(&format!(
+                        "    slice.get_mapped_range_mut().copy_from_slice(&{}[..{}]);",
+                        bytes, size
+                    ));
+                    self.line("}");
+                }
+                self.line(&format!("{}.unmap();", buffer(id)));
+            }
+            A::WriteTexture {
+                ref to,
+                ref data,
+                layout,
+                size,
+            } => {
+                let bytes = self.data(data, false);
+                self.line(&format!(
+                    "queue.write_texture({}, {}, {}, {});",
+                    image_copy_texture(to),
+                    bytes,
+                    plain(layout),
+                    plain(size),
+                ));
+            }
+            A::Submit(_, ref commands) if commands.is_empty() => {
+                self.line("queue.submit([]);");
+            }
+            A::Submit(_, ref commands) => {
+                self.line("{");
+                self.indent += 1;
+                self.line("let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });");
+                for command in commands {
+                    self.command(command);
+                }
+                self.line("queue.submit(Some(encoder.finish()));");
+                self.indent -= 1;
+                self.line("}");
+            }
+        }
+    }
+
+    /// Bind group layouts that are created along with a pipeline.
+    fn implicit_layouts(
+        &mut self,
+        pipeline: &str,
+        implicit_context: &Option<wgc::device::ImplicitPipelineContext>,
+    ) {
+        if let Some(ref context) = *implicit_context {
+            for (index, &id) in context.group_ids.iter().enumerate() {
+                self.declare(
+                    &var("bind_group_layout", id),
+                    &format!("{pipeline}.get_bind_group_layout({index})"),
+                );
+            }
+        }
+    }
+
+    fn command(&mut self, command: &trace::Command) {
+        use trace::Command as C;
+        match *command {
+            C::CopyBufferToBuffer {
+                src,
+                src_offset,
+                dst,
+                dst_offset,
+                size,
+            } => self.line(&format!(
+                "encoder.copy_buffer_to_buffer(&{}, {}, &{}, {}, {});",
+                buffer(src),
+                src_offset,
+                buffer(dst),
+                dst_offset,
+                size
+            )),
+            C::CopyBufferToTexture {
+                ref src,
+                ref dst,
+                size,
+            } => self.line(&format!(
+                "encoder.copy_buffer_to_texture({}, {}, {});",
+                image_copy_buffer(src),
+                image_copy_texture(dst),
+                plain(size)
+            )),
+            C::CopyTextureToBuffer {
+                ref src,
+                ref dst,
+                size,
+            } => self.line(&format!(
+                "encoder.copy_texture_to_buffer({}, {}, {});",
+                image_copy_texture(src),
+                image_copy_buffer(dst),
+                plain(size)
+            )),
+            C::CopyTextureToTexture {
+                ref src,
+                ref dst,
+                size,
+            } => self.line(&format!(
+                "encoder.copy_texture_to_texture({}, {}, {});",
+                image_copy_texture(src),
+                image_copy_texture(dst),
+                plain(size)
+            )),
+            C::ClearBuffer { dst, offset, size } => self.line(&format!(
+                "encoder.clear_buffer(&{}, {}, {});",
+                buffer(dst),
+                offset,
+                size64(size)
+            )),
+            C::ClearTexture {
+                dst,
+                subresource_range: ref range,
+            } => self.line(&format!(
+                "encoder.clear_texture(&{}, &{});",
+                texture(dst),
+                subresource_range(range)
+            )),
+            C::WriteTimestamp {
+                query_set_id,
+                query_index,
+            } => self.line(&format!(
+                "encoder.write_timestamp(&{}, {});",
+                query_set(query_set_id),
+                query_index
+            )),
+            C::ResolveQuerySet {
+                query_set_id,
+                start_query,
+                query_count,
+                destination,
+                destination_offset,
+            } => self.line(&format!(
+                "encoder.resolve_query_set(&{}, {}..{}, &{}, {});",
+                query_set(query_set_id),
+                start_query,
+                start_query + query_count,
+                buffer(destination),
+                destination_offset
+            )),
+            C::PushDebugGroup(ref label) => {
+                self.line(&format!("encoder.push_debug_group({label:?});"))
+            }
+            C::PopDebugGroup => self.line("encoder.pop_debug_group();"),
+            C::InsertDebugMarker(ref label) => {
+                self.line(&format!("encoder.insert_debug_marker({label:?});"))
+            }
+            C::RunComputePass {
+                ref base,
+                ref timestamp_writes,
+            } => {
+                self.line("{");
+                self.indent += 1;
+                self.line(&format!(
+                    "let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {{ label: {:?}, timestamp_writes: {} }});",
+                    base.label.as_deref(),
+                    option(timestamp_writes.as_ref(), |tw| format!(
+                        "wgpu::ComputePassTimestampWrites {{ query_set: &{}, beginning_of_pass_write_index: {:?}, end_of_pass_write_index: {:?} }}",
+                        query_set(tw.query_set),
+                        tw.beginning_of_pass_write_index,
+                        tw.end_of_pass_write_index
+                    )),
+                ));
+                self.compute_commands("pass", base, false);
+                self.indent -= 1;
+                self.line("}");
+            }
+            C::RunRenderPass {
+                ref base,
+                ref target_colors,
+                ref target_depth_stencil,
+                ref timestamp_writes,
+                occlusion_query_set_id,
+            } => {
+                let store = |store_op| match store_op {
+                    command::StoreOp::Store => "wgpu::StoreOp::Store",
+                    command::StoreOp::Discard => "wgpu::StoreOp::Discard",
+                };
+                let colors = target_colors
+                    .iter()
+                    .map(|target| {
+                        option(target.as_ref(), |at| {
+                            let load = match at.channel.load_op {
+                                command::LoadOp::Clear => {
+                                    format!("wgpu::LoadOp::Clear({})", color(at.channel.clear_value))
+                                }
+                                command::LoadOp::Load => "wgpu::LoadOp::Load".to_string(),
+                            };
+                            format!(
+                                "wgpu::RenderPassColorAttachment {{ view: &{}, resolve_target: {}, ops: wgpu::Operations {{ load: {}, store: {} }} }}",
+                                view(at.view),
+                                option(at.resolve_target, |id| format!("&{}", view(id))),
+                                load,
+                                store(at.channel.store_op)
+                            )
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let depth_stencil = option(target_depth_stencil.as_ref(), |at| {
+                    let depth_load = match at.depth.load_op {
+                        command::LoadOp::Clear => {
+                            format!("wgpu::LoadOp::Clear({})", float32(at.depth.clear_value))
+                        }
+                        command::LoadOp::Load => "wgpu::LoadOp::Load".to_string(),
+                    };
+                    let stencil_load = match at.stencil.load_op {
+                        command::LoadOp::Clear => {
+                            format!("wgpu::LoadOp::Clear({})", at.stencil.clear_value)
+                        }
+                        command::LoadOp::Load => "wgpu::LoadOp::Load".to_string(),
+                    };
+                    format!(
+                        "wgpu::RenderPassDepthStencilAttachment {{ view: &{}, depth_ops: {}, stencil_ops: {} }}",
+                        view(at.view),
+                        if at.depth.read_only {
+                            "None".to_string()
+                        } else {
+                            format!(
+                                "Some(wgpu::Operations {{ load: {}, store: {} }})",
+                                depth_load,
+                                store(at.depth.store_op)
+                            )
+                        },
+                        if at.stencil.read_only {
+                            "None".to_string()
+                        } else {
+                            format!(
+                                "Some(wgpu::Operations {{ load: {}, store: {} }})",
+                                stencil_load,
+                                store(at.stencil.store_op)
+                            )
+                        },
+                    )
+                });
+                self.line("{");
+                self.indent += 1;
+                self.line(&format!(
+                    "let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {{ label: {:?}, color_attachments: &[{}], depth_stencil_attachment: {}, timestamp_writes: {}, occlusion_query_set: {} }});",
+                    base.label.as_deref(),
+                    colors.join(", "),
+                    depth_stencil,
+                    option(timestamp_writes.as_ref(), |tw| format!(
+                        "wgpu::RenderPassTimestampWrites {{ query_set: &{}, beginning_of_pass_write_index: {:?}, end_of_pass_write_index: {:?} }}",
+                        query_set(tw.query_set),
+                        tw.beginning_of_pass_write_index,
+                        tw.end_of_pass_write_index
+                    )),
+                    option(occlusion_query_set_id, |id| format!("&{}", query_set(id))),
+                ));
+                self.render_commands("pass", base, false);
+                self.indent -= 1;
+                self.line("}");
+            }
+        }
+    }
+
+    fn compute_commands(&mut self, target: &str, base: &BasePass<ComputeCommand>, bundle: bool) {
+        let mut offsets = base.dynamic_offsets.iter();
+        let mut strings = base.string_data.as_slice();
+        let mut commands = base.commands.iter().peekable();
+        while let Some(command) = commands.next() {
+            use ComputeCommand as C;
+            match *command {
+                C::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                } => {
+                    let offsets = offsets
+                        .by_ref()
+                        .take(num_dynamic_offsets as usize)
+                        .collect::<Vec<_>>();
+                    self.line(&format!(
+                        "{target}.set_bind_group({}, &{}, &{:?});",
+                        index,
+                        var("bind_group", bind_group_id),
+                        offsets
+                    ));
+                }
+                C::SetPipeline(id) => self.line(&format!(
+                    "{target}.set_pipeline(&{});",
+                    var("compute_pipeline", id)
+                )),
+                C::SetPushConstant {
+                    offset,
+                    size_bytes,
+                    values_offset,
+                } => {
+                    let start = values_offset as usize;
+                    let end = start + size_bytes as usize / 4;
+                    let data = base.push_constant_data[start..end]
+                        .iter()
+                        .flat_map(|v| v.to_ne_bytes())
+                        .collect::<Vec<_>>();
+                    self.line(&format!(
+                        "{target}.set_push_constants({}, {});",
+                        offset,
+                        bytes(&data)
+                    ));
+                }
+                C::Dispatch([x, y, z]) => {
+                    self.line(&format!("{target}.dispatch_workgroups({x}, {y}, {z});"))
+                }
+                C::DispatchIndirect { buffer_id, offset } => self.line(&format!(
+                    "{target}.dispatch_workgroups_indirect(&{}, {});",
+                    buffer(buffer_id),
+                    offset
+                )),
+                C::PushDebugGroup { len, .. } if !bundle => {
+                    let label = String::from_utf8_lossy(&strings[..len]).into_owned();
+                    strings = &strings[len..];
+                    self.line(&format!("{target}.push_debug_group({label:?});"));
+                }
+                C::PopDebugGroup if !bundle => self.line(&format!("{target}.pop_debug_group();")),
+                C::InsertDebugMarker { len, .. } if !bundle => {
+                    let label = String::from_utf8_lossy(&strings[..len]).into_owned();
+                    strings = &strings[len..];
+                    self.line(&format!("{target}.insert_debug_marker({label:?});"));
+                }
+                C::WriteTimestamp {
+                    query_set_id,
+                    query_index,
+                } if !bundle => self.line(&format!(
+                    "{target}.write_timestamp(&{}, {});",
+                    query_set(query_set_id),
+                    query_index
+                )),
+                C::BeginPipelineStatisticsQuery {
+                    query_set_id,
+                    query_index,
+                } if !bundle => self.line(&format!(
+                    "{target}.begin_pipeline_statistics_query(&{}, {});",
+                    query_set(query_set_id),
+                    query_index
+                )),
+                C::EndPipelineStatisticsQuery if !bundle => {
+                    self.line(&format!("{target}.end_pipeline_statistics_query();"))
+                }
+                C::ExecuteBundle(id) if !bundle => {
+                    let mut bundles = vec![format!("&{}", var("compute_bundle", id))];
+                    while let Some(&&C::ExecuteBundle(id)) = commands.peek() {
+                        bundles.push(format!("&{}", var("compute_bundle", id)));
+                        commands.next();
+                    }
+                    self.line(&format!(
+                        "{target}.execute_bundles([{}]);",
+                        bundles.join(", ")
+                    ));
+                }
+                ref other => self.skip(other),
+            }
+        }
+    }
+
+    fn render_commands(&mut self, target: &str, base: &BasePass<RenderCommand>, bundle: bool) {
+        let mut offsets = base.dynamic_offsets.iter();
+        let mut strings = base.string_data.as_slice();
+        let mut commands = base.commands.iter().peekable();
+        while let Some(command) = commands.next() {
+            use RenderCommand as C;
+            match *command {
+                C::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                } => {
+                    let offsets = offsets
+                        .by_ref()
+                        .take(num_dynamic_offsets as usize)
+                        .collect::<Vec<_>>();
+                    self.line(&format!(
+                        "{target}.set_bind_group({}, &{}, &{:?});",
+                        index,
+                        var("bind_group", bind_group_id),
+                        offsets
+                    ));
+                }
+                C::SetPipeline(id) => self.line(&format!(
+                    "{target}.set_pipeline(&{});",
+                    var("render_pipeline", id)
+                )),
+                C::SetIndexBuffer {
+                    buffer_id,
+                    index_format,
+                    offset,
+                    size,
+                } => self.line(&format!(
+                    "{target}.set_index_buffer({}, {});",
+                    slice(buffer_id, offset, size),
+                    unit("IndexFormat", index_format)
+                )),
+                C::SetVertexBuffer {
+                    slot,
+                    buffer_id,
+                    offset,
+                    size,
+                } => self.line(&format!(
+                    "{target}.set_vertex_buffer({}, {});",
+                    slot,
+                    slice(buffer_id, offset, size)
+                )),
+                C::SetBlendConstant(value) if !bundle => {
+                    self.line(&format!("{target}.set_blend_constant({});", color(value)))
+                }
+                C::SetStencilReference(value) if !bundle => {
+                    self.line(&format!("{target}.set_stencil_reference({value});"))
+                }
+                C::SetViewport {
+                    ref rect,
+                    depth_min,
+                    depth_max,
+                } if !bundle => self.line(&format!(
+                    "{target}.set_viewport({}, {}, {}, {}, {}, {});",
+                    float32(rect.x),
+                    float32(rect.y),
+                    float32(rect.w),
+                    float32(rect.h),
+                    float32(depth_min),
+                    float32(depth_max)
+                )),
+                C::SetScissor(ref rect) if !bundle => self.line(&format!(
+                    "{target}.set_scissor_rect({}, {}, {}, {});",
+                    rect.x, rect.y, rect.w, rect.h
+                )),
+                C::SetPushConstant {
+                    stages,
+                    offset,
+                    size_bytes,
+                    values_offset,
+                } => {
+                    let data = match values_offset {
+                        Some(start) => {
+                            let start = start as usize;
+                            let end = start + size_bytes as usize / 4;
+                            base.push_constant_data[start..end]
+                                .iter()
+                                .flat_map(|v| v.to_ne_bytes())
+                                .collect::<Vec<_>>()
+                        }
+                        None => vec![0; size_bytes as usize],
+                    };
+                    self.line(&format!(
+                        "{target}.set_push_constants({}, {}, {});",
+                        flags!("ShaderStages", stages),
+                        offset,
+                        bytes(&data)
+                    ));
+                }
+                C::Draw {
+                    vertex_count,
+                    instance_count,
+                    first_vertex,
+                    first_instance,
+                } => self.line(&format!(
+                    "{target}.draw({}..{}, {}..{});",
+                    first_vertex,
+                    first_vertex + vertex_count,
+                    first_instance,
+                    first_instance + instance_count
+                )),
+                C::DrawIndexed {
+                    index_count,
+                    instance_count,
+                    first_index,
+                    base_vertex,
+                    first_instance,
+                } => self.line(&format!(
+                    "{target}.draw_indexed({}..{}, {}, {}..{});",
+                    first_index,
+                    first_index + index_count,
+                    base_vertex,
+                    first_instance,
+                    first_instance + instance_count
+                )),
+                C::MultiDrawIndirect {
+                    buffer_id,
+                    offset,
+                    count: None,
+                    indexed,
+                } => self.line(&format!(
+                    "{target}.{}(&{}, {});",
+                    if indexed {
+                        "draw_indexed_indirect"
+                    } else {
+                        "draw_indirect"
+                    },
+                    buffer(buffer_id),
+                    offset
+                )),
+                C::MultiDrawIndirect {
+                    buffer_id,
+                    offset,
+                    count: Some(count),
+                    indexed,
+                } if !bundle => self.line(&format!(
+                    "{target}.{}(&{}, {}, {});",
+                    if indexed {
+                        "multi_draw_indexed_indirect"
+                    } else {
+                        "multi_draw_indirect"
+                    },
+                    buffer(buffer_id),
+                    offset,
+                    count
+                )),
+                C::MultiDrawIndirectCount {
+                    buffer_id,
+                    offset,
+                    count_buffer_id,
+                    count_buffer_offset,
+                    max_count,
+                    indexed,
+                } if !bundle => self.line(&format!(
+                    "{target}.{}(&{}, {}, &{}, {}, {});",
+                    if indexed {
+                        "multi_draw_indexed_indirect_count"
+                    } else {
+                        "multi_draw_indirect_count"
+                    },
+                    buffer(buffer_id),
+                    offset,
+                    buffer(count_buffer_id),
+                    count_buffer_offset,
+                    max_count
+                )),
+                C::PushDebugGroup { len, .. } if !bundle => {
+                    let label = String::from_utf8_lossy(&strings[..len]).into_owned();
+                    strings = &strings[len..];
+                    self.line(&format!("{target}.push_debug_group({label:?});"));
+                }
+                C::PopDebugGroup if !bundle => self.line(&format!("{target}.pop_debug_group();")),
+                C::InsertDebugMarker { len, .. } if !bundle => {
+                    let label = String::from_utf8_lossy(&strings[..len]).into_owned();
+                    strings = &strings[len..];
+                    self.line(&format!("{target}.insert_debug_marker({label:?});"));
+                }
+                C::WriteTimestamp {
+                    query_set_id,
+                    query_index,
+                } if !bundle => self.line(&format!(
+                    "{target}.write_timestamp(&{}, {});",
+                    query_set(query_set_id),
+                    query_index
+                )),
+                C::BeginOcclusionQuery { query_index } if !bundle => {
+                    self.line(&format!("{target}.begin_occlusion_query({query_index});"))
+                }
+                C::EndOcclusionQuery if !bundle => {
+                    self.line(&format!("{target}.end_occlusion_query();"))
+                }
+                C::BeginPipelineStatisticsQuery {
+                    query_set_id,
+                    query_index,
+                } if !bundle => self.line(&format!(
+                    "{target}.begin_pipeline_statistics_query(&{}, {});",
+                    query_set(query_set_id),
+                    query_index
+                )),
+                C::EndPipelineStatisticsQuery if !bundle => {
+                    self.line(&format!("{target}.end_pipeline_statistics_query();"))
+                }
+                C::ExecuteBundle(id) if !bundle => {
+                    let mut bundles = vec![format!("&{}", var("render_bundle", id))];
+                    while let Some(&&C::ExecuteBundle(id)) = commands.peek() {
+                        bundles.push(format!("&{}", var("render_bundle", id)));
+                        commands.next();
+                    }
+                    self.line(&format!(
+                        "{target}.execute_bundles([{}]);",
+                        bundles.join(", ")
+                    ));
+                }
+                ref other => self.skip(other),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn workspace_paths() {
+        for name in ["wgpu", "naga"] {
+            let path = super::workspace_path(name);
+            let path = path.trim_matches('"').replace("\\\\", "\\");
+            assert!(std::path::Path::new(&path).join("Cargo.toml").is_file());
+        }
+    }
+}
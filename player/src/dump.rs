@@ -0,0 +1,452 @@
+//! Dumping the contents of buffers and textures at a point of a replay.
+//!
+//! Buffers are written as raw `.bin` files. Textures are read back one array
+//! layer (or depth slice) of the first mip level at a time, and written as PNG
+//! files when the format allows it, or as raw `.raw` files with tightly packed
+//! rows otherwise. The depth and stencil aspects of a texture are written to
+//! separate files; depth aspects without a defined layout, like that of
+//! `Depth24Plus`, can't be read back.
+
+use wgc::{device::trace, id};
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The resources to dump.
+#[derive(Debug, Default)]
+pub struct DumpSelection {
+    /// Dump every live buffer and texture.
+    pub all: bool,
+    /// Indices of the buffer IDs to dump.
+    pub buffers: Vec<u32>,
+    /// Indices of the texture IDs to dump.
+    pub textures: Vec<u32>,
+}
+
+impl DumpSelection {
+    /// Returns true if nothing is selected.
+    pub fn is_empty(&self) -> bool {
+        !self.all && self.buffers.is_empty() && self.textures.is_empty()
+    }
+
+    fn has_buffer(&self, id: id::BufferId) -> bool {
+        use wgc::id::TypedId as _;
+        self.all || self.buffers.contains(&id.unzip().0)
+    }
+
+    fn has_texture(&self, id: id::TextureId) -> bool {
+        use wgc::id::TypedId as _;
+        self.all || self.textures.contains(&id.unzip().0)
+    }
+}
+
+/// Keeps track of the live buffers and textures of a replay, so that their
+/// contents can be written to files.
+#[derive(Debug)]
+pub struct Dumper {
+    dir: PathBuf,
+    selection: DumpSelection,
+    buffers: HashMap<id::BufferId, wgt::BufferDescriptor<()>>,
+    textures: HashMap<id::TextureId, wgt::TextureDescriptor<(), ()>>,
+    /// Index for the next staging buffer, past any buffer of the trace.
+    next_staging_index: u32,
+}
+
+impl Dumper {
+    /// Create a dumper writing into `dir`, for a replay of `actions`.
+    pub fn new(dir: &Path, selection: DumpSelection, actions: &[trace::Action]) -> Self {
+        use wgc::id::TypedId as _;
+        let max_buffer_index = actions
+            .iter()
+            .filter_map(|action| match *action {
+                trace::Action::CreateBuffer(id, _) => Some(id.unzip().0),
+                _ => None,
+            })
+            .max();
+        Self {
+            dir: dir.to_path_buf(),
+            selection,
+            buffers: HashMap::new(),
+            textures: HashMap::new(),
+            next_staging_index: max_buffer_index.map_or(0, |index| index + 1),
+        }
+    }
+
+    /// Inspect an action before it is replayed.
+    ///
+    /// Selected resources get the `COPY_SRC` usage added, so that their
+    /// contents can be copied out later.
+    pub fn prepare(&mut self, action: &mut trace::Action) {
+        use trace::Action as A;
+        match *action {
+            A::CreateBuffer(id, ref mut desc) => {
+                let mappable = wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE;
+                if self.selection.has_buffer(id) && !desc.usage.intersects(mappable) {
+                    desc.usage |= wgt::BufferUsages::COPY_SRC;
+                }
+                self.buffers.insert(id, desc.map_label(|_| ()));
+            }
+            A::FreeBuffer(id) | A::DestroyBuffer(id) => {
+                self.buffers.remove(&id);
+            }
+            A::CreateTexture(id, ref mut desc) => {
+                if self.selection.has_texture(id) {
+                    desc.usage |= wgt::TextureUsages::COPY_SRC;
+                }
+                self.textures
+                    .insert(id, desc.map_label_and_view_formats(|_| (), |_| ()));
+            }
+            A::FreeTexture(id) | A::DestroyTexture(id) => {
+                self.textures.remove(&id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a texture that is not created by a `CreateTexture` action,
+    /// such as one standing in for a surface texture.
+    pub fn add_texture(&mut self, id: id::TextureId, desc: &wgc::resource::TextureDescriptor) {
+        self.textures
+            .insert(id, desc.map_label_and_view_formats(|_| (), |_| ()));
+    }
+
+    /// Forget a texture recorded with [`Dumper::add_texture`].
+    pub fn remove_texture(&mut self, id: id::TextureId) {
+        self.textures.remove(&id);
+    }
+
+    /// Write the contents of the selected live resources to files.
+    pub fn dump<A: wgc::hal_api::HalApi>(
+        &mut self,
+        global: &wgc::global::Global<crate::IdentityPassThroughFactory>,
+        device: id::DeviceId,
+        comb_manager: &mut wgc::identity::IdentityManager,
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        // Make sure the work of the trace is done before reading anything.
+        global
+            .device_poll::<A>(device, wgt::Maintain::Wait)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let mut buffers = self
+            .buffers
+            .iter()
+            .filter(|&(&id, _)| self.selection.has_buffer(id))
+            .map(|(&id, desc)| (id, desc.clone()))
+            .collect::<Vec<_>>();
+        buffers.sort_by_key(|&(id, _)| id);
+        for (id, desc) in buffers {
+            match self.dump_buffer::<A>(global, device, comb_manager, id, &desc) {
+                Ok(path) => log::info!("Dumped buffer {:?} into {:?}", id, path),
+                Err(e) => log::warn!("Unable to dump buffer {:?}: {}", id, e),
+            }
+        }
+
+        let mut textures = self
+            .textures
+            .iter()
+            .filter(|&(&id, _)| self.selection.has_texture(id))
+            .map(|(&id, desc)| (id, desc.clone()))
+            .collect::<Vec<_>>();
+        textures.sort_by_key(|&(id, _)| id);
+        for (id, desc) in textures {
+            match self.dump_texture::<A>(global, device, comb_manager, id, &desc) {
+                Ok(paths) => log::info!("Dumped texture {:?} into {:?}", id, paths),
+                Err(e) => log::warn!("Unable to dump texture {:?}: {}", id, e),
+            }
+        }
+        Ok(())
+    }
+
+    fn dump_buffer<A: wgc::hal_api::HalApi>(
+        &mut self,
+        global: &wgc::global::Global<crate::IdentityPassThroughFactory>,
+        device: id::DeviceId,
+        comb_manager: &mut wgc::identity::IdentityManager,
+        id: id::BufferId,
+        desc: &wgt::BufferDescriptor<()>,
+    ) -> io::Result<PathBuf> {
+        use wgc::id::TypedId as _;
+        // Copies and mappings can't cover a trailing partial word.
+        let size = desc.size & !(wgt::COPY_BUFFER_ALIGNMENT - 1);
+        let data = if desc.usage.contains(wgt::BufferUsages::MAP_READ) {
+            read_buffer::<A>(global, device, id, size)?
+        } else {
+            self.read_with_staging::<A>(global, device, comb_manager, size, |encoder, staging| {
+                global
+                    .command_encoder_copy_buffer_to_buffer::<A>(encoder, id, 0, staging, 0, size)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            })?
+        };
+        let path = self.dir.join(format!("buffer-{}.bin", id.unzip().0));
+        fs::write(&path, data)?;
+        Ok(path)
+    }
+
+    fn dump_texture<A: wgc::hal_api::HalApi>(
+        &mut self,
+        global: &wgc::global::Global<crate::IdentityPassThroughFactory>,
+        device: id::DeviceId,
+        comb_manager: &mut wgc::identity::IdentityManager,
+        id: id::TextureId,
+        desc: &wgt::TextureDescriptor<(), ()>,
+    ) -> io::Result<Vec<PathBuf>> {
+        if desc.sample_count > 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "multisampled textures can't be copied",
+            ));
+        }
+        // Depth and stencil can only be copied one aspect at a time.
+        let aspects: &[_] = match (
+            desc.format.has_depth_aspect(),
+            desc.format.has_stencil_aspect(),
+        ) {
+            (false, false) => &[(wgt::TextureAspect::All, "")],
+            (true, false) => &[(wgt::TextureAspect::DepthOnly, "-depth")],
+            (false, true) => &[(wgt::TextureAspect::StencilOnly, "-stencil")],
+            (true, true) => &[
+                (wgt::TextureAspect::DepthOnly, "-depth"),
+                (wgt::TextureAspect::StencilOnly, "-stencil"),
+            ],
+        };
+
+        let mut paths = Vec::new();
+        for &(aspect, suffix) in aspects {
+            match self.dump_texture_aspect::<A>(
+                global,
+                device,
+                comb_manager,
+                id,
+                desc,
+                aspect,
+                suffix,
+            ) {
+                Ok(aspect_paths) => paths.extend(aspect_paths),
+                // Keep the aspects that can be copied.
+                Err(e) if aspects.len() > 1 => {
+                    log::warn!("Unable to dump the {aspect:?} aspect of texture {id:?}: {e}")
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(paths)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn dump_texture_aspect<A: wgc::hal_api::HalApi>(
+        &mut self,
+        global: &wgc::global::Global<crate::IdentityPassThroughFactory>,
+        device: id::DeviceId,
+        comb_manager: &mut wgc::identity::IdentityManager,
+        id: id::TextureId,
+        desc: &wgt::TextureDescriptor<(), ()>,
+        aspect: wgt::TextureAspect,
+        suffix: &str,
+    ) -> io::Result<Vec<PathBuf>> {
+        use wgc::id::TypedId as _;
+        let format = desc.format.aspect_specific_format(aspect).unwrap();
+        let block_size = desc.format.block_size(Some(aspect)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("{format:?} data can't be copied to a buffer"),
+            )
+        })?;
+        let (block_width, block_height) = desc.format.block_dimensions();
+        let size = desc.size.physical_size(desc.format);
+        let layers = match desc.dimension {
+            wgt::TextureDimension::D3 => size.depth_or_array_layers,
+            _ => desc.array_layer_count(),
+        };
+        let unpadded_bytes_per_row = size.width / block_width * block_size;
+        let padded_bytes_per_row =
+            wgt::math::align_to(unpadded_bytes_per_row, wgt::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let rows = size.height / block_height;
+        let layer_size = padded_bytes_per_row as u64 * rows as u64;
+
+        let data = self.read_with_staging::<A>(
+            global,
+            device,
+            comb_manager,
+            layer_size * layers as u64,
+            |encoder, staging| {
+                global
+                    .command_encoder_copy_texture_to_buffer::<A>(
+                        encoder,
+                        &wgt::ImageCopyTexture {
+                            texture: id,
+                            mip_level: 0,
+                            origin: wgt::Origin3d::ZERO,
+                            aspect,
+                        },
+                        &wgt::ImageCopyBuffer {
+                            buffer: staging,
+                            layout: wgt::ImageDataLayout {
+                                offset: 0,
+                                bytes_per_row: Some(padded_bytes_per_row),
+                                rows_per_image: Some(rows),
+                            },
+                        },
+                        &wgt::Extent3d {
+                            depth_or_array_layers: layers,
+                            ..size
+                        },
+                    )
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            },
+        )?;
+
+        let mut paths = Vec::new();
+        for (layer, layer_data) in data.chunks_exact(layer_size as usize).enumerate() {
+            let pixels = layer_data
+                .chunks_exact(padded_bytes_per_row as usize)
+                .flat_map(|row| &row[..unpadded_bytes_per_row as usize])
+                .copied()
+                .collect::<Vec<u8>>();
+            let name = format!("texture-{}{}-layer{}", id.unzip().0, suffix, layer);
+            let path = match write_png(&self.dir, &name, format, size, pixels) {
+                Ok(path) => path,
+                Err(pixels) => {
+                    let path = self.dir.join(format!(
+                        "{}-{:?}-{}x{}.raw",
+                        name, format, size.width, size.height
+                    ));
+                    fs::write(&path, pixels)?;
+                    path
+                }
+            };
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Read `size` bytes copied by `copy` into a temporary staging buffer.
+    fn read_with_staging<A: wgc::hal_api::HalApi>(
+        &mut self,
+        global: &wgc::global::Global<crate::IdentityPassThroughFactory>,
+        device: id::DeviceId,
+        comb_manager: &mut wgc::identity::IdentityManager,
+        size: wgt::BufferAddress,
+        copy: impl FnOnce(id::CommandEncoderId, id::BufferId) -> io::Result<()>,
+    ) -> io::Result<Vec<u8>> {
+        use wgc::id::TypedId as _;
+        let staging = id::BufferId::zip(self.next_staging_index, 1, device.backend());
+        self.next_staging_index += 1;
+
+        global
+            .device_maintain_ids::<A>(device)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let (_, error) = global.device_create_buffer::<A>(
+            device,
+            &wgt::BufferDescriptor {
+                label: Some("(player) dump staging".into()),
+                size,
+                usage: wgt::BufferUsages::MAP_READ | wgt::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+            staging,
+        );
+        if let Some(e) = error {
+            return Err(io::Error::new(io::ErrorKind::Other, e));
+        }
+
+        let result = (|| {
+            let (encoder, error) = global.device_create_command_encoder::<A>(
+                device,
+                &wgt::CommandEncoderDescriptor { label: None },
+                comb_manager.alloc(device.backend()),
+            );
+            if let Some(e) = error {
+                return Err(io::Error::new(io::ErrorKind::Other, e));
+            }
+            copy(encoder, staging)?;
+            let (cmdbuf, error) = global.command_encoder_finish::<A>(
+                encoder,
+                &wgt::CommandBufferDescriptor { label: None },
+            );
+            if let Some(e) = error {
+                return Err(io::Error::new(io::ErrorKind::Other, e));
+            }
+            global
+                .queue_submit::<A>(device, &[cmdbuf])
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            read_buffer::<A>(global, device, staging, size)
+        })();
+
+        global.buffer_drop::<A>(staging, false);
+        result
+    }
+}
+
+/// Read the contents of a buffer that has the `MAP_READ` usage.
+fn read_buffer<A: wgc::hal_api::HalApi>(
+    global: &wgc::global::Global<crate::IdentityPassThroughFactory>,
+    device: id::DeviceId,
+    buffer: id::BufferId,
+    size: wgt::BufferAddress,
+) -> io::Result<Vec<u8>> {
+    global
+        .buffer_map_async::<A>(
+            buffer,
+            0..size,
+            wgc::resource::BufferMapOperation {
+                host: wgc::device::HostMap::Read,
+                callback: wgc::resource::BufferMapCallback::from_rust(Box::new(|_| {})),
+            },
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    global
+        .device_poll::<A>(device, wgt::Maintain::Wait)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let (ptr, mapped_size) = global
+        .buffer_get_mapped_range::<A>(buffer, 0, Some(size))
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let data = unsafe { std::slice::from_raw_parts(ptr, mapped_size as usize) }.to_vec();
+    global
+        .buffer_unmap::<A>(buffer)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    Ok(data)
+}
+
+/// Write 8-bit color `pixels` as a PNG file, or hand them back if the format
+/// can't be represented as one.
+fn write_png(
+    dir: &Path,
+    name: &str,
+    format: wgt::TextureFormat,
+    size: wgt::Extent3d,
+    mut pixels: Vec<u8>,
+) -> Result<PathBuf, Vec<u8>> {
+    use wgt::TextureFormat as Tf;
+    let color_type = match format {
+        Tf::R8Unorm | Tf::Stencil8 => png::ColorType::Grayscale,
+        Tf::Rgba8Unorm | Tf::Rgba8UnormSrgb => png::ColorType::Rgba,
+        Tf::Bgra8Unorm | Tf::Bgra8UnormSrgb => {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            png::ColorType::Rgba
+        }
+        _ => return Err(pixels),
+    };
+
+    let path = dir.join(format!("{name}.png"));
+    let result = fs::File::create(&path)
+        .map_err(png::EncodingError::from)
+        .and_then(|file| {
+            let mut encoder = png::Encoder::new(io::BufWriter::new(file), size.width, size.height);
+            encoder.set_color(color_type);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.write_header()?.write_image_data(&pixels)
+        });
+    match result {
+        Ok(()) => Ok(path),
+        Err(e) => {
+            log::warn!("Unable to write {:?}: {}", path, e);
+            Err(pixels)
+        }
+    }
+}
@@ -10,7 +10,12 @@
 
 use wgc::device::trace;
 
-use std::{borrow::Cow, fmt::Debug, fs, marker::PhantomData, path::Path};
+pub mod codegen;
+pub mod dump;
+pub mod minimize;
+pub mod stats;
+
+use std::{borrow::Cow, fmt::Debug, marker::PhantomData};
 
 #[derive(Debug)]
 pub struct IdentityPassThrough<I>(PhantomData<I>);
@@ -52,7 +57,7 @@ pub trait GlobalPlay {
         &self,
         device: wgc::id::DeviceId,
         action: trace::Action,
-        dir: &Path,
+        data_source: &trace::DataSource,
         comb_manager: &mut wgc::identity::IdentityManager,
     );
 }
@@ -167,7 +172,7 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
         &self,
         device: wgc::id::DeviceId,
         action: trace::Action,
-        dir: &Path,
+        data_source: &trace::DataSource,
         comb_manager: &mut wgc::identity::IdentityManager,
     ) {
         use wgc::device::trace::Action;
@@ -232,6 +237,17 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             Action::DestroySampler(id) => {
                 self.sampler_drop::<A>(id);
             }
+            Action::CreateExternalTexture { id, desc, planes } => {
+                self.device_maintain_ids::<A>(device).unwrap();
+                let (_, error) =
+                    self.device_create_external_texture::<A>(device, &desc, &planes, id);
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyExternalTexture(id) => {
+                self.external_texture_drop::<A>(id);
+            }
             Action::GetSurfaceTexture { id, parent_id } => {
                 self.device_maintain_ids::<A>(device).unwrap();
                 self.surface_get_current_texture::<A>(parent_id, id)
@@ -270,7 +286,7 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             }
             Action::CreateShaderModule { id, desc, data } => {
                 log::info!("Creating shader from {}", data);
-                let code = fs::read_to_string(dir.join(&data)).unwrap();
+                let code = data_source.read_to_string(&data).unwrap();
                 let source = if data.ends_with(".wgsl") {
                     wgc::pipeline::ShaderModuleSource::Wgsl(Cow::Owned(code.clone()))
                 } else if data.ends_with(".ron") {
@@ -346,6 +362,20 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             Action::DestroyRenderBundle(id) => {
                 self.render_bundle_drop::<A>(id);
             }
+            Action::CreateComputeBundle { id, desc, base } => {
+                let bundle = wgc::command::ComputeBundleEncoder::new(&desc, device, Some(base));
+                let (_, error) = self.compute_bundle_encoder_finish::<A>(
+                    bundle,
+                    &wgt::ComputeBundleDescriptor { label: desc.label },
+                    id,
+                );
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
+            Action::DestroyComputeBundle(id) => {
+                self.compute_bundle_drop::<A>(id);
+            }
             Action::CreateQuerySet { id, desc } => {
                 self.device_maintain_ids::<A>(device).unwrap();
                 let (_, error) = self.device_create_query_set::<A>(device, &desc, id);
@@ -362,7 +392,7 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                 range,
                 queued,
             } => {
-                let bin = std::fs::read(dir.join(data)).unwrap();
+                let bin = data_source.read(&data).unwrap();
                 let size = (range.end - range.start) as usize;
                 if queued {
                     self.queue_write_buffer::<A>(device, id, range.start, &bin)
@@ -373,13 +403,38 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                         .unwrap();
                 }
             }
+            Action::UnmapBuffer { id, data, range } => {
+                let bin = data_source.read(&data).unwrap();
+                let size = range.end - range.start;
+                // Buffers mapped at creation are still mapped, others are
+                // mapped the way the application did before writing.
+                if let Err(wgc::resource::BufferAccessError::NotMapped) =
+                    self.buffer_get_mapped_range::<A>(id, range.start, Some(size))
+                {
+                    self.buffer_map_async::<A>(
+                        id,
+                        range.clone(),
+                        wgc::resource::BufferMapOperation {
+                            host: wgc::device::HostMap::Write,
+                            callback: wgc::resource::BufferMapCallback::from_rust(Box::new(|_| {})),
+                        },
+                    )
+                    .unwrap();
+                    self.device_poll::<A>(device, wgt::Maintain::Wait).unwrap();
+                }
+                let (ptr, _) = self
+                    .buffer_get_mapped_range::<A>(id, range.start, Some(size))
+                    .unwrap();
+                unsafe { std::ptr::copy_nonoverlapping(bin.as_ptr(), ptr, size as usize) };
+                self.buffer_unmap::<A>(id).unwrap();
+            }
             Action::WriteTexture {
                 to,
                 data,
                 layout,
                 size,
             } => {
-                let bin = std::fs::read(dir.join(data)).unwrap();
+                let bin = data_source.read(&data).unwrap();
                 self.queue_write_texture::<A>(device, &to, &bin, &layout, &size)
                     .unwrap();
             }
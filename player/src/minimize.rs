@@ -0,0 +1,717 @@
+//! Reducing a trace to the actions that reproduce an issue.
+//!
+//! Actions are removed by delta debugging, first as a whole, then the
+//! commands of each submission, then the commands of each pass. A candidate
+//! trace is only tested if it's consistent: when the action creating an
+//! object is removed, so is every action or command that uses the object.
+//!
+//! Pairs of commands that have to stay balanced, like debug groups and
+//! queries, are never removed on their own.
+
+use wgc::{
+    command::{BasePass, ComputeCommand, RenderCommand},
+    device::trace,
+    id,
+};
+
+use std::{collections::HashSet, io, path::Path};
+
+/// An object of the trace, created by an action and used by others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Object {
+    Surface(id::SurfaceId),
+    Buffer(id::BufferId),
+    Texture(id::TextureId),
+    TextureView(id::TextureViewId),
+    Sampler(id::SamplerId),
+    ExternalTexture(id::ExternalTextureId),
+    BindGroupLayout(id::BindGroupLayoutId),
+    PipelineLayout(id::PipelineLayoutId),
+    BindGroup(id::BindGroupId),
+    ShaderModule(id::ShaderModuleId),
+    ComputePipeline(id::ComputePipelineId),
+    RenderPipeline(id::RenderPipelineId),
+    RenderBundle(id::RenderBundleId),
+    ComputeBundle(id::ComputeBundleId),
+    QuerySet(id::QuerySetId),
+}
+
+/// The objects created by `action`.
+fn created(action: &trace::Action) -> Vec<Object> {
+    use trace::Action as A;
+    match *action {
+        // Surfaces can only be used once configured.
+        A::ConfigureSurface(id, _) => vec![Object::Surface(id)],
+        A::CreateBuffer(id, _) => vec![Object::Buffer(id)],
+        A::CreateTexture(id, _) | A::GetSurfaceTexture { id, .. } => vec![Object::Texture(id)],
+        A::CreateTextureView { id, .. } => vec![Object::TextureView(id)],
+        A::CreateSampler(id, _) => vec![Object::Sampler(id)],
+        A::CreateExternalTexture { id, .. } => vec![Object::ExternalTexture(id)],
+        A::CreateBindGroupLayout(id, _) => vec![Object::BindGroupLayout(id)],
+        A::CreatePipelineLayout(id, _) => vec![Object::PipelineLayout(id)],
+        A::CreateBindGroup(id, _) => vec![Object::BindGroup(id)],
+        A::CreateShaderModule { id, .. } => vec![Object::ShaderModule(id)],
+        A::CreateComputePipeline {
+            id,
+            ref implicit_context,
+            ..
+        } => {
+            let mut objects = vec![Object::ComputePipeline(id)];
+            objects.extend(implicit_objects(implicit_context));
+            objects
+        }
+        A::CreateRenderPipeline {
+            id,
+            ref implicit_context,
+            ..
+        } => {
+            let mut objects = vec![Object::RenderPipeline(id)];
+            objects.extend(implicit_objects(implicit_context));
+            objects
+        }
+        A::CreateRenderBundle { id, .. } => vec![Object::RenderBundle(id)],
+        A::CreateComputeBundle { id, .. } => vec![Object::ComputeBundle(id)],
+        A::CreateQuerySet { id, .. } => vec![Object::QuerySet(id)],
+        _ => Vec::new(),
+    }
+}
+
+fn implicit_objects(context: &Option<wgc::device::ImplicitPipelineContext>) -> Vec<Object> {
+    match *context {
+        Some(ref context) => Some(Object::PipelineLayout(context.root_id))
+            .into_iter()
+            .chain(
+                context
+                    .group_ids
+                    .iter()
+                    .map(|&id| Object::BindGroupLayout(id)),
+            )
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// The objects used by `action`.
+fn used(action: &trace::Action, objects: &mut Vec<Object>) {
+    use trace::Action as A;
+    match *action {
+        A::Init { .. } | A::ConfigureSurface(..) => {}
+        A::CreateBuffer(..) | A::CreateTexture(..) | A::CreateSampler(..) => {}
+        A::FreeBuffer(id) | A::DestroyBuffer(id) => objects.push(Object::Buffer(id)),
+        A::FreeTexture(id) | A::DestroyTexture(id) => objects.push(Object::Texture(id)),
+        A::CreateTextureView { parent_id, .. } => objects.push(Object::Texture(parent_id)),
+        A::DestroyTextureView(id) => objects.push(Object::TextureView(id)),
+        A::DestroySampler(id) => objects.push(Object::Sampler(id)),
+        A::CreateExternalTexture { ref planes, .. } => {
+            objects.extend(planes.iter().map(|&id| Object::TextureView(id)))
+        }
+        A::DestroyExternalTexture(id) => objects.push(Object::ExternalTexture(id)),
+        A::GetSurfaceTexture { parent_id, .. } => objects.push(Object::Surface(parent_id)),
+        A::Present(id) | A::DiscardSurfaceTexture(id) => objects.push(Object::Surface(id)),
+        A::CreateBindGroupLayout(..) => {}
+        A::DestroyBindGroupLayout(id) => objects.push(Object::BindGroupLayout(id)),
+        A::CreatePipelineLayout(_, ref desc) => objects.extend(
+            desc.bind_group_layouts
+                .iter()
+                .map(|&id| Object::BindGroupLayout(id)),
+        ),
+        A::DestroyPipelineLayout(id) => objects.push(Object::PipelineLayout(id)),
+        A::CreateBindGroup(_, ref desc) => {
+            use wgc::binding_model::BindingResource as Br;
+            objects.push(Object::BindGroupLayout(desc.layout));
+            for entry in desc.entries.iter() {
+                match entry.resource {
+                    Br::Buffer(ref binding) => objects.push(Object::Buffer(binding.buffer_id)),
+                    Br::BufferArray(ref bindings) => {
+                        objects.extend(bindings.iter().map(|b| Object::Buffer(b.buffer_id)))
+                    }
+                    Br::Sampler(id) => objects.push(Object::Sampler(id)),
+                    Br::SamplerArray(ref ids) => {
+                        objects.extend(ids.iter().map(|&id| Object::Sampler(id)))
+                    }
+                    Br::TextureView(id) => objects.push(Object::TextureView(id)),
+                    Br::TextureViewArray(ref ids) => {
+                        objects.extend(ids.iter().map(|&id| Object::TextureView(id)))
+                    }
+                    Br::ExternalTexture(id) => objects.push(Object::ExternalTexture(id)),
+                }
+            }
+        }
+        A::DestroyBindGroup(id) => objects.push(Object::BindGroup(id)),
+        A::CreateShaderModule { .. } => {}
+        A::DestroyShaderModule(id) => objects.push(Object::ShaderModule(id)),
+        A::CreateComputePipeline { ref desc, .. } => {
+            objects.extend(desc.layout.map(Object::PipelineLayout));
+            objects.push(Object::ShaderModule(desc.stage.module));
+        }
+        A::DestroyComputePipeline(id) => objects.push(Object::ComputePipeline(id)),
+        A::CreateRenderPipeline { ref desc, .. } => {
+            objects.extend(desc.layout.map(Object::PipelineLayout));
+            objects.push(Object::ShaderModule(desc.vertex.stage.module));
+            if let Some(ref fragment) = desc.fragment {
+                objects.push(Object::ShaderModule(fragment.stage.module));
+            }
+        }
+        A::DestroyRenderPipeline(id) => objects.push(Object::RenderPipeline(id)),
+        A::CreateRenderBundle { ref base, .. } => {
+            for command in base.commands.iter() {
+                render_command_used(command, objects);
+            }
+        }
+        A::DestroyRenderBundle(id) => objects.push(Object::RenderBundle(id)),
+        A::CreateComputeBundle { ref base, .. } => {
+            for command in base.commands.iter() {
+                compute_command_used(command, objects);
+            }
+        }
+        A::DestroyComputeBundle(id) => objects.push(Object::ComputeBundle(id)),
+        A::CreateQuerySet { .. } => {}
+        A::DestroyQuerySet(id) => objects.push(Object::QuerySet(id)),
+        A::WriteBuffer { id, .. } | A::UnmapBuffer { id, .. } => objects.push(Object::Buffer(id)),
+        A::WriteTexture { ref to, .. } => objects.push(Object::Texture(to.texture)),
+        A::Submit(_, ref commands) => {
+            for command in commands.iter() {
+                command_used(command, objects);
+            }
+        }
+    }
+}
+
+fn command_used(command: &trace::Command, objects: &mut Vec<Object>) {
+    use trace::Command as C;
+    match *command {
+        C::CopyBufferToBuffer { src, dst, .. } => {
+            objects.extend([Object::Buffer(src), Object::Buffer(dst)])
+        }
+        C::CopyBufferToTexture {
+            ref src, ref dst, ..
+        } => objects.extend([Object::Buffer(src.buffer), Object::Texture(dst.texture)]),
+        C::CopyTextureToBuffer {
+            ref src, ref dst, ..
+        } => objects.extend([Object::Texture(src.texture), Object::Buffer(dst.buffer)]),
+        C::CopyTextureToTexture {
+            ref src, ref dst, ..
+        } => objects.extend([Object::Texture(src.texture), Object::Texture(dst.texture)]),
+        C::ClearBuffer { dst, .. } => objects.push(Object::Buffer(dst)),
+        C::ClearTexture { dst, .. } => objects.push(Object::Texture(dst)),
+        C::WriteTimestamp { query_set_id, .. } => objects.push(Object::QuerySet(query_set_id)),
+        C::ResolveQuerySet {
+            query_set_id,
+            destination,
+            ..
+        } => objects.extend([Object::QuerySet(query_set_id), Object::Buffer(destination)]),
+        C::PushDebugGroup(_) | C::PopDebugGroup | C::InsertDebugMarker(_) => {}
+        C::RunComputePass {
+            ref base,
+            ref timestamp_writes,
+        } => {
+            for command in base.commands.iter() {
+                compute_command_used(command, objects);
+            }
+            objects.extend(
+                timestamp_writes
+                    .as_ref()
+                    .map(|tw| Object::QuerySet(tw.query_set)),
+            );
+        }
+        C::RunRenderPass {
+            ref base,
+            ref target_colors,
+            ref target_depth_stencil,
+            ref timestamp_writes,
+            occlusion_query_set_id,
+        } => {
+            for command in base.commands.iter() {
+                render_command_used(command, objects);
+            }
+            for at in target_colors.iter().flatten() {
+                objects.push(Object::TextureView(at.view));
+                objects.extend(at.resolve_target.map(Object::TextureView));
+            }
+            objects.extend(
+                target_depth_stencil
+                    .as_ref()
+                    .map(|at| Object::TextureView(at.view)),
+            );
+            objects.extend(
+                timestamp_writes
+                    .as_ref()
+                    .map(|tw| Object::QuerySet(tw.query_set)),
+            );
+            objects.extend(occlusion_query_set_id.map(Object::QuerySet));
+        }
+    }
+}
+
+fn compute_command_used(command: &ComputeCommand, objects: &mut Vec<Object>) {
+    use ComputeCommand as C;
+    match *command {
+        C::SetBindGroup { bind_group_id, .. } => objects.push(Object::BindGroup(bind_group_id)),
+        C::SetPipeline(id) => objects.push(Object::ComputePipeline(id)),
+        C::DispatchIndirect { buffer_id, .. } => objects.push(Object::Buffer(buffer_id)),
+        C::WriteTimestamp { query_set_id, .. }
+        | C::BeginPipelineStatisticsQuery { query_set_id, .. } => {
+            objects.push(Object::QuerySet(query_set_id))
+        }
+        C::ExecuteBundle(id) => objects.push(Object::ComputeBundle(id)),
+        _ => {}
+    }
+}
+
+fn render_command_used(command: &RenderCommand, objects: &mut Vec<Object>) {
+    use RenderCommand as C;
+    match *command {
+        C::SetBindGroup { bind_group_id, .. } => objects.push(Object::BindGroup(bind_group_id)),
+        C::SetPipeline(id) => objects.push(Object::RenderPipeline(id)),
+        C::SetIndexBuffer { buffer_id, .. }
+        | C::SetVertexBuffer { buffer_id, .. }
+        | C::MultiDrawIndirect { buffer_id, .. } => objects.push(Object::Buffer(buffer_id)),
+        C::MultiDrawIndirectCount {
+            buffer_id,
+            count_buffer_id,
+            ..
+        } => objects.extend([Object::Buffer(buffer_id), Object::Buffer(count_buffer_id)]),
+        C::WriteTimestamp { query_set_id, .. }
+        | C::BeginPipelineStatisticsQuery { query_set_id, .. } => {
+            objects.push(Object::QuerySet(query_set_id))
+        }
+        C::ExecuteBundle(id) => objects.push(Object::RenderBundle(id)),
+        _ => {}
+    }
+}
+
+/// Whether an encoder command can be removed on its own.
+fn is_removable_command(command: &trace::Command) -> bool {
+    !matches!(
+        *command,
+        trace::Command::PushDebugGroup(_) | trace::Command::PopDebugGroup
+    )
+}
+
+fn is_removable_compute_command(command: &ComputeCommand) -> bool {
+    use ComputeCommand as C;
+    !matches!(
+        *command,
+        C::PushDebugGroup { .. }
+            | C::PopDebugGroup
+            | C::BeginPipelineStatisticsQuery { .. }
+            | C::EndPipelineStatisticsQuery
+    )
+}
+
+fn is_removable_render_command(command: &RenderCommand) -> bool {
+    use RenderCommand as C;
+    !matches!(
+        *command,
+        C::PushDebugGroup { .. }
+            | C::PopDebugGroup
+            | C::BeginOcclusionQuery { .. }
+            | C::EndOcclusionQuery
+            | C::BeginPipelineStatisticsQuery { .. }
+            | C::EndPipelineStatisticsQuery
+    )
+}
+
+/// Remove the actions that use objects whose creation was removed.
+///
+/// Objects that no action of the full trace creates are assumed to exist.
+fn make_consistent(
+    actions: Vec<trace::Action<'static>>,
+    all_created: &HashSet<Object>,
+) -> Vec<trace::Action<'static>> {
+    let mut available = HashSet::new();
+    let mut objects = Vec::new();
+    actions
+        .into_iter()
+        .filter(|action| {
+            objects.clear();
+            used(action, &mut objects);
+            let consistent = objects
+                .iter()
+                .all(|object| available.contains(object) || !all_created.contains(object));
+            if consistent {
+                available.extend(created(action));
+            }
+            consistent
+        })
+        .collect()
+}
+
+/// Find a subset of `count` units for which `test` still holds, by delta
+/// debugging. `test` is given a mask of the units to keep.
+fn reduce(count: usize, mut test: impl FnMut(&[bool]) -> bool) -> Vec<bool> {
+    let mut keep = vec![true; count];
+    let mut granularity = 2;
+    loop {
+        let kept = (0..count).filter(|&i| keep[i]).collect::<Vec<_>>();
+        if kept.is_empty() {
+            break;
+        }
+        let granularity_now = granularity.min(kept.len());
+        let chunk_size =
+            kept.len() / granularity_now + usize::from(kept.len() % granularity_now != 0);
+        let mut reduced = false;
+        for chunk in kept.chunks(chunk_size) {
+            let mut candidate = keep.clone();
+            for &i in chunk {
+                candidate[i] = false;
+            }
+            if test(&candidate) {
+                keep = candidate;
+                reduced = true;
+                break;
+            }
+        }
+        if reduced {
+            granularity = (granularity_now - 1).max(2);
+        } else if granularity_now < kept.len() {
+            granularity = (granularity_now * 2).min(kept.len());
+        } else {
+            break;
+        }
+    }
+    keep
+}
+
+/// Keep the commands of `base` selected by `keep`, along with their data.
+fn filter_pass<C: Clone>(
+    base: &BasePass<C>,
+    keep: impl Fn(usize) -> bool,
+    data: impl Fn(&C) -> (usize, usize),
+) -> BasePass<C> {
+    let mut result = BasePass {
+        label: base.label.clone(),
+        commands: Vec::new(),
+        dynamic_offsets: Vec::new(),
+        string_data: Vec::new(),
+        push_constant_data: base.push_constant_data.clone(),
+    };
+    let (mut offsets, mut strings) = (0, 0);
+    for (index, command) in base.commands.iter().enumerate() {
+        let (num_offsets, num_bytes) = data(command);
+        if keep(index) {
+            result.commands.push(command.clone());
+            result
+                .dynamic_offsets
+                .extend_from_slice(&base.dynamic_offsets[offsets..offsets + num_offsets]);
+            result
+                .string_data
+                .extend_from_slice(&base.string_data[strings..strings + num_bytes]);
+        }
+        offsets += num_offsets;
+        strings += num_bytes;
+    }
+    result
+}
+
+/// Dynamic offsets and string bytes used by a compute command.
+fn compute_command_data(command: &ComputeCommand) -> (usize, usize) {
+    match *command {
+        ComputeCommand::SetBindGroup {
+            num_dynamic_offsets,
+            ..
+        } => (num_dynamic_offsets as usize, 0),
+        ComputeCommand::PushDebugGroup { len, .. }
+        | ComputeCommand::InsertDebugMarker { len, .. } => (0, len),
+        _ => (0, 0),
+    }
+}
+
+/// Dynamic offsets and string bytes used by a render command.
+fn render_command_data(command: &RenderCommand) -> (usize, usize) {
+    match *command {
+        RenderCommand::SetBindGroup {
+            num_dynamic_offsets,
+            ..
+        } => (num_dynamic_offsets as usize, 0),
+        RenderCommand::PushDebugGroup { len, .. }
+        | RenderCommand::InsertDebugMarker { len, .. } => (0, len),
+        _ => (0, 0),
+    }
+}
+
+/// Reduce `actions` to a smaller trace for which `reproduces` still holds.
+///
+/// `reproduces` must hold for the full trace. The first action, which
+/// initializes the device, is always kept.
+pub fn minimize(
+    mut actions: Vec<trace::Action<'static>>,
+    mut reproduces: impl FnMut(&[trace::Action<'static>]) -> bool,
+) -> Vec<trace::Action<'static>> {
+    let all_created = actions.iter().flat_map(created).collect::<HashSet<_>>();
+    let mut tests = 0;
+    let mut test = |candidate: &[trace::Action<'static>]| {
+        tests += 1;
+        let result = reproduces(candidate);
+        log::info!(
+            "Test {}: {} actions, {}",
+            tests,
+            candidate.len(),
+            if result {
+                "reproduced"
+            } else {
+                "not reproduced"
+            }
+        );
+        result
+    };
+
+    loop {
+        let length = actions.len();
+        let commands = count_commands(&actions);
+
+        // Remove whole actions.
+        let keep = reduce(actions.len() - 1, |keep| {
+            let candidate = select_actions(&actions, keep, &all_created);
+            candidate.len() < actions.len() && test(&candidate)
+        });
+        actions = select_actions(&actions, &keep, &all_created);
+
+        // Remove commands of the submissions.
+        for index in 0..actions.len() {
+            let num_commands = match actions[index] {
+                trace::Action::Submit(_, ref commands) => commands.len(),
+                _ => continue,
+            };
+            let keep = reduce(num_commands, |keep| {
+                let mut candidate = actions.clone();
+                if !select_commands(&mut candidate[index], keep) {
+                    return false;
+                }
+                test(&candidate)
+            });
+            select_commands(&mut actions[index], &keep);
+
+            // Remove commands of the passes.
+            let num_commands = match actions[index] {
+                trace::Action::Submit(_, ref commands) => commands.len(),
+                _ => unreachable!(),
+            };
+            for pass in 0..num_commands {
+                let num_commands = match actions[index] {
+                    trace::Action::Submit(_, ref commands) => match commands[pass] {
+                        trace::Command::RunComputePass { ref base, .. } => base.commands.len(),
+                        trace::Command::RunRenderPass { ref base, .. } => base.commands.len(),
+                        _ => continue,
+                    },
+                    _ => unreachable!(),
+                };
+                let keep = reduce(num_commands, |keep| {
+                    let mut candidate = actions.clone();
+                    if !select_pass_commands(&mut candidate[index], pass, keep) {
+                        return false;
+                    }
+                    test(&candidate)
+                });
+                select_pass_commands(&mut actions[index], pass, &keep);
+            }
+        }
+
+        // Removing commands may allow removing more actions.
+        if actions.len() == length && count_commands(&actions) == commands {
+            break;
+        }
+    }
+    actions
+}
+
+/// The number of commands in submissions and passes.
+fn count_commands(actions: &[trace::Action]) -> usize {
+    actions
+        .iter()
+        .map(|action| match *action {
+            trace::Action::Submit(_, ref commands) => commands
+                .iter()
+                .map(|command| match *command {
+                    trace::Command::RunComputePass { ref base, .. } => 1 + base.commands.len(),
+                    trace::Command::RunRenderPass { ref base, .. } => 1 + base.commands.len(),
+                    _ => 1,
+                })
+                .sum(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Keep the first action, and the actions after it selected by `keep`.
+fn select_actions(
+    actions: &[trace::Action<'static>],
+    keep: &[bool],
+    all_created: &HashSet<Object>,
+) -> Vec<trace::Action<'static>> {
+    let selected = actions
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index == 0 || keep[index - 1])
+        .map(|(_, action)| action.clone())
+        .collect();
+    make_consistent(selected, all_created)
+}
+
+/// Keep the commands of the submission `action` selected by `keep`, and
+/// the commands that can't be removed on their own.
+///
+/// Returns `false` if nothing would be removed.
+fn select_commands(action: &mut trace::Action, keep: &[bool]) -> bool {
+    let commands = match *action {
+        trace::Action::Submit(_, ref mut commands) => commands,
+        _ => unreachable!(),
+    };
+    let before = commands.len();
+    let mut index = 0;
+    commands.retain(|command| {
+        index += 1;
+        keep[index - 1] || !is_removable_command(command)
+    });
+    commands.len() < before
+}
+
+/// Keep the commands of the pass `pass` of the submission `action`
+/// selected by `keep`, and the commands that can't be removed on their own.
+///
+/// Returns `false` if nothing would be removed.
+fn select_pass_commands(action: &mut trace::Action, pass: usize, keep: &[bool]) -> bool {
+    let command = match *action {
+        trace::Action::Submit(_, ref mut commands) => &mut commands[pass],
+        _ => unreachable!(),
+    };
+    match *command {
+        trace::Command::RunComputePass { ref mut base, .. } => {
+            let before = base.commands.len();
+            *base = filter_pass(
+                &*base,
+                |i| keep[i] || !is_removable_compute_command(&base.commands[i]),
+                compute_command_data,
+            );
+            base.commands.len() < before
+        }
+        trace::Command::RunRenderPass { ref mut base, .. } => {
+            let before = base.commands.len();
+            *base = filter_pass(
+                &*base,
+                |i| keep[i] || !is_removable_render_command(&base.commands[i]),
+                render_command_data,
+            );
+            base.commands.len() < before
+        }
+        _ => false,
+    }
+}
+
+/// Write `actions` as a trace at `path`, copying the data they refer to
+/// from `data_source`.
+///
+/// As with [`wgc::device::trace::Trace::new`], the trace is a single file
+/// if `path` has the [`trace::BINARY_EXTENSION`] extension, and a
+/// directory otherwise.
+pub fn write(
+    actions: &[trace::Action<'static>],
+    data_source: &trace::DataSource,
+    path: &Path,
+) -> io::Result<()> {
+    if path.extension() != Some(trace::BINARY_EXTENSION.as_ref()) {
+        std::fs::create_dir_all(path)?;
+    }
+    let mut trace = trace::Trace::new(path)?;
+    for action in actions {
+        let mut action = action.clone();
+        match action {
+            trace::Action::CreateShaderModule { ref mut data, .. }
+            | trace::Action::WriteBuffer { ref mut data, .. }
+            | trace::Action::UnmapBuffer { ref mut data, .. }
+            | trace::Action::WriteTexture { ref mut data, .. } => {
+                let kind = Path::new(data.as_str())
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("bin")
+                    .to_string();
+                *data = trace.make_binary(&kind, &data_source.read(data)?);
+            }
+            _ => {}
+        }
+        trace.add(action);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two buffers are written and copied, and the second one is cleared.
+    const TRACE: &str = "[
+        Init(desc: (label: None, features: 0, limits: ()), backend: Empty),
+        CreateBuffer(Id(0, 1, Empty), (label: None, size: 16, usage: 12, mapped_at_creation: false)),
+        CreateBuffer(Id(1, 1, Empty), (label: None, size: 16, usage: 12, mapped_at_creation: false)),
+        WriteBuffer(id: Id(0, 1, Empty), data: \"a.bin\", range: (start: 0, end: 16), queued: true),
+        WriteBuffer(id: Id(1, 1, Empty), data: \"b.bin\", range: (start: 0, end: 16), queued: true),
+        Submit(1, [
+            CopyBufferToBuffer(src: Id(0, 1, Empty), src_offset: 0, dst: Id(1, 1, Empty), dst_offset: 0, size: 16),
+            ClearBuffer(dst: Id(1, 1, Empty), offset: 0, size: None),
+        ]),
+        DestroyBuffer(Id(0, 1, Empty)),
+        DestroyBuffer(Id(1, 1, Empty)),
+    ]";
+
+    fn trace() -> Vec<trace::Action<'static>> {
+        ron::de::from_str(TRACE).unwrap()
+    }
+
+    fn buffer(index: u32) -> Object {
+        Object::Buffer(id::TypedId::zip(index, 1, wgt::Backend::Empty))
+    }
+
+    fn clears_buffer(actions: &[trace::Action]) -> bool {
+        actions.iter().any(|action| match *action {
+            trace::Action::Submit(_, ref commands) => commands
+                .iter()
+                .any(|command| matches!(*command, trace::Command::ClearBuffer { .. })),
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn shrinks_to_minimal_actions() {
+        let minimized = minimize(trace(), clears_buffer);
+        assert_eq!(minimized.len(), 3, "{minimized:#?}");
+        assert!(matches!(minimized[0], trace::Action::Init { .. }));
+        assert!(matches!(minimized[1], trace::Action::CreateBuffer(..)));
+        assert_eq!(created(&minimized[1]), [buffer(1)]);
+        match minimized[2] {
+            trace::Action::Submit(_, ref commands) => {
+                assert!(matches!(commands[..], [trace::Command::ClearBuffer { .. }]))
+            }
+            ref other => panic!("unexpected action {other:?}"),
+        }
+    }
+
+    #[test]
+    fn dependent_actions_are_dropped() {
+        let actions = trace();
+        let all_created = actions.iter().flat_map(created).collect::<HashSet<_>>();
+
+        // Removing the creation of the first buffer removes its write, the
+        // submission copying from it, and its destruction.
+        let mut keep = vec![true; actions.len() - 1];
+        keep[0] = false;
+        let selected = select_actions(&actions, &keep, &all_created);
+        assert_eq!(selected.len(), 4, "{selected:#?}");
+        let mut objects = Vec::new();
+        for action in &selected {
+            used(action, &mut objects);
+        }
+        assert!(!objects.contains(&buffer(0)));
+
+        // The predicate never sees a trace using a buffer it doesn't create.
+        minimize(actions, |candidate| {
+            let mut available = HashSet::new();
+            for action in candidate {
+                let mut objects = Vec::new();
+                used(action, &mut objects);
+                assert!(objects.iter().all(|object| available.contains(object)));
+                available.extend(created(action));
+            }
+            candidate.len() > 2
+        });
+    }
+}
@@ -0,0 +1,751 @@
+//! Statistics about the contents of a trace.
+//!
+//! Everything is computed from the actions alone, without replaying them,
+//! so this works on machines without a GPU. Two traces of the same
+//! application can be compared with [`Diff`].
+
+use wgc::{
+    command::{ComputeCommand, RenderCommand},
+    device::trace,
+    id,
+};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+/// Counters of a single frame, ended by presenting or discarding a
+/// surface texture.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Frame {
+    pub submissions: usize,
+    pub render_passes: usize,
+    pub compute_passes: usize,
+    /// Draw calls, including the ones of executed render bundles.
+    pub draws: usize,
+    /// Dispatches, including the ones of executed compute bundles.
+    pub dispatches: usize,
+    /// Bytes written to buffers and textures by the host.
+    pub bytes_uploaded: u64,
+    /// Objects alive at the end of the frame.
+    pub objects_alive: usize,
+}
+
+/// A shader module of the trace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shader {
+    pub label: Option<String>,
+    /// Name of the data file holding the source.
+    pub data: String,
+    /// Number of pipelines created with the module.
+    pub pipelines: usize,
+}
+
+impl Shader {
+    /// The name of the shader, for display and comparison.
+    pub fn name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.data)
+    }
+}
+
+/// Statistics of a trace.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    /// Number of actions of each kind.
+    pub actions: BTreeMap<&'static str, usize>,
+    /// Number of encoder commands of each kind.
+    pub commands: BTreeMap<&'static str, usize>,
+    /// Number of render pass commands of each kind.
+    pub render_commands: BTreeMap<&'static str, usize>,
+    /// Number of compute pass commands of each kind.
+    pub compute_commands: BTreeMap<&'static str, usize>,
+    /// Bytes written to buffers.
+    pub buffer_bytes_uploaded: u64,
+    /// Bytes written to textures.
+    pub texture_bytes_uploaded: u64,
+    /// Highest number of objects of each kind alive at the same time.
+    pub peak_alive: BTreeMap<&'static str, usize>,
+    /// Number of objects of each kind alive at the end of the trace.
+    pub final_alive: BTreeMap<&'static str, usize>,
+    pub frames: Vec<Frame>,
+    pub shaders: Vec<Shader>,
+}
+
+fn action_name(action: &trace::Action) -> &'static str {
+    use trace::Action as A;
+    match *action {
+        A::Init { .. } => "Init",
+        A::ConfigureSurface(..) => "ConfigureSurface",
+        A::CreateBuffer(..) => "CreateBuffer",
+        A::FreeBuffer(..) => "FreeBuffer",
+        A::DestroyBuffer(..) => "DestroyBuffer",
+        A::CreateTexture(..) => "CreateTexture",
+        A::FreeTexture(..) => "FreeTexture",
+        A::DestroyTexture(..) => "DestroyTexture",
+        A::CreateTextureView { .. } => "CreateTextureView",
+        A::DestroyTextureView(..) => "DestroyTextureView",
+        A::CreateSampler(..) => "CreateSampler",
+        A::DestroySampler(..) => "DestroySampler",
+        A::CreateExternalTexture { .. } => "CreateExternalTexture",
+        A::DestroyExternalTexture(..) => "DestroyExternalTexture",
+        A::GetSurfaceTexture { .. } => "GetSurfaceTexture",
+        A::Present(..) => "Present",
+        A::DiscardSurfaceTexture(..) => "DiscardSurfaceTexture",
+        A::CreateBindGroupLayout(..) => "CreateBindGroupLayout",
+        A::DestroyBindGroupLayout(..) => "DestroyBindGroupLayout",
+        A::CreatePipelineLayout(..) => "CreatePipelineLayout",
+        A::DestroyPipelineLayout(..) => "DestroyPipelineLayout",
+        A::CreateBindGroup(..) => "CreateBindGroup",
+        A::DestroyBindGroup(..) => "DestroyBindGroup",
+        A::CreateShaderModule { .. } => "CreateShaderModule",
+        A::DestroyShaderModule(..) => "DestroyShaderModule",
+        A::CreateComputePipeline { .. } => "CreateComputePipeline",
+        A::DestroyComputePipeline(..) => "DestroyComputePipeline",
+        A::CreateRenderPipeline { .. } => "CreateRenderPipeline",
+        A::DestroyRenderPipeline(..) => "DestroyRenderPipeline",
+        A::CreateRenderBundle { .. } => "CreateRenderBundle",
+        A::DestroyRenderBundle(..) => "DestroyRenderBundle",
+        A::CreateComputeBundle { .. } => "CreateComputeBundle",
+        A::DestroyComputeBundle(..) => "DestroyComputeBundle",
+        A::CreateQuerySet { .. } => "CreateQuerySet",
+        A::DestroyQuerySet(..) => "DestroyQuerySet",
+        A::WriteBuffer { .. } => "WriteBuffer",
+        A::UnmapBuffer { .. } => "UnmapBuffer",
+        A::WriteTexture { .. } => "WriteTexture",
+        A::Submit(..) => "Submit",
+    }
+}
+
+fn command_name(command: &trace::Command) -> &'static str {
+    use trace::Command as C;
+    match *command {
+        C::CopyBufferToBuffer { .. } => "CopyBufferToBuffer",
+        C::CopyBufferToTexture { .. } => "CopyBufferToTexture",
+        C::CopyTextureToBuffer { .. } => "CopyTextureToBuffer",
+        C::CopyTextureToTexture { .. } => "CopyTextureToTexture",
+        C::ClearBuffer { .. } => "ClearBuffer",
+        C::ClearTexture { .. } => "ClearTexture",
+        C::WriteTimestamp { .. } => "WriteTimestamp",
+        C::ResolveQuerySet { .. } => "ResolveQuerySet",
+        C::PushDebugGroup(..) => "PushDebugGroup",
+        C::PopDebugGroup => "PopDebugGroup",
+        C::InsertDebugMarker(..) => "InsertDebugMarker",
+        C::RunComputePass { .. } => "RunComputePass",
+        C::RunRenderPass { .. } => "RunRenderPass",
+    }
+}
+
+fn render_command_name(command: &RenderCommand) -> &'static str {
+    use RenderCommand as C;
+    match *command {
+        C::SetBindGroup { .. } => "SetBindGroup",
+        C::SetPipeline(..) => "SetPipeline",
+        C::SetIndexBuffer { .. } => "SetIndexBuffer",
+        C::SetVertexBuffer { .. } => "SetVertexBuffer",
+        C::SetBlendConstant(..) => "SetBlendConstant",
+        C::SetStencilReference(..) => "SetStencilReference",
+        C::SetViewport { .. } => "SetViewport",
+        C::SetScissor(..) => "SetScissor",
+        C::SetPushConstant { .. } => "SetPushConstant",
+        C::Draw { .. } => "Draw",
+        C::DrawIndexed { .. } => "DrawIndexed",
+        C::MultiDrawIndirect { .. } => "MultiDrawIndirect",
+        C::MultiDrawIndirectCount { .. } => "MultiDrawIndirectCount",
+        C::PushDebugGroup { .. } => "PushDebugGroup",
+        C::PopDebugGroup => "PopDebugGroup",
+        C::InsertDebugMarker { .. } => "InsertDebugMarker",
+        C::WriteTimestamp { .. } => "WriteTimestamp",
+        C::BeginOcclusionQuery { .. } => "BeginOcclusionQuery",
+        C::EndOcclusionQuery => "EndOcclusionQuery",
+        C::BeginPipelineStatisticsQuery { .. } => "BeginPipelineStatisticsQuery",
+        C::EndPipelineStatisticsQuery => "EndPipelineStatisticsQuery",
+        C::ExecuteBundle(..) => "ExecuteBundle",
+    }
+}
+
+fn compute_command_name(command: &ComputeCommand) -> &'static str {
+    use ComputeCommand as C;
+    match *command {
+        C::SetBindGroup { .. } => "SetBindGroup",
+        C::SetPipeline(..) => "SetPipeline",
+        C::SetPushConstant { .. } => "SetPushConstant",
+        C::Dispatch(..) => "Dispatch",
+        C::DispatchIndirect { .. } => "DispatchIndirect",
+        C::PushDebugGroup { .. } => "PushDebugGroup",
+        C::PopDebugGroup => "PopDebugGroup",
+        C::InsertDebugMarker { .. } => "InsertDebugMarker",
+        C::WriteTimestamp { .. } => "WriteTimestamp",
+        C::BeginPipelineStatisticsQuery { .. } => "BeginPipelineStatisticsQuery",
+        C::EndPipelineStatisticsQuery => "EndPipelineStatisticsQuery",
+        C::ExecuteBundle(..) => "ExecuteBundle",
+    }
+}
+
+/// The kinds of the objects created by `action`, one per object.
+fn created(action: &trace::Action) -> Vec<&'static str> {
+    use trace::Action as A;
+    let kind = match *action {
+        A::CreateBuffer(..) => "Buffer",
+        A::CreateTexture(..) | A::GetSurfaceTexture { .. } => "Texture",
+        A::CreateTextureView { .. } => "TextureView",
+        A::CreateSampler(..) => "Sampler",
+        A::CreateExternalTexture { .. } => "ExternalTexture",
+        A::CreateBindGroupLayout(..) => "BindGroupLayout",
+        A::CreatePipelineLayout(..) => "PipelineLayout",
+        A::CreateBindGroup(..) => "BindGroup",
+        A::CreateShaderModule { .. } => "ShaderModule",
+        A::CreateComputePipeline { .. } => "ComputePipeline",
+        A::CreateRenderPipeline { .. } => "RenderPipeline",
+        A::CreateRenderBundle { .. } => "RenderBundle",
+        A::CreateComputeBundle { .. } => "ComputeBundle",
+        A::CreateQuerySet { .. } => "QuerySet",
+        _ => return Vec::new(),
+    };
+    let mut kinds = vec![kind];
+    // Pipelines with a derived layout also create the layout and its
+    // bind group layouts.
+    match *action {
+        A::CreateComputePipeline {
+            implicit_context: Some(ref context),
+            ..
+        }
+        | A::CreateRenderPipeline {
+            implicit_context: Some(ref context),
+            ..
+        } => {
+            kinds.push("PipelineLayout");
+            kinds.extend(context.group_ids.iter().map(|_| "BindGroupLayout"));
+        }
+        _ => {}
+    }
+    kinds
+}
+
+/// The kind of object dropped by `action`.
+fn dropped(action: &trace::Action) -> Option<&'static str> {
+    use trace::Action as A;
+    Some(match *action {
+        A::DestroyBuffer(..) => "Buffer",
+        A::DestroyTexture(..) => "Texture",
+        A::DestroyTextureView(..) => "TextureView",
+        A::DestroySampler(..) => "Sampler",
+        A::DestroyExternalTexture(..) => "ExternalTexture",
+        A::DestroyBindGroupLayout(..) => "BindGroupLayout",
+        A::DestroyPipelineLayout(..) => "PipelineLayout",
+        A::DestroyBindGroup(..) => "BindGroup",
+        A::DestroyShaderModule(..) => "ShaderModule",
+        A::DestroyComputePipeline(..) => "ComputePipeline",
+        A::DestroyRenderPipeline(..) => "RenderPipeline",
+        A::DestroyRenderBundle(..) => "RenderBundle",
+        A::DestroyComputeBundle(..) => "ComputeBundle",
+        A::DestroyQuerySet(..) => "QuerySet",
+        _ => return None,
+    })
+}
+
+fn increment(map: &mut BTreeMap<&'static str, usize>, key: &'static str) {
+    *map.entry(key).or_insert(0) += 1;
+}
+
+/// The number of bytes that a texture write takes from its data.
+fn texture_write_size(layout: &wgt::ImageDataLayout, size: &wgt::Extent3d) -> u64 {
+    match (layout.bytes_per_row, layout.rows_per_image) {
+        (Some(bytes_per_row), rows_per_image) => {
+            let rows = rows_per_image.unwrap_or(size.height) as u64;
+            bytes_per_row as u64 * rows * size.depth_or_array_layers.max(1) as u64
+        }
+        // A single row of blocks, whose size isn't known without the format.
+        (None, _) => 0,
+    }
+}
+
+impl Stats {
+    /// Compute the statistics of `actions`.
+    pub fn new(actions: &[trace::Action]) -> Self {
+        let mut stats = Self::default();
+        let mut alive = BTreeMap::new();
+        let mut frame = Frame::default();
+        // Draws and dispatches of the bundles.
+        let mut render_bundles = HashMap::<id::RenderBundleId, usize>::new();
+        let mut compute_bundles = HashMap::<id::ComputeBundleId, usize>::new();
+        let mut shaders = HashMap::<id::ShaderModuleId, usize>::new();
+
+        for action in actions {
+            increment(&mut stats.actions, action_name(action));
+
+            for kind in created(action) {
+                let count_alive = alive.entry(kind).or_insert(0);
+                *count_alive += 1;
+                let peak = stats.peak_alive.entry(kind).or_insert(0);
+                *peak = (*peak).max(*count_alive);
+            }
+            if let Some(kind) = dropped(action) {
+                if let Some(count) = alive.get_mut(kind) {
+                    *count = count.saturating_sub(1);
+                }
+            }
+
+            use trace::Action as A;
+            match *action {
+                A::CreateShaderModule {
+                    id,
+                    ref desc,
+                    ref data,
+                } => {
+                    shaders.insert(id, stats.shaders.len());
+                    stats.shaders.push(Shader {
+                        label: desc.label.as_ref().map(|label| label.to_string()),
+                        data: data.clone(),
+                        pipelines: 0,
+                    });
+                }
+                A::CreateComputePipeline { ref desc, .. } => {
+                    if let Some(&index) = shaders.get(&desc.stage.module) {
+                        stats.shaders[index].pipelines += 1;
+                    }
+                }
+                A::CreateRenderPipeline { ref desc, .. } => {
+                    let vertex = desc.vertex.stage.module;
+                    let fragment = desc
+                        .fragment
+                        .as_ref()
+                        .map(|fragment| fragment.stage.module)
+                        .filter(|&module| module != vertex);
+                    for module in Some(vertex).into_iter().chain(fragment) {
+                        if let Some(&index) = shaders.get(&module) {
+                            stats.shaders[index].pipelines += 1;
+                        }
+                    }
+                }
+                A::CreateRenderBundle { id, ref base, .. } => {
+                    let draws = base
+                        .commands
+                        .iter()
+                        .filter(|command| is_draw(command))
+                        .count();
+                    render_bundles.insert(id, draws);
+                }
+                A::CreateComputeBundle { id, ref base, .. } => {
+                    let dispatches = base
+                        .commands
+                        .iter()
+                        .filter(|command| is_dispatch(command))
+                        .count();
+                    compute_bundles.insert(id, dispatches);
+                }
+                A::WriteBuffer { ref range, .. } | A::UnmapBuffer { ref range, .. } => {
+                    let size = range.end - range.start;
+                    stats.buffer_bytes_uploaded += size;
+                    frame.bytes_uploaded += size;
+                }
+                A::WriteTexture {
+                    ref layout,
+                    ref size,
+                    ..
+                } => {
+                    let size = texture_write_size(layout, size);
+                    stats.texture_bytes_uploaded += size;
+                    frame.bytes_uploaded += size;
+                }
+                A::Submit(_, ref commands) => {
+                    frame.submissions += 1;
+                    for command in commands {
+                        increment(&mut stats.commands, command_name(command));
+                        match *command {
+                            trace::Command::RunRenderPass { ref base, .. } => {
+                                frame.render_passes += 1;
+                                for command in base.commands.iter() {
+                                    increment(
+                                        &mut stats.render_commands,
+                                        render_command_name(command),
+                                    );
+                                    if is_draw(command) {
+                                        frame.draws += 1;
+                                    } else if let RenderCommand::ExecuteBundle(id) = *command {
+                                        frame.draws +=
+                                            render_bundles.get(&id).copied().unwrap_or(0);
+                                    }
+                                }
+                            }
+                            trace::Command::RunComputePass { ref base, .. } => {
+                                frame.compute_passes += 1;
+                                for command in base.commands.iter() {
+                                    increment(
+                                        &mut stats.compute_commands,
+                                        compute_command_name(command),
+                                    );
+                                    if is_dispatch(command) {
+                                        frame.dispatches += 1;
+                                    } else if let ComputeCommand::ExecuteBundle(id) = *command {
+                                        frame.dispatches +=
+                                            compute_bundles.get(&id).copied().unwrap_or(0);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                A::Present(_) | A::DiscardSurfaceTexture(_) => {
+                    // Surface textures only live for a frame.
+                    if let Some(count) = alive.get_mut("Texture") {
+                        *count = count.saturating_sub(1);
+                    }
+                    frame.objects_alive = alive.values().sum();
+                    stats.frames.push(std::mem::take(&mut frame));
+                }
+                _ => {}
+            }
+        }
+
+        if frame != Frame::default() {
+            frame.objects_alive = alive.values().sum();
+            stats.frames.push(frame);
+        }
+        stats.final_alive = alive;
+        stats
+    }
+
+    /// The sum of `field` over all the frames.
+    fn total(&self, field: impl Fn(&Frame) -> usize) -> usize {
+        self.frames.iter().map(field).sum()
+    }
+}
+
+fn is_draw(command: &RenderCommand) -> bool {
+    matches!(
+        *command,
+        RenderCommand::Draw { .. }
+            | RenderCommand::DrawIndexed { .. }
+            | RenderCommand::MultiDrawIndirect { .. }
+            | RenderCommand::MultiDrawIndirectCount { .. }
+    )
+}
+
+fn is_dispatch(command: &ComputeCommand) -> bool {
+    matches!(
+        *command,
+        ComputeCommand::Dispatch(..) | ComputeCommand::DispatchIndirect { .. }
+    )
+}
+
+fn write_counts(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    counts: &BTreeMap<&'static str, usize>,
+) -> fmt::Result {
+    if counts.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "{title}:")?;
+    for (name, count) in counts {
+        writeln!(f, "  {name:<32} {count:>10}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_counts(f, "Actions", &self.actions)?;
+        write_counts(f, "Commands", &self.commands)?;
+        write_counts(f, "Render pass commands", &self.render_commands)?;
+        write_counts(f, "Compute pass commands", &self.compute_commands)?;
+
+        writeln!(f, "Bytes uploaded:")?;
+        writeln!(f, "  {:<32} {:>10}", "Buffers", self.buffer_bytes_uploaded)?;
+        writeln!(
+            f,
+            "  {:<32} {:>10}",
+            "Textures", self.texture_bytes_uploaded
+        )?;
+
+        writeln!(f, "Objects alive (peak, at the end):")?;
+        for (kind, peak) in &self.peak_alive {
+            let end = self.final_alive.get(kind).copied().unwrap_or(0);
+            writeln!(f, "  {kind:<32} {peak:>10} {end:>10}")?;
+        }
+
+        writeln!(f, "Frames: {}", self.frames.len())?;
+        if !self.frames.is_empty() {
+            writeln!(
+                f,
+                "  {:>6} {:>8} {:>8} {:>8} {:>8} {:>10} {:>12} {:>8}",
+                "frame", "submits", "render", "compute", "draws", "dispatches", "uploaded", "alive"
+            )?;
+            for (index, frame) in self.frames.iter().enumerate() {
+                writeln!(
+                    f,
+                    "  {:>6} {:>8} {:>8} {:>8} {:>8} {:>10} {:>12} {:>8}",
+                    index + 1,
+                    frame.submissions,
+                    frame.render_passes,
+                    frame.compute_passes,
+                    frame.draws,
+                    frame.dispatches,
+                    frame.bytes_uploaded,
+                    frame.objects_alive
+                )?;
+            }
+        }
+
+        writeln!(f, "Shader modules: {}", self.shaders.len())?;
+        for shader in &self.shaders {
+            writeln!(
+                f,
+                "  {:<32} {:>10} pipelines",
+                shader.name(),
+                shader.pipelines
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The differences between the statistics of two traces.
+pub struct Diff<'a> {
+    pub before: &'a Stats,
+    pub after: &'a Stats,
+}
+
+fn write_count_diff(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    before: &BTreeMap<&'static str, usize>,
+    after: &BTreeMap<&'static str, usize>,
+) -> fmt::Result {
+    let mut names = before.keys().chain(after.keys()).collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    let mut header = false;
+    for name in names {
+        let old = before.get(name).copied().unwrap_or(0);
+        let new = after.get(name).copied().unwrap_or(0);
+        if old != new {
+            if !header {
+                writeln!(f, "{title}:")?;
+                header = true;
+            }
+            write_change(f, name, old as u64, new as u64)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_change(f: &mut fmt::Formatter<'_>, name: &str, old: u64, new: u64) -> fmt::Result {
+    let delta = new as i128 - old as i128;
+    writeln!(f, "  {name:<32} {old:>10} -> {new:>10} ({delta:+})")
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (before, after) = (self.before, self.after);
+        write_count_diff(f, "Actions", &before.actions, &after.actions)?;
+        write_count_diff(f, "Commands", &before.commands, &after.commands)?;
+        write_count_diff(
+            f,
+            "Render pass commands",
+            &before.render_commands,
+            &after.render_commands,
+        )?;
+        write_count_diff(
+            f,
+            "Compute pass commands",
+            &before.compute_commands,
+            &after.compute_commands,
+        )?;
+        write_count_diff(
+            f,
+            "Peak objects alive",
+            &before.peak_alive,
+            &after.peak_alive,
+        )?;
+
+        let totals: [(&str, u64, u64); 8] = [
+            (
+                "Frames",
+                before.frames.len() as u64,
+                after.frames.len() as u64,
+            ),
+            (
+                "Submissions",
+                before.total(|frame| frame.submissions) as u64,
+                after.total(|frame| frame.submissions) as u64,
+            ),
+            (
+                "Render passes",
+                before.total(|frame| frame.render_passes) as u64,
+                after.total(|frame| frame.render_passes) as u64,
+            ),
+            (
+                "Compute passes",
+                before.total(|frame| frame.compute_passes) as u64,
+                after.total(|frame| frame.compute_passes) as u64,
+            ),
+            (
+                "Draws",
+                before.total(|frame| frame.draws) as u64,
+                after.total(|frame| frame.draws) as u64,
+            ),
+            (
+                "Dispatches",
+                before.total(|frame| frame.dispatches) as u64,
+                after.total(|frame| frame.dispatches) as u64,
+            ),
+            (
+                "Buffer bytes uploaded",
+                before.buffer_bytes_uploaded,
+                after.buffer_bytes_uploaded,
+            ),
+            (
+                "Texture bytes uploaded",
+                before.texture_bytes_uploaded,
+                after.texture_bytes_uploaded,
+            ),
+        ];
+        if totals.iter().any(|&(_, old, new)| old != new) {
+            writeln!(f, "Totals:")?;
+            for (name, old, new) in totals {
+                if old != new {
+                    write_change(f, name, old, new)?;
+                }
+            }
+        }
+
+        // Frames are compared pairwise, as long as both traces have them.
+        let mut header = false;
+        for (index, (old, new)) in before.frames.iter().zip(&after.frames).enumerate() {
+            let old = (
+                old.render_passes,
+                old.compute_passes,
+                old.draws,
+                old.dispatches,
+            );
+            let new = (
+                new.render_passes,
+                new.compute_passes,
+                new.draws,
+                new.dispatches,
+            );
+            if old != new {
+                if !header {
+                    writeln!(
+                        f,
+                        "Frames with different work (render passes, compute passes, draws, dispatches):"
+                    )?;
+                    header = true;
+                }
+                writeln!(f, "  {:>6} {:?} -> {:?}", index + 1, old, new)?;
+            }
+        }
+
+        let names = |stats: &Stats| {
+            let mut names = stats
+                .shaders
+                .iter()
+                .map(|shader| shader.name().to_string())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        let (old, new) = (names(before), names(after));
+        let removed = old.iter().filter(|name| !new.contains(name));
+        let added = new.iter().filter(|name| !old.contains(name));
+        for (sign, name) in removed.map(|n| ('-', n)).chain(added.map(|n| ('+', n))) {
+            writeln!(f, "Shader module {sign} {name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A compute pipeline with a derived layout, used in the first frame.
+    const TRACE: &str = "[
+        Init(desc: (label: None, features: 0, limits: ()), backend: Empty),
+        CreateShaderModule(id: Id(0, 1, Empty), desc: (label: Some(\"compute\")), data: \"compute.wgsl\"),
+        CreateComputePipeline(
+            id: Id(0, 1, Empty),
+            desc: (label: None, layout: None, stage: (module: Id(0, 1, Empty), entry_point: \"main\")),
+            implicit_context: Some((root_id: Id(0, 1, Empty), group_ids: [Id(0, 1, Empty), Id(1, 1, Empty)])),
+        ),
+        CreateBuffer(Id(0, 1, Empty), (label: None, size: 16, usage: 136, mapped_at_creation: false)),
+        WriteBuffer(id: Id(0, 1, Empty), data: \"data.bin\", range: (start: 0, end: 16), queued: true),
+        Submit(1, [
+            RunComputePass(
+                base: (
+                    label: None,
+                    commands: [SetPipeline(Id(0, 1, Empty)), Dispatch((1, 1, 1))],
+                    dynamic_offsets: [],
+                    string_data: [],
+                    push_constant_data: [],
+                ),
+                timestamp_writes: None,
+            ),
+        ]),
+        Present(Id(0, 1, Empty)),
+        DestroyBuffer(Id(0, 1, Empty)),
+    ]";
+
+    fn trace() -> Vec<trace::Action<'static>> {
+        ron::de::from_str(TRACE).unwrap()
+    }
+
+    #[test]
+    fn counts() {
+        let stats = Stats::new(&trace());
+        assert_eq!(stats.actions["CreateBuffer"], 1);
+        assert_eq!(stats.commands["RunComputePass"], 1);
+        assert_eq!(stats.compute_commands["Dispatch"], 1);
+        assert_eq!(stats.buffer_bytes_uploaded, 16);
+
+        // The derived layout is counted with the other layouts.
+        assert_eq!(stats.peak_alive["ComputePipeline"], 1);
+        assert_eq!(stats.peak_alive["PipelineLayout"], 1);
+        assert_eq!(stats.peak_alive["BindGroupLayout"], 2);
+        assert_eq!(stats.peak_alive["Buffer"], 1);
+        assert_eq!(stats.final_alive["Buffer"], 0);
+
+        assert_eq!(
+            stats.frames,
+            [Frame {
+                submissions: 1,
+                render_passes: 0,
+                compute_passes: 1,
+                draws: 0,
+                dispatches: 1,
+                bytes_uploaded: 16,
+                objects_alive: 6,
+            }]
+        );
+        assert_eq!(stats.shaders.len(), 1);
+        assert_eq!(stats.shaders[0].name(), "compute");
+        assert_eq!(stats.shaders[0].pipelines, 1);
+    }
+
+    #[test]
+    fn diff() {
+        let before = Stats::new(&trace());
+        let same = Diff {
+            before: &before,
+            after: &before,
+        };
+        assert_eq!(same.to_string(), "");
+
+        let mut actions = trace();
+        let write = actions[4].clone();
+        actions.insert(4, write);
+        let after = Stats::new(&actions);
+        let diff = Diff {
+            before: &before,
+            after: &after,
+        }
+        .to_string();
+        assert!(diff.contains("Actions:\n  WriteBuffer "), "{diff}");
+        assert!(diff.contains("Totals:\n  Buffer bytes uploaded "), "{diff}");
+        assert!(diff.contains("(+16)"), "{diff}");
+        assert!(!diff.contains("Frames"), "{diff}");
+    }
+}
@@ -0,0 +1,42 @@
+//! Generated from a wgpu trace by `play codegen`.
+#![allow(unused, clippy::all)]
+
+fn main() {
+    env_logger::init();
+    pollster::block_on(run());
+}
+
+async fn run() {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: wgpu::util::backend_bits_from_env().unwrap_or(wgpu::Backends::all()),
+        ..Default::default()
+    });
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions::default())
+        .await
+        .expect("Unable to find an adapter");
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                features: wgpu::Features::MAPPABLE_PRIMARY_BUFFERS | wgpu::Features::CLEAR_TEXTURE,
+                limits: wgpu::Limits { max_texture_dimension_1d: 8192, max_texture_dimension_2d: 8192, max_texture_dimension_3d: 2048, max_texture_array_layers: 256, max_bind_groups: 4, max_bindings_per_bind_group: 1000, max_dynamic_uniform_buffers_per_pipeline_layout: 8, max_dynamic_storage_buffers_per_pipeline_layout: 4, max_sampled_textures_per_shader_stage: 16, max_samplers_per_shader_stage: 16, max_storage_buffers_per_shader_stage: 8, max_storage_textures_per_shader_stage: 4, max_uniform_buffers_per_shader_stage: 12, max_uniform_buffer_binding_size: 65536, max_storage_buffer_binding_size: 134217728, max_vertex_buffers: 8, max_buffer_size: 268435456, max_vertex_attributes: 16, max_vertex_buffer_array_stride: 2048, min_uniform_buffer_offset_alignment: 256, min_storage_buffer_offset_alignment: 256, max_inter_stage_shader_components: 60, max_compute_workgroup_storage_size: 16384, max_compute_invocations_per_workgroup: 256, max_compute_workgroup_size_x: 256, max_compute_workgroup_size_y: 256, max_compute_workgroup_size_z: 64, max_compute_workgroups_per_dimension: 65535, max_push_constant_size: 0, max_non_sampler_bindings: 1000000 },
+            },
+            None,
+        )
+        .await
+        .expect("Unable to create the device");
+    let texture_0_1 = device.create_texture(&wgpu::TextureDescriptor { label: Some("Output Texture"), size: wgpu::Extent3d { width: 64, height: 64, depth_or_array_layers: 1 }, mip_level_count: 1, sample_count: 1, dimension: wgpu::TextureDimension::D2, format: wgpu::TextureFormat::Rgba8Unorm, usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT, view_formats: &[] });
+    queue.write_texture(wgpu::ImageCopyTexture { texture: &texture_0_1, mip_level: 0, origin: wgpu::Origin3d { x: 0, y: 0, z: 0 }, aspect: wgpu::TextureAspect::All }, include_bytes!("../data/quad.bin"), wgpu::ImageDataLayout { offset: 0, bytes_per_row: Some(256), rows_per_image: None }, wgpu::Extent3d { width: 64, height: 64, depth_or_array_layers: 1 });
+    let buffer_0_1 = device.create_buffer(&wgpu::BufferDescriptor { label: Some("Output Buffer"), size: 16384, usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false });
+    let buffer_1_1 = device.create_buffer(&wgpu::BufferDescriptor { label: Some("Buffer to be cleared"), size: 16, usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::VERTEX, mapped_at_creation: false });
+    queue.write_buffer(&buffer_1_1, 0, include_bytes!("../data/data1.bin"));
+    {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.clear_texture(&texture_0_1, &wgpu::ImageSubresourceRange { aspect: wgpu::TextureAspect::All, base_mip_level: 0, mip_level_count: None, base_array_layer: 0, array_layer_count: None });
+        encoder.copy_texture_to_buffer(wgpu::ImageCopyTexture { texture: &texture_0_1, mip_level: 0, origin: wgpu::Origin3d { x: 0, y: 0, z: 0 }, aspect: wgpu::TextureAspect::All }, wgpu::ImageCopyBuffer { buffer: &buffer_0_1, layout: wgpu::ImageDataLayout { offset: 0, bytes_per_row: Some(256), rows_per_image: None } }, wgpu::Extent3d { width: 64, height: 64, depth_or_array_layers: 1 });
+        encoder.clear_buffer(&buffer_1_1, 4, Some(wgpu::BufferSize::new(8).unwrap()));
+        queue.submit(Some(encoder.finish()));
+    }
+    device.poll(wgpu::Maintain::Wait);
+}
@@ -0,0 +1,47 @@
+//! Generated from a wgpu trace by `play codegen`.
+#![allow(unused, clippy::all)]
+
+fn main() {
+    env_logger::init();
+    pollster::block_on(run());
+}
+
+async fn run() {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: wgpu::util::backend_bits_from_env().unwrap_or(wgpu::Backends::all()),
+        ..Default::default()
+    });
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions::default())
+        .await
+        .expect("Unable to find an adapter");
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                features: wgpu::Features::PIPELINE_STATISTICS_QUERY | wgpu::Features::MAPPABLE_PRIMARY_BUFFERS,
+                limits: wgpu::Limits { max_texture_dimension_1d: 8192, max_texture_dimension_2d: 8192, max_texture_dimension_3d: 2048, max_texture_array_layers: 256, max_bind_groups: 4, max_bindings_per_bind_group: 1000, max_dynamic_uniform_buffers_per_pipeline_layout: 8, max_dynamic_storage_buffers_per_pipeline_layout: 4, max_sampled_textures_per_shader_stage: 16, max_samplers_per_shader_stage: 16, max_storage_buffers_per_shader_stage: 8, max_storage_textures_per_shader_stage: 4, max_uniform_buffers_per_shader_stage: 12, max_uniform_buffer_binding_size: 65536, max_storage_buffer_binding_size: 134217728, max_vertex_buffers: 8, max_buffer_size: 268435456, max_vertex_attributes: 16, max_vertex_buffer_array_stride: 2048, min_uniform_buffer_offset_alignment: 256, min_storage_buffer_offset_alignment: 256, max_inter_stage_shader_components: 60, max_compute_workgroup_storage_size: 16384, max_compute_invocations_per_workgroup: 256, max_compute_workgroup_size_x: 256, max_compute_workgroup_size_y: 256, max_compute_workgroup_size_z: 64, max_compute_workgroups_per_dimension: 65535, max_push_constant_size: 0, max_non_sampler_bindings: 1000000 },
+            },
+            None,
+        )
+        .await
+        .expect("Unable to create the device");
+    let pipeline_layout_0_1 = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor { label: Some("empty"), bind_group_layouts: &[], push_constant_ranges: &[] });
+    let shader_0_1 = device.create_shader_module(wgpu::ShaderModuleDescriptor { label: None, source: wgpu::ShaderSource::Wgsl(include_str!("../data/empty.wgsl").into()) });
+    let compute_pipeline_0_1 = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor { label: None, layout: Some(&pipeline_layout_0_1), module: &shader_0_1, entry_point: "main" });
+    let query_set_0_1 = device.create_query_set(&wgpu::QuerySetDescriptor { label: Some("Compute Invocation QuerySet"), ty: wgpu::QueryType::PipelineStatistics(wgpu::PipelineStatisticsTypes::FRAGMENT_SHADER_INVOCATIONS | wgpu::PipelineStatisticsTypes::COMPUTE_SHADER_INVOCATIONS), count: 2 });
+    let buffer_0_1 = device.create_buffer(&wgpu::BufferDescriptor { label: Some("Compute Invocation Result Buffer"), size: 16, usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::QUERY_RESOLVE, mapped_at_creation: false });
+    {
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: None, timestamp_writes: None });
+            pass.set_pipeline(&compute_pipeline_0_1);
+            pass.begin_pipeline_statistics_query(&query_set_0_1, 0);
+            pass.dispatch_workgroups(2, 3, 7);
+            pass.end_pipeline_statistics_query();
+        }
+        encoder.resolve_query_set(&query_set_0_1, 0..1, &buffer_0_1, 0);
+        queue.submit(Some(encoder.finish()));
+    }
+    device.poll(wgpu::Maintain::Wait);
+}
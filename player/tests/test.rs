@@ -100,9 +100,10 @@ impl Test<'_> {
         }
 
         let mut command_buffer_id_manager = wgc::identity::IdentityManager::default();
+        let data_source = wgc::device::trace::DataSource::directory(dir);
         println!("\t\t\tRunning...");
         for action in self.actions {
-            wgc::gfx_select!(device => global.process(device, action, dir, &mut command_buffer_id_manager));
+            wgc::gfx_select!(device => global.process(device, action, &data_source, &mut command_buffer_id_manager));
         }
         println!("\t\t\tMapping...");
         for expect in &self.expectations {
@@ -187,6 +188,7 @@ impl Corpus {
                 flags: wgt::InstanceFlags::debugging(),
                 dx12_shader_compiler: wgt::Dx12Compiler::Fxc,
                 gles_minor_version: wgt::Gles3MinorVersion::default(),
+                pipeline_worker_threads: None,
             },
         );
         for &backend in BACKENDS {
@@ -245,3 +247,113 @@ fn test_api() {
 
     Corpus::run_from(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/all.ron"))
 }
+
+#[test]
+fn test_frame_dump() {
+    let global = wgc::global::Global::new(
+        "test",
+        IdentityPassThroughFactory,
+        wgt::InstanceDescriptor::default(),
+    );
+    let backend = BACKENDS.iter().copied().find(|&backend| {
+        global
+            .request_adapter(
+                &wgc::instance::RequestAdapterOptions {
+                    power_preference: wgt::PowerPreference::None,
+                    force_fallback_adapter: false,
+                    compatible_surface: None,
+                },
+                wgc::instance::AdapterInputs::IdSet(
+                    &[wgc::id::TypedId::zip(0, 0, backend)],
+                    |id| id.backend(),
+                ),
+            )
+            .is_ok()
+    });
+    let Some(backend) = backend else {
+        println!("Skipped due to missing adapters");
+        return;
+    };
+
+    // The buffer is written before and after the first frame, and stopping
+    // at that frame has to dump the first contents.
+    let dir = std::env::temp_dir().join(format!("wgpu-player-dump-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let first: Vec<u8> = (0..16).collect();
+    std::fs::write(dir.join("first.bin"), &first).unwrap();
+    std::fs::write(dir.join("second.bin"), [0xFF; 16]).unwrap();
+    // Surfaces don't belong to a backend, and keep the `Empty` one.
+    let trace = format!(
+        "[
+        Init(desc: (label: None, features: 0, limits: ()), backend: {backend:?}),
+        CreateBuffer(
+            Id(0, 1, {backend:?}),
+            (label: None, size: 16, usage: 9, mapped_at_creation: false),
+        ),
+        WriteBuffer(id: Id(0, 1, {backend:?}), data: \"first.bin\", range: (start: 0, end: 16), queued: true),
+        Submit(1, []),
+        Present(Id(0, 1, Empty)),
+        WriteBuffer(id: Id(0, 1, {backend:?}), data: \"second.bin\", range: (start: 0, end: 16), queued: true),
+        Submit(2, []),
+        Present(Id(0, 1, Empty)),
+    ]"
+    );
+    std::fs::write(dir.join("trace.ron"), trace).unwrap();
+
+    let dump_dir = dir.join("dump");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_play"))
+        .args(["--frame", "1", "--dump-buffer", "0", "--dump-dir"])
+        .arg(&dump_dir)
+        .arg(&dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Stopped at frame 1"));
+    assert_eq!(std::fs::read(dump_dir.join("buffer-0.bin")).unwrap(), first);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_codegen() {
+    // The program generated for every test of the corpus is compared to the
+    // one in `tests/data/codegen`, which is rewritten with the new output.
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let corpus: Corpus = ron::de::from_reader(File::open(dir.join("all.ron")).unwrap()).unwrap();
+    let data_source = wgc::device::trace::DataSource::directory(&dir);
+    let out_dir = std::env::temp_dir().join(format!("wgpu-player-codegen-{}", std::process::id()));
+    let mut mismatches = Vec::new();
+    for test_path in &corpus.tests {
+        let test: Test = ron::de::from_str(&read_to_string(dir.join(test_path)).unwrap()).unwrap();
+        let mut actions = vec![wgc::device::trace::Action::Init {
+            desc: wgt::DeviceDescriptor {
+                label: None,
+                features: test.features,
+                limits: wgt::Limits::default(),
+            },
+            backend: wgt::Backend::Empty,
+        }];
+        actions.extend(test.actions);
+        player::codegen::generate(&actions, &data_source, &out_dir).unwrap();
+
+        let generated = read_to_string(out_dir.join("src/main.rs")).unwrap();
+        let expected_path = dir
+            .join("codegen")
+            .join(Path::new(test_path).with_extension("rs"));
+        let expected = read_to_string(&expected_path).unwrap_or_default();
+        if generated != expected {
+            std::fs::write(&expected_path, generated).unwrap();
+            mismatches.push(expected_path);
+        }
+    }
+    let _ = std::fs::remove_dir_all(&out_dir);
+    assert!(
+        mismatches.is_empty(),
+        "Generated programs changed, review the differences of {mismatches:?}"
+    );
+}
@@ -15,6 +15,7 @@ pub fn initialize_instance() -> Instance {
         flags: wgpu::InstanceFlags::debugging().with_env(),
         dx12_shader_compiler,
         gles_minor_version,
+        pipeline_worker_threads: None,
     })
 }
 
@@ -0,0 +1,156 @@
+use std::num::NonZeroU64;
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestingContext};
+
+const SHADER: &str = r#"
+    @group(0) @binding(0)
+    var<storage, read_write> output: array<u32>;
+
+    @compute @workgroup_size(1)
+    fn main(@builtin(global_invocation_id) id: vec3u) {
+        output[id.x] += 1u;
+    }
+"#;
+
+struct Resources {
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    gpu_buffer: wgpu::Buffer,
+}
+
+fn create_resources(ctx: &TestingContext) -> Resources {
+    let sm = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+    let bgl = ctx
+        .device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(4),
+                },
+                count: None,
+            }],
+        });
+
+    let gpu_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("gpu_buffer"),
+        size: 16,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("bind_group"),
+        layout: &bgl,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: gpu_buffer.as_entire_binding(),
+        }],
+    });
+
+    let pipeline_layout = ctx
+        .device
+        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("pipeline_layout"),
+            bind_group_layouts: &[&bgl],
+            push_constant_ranges: &[],
+        });
+
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &sm,
+            entry_point: "main",
+        });
+
+    Resources {
+        pipeline,
+        bind_group,
+        gpu_buffer,
+    }
+}
+
+/// A compute bundle can be executed several times, in the same pass and
+/// across submissions, and each execution sees the writes of the previous one.
+#[gpu_test]
+static EXECUTE_TWICE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    let res = create_resources(&ctx);
+
+    let mut bundle_encoder =
+        ctx.device
+            .create_compute_bundle_encoder(&wgpu::ComputeBundleEncoderDescriptor {
+                label: Some("bundle_encoder"),
+            });
+    bundle_encoder.set_pipeline(&res.pipeline);
+    bundle_encoder.set_bind_group(0, &res.bind_group, &[]);
+    bundle_encoder.dispatch_workgroups(4, 1, 1);
+    let bundle = bundle_encoder.finish(&wgpu::ComputeBundleDescriptor {
+        label: Some("bundle"),
+    });
+
+    let cpu_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("cpu_buffer"),
+        size: 16,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    for _ in 0..2 {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            cpass.execute_bundles([&bundle, &bundle]);
+        }
+        ctx.queue.submit([encoder.finish()]);
+    }
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_buffer_to_buffer(&res.gpu_buffer, 0, &cpu_buffer, 0, 16);
+    ctx.queue.submit([encoder.finish()]);
+    cpu_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+    ctx.device.poll(wgpu::Maintain::Wait);
+
+    let data = cpu_buffer.slice(..).get_mapped_range();
+    let values: &[u32] = bytemuck::cast_slice(&data);
+    assert_eq!(values, [4, 4, 4, 4]);
+});
+
+/// The pipeline set by a compute bundle does not leak into the pass that
+/// executes it.
+#[gpu_test]
+static STATE_NOT_INHERITED: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    let res = create_resources(&ctx);
+
+    let mut bundle_encoder = ctx
+        .device
+        .create_compute_bundle_encoder(&wgpu::ComputeBundleEncoderDescriptor::default());
+    bundle_encoder.set_pipeline(&res.pipeline);
+    bundle_encoder.set_bind_group(0, &res.bind_group, &[]);
+    bundle_encoder.dispatch_workgroups(1, 1, 1);
+    let bundle = bundle_encoder.finish(&wgpu::ComputeBundleDescriptor::default());
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    fail(&ctx.device, || {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        cpass.execute_bundles([&bundle]);
+        cpass.dispatch_workgroups(1, 1, 1);
+    });
+});
@@ -448,3 +448,150 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
             buffer_for_unmap.unmap();
         });
     });
+
+#[cfg(not(all(target_arch = "wasm32", not(target_os = "emscripten"))))]
+#[gpu_test]
+static MEMORY_REPORT_TRACKS_RESOURCES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let before = ctx.device.memory_report().unwrap();
+
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 1 << 20,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 256,
+                height: 256,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let during = ctx.device.memory_report().unwrap();
+        assert!(during.buffers.allocated_bytes >= before.buffers.allocated_bytes + (1 << 20));
+        assert!(during.textures.allocated_bytes >= before.textures.allocated_bytes + 256 * 256 * 4);
+        assert!(during.peak_allocated_bytes >= during.allocated_bytes());
+        assert!(!during.heaps.is_empty());
+
+        buffer.destroy();
+        texture.destroy();
+        ctx.device.poll(wgpu::Maintain::Wait);
+
+        let after = ctx.device.memory_report().unwrap();
+        assert!(after.allocated_bytes() < during.allocated_bytes());
+        assert!(after.peak_allocated_bytes >= during.allocated_bytes());
+
+        // A lost device has nothing to report, but asking isn't an error.
+        ctx.device.destroy();
+        assert!(ctx.device.memory_report().is_none());
+    });
+
+#[cfg(not(all(target_arch = "wasm32", not(target_os = "emscripten"))))]
+#[gpu_test]
+static LIVE_RESOURCES_REPORT_BIND_GROUP_REFERENCES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("leaked buffer"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+        let bind_group_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: None,
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    }],
+                });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("holder"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+        drop(buffer);
+        ctx.device.poll(wgpu::Maintain::Wait);
+
+        let live = ctx.device.live_resources().unwrap();
+        let leaked = live
+            .iter()
+            .find(|resource| resource.kind == "Buffer" && !resource.user_held)
+            .expect("dropped buffer should still be alive");
+        assert!(leaked.is_held_only_by_references());
+        assert_eq!(leaked.held_by.len(), 1);
+        assert!(live
+            .iter()
+            .any(|resource| resource.kind == "BindGroup" && resource.user_held));
+        let layout = live
+            .iter()
+            .find(|resource| resource.kind == "BindGroupLayout")
+            .expect("bind group layout should be reported");
+        assert_eq!(layout.held_by.len(), 1);
+
+        drop(bind_group);
+        ctx.device.poll(wgpu::Maintain::Wait);
+
+        let live = ctx.device.live_resources().unwrap();
+        assert!(!live
+            .iter()
+            .any(|resource| resource.kind == "Buffer" && !resource.user_held));
+    });
+
+#[cfg(all(
+    feature = "fault-injection",
+    not(all(target_arch = "wasm32", not(target_os = "emscripten")))
+))]
+#[gpu_test]
+static FAULT_INJECTION_FAILS_SELECTED_ALLOCATIONS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        use wgpu::core::device::fault::{
+            FaultInjector, FaultKind, FaultRule, FaultSite, FaultTrigger,
+        };
+
+        ctx.device
+            .set_fault_injector(Some(FaultInjector::new([FaultRule {
+                site: FaultSite::CreateBuffer,
+                trigger: FaultTrigger::Nth(2),
+                kind: FaultKind::OutOfMemory,
+            }])));
+
+        let desc = wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        };
+        ctx.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let _first = ctx.device.create_buffer(&desc);
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_none());
+
+        ctx.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let _second = ctx.device.create_buffer(&desc);
+        let error = pollster::block_on(ctx.device.pop_error_scope());
+        assert!(matches!(error, Some(wgpu::Error::OutOfMemory { .. })));
+
+        ctx.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let _third = ctx.device.create_buffer(&desc);
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_none());
+
+        ctx.device.set_fault_injector(None);
+    });
@@ -11,6 +11,7 @@ mod buffer;
 mod buffer_copy;
 mod buffer_usages;
 mod clear_texture;
+mod compute_bundle;
 mod create_surface_error;
 mod device;
 mod encoder;
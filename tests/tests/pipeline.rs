@@ -33,3 +33,51 @@ static PIPELINE_DEFAULT_LAYOUT_BAD_MODULE: GpuTestConfiguration = GpuTestConfigu
             pipeline.get_bind_group_layout(0);
         });
     });
+
+const TRIVIAL_COMPUTE_SHADER: &str = "@compute @workgroup_size(1) fn main() {}";
+
+#[gpu_test]
+static COMPUTE_PIPELINE_ASYNC: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    let module = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(TRIVIAL_COMPUTE_SHADER.into()),
+        });
+
+    let pipeline = pollster::block_on(ctx.device.create_compute_pipeline_async(
+        &wgpu::ComputePipelineDescriptor {
+            label: Some("async compute pipeline"),
+            layout: None,
+            module: &module,
+            entry_point: "main",
+        },
+    ))
+    .unwrap();
+    pipeline.get_bind_group_layout(0);
+});
+
+// Errors from asynchronous pipeline creation are returned by the future,
+// rather than reported to the error scopes.
+#[gpu_test]
+static COMPUTE_PIPELINE_ASYNC_RETURNS_ERRORS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(TRIVIAL_COMPUTE_SHADER.into()),
+            });
+
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let result = pollster::block_on(ctx.device.create_compute_pipeline_async(
+            &wgpu::ComputePipelineDescriptor {
+                label: Some("async compute pipeline"),
+                layout: None,
+                module: &module,
+                entry_point: "doesn't exist",
+            },
+        ));
+        assert!(matches!(result, Err(wgpu::Error::Validation { .. })));
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_none());
+    });
@@ -2,7 +2,10 @@ use crate::{
     device::{DeviceError, MissingDownlevelFlags, MissingFeatures, SHADER_STAGE_COUNT},
     error::{ErrorFormatter, PrettyError},
     hal_api::HalApi,
-    id::{BindGroupLayoutId, BufferId, DeviceId, SamplerId, TextureId, TextureViewId, Valid},
+    id::{
+        BindGroupLayoutId, BufferId, DeviceId, ExternalTextureId, SamplerId, TextureId,
+        TextureViewId, Valid,
+    },
     init_tracker::{BufferInitTrackerAction, TextureInitTrackerAction},
     resource::Resource,
     track::{BindGroupStates, UsageConflict},
@@ -26,12 +29,14 @@ use thiserror::Error;
 pub enum BindGroupLayoutEntryError {
     #[error("Cube dimension is not expected for texture storage")]
     StorageTextureCube,
-    #[error("Read-write and read-only storage textures are not allowed by webgpu, they require the native only feature TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES")]
-    StorageTextureReadWrite,
+    #[error("Read-write and read-only storage textures with format {0:?} require the RW_STORAGE_TEXTURE_TIER_1 or TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES feature")]
+    StorageTextureReadWrite(wgt::TextureFormat),
     #[error("Arrays of bindings unsupported for this type of binding")]
     ArrayUnsupported,
     #[error("Multisampled binding with sample type `TextureSampleType::Float` must have filterable set to false.")]
     SampleTypeFloatFilterableBindingMultisampled,
+    #[error("External texture bindings also occupy the next three binding indices, but binding {0} is already in use")]
+    ExternalTextureBindingOverlap(u32),
     #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
     #[error(transparent)]
@@ -76,6 +81,8 @@ pub enum CreateBindGroupError {
     InvalidTexture(TextureId),
     #[error("Sampler {0:?} is invalid")]
     InvalidSampler(SamplerId),
+    #[error("External texture {0:?} is invalid")]
+    InvalidExternalTexture(ExternalTextureId),
     #[error(
         "Binding count declared with at most {expected} items, but {actual} items were provided"
     )]
@@ -336,6 +343,10 @@ impl BindingTypeMaxCountValidator {
             wgt::BindingType::StorageTexture { .. } => {
                 self.storage_textures.add(binding.visibility, count);
             }
+            wgt::BindingType::ExternalTexture => {
+                self.sampled_textures.add(binding.visibility, 3 * count);
+                self.uniform_buffers.add(binding.visibility, count);
+            }
         }
     }
 
@@ -436,6 +447,40 @@ pub struct BindGroupLayoutDescriptor<'a> {
 
 pub(crate) type BindEntryMap = FastHashMap<u32, wgt::BindGroupLayoutEntry>;
 
+/// Returns the hal entries an external texture `entry` is made of: its three
+/// planes at consecutive bindings, followed by its conversion parameters.
+///
+/// This matches `naga::external_texture::ExternalTextureBindings::consecutive`.
+pub(crate) fn external_texture_hal_entries(
+    entry: &wgt::BindGroupLayoutEntry,
+) -> [wgt::BindGroupLayoutEntry; 4] {
+    let plane = |offset| wgt::BindGroupLayoutEntry {
+        binding: entry.binding + offset,
+        ty: wgt::BindingType::Texture {
+            sample_type: wgt::TextureSampleType::Float { filterable: true },
+            view_dimension: wgt::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        ..*entry
+    };
+    [
+        plane(0),
+        plane(1),
+        plane(2),
+        wgt::BindGroupLayoutEntry {
+            binding: entry.binding + 3,
+            ty: wgt::BindingType::Buffer {
+                ty: wgt::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: wgt::BufferSize::new(
+                    crate::resource::EXTERNAL_TEXTURE_PARAMS_SIZE,
+                ),
+            },
+            ..*entry
+        },
+    ]
+}
+
 pub type BindGroupLayouts<A> = crate::storage::Storage<BindGroupLayout<A>, BindGroupLayoutId>;
 
 /// Bind group layout.
@@ -753,6 +798,7 @@ pub enum BindingResource<'a> {
     SamplerArray(Cow<'a, [SamplerId]>),
     TextureView(TextureViewId),
     TextureViewArray(Cow<'a, [TextureViewId]>),
+    ExternalTexture(ExternalTextureId),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -94,7 +94,7 @@ mod compat {
             self.make_range(index)
         }
 
-        pub fn list_active(&self) -> impl Iterator<Item = usize> + '_ {
+        pub fn list_active(&self) -> impl Iterator<Item = usize> + Clone + '_ {
             self.entries
                 .iter()
                 .enumerate()
@@ -285,7 +285,7 @@ impl Binder {
         &self.payloads[bind_range]
     }
 
-    pub(super) fn list_active(&self) -> impl Iterator<Item = Valid<BindGroupId>> + '_ {
+    pub(super) fn list_active(&self) -> impl Iterator<Item = Valid<BindGroupId>> + Clone + '_ {
         let payloads = &self.payloads;
         self.manager
             .list_active()
@@ -667,7 +667,7 @@ impl RenderBundleEncoder {
             buffer_memory_init_actions,
             texture_memory_init_actions,
             context: self.context,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: device.new_life_guard(desc.label.borrow_or_default()),
             discard_hal_labels: device
                 .instance_flags
                 .contains(wgt::InstanceFlags::DISCARD_HAL_LABELS),
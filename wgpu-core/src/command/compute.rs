@@ -8,9 +8,10 @@ use crate::{
         end_pipeline_statistics_query,
         memory_init::{fixup_discarded_surfaces, SurfacesInDiscardState},
         BasePass, BasePassRef, BindGroupStateChange, CommandBuffer, CommandEncoderError,
-        CommandEncoderStatus, MapPassErr, PassErrorScope, QueryUseError, StateChange,
+        CommandEncoderStatus, ComputeBundle, MapPassErr, PassErrorScope, QueryUseError,
+        StateChange,
     },
-    device::{MissingDownlevelFlags, MissingFeatures},
+    device::{DeviceError, MissingDownlevelFlags, MissingFeatures},
     error::{ErrorFormatter, PrettyError},
     global::Global,
     hal_api::HalApi,
@@ -96,6 +97,7 @@ pub enum ComputeCommand {
         query_index: u32,
     },
     EndPipelineStatisticsQuery,
+    ExecuteBundle(id::ComputeBundleId),
 }
 
 #[cfg_attr(feature = "serial-pass", derive(serde::Deserialize, serde::Serialize))]
@@ -201,6 +203,10 @@ pub enum ComputePassErrorInner {
     BindGroupIndexOutOfRange { index: u32, max: u32 },
     #[error("Compute pipeline {0:?} is invalid")]
     InvalidPipeline(id::ComputePipelineId),
+    #[error("Compute bundle {0:?} is invalid")]
+    InvalidComputeBundle(id::ComputeBundleId),
+    #[error("Compute bundles can't contain the command {0:?}")]
+    UnsupportedBundleCommand(ComputeCommand),
     #[error("QuerySet {0:?} is invalid")]
     InvalidQuerySet(id::QuerySetId),
     #[error("Indirect buffer {0:?} is invalid or destroyed")]
@@ -231,6 +237,8 @@ pub enum ComputePassErrorInner {
     MissingFeatures(#[from] MissingFeatures),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    Device(DeviceError),
 }
 
 impl PrettyError for ComputePassErrorInner {
@@ -315,37 +323,62 @@ impl<A: HalApi> State<A> {
         texture_guard: &Storage<Texture<A>, id::TextureId>,
         indirect_buffer: Option<id::Valid<id::BufferId>>,
     ) -> Result<(), UsageConflict> {
-        for id in self.binder.list_active() {
-            unsafe {
-                self.scope
-                    .merge_bind_group(texture_guard, &bind_group_guard[id].used)?
-            };
-            // Note: stateless trackers are not merged: the lifetime reference
-            // is held to the bind group itself.
-        }
+        flush_dispatch_states(
+            raw_encoder,
+            &mut self.scope,
+            base_trackers,
+            self.binder.list_active(),
+            bind_group_guard,
+            buffer_guard,
+            texture_guard,
+            indirect_buffer,
+        )
+    }
+}
 
-        for id in self.binder.list_active() {
-            unsafe {
-                base_trackers.set_and_remove_from_usage_scope_sparse(
-                    texture_guard,
-                    &mut self.scope,
-                    &bind_group_guard[id].used,
-                )
-            }
-        }
+/// Merge the resources used by a dispatch into `scope`, and encode the
+/// barriers they need.
+///
+/// `bind_groups` are the bind groups the dispatch uses. This is shared by
+/// compute passes and the compute bundles they execute.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn flush_dispatch_states<A: HalApi>(
+    raw_encoder: &mut A::CommandEncoder,
+    scope: &mut UsageScope<A>,
+    base_trackers: &mut Tracker<A>,
+    bind_groups: impl Iterator<Item = id::Valid<id::BindGroupId>> + Clone,
+    bind_group_guard: &Storage<BindGroup<A>, id::BindGroupId>,
+    buffer_guard: &Storage<Buffer<A>, id::BufferId>,
+    texture_guard: &Storage<Texture<A>, id::TextureId>,
+    indirect_buffer: Option<id::Valid<id::BufferId>>,
+) -> Result<(), UsageConflict> {
+    for id in bind_groups.clone() {
+        unsafe { scope.merge_bind_group(texture_guard, &bind_group_guard[id].used)? };
+        // Note: stateless trackers are not merged: the lifetime reference
+        // is held to the bind group itself.
+    }
 
-        // Add the state of the indirect buffer if it hasn't been hit before.
+    for id in bind_groups {
         unsafe {
-            base_trackers
-                .buffers
-                .set_and_remove_from_usage_scope_sparse(&mut self.scope.buffers, indirect_buffer);
+            base_trackers.set_and_remove_from_usage_scope_sparse(
+                texture_guard,
+                scope,
+                &bind_group_guard[id].used,
+            )
         }
+    }
 
-        log::trace!("Encoding dispatch barriers");
-
-        CommandBuffer::drain_barriers(raw_encoder, base_trackers, buffer_guard, texture_guard);
-        Ok(())
+    // Add the state of the indirect buffer if it hasn't been hit before.
+    unsafe {
+        base_trackers
+            .buffers
+            .set_and_remove_from_usage_scope_sparse(&mut scope.buffers, indirect_buffer);
     }
+
+    log::trace!("Encoding dispatch barriers");
+
+    CommandBuffer::drain_barriers(raw_encoder, base_trackers, buffer_guard, texture_guard);
+    Ok(())
 }
 
 // Common routines between render/compute
@@ -410,6 +443,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         }
 
         let (_, mut token) = hub.render_bundles.read(&mut token);
+        let (compute_bundle_guard, mut token) = hub.compute_bundles.read(&mut token);
         let (pipeline_layout_guard, mut token) = hub.pipeline_layouts.read(&mut token);
         let (bind_group_guard, mut token) = hub.bind_groups.read(&mut token);
         let (pipeline_guard, mut token) = hub.compute_pipelines.read(&mut token);
@@ -474,6 +508,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             Some(&*pipeline_guard),
             None,
             None,
+            Some(&*compute_bundle_guard),
             Some(&*query_set_guard),
         );
 
@@ -859,6 +894,59 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     end_pipeline_statistics_query(raw, &*query_set_guard, &mut active_query)
                         .map_pass_err(scope)?;
                 }
+                ComputeCommand::ExecuteBundle(bundle_id) => {
+                    let scope = PassErrorScope::ExecuteBundle;
+
+                    let bundle: &ComputeBundle<A> = cmd_buf
+                        .trackers
+                        .compute_bundles
+                        .add_single(&*compute_bundle_guard, bundle_id)
+                        .ok_or(ComputePassErrorInner::InvalidComputeBundle(bundle_id))
+                        .map_pass_err(scope)?;
+
+                    if bundle.device_id.value != cmd_buf.device_id.value {
+                        return Err(ComputePassErrorInner::Device(DeviceError::WrongDevice))
+                            .map_pass_err(scope);
+                    }
+
+                    cmd_buf.buffer_memory_init_actions.extend(
+                        bundle
+                            .buffer_memory_init_actions
+                            .iter()
+                            .filter_map(|action| match buffer_guard.get(action.id) {
+                                Ok(buffer) => buffer.initialization_status.check_action(action),
+                                Err(_) => None,
+                            }),
+                    );
+                    for action in bundle.texture_memory_init_actions.iter() {
+                        pending_discard_init_fixups.extend(
+                            cmd_buf
+                                .texture_memory_actions
+                                .register_init_action(action, &texture_guard),
+                        );
+                    }
+
+                    cmd_buf.trackers.add_from_compute_bundle(&bundle.used);
+
+                    unsafe {
+                        bundle.execute(
+                            raw,
+                            &mut state.scope,
+                            &mut intermediate_trackers,
+                            &*pipeline_layout_guard,
+                            &*bind_group_guard,
+                            &*pipeline_guard,
+                            &*buffer_guard,
+                            &*texture_guard,
+                        )
+                    }
+                    .map_pass_err(scope)?;
+
+                    // The bundle's pipeline and bind groups are not inherited
+                    // by the commands that follow it.
+                    state.binder.reset();
+                    state.pipeline = None;
+                }
             }
         }
 
@@ -1087,4 +1175,25 @@ pub mod compute_ffi {
             .commands
             .push(ComputeCommand::EndPipelineStatisticsQuery);
     }
+
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `compute_bundle_ids_length` elements.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_compute_pass_execute_bundles(
+        pass: &mut ComputePass,
+        compute_bundle_ids: *const id::ComputeBundleId,
+        compute_bundle_ids_length: usize,
+    ) {
+        for &bundle_id in
+            unsafe { slice::from_raw_parts(compute_bundle_ids, compute_bundle_ids_length) }
+        {
+            pass.base
+                .commands
+                .push(ComputeCommand::ExecuteBundle(bundle_id));
+        }
+        pass.current_pipeline.reset();
+        pass.current_bind_groups.reset();
+    }
 }
@@ -0,0 +1,769 @@
+/*! Compute Bundles
+
+A compute bundle is a prerecorded sequence of dispatches that can be replayed in
+a compute pass with a single call, any number of times. It is the compute
+counterpart of a [render bundle](super::bundle): `wgpu` validates the bundle's
+commands once, when it is finished, so that executing it only has to encode
+them and the barriers between them.
+
+Compute bundles are not part of WebGPU.
+
+## Compute Bundle Isolation
+
+Like render bundles, compute bundles are isolated from the passes executing
+them. Dispatches in a bundle only use the pipeline and bind groups set within
+the bundle, and after a bundle executes, the compute pass's pipeline and bind
+groups are unset.
+
+Push constant values are not isolated, in the same way as for render bundles.
+
+## Compute Bundle Lifecycle
+
+1) Create a [`ComputeBundleEncoder`] by calling
+   [`Global::device_create_compute_bundle_encoder`][Gdccbe].
+
+2) Record commands in the `ComputeBundleEncoder` using functions from the
+   [`compute_bundle_ffi`] module.
+
+3) Call [`Global::compute_bundle_encoder_finish`][Gcbef], which validates the
+   command stream and returns a `ComputeBundleId`.
+
+4) Then, any number of times, call [`wgpu_compute_pass_execute_bundles`][wcpeb]
+   to execute the bundle as part of some compute pass.
+
+## Implementation
+
+[`ComputeBundleEncoder::finish`] goes through the recorded commands with the
+same [`Binder`] a compute pass uses, and replaces the recorded `SetBindGroup`
+commands by the ones the pass would actually encode. The resulting command
+stream can be encoded without any bind group compatibility tracking.
+
+What cannot be done up front is resource state tracking: each dispatch in a
+compute pass is its own usage scope, whose resources may need barriers
+depending on what the pass did before. When the bundle is executed,
+[`ComputeBundle::execute`] merges the bind groups of each dispatch into the
+pass's usage scope, exactly as the pass does for its own dispatches.
+
+[Gdccbe]: crate::global::Global::device_create_compute_bundle_encoder
+[Gcbef]: crate::global::Global::compute_bundle_encoder_finish
+[wcpeb]: crate::command::compute_ffi::wgpu_compute_pass_execute_bundles
+!*/
+
+use crate::{
+    binding_model::{BindGroup, PipelineLayout},
+    command::{
+        bind::{Binder, EntryPayload},
+        compute::flush_dispatch_states,
+        BasePass, BindGroupStateChange, ComputeCommand, ComputePassErrorInner, DispatchError,
+        MapPassErr, PassErrorScope, StateChange,
+    },
+    device::{Device, DeviceError},
+    error::{ErrorFormatter, PrettyError},
+    hal_api::HalApi,
+    hub::{Hub, Token},
+    id,
+    identity::GlobalIdentityHandlerFactory,
+    init_tracker::{BufferInitTrackerAction, MemoryInitKind, TextureInitTrackerAction},
+    pipeline::ComputePipeline,
+    resource::{Buffer, Resource, Texture},
+    storage::Storage,
+    track::{ComputeBundleScope, Tracker, UsageScope},
+    validation::check_buffer_usage,
+    Label, LabelHelpers, LifeGuard, Stored,
+};
+use std::mem;
+use thiserror::Error;
+
+use hal::CommandEncoder as _;
+
+/// Describes a [`ComputeBundleEncoder`].
+pub type ComputeBundleEncoderDescriptor<'a> = wgt::ComputeBundleEncoderDescriptor<Label<'a>>;
+
+/// Describes a [`ComputeBundle`].
+pub type ComputeBundleDescriptor<'a> = wgt::ComputeBundleDescriptor<Label<'a>>;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serial-pass", derive(serde::Deserialize, serde::Serialize))]
+pub struct ComputeBundleEncoder {
+    base: BasePass<ComputeCommand>,
+    parent_id: id::DeviceId,
+
+    // Resource binding dedupe state.
+    #[cfg_attr(feature = "serial-pass", serde(skip))]
+    current_bind_groups: BindGroupStateChange,
+    #[cfg_attr(feature = "serial-pass", serde(skip))]
+    current_pipeline: StateChange<id::ComputePipelineId>,
+}
+
+impl ComputeBundleEncoder {
+    pub fn new(
+        desc: &ComputeBundleEncoderDescriptor,
+        parent_id: id::DeviceId,
+        base: Option<BasePass<ComputeCommand>>,
+    ) -> Self {
+        Self {
+            base: base.unwrap_or_else(|| BasePass::new(&desc.label)),
+            parent_id,
+            current_bind_groups: BindGroupStateChange::new(),
+            current_pipeline: StateChange::new(),
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    pub(crate) fn to_base_pass(&self) -> BasePass<ComputeCommand> {
+        BasePass::from_ref(self.base.as_ref())
+    }
+
+    pub fn parent(&self) -> id::DeviceId {
+        self.parent_id
+    }
+
+    /// Convert this encoder's commands into a [`ComputeBundle`].
+    ///
+    /// This validates every command the way `command_encoder_run_compute_pass`
+    /// would, except for usage conflicts between the resources of a dispatch,
+    /// which are found when the bundle is executed. The bind group commands
+    /// are normalized so that [`ComputeBundle::execute`] does not need a
+    /// [`Binder`].
+    pub(crate) fn finish<A: HalApi, G: GlobalIdentityHandlerFactory>(
+        self,
+        desc: &ComputeBundleDescriptor,
+        device: &Device<A>,
+        hub: &Hub<A, G>,
+        token: &mut Token<Device<A>>,
+    ) -> Result<ComputeBundle<A>, ComputeBundleError> {
+        let (pipeline_layout_guard, mut token) = hub.pipeline_layouts.read(token);
+        let (bind_group_layout_guard, mut token) = hub.bind_group_layouts.read(&mut token);
+        let (bind_group_guard, mut token) = hub.bind_groups.read(&mut token);
+        let (pipeline_guard, mut token) = hub.compute_pipelines.read(&mut token);
+        let (buffer_guard, _) = hub.buffers.read(&mut token);
+
+        let mut used =
+            ComputeBundleScope::new(&*buffer_guard, &*bind_group_guard, &*pipeline_guard);
+        let mut binder = Binder::new();
+        let mut pipeline = None;
+        let mut commands = Vec::new();
+        let mut dynamic_offsets = Vec::new();
+        let mut buffer_memory_init_actions = Vec::new();
+        let mut texture_memory_init_actions = Vec::new();
+
+        let base = self.base.as_ref();
+        let mut next_dynamic_offset = 0;
+
+        for &command in base.commands {
+            match command {
+                ComputeCommand::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                } => {
+                    let scope = PassErrorScope::SetBindGroup(bind_group_id);
+
+                    let max_bind_groups = device.limits.max_bind_groups;
+                    if index >= max_bind_groups {
+                        return Err(ComputePassErrorInner::BindGroupIndexOutOfRange {
+                            index,
+                            max: max_bind_groups,
+                        })
+                        .map_pass_err(scope);
+                    }
+
+                    let offsets_range =
+                        next_dynamic_offset..next_dynamic_offset + num_dynamic_offsets as usize;
+                    next_dynamic_offset = offsets_range.end;
+                    let offsets = &base.dynamic_offsets[offsets_range];
+
+                    let bind_group: &BindGroup<A> = used
+                        .bind_groups
+                        .add_single(&*bind_group_guard, bind_group_id)
+                        .ok_or(ComputePassErrorInner::InvalidBindGroup(bind_group_id))
+                        .map_pass_err(scope)?;
+                    self.check_valid_to_use(bind_group.device_id.value)
+                        .map_pass_err(scope)?;
+                    bind_group
+                        .validate_dynamic_bindings(index, offsets, &device.limits)
+                        .map_pass_err(scope)?;
+
+                    buffer_memory_init_actions.extend_from_slice(&bind_group.used_buffer_ranges);
+                    texture_memory_init_actions.extend_from_slice(&bind_group.used_texture_ranges);
+
+                    let entries = binder.assign_group(
+                        index as usize,
+                        id::Valid(bind_group_id),
+                        bind_group,
+                        offsets,
+                    );
+                    push_bind_groups(&mut commands, &mut dynamic_offsets, index, entries);
+                }
+                ComputeCommand::SetPipeline(pipeline_id) => {
+                    let scope = PassErrorScope::SetPipelineCompute(pipeline_id);
+
+                    pipeline = Some(pipeline_id);
+
+                    let pipeline: &ComputePipeline<A> = used
+                        .compute_pipelines
+                        .add_single(&*pipeline_guard, pipeline_id)
+                        .ok_or(ComputePassErrorInner::InvalidPipeline(pipeline_id))
+                        .map_pass_err(scope)?;
+                    self.check_valid_to_use(pipeline.device_id.value)
+                        .map_pass_err(scope)?;
+
+                    commands.push(command);
+
+                    if binder.pipeline_layout_id != Some(pipeline.layout_id.value) {
+                        let (start_index, entries) = binder.change_pipeline_layout(
+                            &*pipeline_layout_guard,
+                            pipeline.layout_id.value,
+                            &pipeline.late_sized_buffer_groups,
+                        );
+                        push_bind_groups(
+                            &mut commands,
+                            &mut dynamic_offsets,
+                            start_index as u32,
+                            entries,
+                        );
+                    }
+                }
+                ComputeCommand::SetPushConstant {
+                    offset,
+                    size_bytes,
+                    values_offset: _,
+                } => {
+                    let scope = PassErrorScope::SetPushConstant;
+
+                    let pipeline_layout_id = binder
+                        .pipeline_layout_id
+                        .ok_or(ComputePassErrorInner::Dispatch(
+                            DispatchError::MissingPipeline,
+                        ))
+                        .map_pass_err(scope)?;
+                    pipeline_layout_guard[pipeline_layout_id]
+                        .validate_push_constant_ranges(
+                            wgt::ShaderStages::COMPUTE,
+                            offset,
+                            offset + size_bytes,
+                        )
+                        .map_pass_err(scope)?;
+
+                    commands.push(command);
+                }
+                ComputeCommand::Dispatch(groups) => {
+                    let scope = PassErrorScope::Dispatch {
+                        indirect: false,
+                        pipeline,
+                    };
+
+                    check_ready(&binder, pipeline, &*bind_group_layout_guard)
+                        .map_pass_err(scope)?;
+
+                    let groups_size_limit = device.limits.max_compute_workgroups_per_dimension;
+                    if groups.iter().any(|&size| size > groups_size_limit) {
+                        return Err(DispatchError::InvalidGroupSize {
+                            current: groups,
+                            limit: groups_size_limit,
+                        })
+                        .map_pass_err(scope);
+                    }
+
+                    commands.push(command);
+                }
+                ComputeCommand::DispatchIndirect { buffer_id, offset } => {
+                    let scope = PassErrorScope::Dispatch {
+                        indirect: true,
+                        pipeline,
+                    };
+
+                    check_ready(&binder, pipeline, &*bind_group_layout_guard)
+                        .map_pass_err(scope)?;
+
+                    device
+                        .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+                        .map_pass_err(scope)?;
+
+                    let buffer: &Buffer<A> = used
+                        .buffers
+                        .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
+                        .map_pass_err(scope)?;
+                    self.check_valid_to_use(buffer.device_id.value)
+                        .map_pass_err(scope)?;
+                    check_buffer_usage(buffer.usage, wgt::BufferUsages::INDIRECT)
+                        .map_pass_err(scope)?;
+
+                    let end_offset = offset + mem::size_of::<wgt::DispatchIndirectArgs>() as u64;
+                    if end_offset > buffer.size {
+                        return Err(ComputePassErrorInner::IndirectBufferOverrun {
+                            offset,
+                            end_offset,
+                            buffer_size: buffer.size,
+                        })
+                        .map_pass_err(scope);
+                    }
+
+                    buffer_memory_init_actions.extend(buffer.initialization_status.create_action(
+                        buffer_id,
+                        offset..end_offset,
+                        MemoryInitKind::NeedsInitializedMemory,
+                    ));
+
+                    commands.push(command);
+                }
+                ComputeCommand::PushDebugGroup { .. }
+                | ComputeCommand::PopDebugGroup
+                | ComputeCommand::InsertDebugMarker { .. }
+                | ComputeCommand::WriteTimestamp { .. }
+                | ComputeCommand::BeginPipelineStatisticsQuery { .. }
+                | ComputeCommand::EndPipelineStatisticsQuery
+                | ComputeCommand::ExecuteBundle(_) => {
+                    // Only possible with commands that didn't come from a
+                    // `ComputeBundleEncoder`, like a deserialized trace.
+                    return Err(ComputePassErrorInner::UnsupportedBundleCommand(command))
+                        .map_pass_err(PassErrorScope::Bundle);
+                }
+            }
+        }
+
+        Ok(ComputeBundle {
+            base: BasePass {
+                label: desc.label.as_ref().map(|cow| cow.to_string()),
+                commands,
+                dynamic_offsets,
+                string_data: Vec::new(),
+                push_constant_data: self.base.push_constant_data,
+            },
+            device_id: Stored {
+                value: id::Valid(self.parent_id),
+                ref_count: device.life_guard.add_ref(),
+            },
+            used,
+            buffer_memory_init_actions,
+            texture_memory_init_actions,
+            life_guard: device.new_life_guard(desc.label.borrow_or_default()),
+            discard_hal_labels: device
+                .instance_flags
+                .contains(wgt::InstanceFlags::DISCARD_HAL_LABELS),
+        })
+    }
+
+    fn check_valid_to_use(
+        &self,
+        device_id: id::Valid<id::DeviceId>,
+    ) -> Result<(), ComputeBundleErrorInner> {
+        if device_id.0 != self.parent_id {
+            return Err(ComputeBundleErrorInner::NotValidToUse);
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that a dispatch recorded after `binder` and `pipeline` can run.
+fn check_ready<A: HalApi>(
+    binder: &Binder,
+    pipeline: Option<id::ComputePipelineId>,
+    bind_group_layouts: &crate::binding_model::BindGroupLayouts<A>,
+) -> Result<(), DispatchError> {
+    let bind_mask = binder.invalid_mask(bind_group_layouts);
+    if bind_mask != 0 {
+        return Err(DispatchError::IncompatibleBindGroup {
+            index: bind_mask.trailing_zeros(),
+        });
+    }
+    if pipeline.is_none() {
+        return Err(DispatchError::MissingPipeline);
+    }
+    binder.check_late_buffer_bindings()?;
+
+    Ok(())
+}
+
+/// Append a `SetBindGroup` command for each of the `entries` the [`Binder`]
+/// asks to bind, starting at group `start_index`.
+fn push_bind_groups(
+    commands: &mut Vec<ComputeCommand>,
+    dynamic_offsets: &mut Vec<wgt::DynamicOffset>,
+    start_index: u32,
+    entries: &[EntryPayload],
+) {
+    for (i, e) in entries.iter().enumerate() {
+        dynamic_offsets.extend_from_slice(&e.dynamic_offsets);
+        commands.push(ComputeCommand::SetBindGroup {
+            index: start_index + i as u32,
+            num_dynamic_offsets: e.dynamic_offsets.len() as u8,
+            bind_group_id: e.group_id.as_ref().unwrap().value.0,
+        });
+    }
+}
+
+pub struct ComputeBundle<A: HalApi> {
+    // Normalized command stream. Every `SetBindGroup` command in it is
+    // compatible with the pipeline set before it.
+    base: BasePass<ComputeCommand>,
+    pub(crate) device_id: Stored<id::DeviceId>,
+    pub(crate) used: ComputeBundleScope<A>,
+    pub(super) buffer_memory_init_actions: Vec<BufferInitTrackerAction>,
+    pub(super) texture_memory_init_actions: Vec<TextureInitTrackerAction>,
+    pub(crate) life_guard: LifeGuard,
+    discard_hal_labels: bool,
+}
+
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+unsafe impl<A: HalApi> Send for ComputeBundle<A> {}
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+unsafe impl<A: HalApi> Sync for ComputeBundle<A> {}
+
+impl<A: HalApi> ComputeBundle<A> {
+    /// Encode the bundle's commands into a native command buffer, as part of
+    /// a compute pass.
+    ///
+    /// The resources of each dispatch are merged into `scope`, and their
+    /// barriers recorded into `base_trackers`, like
+    /// `command_encoder_run_compute_pass` does for its own dispatches. This
+    /// can fail if these resources conflict with each other, or if an
+    /// indirect buffer was destroyed.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) unsafe fn execute(
+        &self,
+        raw: &mut A::CommandEncoder,
+        scope: &mut UsageScope<A>,
+        base_trackers: &mut Tracker<A>,
+        pipeline_layout_guard: &Storage<PipelineLayout<A>, id::PipelineLayoutId>,
+        bind_group_guard: &Storage<BindGroup<A>, id::BindGroupId>,
+        pipeline_guard: &Storage<ComputePipeline<A>, id::ComputePipelineId>,
+        buffer_guard: &Storage<Buffer<A>, id::BufferId>,
+        texture_guard: &Storage<Texture<A>, id::TextureId>,
+    ) -> Result<(), ComputePassErrorInner> {
+        let mut offsets = self.base.dynamic_offsets.as_slice();
+        let mut pipeline_layout_id = None::<id::Valid<id::PipelineLayoutId>>;
+        // The bind groups set so far, of which the dispatches use the first
+        // `bind_group_count`: `finish` checked that they are all set.
+        let mut bind_groups = [None::<id::Valid<id::BindGroupId>>; hal::MAX_BIND_GROUPS];
+        let mut bind_group_count = 0;
+        if !self.discard_hal_labels {
+            if let Some(ref label) = self.base.label {
+                unsafe { raw.begin_debug_marker(label) };
+            }
+        }
+
+        for command in self.base.commands.iter() {
+            match *command {
+                ComputeCommand::SetBindGroup {
+                    index,
+                    num_dynamic_offsets,
+                    bind_group_id,
+                } => {
+                    let bind_group = bind_group_guard.get(bind_group_id).unwrap();
+                    unsafe {
+                        raw.set_bind_group(
+                            &pipeline_layout_guard[pipeline_layout_id.unwrap()].raw,
+                            index,
+                            &bind_group.raw,
+                            &offsets[..num_dynamic_offsets as usize],
+                        )
+                    };
+                    offsets = &offsets[num_dynamic_offsets as usize..];
+                    bind_groups[index as usize] = Some(id::Valid(bind_group_id));
+                }
+                ComputeCommand::SetPipeline(pipeline_id) => {
+                    let pipeline = pipeline_guard.get(pipeline_id).unwrap();
+                    unsafe { raw.set_compute_pipeline(&pipeline.raw) };
+
+                    if pipeline_layout_id != Some(pipeline.layout_id.value) {
+                        pipeline_layout_id = Some(pipeline.layout_id.value);
+
+                        let pipeline_layout = &pipeline_layout_guard[pipeline.layout_id.value];
+                        bind_group_count = pipeline_layout.bind_group_layout_ids.len();
+
+                        // Clear push constant ranges
+                        let non_overlapping = super::bind::compute_nonoverlapping_ranges(
+                            &pipeline_layout.push_constant_ranges,
+                        );
+                        for range in non_overlapping {
+                            let offset = range.range.start;
+                            let size_bytes = range.range.end - offset;
+                            super::push_constant_clear(
+                                offset,
+                                size_bytes,
+                                |clear_offset, clear_data| unsafe {
+                                    raw.set_push_constants(
+                                        &pipeline_layout.raw,
+                                        wgt::ShaderStages::COMPUTE,
+                                        clear_offset,
+                                        clear_data,
+                                    );
+                                },
+                            );
+                        }
+                    }
+                }
+                ComputeCommand::SetPushConstant {
+                    offset,
+                    size_bytes,
+                    values_offset,
+                } => {
+                    let pipeline_layout = &pipeline_layout_guard[pipeline_layout_id.unwrap()];
+                    let values_end_offset =
+                        (values_offset + size_bytes / wgt::PUSH_CONSTANT_ALIGNMENT) as usize;
+                    let data_slice =
+                        &self.base.push_constant_data[(values_offset as usize)..values_end_offset];
+
+                    unsafe {
+                        raw.set_push_constants(
+                            &pipeline_layout.raw,
+                            wgt::ShaderStages::COMPUTE,
+                            offset,
+                            data_slice,
+                        )
+                    }
+                }
+                ComputeCommand::Dispatch(groups) => {
+                    flush_dispatch_states(
+                        raw,
+                        scope,
+                        base_trackers,
+                        bind_groups[..bind_group_count].iter().flatten().copied(),
+                        bind_group_guard,
+                        buffer_guard,
+                        texture_guard,
+                        None,
+                    )?;
+                    unsafe { raw.dispatch(groups) };
+                }
+                ComputeCommand::DispatchIndirect { buffer_id, offset } => {
+                    let buffer = scope.buffers.merge_single(
+                        buffer_guard,
+                        buffer_id,
+                        hal::BufferUses::INDIRECT,
+                    )?;
+                    let buf_raw = buffer
+                        .raw
+                        .as_ref()
+                        .ok_or(ComputePassErrorInner::InvalidIndirectBuffer(buffer_id))?;
+
+                    flush_dispatch_states(
+                        raw,
+                        scope,
+                        base_trackers,
+                        bind_groups[..bind_group_count].iter().flatten().copied(),
+                        bind_group_guard,
+                        buffer_guard,
+                        texture_guard,
+                        Some(id::Valid(buffer_id)),
+                    )?;
+                    unsafe { raw.dispatch_indirect(buf_raw, offset) };
+                }
+                ComputeCommand::PushDebugGroup { .. }
+                | ComputeCommand::PopDebugGroup
+                | ComputeCommand::InsertDebugMarker { .. }
+                | ComputeCommand::WriteTimestamp { .. }
+                | ComputeCommand::BeginPipelineStatisticsQuery { .. }
+                | ComputeCommand::EndPipelineStatisticsQuery
+                | ComputeCommand::ExecuteBundle(_) => {
+                    return Err(ComputePassErrorInner::UnsupportedBundleCommand(*command))
+                }
+            }
+        }
+
+        if !self.discard_hal_labels {
+            if let Some(_) = self.base.label {
+                unsafe { raw.end_debug_marker() };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<A: HalApi> Resource for ComputeBundle<A> {
+    const TYPE: &'static str = "ComputeBundle";
+
+    fn life_guard(&self) -> &LifeGuard {
+        &self.life_guard
+    }
+}
+
+/// Error encountered when finishing recording a compute bundle.
+#[derive(Clone, Debug, Error)]
+pub(super) enum ComputeBundleErrorInner {
+    #[error("Resource is not valid to use with this compute bundle because the resource and the bundle come from different devices")]
+    NotValidToUse,
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    ComputeCommand(ComputePassErrorInner),
+}
+
+impl<T> From<T> for ComputeBundleErrorInner
+where
+    T: Into<ComputePassErrorInner>,
+{
+    fn from(t: T) -> Self {
+        Self::ComputeCommand(t.into())
+    }
+}
+
+/// Error encountered when finishing recording a compute bundle.
+#[derive(Clone, Debug, Error)]
+#[error("{scope}")]
+pub struct ComputeBundleError {
+    pub scope: PassErrorScope,
+    #[source]
+    inner: ComputeBundleErrorInner,
+}
+
+impl ComputeBundleError {
+    pub(crate) const INVALID_DEVICE: Self = ComputeBundleError {
+        scope: PassErrorScope::Bundle,
+        inner: ComputeBundleErrorInner::Device(DeviceError::Invalid),
+    };
+}
+impl PrettyError for ComputeBundleError {
+    fn fmt_pretty(&self, fmt: &mut ErrorFormatter) {
+        // This error is wrapper for the inner error,
+        // but the scope has useful labels
+        fmt.error(self);
+        self.scope.fmt_pretty(fmt);
+    }
+}
+
+impl<T, E> MapPassErr<T, ComputeBundleError> for Result<T, E>
+where
+    E: Into<ComputeBundleErrorInner>,
+{
+    fn map_pass_err(self, scope: PassErrorScope) -> Result<T, ComputeBundleError> {
+        self.map_err(|inner| ComputeBundleError {
+            scope,
+            inner: inner.into(),
+        })
+    }
+}
+
+pub mod compute_bundle_ffi {
+    use super::{ComputeBundleEncoder, ComputeCommand};
+    use crate::id;
+    use std::{convert::TryInto, slice};
+    use wgt::{BufferAddress, DynamicOffset};
+
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `offset_length` elements.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_compute_bundle_set_bind_group(
+        bundle: &mut ComputeBundleEncoder,
+        index: u32,
+        bind_group_id: id::BindGroupId,
+        offsets: *const DynamicOffset,
+        offset_length: usize,
+    ) {
+        let redundant = unsafe {
+            bundle.current_bind_groups.set_and_check_redundant(
+                bind_group_id,
+                index,
+                &mut bundle.base.dynamic_offsets,
+                offsets,
+                offset_length,
+            )
+        };
+
+        if redundant {
+            return;
+        }
+
+        bundle.base.commands.push(ComputeCommand::SetBindGroup {
+            index,
+            num_dynamic_offsets: offset_length.try_into().unwrap(),
+            bind_group_id,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_bundle_set_pipeline(
+        bundle: &mut ComputeBundleEncoder,
+        pipeline_id: id::ComputePipelineId,
+    ) {
+        if bundle.current_pipeline.set_and_check_redundant(pipeline_id) {
+            return;
+        }
+
+        bundle
+            .base
+            .commands
+            .push(ComputeCommand::SetPipeline(pipeline_id));
+    }
+
+    /// # Safety
+    ///
+    /// This function is unsafe as there is no guarantee that the given pointer is
+    /// valid for `size_bytes` bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn wgpu_compute_bundle_set_push_constant(
+        bundle: &mut ComputeBundleEncoder,
+        offset: u32,
+        size_bytes: u32,
+        data: *const u8,
+    ) {
+        assert_eq!(
+            offset & (wgt::PUSH_CONSTANT_ALIGNMENT - 1),
+            0,
+            "Push constant offset must be aligned to 4 bytes."
+        );
+        assert_eq!(
+            size_bytes & (wgt::PUSH_CONSTANT_ALIGNMENT - 1),
+            0,
+            "Push constant size must be aligned to 4 bytes."
+        );
+        let data_slice = unsafe { slice::from_raw_parts(data, size_bytes as usize) };
+        let value_offset = bundle.base.push_constant_data.len().try_into().expect(
+            "Ran out of push constant space. Don't set 4gb of push constants per ComputeBundle.",
+        );
+
+        bundle.base.push_constant_data.extend(
+            data_slice
+                .chunks_exact(wgt::PUSH_CONSTANT_ALIGNMENT as usize)
+                .map(|arr| u32::from_ne_bytes([arr[0], arr[1], arr[2], arr[3]])),
+        );
+
+        bundle.base.commands.push(ComputeCommand::SetPushConstant {
+            offset,
+            size_bytes,
+            values_offset: value_offset,
+        });
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_bundle_dispatch_workgroups(
+        bundle: &mut ComputeBundleEncoder,
+        groups_x: u32,
+        groups_y: u32,
+        groups_z: u32,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(ComputeCommand::Dispatch([groups_x, groups_y, groups_z]));
+    }
+
+    #[no_mangle]
+    pub extern "C" fn wgpu_compute_bundle_dispatch_workgroups_indirect(
+        bundle: &mut ComputeBundleEncoder,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+    ) {
+        bundle
+            .base
+            .commands
+            .push(ComputeCommand::DispatchIndirect { buffer_id, offset });
+    }
+}
@@ -2,6 +2,7 @@ mod bind;
 mod bundle;
 mod clear;
 mod compute;
+mod compute_bundle;
 mod draw;
 mod memory_init;
 mod query;
@@ -12,7 +13,8 @@ use std::slice;
 
 pub(crate) use self::clear::clear_texture;
 pub use self::{
-    bundle::*, clear::ClearError, compute::*, draw::*, query::*, render::*, transfer::*,
+    bundle::*, clear::ClearError, compute::*, compute_bundle::*, draw::*, query::*, render::*,
+    transfer::*,
 };
 
 use self::memory_init::CommandBufferTextureMemoryActions;
@@ -275,7 +277,7 @@ pub struct BasePassRef<'a, C> {
 /// [`SetBindGroup`]: RenderCommand::SetBindGroup
 /// [`InsertDebugMarker`]: RenderCommand::InsertDebugMarker
 #[doc(hidden)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(
     any(feature = "serial-pass", feature = "trace"),
     derive(serde::Serialize)
@@ -1411,6 +1411,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 None,
                 Some(&*render_pipeline_guard),
                 Some(&*bundle_guard),
+                None,
                 Some(&*query_set_guard),
             );
 
@@ -0,0 +1,308 @@
+//! Deterministic failure of selected hal calls, for testing error paths.
+//!
+//! Real drivers rarely report running out of memory or losing the device, so
+//! the code handling those errors is hard to exercise. A [`FaultInjector`]
+//! installed on a device with [`Global::device_set_fault_injector`] makes the
+//! device fail the hal calls its [`FaultRule`]s select, before they reach the
+//! backend. This works on every backend.
+//!
+//! [`Global::device_set_fault_injector`]: crate::global::Global::device_set_fault_injector
+
+/// A hal call that can be made to fail.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FaultSite {
+    /// [`hal::Device::create_buffer`].
+    CreateBuffer,
+    /// [`hal::Device::create_texture`].
+    CreateTexture,
+    /// [`hal::Queue::submit`].
+    Submit,
+}
+
+impl FaultSite {
+    const COUNT: usize = 3;
+}
+
+/// The error an injected fault produces.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FaultKind {
+    /// Fail with [`hal::DeviceError::OutOfMemory`].
+    OutOfMemory,
+    /// Fail with [`hal::DeviceError::Lost`], and lose the device.
+    ///
+    /// The device is marked invalid just as if it had been lost for real, so
+    /// every later operation on it fails.
+    Lost,
+}
+
+/// When a [`FaultRule`] fires.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FaultTrigger {
+    /// Fail the `n`th call to the site only, counting from 1.
+    Nth(u64),
+    /// Fail the `n`th call to the site and every call after it.
+    From(u64),
+    /// Fail each call with the given probability, between 0 and 1.
+    ///
+    /// Calls are decided by a pseudo-random generator started from `seed`, so
+    /// the same sequence of calls fails the same way every time.
+    Probability { probability: f32, seed: u64 },
+    /// Fail calls allocating at least this many bytes.
+    ///
+    /// Texture sizes are estimated from their format and extent. Never fires
+    /// for [`FaultSite::Submit`].
+    MinSize(wgt::BufferAddress),
+}
+
+/// Which calls to fail, and how.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FaultRule {
+    pub site: FaultSite,
+    pub trigger: FaultTrigger,
+    pub kind: FaultKind,
+}
+
+/// Decides which hal calls of a device fail, according to a set of [`FaultRule`]s.
+#[derive(Debug)]
+pub struct FaultInjector {
+    rules: Vec<(FaultRule, u64)>,
+    calls: [u64; FaultSite::COUNT],
+    lost: bool,
+}
+
+impl FaultInjector {
+    pub fn new(rules: impl IntoIterator<Item = FaultRule>) -> Self {
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|rule| {
+                    let state = match rule.trigger {
+                        // Xorshift gets stuck at zero.
+                        FaultTrigger::Probability { seed, .. } => seed | 1,
+                        _ => 0,
+                    };
+                    (rule, state)
+                })
+                .collect(),
+            calls: [0; FaultSite::COUNT],
+            lost: false,
+        }
+    }
+
+    /// Returns the number of calls to `site` checked so far.
+    pub fn call_count(&self, site: FaultSite) -> u64 {
+        self.calls[site as usize]
+    }
+
+    /// Records a call to `site` allocating `size` bytes, and returns the error
+    /// it should fail with, if any.
+    pub(crate) fn check(&mut self, site: FaultSite, size: u64) -> Result<(), hal::DeviceError> {
+        let call = {
+            let count = &mut self.calls[site as usize];
+            *count += 1;
+            *count
+        };
+        if self.lost {
+            return Err(hal::DeviceError::Lost);
+        }
+
+        let mut fault = None;
+        for &mut (ref rule, ref mut state) in self.rules.iter_mut() {
+            if rule.site != site {
+                continue;
+            }
+            let fires = match rule.trigger {
+                FaultTrigger::Nth(n) => call == n,
+                FaultTrigger::From(n) => call >= n,
+                FaultTrigger::Probability { probability, .. } => {
+                    // xorshift64
+                    *state ^= *state << 13;
+                    *state ^= *state >> 7;
+                    *state ^= *state << 17;
+                    ((*state >> 11) as f64 / (1u64 << 53) as f64) < probability as f64
+                }
+                FaultTrigger::MinSize(min_size) => site != FaultSite::Submit && size >= min_size,
+            };
+            if fires && fault != Some(FaultKind::Lost) {
+                fault = Some(rule.kind);
+            }
+        }
+
+        match fault {
+            None => Ok(()),
+            Some(FaultKind::OutOfMemory) => Err(hal::DeviceError::OutOfMemory),
+            Some(FaultKind::Lost) => {
+                log::warn!("Injecting device loss at {site:?} call {call}");
+                self.lost = true;
+                Err(hal::DeviceError::Lost)
+            }
+        }
+    }
+}
+
+/// Estimates the bytes a texture created from `desc` occupies, for
+/// [`FaultTrigger::MinSize`].
+pub(crate) fn texture_size(desc: &crate::resource::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    let block_size = desc.format.block_size(None).unwrap_or(4) as u64;
+    let layers = desc.array_layer_count() as u64;
+    (0..desc.mip_level_count)
+        .map(|level| {
+            let size = desc.mip_level_size(level).unwrap_or_default();
+            let blocks_wide = ((size.width + block_width - 1) / block_width) as u64;
+            let blocks_high = ((size.height + block_height - 1) / block_height) as u64;
+            let depth = match desc.dimension {
+                wgt::TextureDimension::D3 => size.depth_or_array_layers as u64,
+                _ => 1,
+            };
+            blocks_wide * blocks_high * depth * block_size
+        })
+        .sum::<u64>()
+        * layers
+        * desc.sample_count as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(site: FaultSite, trigger: FaultTrigger, kind: FaultKind) -> FaultRule {
+        FaultRule {
+            site,
+            trigger,
+            kind,
+        }
+    }
+
+    #[test]
+    fn nth_and_from() {
+        let mut injector = FaultInjector::new([
+            rule(
+                FaultSite::CreateBuffer,
+                FaultTrigger::Nth(2),
+                FaultKind::OutOfMemory,
+            ),
+            rule(
+                FaultSite::CreateTexture,
+                FaultTrigger::From(3),
+                FaultKind::OutOfMemory,
+            ),
+        ]);
+        let buffers = (0..3)
+            .map(|_| injector.check(FaultSite::CreateBuffer, 4).is_err())
+            .collect::<Vec<_>>();
+        assert_eq!(buffers, [false, true, false]);
+        let textures = (0..4)
+            .map(|_| injector.check(FaultSite::CreateTexture, 4).is_err())
+            .collect::<Vec<_>>();
+        assert_eq!(textures, [false, false, true, true]);
+        assert_eq!(injector.call_count(FaultSite::CreateBuffer), 3);
+        assert_eq!(injector.call_count(FaultSite::Submit), 0);
+    }
+
+    #[test]
+    fn min_size() {
+        let mut injector = FaultInjector::new([rule(
+            FaultSite::CreateBuffer,
+            FaultTrigger::MinSize(1024),
+            FaultKind::OutOfMemory,
+        )]);
+        assert!(injector.check(FaultSite::CreateBuffer, 1023).is_ok());
+        assert!(matches!(
+            injector.check(FaultSite::CreateBuffer, 1024),
+            Err(hal::DeviceError::OutOfMemory)
+        ));
+    }
+
+    #[test]
+    fn probability_is_deterministic() {
+        let trigger = FaultTrigger::Probability {
+            probability: 0.5,
+            seed: 42,
+        };
+        let run = || {
+            let mut injector =
+                FaultInjector::new([rule(FaultSite::Submit, trigger, FaultKind::OutOfMemory)]);
+            (0..64)
+                .map(|_| injector.check(FaultSite::Submit, 0).is_err())
+                .collect::<Vec<_>>()
+        };
+        let first = run();
+        assert_eq!(first, run());
+        assert!(first.iter().any(|&failed| failed));
+        assert!(first.iter().any(|&failed| !failed));
+    }
+
+    /// An injected device loss loses the [`Device`], not just the injector.
+    ///
+    /// [`Device`]: crate::device::Device
+    #[test]
+    fn loss_loses_the_device() {
+        use crate::{
+            device::{Device, DeviceError},
+            id::{self, TypedId as _},
+            resource::{BufferDescriptor, CreateBufferError},
+        };
+
+        let open = hal::OpenDevice::<hal::api::Empty> {
+            device: hal::empty::Device::default(),
+            queue: hal::empty::Context,
+        };
+        let adapter = crate::Stored {
+            value: id::Valid(id::AdapterId::zip(0, 1, wgt::Backend::Empty)),
+            ref_count: crate::RefCount::new(),
+        };
+        let alignments = hal::Alignments {
+            buffer_copy_offset: wgt::BufferSize::new(4).unwrap(),
+            buffer_copy_pitch: wgt::BufferSize::new(4).unwrap(),
+        };
+        let device = Device::new(
+            open,
+            adapter,
+            alignments,
+            wgt::DownlevelCapabilities::default(),
+            &wgt::DeviceDescriptor::default(),
+            None,
+            wgt::InstanceFlags::empty(),
+        )
+        .unwrap();
+        *device.fault_injector.lock() = Some(FaultInjector::new([rule(
+            FaultSite::CreateBuffer,
+            FaultTrigger::Nth(2),
+            FaultKind::Lost,
+        )]));
+
+        let device_id = id::DeviceId::zip(0, 1, wgt::Backend::Empty);
+        let desc = BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgt::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        };
+        assert!(device.create_buffer(device_id, &desc, false).is_ok());
+        assert!(device.is_valid());
+        assert!(matches!(
+            device.create_buffer(device_id, &desc, false),
+            Err(CreateBufferError::Device(DeviceError::Lost))
+        ));
+        assert!(!device.is_valid());
+    }
+
+    #[test]
+    fn loss_is_sticky() {
+        let mut injector = FaultInjector::new([rule(
+            FaultSite::Submit,
+            FaultTrigger::Nth(1),
+            FaultKind::Lost,
+        )]);
+        assert!(injector.check(FaultSite::CreateBuffer, 4).is_ok());
+        assert!(matches!(
+            injector.check(FaultSite::Submit, 0),
+            Err(hal::DeviceError::Lost)
+        ));
+        assert!(matches!(
+            injector.check(FaultSite::CreateBuffer, 4),
+            Err(hal::DeviceError::Lost)
+        ));
+    }
+}
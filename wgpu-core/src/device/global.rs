@@ -3,7 +3,9 @@ use crate::device::trace;
 use crate::{
     binding_model::{self, BindGroupLayout},
     command, conv,
-    device::{life::WaitIdleError, map_buffer, queue, Device, DeviceError, HostMap},
+    device::{
+        life::WaitIdleError, live, map_buffer, queue, Device, DeviceError, HostMap, LiveResource,
+    },
     global::Global,
     hal_api::HalApi,
     hub::Token,
@@ -16,6 +18,7 @@ use crate::{
     resource::{BufferAccessError, BufferMapOperation, TextureClearMode},
     storage::InvalidId,
     validation::check_buffer_usage,
+    worker::JobFuture,
     FastHashMap, Label, LabelHelpers as _, Stored,
 };
 
@@ -24,7 +27,7 @@ use smallvec::SmallVec;
 
 use wgt::{BufferAddress, TextureFormat};
 
-use std::{borrow::Cow, iter, mem, ops::Range, ptr};
+use std::{borrow::Cow, iter, mem, ops::Range, ptr, sync::Arc};
 
 use super::{
     BufferMapPendingClosure, ImplicitPipelineIds, InvalidDevice, UserClosures, IMPLICIT_FAILURE,
@@ -105,7 +108,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (device_guard, _) = hub.devices.read(&mut token);
         let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
-        if !device.valid {
+        if !device.is_valid() {
             return Err(InvalidDevice);
         }
 
@@ -120,7 +123,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (device_guard, _) = hub.devices.read(&mut token);
         let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
-        if !device.valid {
+        if !device.is_valid() {
             return Err(InvalidDevice);
         }
 
@@ -135,13 +138,67 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (device_guard, _) = hub.devices.read(&mut token);
         let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
-        if !device.valid {
+        if !device.is_valid() {
             return Err(InvalidDevice);
         }
 
         Ok(device.downlevel.clone())
     }
 
+    /// Returns the memory the device's backend has allocated for buffers and
+    /// textures, along with per-heap usage and budget.
+    ///
+    /// Unlike [`Global::generate_report`], which counts registry slots, this
+    /// reports bytes, as tracked by the wgpu-hal allocator.
+    pub fn device_memory_report<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<wgt::MemoryReport, InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (device_guard, _) = hub.devices.read(&mut token);
+        let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
+        if !device.is_valid() {
+            return Err(InvalidDevice);
+        }
+
+        Ok(device.raw.memory_report())
+    }
+
+    /// Lists the resources `device_id` still tracks.
+    ///
+    /// Release builds only keep resource labels if the instance was created
+    /// with [`wgt::InstanceFlags::TRACK_LIVE_RESOURCES`]. Creation backtraces
+    /// additionally need [`wgt::InstanceFlags::RESOURCE_BACKTRACES`].
+    pub fn device_live_resources<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<Vec<LiveResource>, InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
+
+        Ok(live::collect(hub, device_id, device, &mut token))
+    }
+
+    /// Installs `injector` on `device_id`, replacing any previous one, or
+    /// removes it if `injector` is `None`.
+    #[cfg(feature = "fault-injection")]
+    pub fn device_set_fault_injector<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        injector: Option<super::fault::FaultInjector>,
+    ) -> Result<(), InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (device_guard, _) = hub.devices.read(&mut token);
+        let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
+
+        *device.fault_injector.lock() = injector;
+        Ok(())
+    }
+
     pub fn device_create_buffer<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -160,7 +217,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -171,14 +228,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
             #[cfg(feature = "trace")]
             if let Some(ref trace) = device.trace {
-                let mut desc = desc.clone();
-                let mapped_at_creation = mem::replace(&mut desc.mapped_at_creation, false);
-                if mapped_at_creation && !desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
-                    desc.usage |= wgt::BufferUsages::COPY_DST;
-                }
                 trace
                     .lock()
-                    .add(trace::Action::CreateBuffer(fid.id(), desc));
+                    .add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
             }
 
             let mut buffer = match device.create_buffer(device_id, desc, false) {
@@ -381,7 +433,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let device = device_guard
             .get(device_id)
             .map_err(|_| DeviceError::Invalid)?;
-        if !device.valid {
+        if !device.is_valid() {
             return Err(DeviceError::Lost.into());
         }
         let buffer = buffer_guard
@@ -441,7 +493,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let device = device_guard
             .get(device_id)
             .map_err(|_| DeviceError::Invalid)?;
-        if !device.valid {
+        if !device.is_valid() {
             return Err(DeviceError::Lost.into());
         }
         let buffer = buffer_guard
@@ -607,7 +659,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
             #[cfg(feature = "trace")]
@@ -665,7 +717,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -744,7 +796,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1013,7 +1065,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1076,6 +1128,107 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .push(id::Valid(sampler_id));
     }
 
+    /// Create an external texture from `planes`, whose number and formats
+    /// must match `desc.format`.
+    pub fn device_create_external_texture<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::ExternalTextureDescriptor,
+        planes: &[id::TextureViewId],
+        id_in: Input<G, id::ExternalTextureId>,
+    ) -> (
+        id::ExternalTextureId,
+        Option<resource::CreateExternalTextureError>,
+    ) {
+        profiling::scope!("Device::create_external_texture");
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let fid = hub.external_textures.prepare(id_in);
+
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        let error = loop {
+            let device = match device_guard.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref trace) = device.trace {
+                trace.lock().add(trace::Action::CreateExternalTexture {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                    planes: planes.to_vec(),
+                });
+            }
+
+            let (texture_guard, mut token) = hub.textures.read(&mut token);
+            let (texture_view_guard, mut token) = hub.texture_views.read(&mut token);
+            let external_texture = match device.create_external_texture(
+                device_id,
+                desc,
+                planes,
+                &texture_guard,
+                &texture_view_guard,
+            ) {
+                Ok(external_texture) => external_texture,
+                Err(e) => break e,
+            };
+            let ref_count = external_texture.life_guard.add_ref();
+            let id = fid.assign(external_texture, &mut token);
+
+            device
+                .trackers
+                .lock()
+                .external_textures
+                .insert_single(id, ref_count);
+
+            log::trace!("Device::create_external_texture -> {:?}", id.0);
+
+            return (id.0, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default(), &mut token);
+        (id, Some(error))
+    }
+
+    pub fn external_texture_label<A: HalApi>(&self, id: id::ExternalTextureId) -> String {
+        A::hub(self).external_textures.label_for_resource(id)
+    }
+
+    pub fn external_texture_drop<A: HalApi>(&self, external_texture_id: id::ExternalTextureId) {
+        profiling::scope!("ExternalTexture::drop");
+        log::trace!("ExternalTexture::drop {external_texture_id:?}");
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+
+        let device_id = {
+            let (mut external_texture_guard, _) = hub.external_textures.write(&mut token);
+            match external_texture_guard.get_mut(external_texture_id) {
+                Ok(external_texture) => {
+                    external_texture.life_guard.ref_count.take();
+                    external_texture.device_id.value
+                }
+                Err(InvalidId) => {
+                    hub.external_textures
+                        .unregister_locked(external_texture_id, &mut *external_texture_guard);
+                    return;
+                }
+            }
+        };
+
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        device_guard[device_id]
+            .lock_life(&mut token)
+            .suspected_resources
+            .external_textures
+            .push(id::Valid(external_texture_id));
+    }
+
     pub fn device_create_bind_group_layout<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -1097,7 +1250,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1228,7 +1381,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1314,7 +1467,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1425,7 +1578,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1497,7 +1650,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1571,7 +1724,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid,
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost;
             }
 
@@ -1674,7 +1827,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break command::RenderBundleError::INVALID_DEVICE,
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break command::RenderBundleError::INVALID_DEVICE;
             }
 
@@ -1745,6 +1898,107 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .push(id::Valid(render_bundle_id));
     }
 
+    pub fn device_create_compute_bundle_encoder(
+        &self,
+        device_id: DeviceId,
+        desc: &command::ComputeBundleEncoderDescriptor,
+    ) -> id::ComputeBundleEncoderId {
+        profiling::scope!("Device::create_compute_bundle_encoder");
+        log::trace!("Device::device_create_compute_bundle_encoder");
+        let encoder = command::ComputeBundleEncoder::new(desc, device_id, None);
+        Box::into_raw(Box::new(encoder))
+    }
+
+    pub fn compute_bundle_encoder_finish<A: HalApi>(
+        &self,
+        bundle_encoder: command::ComputeBundleEncoder,
+        desc: &command::ComputeBundleDescriptor,
+        id_in: Input<G, id::ComputeBundleId>,
+    ) -> (id::ComputeBundleId, Option<command::ComputeBundleError>) {
+        profiling::scope!("ComputeBundleEncoder::finish");
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let fid = hub.compute_bundles.prepare(id_in);
+
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        let error = loop {
+            let device = match device_guard.get(bundle_encoder.parent()) {
+                Ok(device) => device,
+                Err(_) => break command::ComputeBundleError::INVALID_DEVICE,
+            };
+            if !device.is_valid() {
+                break command::ComputeBundleError::INVALID_DEVICE;
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref trace) = device.trace {
+                trace.lock().add(trace::Action::CreateComputeBundle {
+                    id: fid.id(),
+                    desc: wgt::ComputeBundleEncoderDescriptor {
+                        label: desc.label.clone(),
+                    },
+                    base: bundle_encoder.to_base_pass(),
+                });
+            }
+
+            let compute_bundle = match bundle_encoder.finish(desc, device, hub, &mut token) {
+                Ok(bundle) => bundle,
+                Err(e) => break e,
+            };
+
+            log::debug!("Compute bundle");
+            let ref_count = compute_bundle.life_guard.add_ref();
+            let id = fid.assign(compute_bundle, &mut token);
+
+            device
+                .trackers
+                .lock()
+                .compute_bundles
+                .insert_single(id, ref_count);
+
+            log::trace!("ComputeBundleEncoder::finish -> {:?}", id.0);
+
+            return (id.0, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default(), &mut token);
+        (id, Some(error))
+    }
+
+    pub fn compute_bundle_label<A: HalApi>(&self, id: id::ComputeBundleId) -> String {
+        A::hub(self).compute_bundles.label_for_resource(id)
+    }
+
+    pub fn compute_bundle_drop<A: HalApi>(&self, compute_bundle_id: id::ComputeBundleId) {
+        profiling::scope!("ComputeBundle::drop");
+        log::trace!("ComputeBundle::drop {:?}", compute_bundle_id);
+        let hub = A::hub(self);
+        let mut token = Token::root();
+
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        let device_id = {
+            let (mut bundle_guard, _) = hub.compute_bundles.write(&mut token);
+            match bundle_guard.get_mut(compute_bundle_id) {
+                Ok(bundle) => {
+                    bundle.life_guard.ref_count.take();
+                    bundle.device_id.value
+                }
+                Err(InvalidId) => {
+                    hub.compute_bundles
+                        .unregister_locked(compute_bundle_id, &mut *bundle_guard);
+                    return;
+                }
+            }
+        };
+
+        device_guard[device_id]
+            .lock_life(&mut token)
+            .suspected_resources
+            .compute_bundles
+            .push(id::Valid(compute_bundle_id));
+    }
+
     pub fn device_create_query_set<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -1763,7 +2017,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1860,7 +2114,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -1921,6 +2175,43 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Creates a render pipeline on one of the worker threads of this
+    /// `Global`, rather than the calling thread.
+    ///
+    /// Shader translation and driver compilation happen on the worker. The
+    /// returned future resolves to the same results as
+    /// [`Global::device_create_render_pipeline`].
+    /// If the future is dropped before it resolves, the pipeline is dropped
+    /// once it's created.
+    pub fn device_create_render_pipeline_async<A: HalApi>(
+        self: &Arc<Self>,
+        device_id: DeviceId,
+        desc: pipeline::RenderPipelineDescriptor<'static>,
+        id_in: Input<G, id::RenderPipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<'static, G>>,
+    ) -> JobFuture<(
+        id::RenderPipelineId,
+        Option<pipeline::CreateRenderPipelineError>,
+    )>
+    where
+        Self: Send + Sync + 'static,
+        Input<G, id::RenderPipelineId>: Send,
+        ImplicitPipelineIds<'static, G>: Send,
+    {
+        let global = Arc::clone(self);
+        let cleanup = Arc::clone(self);
+        self.workers
+            .spawn(move || {
+                global.device_create_render_pipeline::<A>(
+                    device_id,
+                    &desc,
+                    id_in,
+                    implicit_pipeline_ids,
+                )
+            })
+            .on_cancel(move |(id, _)| cleanup.render_pipeline_drop::<A>(id))
+    }
+
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
     /// which needs to be released by calling `bind_group_layout_drop`.
     pub fn render_pipeline_get_bind_group_layout<A: HalApi>(
@@ -2051,7 +2342,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 Ok(device) => device,
                 Err(_) => break DeviceError::Invalid.into(),
             };
-            if !device.valid {
+            if !device.is_valid() {
                 break DeviceError::Lost.into();
             }
 
@@ -2108,6 +2399,39 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Creates a compute pipeline on one of the worker threads of this
+    /// `Global`, rather than the calling thread.
+    ///
+    /// See [`Global::device_create_render_pipeline_async`].
+    pub fn device_create_compute_pipeline_async<A: HalApi>(
+        self: &Arc<Self>,
+        device_id: DeviceId,
+        desc: pipeline::ComputePipelineDescriptor<'static>,
+        id_in: Input<G, id::ComputePipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<'static, G>>,
+    ) -> JobFuture<(
+        id::ComputePipelineId,
+        Option<pipeline::CreateComputePipelineError>,
+    )>
+    where
+        Self: Send + Sync + 'static,
+        Input<G, id::ComputePipelineId>: Send,
+        ImplicitPipelineIds<'static, G>: Send,
+    {
+        let global = Arc::clone(self);
+        let cleanup = Arc::clone(self);
+        self.workers
+            .spawn(move || {
+                global.device_create_compute_pipeline::<A>(
+                    device_id,
+                    &desc,
+                    id_in,
+                    implicit_pipeline_ids,
+                )
+            })
+            .on_cancel(move |(id, _)| cleanup.compute_pipeline_drop::<A>(id))
+    }
+
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
     /// which needs to be released by calling `bind_group_layout_drop`.
     pub fn compute_pipeline_get_bind_group_layout<A: HalApi>(
@@ -2349,7 +2673,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     Ok(device) => device,
                     Err(_) => break DeviceError::Invalid.into(),
                 };
-                if !device.valid {
+                if !device.is_valid() {
                     break DeviceError::Lost.into();
                 }
 
@@ -2490,7 +2814,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (device_guard, mut token) = hub.devices.read(&mut token);
         let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
-        if !device.valid {
+        if !device.is_valid() {
             return Err(InvalidDevice);
         }
         device.lock_life(&mut token).triage_suspected(
@@ -2634,7 +2958,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (device_guard, _) = hub.devices.read(&mut token);
         if let Ok(device) = device_guard.get(id) {
-            if !device.valid {
+            if !device.is_valid() {
                 return;
             }
             unsafe { device.raw.start_capture() };
@@ -2648,7 +2972,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (device_guard, _) = hub.devices.read(&mut token);
         if let Ok(device) = device_guard.get(id) {
-            if !device.valid {
+            if !device.is_valid() {
                 return;
             }
             unsafe { device.raw.stop_capture() };
@@ -2662,6 +2986,20 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let hub = A::hub(self);
         let mut token = Token::root();
 
+        {
+            let (device_guard, mut token) = hub.devices.read(&mut token);
+            if let Ok(device) = device_guard.get(device_id) {
+                if device
+                    .instance_flags
+                    .contains(wgt::InstanceFlags::TRACK_LIVE_RESOURCES)
+                {
+                    for resource in live::collect(hub, device_id, device, &mut token) {
+                        log::warn!("{device_id:?} dropped while {resource} is alive");
+                    }
+                }
+            }
+        }
+
         // For now, just drop the `RefCount` in `device.life_guard`, which
         // stands for the user's reference to the device. We'll take care of
         // cleaning up the device when we're polled, once its queue submissions
@@ -2686,7 +3024,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             // It's legal to call destroy multiple times, but if the device
             // is already invalid, there's nothing more to do. There's also
             // no need to return an error.
-            if !device.valid {
+            if !device.is_valid() {
                 return;
             }
 
@@ -2803,7 +3141,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             };
 
             let device = &device_guard[buffer.device_id.value];
-            if !device.valid {
+            if !device.is_valid() {
                 return Err((op, DeviceError::Lost.into()));
             }
 
@@ -2952,11 +3290,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     let data = trace.make_binary("bin", unsafe {
                         std::slice::from_raw_parts(ptr.as_ptr(), buffer.size as usize)
                     });
-                    trace.add(trace::Action::WriteBuffer {
+                    trace.add(trace::Action::UnmapBuffer {
                         id: buffer_id,
                         data,
                         range: 0..buffer.size,
-                        queued: true,
                     });
                 }
                 let _ = ptr;
@@ -3013,11 +3350,10 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         let data = trace.make_binary("bin", unsafe {
                             std::slice::from_raw_parts(ptr.as_ptr(), size as usize)
                         });
-                        trace.add(trace::Action::WriteBuffer {
+                        trace.add(trace::Action::UnmapBuffer {
                             id: buffer_id,
                             data,
                             range: range.clone(),
-                            queued: false,
                         });
                     }
                     let _ = (ptr, range);
@@ -3049,7 +3385,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 .get_mut(buffer_id)
                 .map_err(|_| BufferAccessError::Invalid)?;
             let device = &mut device_guard[buffer.device_id.value];
-            if !device.valid {
+            if !device.is_valid() {
                 return Err(DeviceError::Lost.into());
             }
 
@@ -10,7 +10,7 @@ use crate::{
     id,
     identity::GlobalIdentityHandlerFactory,
     resource,
-    track::{BindGroupStates, RenderBundleScope, Tracker},
+    track::{BindGroupStates, ComputeBundleScope, RenderBundleScope, Tracker},
     RefCount, Stored, SubmissionIndex,
 };
 use smallvec::SmallVec;
@@ -27,6 +27,7 @@ pub(super) struct SuspectedResources {
     pub(super) buffers: Vec<id::Valid<id::BufferId>>,
     pub(super) textures: Vec<id::Valid<id::TextureId>>,
     pub(super) texture_views: Vec<id::Valid<id::TextureViewId>>,
+    pub(super) external_textures: Vec<id::Valid<id::ExternalTextureId>>,
     pub(super) samplers: Vec<id::Valid<id::SamplerId>>,
     pub(super) bind_groups: Vec<id::Valid<id::BindGroupId>>,
     pub(super) compute_pipelines: Vec<id::Valid<id::ComputePipelineId>>,
@@ -34,6 +35,7 @@ pub(super) struct SuspectedResources {
     pub(super) bind_group_layouts: Vec<id::Valid<id::BindGroupLayoutId>>,
     pub(super) pipeline_layouts: Vec<Stored<id::PipelineLayoutId>>,
     pub(super) render_bundles: Vec<id::Valid<id::RenderBundleId>>,
+    pub(super) compute_bundles: Vec<id::Valid<id::ComputeBundleId>>,
     pub(super) query_sets: Vec<id::Valid<id::QuerySetId>>,
 }
 
@@ -42,6 +44,7 @@ impl SuspectedResources {
         self.buffers.clear();
         self.textures.clear();
         self.texture_views.clear();
+        self.external_textures.clear();
         self.samplers.clear();
         self.bind_groups.clear();
         self.compute_pipelines.clear();
@@ -49,6 +52,7 @@ impl SuspectedResources {
         self.bind_group_layouts.clear();
         self.pipeline_layouts.clear();
         self.render_bundles.clear();
+        self.compute_bundles.clear();
         self.query_sets.clear();
     }
 
@@ -56,6 +60,8 @@ impl SuspectedResources {
         self.buffers.extend_from_slice(&other.buffers);
         self.textures.extend_from_slice(&other.textures);
         self.texture_views.extend_from_slice(&other.texture_views);
+        self.external_textures
+            .extend_from_slice(&other.external_textures);
         self.samplers.extend_from_slice(&other.samplers);
         self.bind_groups.extend_from_slice(&other.bind_groups);
         self.compute_pipelines
@@ -67,6 +73,8 @@ impl SuspectedResources {
         self.pipeline_layouts
             .extend_from_slice(&other.pipeline_layouts);
         self.render_bundles.extend_from_slice(&other.render_bundles);
+        self.compute_bundles
+            .extend_from_slice(&other.compute_bundles);
         self.query_sets.extend_from_slice(&other.query_sets);
     }
 
@@ -79,10 +87,19 @@ impl SuspectedResources {
         self.query_sets.extend(trackers.query_sets.used());
     }
 
+    pub(super) fn add_compute_bundle_scope<A: HalApi>(&mut self, trackers: &ComputeBundleScope<A>) {
+        self.buffers.extend(trackers.buffers.used());
+        self.bind_groups.extend(trackers.bind_groups.used());
+        self.compute_pipelines
+            .extend(trackers.compute_pipelines.used());
+    }
+
     pub(super) fn add_bind_group_states<A: HalApi>(&mut self, trackers: &BindGroupStates<A>) {
         self.buffers.extend(trackers.buffers.used());
         self.textures.extend(trackers.textures.used());
         self.texture_views.extend(trackers.views.used());
+        self.external_textures
+            .extend(trackers.external_textures.used());
         self.samplers.extend(trackers.samplers.used());
     }
 }
@@ -537,6 +554,25 @@ impl<A: HalApi> LifetimeTracker<A> {
             }
         }
 
+        if !self.suspected_resources.compute_bundles.is_empty() {
+            let (mut guard, _) = hub.compute_bundles.write(token);
+            let mut trackers = trackers.lock();
+
+            while let Some(id) = self.suspected_resources.compute_bundles.pop() {
+                if trackers.compute_bundles.remove_abandoned(id) {
+                    log::debug!("Compute bundle {:?} will be destroyed", id);
+                    #[cfg(feature = "trace")]
+                    if let Some(t) = trace {
+                        t.lock().add(trace::Action::DestroyComputeBundle(id.0));
+                    }
+
+                    if let Some(res) = hub.compute_bundles.unregister_locked(id.0, &mut *guard) {
+                        self.suspected_resources.add_compute_bundle_scope(&res.used);
+                    }
+                }
+            }
+        }
+
         if !self.suspected_resources.bind_groups.is_empty() {
             let (mut guard, _) = hub.bind_groups.write(token);
             let mut trackers = trackers.lock();
@@ -568,6 +604,35 @@ impl<A: HalApi> LifetimeTracker<A> {
             }
         }
 
+        if !self.suspected_resources.external_textures.is_empty() {
+            let (mut guard, _) = hub.external_textures.write(token);
+            let mut trackers = trackers.lock();
+
+            for id in self.suspected_resources.external_textures.drain(..) {
+                if trackers.external_textures.remove_abandoned(id) {
+                    log::debug!("External texture {:?} will be destroyed", id);
+                    #[cfg(feature = "trace")]
+                    if let Some(t) = trace {
+                        t.lock().add(trace::Action::DestroyExternalTexture(id.0));
+                    }
+
+                    if let Some(res) = hub.external_textures.unregister_locked(id.0, &mut *guard)
+                    {
+                        self.suspected_resources
+                            .texture_views
+                            .extend(res.planes.iter().map(|plane| plane.value));
+                        let submit_index = res.life_guard.life_count();
+                        self.active
+                            .iter_mut()
+                            .find(|a| a.index == submit_index)
+                            .map_or(&mut self.free_resources, |a| &mut a.last_resources)
+                            .buffers
+                            .push(res.params);
+                    }
+                }
+            }
+        }
+
         if !self.suspected_resources.texture_views.is_empty() {
             let (mut guard, _) = hub.texture_views.write(token);
             let mut trackers = trackers.lock();
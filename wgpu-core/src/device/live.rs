@@ -0,0 +1,441 @@
+//! Reporting of the resources still alive on a device.
+//!
+//! When [`wgt::InstanceFlags::TRACK_LIVE_RESOURCES`] is set, every resource
+//! created by a device records a [`ResourceOrigin`] in its [`LifeGuard`]. This
+//! lets [`Global::device_live_resources`] describe each resource the device
+//! still tracks, which is also logged when the device is dropped, to help find
+//! leaks.
+//!
+//! [`LifeGuard`]: crate::LifeGuard
+//! [`Global::device_live_resources`]: crate::global::Global::device_live_resources
+
+use std::{backtrace::Backtrace, fmt};
+
+use crate::{
+    device::Device,
+    hal_api::HalApi,
+    hub::{Hub, Token},
+    id::{self, TypedId},
+    identity::GlobalIdentityHandlerFactory,
+    resource::Resource,
+    storage::Storage,
+    Epoch, FastHashMap, Index,
+};
+
+/// Creation details of a resource, kept for leak reporting.
+#[derive(Debug)]
+pub(crate) struct ResourceOrigin {
+    /// The `label` from the descriptor used to create the resource.
+    ///
+    /// Debug builds always keep it in `LifeGuard::label` instead.
+    #[cfg(not(debug_assertions))]
+    pub(crate) label: String,
+    /// The call stack that created the resource, if
+    /// [`wgt::InstanceFlags::RESOURCE_BACKTRACES`] is set.
+    pub(crate) backtrace: Option<Backtrace>,
+}
+
+impl ResourceOrigin {
+    #[allow(unused_variables)]
+    pub(crate) fn capture(label: &str, backtrace: bool) -> Self {
+        Self {
+            #[cfg(not(debug_assertions))]
+            label: label.to_string(),
+            backtrace: backtrace.then(Backtrace::force_capture),
+        }
+    }
+}
+
+/// A resource still alive on a device.
+#[derive(Clone, Debug)]
+pub struct LiveResource {
+    /// The type of the resource, such as `"Buffer"`.
+    pub kind: &'static str,
+    /// The index of the resource's id.
+    pub index: Index,
+    /// The epoch of the resource's id.
+    pub epoch: Epoch,
+    /// The label the resource was created with.
+    pub label: String,
+    /// Where the resource was created, if resource backtraces are enabled.
+    pub backtrace: Option<String>,
+    /// Whether the user still holds the resource, as opposed to having dropped it.
+    ///
+    /// Always `true` for bind group layouts and shader modules, which don't
+    /// keep track of this.
+    pub user_held: bool,
+    /// The bind groups, bundles, pipeline layouts and external textures that
+    /// reference this resource.
+    pub held_by: Vec<String>,
+}
+
+impl LiveResource {
+    /// Returns `true` if the user dropped this resource, but it is kept alive by
+    /// other resources that reference it.
+    pub fn is_held_only_by_references(&self) -> bool {
+        !self.user_held && !self.held_by.is_empty()
+    }
+}
+
+impl fmt::Display for LiveResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", describe(self.kind, self.index, &self.label))?;
+        if !self.user_held {
+            write!(f, " (dropped by the user)")?;
+        }
+        if !self.held_by.is_empty() {
+            write!(f, ", held by {}", self.held_by.join(", "))?;
+        }
+        if let Some(ref backtrace) = self.backtrace {
+            write!(f, ", created at:\n{backtrace}")?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(kind: &str, index: Index, label: &str) -> String {
+    if label.is_empty() {
+        format!("{kind} #{index}")
+    } else {
+        format!("{kind} #{index} {label:?}")
+    }
+}
+
+/// Resources referencing other resources, keyed by the type and index of the
+/// referenced resource.
+type References = FastHashMap<(&'static str, Index), Vec<String>>;
+
+fn add_references<T: Resource, I: TypedId>(
+    references: &mut References,
+    holder: String,
+    ids: impl Iterator<Item = id::Valid<I>>,
+) {
+    for id in ids {
+        let (index, _, _) = id.0.unzip();
+        references
+            .entry((T::TYPE, index))
+            .or_default()
+            .push(holder.clone());
+    }
+}
+
+fn collect_storage<T: Resource, I: TypedId + Copy>(
+    live: &mut Vec<LiveResource>,
+    references: &References,
+    storage: &Storage<T, I>,
+    ids: impl Iterator<Item = id::Valid<I>>,
+) {
+    for id in ids {
+        if let Ok(resource) = storage.get(id.0) {
+            live.push(live_resource(
+                references,
+                id.0,
+                resource,
+                Some(resource.life_guard()),
+            ));
+        }
+    }
+}
+
+/// Like [`collect_storage`], for the resources of `device_id` that the device
+/// doesn't track. `owner` returns the device of a resource, and its
+/// [`LifeGuard`] if it has one.
+///
+/// [`LifeGuard`]: crate::LifeGuard
+fn collect_owned<T: Resource, I: TypedId + Copy>(
+    live: &mut Vec<LiveResource>,
+    references: &References,
+    storage: &Storage<T, I>,
+    device_id: id::DeviceId,
+    owner: impl Fn(&T) -> (id::DeviceId, Option<&crate::LifeGuard>),
+) {
+    for (id, resource) in storage.iter(device_id.backend()) {
+        let (owner_id, life_guard) = owner(resource);
+        if owner_id == device_id {
+            live.push(live_resource(references, id, resource, life_guard));
+        }
+    }
+}
+
+fn live_resource<T: Resource, I: TypedId>(
+    references: &References,
+    id: I,
+    resource: &T,
+    life_guard: Option<&crate::LifeGuard>,
+) -> LiveResource {
+    let (index, epoch, _) = id.unzip();
+    let origin = life_guard.and_then(|life_guard| life_guard.origin.as_ref());
+    LiveResource {
+        kind: T::TYPE,
+        index,
+        epoch,
+        label: resource.label().to_string(),
+        backtrace: origin
+            .and_then(|origin| origin.backtrace.as_ref())
+            .map(ToString::to_string),
+        user_held: life_guard.map_or(true, |life_guard| life_guard.ref_count.is_some()),
+        held_by: references
+            .get(&(T::TYPE, index))
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+/// Lists the resources of `device`.
+pub(crate) fn collect<A: HalApi, G: GlobalIdentityHandlerFactory>(
+    hub: &Hub<A, G>,
+    device_id: id::DeviceId,
+    device: &Device<A>,
+    token: &mut Token<Device<A>>,
+) -> Vec<LiveResource> {
+    let mut live = Vec::new();
+    {
+        // Shader modules come late in the lock order, and nothing holds on
+        // to them, so they are listed on their own.
+        let (shader_module_guard, _) = hub.shader_modules.read(token);
+        collect_owned(
+            &mut live,
+            &References::default(),
+            &*shader_module_guard,
+            device_id,
+            |module| (module.device_id.value.0, None),
+        );
+    }
+
+    let (bundle_guard, mut token) = hub.render_bundles.read(token);
+    let (compute_bundle_guard, mut token) = hub.compute_bundles.read(&mut token);
+    let (pipeline_layout_guard, mut token) = hub.pipeline_layouts.read(&mut token);
+    let (bind_group_layout_guard, mut token) = hub.bind_group_layouts.read(&mut token);
+    let (bind_group_guard, mut token) = hub.bind_groups.read(&mut token);
+    let (compute_pipeline_guard, mut token) = hub.compute_pipelines.read(&mut token);
+    let (render_pipeline_guard, mut token) = hub.render_pipelines.read(&mut token);
+    let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
+    let (buffer_guard, mut token) = hub.buffers.read(&mut token);
+    let (texture_guard, mut token) = hub.textures.read(&mut token);
+    let (texture_view_guard, mut token) = hub.texture_views.read(&mut token);
+    let (external_texture_guard, mut token) = hub.external_textures.read(&mut token);
+    let (sampler_guard, _) = hub.samplers.read(&mut token);
+
+    let trackers = device.trackers.lock();
+
+    let mut references = References::default();
+    for id in trackers.bind_groups.used() {
+        let bind_group = match bind_group_guard.get(id.0) {
+            Ok(bind_group) => bind_group,
+            Err(_) => continue,
+        };
+        let holder = describe(
+            crate::binding_model::BindGroup::<A>::TYPE,
+            id.0.unzip().0,
+            bind_group.label(),
+        );
+        add_references::<crate::binding_model::BindGroupLayout<A>, _>(
+            &mut references,
+            holder.clone(),
+            std::iter::once(bind_group.layout_id),
+        );
+        let used = &bind_group.used;
+        add_references::<crate::resource::Buffer<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.buffers.used(),
+        );
+        add_references::<crate::resource::Texture<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.textures.used(),
+        );
+        add_references::<crate::resource::TextureView<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.views.used(),
+        );
+        add_references::<crate::resource::Sampler<A>, _>(
+            &mut references,
+            holder,
+            used.samplers.used(),
+        );
+    }
+    for (id, layout) in pipeline_layout_guard.iter(device_id.backend()) {
+        if layout.device_id.value.0 != device_id {
+            continue;
+        }
+        let holder = describe(
+            crate::binding_model::PipelineLayout::<A>::TYPE,
+            id.unzip().0,
+            layout.label(),
+        );
+        add_references::<crate::binding_model::BindGroupLayout<A>, _>(
+            &mut references,
+            holder,
+            layout.bind_group_layout_ids.iter().cloned(),
+        );
+    }
+    for (id, external_texture) in external_texture_guard.iter(device_id.backend()) {
+        if external_texture.device_id.value.0 != device_id {
+            continue;
+        }
+        let holder = describe(
+            crate::resource::ExternalTexture::<A>::TYPE,
+            id.unzip().0,
+            external_texture.label(),
+        );
+        add_references::<crate::resource::TextureView<A>, _>(
+            &mut references,
+            holder,
+            external_texture.planes.iter().map(|plane| plane.value),
+        );
+    }
+    for id in trackers.bundles.used() {
+        let bundle = match bundle_guard.get(id.0) {
+            Ok(bundle) => bundle,
+            Err(_) => continue,
+        };
+        let holder = describe(
+            crate::command::RenderBundle::<A>::TYPE,
+            id.0.unzip().0,
+            bundle.label(),
+        );
+        let used = &bundle.used;
+        add_references::<crate::resource::Buffer<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.buffers.used(),
+        );
+        add_references::<crate::resource::Texture<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.textures.used(),
+        );
+        add_references::<crate::binding_model::BindGroup<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.bind_groups.used(),
+        );
+        add_references::<crate::pipeline::RenderPipeline<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.render_pipelines.used(),
+        );
+        add_references::<crate::resource::QuerySet<A>, _>(
+            &mut references,
+            holder,
+            used.query_sets.used(),
+        );
+    }
+    for id in trackers.compute_bundles.used() {
+        let bundle = match compute_bundle_guard.get(id.0) {
+            Ok(bundle) => bundle,
+            Err(_) => continue,
+        };
+        let holder = describe(
+            crate::command::ComputeBundle::<A>::TYPE,
+            id.0.unzip().0,
+            bundle.label(),
+        );
+        let used = &bundle.used;
+        add_references::<crate::resource::Buffer<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.buffers.used(),
+        );
+        add_references::<crate::binding_model::BindGroup<A>, _>(
+            &mut references,
+            holder.clone(),
+            used.bind_groups.used(),
+        );
+        add_references::<crate::pipeline::ComputePipeline<A>, _>(
+            &mut references,
+            holder,
+            used.compute_pipelines.used(),
+        );
+    }
+
+    collect_storage(
+        &mut live,
+        &references,
+        &*bundle_guard,
+        trackers.bundles.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*compute_bundle_guard,
+        trackers.compute_bundles.used(),
+    );
+    collect_owned(
+        &mut live,
+        &references,
+        &*pipeline_layout_guard,
+        device_id,
+        |layout| (layout.device_id.value.0, Some(&layout.life_guard)),
+    );
+    collect_owned(
+        &mut live,
+        &references,
+        &*bind_group_layout_guard,
+        device_id,
+        |layout| (layout.device_id.value.0, None),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*bind_group_guard,
+        trackers.bind_groups.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*compute_pipeline_guard,
+        trackers.compute_pipelines.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*render_pipeline_guard,
+        trackers.render_pipelines.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*query_set_guard,
+        trackers.query_sets.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*buffer_guard,
+        trackers.buffers.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*texture_guard,
+        trackers.textures.used(),
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*texture_view_guard,
+        trackers.views.used(),
+    );
+    collect_owned(
+        &mut live,
+        &references,
+        &*external_texture_guard,
+        device_id,
+        |external_texture| {
+            (
+                external_texture.device_id.value.0,
+                Some(&external_texture.life_guard),
+            )
+        },
+    );
+    collect_storage(
+        &mut live,
+        &references,
+        &*sampler_guard,
+        trackers.samplers.used(),
+    );
+    live
+}
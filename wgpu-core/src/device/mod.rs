@@ -17,13 +17,16 @@ use wgt::{BufferAddress, TextureFormat};
 
 use std::{iter, num::NonZeroU32, ptr};
 
+#[cfg(feature = "fault-injection")]
+pub mod fault;
 pub mod global;
 mod life;
+pub mod live;
 pub mod queue;
 pub mod resource;
 #[cfg(any(feature = "trace", feature = "replay"))]
 pub mod trace;
-pub use {life::WaitIdleError, resource::Device};
+pub use {life::WaitIdleError, live::LiveResource, resource::Device};
 
 pub const SHADER_STAGE_COUNT: usize = 3;
 // Should be large enough for the largest possible texture row. This
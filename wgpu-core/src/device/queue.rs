@@ -873,6 +873,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .get_mut(queue_id)
             .map_err(|_| DeviceError::Invalid)?;
 
+        // Not traced: traces are written to files, which the web doesn't
+        // have, and the source image lives in the browser.
+
         if size.width == 0 || size.height == 0 || size.depth_or_array_layers == 0 {
             log::trace!("Ignoring write_texture of size 0");
             return Ok(());
@@ -1087,6 +1090,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     profiling::scope!("prepare");
 
                     let (render_bundle_guard, mut token) = hub.render_bundles.read(&mut token);
+                    let (compute_bundle_guard, mut token) = hub.compute_bundles.read(&mut token);
                     let (_, mut token) = hub.pipeline_layouts.read(&mut token);
                     let (bind_group_guard, mut token) = hub.bind_groups.read(&mut token);
                     let (compute_pipe_guard, mut token) = hub.compute_pipelines.read(&mut token);
@@ -1094,6 +1098,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     let (mut buffer_guard, mut token) = hub.buffers.write(&mut token);
                     let (mut texture_guard, mut token) = hub.textures.write(&mut token);
                     let (texture_view_guard, mut token) = hub.texture_views.read(&mut token);
+                    let (external_texture_guard, mut token) =
+                        hub.external_textures.read(&mut token);
                     let (sampler_guard, mut token) = hub.samplers.read(&mut token);
                     let (query_set_guard, _) = hub.query_sets.read(&mut token);
 
@@ -1209,6 +1215,11 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             for sub_id in bg.used.views.used() {
                                 texture_view_guard[sub_id].life_guard.use_at(submit_index);
                             }
+                            for sub_id in bg.used.external_textures.used() {
+                                external_texture_guard[sub_id]
+                                    .life_guard
+                                    .use_at(submit_index);
+                            }
                             for sub_id in bg.used.samplers.used() {
                                 sampler_guard[sub_id].life_guard.use_at(submit_index);
                             }
@@ -1244,6 +1255,13 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 query_set_guard[sub_id].life_guard.use_at(submit_index);
                             }
                         }
+                        // The stateless resources of compute bundles are already
+                        // tracked by the command buffer itself.
+                        for id in cmdbuf.trackers.compute_bundles.used() {
+                            if !compute_bundle_guard[id].life_guard.use_at(submit_index) {
+                                device.temp_suspected.compute_bundles.push(id);
+                            }
+                        }
 
                         let mut baked = cmdbuf.into_baked();
                         // execute resource transitions
@@ -1314,6 +1332,11 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     log::trace!("Device after submission {}", submit_index);
                 }
 
+                // The device can't be borrowed as a whole once its fields are,
+                // so the fault is drawn here, and reported where `submit` fails.
+                #[cfg(feature = "fault-injection")]
+                let injected_fault = device.inject_fault(super::fault::FaultSite::Submit, 0);
+
                 let super::Device {
                     ref mut pending_writes,
                     ref mut queue,
@@ -1390,6 +1413,8 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             .flat_map(|pool_execution| pool_execution.cmd_buffers.iter()),
                     )
                     .collect::<Vec<_>>();
+                #[cfg(feature = "fault-injection")]
+                injected_fault?;
                 unsafe {
                     queue
                         .submit(&refs, Some((fence, submit_index)))
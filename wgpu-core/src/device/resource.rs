@@ -1,3 +1,5 @@
+#[cfg(feature = "fault-injection")]
+use crate::device::fault;
 #[cfg(feature = "trace")]
 use crate::device::trace;
 use crate::{
@@ -7,6 +9,7 @@ use crate::{
     },
     command, conv,
     device::life::WaitIdleError,
+    device::live::ResourceOrigin,
     device::{
         AttachmentData, CommandAllocator, MissingDownlevelFlags, MissingFeatures,
         RenderPassContext, CLEANUP_WAIT_MS,
@@ -36,7 +39,12 @@ use smallvec::SmallVec;
 use thiserror::Error;
 use wgt::{TextureFormat, TextureSampleType, TextureViewDimension};
 
-use std::{borrow::Cow, iter, num::NonZeroU32};
+use std::{
+    borrow::Cow,
+    iter,
+    num::NonZeroU32,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use super::{
     life, queue, DeviceDescriptor, DeviceError, ImplicitPipelineContext, UserClosures, EP_FAILURE,
@@ -87,7 +95,7 @@ pub struct Device<A: HalApi> {
     /// Error enums, we wouldn't need this. For now, we need it. All the call
     /// sites where we check it are areas that should be revisited if we start
     /// using ref-counted references for internal access.
-    pub(crate) valid: bool,
+    valid: AtomicBool,
 
     /// All live resources allocated with this [`Device`].
     ///
@@ -109,6 +117,8 @@ pub struct Device<A: HalApi> {
     pub(super) pending_writes: queue::PendingWrites<A>,
     #[cfg(feature = "trace")]
     pub(crate) trace: Option<Mutex<trace::Trace>>,
+    #[cfg(feature = "fault-injection")]
+    pub(crate) fault_injector: Mutex<Option<fault::FaultInjector>>,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -139,6 +149,40 @@ impl<A: HalApi> Device<A> {
             Err(MissingDownlevelFlags(flags))
         }
     }
+
+    /// Checks whether the installed [`fault::FaultInjector`], if any, fails
+    /// this call to `site`.
+    #[cfg(feature = "fault-injection")]
+    pub(crate) fn inject_fault(
+        &self,
+        site: fault::FaultSite,
+        size: u64,
+    ) -> Result<(), DeviceError> {
+        let result = match *self.fault_injector.lock() {
+            Some(ref mut injector) => injector.check(site, size),
+            None => Ok(()),
+        };
+        if let Err(hal::DeviceError::Lost) = result {
+            self.lose(Some("injected fault"));
+        }
+        Ok(result?)
+    }
+
+    /// Creates the [`LifeGuard`] for a new resource of this device, recording
+    /// its origin if live resource tracking is enabled.
+    pub(crate) fn new_life_guard(&self, label: &str) -> LifeGuard {
+        let mut life_guard = LifeGuard::new(label);
+        if self
+            .instance_flags
+            .contains(wgt::InstanceFlags::TRACK_LIVE_RESOURCES)
+        {
+            let backtrace = self
+                .instance_flags
+                .contains(wgt::InstanceFlags::RESOURCE_BACKTRACES);
+            life_guard.origin = Some(Box::new(ResourceOrigin::capture(label, backtrace)));
+        }
+        life_guard
+    }
 }
 
 impl<A: HalApi> Device<A> {
@@ -208,7 +252,7 @@ impl<A: HalApi> Device<A> {
             command_allocator: Mutex::new(com_alloc),
             active_submission_index: 0,
             fence,
-            valid: true,
+            valid: AtomicBool::new(true),
             trackers: Mutex::new(Tracker::new()),
             life_tracker: Mutex::new(life::LifetimeTracker::new()),
             temp_suspected: life::SuspectedResources::default(),
@@ -232,11 +276,13 @@ impl<A: HalApi> Device<A> {
             downlevel,
             instance_flags,
             pending_writes,
+            #[cfg(feature = "fault-injection")]
+            fault_injector: Mutex::new(None),
         })
     }
 
     pub fn is_valid(&self) -> bool {
-        self.valid
+        self.valid.load(Ordering::Acquire)
     }
 
     pub(super) fn lock_life<'this, 'token: 'this>(
@@ -339,6 +385,7 @@ impl<A: HalApi> Device<A> {
             let (buffer_guard, mut token) = hub.buffers.read(&mut token);
             let (texture_guard, mut token) = hub.textures.read(&mut token);
             let (texture_view_guard, mut token) = hub.texture_views.read(&mut token);
+            let (external_texture_guard, mut token) = hub.external_textures.read(&mut token);
             let (sampler_guard, _) = hub.samplers.read(&mut token);
 
             for id in trackers.buffers.used() {
@@ -361,6 +408,11 @@ impl<A: HalApi> Device<A> {
                     self.temp_suspected.bind_groups.push(id);
                 }
             }
+            for id in trackers.external_textures.used() {
+                if external_texture_guard[id].life_guard.ref_count.is_none() {
+                    self.temp_suspected.external_textures.push(id);
+                }
+            }
             for id in trackers.samplers.used() {
                 if sampler_guard[id].life_guard.ref_count.is_none() {
                     self.temp_suspected.samplers.push(id);
@@ -475,6 +527,8 @@ impl<A: HalApi> Device<A> {
             usage,
             memory_flags,
         };
+        #[cfg(feature = "fault-injection")]
+        self.inject_fault(fault::FaultSite::CreateBuffer, aligned_size)?;
         let buffer = unsafe { self.raw.create_buffer(&hal_desc) }.map_err(DeviceError::from)?;
 
         Ok(Buffer {
@@ -488,7 +542,7 @@ impl<A: HalApi> Device<A> {
             initialization_status: BufferInitTracker::new(desc.size),
             sync_mapped_writes: None,
             map_state: resource::BufferMapState::Idle,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
         })
     }
 
@@ -522,7 +576,7 @@ impl<A: HalApi> Device<A> {
                 mips: 0..desc.mip_level_count,
                 layers: 0..desc.array_layer_count(),
             },
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
             clear_mode,
         }
     }
@@ -546,7 +600,7 @@ impl<A: HalApi> Device<A> {
             initialization_status: BufferInitTracker::new(0),
             sync_mapped_writes: None,
             map_state: resource::BufferMapState::Idle,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
         }
     }
 
@@ -745,6 +799,8 @@ impl<A: HalApi> Device<A> {
             view_formats: hal_view_formats,
         };
 
+        #[cfg(feature = "fault-injection")]
+        self.inject_fault(fault::FaultSite::CreateTexture, fault::texture_size(desc))?;
         let raw_texture = unsafe {
             self.raw
                 .create_texture(&hal_desc)
@@ -1110,7 +1166,7 @@ impl<A: HalApi> Device<A> {
             render_extent,
             samples: texture.desc.sample_count,
             selector,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
         })
     }
 
@@ -1216,13 +1272,114 @@ impl<A: HalApi> Device<A> {
                 value: id::Valid(self_id),
                 ref_count: self.life_guard.add_ref(),
             },
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
             comparison: desc.compare.is_some(),
             filtering: desc.min_filter == wgt::FilterMode::Linear
                 || desc.mag_filter == wgt::FilterMode::Linear,
         })
     }
 
+    pub(super) fn create_external_texture(
+        &self,
+        self_id: id::DeviceId,
+        desc: &resource::ExternalTextureDescriptor,
+        planes: &[id::TextureViewId],
+        texture_guard: &Storage<resource::Texture<A>, id::TextureId>,
+        view_guard: &Storage<resource::TextureView<A>, id::TextureViewId>,
+    ) -> Result<resource::ExternalTexture<A>, resource::CreateExternalTextureError> {
+        use resource::CreateExternalTextureError as Error;
+
+        let expected = desc.format.plane_count();
+        if planes.len() != expected {
+            return Err(Error::WrongPlaneCount {
+                format: desc.format,
+                expected,
+                actual: planes.len(),
+            });
+        }
+
+        let mut stored_planes = ArrayVec::new();
+        for (plane, &view_id) in planes.iter().enumerate() {
+            let view = view_guard
+                .get(view_id)
+                .map_err(|_| Error::InvalidTextureView(view_id))?;
+            if view.device_id.value.0 != self_id {
+                return Err(DeviceError::WrongDevice.into());
+            }
+            let texture = texture_guard
+                .get(view.parent_id.value.0)
+                .map_err(|_| Error::InvalidTextureView(view_id))?;
+            check_texture_usage(texture.desc.usage, wgt::TextureUsages::TEXTURE_BINDING)?;
+
+            let dimension = view.desc.dimension;
+            if dimension != TextureViewDimension::D2 || view.samples != 1 {
+                return Err(Error::InvalidPlaneDimension {
+                    plane,
+                    dimension,
+                    samples: view.samples,
+                });
+            }
+            match view.desc.format.sample_type(Some(view.desc.range.aspect)) {
+                Some(TextureSampleType::Float { filterable: true }) => {}
+                _ => {
+                    return Err(Error::InvalidPlaneFormat {
+                        plane,
+                        format: view.desc.format,
+                    })
+                }
+            }
+
+            stored_planes.push(Stored {
+                value: id::Valid(view_id),
+                ref_count: view.life_guard.add_ref(),
+            });
+        }
+
+        // Laid out as `NagaExternalTextureParams`: a column-major `mat4x4<f32>`
+        // followed by the number of planes.
+        let mut params = [0; resource::EXTERNAL_TEXTURE_PARAMS_SIZE as usize];
+        for (bytes, value) in params
+            .chunks_exact_mut(4)
+            .zip(desc.yuv_conversion_matrix.iter())
+        {
+            bytes.copy_from_slice(&value.to_ne_bytes());
+        }
+        params[64..68].copy_from_slice(&(expected as u32).to_ne_bytes());
+
+        let hal_desc = hal::BufferDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            size: resource::EXTERNAL_TEXTURE_PARAMS_SIZE,
+            usage: hal::BufferUses::MAP_WRITE | hal::BufferUses::UNIFORM,
+            memory_flags: hal::MemoryFlags::empty(),
+        };
+        let raw = unsafe { self.raw.create_buffer(&hal_desc) }.map_err(DeviceError::from)?;
+        let write = || -> Result<(), hal::DeviceError> {
+            let range = 0..resource::EXTERNAL_TEXTURE_PARAMS_SIZE;
+            unsafe {
+                let mapping = self.raw.map_buffer(&raw, range.clone())?;
+                std::ptr::copy_nonoverlapping(params.as_ptr(), mapping.ptr.as_ptr(), params.len());
+                if !mapping.is_coherent {
+                    self.raw.flush_mapped_ranges(&raw, iter::once(range));
+                }
+                self.raw.unmap_buffer(&raw)
+            }
+        };
+        if let Err(error) = write() {
+            unsafe { self.raw.destroy_buffer(raw) };
+            return Err(DeviceError::from(error).into());
+        }
+
+        Ok(resource::ExternalTexture {
+            params: raw,
+            device_id: Stored {
+                value: id::Valid(self_id),
+                ref_count: self.life_guard.add_ref(),
+            },
+            planes: stored_planes,
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
+        })
+    }
+
     pub(super) fn create_shader_module<'a>(
         &self,
         self_id: id::DeviceId,
@@ -1270,6 +1427,15 @@ impl<A: HalApi> Device<A> {
             Caps::FLOAT64,
             self.features.contains(wgt::Features::SHADER_F64),
         );
+        caps.set(
+            Caps::SHADER_INT64,
+            self.features.contains(wgt::Features::SHADER_INT64),
+        );
+        caps.set(
+            Caps::SHADER_INT64_ATOMIC_MIN_MAX,
+            self.features
+                .contains(wgt::Features::SHADER_INT64_ATOMIC_MIN_MAX),
+        );
         caps.set(
             Caps::PRIMITIVE_INDEX,
             self.features
@@ -1339,18 +1505,66 @@ impl<A: HalApi> Device<A> {
                 None
             };
 
-        let info = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), caps)
-            .validate(&module)
-            .map_err(|inner| {
-                pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
-                    source,
-                    label: desc.label.as_ref().map(|l| l.to_string()),
-                    inner: Box::new(inner),
-                })
-            })?;
+        let mut validator = naga::valid::Validator::new(naga::valid::ValidationFlags::all(), caps);
+        let info = validator.validate(&module).map_err(|inner| {
+            pipeline::CreateShaderModuleError::Validation(pipeline::ShaderError {
+                source,
+                label: desc.label.as_ref().map(|l| l.to_string()),
+                inner: Box::new(inner),
+            })
+        })?;
 
         let interface =
             validation::Interface::new(&module, &info, self.limits.clone(), self.features);
+
+        // The interface keeps the external textures, which match
+        // `BindingType::ExternalTexture` entries, but the backends only see
+        // the planes and parameters those entries are expanded into.
+        let external_textures = module
+            .global_variables
+            .iter()
+            .filter(|&(_, var)| {
+                matches!(
+                    module.types[var.ty].inner,
+                    naga::TypeInner::Image {
+                        class: naga::ImageClass::External,
+                        ..
+                    }
+                )
+            })
+            .filter_map(|(_, var)| var.binding.as_ref())
+            .map(|binding| {
+                let bindings =
+                    naga::external_texture::ExternalTextureBindings::consecutive(binding);
+                (binding.clone(), bindings)
+            })
+            .collect::<naga::external_texture::BindingMap>();
+        let (module, info) = if external_textures.is_empty() {
+            (module, info)
+        } else {
+            profiling::scope!("naga::external_texture::lower");
+            let mut module = module.into_owned();
+            naga::external_texture::lower(&mut module, &external_textures)?;
+            let info = validator.validate(&module).map_err(|error| {
+                log::error!("Lowered external textures are invalid: {:?}", error);
+                pipeline::CreateShaderModuleError::Generation
+            })?;
+            (Cow::Owned(module), info)
+        };
+        // Most backends can't pass pointers into the `storage`, `uniform`
+        // and `workgroup` address spaces to functions.
+        let (module, info) = if naga::proc::find_unspecialized_pointer_argument(&module).is_none() {
+            (module, info)
+        } else {
+            profiling::scope!("naga::proc::specialize_pointer_arguments");
+            let mut module = module.into_owned();
+            naga::proc::specialize_pointer_arguments(&mut module);
+            let info = validator.validate(&module).map_err(|error| {
+                log::error!("Specialized pointer arguments are invalid: {:?}", error);
+                pipeline::CreateShaderModuleError::Generation
+            })?;
+            (Cow::Owned(module), info)
+        };
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
             module,
             info,
@@ -1570,7 +1784,7 @@ impl<A: HalApi> Device<A> {
                 Bt::StorageTexture {
                     access,
                     view_dimension,
-                    format: _,
+                    format,
                 } => {
                     match view_dimension {
                         wgt::TextureViewDimension::Cube | wgt::TextureViewDimension::CubeArray => {
@@ -1586,11 +1800,14 @@ impl<A: HalApi> Device<A> {
                         | wgt::StorageTextureAccess::ReadWrite
                             if !self.features.contains(
                                 wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
-                            ) =>
+                            ) && !format
+                                .guaranteed_format_features(self.features)
+                                .flags
+                                .contains(wgt::TextureFormatFeatureFlags::STORAGE_READ_WRITE) =>
                         {
                             return Err(binding_model::CreateBindGroupLayoutError::Entry {
                                 binding: entry.binding,
-                                error: binding_model::BindGroupLayoutEntryError::StorageTextureReadWrite,
+                                error: binding_model::BindGroupLayoutEntryError::StorageTextureReadWrite(format),
                             });
                         }
                         _ => (),
@@ -1601,20 +1818,32 @@ impl<A: HalApi> Device<A> {
                                 | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY,
                         ),
                         match access {
-                            wgt::StorageTextureAccess::WriteOnly => WritableStorage::Yes,
-                            wgt::StorageTextureAccess::ReadOnly => {
-                                required_features |=
-                                    wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
-                                WritableStorage::No
-                            }
-                            wgt::StorageTextureAccess::ReadWrite => {
-                                required_features |=
-                                    wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
-                                WritableStorage::Yes
-                            }
+                            wgt::StorageTextureAccess::WriteOnly
+                            | wgt::StorageTextureAccess::ReadWrite => WritableStorage::Yes,
+                            wgt::StorageTextureAccess::ReadOnly => WritableStorage::No,
                         },
                     )
                 }
+                Bt::ExternalTexture => {
+                    let last = entry.binding + 3;
+                    if last > self.limits.max_bindings_per_bind_group {
+                        return Err(
+                            binding_model::CreateBindGroupLayoutError::InvalidBindingIndex {
+                                binding: last,
+                                maximum: self.limits.max_bindings_per_bind_group,
+                            },
+                        );
+                    }
+                    if let Some(overlap) =
+                        (entry.binding + 1..=last).find(|b| entry_map.contains_key(b))
+                    {
+                        return Err(binding_model::CreateBindGroupLayoutError::Entry {
+                            binding: entry.binding,
+                            error: binding_model::BindGroupLayoutEntryError::ExternalTextureBindingOverlap(overlap),
+                        });
+                    }
+                    (None, WritableStorage::No)
+                }
             };
 
             // Validate the count parameter
@@ -1667,7 +1896,15 @@ impl<A: HalApi> Device<A> {
 
         let bgl_flags = conv::bind_group_layout_flags(self.features);
 
-        let mut hal_bindings = entry_map.values().cloned().collect::<Vec<_>>();
+        let mut hal_bindings = Vec::with_capacity(entry_map.len());
+        for entry in entry_map.values() {
+            match entry.ty {
+                wgt::BindingType::ExternalTexture => {
+                    hal_bindings.extend(binding_model::external_texture_hal_entries(entry))
+                }
+                _ => hal_bindings.push(*entry),
+            }
+        }
         hal_bindings.sort_by_key(|b| b.binding);
         let hal_desc = hal::BindGroupLayoutDescriptor {
             label: label.to_hal(self.instance_flags),
@@ -1923,6 +2160,7 @@ impl<A: HalApi> Device<A> {
         let (buffer_guard, mut token) = hub.buffers.read(token);
         let (texture_guard, mut token) = hub.textures.read(&mut token); //skip token
         let (texture_view_guard, mut token) = hub.texture_views.read(&mut token);
+        let (external_texture_guard, mut token) = hub.external_textures.read(&mut token);
         let (sampler_guard, _) = hub.samplers.read(&mut token);
 
         let mut used_buffer_ranges = Vec::new();
@@ -2107,6 +2345,63 @@ impl<A: HalApi> Device<A> {
 
                     (res_index, num_bindings)
                 }
+                Br::ExternalTexture(id) => {
+                    if decl.ty != wgt::BindingType::ExternalTexture {
+                        return Err(Error::WrongBindingType {
+                            binding,
+                            actual: decl.ty,
+                            expected: "ExternalTexture",
+                        });
+                    }
+                    let external_texture = used
+                        .external_textures
+                        .add_single(&*external_texture_guard, id)
+                        .ok_or(Error::InvalidExternalTexture(id))?;
+
+                    if external_texture.device_id.value.0 != self_id {
+                        return Err(DeviceError::WrongDevice.into());
+                    }
+
+                    // The planes and parameters occupy the hal bindings laid
+                    // out by `external_texture_hal_entries`.
+                    for (offset, view_id) in (0..).zip(external_texture.plane_slots()) {
+                        let view = used
+                            .views
+                            .add_single(&*texture_view_guard, view_id.0)
+                            .ok_or(Error::InvalidTextureView(view_id.0))?;
+                        Self::create_texture_binding(
+                            self_id,
+                            view,
+                            &texture_guard,
+                            hal::TextureUses::RESOURCE,
+                            wgt::TextureUsages::TEXTURE_BINDING,
+                            &mut used,
+                            &mut used_texture_ranges,
+                        )?;
+                        hal_entries.push(hal::BindGroupEntry {
+                            binding: binding + offset,
+                            resource_index: hal_textures.len() as u32,
+                            count: 1,
+                        });
+                        hal_textures.push(hal::TextureBinding {
+                            view: &view.raw,
+                            usage: hal::TextureUses::RESOURCE,
+                        });
+                    }
+
+                    let res_index = hal_buffers.len();
+                    hal_buffers.push(hal::BufferBinding {
+                        buffer: &external_texture.params,
+                        offset: 0,
+                        size: wgt::BufferSize::new(resource::EXTERNAL_TEXTURE_PARAMS_SIZE),
+                    });
+                    hal_entries.push(hal::BindGroupEntry {
+                        binding: binding + 3,
+                        resource_index: res_index as u32,
+                        count: 1,
+                    });
+                    continue;
+                }
             };
 
             hal_entries.push(hal::BindGroupEntry {
@@ -2151,7 +2446,7 @@ impl<A: HalApi> Device<A> {
                 ref_count: self.life_guard.add_ref(),
             },
             layout_id,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
             used,
             used_buffer_ranges,
             used_texture_ranges,
@@ -2430,7 +2725,7 @@ impl<A: HalApi> Device<A> {
                 value: id::Valid(self_id),
                 ref_count: self.life_guard.add_ref(),
             },
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
             bind_group_layout_ids: desc
                 .bind_group_layouts
                 .iter()
@@ -2617,7 +2912,7 @@ impl<A: HalApi> Device<A> {
                 ref_count: self.life_guard.add_ref(),
             },
             late_sized_buffer_groups,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
         };
         Ok(pipeline)
     }
@@ -3210,7 +3505,7 @@ impl<A: HalApi> Device<A> {
             strip_index_format: desc.primitive.strip_index_format,
             vertex_steps,
             late_sized_buffer_groups,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
         };
         Ok(pipeline)
     }
@@ -3299,17 +3594,17 @@ impl<A: HalApi> Device<A> {
                 value: id::Valid(self_id),
                 ref_count: self.life_guard.add_ref(),
             },
-            life_guard: LifeGuard::new(""),
+            life_guard: self.new_life_guard(desc.label.borrow_or_default()),
             desc: desc.map_label(|_| ()),
         })
     }
 
-    pub(crate) fn lose(&mut self, _reason: Option<&str>) {
+    pub(crate) fn lose(&self, _reason: Option<&str>) {
         // Follow the steps at https://gpuweb.github.io/gpuweb/#lose-the-device.
 
         // Mark the device explicitly as invalid. This is checked in various
         // places to prevent new work from being submitted.
-        self.valid = false;
+        self.valid.store(false, Ordering::Release);
 
         // The following steps remain in "lose the device":
         // 1) Resolve the GPUDevice device.lost promise.
@@ -1,7 +1,11 @@
 use crate::id;
-use std::ops::Range;
 #[cfg(feature = "trace")]
 use std::{borrow::Cow, io::Write as _};
+use std::{
+    io::{Read as _, Seek as _},
+    ops::Range,
+    path::{Path, PathBuf},
+};
 
 //TODO: consider a readable Id that doesn't include the backend
 
@@ -9,6 +13,31 @@ type FileName = String;
 
 pub const FILE_NAME: &str = "trace.ron";
 
+/// Extension of a trace path that selects the compact binary format.
+///
+/// A trace path ending with this extension is written as a single file
+/// instead of a directory containing [`FILE_NAME`] and the data files.
+pub const BINARY_EXTENSION: &str = "wgputrace";
+
+/// Magic bytes at the beginning of a binary trace.
+const BINARY_MAGIC: &[u8; 8] = b"wgputrc\0";
+/// Version of the binary container layout, following the magic bytes.
+const BINARY_VERSION: u32 = 1;
+
+/// A record holding a `bincode`-serialized [`Action`].
+const RECORD_ACTION: u8 = 0;
+/// A record holding a named blob of data referenced by later actions.
+const RECORD_BLOB: u8 = 1;
+
+/// Blob data is stored as-is.
+const BLOB_RAW: u8 = 0;
+/// Blob data is compressed with LZ4, prefixed by its uncompressed size.
+const BLOB_LZ4: u8 = 1;
+
+/// Blobs smaller than this are never compressed.
+#[cfg(feature = "trace")]
+const BLOB_COMPRESSION_THRESHOLD: usize = 64;
+
 #[cfg(feature = "trace")]
 pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
     label: crate::Label<'a>,
@@ -32,7 +61,7 @@ pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
 }
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
 #[cfg_attr(feature = "replay", derive(serde::Deserialize))]
 pub enum Action<'a> {
@@ -58,6 +87,12 @@ pub enum Action<'a> {
     DestroyTextureView(id::TextureViewId),
     CreateSampler(id::SamplerId, crate::resource::SamplerDescriptor<'a>),
     DestroySampler(id::SamplerId),
+    CreateExternalTexture {
+        id: id::ExternalTextureId,
+        desc: crate::resource::ExternalTextureDescriptor<'a>,
+        planes: Vec<id::TextureViewId>,
+    },
+    DestroyExternalTexture(id::ExternalTextureId),
     GetSurfaceTexture {
         id: id::TextureId,
         parent_id: id::SurfaceId,
@@ -105,6 +140,12 @@ pub enum Action<'a> {
         base: crate::command::BasePass<crate::command::RenderCommand>,
     },
     DestroyRenderBundle(id::RenderBundleId),
+    CreateComputeBundle {
+        id: id::ComputeBundleId,
+        desc: crate::command::ComputeBundleEncoderDescriptor<'a>,
+        base: crate::command::BasePass<crate::command::ComputeCommand>,
+    },
+    DestroyComputeBundle(id::ComputeBundleId),
     CreateQuerySet {
         id: id::QuerySetId,
         desc: crate::resource::QuerySetDescriptor<'a>,
@@ -116,6 +157,16 @@ pub enum Action<'a> {
         range: Range<wgt::BufferAddress>,
         queued: bool,
     },
+    /// Bytes written by the host into a mapped range of a buffer, recorded
+    /// when the buffer is unmapped.
+    ///
+    /// The range is mapped at creation if the buffer was created with
+    /// `mapped_at_creation`, and by `buffer_map_async` otherwise.
+    UnmapBuffer {
+        id: id::BufferId,
+        data: FileName,
+        range: Range<wgt::BufferAddress>,
+    },
     WriteTexture {
         to: crate::command::ImageCopyTexture,
         data: FileName,
@@ -125,7 +176,7 @@ pub enum Action<'a> {
     Submit(crate::SubmissionIndex, Vec<Command>),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
 #[cfg_attr(feature = "replay", derive(serde::Deserialize))]
 pub enum Command {
@@ -187,51 +238,506 @@ pub enum Command {
     },
 }
 
+/// Where the trace is written to.
+#[cfg(feature = "trace")]
+#[derive(Debug)]
+enum Sink {
+    /// Pretty-printed RON actions, with one file per binary blob.
+    Ron {
+        dir: PathBuf,
+        file: std::fs::File,
+        config: ron::ser::PrettyConfig,
+    },
+    /// A single file of length-prefixed records.
+    ///
+    /// Every record is written with a single unbuffered write, so the
+    /// trace of an application that crashes is only missing its last
+    /// record at worst.
+    Binary {
+        file: std::fs::File,
+        /// Blobs already written, keyed by content hash and length.
+        ///
+        /// Blobs with the same key are read back and compared before being
+        /// reused, so a hash collision only costs some time.
+        blobs: crate::FastHashMap<(u64, usize), Vec<(FileName, BlobLocation)>>,
+    },
+}
+
 #[cfg(feature = "trace")]
 #[derive(Debug)]
 pub struct Trace {
-    path: std::path::PathBuf,
-    file: std::fs::File,
-    config: ron::ser::PrettyConfig,
+    sink: Sink,
     binary_id: usize,
 }
 
 #[cfg(feature = "trace")]
 impl Trace {
-    pub fn new(path: &std::path::Path) -> Result<Self, std::io::Error> {
+    /// Start a trace at `path`.
+    ///
+    /// If `path` has the [`BINARY_EXTENSION`] extension, the trace is written
+    /// as a single binary file. Otherwise, `path` is a directory that receives
+    /// a RON trace and its data files.
+    pub fn new(path: &Path) -> Result<Self, std::io::Error> {
         log::info!("Tracing into '{:?}'", path);
-        let mut file = std::fs::File::create(path.join(FILE_NAME))?;
-        file.write_all(b"[\n")?;
-        Ok(Self {
-            path: path.to_path_buf(),
-            file,
-            config: ron::ser::PrettyConfig::default(),
-            binary_id: 0,
-        })
+        let sink = if path
+            .extension()
+            .map_or(false, |ext| ext == BINARY_EXTENSION)
+        {
+            // Readable too, to compare new blobs with the ones written before.
+            let mut file = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(path)?;
+            let mut header = BINARY_MAGIC.to_vec();
+            header.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+            file.write_all(&header)?;
+            Sink::Binary {
+                file,
+                blobs: crate::FastHashMap::default(),
+            }
+        } else {
+            let mut file = std::fs::File::create(path.join(FILE_NAME))?;
+            file.write_all(b"[\n")?;
+            Sink::Ron {
+                dir: path.to_path_buf(),
+                file,
+                config: ron::ser::PrettyConfig::default(),
+            }
+        };
+        Ok(Self { sink, binary_id: 0 })
     }
 
     pub fn make_binary(&mut self, kind: &str, data: &[u8]) -> String {
-        self.binary_id += 1;
-        let name = format!("data{}.{}", self.binary_id, kind);
-        let _ = std::fs::write(self.path.join(&name), data);
-        name
+        match self.sink {
+            Sink::Ron { ref dir, .. } => {
+                self.binary_id += 1;
+                let name = format!("data{}.{}", self.binary_id, kind);
+                let _ = std::fs::write(dir.join(&name), data);
+                name
+            }
+            Sink::Binary {
+                ref mut file,
+                ref mut blobs,
+            } => {
+                let candidates = blobs.entry(blob_key(kind, data)).or_default();
+                for &(ref name, location) in candidates.iter() {
+                    let same = name.ends_with(&format!(".{kind}"))
+                        && read_blob(file, location).map_or(false, |stored| stored == data);
+                    if same {
+                        return name.clone();
+                    }
+                }
+
+                self.binary_id += 1;
+                let name = format!("data{}.{}", self.binary_id, kind);
+                let compressed;
+                let (compression, contents) = if data.len() >= BLOB_COMPRESSION_THRESHOLD {
+                    compressed = lz4_flex::compress_prepend_size(data);
+                    if compressed.len() < data.len() {
+                        (BLOB_LZ4, &compressed[..])
+                    } else {
+                        (BLOB_RAW, data)
+                    }
+                } else {
+                    (BLOB_RAW, data)
+                };
+
+                let mut payload = Vec::with_capacity(4 + name.len() + 1 + contents.len());
+                payload.extend_from_slice(&(name.len() as u32).to_le_bytes());
+                payload.extend_from_slice(name.as_bytes());
+                payload.push(compression);
+                let data_start = payload.len();
+                payload.extend_from_slice(contents);
+                let written = file
+                    .seek(std::io::SeekFrom::End(0))
+                    .and_then(|record_start| {
+                        write_record(file, RECORD_BLOB, &payload)?;
+                        Ok(record_start)
+                    });
+                match written {
+                    Ok(record_start) => candidates.push((
+                        name.clone(),
+                        BlobLocation {
+                            offset: record_start + 5 + data_start as u64,
+                            size: contents.len(),
+                            compression,
+                        },
+                    )),
+                    Err(e) => log::warn!("Failed to write trace blob: {:?}", e),
+                }
+                name
+            }
+        }
+    }
+
+    pub fn add(&mut self, action: Action) {
+        match self.sink {
+            Sink::Ron {
+                ref mut file,
+                ref config,
+                ..
+            } => match ron::ser::to_string_pretty(&action, config.clone()) {
+                Ok(string) => {
+                    let _ = writeln!(file, "{},", string);
+                }
+                Err(e) => {
+                    log::warn!("RON serialization failure: {:?}", e);
+                }
+            },
+            Sink::Binary { ref mut file, .. } => match bincode::serialize(&action) {
+                Ok(payload) => {
+                    if let Err(e) = write_record(file, RECORD_ACTION, &payload) {
+                        log::warn!("Failed to write trace action: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Binary serialization failure: {:?}", e);
+                }
+            },
+        }
     }
+}
+
+/// The key of a blob in [`Sink::Binary::blobs`].
+#[cfg(feature = "trace")]
+fn blob_key(kind: &str, data: &[u8]) -> (u64, usize) {
+    use std::hash::{Hash as _, Hasher as _};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    kind.hash(&mut hasher);
+    data.hash(&mut hasher);
+    (hasher.finish(), data.len())
+}
 
-    pub(crate) fn add(&mut self, action: Action) {
-        match ron::ser::to_string_pretty(&action, self.config.clone()) {
-            Ok(string) => {
-                let _ = writeln!(self.file, "{},", string);
+/// Write a record of the binary format in one go, at the end of `file`.
+#[cfg(feature = "trace")]
+fn write_record(file: &mut std::fs::File, kind: u8, payload: &[u8]) -> std::io::Result<()> {
+    let mut record = Vec::with_capacity(5 + payload.len());
+    record.push(kind);
+    record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    record.extend_from_slice(payload);
+    file.seek(std::io::SeekFrom::End(0))?;
+    file.write_all(&record)
+}
+
+/// Read and decompress the blob at `location` in a binary trace.
+fn read_blob(file: &mut std::fs::File, location: BlobLocation) -> std::io::Result<Vec<u8>> {
+    file.seek(std::io::SeekFrom::Start(location.offset))?;
+    let mut contents = vec![0; location.size];
+    file.read_exact(&mut contents)?;
+    match location.compression {
+        BLOB_RAW => Ok(contents),
+        BLOB_LZ4 => {
+            // LZ4 can't expand a byte into more than 255, so a larger size is
+            // a corrupted file, which shouldn't make us allocate it.
+            if contents.len() < 4 {
+                return Err(invalid_data("truncated compressed blob".to_string()));
             }
-            Err(e) => {
-                log::warn!("RON serialization failure: {:?}", e);
+            let (size, compressed) = contents.split_at(4);
+            let size = u32::from_le_bytes(size.try_into().unwrap()) as usize;
+            if size > compressed.len().saturating_mul(255) {
+                return Err(invalid_data(format!(
+                    "compressed blob of {} bytes can't hold {size} bytes",
+                    compressed.len()
+                )));
             }
+            lz4_flex::decompress(compressed, size).map_err(|e| invalid_data(e.to_string()))
         }
+        other => Err(invalid_data(format!("unknown blob compression {other}"))),
     }
 }
 
 #[cfg(feature = "trace")]
 impl Drop for Trace {
     fn drop(&mut self) {
-        let _ = self.file.write_all(b"]");
+        if let Sink::Ron { ref mut file, .. } = self.sink {
+            let _ = file.write_all(b"]");
+        }
+    }
+}
+
+/// Location of a blob within a binary trace.
+#[derive(Clone, Copy, Debug)]
+struct BlobLocation {
+    offset: u64,
+    size: usize,
+    compression: u8,
+}
+
+#[cfg(feature = "replay")]
+#[derive(Debug)]
+enum DataSourceInner {
+    /// Data files stored next to a RON trace.
+    Directory(PathBuf),
+    /// Blobs stored inside of a binary trace.
+    Binary {
+        file: parking_lot::Mutex<std::fs::File>,
+        blobs: crate::FastHashMap<FileName, BlobLocation>,
+    },
+}
+
+/// Provides the data files referenced by the actions of a trace.
+#[cfg(feature = "replay")]
+#[derive(Debug)]
+pub struct DataSource(DataSourceInner);
+
+#[cfg(feature = "replay")]
+impl DataSource {
+    /// Read data files from the directory `dir`.
+    pub fn directory(dir: &Path) -> Self {
+        Self(DataSourceInner::Directory(dir.to_path_buf()))
+    }
+
+    /// Read the contents of the data file `name`.
+    pub fn read(&self, name: &str) -> std::io::Result<Vec<u8>> {
+        match self.0 {
+            DataSourceInner::Directory(ref dir) => std::fs::read(dir.join(name)),
+            DataSourceInner::Binary {
+                ref file,
+                ref blobs,
+            } => {
+                let location = blobs.get(name).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("blob {name:?} is not in the trace"),
+                    )
+                })?;
+                read_blob(&mut file.lock(), *location)
+            }
+        }
+    }
+
+    /// Read the contents of the data file `name` as UTF-8 text.
+    pub fn read_to_string(&self, name: &str) -> std::io::Result<String> {
+        String::from_utf8(self.read(name)?).map_err(|e| invalid_data(e.to_string()))
+    }
+}
+
+fn invalid_data(message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+/// Load the actions of the trace at `path`, along with the source of their data.
+///
+/// `path` is either a trace directory, or a single file in the binary format.
+/// A binary trace that ends with a partially written record, for example
+/// because the traced application crashed, is loaded up to that record.
+///
+/// If `backend` is given, the ids of the trace are moved to it, so that the
+/// trace can be replayed on a different backend than the one it was recorded on.
+#[cfg(feature = "replay")]
+pub fn load(
+    path: &Path,
+    backend: Option<wgt::Backend>,
+) -> std::io::Result<(Vec<Action<'static>>, DataSource)> {
+    id::with_replay_backend(backend, || load_impl(path))
+}
+
+#[cfg(feature = "replay")]
+fn load_impl(path: &Path) -> std::io::Result<(Vec<Action<'static>>, DataSource)> {
+    if path.is_dir() {
+        let file = std::fs::File::open(path.join(FILE_NAME))?;
+        let actions = ron::de::from_reader(file).map_err(|e| invalid_data(e.to_string()))?;
+        return Ok((actions, DataSource::directory(path)));
+    }
+
+    let mut file = std::fs::File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut reader = std::io::BufReader::new(&mut file);
+    let mut header = [0; BINARY_MAGIC.len() + 4];
+    if reader.read_exact(&mut header).is_err() || &header[..BINARY_MAGIC.len()] != BINARY_MAGIC {
+        return Err(invalid_data(format!("{path:?} is not a binary trace")));
+    }
+    let version = u32::from_le_bytes(header[BINARY_MAGIC.len()..].try_into().unwrap());
+    if version != BINARY_VERSION {
+        return Err(invalid_data(format!(
+            "unsupported binary trace version {version}"
+        )));
+    }
+
+    // Actions are read into memory, but blobs are only located, and read
+    // from the file when the player asks for them.
+    let mut actions = Vec::new();
+    let mut blobs = crate::FastHashMap::default();
+    let mut offset = header.len() as u64;
+    let mut record_header = [0; 5];
+    while offset < file_size {
+        let payload_start = offset + 5;
+        let size = reader
+            .read_exact(&mut record_header)
+            .ok()
+            .map(|()| u32::from_le_bytes(record_header[1..].try_into().unwrap()) as u64)
+            .filter(|&size| payload_start + size <= file_size);
+        let size = match size {
+            Some(size) => size,
+            None => {
+                log::warn!("Ignoring a truncated record at the end of the trace");
+                break;
+            }
+        };
+
+        match record_header[0] {
+            RECORD_ACTION => {
+                let mut payload = vec![0; size as usize];
+                reader.read_exact(&mut payload)?;
+                let action = bincode::deserialize(&payload)
+                    .map_err(|e| invalid_data(format!("bad action at {offset}: {e}")))?;
+                actions.push(action);
+            }
+            RECORD_BLOB => {
+                let bad_blob = || invalid_data(format!("bad blob at {offset}"));
+                let mut name_size = [0; 4];
+                reader.read_exact(&mut name_size).map_err(|_| bad_blob())?;
+                let name_size = u32::from_le_bytes(name_size) as u64;
+                if 4 + name_size + 1 > size {
+                    return Err(bad_blob());
+                }
+                let mut name = vec![0; name_size as usize];
+                reader.read_exact(&mut name)?;
+                let name = String::from_utf8(name).map_err(|_| bad_blob())?;
+                let mut compression = [0];
+                reader.read_exact(&mut compression)?;
+                let data_start = 4 + name_size + 1;
+                reader.seek_relative((size - data_start) as i64)?;
+                blobs.insert(
+                    name,
+                    BlobLocation {
+                        offset: payload_start + data_start,
+                        size: (size - data_start) as usize,
+                        compression: compression[0],
+                    },
+                );
+            }
+            other => {
+                return Err(invalid_data(format!(
+                    "unknown record kind {other} at {offset}"
+                )));
+            }
+        }
+        offset = payload_start + size;
+    }
+    drop(reader);
+
+    let source = DataSource(DataSourceInner::Binary {
+        file: parking_lot::Mutex::new(file),
+        blobs,
+    });
+    Ok((actions, source))
+}
+
+#[cfg(all(test, feature = "trace", feature = "replay"))]
+mod tests {
+    use super::*;
+    use crate::id::TypedId as _;
+
+    #[test]
+    fn binary_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "wgpu-binary-round-trip-{}.{}",
+            std::process::id(),
+            BINARY_EXTENSION
+        ));
+        let buffer = id::BufferId::zip(1, 1, wgt::Backend::Empty);
+        let compressible = vec![7u8; 4096];
+
+        let (first, second, third) = {
+            let mut trace = Trace::new(&path).unwrap();
+            let first = trace.make_binary("bin", &compressible);
+            let second = trace.make_binary("bin", &compressible);
+            let third = trace.make_binary("bin", &[1, 2, 3]);
+            trace.add(Action::WriteBuffer {
+                id: buffer,
+                data: first.clone(),
+                range: 0..4096,
+                queued: true,
+            });
+            trace.add(Action::DestroyBuffer(buffer));
+            (first, second, third)
+        };
+        // Identical blobs are only stored once.
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+        assert!(std::fs::metadata(&path).unwrap().len() < 1024);
+
+        // Simulate a crash in the middle of writing a record.
+        {
+            use std::io::Write as _;
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(&[RECORD_ACTION, 0xFF, 0, 0, 0, 1]).unwrap();
+        }
+
+        let (actions, data_source) = load(&path, None).unwrap();
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            actions[0],
+            Action::WriteBuffer { ref data, queued: true, .. } if *data == first
+        ));
+        assert!(matches!(actions[1], Action::DestroyBuffer(id) if id == buffer));
+        assert_eq!(data_source.read(&first).unwrap(), compressible);
+        assert_eq!(data_source.read(&third).unwrap(), [1, 2, 3]);
+        assert!(data_source.read("missing.bin").is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Blobs whose keys collide are still stored separately.
+    #[test]
+    fn binary_hash_collision() {
+        let path = std::env::temp_dir().join(format!(
+            "wgpu-binary-hash-collision-{}.{}",
+            std::process::id(),
+            BINARY_EXTENSION
+        ));
+        let (first, second) = {
+            let mut trace = Trace::new(&path).unwrap();
+            let first = trace.make_binary("bin", &[1, 2, 3]);
+            // Pretend `[4, 5, 6]` has the same hash as `[1, 2, 3]`.
+            if let Sink::Binary { ref mut blobs, .. } = trace.sink {
+                let stored = blobs.remove(&blob_key("bin", &[1, 2, 3])).unwrap();
+                blobs.insert(blob_key("bin", &[4, 5, 6]), stored);
+            }
+            let second = trace.make_binary("bin", &[4, 5, 6]);
+            (first, second)
+        };
+        assert_ne!(first, second);
+
+        let (_, data_source) = load(&path, None).unwrap();
+        assert_eq!(data_source.read(&first).unwrap(), [1, 2, 3]);
+        assert_eq!(data_source.read(&second).unwrap(), [4, 5, 6]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// A compressed blob claiming an impossible size is rejected.
+    #[test]
+    fn binary_blob_size_is_bounded() {
+        use std::io::Write as _;
+
+        let path = std::env::temp_dir().join(format!(
+            "wgpu-binary-blob-size-{}.{}",
+            std::process::id(),
+            BINARY_EXTENSION
+        ));
+        let mut contents = u32::MAX.to_le_bytes().to_vec();
+        contents.extend_from_slice(&[0x10, 0]);
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(&contents)
+            .unwrap();
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        let location = BlobLocation {
+            offset: 0,
+            size: contents.len(),
+            compression: BLOB_LZ4,
+        };
+        let error = read_blob(&mut file, location).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        let _ = std::fs::remove_file(&path);
     }
 }
@@ -142,6 +142,9 @@ pub fn format_pretty_any(
     if let Some(pretty_err) = error.downcast_ref::<crate::command::RenderBundleError>() {
         return pretty_err.fmt_pretty(&mut fmt);
     }
+    if let Some(pretty_err) = error.downcast_ref::<crate::command::ComputeBundleError>() {
+        return pretty_err.fmt_pretty(&mut fmt);
+    }
     if let Some(pretty_err) = error.downcast_ref::<crate::command::TransferError>() {
         return pretty_err.fmt_pretty(&mut fmt);
     }
@@ -6,6 +6,7 @@ use crate::{
     instance::{Instance, Surface},
     registry::Registry,
     storage::{Element, StorageReport},
+    worker::WorkerPool,
 };
 
 #[derive(Debug)]
@@ -27,12 +28,14 @@ pub struct Global<G: GlobalIdentityHandlerFactory> {
     pub instance: Instance,
     pub surfaces: Registry<Surface, id::SurfaceId, G>,
     pub(crate) hubs: Hubs<G>,
+    pub(crate) workers: WorkerPool,
 }
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
     pub fn new(name: &str, factory: G, instance_desc: wgt::InstanceDescriptor) -> Self {
         profiling::scope!("Global::new");
         Self {
+            workers: WorkerPool::new(instance_desc.pipeline_worker_threads),
             instance: Instance::new(name, instance_desc),
             surfaces: Registry::without_backend(&factory, "Surface"),
             hubs: Hubs::new(&factory),
@@ -52,6 +55,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             instance: A::create_instance_from_hal(name, hal_instance),
             surfaces: Registry::without_backend(&factory, "Surface"),
             hubs: Hubs::new(&factory),
+            workers: WorkerPool::new(None),
         }
     }
 
@@ -71,6 +75,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             instance,
             surfaces: Registry::without_backend(&factory, "Surface"),
             hubs: Hubs::new(&factory),
+            workers: WorkerPool::new(None),
         }
     }
 
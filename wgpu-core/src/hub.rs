@@ -157,7 +157,7 @@ flagged as errors as well.
 
 use crate::{
     binding_model::{BindGroup, BindGroupLayout, PipelineLayout},
-    command::{CommandBuffer, RenderBundle},
+    command::{CommandBuffer, ComputeBundle, RenderBundle},
     device::Device,
     hal_api::HalApi,
     id,
@@ -165,7 +165,10 @@ use crate::{
     instance::{Adapter, HalSurface, Instance, Surface},
     pipeline::{ComputePipeline, RenderPipeline, ShaderModule},
     registry::Registry,
-    resource::{Buffer, QuerySet, Sampler, StagingBuffer, Texture, TextureClearMode, TextureView},
+    resource::{
+        Buffer, ExternalTexture, QuerySet, Sampler, StagingBuffer, Texture, TextureClearMode,
+        TextureView,
+    },
     storage::{Element, Storage, StorageReport},
 };
 
@@ -195,6 +198,7 @@ use std::{fmt::Debug, marker::PhantomData};
 /// - [`Device`]
 /// - [`CommandBuffer`]
 /// - [`RenderBundle`]
+/// - [`ComputeBundle`]
 /// - [`PipelineLayout`]
 /// - [`BindGroupLayout`]
 /// - [`BindGroup`]
@@ -205,6 +209,7 @@ use std::{fmt::Debug, marker::PhantomData};
 /// - [`StagingBuffer`]
 /// - [`Texture`]
 /// - [`TextureView`]
+/// - [`ExternalTexture`]
 /// - [`Sampler`]
 /// - [`QuerySet`]
 ///
@@ -239,9 +244,13 @@ impl<A: HalApi> Access<CommandBuffer<A>> for Root {}
 impl<A: HalApi> Access<CommandBuffer<A>> for Device<A> {}
 impl<A: HalApi> Access<RenderBundle<A>> for Device<A> {}
 impl<A: HalApi> Access<RenderBundle<A>> for CommandBuffer<A> {}
+impl<A: HalApi> Access<ComputeBundle<A>> for Device<A> {}
+impl<A: HalApi> Access<ComputeBundle<A>> for CommandBuffer<A> {}
+impl<A: HalApi> Access<ComputeBundle<A>> for RenderBundle<A> {}
 impl<A: HalApi> Access<PipelineLayout<A>> for Root {}
 impl<A: HalApi> Access<PipelineLayout<A>> for Device<A> {}
 impl<A: HalApi> Access<PipelineLayout<A>> for RenderBundle<A> {}
+impl<A: HalApi> Access<PipelineLayout<A>> for ComputeBundle<A> {}
 impl<A: HalApi> Access<BindGroupLayout<A>> for Root {}
 impl<A: HalApi> Access<BindGroupLayout<A>> for Device<A> {}
 impl<A: HalApi> Access<BindGroupLayout<A>> for PipelineLayout<A> {}
@@ -273,9 +282,13 @@ impl<A: HalApi> Access<Texture<A>> for Buffer<A> {}
 impl<A: HalApi> Access<TextureView<A>> for Root {}
 impl<A: HalApi> Access<TextureView<A>> for Device<A> {}
 impl<A: HalApi> Access<TextureView<A>> for Texture<A> {}
+impl<A: HalApi> Access<ExternalTexture<A>> for Root {}
+impl<A: HalApi> Access<ExternalTexture<A>> for Device<A> {}
+impl<A: HalApi> Access<ExternalTexture<A>> for TextureView<A> {}
 impl<A: HalApi> Access<Sampler<A>> for Root {}
 impl<A: HalApi> Access<Sampler<A>> for Device<A> {}
 impl<A: HalApi> Access<Sampler<A>> for TextureView<A> {}
+impl<A: HalApi> Access<Sampler<A>> for ExternalTexture<A> {}
 impl<A: HalApi> Access<QuerySet<A>> for Root {}
 impl<A: HalApi> Access<QuerySet<A>> for Device<A> {}
 impl<A: HalApi> Access<QuerySet<A>> for CommandBuffer<A> {}
@@ -367,12 +380,14 @@ pub struct HubReport {
     pub bind_groups: StorageReport,
     pub command_buffers: StorageReport,
     pub render_bundles: StorageReport,
+    pub compute_bundles: StorageReport,
     pub render_pipelines: StorageReport,
     pub compute_pipelines: StorageReport,
     pub query_sets: StorageReport,
     pub buffers: StorageReport,
     pub textures: StorageReport,
     pub texture_views: StorageReport,
+    pub external_textures: StorageReport,
     pub samplers: StorageReport,
 }
 
@@ -457,6 +472,7 @@ pub struct Hub<A: HalApi, F: GlobalIdentityHandlerFactory> {
     pub bind_groups: Registry<BindGroup<A>, id::BindGroupId, F>,
     pub command_buffers: Registry<CommandBuffer<A>, id::CommandBufferId, F>,
     pub render_bundles: Registry<RenderBundle<A>, id::RenderBundleId, F>,
+    pub compute_bundles: Registry<ComputeBundle<A>, id::ComputeBundleId, F>,
     pub render_pipelines: Registry<RenderPipeline<A>, id::RenderPipelineId, F>,
     pub compute_pipelines: Registry<ComputePipeline<A>, id::ComputePipelineId, F>,
     pub query_sets: Registry<QuerySet<A>, id::QuerySetId, F>,
@@ -464,6 +480,7 @@ pub struct Hub<A: HalApi, F: GlobalIdentityHandlerFactory> {
     pub staging_buffers: Registry<StagingBuffer<A>, id::StagingBufferId, F>,
     pub textures: Registry<Texture<A>, id::TextureId, F>,
     pub texture_views: Registry<TextureView<A>, id::TextureViewId, F>,
+    pub external_textures: Registry<ExternalTexture<A>, id::ExternalTextureId, F>,
     pub samplers: Registry<Sampler<A>, id::SamplerId, F>,
 }
 
@@ -478,6 +495,7 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
             bind_groups: Registry::new(A::VARIANT, factory),
             command_buffers: Registry::new(A::VARIANT, factory),
             render_bundles: Registry::new(A::VARIANT, factory),
+            compute_bundles: Registry::new(A::VARIANT, factory),
             render_pipelines: Registry::new(A::VARIANT, factory),
             compute_pipelines: Registry::new(A::VARIANT, factory),
             query_sets: Registry::new(A::VARIANT, factory),
@@ -485,6 +503,7 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
             staging_buffers: Registry::new(A::VARIANT, factory),
             textures: Registry::new(A::VARIANT, factory),
             texture_views: Registry::new(A::VARIANT, factory),
+            external_textures: Registry::new(A::VARIANT, factory),
             samplers: Registry::new(A::VARIANT, factory),
         }
     }
@@ -525,6 +544,16 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
             }
         }
 
+        for element in self.external_textures.data.write().map.drain(..) {
+            if let Element::Occupied(external_texture, _) = element {
+                unsafe {
+                    devices[external_texture.device_id.value]
+                        .raw
+                        .destroy_buffer(external_texture.params);
+                }
+            }
+        }
+
         for element in self.texture_views.data.write().map.drain(..) {
             if let Element::Occupied(texture_view, _) = element {
                 let device = &devices[texture_view.device_id.value];
@@ -675,12 +704,14 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
             bind_groups: self.bind_groups.data.read().generate_report(),
             command_buffers: self.command_buffers.data.read().generate_report(),
             render_bundles: self.render_bundles.data.read().generate_report(),
+            compute_bundles: self.compute_bundles.data.read().generate_report(),
             render_pipelines: self.render_pipelines.data.read().generate_report(),
             compute_pipelines: self.compute_pipelines.data.read().generate_report(),
             query_sets: self.query_sets.data.read().generate_report(),
             buffers: self.buffers.data.read().generate_report(),
             textures: self.textures.data.read().generate_report(),
             texture_views: self.texture_views.data.read().generate_report(),
+            external_textures: self.external_textures.data.read().generate_report(),
             samplers: self.samplers.data.read().generate_report(),
         }
     }
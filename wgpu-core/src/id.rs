@@ -87,11 +87,35 @@ impl<T> From<Id<T>> for SerialId {
 impl<T> From<SerialId> for Id<T> {
     fn from(id: SerialId) -> Self {
         match id {
-            SerialId::Id(index, epoch, backend) => TypedId::zip(index, epoch, backend),
+            SerialId::Id(index, epoch, backend) => {
+                let backend = match REPLAY_BACKEND.with(|replay| replay.get()) {
+                    Some(replay) if backend != Backend::Empty => replay,
+                    _ => backend,
+                };
+                TypedId::zip(index, epoch, backend)
+            }
         }
     }
 }
 
+#[cfg(feature = "replay")]
+thread_local! {
+    /// Backend that deserialized ids are moved to, if any.
+    static REPLAY_BACKEND: std::cell::Cell<Option<Backend>> = std::cell::Cell::new(None);
+}
+
+/// Run `fun`, with the ids it deserializes moved to `backend`.
+///
+/// Ids of the `Empty` backend, used by objects that don't belong to a
+/// backend, are left alone.
+#[cfg(feature = "replay")]
+pub(crate) fn with_replay_backend<R>(backend: Option<Backend>, fun: impl FnOnce() -> R) -> R {
+    let old = REPLAY_BACKEND.with(|replay| replay.replace(backend));
+    let result = fun();
+    REPLAY_BACKEND.with(|replay| replay.set(old));
+    result
+}
+
 impl<T> Id<T> {
     /// # Safety
     ///
@@ -218,6 +242,7 @@ pub type StagingBufferId = Id<crate::resource::StagingBuffer<Dummy>>;
 pub type TextureViewId = Id<crate::resource::TextureView<Dummy>>;
 pub type TextureId = Id<crate::resource::Texture<Dummy>>;
 pub type SamplerId = Id<crate::resource::Sampler<Dummy>>;
+pub type ExternalTextureId = Id<crate::resource::ExternalTexture<Dummy>>;
 // Binding model
 pub type BindGroupLayoutId = Id<crate::binding_model::BindGroupLayout<Dummy>>;
 pub type PipelineLayoutId = Id<crate::binding_model::PipelineLayout<Dummy>>;
@@ -233,6 +258,8 @@ pub type RenderPassEncoderId = *mut crate::command::RenderPass;
 pub type ComputePassEncoderId = *mut crate::command::ComputePass;
 pub type RenderBundleEncoderId = *mut crate::command::RenderBundleEncoder;
 pub type RenderBundleId = Id<crate::command::RenderBundle<Dummy>>;
+pub type ComputeBundleEncoderId = *mut crate::command::ComputeBundleEncoder;
+pub type ComputeBundleId = Id<crate::command::ComputeBundle<Dummy>>;
 pub type QuerySetId = Id<crate::resource::QuerySet<Dummy>>;
 
 #[test]
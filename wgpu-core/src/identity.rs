@@ -152,6 +152,7 @@ pub trait GlobalIdentityHandlerFactory:
     + IdentityHandlerFactory<id::BindGroupId>
     + IdentityHandlerFactory<id::CommandBufferId>
     + IdentityHandlerFactory<id::RenderBundleId>
+    + IdentityHandlerFactory<id::ComputeBundleId>
     + IdentityHandlerFactory<id::RenderPipelineId>
     + IdentityHandlerFactory<id::ComputePipelineId>
     + IdentityHandlerFactory<id::QuerySetId>
@@ -160,6 +161,7 @@ pub trait GlobalIdentityHandlerFactory:
     + IdentityHandlerFactory<id::TextureId>
     + IdentityHandlerFactory<id::TextureViewId>
     + IdentityHandlerFactory<id::SamplerId>
+    + IdentityHandlerFactory<id::ExternalTextureId>
     + IdentityHandlerFactory<id::SurfaceId>
 {
     fn ids_are_generated_in_wgpu() -> bool;
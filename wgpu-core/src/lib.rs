@@ -67,6 +67,7 @@ pub mod resource;
 pub mod storage;
 mod track;
 mod validation;
+pub mod worker;
 
 pub use hal::{api, MAX_BIND_GROUPS, MAX_COLOR_ATTACHMENTS, MAX_VERTEX_BUFFERS};
 
@@ -225,6 +226,10 @@ pub struct LifeGuard {
     /// The `label` from the descriptor used to create the resource.
     #[cfg(debug_assertions)]
     pub(crate) label: String,
+
+    /// Where the resource came from, recorded when
+    /// [`wgt::InstanceFlags::TRACK_LIVE_RESOURCES`] is set.
+    pub(crate) origin: Option<Box<device::live::ResourceOrigin>>,
 }
 
 impl LifeGuard {
@@ -235,6 +240,7 @@ impl LifeGuard {
             submission_index: AtomicUsize::new(0),
             #[cfg(debug_assertions)]
             label: label.to_string(),
+            origin: None,
         }
     }
 
@@ -132,6 +132,8 @@ pub enum CreateShaderModuleError {
     #[error(transparent)]
     Validation(#[from] ShaderError<naga::WithSpan<naga::valid::ValidationError>>),
     #[error(transparent)]
+    ExternalTexture(#[from] naga::external_texture::LowerError),
+    #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
     #[error(
         "Shader global {bind:?} uses a group index {group} that exceeds the max_bind_groups limit of {limit}."
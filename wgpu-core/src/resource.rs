@@ -3,14 +3,15 @@ use crate::{
     global::Global,
     hal_api::HalApi,
     hub::Token,
-    id::{AdapterId, DeviceId, SurfaceId, TextureId, Valid},
+    id::{AdapterId, DeviceId, SurfaceId, TextureId, TextureViewId, Valid},
     identity::GlobalIdentityHandlerFactory,
     init_tracker::{BufferInitTracker, TextureInitTracker},
     track::TextureSelector,
-    validation::MissingBufferUsageError,
+    validation::{MissingBufferUsageError, MissingTextureUsageError},
     Label, LifeGuard, RefCount, Stored,
 };
 
+use arrayvec::ArrayVec;
 use smallvec::SmallVec;
 use thiserror::Error;
 
@@ -23,7 +24,11 @@ pub trait Resource {
         #[cfg(debug_assertions)]
         return &self.life_guard().label;
         #[cfg(not(debug_assertions))]
-        return "";
+        return self
+            .life_guard()
+            .origin
+            .as_ref()
+            .map_or("", |origin| &origin.label);
     }
 }
 
@@ -825,6 +830,70 @@ impl<A: hal::Api> Resource for Sampler<A> {
     }
 }
 
+pub type ExternalTextureDescriptor<'a> = wgt::ExternalTextureDescriptor<Label<'a>>;
+
+/// Size in bytes of the conversion parameters of an [`ExternalTexture`], as
+/// read by shaders lowered with `naga::external_texture`.
+pub(crate) const EXTERNAL_TEXTURE_PARAMS_SIZE: wgt::BufferAddress = 80;
+
+/// A video frame or other image made of one to three planes, converted to
+/// RGBA when sampled.
+#[derive(Debug)]
+pub struct ExternalTexture<A: hal::Api> {
+    /// Uniform buffer holding the conversion matrix and the number of planes.
+    pub(crate) params: A::Buffer,
+    pub(crate) device_id: Stored<DeviceId>,
+    /// The plane views, in the order the format defines them.
+    pub(crate) planes: ArrayVec<Stored<TextureViewId>, 3>,
+    pub(crate) life_guard: LifeGuard,
+}
+
+impl<A: hal::Api> ExternalTexture<A> {
+    /// Returns the view to bind at each of the three plane slots.
+    ///
+    /// Slots past the last plane repeat the first one; shaders never read them.
+    pub(crate) fn plane_slots(&self) -> [Valid<TextureViewId>; 3] {
+        let plane = |index: usize| self.planes.get(index).unwrap_or(&self.planes[0]).value;
+        [plane(0), plane(1), plane(2)]
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateExternalTextureError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("TextureView {0:?} is invalid")]
+    InvalidTextureView(TextureViewId),
+    #[error("External texture format {format:?} has {expected} planes, but {actual} were given")]
+    WrongPlaneCount {
+        format: wgt::ExternalTextureFormat,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Plane {plane} must be a single sampled two-dimensional view, but has dimension {dimension:?} and {samples} samples")]
+    InvalidPlaneDimension {
+        plane: usize,
+        dimension: wgt::TextureViewDimension,
+        samples: u32,
+    },
+    #[error("Plane {plane} has format {format:?}, which can't be sampled as a filterable float texture")]
+    InvalidPlaneFormat {
+        plane: usize,
+        format: wgt::TextureFormat,
+    },
+    #[error(transparent)]
+    MissingTextureUsage(#[from] MissingTextureUsageError),
+}
+
+impl<A: hal::Api> Resource for ExternalTexture<A> {
+    const TYPE: &'static str = "ExternalTexture";
+
+    fn life_guard(&self) -> &LifeGuard {
+        &self.life_guard
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum CreateQuerySetError {
@@ -319,6 +319,8 @@ pub(crate) struct BindGroupStates<A: HalApi> {
     pub buffers: BufferBindGroupState<A>,
     pub textures: TextureBindGroupState<A>,
     pub views: StatelessBindGroupSate<resource::TextureView<A>, id::TextureViewId>,
+    pub external_textures:
+        StatelessBindGroupSate<resource::ExternalTexture<A>, id::ExternalTextureId>,
     pub samplers: StatelessBindGroupSate<resource::Sampler<A>, id::SamplerId>,
 }
 
@@ -328,6 +330,7 @@ impl<A: HalApi> BindGroupStates<A> {
             buffers: BufferBindGroupState::new(),
             textures: TextureBindGroupState::new(),
             views: StatelessBindGroupSate::new(),
+            external_textures: StatelessBindGroupSate::new(),
             samplers: StatelessBindGroupSate::new(),
         }
     }
@@ -340,6 +343,7 @@ impl<A: HalApi> BindGroupStates<A> {
         self.buffers.optimize();
         self.textures.optimize();
         self.views.optimize();
+        self.external_textures.optimize();
         self.samplers.optimize();
     }
 }
@@ -406,6 +410,39 @@ impl<A: HalApi> RenderBundleScope<A> {
     }
 }
 
+/// The resources owned by a compute bundle.
+///
+/// Unlike a [`RenderBundleScope`], this is not a usage scope: every dispatch
+/// in a compute pass is its own usage scope, so the buffers and textures used
+/// by the bundle's bind groups are only merged when the bundle is executed.
+/// `buffers` holds the indirect buffers the bundle dispatches from.
+pub(crate) struct ComputeBundleScope<A: HalApi> {
+    pub buffers: BufferUsageScope<A>,
+    pub bind_groups: StatelessTracker<A, binding_model::BindGroup<A>, id::BindGroupId>,
+    pub compute_pipelines: StatelessTracker<A, pipeline::ComputePipeline<A>, id::ComputePipelineId>,
+}
+
+impl<A: HalApi> ComputeBundleScope<A> {
+    /// Create the compute bundle scope and pull the maximum IDs from the hubs.
+    pub fn new(
+        buffers: &storage::Storage<resource::Buffer<A>, id::BufferId>,
+        bind_groups: &storage::Storage<binding_model::BindGroup<A>, id::BindGroupId>,
+        compute_pipelines: &storage::Storage<pipeline::ComputePipeline<A>, id::ComputePipelineId>,
+    ) -> Self {
+        let mut value = Self {
+            buffers: BufferUsageScope::new(),
+            bind_groups: StatelessTracker::new(),
+            compute_pipelines: StatelessTracker::new(),
+        };
+
+        value.buffers.set_size(buffers.len());
+        value.bind_groups.set_size(bind_groups.len());
+        value.compute_pipelines.set_size(compute_pipelines.len());
+
+        value
+    }
+}
+
 /// A usage scope tracker. Only needs to store stateful resources as stateless
 /// resources cannot possibly have a usage conflict.
 #[derive(Debug)]
@@ -481,11 +518,14 @@ pub(crate) struct Tracker<A: HalApi> {
     pub buffers: BufferTracker<A>,
     pub textures: TextureTracker<A>,
     pub views: StatelessTracker<A, resource::TextureView<A>, id::TextureViewId>,
+    pub external_textures:
+        StatelessTracker<A, resource::ExternalTexture<A>, id::ExternalTextureId>,
     pub samplers: StatelessTracker<A, resource::Sampler<A>, id::SamplerId>,
     pub bind_groups: StatelessTracker<A, binding_model::BindGroup<A>, id::BindGroupId>,
     pub compute_pipelines: StatelessTracker<A, pipeline::ComputePipeline<A>, id::ComputePipelineId>,
     pub render_pipelines: StatelessTracker<A, pipeline::RenderPipeline<A>, id::RenderPipelineId>,
     pub bundles: StatelessTracker<A, command::RenderBundle<A>, id::RenderBundleId>,
+    pub compute_bundles: StatelessTracker<A, command::ComputeBundle<A>, id::ComputeBundleId>,
     pub query_sets: StatelessTracker<A, resource::QuerySet<A>, id::QuerySetId>,
 }
 
@@ -495,11 +535,13 @@ impl<A: HalApi> Tracker<A> {
             buffers: BufferTracker::new(),
             textures: TextureTracker::new(),
             views: StatelessTracker::new(),
+            external_textures: StatelessTracker::new(),
             samplers: StatelessTracker::new(),
             bind_groups: StatelessTracker::new(),
             compute_pipelines: StatelessTracker::new(),
             render_pipelines: StatelessTracker::new(),
             bundles: StatelessTracker::new(),
+            compute_bundles: StatelessTracker::new(),
             query_sets: StatelessTracker::new(),
         }
     }
@@ -519,6 +561,7 @@ impl<A: HalApi> Tracker<A> {
             &storage::Storage<pipeline::RenderPipeline<A>, id::RenderPipelineId>,
         >,
         bundles: Option<&storage::Storage<command::RenderBundle<A>, id::RenderBundleId>>,
+        compute_bundles: Option<&storage::Storage<command::ComputeBundle<A>, id::ComputeBundleId>>,
         query_sets: Option<&storage::Storage<resource::QuerySet<A>, id::QuerySetId>>,
     ) {
         if let Some(buffers) = buffers {
@@ -545,6 +588,9 @@ impl<A: HalApi> Tracker<A> {
         if let Some(bundles) = bundles {
             self.bundles.set_size(bundles.len());
         };
+        if let Some(compute_bundles) = compute_bundles {
+            self.compute_bundles.set_size(compute_bundles.len());
+        };
         if let Some(query_sets) = query_sets {
             self.query_sets.set_size(query_sets.len());
         };
@@ -612,4 +658,13 @@ impl<A: HalApi> Tracker<A> {
 
         Ok(())
     }
+
+    /// Tracks the stateless resources from the given compute bundle. The
+    /// stateful resources are tracked by each dispatch of the bundle.
+    pub fn add_from_compute_bundle(&mut self, compute_bundle: &ComputeBundleScope<A>) {
+        self.bind_groups
+            .add_from_tracker(&compute_bundle.bind_groups);
+        self.compute_pipelines
+            .add_from_tracker(&compute_bundle.compute_pipelines);
+    }
 }
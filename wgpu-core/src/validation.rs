@@ -422,6 +422,13 @@ impl Resource {
                 }
                 _ => return Err(BindingError::WrongType),
             },
+            ResourceType::Texture {
+                class: naga::ImageClass::External,
+                ..
+            } => match entry.ty {
+                BindingType::ExternalTexture => {}
+                _ => return Err(BindingError::WrongType),
+            },
             ResourceType::Texture {
                 dim,
                 arrayed,
@@ -565,6 +572,7 @@ impl Resource {
                         view_dimension,
                         multisampled: multi,
                     },
+                    naga::ImageClass::External => BindingType::ExternalTexture,
                     naga::ImageClass::Storage { format, access } => BindingType::StorageTexture {
                         access: {
                             const LOAD_STORE: naga::StorageAccess = naga::StorageAccess::all();
@@ -1026,6 +1034,9 @@ impl Interface {
                 );
                 let texture_sample_type = match texture_layout.ty {
                     BindingType::Texture { sample_type, .. } => sample_type,
+                    BindingType::ExternalTexture => {
+                        wgt::TextureSampleType::Float { filterable: true }
+                    }
                     _ => unreachable!(),
                 };
 
@@ -0,0 +1,280 @@
+//! A pool of threads for the work of the `*_async` entry points of [`Global`],
+//! such as [`Global::device_create_render_pipeline_async`].
+//!
+//! The threads are only started on first use. On targets without threads,
+//! jobs run on the calling thread instead, and their futures are ready as soon
+//! as they are returned.
+//!
+//! [`Global`]: crate::global::Global
+//! [`Global::device_create_render_pipeline_async`]: crate::global::Global::device_create_render_pipeline_async
+
+use std::{
+    future::Future,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{mpsc, Arc},
+    task::{Context, Poll, Waker},
+};
+
+use parking_lot::Mutex;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Debug)]
+enum State {
+    NotStarted,
+    Running(mpsc::Sender<Job>),
+    /// No threads could be started, jobs run on the calling thread.
+    Inline,
+}
+
+#[derive(Debug)]
+pub(crate) struct WorkerPool {
+    /// How many threads to start, or `None` for one per available core.
+    thread_count: Option<NonZeroUsize>,
+    state: Mutex<State>,
+}
+
+impl WorkerPool {
+    pub(crate) fn new(thread_count: Option<NonZeroUsize>) -> Self {
+        Self {
+            thread_count,
+            state: Mutex::new(State::NotStarted),
+        }
+    }
+
+    /// Runs `job` on one of the pool's threads, and returns a future
+    /// resolving to its result.
+    ///
+    /// If `job` panics, the panic is resumed when the future is polled, and
+    /// the thread goes on to run other jobs.
+    pub(crate) fn spawn<T: Send + 'static>(
+        &self,
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> JobFuture<T> {
+        let future = JobFuture {
+            shared: Arc::new(Mutex::new(Shared {
+                result: None,
+                waker: None,
+                cleanup: None,
+                cancelled: false,
+            })),
+        };
+        let shared = Arc::clone(&future.shared);
+        let job: Job = Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(job));
+            let mut shared = shared.lock();
+            if shared.cancelled {
+                let cleanup = shared.cleanup.take();
+                drop(shared);
+                if let (Some(cleanup), Ok(result)) = (cleanup, result) {
+                    cleanup(result);
+                }
+                return;
+            }
+            shared.result = Some(result);
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        });
+
+        let mut state = self.state.lock();
+        if let State::NotStarted = *state {
+            *state = self.start();
+        }
+        let job = match *state {
+            State::Running(ref sender) => match sender.send(job) {
+                Ok(()) => return future,
+                Err(mpsc::SendError(job)) => job,
+            },
+            _ => job,
+        };
+        drop(state);
+        job();
+        future
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start(&self) -> State {
+        let thread_count = self
+            .thread_count
+            .or_else(|| std::thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let mut started = 0;
+        for index in 0..thread_count {
+            let receiver = Arc::clone(&receiver);
+            let spawned = std::thread::Builder::new()
+                .name(format!("wgpu worker {index}"))
+                .spawn(move || loop {
+                    let job = receiver.lock().recv();
+                    match job {
+                        Ok(job) => job(),
+                        // The pool was dropped.
+                        Err(mpsc::RecvError) => break,
+                    }
+                });
+            match spawned {
+                Ok(_) => started += 1,
+                Err(e) => log::warn!("Unable to start a worker thread: {e}"),
+            }
+        }
+
+        if started == 0 {
+            State::Inline
+        } else {
+            log::debug!("Started {started} worker threads");
+            State::Running(sender)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn start(&self) -> State {
+        State::Inline
+    }
+}
+
+struct Shared<T> {
+    result: Option<std::thread::Result<T>>,
+    waker: Option<Waker>,
+    /// Disposes of the result if the future is dropped before taking it.
+    cleanup: Option<Box<dyn FnOnce(T) + Send>>,
+    /// Whether the future was dropped before the job finished.
+    cancelled: bool,
+}
+
+/// The result of a job run by the worker pool of a [`Global`].
+///
+/// [`Global`]: crate::global::Global
+pub struct JobFuture<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> JobFuture<T> {
+    /// Calls `cleanup` with the result of the job if this future is dropped
+    /// before resolving, so that what the job created isn't leaked.
+    pub(crate) fn on_cancel(self, cleanup: impl FnOnce(T) + Send + 'static) -> Self {
+        self.shared.lock().cleanup = Some(Box::new(cleanup));
+        self
+    }
+}
+
+impl<T> Future for JobFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock();
+        match shared.result.take() {
+            Some(Ok(result)) => {
+                shared.cleanup = None;
+                Poll::Ready(result)
+            }
+            Some(Err(payload)) => {
+                shared.cleanup = None;
+                drop(shared);
+                panic::resume_unwind(payload)
+            }
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for JobFuture<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock();
+        let cleanup = match shared.cleanup.take() {
+            Some(cleanup) => cleanup,
+            // Resolved, or nothing to clean up.
+            None => return,
+        };
+        match shared.result.take() {
+            Some(Ok(result)) => {
+                drop(shared);
+                cleanup(result);
+            }
+            Some(Err(_)) => {}
+            // The job cleans up after itself when it's done.
+            None => {
+                shared.cleanup = Some(cleanup);
+                shared.cancelled = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, task::Wake};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn wait<T>(mut future: JobFuture<T>) -> T {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(result) = Pin::new(&mut future).poll(&mut cx) {
+                return result;
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn panics_are_resumed_in_poll() {
+        let pool = WorkerPool::new(NonZeroUsize::new(1));
+        let future = pool.spawn(|| -> u32 { panic!("job failed") });
+        let payload = panic::catch_unwind(AssertUnwindSafe(|| wait(future))).unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"job failed"));
+
+        // The only thread survived the panic.
+        assert_eq!(wait(pool.spawn(|| 7)), 7);
+    }
+
+    #[test]
+    fn cancelled_results_are_cleaned_up() {
+        let pool = WorkerPool::new(NonZeroUsize::new(1));
+        let (sender, receiver) = mpsc::channel();
+
+        // Dropped while the job is still blocked.
+        let (unblock, blocked) = mpsc::channel::<()>();
+        let cleanup = sender.clone();
+        let future = pool
+            .spawn(move || {
+                blocked.recv().unwrap();
+                1
+            })
+            .on_cancel(move |result| cleanup.send(result).unwrap());
+        drop(future);
+        unblock.send(()).unwrap();
+        assert_eq!(receiver.recv().unwrap(), 1);
+
+        // Dropped after the job is done.
+        let cleanup = sender.clone();
+        let future = pool
+            .spawn(|| 2)
+            .on_cancel(move |result| cleanup.send(result).unwrap());
+        assert_eq!(wait(pool.spawn(|| 0)), 0);
+        drop(future);
+        assert_eq!(receiver.recv().unwrap(), 2);
+
+        // Resolved results belong to the caller.
+        let cleanup = sender.clone();
+        let future = pool
+            .spawn(|| 3)
+            .on_cancel(move |result| cleanup.send(result).unwrap());
+        assert_eq!(wait(future), 3);
+        drop(sender);
+        assert!(receiver.recv().is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! Bookkeeping shared by the backends to implement [`crate::Device::memory_report`].
+
+use parking_lot::Mutex;
+
+/// Kind of resource an allocation is made for.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ResourceKind {
+    Buffer,
+    Texture,
+}
+
+/// Running totals of the memory a device hands out, by resource kind and by heap.
+///
+/// Backends that sub-allocate call [`reserve`] and [`release`] whenever they
+/// obtain or free a memory block, and [`allocate`] and [`free`] for every
+/// resource placed in it. Backends without sub-allocation call both pairs.
+///
+/// [`reserve`]: MemoryCounters::reserve
+/// [`release`]: MemoryCounters::release
+/// [`allocate`]: MemoryCounters::allocate
+/// [`free`]: MemoryCounters::free
+#[derive(Debug, Default)]
+pub(crate) struct MemoryCounters {
+    report: Mutex<wgt::MemoryReport>,
+}
+
+impl MemoryCounters {
+    pub fn new(heaps: Vec<wgt::MemoryHeapReport>) -> Self {
+        Self {
+            report: Mutex::new(wgt::MemoryReport {
+                heaps,
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn reserve(&self, heap: usize, size: u64) {
+        if let Some(heap) = self.report.lock().heaps.get_mut(heap) {
+            heap.reserved_bytes += size;
+        }
+    }
+
+    pub fn release(&self, heap: usize, size: u64) {
+        if let Some(heap) = self.report.lock().heaps.get_mut(heap) {
+            heap.reserved_bytes = heap.reserved_bytes.saturating_sub(size);
+        }
+    }
+
+    pub fn allocate(&self, kind: ResourceKind, heap: usize, size: u64) {
+        let mut report = self.report.lock();
+        if let Some(heap) = report.heaps.get_mut(heap) {
+            heap.allocated_bytes += size;
+        }
+        let usage = usage_mut(&mut report, kind);
+        usage.count += 1;
+        usage.allocated_bytes += size;
+        usage.peak_allocated_bytes = usage.peak_allocated_bytes.max(usage.allocated_bytes);
+        report.peak_allocated_bytes = report.peak_allocated_bytes.max(report.allocated_bytes());
+    }
+
+    pub fn free(&self, kind: ResourceKind, heap: usize, size: u64) {
+        let mut report = self.report.lock();
+        if let Some(heap) = report.heaps.get_mut(heap) {
+            heap.allocated_bytes = heap.allocated_bytes.saturating_sub(size);
+        }
+        let usage = usage_mut(&mut report, kind);
+        usage.count = usage.count.saturating_sub(1);
+        usage.allocated_bytes = usage.allocated_bytes.saturating_sub(size);
+    }
+
+    /// Returns a snapshot of the current totals.
+    pub fn report(&self) -> wgt::MemoryReport {
+        self.report.lock().clone()
+    }
+}
+
+fn usage_mut(report: &mut wgt::MemoryReport, kind: ResourceKind) -> &mut wgt::MemoryUsage {
+    match kind {
+        ResourceKind::Buffer => &mut report.buffers,
+        ResourceKind::Texture => &mut report.textures,
+    }
+}
+
+/// Estimates the memory used by a texture, for backends that can't query
+/// the size of the underlying allocation.
+///
+/// Formats without a well-defined texel block size, such as `Depth24Plus`,
+/// are assumed to use 4 bytes per texel.
+pub fn texture_size_estimate(
+    format: wgt::TextureFormat,
+    size: crate::CopyExtent,
+    mip_level_count: u32,
+    array_layer_count: u32,
+) -> u64 {
+    let (block_width, block_height) = format.block_dimensions();
+    let block_size = format.block_size(None).unwrap_or(4) as u64;
+    (0..mip_level_count)
+        .map(|level| {
+            let width = (size.width >> level).max(1);
+            let height = (size.height >> level).max(1);
+            let depth = (size.depth >> level).max(1);
+            let blocks_wide = ((width + block_width - 1) / block_width) as u64;
+            let blocks_high = ((height + block_height - 1) / block_height) as u64;
+            blocks_wide * blocks_high * depth as u64 * block_size
+        })
+        .sum::<u64>()
+        * array_layer_count as u64
+}
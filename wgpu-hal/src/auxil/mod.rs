@@ -4,6 +4,8 @@ pub(super) mod dxgi;
 #[cfg(all(not(target_arch = "wasm32"), feature = "renderdoc"))]
 pub(super) mod renderdoc;
 
+pub(super) mod memory;
+
 pub mod db {
     pub mod amd {
         pub const VENDOR: u32 = 0x1002;
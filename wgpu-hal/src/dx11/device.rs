@@ -200,6 +200,10 @@ impl crate::Device<super::Api> for super::Device {
     unsafe fn stop_capture(&self) {
         todo!()
     }
+
+    fn memory_report(&self) -> wgt::MemoryReport {
+        self.memory_counters.report()
+    }
 }
 
 impl crate::Queue<super::Api> for super::Queue {
@@ -65,7 +65,9 @@ d3d12::weak_com_inheritance_chain! {
     }
 }
 
-pub struct Device {}
+pub struct Device {
+    memory_counters: crate::auxil::memory::MemoryCounters,
+}
 
 unsafe impl Send for Device {}
 unsafe impl Sync for Device {}
@@ -114,17 +114,38 @@ impl super::Adapter {
             )
         });
 
-        let mut shader_model_support: d3d12_ty::D3D12_FEATURE_DATA_SHADER_MODEL =
-            d3d12_ty::D3D12_FEATURE_DATA_SHADER_MODEL {
-                HighestShaderModel: d3d12_ty::D3D_SHADER_MODEL_6_0,
-            };
-        assert_eq!(0, unsafe {
-            device.CheckFeatureSupport(
-                d3d12_ty::D3D12_FEATURE_SHADER_MODEL,
-                &mut shader_model_support as *mut _ as *mut _,
-                mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_SHADER_MODEL>() as _,
-            )
-        });
+        // `CheckFeatureSupport` rejects shader models that the runtime doesn't
+        // know about, so start from the newest one and work our way down.
+        let shader_model_support = (d3d12_ty::D3D_SHADER_MODEL_6_0
+            ..=crate::dx12::types::D3D_SHADER_MODEL_6_7)
+            .rev()
+            .find_map(|highest_shader_model| {
+                let mut shader_model_support = d3d12_ty::D3D12_FEATURE_DATA_SHADER_MODEL {
+                    HighestShaderModel: highest_shader_model,
+                };
+                let hr = unsafe {
+                    device.CheckFeatureSupport(
+                        d3d12_ty::D3D12_FEATURE_SHADER_MODEL,
+                        &mut shader_model_support as *mut _ as *mut _,
+                        mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_SHADER_MODEL>() as _,
+                    )
+                };
+                (hr == 0).then_some(shader_model_support)
+            })
+            .expect("Unable to query the supported shader model");
+
+        let shader_model = match shader_model_support.HighestShaderModel {
+            0x51 => naga::back::hlsl::ShaderModel::V5_1,
+            0x60 => naga::back::hlsl::ShaderModel::V6_0,
+            0x61 => naga::back::hlsl::ShaderModel::V6_1,
+            0x62 => naga::back::hlsl::ShaderModel::V6_2,
+            0x63 => naga::back::hlsl::ShaderModel::V6_3,
+            0x64 => naga::back::hlsl::ShaderModel::V6_4,
+            0x65 => naga::back::hlsl::ShaderModel::V6_5,
+            0x66 => naga::back::hlsl::ShaderModel::V6_6,
+            0x67 => naga::back::hlsl::ShaderModel::V6_7,
+            _ => naga::back::hlsl::ShaderModel::V5_0,
+        };
 
         let mut workarounds = super::Workarounds::default();
 
@@ -180,6 +201,19 @@ impl super::Adapter {
             hr == 0 && features3.CastingFullyTypedFormatSupported != 0
         };
 
+        let int64_shader_ops_supported = {
+            let mut features1: d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS1 =
+                unsafe { mem::zeroed() };
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    d3d12_ty::D3D12_FEATURE_D3D12_OPTIONS1,
+                    &mut features1 as *mut _ as *mut _,
+                    mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS1>() as _,
+                )
+            };
+            hr == 0 && features1.Int64ShaderOps != 0
+        };
+
         let private_caps = super::PrivateCapabilities {
             instance_flags,
             heterogeneous_resource_heaps: options.ResourceHeapTier
@@ -195,6 +229,7 @@ impl super::Adapter {
             casting_fully_typed_format_supported,
             // See https://github.com/gfx-rs/wgpu/issues/3552
             suballocation_supported: !info.name.contains("Iris(R) Xe"),
+            shader_model,
         };
 
         // Theoretically vram limited, but in practice 2^20 is the limit
@@ -273,6 +308,17 @@ impl super::Adapter {
             shader_model_support.HighestShaderModel >= d3d12_ty::D3D_SHADER_MODEL_5_1,
         );
 
+        // 64-bit integers need DXC, since FXC only supports shader model 5.1.
+        let int64_supported = dxc_container.is_some()
+            && shader_model >= naga::back::hlsl::ShaderModel::V6_0
+            && int64_shader_ops_supported;
+        features.set(wgt::Features::SHADER_INT64, int64_supported);
+        // 64-bit atomics on raw buffers are required by shader model 6.6.
+        features.set(
+            wgt::Features::SHADER_INT64_ATOMIC_MIN_MAX,
+            int64_supported && shader_model >= naga::back::hlsl::ShaderModel::V6_6,
+        );
+
         let bgra8unorm_storage_supported = {
             let mut bgra8unorm_info: d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT =
                 unsafe { mem::zeroed() };
@@ -292,6 +338,13 @@ impl super::Adapter {
             bgra8unorm_storage_supported,
         );
 
+        // All formats of the read-write tier are part of the set guaranteed by
+        // `TypedUAVLoadAdditionalFormats`.
+        features.set(
+            wgt::Features::RW_STORAGE_TEXTURE_TIER_1,
+            options.TypedUAVLoadAdditionalFormats != 0,
+        );
+
         // TODO: Determine if IPresentationManager is supported
         let presentation_timer = auxil::dxgi::time::PresentationTimer::new_dxgi();
 
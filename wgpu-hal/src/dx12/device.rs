@@ -5,7 +5,7 @@ use crate::{
 
 use super::{conv, descriptor, view};
 use parking_lot::Mutex;
-use std::{ffi, mem, num::NonZeroU32, ptr, sync::Arc};
+use std::{borrow::Cow, ffi, mem, num::NonZeroU32, ptr, sync::Arc};
 use winapi::{
     shared::{dxgiformat, dxgitype, minwindef::BOOL, winerror},
     um::{d3d12 as d3d12_ty, synchapi, winbase},
@@ -173,6 +173,12 @@ impl super::Device {
             null_rtv_handle,
             mem_allocator,
             dxc_container,
+            memory_counters: crate::auxil::memory::MemoryCounters::new(vec![
+                wgt::MemoryHeapReport {
+                    device_local: true,
+                    ..Default::default()
+                },
+            ]),
         })
     }
 
@@ -207,9 +213,27 @@ impl super::Device {
 
         let stage_bit = crate::auxil::map_naga_stage(naga_stage);
         let module = &stage.module.naga.module;
+        // 64-bit atomics need shader model 6.6. Other shaders keep the model
+        // of the layout, and so do these if the device is too old, in which
+        // case naga reports the model as too low.
+        let int64_atomics = module
+            .types
+            .iter()
+            .any(|(_, ty)| matches!(ty.inner, naga::TypeInner::Atomic { width: 8, .. }));
+        let naga_options = if int64_atomics
+            && self.dxc_container.is_some()
+            && self.private_caps.shader_model >= hlsl::ShaderModel::V6_6
+        {
+            Cow::Owned(hlsl::Options {
+                shader_model: hlsl::ShaderModel::V6_6,
+                ..layout.naga_options.clone()
+            })
+        } else {
+            Cow::Borrowed(&layout.naga_options)
+        };
         //TODO: reuse the writer
         let mut source = String::new();
-        let mut writer = hlsl::Writer::new(&mut source, &layout.naga_options);
+        let mut writer = hlsl::Writer::new(&mut source, &naga_options);
         let reflection_info = {
             profiling::scope!("naga::back::hlsl::write");
             writer
@@ -220,7 +244,7 @@ impl super::Device {
         let full_stage = format!(
             "{}_{}\0",
             naga_stage.to_hlsl_str(),
-            layout.naga_options.shader_model.to_str()
+            naga_options.shader_model.to_str()
         );
 
         let ep_index = module
@@ -353,6 +377,10 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { resource.SetName(cwstr.as_ptr()) };
         }
 
+        self.memory_counters.reserve(0, size);
+        self.memory_counters
+            .allocate(crate::auxil::memory::ResourceKind::Buffer, 0, size);
+
         Ok(super::Buffer {
             resource,
             size,
@@ -361,6 +389,9 @@ impl crate::Device<super::Api> for super::Device {
     }
 
     unsafe fn destroy_buffer(&self, mut buffer: super::Buffer) {
+        self.memory_counters
+            .free(crate::auxil::memory::ResourceKind::Buffer, 0, buffer.size);
+        self.memory_counters.release(0, buffer.size);
         // Only happens when it's using the windows_rs feature and there's an allocation
         if let Some(alloc) = buffer.allocation.take() {
             super::suballocation::free_buffer_allocation(
@@ -434,7 +465,7 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { resource.SetName(cwstr.as_ptr()) };
         }
 
-        Ok(super::Texture {
+        let texture = super::Texture {
             resource,
             format: desc.format,
             dimension: desc.dimension,
@@ -442,10 +473,19 @@ impl crate::Device<super::Api> for super::Device {
             mip_level_count: desc.mip_level_count,
             sample_count: desc.sample_count,
             allocation,
-        })
+        };
+        let memory_size = texture.memory_size();
+        self.memory_counters.reserve(0, memory_size);
+        self.memory_counters
+            .allocate(crate::auxil::memory::ResourceKind::Texture, 0, memory_size);
+        Ok(texture)
     }
 
     unsafe fn destroy_texture(&self, mut texture: super::Texture) {
+        let memory_size = texture.memory_size();
+        self.memory_counters
+            .free(crate::auxil::memory::ResourceKind::Texture, 0, memory_size);
+        self.memory_counters.release(0, memory_size);
         if let Some(alloc) = texture.allocation.take() {
             super::suballocation::free_texture_allocation(
                 alloc,
@@ -669,6 +709,7 @@ impl crate::Device<super::Api> for super::Device {
                     num_texture_views += count
                 }
                 wgt::BindingType::Sampler { .. } => num_samplers += count,
+                wgt::BindingType::ExternalTexture => unreachable!(),
             }
         }
 
@@ -1190,6 +1231,7 @@ impl crate::Device<super::Api> for super::Device {
                         cpu_samplers.as_mut().unwrap().stage.push(data.handle.raw);
                     }
                 }
+                wgt::BindingType::ExternalTexture => unreachable!(),
             }
         }
 
@@ -1569,4 +1611,8 @@ impl crate::Device<super::Api> for super::Device {
                 .end_frame_capture(self.raw.as_mut_ptr() as *mut _, ptr::null_mut())
         }
     }
+
+    fn memory_report(&self) -> wgt::MemoryReport {
+        self.memory_counters.report()
+    }
 }
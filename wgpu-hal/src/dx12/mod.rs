@@ -193,6 +193,7 @@ struct PrivateCapabilities {
     heap_create_not_zeroed: bool,
     casting_fully_typed_format_supported: bool,
     suballocation_supported: bool,
+    shader_model: naga::back::hlsl::ShaderModel,
 }
 
 #[derive(Default)]
@@ -254,6 +255,7 @@ pub struct Device {
     null_rtv_handle: descriptor::Handle,
     mem_allocator: Option<Mutex<suballocation::GpuAllocatorWrapper>>,
     dxc_container: Option<Arc<shader_compilation::DxcContainer>>,
+    memory_counters: crate::auxil::memory::MemoryCounters,
 }
 
 unsafe impl Send for Device {}
@@ -435,6 +437,16 @@ impl Texture {
         }
     }
 
+    /// Estimated memory used by this texture, ignoring the placement alignment.
+    fn memory_size(&self) -> u64 {
+        crate::auxil::memory::texture_size_estimate(
+            self.format,
+            crate::CopyExtent::map_extent_to_copy_size(&self.size, self.dimension),
+            self.mip_level_count,
+            self.array_layer_count(),
+        ) * self.sample_count as u64
+    }
+
     /// see https://learn.microsoft.com/en-us/windows/win32/direct3d12/subresources#plane-slice
     fn calc_subresource(&self, mip_level: u32, array_layer: u32, plane: u32) -> u32 {
         mip_level + (array_layer + plane * self.array_layer_count()) * self.mip_level_count
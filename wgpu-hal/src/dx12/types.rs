@@ -10,6 +10,9 @@ interface ISwapChainPanelNative(ISwapChainPanelNativeVtbl): IUnknown(IUnknownVtb
     fn SetSwapChain(swapChain: *const winapi::shared::dxgi1_2::IDXGISwapChain1,) -> winapi::um::winnt::HRESULT,
 }}
 
+// `winapi` only knows about shader models up to 6.0.
+pub const D3D_SHADER_MODEL_6_7: winapi::um::d3d12::D3D_SHADER_MODEL = 0x67;
+
 winapi::ENUM! {
     enum D3D12_VIEW_INSTANCING_TIER {
         D3D12_VIEW_INSTANCING_TIER_NOT_SUPPORTED  = 0,
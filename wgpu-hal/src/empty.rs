@@ -10,21 +10,49 @@ pub struct Encoder;
 #[derive(Debug)]
 pub struct Resource;
 
+/// A device that only keeps count of the memory its resources would use.
+pub struct Device {
+    memory_counters: crate::auxil::memory::MemoryCounters,
+}
+
+impl Default for Device {
+    fn default() -> Self {
+        Self {
+            memory_counters: crate::auxil::memory::MemoryCounters::new(vec![
+                wgt::MemoryHeapReport {
+                    device_local: true,
+                    ..Default::default()
+                },
+            ]),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Buffer {
+    size: wgt::BufferAddress,
+}
+
+#[derive(Debug)]
+pub struct Texture {
+    size: u64,
+}
+
 type DeviceResult<T> = Result<T, crate::DeviceError>;
 
 impl crate::Api for Api {
     type Instance = Context;
     type Surface = Context;
     type Adapter = Context;
-    type Device = Context;
+    type Device = Device;
 
     type Queue = Context;
     type CommandEncoder = Encoder;
     type CommandBuffer = Resource;
 
-    type Buffer = Resource;
-    type Texture = Resource;
-    type SurfaceTexture = Resource;
+    type Buffer = Buffer;
+    type Texture = Texture;
+    type SurfaceTexture = Texture;
     type TextureView = Resource;
     type Sampler = Resource;
     type QuerySet = Resource;
@@ -58,13 +86,13 @@ impl crate::Instance<Api> for Context {
 impl crate::Surface<Api> for Context {
     unsafe fn configure(
         &mut self,
-        device: &Context,
+        device: &Device,
         config: &crate::SurfaceConfiguration,
     ) -> Result<(), crate::SurfaceError> {
         Ok(())
     }
 
-    unsafe fn unconfigure(&mut self, device: &Context) {}
+    unsafe fn unconfigure(&mut self, device: &Device) {}
 
     unsafe fn acquire_texture(
         &mut self,
@@ -72,7 +100,7 @@ impl crate::Surface<Api> for Context {
     ) -> Result<Option<crate::AcquiredSurfaceTexture<Api>>, crate::SurfaceError> {
         Ok(None)
     }
-    unsafe fn discard_texture(&mut self, texture: Resource) {}
+    unsafe fn discard_texture(&mut self, texture: Texture) {}
 }
 
 impl crate::Adapter<Api> for Context {
@@ -110,7 +138,7 @@ impl crate::Queue<Api> for Context {
     unsafe fn present(
         &mut self,
         surface: &mut Context,
-        texture: Resource,
+        texture: Texture,
     ) -> Result<(), crate::SurfaceError> {
         Ok(())
     }
@@ -120,32 +148,52 @@ impl crate::Queue<Api> for Context {
     }
 }
 
-impl crate::Device<Api> for Context {
+impl crate::Device<Api> for Device {
     unsafe fn exit(self, queue: Context) {}
-    unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Resource> {
-        Ok(Resource)
+    unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Buffer> {
+        self.memory_counters.reserve(0, desc.size);
+        self.memory_counters
+            .allocate(crate::auxil::memory::ResourceKind::Buffer, 0, desc.size);
+        Ok(Buffer { size: desc.size })
+    }
+    unsafe fn destroy_buffer(&self, buffer: Buffer) {
+        self.memory_counters
+            .free(crate::auxil::memory::ResourceKind::Buffer, 0, buffer.size);
+        self.memory_counters.release(0, buffer.size);
     }
-    unsafe fn destroy_buffer(&self, buffer: Resource) {}
     unsafe fn map_buffer(
         &self,
-        buffer: &Resource,
+        buffer: &Buffer,
         range: crate::MemoryRange,
     ) -> DeviceResult<crate::BufferMapping> {
         Err(crate::DeviceError::Lost)
     }
-    unsafe fn unmap_buffer(&self, buffer: &Resource) -> DeviceResult<()> {
+    unsafe fn unmap_buffer(&self, buffer: &Buffer) -> DeviceResult<()> {
         Ok(())
     }
-    unsafe fn flush_mapped_ranges<I>(&self, buffer: &Resource, ranges: I) {}
-    unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Resource, ranges: I) {}
-
-    unsafe fn create_texture(&self, desc: &crate::TextureDescriptor) -> DeviceResult<Resource> {
-        Ok(Resource)
+    unsafe fn flush_mapped_ranges<I>(&self, buffer: &Buffer, ranges: I) {}
+    unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Buffer, ranges: I) {}
+
+    unsafe fn create_texture(&self, desc: &crate::TextureDescriptor) -> DeviceResult<Texture> {
+        let size = crate::auxil::memory::texture_size_estimate(
+            desc.format,
+            desc.copy_extent(),
+            desc.mip_level_count,
+            desc.array_layer_count(),
+        );
+        self.memory_counters.reserve(0, size);
+        self.memory_counters
+            .allocate(crate::auxil::memory::ResourceKind::Texture, 0, size);
+        Ok(Texture { size })
+    }
+    unsafe fn destroy_texture(&self, texture: Texture) {
+        self.memory_counters
+            .free(crate::auxil::memory::ResourceKind::Texture, 0, texture.size);
+        self.memory_counters.release(0, texture.size);
     }
-    unsafe fn destroy_texture(&self, texture: Resource) {}
     unsafe fn create_texture_view(
         &self,
-        texture: &Resource,
+        texture: &Texture,
         desc: &crate::TextureViewDescriptor,
     ) -> DeviceResult<Resource> {
         Ok(Resource)
@@ -236,6 +284,9 @@ impl crate::Device<Api> for Context {
         false
     }
     unsafe fn stop_capture(&self) {}
+    fn memory_report(&self) -> wgt::MemoryReport {
+        self.memory_counters.report()
+    }
 }
 
 impl crate::CommandEncoder<Api> for Encoder {
@@ -260,15 +311,15 @@ impl crate::CommandEncoder<Api> for Encoder {
     {
     }
 
-    unsafe fn clear_buffer(&mut self, buffer: &Resource, range: crate::MemoryRange) {}
+    unsafe fn clear_buffer(&mut self, buffer: &Buffer, range: crate::MemoryRange) {}
 
-    unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
+    unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Buffer, dst: &Buffer, regions: T) {}
 
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
     unsafe fn copy_external_image_to_texture<T>(
         &mut self,
         src: &wgt::ImageCopyExternalImage,
-        dst: &Resource,
+        dst: &Texture,
         dst_premultiplication: bool,
         regions: T,
     ) where
@@ -278,20 +329,20 @@ impl crate::CommandEncoder<Api> for Encoder {
 
     unsafe fn copy_texture_to_texture<T>(
         &mut self,
-        src: &Resource,
+        src: &Texture,
         src_usage: crate::TextureUses,
-        dst: &Resource,
+        dst: &Texture,
         regions: T,
     ) {
     }
 
-    unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
+    unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Buffer, dst: &Texture, regions: T) {}
 
     unsafe fn copy_texture_to_buffer<T>(
         &mut self,
-        src: &Resource,
+        src: &Texture,
         src_usage: crate::TextureUses,
-        dst: &Resource,
+        dst: &Buffer,
         regions: T,
     ) {
     }
@@ -304,7 +355,7 @@ impl crate::CommandEncoder<Api> for Encoder {
         &mut self,
         set: &Resource,
         range: Range<u32>,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
         stride: wgt::BufferSize,
     ) {
@@ -370,32 +421,32 @@ impl crate::CommandEncoder<Api> for Encoder {
     }
     unsafe fn draw_indirect(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
     }
     unsafe fn draw_indexed_indirect(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
     }
     unsafe fn draw_indirect_count(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
-        count_buffer: &Resource,
+        count_buffer: &Buffer,
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
     }
     unsafe fn draw_indexed_indirect_count(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
-        count_buffer: &Resource,
+        count_buffer: &Buffer,
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
@@ -409,5 +460,5 @@ impl crate::CommandEncoder<Api> for Encoder {
     unsafe fn set_compute_pipeline(&mut self, pipeline: &Resource) {}
 
     unsafe fn dispatch(&mut self, count: [u32; 3]) {}
-    unsafe fn dispatch_indirect(&mut self, buffer: &Resource, offset: wgt::BufferAddress) {}
+    unsafe fn dispatch_indirect(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {}
 }
@@ -906,6 +906,12 @@ impl crate::Adapter<super::Api> for super::Adapter {
             device: super::Device {
                 shared: Arc::clone(&self.shared),
                 main_vao,
+                memory_counters: crate::auxil::memory::MemoryCounters::new(vec![
+                    wgt::MemoryHeapReport {
+                        device_local: true,
+                        ..Default::default()
+                    },
+                ]),
                 #[cfg(all(not(target_arch = "wasm32"), feature = "renderdoc"))]
                 render_doc: Default::default(),
             },
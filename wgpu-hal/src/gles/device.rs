@@ -122,7 +122,7 @@ impl super::Device {
         desc: &crate::TextureDescriptor,
         drop_guard: Option<crate::DropGuard>,
     ) -> super::Texture {
-        super::Texture {
+        let texture = super::Texture {
             inner: super::TextureInner::Texture {
                 raw: glow::NativeTexture(name),
                 target: super::Texture::get_info_from_desc(desc),
@@ -133,7 +133,9 @@ impl super::Device {
             format: desc.format,
             format_desc: self.shared.describe_texture_format(desc.format),
             copy_size: desc.copy_extent(),
-        }
+        };
+        self.track_texture_allocation(&texture);
+        texture
     }
 
     /// # Safety
@@ -150,7 +152,7 @@ impl super::Device {
         desc: &crate::TextureDescriptor,
         drop_guard: Option<crate::DropGuard>,
     ) -> super::Texture {
-        super::Texture {
+        let texture = super::Texture {
             inner: super::TextureInner::Renderbuffer {
                 raw: glow::NativeRenderbuffer(name),
             },
@@ -160,6 +162,18 @@ impl super::Device {
             format: desc.format,
             format_desc: self.shared.describe_texture_format(desc.format),
             copy_size: desc.copy_extent(),
+        };
+        self.track_texture_allocation(&texture);
+        texture
+    }
+
+    /// Counts `texture` towards the memory report, unless it is owned by a drop guard.
+    fn track_texture_allocation(&self, texture: &super::Texture) {
+        if texture.drop_guard.is_none() {
+            let size = texture.memory_size();
+            self.memory_counters.reserve(0, size);
+            self.memory_counters
+                .allocate(crate::auxil::memory::ResourceKind::Texture, 0, size);
         }
     }
 
@@ -597,6 +611,10 @@ impl crate::Device<super::Api> for super::Device {
             None
         };
 
+        self.memory_counters.reserve(0, desc.size);
+        self.memory_counters
+            .allocate(crate::auxil::memory::ResourceKind::Buffer, 0, desc.size);
+
         Ok(super::Buffer {
             raw,
             target,
@@ -606,6 +624,9 @@ impl crate::Device<super::Api> for super::Device {
         })
     }
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
+        self.memory_counters
+            .free(crate::auxil::memory::ResourceKind::Buffer, 0, buffer.size);
+        self.memory_counters.release(0, buffer.size);
         if let Some(raw) = buffer.raw {
             let gl = &self.shared.context.lock();
             unsafe { gl.delete_buffer(raw) };
@@ -898,7 +919,7 @@ impl crate::Device<super::Api> for super::Device {
             super::TextureInner::Texture { raw, target }
         };
 
-        Ok(super::Texture {
+        let texture = super::Texture {
             inner,
             drop_guard: None,
             mip_level_count: desc.mip_level_count,
@@ -906,10 +927,16 @@ impl crate::Device<super::Api> for super::Device {
             format: desc.format,
             format_desc,
             copy_size: desc.copy_extent(),
-        })
+        };
+        self.track_texture_allocation(&texture);
+        Ok(texture)
     }
     unsafe fn destroy_texture(&self, texture: super::Texture) {
         if texture.drop_guard.is_none() {
+            let size = texture.memory_size();
+            self.memory_counters
+                .free(crate::auxil::memory::ResourceKind::Texture, 0, size);
+            self.memory_counters.release(0, size);
             let gl = &self.shared.context.lock();
             match texture.inner {
                 super::TextureInner::Renderbuffer { raw, .. } => {
@@ -1111,6 +1138,7 @@ impl crate::Device<super::Api> for super::Device {
                         ty: wgt::BufferBindingType::Storage { .. },
                         ..
                     } => &mut num_storage_buffers,
+                    wgt::BindingType::ExternalTexture => unreachable!(),
                 };
 
                 binding_to_slot[entry.binding as usize] = *counter;
@@ -1197,6 +1225,7 @@ impl crate::Device<super::Api> for super::Device {
                         format: format_desc.internal,
                     })
                 }
+                wgt::BindingType::ExternalTexture => unreachable!(),
             };
             contents.push(binding);
         }
@@ -1457,6 +1486,10 @@ impl crate::Device<super::Api> for super::Device {
                 .end_frame_capture(ptr::null_mut(), ptr::null_mut())
         }
     }
+
+    fn memory_report(&self) -> wgt::MemoryReport {
+        self.memory_counters.report()
+    }
 }
 
 #[cfg(all(
@@ -231,6 +231,7 @@ pub struct Adapter {
 pub struct Device {
     shared: Arc<AdapterShared>,
     main_vao: glow::VertexArray,
+    memory_counters: crate::auxil::memory::MemoryCounters,
     #[cfg(all(not(target_arch = "wasm32"), feature = "renderdoc"))]
     render_doc: crate::auxil::renderdoc::RenderDoc,
 }
@@ -330,6 +331,16 @@ pub struct Texture {
 }
 
 impl Texture {
+    /// Estimated memory used by this texture, since GL doesn't expose allocation sizes.
+    fn memory_size(&self) -> u64 {
+        crate::auxil::memory::texture_size_estimate(
+            self.format,
+            self.copy_size,
+            self.mip_level_count,
+            self.array_layer_count,
+        )
+    }
+
     pub fn default_framebuffer(format: wgt::TextureFormat) -> Self {
         Self {
             inner: TextureInner::DefaultRenderbuffer,
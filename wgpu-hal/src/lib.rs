@@ -389,6 +389,10 @@ pub trait Device<A: Api>: WasmNotSend + WasmNotSync {
 
     unsafe fn start_capture(&self) -> bool;
     unsafe fn stop_capture(&self);
+
+    /// Returns a snapshot of the memory allocated by this device for buffers
+    /// and textures, together with per-heap usage and budget.
+    fn memory_report(&self) -> wgt::MemoryReport;
 }
 
 pub trait Queue<A: Api>: WasmNotSend + WasmNotSync {
@@ -1002,6 +1006,8 @@ pub struct SamplerDescriptor<'a> {
 ///
 /// Valid usage:
 /// - `entries` are sorted by ascending `wgt::BindGroupLayoutEntry::binding`
+/// - `entries` contain no `wgt::BindingType::ExternalTexture`; those are
+///   expanded into their planes and parameters buffer by the caller
 #[derive(Clone, Debug)]
 pub struct BindGroupLayoutDescriptor<'a> {
     pub label: Label<'a>,
@@ -60,6 +60,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
             device: super::Device {
                 shared: Arc::clone(&self.shared),
                 features,
+                memory_counters: super::memory_counters(),
             },
             queue: super::Queue {
                 raw: Arc::new(Mutex::new(queue)),
@@ -772,6 +773,7 @@ impl super::PrivateCapabilities {
                 && (device.supports_family(MTLGPUFamily::Apple3)
                     || device.supports_family(MTLGPUFamily::Mac1)),
             supports_capture_manager: version.at_least((10, 13), (11, 0), os_is_mac),
+            supports_memory_budget: version.at_least((10, 13), (16, 0), os_is_mac),
             can_set_maximum_drawables_count: version.at_least((10, 14), (11, 2), os_is_mac),
             can_set_display_sync: version.at_least((10, 13), OS_NOT_SUPPORT, os_is_mac),
             can_set_next_drawable_timeout: version.at_least((10, 13), (11, 0), os_is_mac),
@@ -794,6 +796,10 @@ impl super::PrivateCapabilities {
             supports_preserve_invariance: version.at_least((11, 0), (13, 0), os_is_mac),
             // Metal 2.2 on mac, 2.3 on iOS.
             supports_shader_primitive_index: version.at_least((10, 15), (14, 0), os_is_mac),
+            // `atomic_ulong` min/max is only available on Apple8 and Mac2 GPUs.
+            supports_int64_atomic_min_max: family_check
+                && (device.supports_family(MTLGPUFamily::Apple8)
+                    || device.supports_family(MTLGPUFamily::Mac2)),
             has_unified_memory: if version.at_least((10, 15), (13, 0), os_is_mac) {
                 Some(device.has_unified_memory())
             } else {
@@ -842,6 +848,13 @@ impl super::PrivateCapabilities {
             F::DUAL_SOURCE_BLENDING,
             self.msl_version >= MTLLanguageVersion::V1_2 && self.dual_source_blending,
         );
+        features.set(
+            F::RW_STORAGE_TEXTURE_TIER_1,
+            matches!(
+                self.read_write_texture_tier,
+                metal::MTLReadWriteTextureTier::Tier2
+            ),
+        );
         features.set(F::TEXTURE_COMPRESSION_ASTC, self.format_astc);
         features.set(F::TEXTURE_COMPRESSION_ASTC_HDR, self.format_astc_hdr);
         features.set(F::TEXTURE_COMPRESSION_BC, self.format_bc);
@@ -852,6 +865,14 @@ impl super::PrivateCapabilities {
             F::SHADER_PRIMITIVE_INDEX,
             self.supports_shader_primitive_index,
         );
+        features.set(
+            F::SHADER_INT64,
+            self.msl_version >= MTLLanguageVersion::V2_3,
+        );
+        features.set(
+            F::SHADER_INT64_ATOMIC_MIN_MAX,
+            self.msl_version >= MTLLanguageVersion::V2_4 && self.supports_int64_atomic_min_max,
+        );
 
         features.set(
             F::TEXTURE_BINDING_ARRAY
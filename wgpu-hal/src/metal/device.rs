@@ -261,6 +261,7 @@ impl super::Device {
         super::Device {
             shared: Arc::new(super::AdapterShared::new(raw)),
             features,
+            memory_counters: super::memory_counters(),
         }
     }
 
@@ -299,13 +300,24 @@ impl crate::Device<super::Api> for super::Device {
             if let Some(label) = desc.label {
                 raw.set_label(label);
             }
+            self.memory_counters.allocate(
+                crate::auxil::memory::ResourceKind::Buffer,
+                0,
+                raw.allocated_size(),
+            );
             Ok(super::Buffer {
                 raw,
                 size: desc.size,
             })
         })
     }
-    unsafe fn destroy_buffer(&self, _buffer: super::Buffer) {}
+    unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
+        self.memory_counters.free(
+            crate::auxil::memory::ResourceKind::Buffer,
+            0,
+            buffer.raw.allocated_size(),
+        );
+    }
 
     unsafe fn map_buffer(
         &self,
@@ -371,6 +383,11 @@ impl crate::Device<super::Api> for super::Device {
             if let Some(label) = desc.label {
                 raw.set_label(label);
             }
+            self.memory_counters.allocate(
+                crate::auxil::memory::ResourceKind::Texture,
+                0,
+                raw.allocated_size(),
+            );
 
             Ok(super::Texture {
                 raw,
@@ -383,7 +400,13 @@ impl crate::Device<super::Api> for super::Device {
         })
     }
 
-    unsafe fn destroy_texture(&self, _texture: super::Texture) {}
+    unsafe fn destroy_texture(&self, texture: super::Texture) {
+        self.memory_counters.free(
+            crate::auxil::memory::ResourceKind::Texture,
+            0,
+            texture.raw.allocated_size(),
+        );
+    }
 
     unsafe fn create_texture_view(
         &self,
@@ -637,6 +660,7 @@ impl crate::Device<super::Api> for super::Device {
                                 wgt::StorageTextureAccess::ReadWrite => true,
                             };
                         }
+                        wgt::BindingType::ExternalTexture => unreachable!(),
                     }
 
                     let br = naga::ResourceBinding {
@@ -768,6 +792,7 @@ impl crate::Device<super::Api> for super::Device {
                         );
                         counter.textures += size;
                     }
+                    wgt::BindingType::ExternalTexture => unreachable!(),
                 }
             }
         }
@@ -1218,4 +1243,19 @@ impl crate::Device<super::Api> for super::Device {
         }
         shared_capture_manager.stop_capture();
     }
+
+    fn memory_report(&self) -> wgt::MemoryReport {
+        let mut report = self.memory_counters.report();
+        if self.shared.private_caps.supports_memory_budget {
+            let device = self.shared.device.lock();
+            // Metal only tells us the total for the whole device.
+            let heap = &mut report.heaps[0];
+            heap.reserved_bytes = device.current_allocated_size();
+            heap.budget_bytes = Some(device.recommended_max_working_set_size());
+        } else {
+            report.heaps[0].reserved_bytes =
+                report.buffers.allocated_bytes + report.textures.allocated_bytes;
+        }
+        report
+    }
 }
@@ -252,6 +252,7 @@ struct PrivateCapabilities {
     supports_debug_markers: bool,
     supports_binary_archives: bool,
     supports_capture_manager: bool,
+    supports_memory_budget: bool,
     can_set_maximum_drawables_count: bool,
     can_set_display_sync: bool,
     can_set_next_drawable_timeout: bool,
@@ -261,6 +262,7 @@ struct PrivateCapabilities {
     supports_depth_clip_control: bool,
     supports_preserve_invariance: bool,
     supports_shader_primitive_index: bool,
+    supports_int64_atomic_min_max: bool,
     has_unified_memory: Option<bool>,
     timestamp_query_support: TimestampQuerySupport,
 }
@@ -290,6 +292,15 @@ struct AdapterShared {
 unsafe impl Send for AdapterShared {}
 unsafe impl Sync for AdapterShared {}
 
+/// Metal has a single, unified pool of memory from the application's point of view.
+fn memory_counters() -> crate::auxil::memory::MemoryCounters {
+    crate::auxil::memory::MemoryCounters::new(vec![wgt::MemoryHeapReport {
+        size: None,
+        device_local: true,
+        ..Default::default()
+    }])
+}
+
 impl AdapterShared {
     fn new(device: metal::Device) -> Self {
         let private_caps = PrivateCapabilities::new(&device);
@@ -329,6 +340,7 @@ impl Queue {
 pub struct Device {
     shared: Arc<AdapterShared>,
     features: wgt::Features,
+    memory_counters: crate::auxil::memory::MemoryCounters,
 }
 
 pub struct Surface {
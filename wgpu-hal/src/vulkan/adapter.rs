@@ -33,6 +33,7 @@ pub struct PhysicalDeviceFeatures {
     )>,
     zero_initialize_workgroup_memory:
         Option<vk::PhysicalDeviceZeroInitializeWorkgroupMemoryFeatures>,
+    shader_atomic_int64: Option<vk::PhysicalDeviceShaderAtomicInt64Features>,
 }
 
 // This is safe because the structs have `p_next: *mut c_void`, which we null out/never read.
@@ -71,6 +72,9 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.zero_initialize_workgroup_memory {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.shader_atomic_int64 {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -153,7 +157,9 @@ impl PhysicalDeviceFeatures {
                     downlevel_flags.contains(wgt::DownlevelFlags::FRAGMENT_WRITABLE_STORAGE),
                 )
                 //.shader_image_gather_extended(
-                //.shader_storage_image_extended_formats(
+                .shader_storage_image_extended_formats(
+                    requested_features.contains(wgt::Features::RW_STORAGE_TEXTURE_TIER_1),
+                )
                 .shader_uniform_buffer_array_dynamic_indexing(
                     requested_features.contains(wgt::Features::BUFFER_BINDING_ARRAY),
                 )
@@ -172,7 +178,7 @@ impl PhysicalDeviceFeatures {
                 //.shader_clip_distance(requested_features.contains(wgt::Features::SHADER_CLIP_DISTANCE))
                 //.shader_cull_distance(requested_features.contains(wgt::Features::SHADER_CULL_DISTANCE))
                 .shader_float64(requested_features.contains(wgt::Features::SHADER_F64))
-                //.shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
+                .shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
                 .shader_int16(requested_features.contains(wgt::Features::SHADER_I16))
                 //.shader_resource_residency(requested_features.contains(wgt::Features::SHADER_RESOURCE_RESIDENCY))
                 .geometry_shader(requested_features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX))
@@ -292,6 +298,17 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            shader_atomic_int64: if requested_features
+                .contains(wgt::Features::SHADER_INT64_ATOMIC_MIN_MAX)
+            {
+                Some(
+                    vk::PhysicalDeviceShaderAtomicInt64Features::builder()
+                        .shader_buffer_int64_atomics(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
         }
     }
 
@@ -379,7 +396,11 @@ impl PhysicalDeviceFeatures {
             self.core.vertex_pipeline_stores_and_atomics != 0,
         );
         //if self.core.shader_image_gather_extended != 0 {
-        //if self.core.shader_storage_image_extended_formats != 0 {
+        features.set(
+            F::RW_STORAGE_TEXTURE_TIER_1,
+            self.core.shader_storage_image_extended_formats != 0
+                && supports_rw_storage_texture_tier_1(instance, phd),
+        );
         features.set(
             F::BUFFER_BINDING_ARRAY,
             self.core.shader_uniform_buffer_array_dynamic_indexing != 0,
@@ -408,7 +429,7 @@ impl PhysicalDeviceFeatures {
         //if self.core.shader_clip_distance != 0 {
         //if self.core.shader_cull_distance != 0 {
         features.set(F::SHADER_F64, self.core.shader_float64 != 0);
-        //if self.core.shader_int64 != 0 {
+        features.set(F::SHADER_INT64, self.core.shader_int64 != 0);
         features.set(F::SHADER_I16, self.core.shader_int16 != 0);
 
         //if caps.supports_extension(vk::KhrSamplerMirrorClampToEdgeFn::name()) {
@@ -480,6 +501,13 @@ impl PhysicalDeviceFeatures {
             );
         }
 
+        if let Some(ref atomic_int64) = self.shader_atomic_int64 {
+            features.set(
+                F::SHADER_INT64_ATOMIC_MIN_MAX,
+                self.core.shader_int64 != 0 && atomic_int64.shader_buffer_int64_atomics != 0,
+            );
+        }
+
         if let Some((ref f16_i8, ref bit16)) = self.shader_float16 {
             features.set(
                 F::SHADER_F16,
@@ -652,6 +680,11 @@ impl PhysicalDeviceCapabilities {
                 }
             }
 
+            // Require `VK_KHR_shader_atomic_int64` if the associated feature was requested
+            if requested_features.contains(wgt::Features::SHADER_INT64_ATOMIC_MIN_MAX) {
+                extensions.push(vk::KhrShaderAtomicInt64Fn::name());
+            }
+
             //extensions.push(vk::KhrSamplerMirrorClampToEdgeFn::name());
             //extensions.push(vk::ExtSamplerFilterMinmaxFn::name());
         }
@@ -673,6 +706,11 @@ impl PhysicalDeviceCapabilities {
             extensions.push(vk::ExtRobustness2Fn::name());
         }
 
+        // Optional `VK_EXT_memory_budget`
+        if self.supports_extension(vk::ExtMemoryBudgetFn::name()) {
+            extensions.push(vk::ExtMemoryBudgetFn::name());
+        }
+
         // Require `VK_KHR_draw_indirect_count` if the associated feature was requested
         // Even though Vulkan 1.2 has promoted the extension to core, we must require the extension to avoid
         // large amounts of spaghetti involved with using PhysicalDeviceVulkan12Features.
@@ -892,6 +930,16 @@ impl super::InstanceShared {
                 builder = builder.push_next(&mut next.1);
             }
 
+            // `VK_KHR_shader_atomic_int64` is promoted to 1.2
+            if capabilities.device_api_version >= vk::API_VERSION_1_2
+                || capabilities.supports_extension(vk::KhrShaderAtomicInt64Fn::name())
+            {
+                let next = features
+                    .shader_atomic_int64
+                    .insert(vk::PhysicalDeviceShaderAtomicInt64Features::default());
+                builder = builder.push_next(next);
+            }
+
             // `VK_KHR_zero_initialize_workgroup_memory` is promoted to 1.3
             if capabilities.device_api_version >= vk::API_VERSION_1_3
                 || capabilities.supports_extension(vk::KhrZeroInitializeWorkgroupMemoryFn::name())
@@ -1196,6 +1244,9 @@ impl super::Adapter {
             }
         });
 
+        let memory_heaps =
+            &mem_properties.memory_heaps[..mem_properties.memory_heap_count as usize];
+
         let swapchain_fn = khr::Swapchain::new(&self.instance.raw, &raw_device);
 
         let indirect_count_fn = if enabled_extensions.contains(&khr::DrawIndirectCount::name()) {
@@ -1336,6 +1387,23 @@ impl super::Adapter {
             workarounds: self.workarounds,
             render_passes: Mutex::new(Default::default()),
             framebuffers: Mutex::new(Default::default()),
+            memory_type_heaps: memory_types
+                .iter()
+                .map(|memory_type| memory_type.heap_index as usize)
+                .collect(),
+            memory_blocks: Mutex::new(Default::default()),
+            memory_counters: crate::auxil::memory::MemoryCounters::new(
+                memory_heaps
+                    .iter()
+                    .map(|memory_heap| wgt::MemoryHeapReport {
+                        size: Some(memory_heap.size),
+                        device_local: memory_heap
+                            .flags
+                            .contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
         });
         let mut relay_semaphores = [vk::Semaphore::null(); 2];
         for sem in relay_semaphores.iter_mut() {
@@ -1376,8 +1444,7 @@ impl super::Adapter {
                         heap: memory_type.heap_index,
                     })
                     .collect(),
-                memory_heaps: mem_properties.memory_heaps
-                    [..mem_properties.memory_heap_count as usize]
+                memory_heaps: memory_heaps
                     .iter()
                     .map(|&memory_heap| gpu_alloc::MemoryHeap {
                         size: memory_heap.size,
@@ -1747,6 +1814,33 @@ fn supports_format(
     }
 }
 
+fn supports_rw_storage_texture_tier_1(instance: &ash::Instance, phd: vk::PhysicalDevice) -> bool {
+    [
+        vk::Format::R8_UNORM,
+        vk::Format::R8_UINT,
+        vk::Format::R8_SINT,
+        vk::Format::R16_UINT,
+        vk::Format::R16_SINT,
+        vk::Format::R16_SFLOAT,
+        vk::Format::R8G8B8A8_UNORM,
+        vk::Format::R8G8B8A8_UINT,
+        vk::Format::R8G8B8A8_SINT,
+        vk::Format::R16G16B16A16_UINT,
+        vk::Format::R16G16B16A16_SINT,
+        vk::Format::R16G16B16A16_SFLOAT,
+        vk::Format::R32G32B32A32_UINT,
+        vk::Format::R32G32B32A32_SINT,
+        vk::Format::R32G32B32A32_SFLOAT,
+    ]
+    .iter()
+    .all(|&format| {
+        let properties = unsafe { instance.get_physical_device_format_properties(phd, format) };
+        properties
+            .optimal_tiling_features
+            .contains(vk::FormatFeatureFlags::STORAGE_IMAGE)
+    })
+}
+
 fn supports_bgra8unorm_storage(
     instance: &ash::Instance,
     phd: vk::PhysicalDevice,
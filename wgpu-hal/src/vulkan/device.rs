@@ -325,7 +325,12 @@ impl gpu_alloc::MemoryDevice<vk::DeviceMemory> for super::DeviceShared {
         }
 
         match unsafe { self.raw.allocate_memory(&info, None) } {
-            Ok(memory) => Ok(memory),
+            Ok(memory) => {
+                let heap = self.memory_type_heaps[memory_type as usize];
+                self.memory_counters.reserve(heap, size);
+                self.memory_blocks.lock().insert(memory, (heap, size));
+                Ok(memory)
+            }
             Err(vk::Result::ERROR_OUT_OF_DEVICE_MEMORY) => {
                 Err(gpu_alloc::OutOfMemory::OutOfDeviceMemory)
             }
@@ -338,6 +343,9 @@ impl gpu_alloc::MemoryDevice<vk::DeviceMemory> for super::DeviceShared {
     }
 
     unsafe fn deallocate_memory(&self, memory: vk::DeviceMemory) {
+        if let Some((heap, size)) = self.memory_blocks.lock().remove(&memory) {
+            self.memory_counters.release(heap, size);
+        }
         unsafe { self.raw.free_memory(memory, None) };
     }
 
@@ -880,6 +888,11 @@ impl crate::Device<super::Api> for super::Device {
                 .raw
                 .bind_buffer_memory(raw, *block.memory(), block.offset())?
         };
+        self.shared.memory_counters.allocate(
+            crate::auxil::memory::ResourceKind::Buffer,
+            self.shared.memory_type_heaps[block.memory_type() as usize],
+            block.size(),
+        );
 
         if let Some(label) = desc.label {
             unsafe {
@@ -896,11 +909,13 @@ impl crate::Device<super::Api> for super::Device {
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
         unsafe { self.shared.raw.destroy_buffer(buffer.raw, None) };
         if let Some(block) = buffer.block {
-            unsafe {
-                self.mem_allocator
-                    .lock()
-                    .dealloc(&*self.shared, block.into_inner())
-            };
+            let block = block.into_inner();
+            self.shared.memory_counters.free(
+                crate::auxil::memory::ResourceKind::Buffer,
+                self.shared.memory_type_heaps[block.memory_type() as usize],
+                block.size(),
+            );
+            unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
         }
     }
 
@@ -1029,6 +1044,11 @@ impl crate::Device<super::Api> for super::Device {
                 .raw
                 .bind_image_memory(raw, *block.memory(), block.offset())?
         };
+        self.shared.memory_counters.allocate(
+            crate::auxil::memory::ResourceKind::Texture,
+            self.shared.memory_type_heaps[block.memory_type() as usize],
+            block.size(),
+        );
 
         if let Some(label) = desc.label {
             unsafe {
@@ -1053,6 +1073,11 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { self.shared.raw.destroy_image(texture.raw, None) };
         }
         if let Some(block) = texture.block {
+            self.shared.memory_counters.free(
+                crate::auxil::memory::ResourceKind::Texture,
+                self.shared.memory_type_heaps[block.memory_type() as usize],
+                block.size(),
+            );
             unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
         }
     }
@@ -1256,6 +1281,7 @@ impl crate::Device<super::Api> for super::Device {
                 wgt::BindingType::StorageTexture { .. } => {
                     desc_count.storage_image += count;
                 }
+                wgt::BindingType::ExternalTexture => unreachable!(),
             }
         }
 
@@ -2024,6 +2050,34 @@ impl crate::Device<super::Api> for super::Device {
             }
         }
     }
+
+    fn memory_report(&self) -> wgt::MemoryReport {
+        let mut report = self.shared.memory_counters.report();
+        if !self
+            .shared
+            .enabled_extensions
+            .contains(&vk::ExtMemoryBudgetFn::name())
+        {
+            return report;
+        }
+        if let Some(ref get_device_properties) = self.shared.instance.get_physical_device_properties
+        {
+            let mut budget = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+            let mut properties = vk::PhysicalDeviceMemoryProperties2::builder()
+                .push_next(&mut budget)
+                .build();
+            unsafe {
+                get_device_properties.get_physical_device_memory_properties2(
+                    self.shared.physical_device,
+                    &mut properties,
+                )
+            };
+            for (heap, &budget) in report.heaps.iter_mut().zip(budget.heap_budget.iter()) {
+                heap.budget_bytes = Some(budget);
+            }
+        }
+        report
+    }
 }
 
 impl From<gpu_alloc::AllocationError> for crate::DeviceError {
@@ -316,6 +316,11 @@ struct DeviceShared {
     workarounds: Workarounds,
     render_passes: Mutex<rustc_hash::FxHashMap<RenderPassKey, vk::RenderPass>>,
     framebuffers: Mutex<rustc_hash::FxHashMap<FramebufferKey, vk::Framebuffer>>,
+    /// Heap index of each memory type.
+    memory_type_heaps: Vec<usize>,
+    /// Heap index and size of each `vk::DeviceMemory` handed to the allocator.
+    memory_blocks: Mutex<rustc_hash::FxHashMap<vk::DeviceMemory, (usize, u64)>>,
+    memory_counters: crate::auxil::memory::MemoryCounters,
 }
 
 pub struct Device {
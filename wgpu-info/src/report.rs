@@ -22,6 +22,7 @@ impl GpuReport {
             flags: wgpu::InstanceFlags::debugging().with_env(),
             dx12_shader_compiler: wgpu::util::dx12_shader_compiler_from_env().unwrap_or_default(),
             gles_minor_version: wgpu::util::gles_minor_version_from_env().unwrap_or_default(),
+            pipeline_worker_threads: None,
         });
         let adapters = instance.enumerate_adapters(wgpu::Backends::all());
 
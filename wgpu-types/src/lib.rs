@@ -314,7 +314,27 @@ bitflags::bitflags! {
         // possible formats and capabilities across backends.
         //
         // ? const FORMATS_TIER_1 = 1 << 14; (https://github.com/gpuweb/gpuweb/issues/3837)
-        // ? const RW_STORAGE_TEXTURE_TIER_1 = 1 << 15; (https://github.com/gpuweb/gpuweb/issues/3838)
+
+        /// Allows [`StorageTextureAccess::ReadOnly`] and [`StorageTextureAccess::ReadWrite`]
+        /// storage textures with the following formats, in addition to `R32Float`, `R32Uint`
+        /// and `R32Sint` which are always supported:
+        /// - `R8Unorm`, `R8Uint`, `R8Sint`
+        /// - `R16Uint`, `R16Sint`, `R16Float`
+        /// - `Rgba8Unorm`, `Rgba8Uint`, `Rgba8Sint`
+        /// - `Rgba16Uint`, `Rgba16Sint`, `Rgba16Float`
+        /// - `Rgba32Uint`, `Rgba32Sint`, `Rgba32Float`
+        ///
+        /// The `R8` and `R16` formats listed above also gain the [`TextureUsages::STORAGE_BINDING`] usage.
+        ///
+        /// See <https://github.com/gpuweb/gpuweb/issues/3838>.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan (with `shaderStorageImageExtendedFormats`)
+        /// - DX12 (with `TypedUAVLoadAdditionalFormats`)
+        /// - Metal (with read-write texture tier 2)
+        ///
+        /// This is a native only feature.
+        const RW_STORAGE_TEXTURE_TIER_1 = 1 << 15;
 
         /// Allows the [`wgpu::TextureUsages::STORAGE_BINDING`] usage on textures with format [`TextureFormat::Bgra8unorm`]
         ///
@@ -762,10 +782,31 @@ bitflags::bitflags! {
         /// - OpenGL
         const SHADER_UNUSED_VERTEX_OUTPUT = 1 << 54;
 
-        // 54..59 available
+        // 57..59 available
 
         // Shader:
 
+        /// Allows shaders to use the `i64` and `u64` types and 64-bit integer literals.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with shaderInt64)
+        /// - DX12 (DXC with Int64ShaderOps)
+        /// - Metal (with MSL 2.3+)
+        ///
+        /// This is a native only feature.
+        const SHADER_INT64 = 1 << 55;
+        /// Allows shaders to use `atomicMin` and `atomicMax` on `atomic<u64>` and
+        /// `atomic<i64>` values in storage buffers.
+        ///
+        /// Requires [`Features::SHADER_INT64`].
+        ///
+        /// Supported platforms:
+        /// - Vulkan (with shaderBufferInt64Atomics)
+        /// - DX12 (DXC with shader model 6.6+)
+        /// - Metal (Apple8+ and Mac2 with MSL 2.4+; only `atomic<u64>`)
+        ///
+        /// This is a native only feature.
+        const SHADER_INT64_ATOMIC_MIN_MAX = 1 << 56;
         /// Enables 64-bit floating point types in SPIR-V shaders.
         ///
         /// Note: even when supported by GPU hardware, 64-bit floating point operations are
@@ -849,6 +890,12 @@ bitflags::bitflags! {
         const VALIDATION = 1 << 1;
         /// Don't pass labels to wgpu-hal.
         const DISCARD_HAL_LABELS = 1 << 2;
+        /// Record the label of every resource, so that the resources still alive on a
+        /// device can be listed, and are logged when the device is dropped.
+        const TRACK_LIVE_RESOURCES = 1 << 3;
+        /// When tracking live resources, also capture a backtrace of where each resource
+        /// was created. This is slow, and has no effect without `TRACK_LIVE_RESOURCES`.
+        const RESOURCE_BACKTRACES = 1 << 4;
     }
 }
 
@@ -887,6 +934,8 @@ impl InstanceFlags {
     /// The environment variables are named after the flags prefixed with "WGPU_". For example:
     /// - WGPU_DEBUG
     /// - WGPU_VALIDATION
+    /// - WGPU_TRACK_LIVE_RESOURCES
+    /// - WGPU_RESOURCE_BACKTRACES
     pub fn with_env(mut self) -> Self {
         fn env(key: &str) -> Option<bool> {
             std::env::var(key).ok().map(|s| match s.as_str() {
@@ -901,6 +950,12 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_DEBUG") {
             self.set(Self::DEBUG, bit);
         }
+        if let Some(bit) = env("WGPU_TRACK_LIVE_RESOURCES") {
+            self.set(Self::TRACK_LIVE_RESOURCES, bit);
+        }
+        if let Some(bit) = env("WGPU_RESOURCE_BACKTRACES") {
+            self.set(Self::RESOURCE_BACKTRACES, bit);
+        }
 
         self
     }
@@ -1572,6 +1627,86 @@ pub struct AdapterInfo {
     pub backend: Backend,
 }
 
+/// Device memory used by resources of a single kind, as part of a [`MemoryReport`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryUsage {
+    /// Number of live resources of this kind.
+    pub count: u64,
+    /// Bytes of device memory currently allocated for these resources.
+    pub allocated_bytes: u64,
+    /// Highest value `allocated_bytes` has reached over the lifetime of the device.
+    pub peak_allocated_bytes: u64,
+}
+
+/// Usage of a single memory heap, as part of a [`MemoryReport`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryHeapReport {
+    /// Total size of the heap in bytes, if the backend knows it.
+    pub size: Option<u64>,
+    /// Whether the heap is local to the device, i.e. VRAM on a discrete GPU.
+    pub device_local: bool,
+    /// Bytes of device memory the device has obtained from the heap.
+    ///
+    /// Sub-allocating backends reserve memory in large blocks, so this is
+    /// usually larger than `allocated_bytes`.
+    pub reserved_bytes: u64,
+    /// Bytes of `reserved_bytes` that are handed out to live resources.
+    pub allocated_bytes: u64,
+    /// How many bytes of this heap the whole process may use without
+    /// risking eviction or allocation failures, if the platform exposes it.
+    ///
+    /// * For [`Backend::Vulkan`], this requires `VK_EXT_memory_budget`.
+    /// * For [`Backend::Metal`], this is the device's recommended maximum working set size.
+    pub budget_bytes: Option<u64>,
+}
+
+/// Report of the device memory used by a [`Device`](../wgpu/struct.Device.html).
+///
+/// The numbers are tracked by the backend allocator, so they include alignment
+/// padding but not memory used internally by the driver.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryReport {
+    /// Memory used by buffers.
+    pub buffers: MemoryUsage,
+    /// Memory used by textures.
+    pub textures: MemoryUsage,
+    /// Per-heap usage. Backends without the notion of heaps report a single heap.
+    pub heaps: Vec<MemoryHeapReport>,
+    /// Highest total of allocated bytes over the lifetime of the device.
+    pub peak_allocated_bytes: u64,
+}
+
+impl MemoryReport {
+    /// Bytes of device memory currently allocated for resources of any kind.
+    pub fn allocated_bytes(&self) -> u64 {
+        self.buffers.allocated_bytes + self.textures.allocated_bytes
+    }
+
+    /// Bytes of device memory obtained from all heaps.
+    pub fn reserved_bytes(&self) -> u64 {
+        self.heaps.iter().map(|heap| heap.reserved_bytes).sum()
+    }
+
+    /// Fraction of the reserved memory that is not allocated to any resource,
+    /// between `0.0` and `1.0`.
+    pub fn fragmentation(&self) -> f32 {
+        let reserved = self.reserved_bytes();
+        if reserved == 0 {
+            return 0.0;
+        }
+        let allocated = self
+            .heaps
+            .iter()
+            .map(|heap| heap.allocated_bytes)
+            .sum::<u64>()
+            .min(reserved);
+        (reserved - allocated) as f32 / reserved as f32
+    }
+}
+
 /// Describes a [`Device`](../wgpu/struct.Device.html).
 ///
 /// Corresponds to [WebGPU `GPUDeviceDescriptor`](
@@ -3029,19 +3164,25 @@ impl TextureFormat {
         } else {
             attachment
         };
+        let rw_tier_1 = device_features.contains(Features::RW_STORAGE_TEXTURE_TIER_1);
+        let rw_storage = if rw_tier_1 {
+            attachment | TextureUsages::STORAGE_BINDING
+        } else {
+            attachment
+        };
 
         #[rustfmt::skip] // lets make a nice table
         let (
             mut flags,
             allowed_usages,
         ) = match *self {
-            Self::R8Unorm =>              (msaa_resolve, attachment),
+            Self::R8Unorm =>              (msaa_resolve, rw_storage),
             Self::R8Snorm =>              (        noaa,      basic),
-            Self::R8Uint =>               (        msaa, attachment),
-            Self::R8Sint =>               (        msaa, attachment),
-            Self::R16Uint =>              (        msaa, attachment),
-            Self::R16Sint =>              (        msaa, attachment),
-            Self::R16Float =>             (msaa_resolve, attachment),
+            Self::R8Uint =>               (        msaa, rw_storage),
+            Self::R8Sint =>               (        msaa, rw_storage),
+            Self::R16Uint =>              (        msaa, rw_storage),
+            Self::R16Sint =>              (        msaa, rw_storage),
+            Self::R16Float =>             (msaa_resolve, rw_storage),
             Self::Rg8Unorm =>             (msaa_resolve, attachment),
             Self::Rg8Snorm =>             (        noaa,      basic),
             Self::Rg8Uint =>              (        msaa, attachment),
@@ -3122,6 +3263,30 @@ impl TextureFormat {
         flags.set(TextureFormatFeatureFlags::FILTERABLE, is_filterable);
         flags.set(TextureFormatFeatureFlags::BLENDABLE, is_filterable);
 
+        let is_read_write_storage = match *self {
+            Self::R32Uint | Self::R32Sint | Self::R32Float => true,
+            Self::R8Unorm
+            | Self::R8Uint
+            | Self::R8Sint
+            | Self::R16Uint
+            | Self::R16Sint
+            | Self::R16Float
+            | Self::Rgba8Unorm
+            | Self::Rgba8Uint
+            | Self::Rgba8Sint
+            | Self::Rgba16Uint
+            | Self::Rgba16Sint
+            | Self::Rgba16Float
+            | Self::Rgba32Uint
+            | Self::Rgba32Sint
+            | Self::Rgba32Float => rw_tier_1,
+            _ => false,
+        };
+        flags.set(
+            TextureFormatFeatureFlags::STORAGE_READ_WRITE,
+            is_read_write_storage,
+        );
+
         TextureFormatFeatures {
             allowed_usages,
             flags,
@@ -5622,6 +5787,60 @@ impl<T> Default for RenderBundleDescriptor<Option<T>> {
     }
 }
 
+/// Describes a [`ComputeBundleEncoder`](../wgpu/struct.ComputeBundleEncoder.html).
+///
+/// Compute bundles are a `wgpu` extension, there is no WebGPU equivalent.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "trace", derive(Serialize))]
+#[cfg_attr(feature = "replay", derive(Deserialize))]
+pub struct ComputeBundleEncoderDescriptor<L> {
+    /// Debug label of the compute bundle encoder. This will show up in graphics debuggers for easy identification.
+    pub label: L,
+}
+
+impl<L> ComputeBundleEncoderDescriptor<L> {
+    /// Takes a closure and maps the label of the compute bundle encoder descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> ComputeBundleEncoderDescriptor<K> {
+        ComputeBundleEncoderDescriptor {
+            label: fun(&self.label),
+        }
+    }
+}
+
+impl<T> Default for ComputeBundleEncoderDescriptor<Option<T>> {
+    fn default() -> Self {
+        Self { label: None }
+    }
+}
+
+/// Describes a [`ComputeBundle`](../wgpu/struct.ComputeBundle.html).
+///
+/// Compute bundles are a `wgpu` extension, there is no WebGPU equivalent.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "trace", derive(Serialize))]
+#[cfg_attr(feature = "replay", derive(Deserialize))]
+pub struct ComputeBundleDescriptor<L> {
+    /// Debug label of the compute bundle. This will show up in graphics debuggers for easy identification.
+    pub label: L,
+}
+
+impl<L> ComputeBundleDescriptor<L> {
+    /// Takes a closure and maps the label of the compute bundle descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> ComputeBundleDescriptor<K> {
+        ComputeBundleDescriptor {
+            label: fun(&self.label),
+        }
+    }
+}
+
+impl<T> Default for ComputeBundleDescriptor<Option<T>> {
+    fn default() -> Self {
+        Self { label: None }
+    }
+}
+
 /// Layout of a texture in a buffer's memory.
 ///
 /// The bytes per row and rows per image can be hard to figure out so here are some examples:
@@ -5849,8 +6068,9 @@ pub enum StorageTextureAccess {
     /// The texture can only be read in the shader and it must be annotated with `read` (WGSL) or
     /// `readonly` (GLSL).
     ///
-    /// [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`] must be enabled to use this access
-    /// mode. This is a native-only extension.
+    /// The format must support [`TextureFormatFeatureFlags::STORAGE_READ_WRITE`]. This is always
+    /// the case for `R32Float`, `R32Uint` and `R32Sint`; other formats require
+    /// [`Features::RW_STORAGE_TEXTURE_TIER_1`] or [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`].
     ///
     /// Example WGSL syntax:
     /// ```rust,ignore
@@ -5866,8 +6086,9 @@ pub enum StorageTextureAccess {
     /// The texture can be both read and written in the shader and must be annotated with
     /// `read_write` in WGSL.
     ///
-    /// [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`] must be enabled to use this access
-    /// mode.  This is a nonstandard, native-only extension.
+    /// The format must support [`TextureFormatFeatureFlags::STORAGE_READ_WRITE`]. This is always
+    /// the case for `R32Float`, `R32Uint` and `R32Sint`; other formats require
+    /// [`Features::RW_STORAGE_TEXTURE_TIER_1`] or [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`].
     ///
     /// Example WGSL syntax:
     /// ```rust,ignore
@@ -6027,6 +6248,21 @@ pub enum BindingType {
         /// Dimension of the texture view that is going to be sampled.
         view_dimension: TextureViewDimension,
     },
+    /// An external texture, such as a decoded video frame.
+    ///
+    /// Example WGSL syntax:
+    /// ```rust,ignore
+    /// @group(0) @binding(0)
+    /// var video: texture_external;
+    /// ```
+    ///
+    /// On native, an external texture binding at index `n` also occupies
+    /// bindings `n + 1` to `n + 3`, which hold its extra planes and conversion
+    /// parameters. Those indices must not be used by other entries of the layout.
+    ///
+    /// Corresponds to [WebGPU `GPUExternalTextureBindingLayout`](
+    /// https://gpuweb.github.io/gpuweb/#dictdef-gpuexternaltexturebindinglayout).
+    ExternalTexture,
 }
 
 impl BindingType {
@@ -6387,6 +6623,82 @@ pub enum SamplerBorderColor {
     Zero,
 }
 
+/// Layout of the planes making up an [`ExternalTexture`](../wgpu/struct.ExternalTexture.html).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub enum ExternalTextureFormat {
+    /// A single plane holding RGBA texels. The conversion matrix is not used.
+    Rgba,
+    /// Two planes: luma in the first, and interleaved chroma (U in red, V in
+    /// green) at half resolution in the second.
+    Nv12,
+    /// Three planes: luma, U chroma and V chroma, each sampled from the red
+    /// channel. Also known as YUV420p.
+    I420,
+}
+
+impl ExternalTextureFormat {
+    /// Returns the number of plane views an external texture of this format is built from.
+    pub fn plane_count(&self) -> usize {
+        match *self {
+            Self::Rgba => 1,
+            Self::Nv12 => 2,
+            Self::I420 => 3,
+        }
+    }
+}
+
+/// Describes an [`ExternalTexture`](../wgpu/struct.ExternalTexture.html).
+///
+/// On native, an external texture is assembled from one to three plane
+/// texture views and a YUV to RGB conversion matrix, which shaders apply when
+/// sampling or loading from the `texture_external`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "trace", derive(serde::Serialize))]
+#[cfg_attr(feature = "replay", derive(serde::Deserialize))]
+pub struct ExternalTextureDescriptor<L> {
+    /// Debug label of the external texture. This will show up in graphics debuggers for easy identification.
+    pub label: L,
+    /// Layout of the plane views.
+    pub format: ExternalTextureFormat,
+    /// Column-major 4x4 matrix that converts `(y, u, v, 1)` into RGBA.
+    ///
+    /// Ignored for [`ExternalTextureFormat::Rgba`].
+    pub yuv_conversion_matrix: [f32; 16],
+}
+
+impl<L> ExternalTextureDescriptor<L> {
+    /// Takes a closure and maps the label of the external texture descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> ExternalTextureDescriptor<K> {
+        ExternalTextureDescriptor {
+            label: fun(&self.label),
+            format: self.format,
+            yuv_conversion_matrix: self.yuv_conversion_matrix,
+        }
+    }
+}
+
+/// Conversion matrix for limited range BT.601 YUV, for use with
+/// [`ExternalTextureDescriptor::yuv_conversion_matrix`].
+#[rustfmt::skip]
+pub const YUV_BT601_TO_RGB: [f32; 16] = [
+    1.164_383,  1.164_383,  1.164_383, 0.0,
+    0.0,       -0.391_762,  2.017_232, 0.0,
+    1.596_027, -0.812_968,  0.0,       0.0,
+   -0.874_202,  0.531_668, -1.085_631, 1.0,
+];
+
+/// Conversion matrix for limited range BT.709 YUV, for use with
+/// [`ExternalTextureDescriptor::yuv_conversion_matrix`].
+#[rustfmt::skip]
+pub const YUV_BT709_TO_RGB: [f32; 16] = [
+    1.164_383,  1.164_383,  1.164_383, 0.0,
+    0.0,       -0.213_249,  2.112_402, 0.0,
+    1.792_741, -0.532_909,  0.0,       0.0,
+   -0.972_945,  0.301_483, -1.133_402, 1.0,
+];
+
 /// Describes how to create a QuerySet.
 ///
 /// Corresponds to [WebGPU `GPUQuerySetDescriptor`](
@@ -6614,6 +6926,9 @@ pub enum Gles3MinorVersion {
 }
 
 /// Options for creating an instance.
+///
+/// Fields may be added in later versions, so fill in the fields you don't set
+/// with `..Default::default()`.
 #[derive(Debug)]
 pub struct InstanceDescriptor {
     /// Which `Backends` to enable.
@@ -6624,6 +6939,11 @@ pub struct InstanceDescriptor {
     pub dx12_shader_compiler: Dx12Compiler,
     /// Which OpenGL ES 3 minor version to request.
     pub gles_minor_version: Gles3MinorVersion,
+    /// How many threads to use for creating pipelines asynchronously.
+    ///
+    /// `None` uses one thread per available core. The threads are only started
+    /// on the first asynchronous pipeline creation.
+    pub pipeline_worker_threads: Option<std::num::NonZeroUsize>,
 }
 
 impl Default for InstanceDescriptor {
@@ -6633,6 +6953,7 @@ impl Default for InstanceDescriptor {
             flags: InstanceFlags::default(),
             dx12_shader_compiler: Dx12Compiler::default(),
             gles_minor_version: Gles3MinorVersion::default(),
+            pipeline_worker_threads: None,
         }
     }
 }
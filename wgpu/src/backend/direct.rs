@@ -1,11 +1,12 @@
 use crate::{
     context::{ObjectId, Unused},
     AdapterInfo, BindGroupDescriptor, BindGroupLayoutDescriptor, BindingResource, BufferBinding,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DownlevelCapabilities, Features, Label, Limits, LoadOp, MapMode, Operations,
+    BufferDescriptor, CommandEncoderDescriptor, ComputeBundleEncoderDescriptor,
+    ComputePassDescriptor, ComputePipelineDescriptor, DownlevelCapabilities,
+    ExternalTextureDescriptor, Features, Label, Limits, LoadOp, MapMode, Operations,
     PipelineLayoutDescriptor, RenderBundleEncoderDescriptor, RenderPipelineDescriptor,
     SamplerDescriptor, ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, ShaderSource, StoreOp,
-    SurfaceStatus, TextureDescriptor, TextureViewDescriptor, UncapturedErrorHandler,
+    SurfaceStatus, TextureDescriptor, TextureView, TextureViewDescriptor, UncapturedErrorHandler,
 };
 
 use arrayvec::ArrayVec;
@@ -16,18 +17,20 @@ use std::{
     borrow::Cow::{Borrowed, Owned},
     error::Error,
     fmt,
-    future::{ready, Ready},
+    future::{ready, Future, Ready},
     ops::Range,
+    pin::Pin,
     slice,
     sync::Arc,
+    task::{self, Poll},
 };
-use wgc::command::{bundle_ffi::*, compute_ffi::*, render_ffi::*};
+use wgc::command::{bundle_ffi::*, compute_bundle_ffi::*, compute_ffi::*, render_ffi::*};
 use wgc::id::TypedId;
 use wgt::{WasmNotSend, WasmNotSync};
 
 const LABEL: &str = "label";
 
-pub struct Context(wgc::global::Global<wgc::identity::IdentityManagerFactory>);
+pub struct Context(Arc<wgc::global::Global<wgc::identity::IdentityManagerFactory>>);
 
 impl Drop for Context {
     fn drop(&mut self) {
@@ -43,13 +46,13 @@ impl fmt::Debug for Context {
 
 impl Context {
     pub unsafe fn from_hal_instance<A: wgc::hal_api::HalApi>(hal_instance: A::Instance) -> Self {
-        Self(unsafe {
+        Self(Arc::new(unsafe {
             wgc::global::Global::from_hal_instance::<A>(
                 "wgpu",
                 wgc::identity::IdentityManagerFactory,
                 hal_instance,
             )
-        })
+        }))
     }
 
     /// # Safety
@@ -60,9 +63,9 @@ impl Context {
     }
 
     pub unsafe fn from_core_instance(core_instance: wgc::instance::Instance) -> Self {
-        Self(unsafe {
+        Self(Arc::new(unsafe {
             wgc::global::Global::from_instance(wgc::identity::IdentityManagerFactory, core_instance)
-        })
+        }))
     }
 
     pub(crate) fn global(&self) -> &wgc::global::Global<wgc::identity::IdentityManagerFactory> {
@@ -227,6 +230,30 @@ impl Context {
         self.0.generate_report()
     }
 
+    pub fn device_memory_report(&self, device: &Device) -> Option<wgt::MemoryReport> {
+        let global = &self.0;
+        wgc::gfx_select!(device.id => global.device_memory_report(device.id)).ok()
+    }
+
+    pub fn device_live_resources(&self, device: &Device) -> Option<Vec<wgc::device::LiveResource>> {
+        let global = &self.0;
+        wgc::gfx_select!(device.id => global.device_live_resources(device.id)).ok()
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn device_set_fault_injector(
+        &self,
+        device: &Device,
+        injector: Option<wgc::device::fault::FaultInjector>,
+    ) {
+        let global = &self.0;
+        if let Err(err) =
+            wgc::gfx_select!(device.id => global.device_set_fault_injector(device.id, injector))
+        {
+            self.handle_error_fatal(err, "Device::set_fault_injector");
+        }
+    }
+
     #[cfg(any(target_os = "ios", target_os = "macos"))]
     pub unsafe fn create_surface_from_core_animation_layer(
         &self,
@@ -302,38 +329,48 @@ impl Context {
         }
     }
 
-    fn handle_error(
+    fn make_error(
         &self,
-        sink_mutex: &Mutex<ErrorSinkRaw>,
         cause: impl Error + WasmNotSend + WasmNotSync + 'static,
         label_key: &'static str,
         label: Label,
         string: &'static str,
-    ) {
+    ) -> crate::Error {
         let error = wgc::error::ContextError {
             string,
             cause: Box::new(cause),
             label: label.unwrap_or_default().to_string(),
             label_key,
         };
-        let mut sink = sink_mutex.lock();
         let mut source_opt: Option<&(dyn Error + 'static)> = Some(&error);
         while let Some(source) = source_opt {
             if let Some(wgc::device::DeviceError::OutOfMemory) =
                 source.downcast_ref::<wgc::device::DeviceError>()
             {
-                return sink.handle_error(crate::Error::OutOfMemory {
+                return crate::Error::OutOfMemory {
                     source: Box::new(error),
-                });
+                };
             }
             source_opt = source.source();
         }
 
         // Otherwise, it is a validation error
-        sink.handle_error(crate::Error::Validation {
+        crate::Error::Validation {
             description: self.format_error(&error),
             source: Box::new(error),
-        });
+        }
+    }
+
+    fn handle_error(
+        &self,
+        sink_mutex: &Mutex<ErrorSinkRaw>,
+        cause: impl Error + WasmNotSend + WasmNotSync + 'static,
+        label_key: &'static str,
+        label: Label,
+        string: &'static str,
+    ) {
+        let error = self.make_error(cause, label_key, label, string);
+        sink_mutex.lock().handle_error(error);
     }
 
     fn handle_error_nolabel(
@@ -515,6 +552,44 @@ pub struct CommandEncoder {
     open: bool,
 }
 
+/// A pipeline being created by the worker threads of the [`Global`].
+///
+/// Creation errors are returned, rather than sent to an error sink. The
+/// pipeline's id is released if creation fails, or if the future is dropped
+/// before it resolves.
+///
+/// [`Global`]: wgc::global::Global
+pub struct CreatePipelineFuture<I, E> {
+    context: Context,
+    future: wgc::worker::JobFuture<(I, Option<E>)>,
+    label: Option<String>,
+    string: &'static str,
+    drop: fn(&Context, I),
+}
+
+impl<I, E> Future for CreatePipelineFuture<I, E>
+where
+    E: Error + WasmNotSend + WasmNotSync + 'static,
+{
+    type Output = Result<(I, ()), crate::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let (id, error) = match Pin::new(&mut self.future).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(match error {
+            None => Ok((id, ())),
+            Some(cause) => {
+                (self.drop)(&self.context, id);
+                Err(self
+                    .context
+                    .make_error(cause, LABEL, self.label.as_deref(), self.string))
+            }
+        })
+    }
+}
+
 impl crate::Context for Context {
     type AdapterId = wgc::id::AdapterId;
     type AdapterData = ();
@@ -532,6 +607,8 @@ impl crate::Context for Context {
     type TextureViewData = ();
     type SamplerId = wgc::id::SamplerId;
     type SamplerData = ();
+    type ExternalTextureId = wgc::id::ExternalTextureId;
+    type ExternalTextureData = ();
     type BufferId = wgc::id::BufferId;
     type BufferData = Buffer;
     type TextureId = wgc::id::TextureId;
@@ -556,6 +633,10 @@ impl crate::Context for Context {
     type RenderBundleEncoderData = wgc::command::RenderBundleEncoder;
     type RenderBundleId = wgc::id::RenderBundleId;
     type RenderBundleData = ();
+    type ComputeBundleEncoderId = Unused;
+    type ComputeBundleEncoderData = wgc::command::ComputeBundleEncoder;
+    type ComputeBundleId = wgc::id::ComputeBundleId;
+    type ComputeBundleData = ();
 
     type SurfaceId = wgc::id::SurfaceId;
     type SurfaceData = Surface;
@@ -579,13 +660,17 @@ impl crate::Context for Context {
     >;
 
     type PopErrorScopeFuture = Ready<Option<crate::Error>>;
+    type CreateRenderPipelineFuture =
+        CreatePipelineFuture<wgc::id::RenderPipelineId, wgc::pipeline::CreateRenderPipelineError>;
+    type CreateComputePipelineFuture =
+        CreatePipelineFuture<wgc::id::ComputePipelineId, wgc::pipeline::CreateComputePipelineError>;
 
     fn init(instance_desc: wgt::InstanceDescriptor) -> Self {
-        Self(wgc::global::Global::new(
+        Self(Arc::new(wgc::global::Global::new(
             "wgpu",
             wgc::identity::IdentityManagerFactory,
             instance_desc,
-        ))
+        )))
     }
 
     unsafe fn instance_create_surface(
@@ -1089,6 +1174,9 @@ impl crate::Context for Context {
                             &remaining_arrayed_texture_views[array.len()..];
                         bm::BindingResource::TextureViewArray(Owned(views))
                     }
+                    BindingResource::ExternalTexture(external_texture) => {
+                        bm::BindingResource::ExternalTexture(external_texture.id.into())
+                    }
                 },
             })
             .collect::<Vec<_>>();
@@ -1229,6 +1317,73 @@ impl crate::Context for Context {
         }
         (id, ())
     }
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        desc: &RenderPipelineDescriptor,
+    ) -> Self::CreateRenderPipelineFuture {
+        use wgc::pipeline as pipe;
+
+        // The descriptor is sent to a worker thread, so it can't borrow from `desc`.
+        let vertex_buffers = desc
+            .vertex
+            .buffers
+            .iter()
+            .map(|vbuf| pipe::VertexBufferLayout {
+                array_stride: vbuf.array_stride,
+                step_mode: vbuf.step_mode,
+                attributes: Owned(vbuf.attributes.to_vec()),
+            })
+            .collect::<Vec<_>>();
+
+        let implicit_pipeline_ids = match desc.layout {
+            Some(_) => None,
+            None => Some(wgc::device::ImplicitPipelineIds {
+                root_id: (),
+                group_ids: &[(); wgc::MAX_BIND_GROUPS],
+            }),
+        };
+        let descriptor = pipe::RenderPipelineDescriptor {
+            label: desc.label.map(|label| Owned(label.to_string())),
+            layout: desc.layout.map(|l| l.id.into()),
+            vertex: pipe::VertexState {
+                stage: pipe::ProgrammableStageDescriptor {
+                    module: desc.vertex.module.id.into(),
+                    entry_point: Owned(desc.vertex.entry_point.to_string()),
+                },
+                buffers: Owned(vertex_buffers),
+            },
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment: desc.fragment.as_ref().map(|frag| pipe::FragmentState {
+                stage: pipe::ProgrammableStageDescriptor {
+                    module: frag.module.id.into(),
+                    entry_point: Owned(frag.entry_point.to_string()),
+                },
+                targets: Owned(frag.targets.to_vec()),
+            }),
+            multiview: desc.multiview,
+        };
+
+        let global = &self.0;
+        let future = wgc::gfx_select!(device => global.device_create_render_pipeline_async(
+            *device,
+            descriptor,
+            (),
+            implicit_pipeline_ids
+        ));
+        CreatePipelineFuture {
+            context: Context(Arc::clone(&self.0)),
+            future,
+            label: desc.label.map(str::to_string),
+            string: "Device::create_render_pipeline_async",
+            drop: |context, pipeline| {
+                crate::context::Context::render_pipeline_drop(context, &pipeline, &())
+            },
+        }
+    }
     fn device_create_compute_pipeline(
         &self,
         device: &Self::DeviceId,
@@ -1279,6 +1434,47 @@ impl crate::Context for Context {
         }
         (id, ())
     }
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor,
+    ) -> Self::CreateComputePipelineFuture {
+        use wgc::pipeline as pipe;
+
+        let implicit_pipeline_ids = match desc.layout {
+            Some(_) => None,
+            None => Some(wgc::device::ImplicitPipelineIds {
+                root_id: (),
+                group_ids: &[(); wgc::MAX_BIND_GROUPS],
+            }),
+        };
+        let descriptor = pipe::ComputePipelineDescriptor {
+            label: desc.label.map(|label| Owned(label.to_string())),
+            layout: desc.layout.map(|l| l.id.into()),
+            stage: pipe::ProgrammableStageDescriptor {
+                module: desc.module.id.into(),
+                entry_point: Owned(desc.entry_point.to_string()),
+            },
+        };
+
+        let global = &self.0;
+        let future = wgc::gfx_select!(device => global.device_create_compute_pipeline_async(
+            *device,
+            descriptor,
+            (),
+            implicit_pipeline_ids
+        ));
+        CreatePipelineFuture {
+            context: Context(Arc::clone(&self.0)),
+            future,
+            label: desc.label.map(str::to_string),
+            string: "Device::create_compute_pipeline_async",
+            drop: |context, pipeline| {
+                crate::context::Context::compute_pipeline_drop(context, &pipeline, &())
+            },
+        }
+    }
     fn device_create_buffer(
         &self,
         device: &Self::DeviceId,
@@ -1377,6 +1573,36 @@ impl crate::Context for Context {
         }
         (id, ())
     }
+    fn device_create_external_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ExternalTextureDescriptor,
+        planes: &[&TextureView],
+    ) -> (Self::ExternalTextureId, Self::ExternalTextureData) {
+        let planes = planes
+            .iter()
+            .map(|view| <Self::TextureViewId>::from(view.id))
+            .collect::<Vec<_>>();
+
+        let global = &self.0;
+        let (id, error) = wgc::gfx_select!(device => global.device_create_external_texture(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            &planes,
+            ()
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_external_texture",
+            );
+        }
+        (id, ())
+    }
     fn device_create_query_set(
         &self,
         device: &Self::DeviceId,
@@ -1441,6 +1667,18 @@ impl crate::Context for Context {
             Err(e) => panic!("Error in Device::create_render_bundle_encoder: {e}"),
         }
     }
+    fn device_create_compute_bundle_encoder(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        desc: &ComputeBundleEncoderDescriptor,
+    ) -> (Self::ComputeBundleEncoderId, Self::ComputeBundleEncoderData) {
+        let descriptor = desc.map_label(|l| l.map(Borrowed));
+        (
+            Unused,
+            wgc::command::ComputeBundleEncoder::new(&descriptor, *device, None),
+        )
+    }
     #[cfg_attr(target_arch = "wasm32", allow(unused))]
     fn device_drop(&self, device: &Self::DeviceId, _device_data: &Self::DeviceData) {
         let global = &self.0;
@@ -1648,6 +1886,15 @@ impl crate::Context for Context {
         wgc::gfx_select!(*sampler => global.sampler_drop(*sampler))
     }
 
+    fn external_texture_drop(
+        &self,
+        external_texture: &Self::ExternalTextureId,
+        _external_texture_data: &Self::ExternalTextureData,
+    ) {
+        let global = &self.0;
+        wgc::gfx_select!(*external_texture => global.external_texture_drop(*external_texture))
+    }
+
     fn query_set_drop(&self, query_set: &Self::QuerySetId, _query_set_data: &Self::QuerySetData) {
         let global = &self.0;
         wgc::gfx_select!(*query_set => global.query_set_drop(*query_set))
@@ -1716,6 +1963,15 @@ impl crate::Context for Context {
         wgc::gfx_select!(*render_bundle => global.render_bundle_drop(*render_bundle))
     }
 
+    fn compute_bundle_drop(
+        &self,
+        compute_bundle: &Self::ComputeBundleId,
+        _compute_bundle_data: &Self::ComputeBundleData,
+    ) {
+        let global = &self.0;
+        wgc::gfx_select!(*compute_bundle => global.compute_bundle_drop(*compute_bundle))
+    }
+
     fn compute_pipeline_drop(
         &self,
         pipeline: &Self::ComputePipelineId,
@@ -2173,6 +2429,24 @@ impl crate::Context for Context {
         (id, ())
     }
 
+    fn compute_bundle_encoder_finish(
+        &self,
+        _encoder: Self::ComputeBundleEncoderId,
+        encoder_data: Self::ComputeBundleEncoderData,
+        desc: &crate::ComputeBundleDescriptor,
+    ) -> (Self::ComputeBundleId, Self::ComputeBundleData) {
+        let global = &self.0;
+        let (id, error) = wgc::gfx_select!(encoder_data.parent() => global.compute_bundle_encoder_finish(
+            encoder_data,
+            &desc.map_label(|l| l.map(Borrowed)),
+            ()
+        ));
+        if let Some(err) = error {
+            self.handle_error_fatal(err, "ComputeBundleEncoder::finish");
+        }
+        (id, ())
+    }
+
     fn queue_write_buffer(
         &self,
         queue: &Self::QueueId,
@@ -2503,6 +2777,99 @@ impl crate::Context for Context {
         wgpu_compute_pass_dispatch_workgroups_indirect(pass_data, *indirect_buffer, indirect_offset)
     }
 
+    fn compute_pass_execute_bundles<'a>(
+        &self,
+        _pass: &mut Self::ComputePassId,
+        pass_data: &mut Self::ComputePassData,
+        compute_bundles: Box<
+            dyn Iterator<Item = (Self::ComputeBundleId, &'a Self::ComputeBundleData)> + 'a,
+        >,
+    ) {
+        let temp_compute_bundles = compute_bundles
+            .map(|(i, _)| i)
+            .collect::<SmallVec<[_; 4]>>();
+        unsafe {
+            wgpu_compute_pass_execute_bundles(
+                pass_data,
+                temp_compute_bundles.as_ptr(),
+                temp_compute_bundles.len(),
+            )
+        }
+    }
+
+    fn compute_bundle_encoder_set_pipeline(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        pipeline: &Self::ComputePipelineId,
+        _pipeline_data: &Self::ComputePipelineData,
+    ) {
+        wgpu_compute_bundle_set_pipeline(encoder_data, *pipeline)
+    }
+
+    fn compute_bundle_encoder_set_bind_group(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        index: u32,
+        bind_group: &Self::BindGroupId,
+        _bind_group_data: &Self::BindGroupData,
+        offsets: &[wgt::DynamicOffset],
+    ) {
+        unsafe {
+            wgpu_compute_bundle_set_bind_group(
+                encoder_data,
+                index,
+                *bind_group,
+                offsets.as_ptr(),
+                offsets.len(),
+            )
+        }
+    }
+
+    fn compute_bundle_encoder_set_push_constants(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        offset: u32,
+        data: &[u8],
+    ) {
+        unsafe {
+            wgpu_compute_bundle_set_push_constant(
+                encoder_data,
+                offset,
+                data.len().try_into().unwrap(),
+                data.as_ptr(),
+            )
+        }
+    }
+
+    fn compute_bundle_encoder_dispatch_workgroups(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        x: u32,
+        y: u32,
+        z: u32,
+    ) {
+        wgpu_compute_bundle_dispatch_workgroups(encoder_data, x, y, z)
+    }
+
+    fn compute_bundle_encoder_dispatch_workgroups_indirect(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        indirect_buffer: &Self::BufferId,
+        _indirect_buffer_data: &Self::BufferData,
+        indirect_offset: wgt::BufferAddress,
+    ) {
+        wgpu_compute_bundle_dispatch_workgroups_indirect(
+            encoder_data,
+            *indirect_buffer,
+            indirect_offset,
+        )
+    }
+
     fn render_bundle_encoder_set_pipeline(
         &self,
         _encoder: &mut Self::RenderBundleEncoderId,
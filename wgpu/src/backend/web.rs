@@ -821,6 +821,123 @@ fn future_request_adapter(
     }
 }
 
+fn map_render_pipeline_descriptor(
+    desc: &crate::RenderPipelineDescriptor,
+) -> web_sys::GpuRenderPipelineDescriptor {
+    let module: &<Context as crate::Context>::ShaderModuleData =
+        downcast_ref(desc.vertex.module.data.as_ref());
+    let mut mapped_vertex_state = web_sys::GpuVertexState::new(desc.vertex.entry_point, &module.0);
+
+    let buffers = desc
+        .vertex
+        .buffers
+        .iter()
+        .map(|vbuf| {
+            let mapped_attributes = vbuf
+                .attributes
+                .iter()
+                .map(|attr| {
+                    web_sys::GpuVertexAttribute::new(
+                        map_vertex_format(attr.format),
+                        attr.offset as f64,
+                        attr.shader_location,
+                    )
+                })
+                .collect::<js_sys::Array>();
+
+            let mut mapped_vbuf =
+                web_sys::GpuVertexBufferLayout::new(vbuf.array_stride as f64, &mapped_attributes);
+            mapped_vbuf.step_mode(map_vertex_step_mode(vbuf.step_mode));
+            mapped_vbuf
+        })
+        .collect::<js_sys::Array>();
+
+    mapped_vertex_state.buffers(&buffers);
+
+    let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
+    let mut mapped_desc = web_sys::GpuRenderPipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout: &<Context as crate::Context>::PipelineLayoutData =
+                    downcast_ref(layout.data.as_ref());
+                JsValue::from(&layout.0)
+            }
+            None => auto_layout,
+        },
+        &mapped_vertex_state,
+    );
+
+    if let Some(label) = desc.label {
+        mapped_desc.label(label);
+    }
+
+    if let Some(ref depth_stencil) = desc.depth_stencil {
+        mapped_desc.depth_stencil(&map_depth_stencil_state(depth_stencil));
+    }
+
+    if let Some(ref frag) = desc.fragment {
+        let targets = frag
+            .targets
+            .iter()
+            .map(|target| match target {
+                Some(target) => {
+                    let mapped_format = map_texture_format(target.format);
+                    let mut mapped_color_state = web_sys::GpuColorTargetState::new(mapped_format);
+                    if let Some(ref bs) = target.blend {
+                        let alpha = map_blend_component(&bs.alpha);
+                        let color = map_blend_component(&bs.color);
+                        let mapped_blend_state = web_sys::GpuBlendState::new(&alpha, &color);
+                        mapped_color_state.blend(&mapped_blend_state);
+                    }
+                    mapped_color_state.write_mask(target.write_mask.bits());
+                    wasm_bindgen::JsValue::from(mapped_color_state)
+                }
+                None => wasm_bindgen::JsValue::null(),
+            })
+            .collect::<js_sys::Array>();
+        let module: &<Context as crate::Context>::ShaderModuleData =
+            downcast_ref(frag.module.data.as_ref());
+        let mapped_fragment_desc =
+            web_sys::GpuFragmentState::new(frag.entry_point, &module.0, &targets);
+        mapped_desc.fragment(&mapped_fragment_desc);
+    }
+
+    let mut mapped_multisample = web_sys::GpuMultisampleState::new();
+    mapped_multisample.count(desc.multisample.count);
+    mapped_multisample.mask(desc.multisample.mask as u32);
+    mapped_multisample.alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
+    mapped_desc.multisample(&mapped_multisample);
+
+    let mapped_primitive = map_primitive_state(&desc.primitive);
+    mapped_desc.primitive(&mapped_primitive);
+    mapped_desc
+}
+
+fn map_compute_pipeline_descriptor(
+    desc: &crate::ComputePipelineDescriptor,
+) -> web_sys::GpuComputePipelineDescriptor {
+    let shader_module: &<Context as crate::Context>::ShaderModuleData =
+        downcast_ref(desc.module.data.as_ref());
+    let mapped_compute_stage =
+        web_sys::GpuProgrammableStage::new(desc.entry_point, &shader_module.0);
+    let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
+    let mut mapped_desc = web_sys::GpuComputePipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout: &<Context as crate::Context>::PipelineLayoutData =
+                    downcast_ref(layout.data.as_ref());
+                JsValue::from(&layout.0)
+            }
+            None => auto_layout,
+        },
+        &mapped_compute_stage,
+    );
+    if let Some(label) = desc.label {
+        mapped_desc.label(label);
+    }
+    mapped_desc
+}
+
 fn future_request_device(
     result: JsFutureResult,
 ) -> Result<
@@ -844,6 +961,22 @@ fn future_request_device(
         })
 }
 
+fn future_create_pipeline<T: JsCast>(
+    result: JsFutureResult,
+) -> Result<(Identified<T>, Sendable<T>), crate::Error> {
+    match result {
+        Ok(js_value) => Ok(create_identified(js_value.unchecked_into())),
+        // Rejected with a `GPUPipelineError`.
+        Err(js_error) => Err(crate::Error::Validation {
+            source: Box::from("<WebGPU Error>"),
+            description: js_sys::Reflect::get(&js_error, &JsValue::from_str("message"))
+                .ok()
+                .and_then(|message| message.as_string())
+                .unwrap_or_default(),
+        }),
+    }
+}
+
 fn future_pop_error_scope(result: JsFutureResult) -> Option<crate::Error> {
     match result {
         Ok(js_value) if js_value.is_object() => {
@@ -999,6 +1132,25 @@ pub enum Canvas {
     Offscreen(web_sys::OffscreenCanvas),
 }
 
+/// A command recorded into a compute bundle.
+///
+/// WebGPU has no compute bundles, so they are recorded on the client and
+/// replayed into the compute passes executing them.
+#[derive(Debug)]
+pub(crate) enum ComputeBundleCommand {
+    SetPipeline(web_sys::GpuComputePipeline),
+    SetBindGroup {
+        index: u32,
+        bind_group: web_sys::GpuBindGroup,
+        offsets: Vec<wgt::DynamicOffset>,
+    },
+    Dispatch([u32; 3]),
+    DispatchIndirect {
+        buffer: web_sys::GpuBuffer,
+        offset: wgt::BufferAddress,
+    },
+}
+
 impl crate::context::Context for Context {
     type AdapterId = Identified<web_sys::GpuAdapter>;
     type AdapterData = Sendable<web_sys::GpuAdapter>;
@@ -1016,6 +1168,8 @@ impl crate::context::Context for Context {
     type TextureViewData = Sendable<web_sys::GpuTextureView>;
     type SamplerId = Identified<web_sys::GpuSampler>;
     type SamplerData = Sendable<web_sys::GpuSampler>;
+    type ExternalTextureId = Identified<web_sys::GpuExternalTexture>;
+    type ExternalTextureData = Sendable<web_sys::GpuExternalTexture>;
     type BufferId = Identified<web_sys::GpuBuffer>;
     type BufferData = Sendable<web_sys::GpuBuffer>;
     type TextureId = Identified<web_sys::GpuTexture>;
@@ -1040,6 +1194,10 @@ impl crate::context::Context for Context {
     type RenderBundleEncoderData = Sendable<web_sys::GpuRenderBundleEncoder>;
     type RenderBundleId = Identified<web_sys::GpuRenderBundle>;
     type RenderBundleData = Sendable<web_sys::GpuRenderBundle>;
+    type ComputeBundleEncoderId = Identified<Vec<ComputeBundleCommand>>;
+    type ComputeBundleEncoderData = Sendable<Vec<ComputeBundleCommand>>;
+    type ComputeBundleId = Identified<Vec<ComputeBundleCommand>>;
+    type ComputeBundleData = Sendable<Vec<ComputeBundleCommand>>;
     type SurfaceId = Identified<(Canvas, web_sys::GpuCanvasContext)>;
     type SurfaceData = Sendable<(Canvas, web_sys::GpuCanvasContext)>;
 
@@ -1067,6 +1225,18 @@ impl crate::context::Context for Context {
     >;
     type PopErrorScopeFuture =
         MakeSendFuture<wasm_bindgen_futures::JsFuture, fn(JsFutureResult) -> Option<crate::Error>>;
+    type CreateRenderPipelineFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(
+            JsFutureResult,
+        ) -> Result<(Self::RenderPipelineId, Self::RenderPipelineData), crate::Error>,
+    >;
+    type CreateComputePipelineFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(
+            JsFutureResult,
+        ) -> Result<(Self::ComputePipelineId, Self::ComputePipelineData), crate::Error>,
+    >;
 
     fn init(_instance_desc: wgt::InstanceDescriptor) -> Self {
         let global: Global = js_sys::global().unchecked_into();
@@ -1578,6 +1748,10 @@ impl crate::context::Context for Context {
                         storage_texture.view_dimension(map_texture_view_dimension(view_dimension));
                         mapped_entry.storage_texture(&storage_texture);
                     }
+                    wgt::BindingType::ExternalTexture => {
+                        let external_texture = web_sys::GpuExternalTextureBindingLayout::new();
+                        mapped_entry.external_texture(&external_texture);
+                    }
                 }
 
                 mapped_entry
@@ -1635,6 +1809,11 @@ impl crate::context::Context for Context {
                     crate::BindingResource::TextureViewArray(..) => {
                         panic!("Web backend does not support BINDING_INDEXING extension")
                     }
+                    crate::BindingResource::ExternalTexture(external_texture) => {
+                        let external_texture: &<Context as crate::Context>::ExternalTextureData =
+                            downcast_ref(external_texture.data.as_ref());
+                        JsValue::from(&external_texture.0)
+                    }
                 };
 
                 web_sys::GpuBindGroupEntry::new(binding.binding, &mapped_resource)
@@ -1678,128 +1857,48 @@ impl crate::context::Context for Context {
         device_data: &Self::DeviceData,
         desc: &crate::RenderPipelineDescriptor,
     ) -> (Self::RenderPipelineId, Self::RenderPipelineData) {
-        let module: &<Context as crate::Context>::ShaderModuleData =
-            downcast_ref(desc.vertex.module.data.as_ref());
-        let mut mapped_vertex_state =
-            web_sys::GpuVertexState::new(desc.vertex.entry_point, &module.0);
-
-        let buffers = desc
-            .vertex
-            .buffers
-            .iter()
-            .map(|vbuf| {
-                let mapped_attributes = vbuf
-                    .attributes
-                    .iter()
-                    .map(|attr| {
-                        web_sys::GpuVertexAttribute::new(
-                            map_vertex_format(attr.format),
-                            attr.offset as f64,
-                            attr.shader_location,
-                        )
-                    })
-                    .collect::<js_sys::Array>();
-
-                let mut mapped_vbuf = web_sys::GpuVertexBufferLayout::new(
-                    vbuf.array_stride as f64,
-                    &mapped_attributes,
-                );
-                mapped_vbuf.step_mode(map_vertex_step_mode(vbuf.step_mode));
-                mapped_vbuf
-            })
-            .collect::<js_sys::Array>();
-
-        mapped_vertex_state.buffers(&buffers);
-
-        let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
-        let mut mapped_desc = web_sys::GpuRenderPipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout: &<Context as crate::Context>::PipelineLayoutData =
-                        downcast_ref(layout.data.as_ref());
-                    JsValue::from(&layout.0)
-                }
-                None => auto_layout,
-            },
-            &mapped_vertex_state,
-        );
-
-        if let Some(label) = desc.label {
-            mapped_desc.label(label);
-        }
-
-        if let Some(ref depth_stencil) = desc.depth_stencil {
-            mapped_desc.depth_stencil(&map_depth_stencil_state(depth_stencil));
-        }
-
-        if let Some(ref frag) = desc.fragment {
-            let targets = frag
-                .targets
-                .iter()
-                .map(|target| match target {
-                    Some(target) => {
-                        let mapped_format = map_texture_format(target.format);
-                        let mut mapped_color_state =
-                            web_sys::GpuColorTargetState::new(mapped_format);
-                        if let Some(ref bs) = target.blend {
-                            let alpha = map_blend_component(&bs.alpha);
-                            let color = map_blend_component(&bs.color);
-                            let mapped_blend_state = web_sys::GpuBlendState::new(&alpha, &color);
-                            mapped_color_state.blend(&mapped_blend_state);
-                        }
-                        mapped_color_state.write_mask(target.write_mask.bits());
-                        wasm_bindgen::JsValue::from(mapped_color_state)
-                    }
-                    None => wasm_bindgen::JsValue::null(),
-                })
-                .collect::<js_sys::Array>();
-            let module: &<Context as crate::Context>::ShaderModuleData =
-                downcast_ref(frag.module.data.as_ref());
-            let mapped_fragment_desc =
-                web_sys::GpuFragmentState::new(frag.entry_point, &module.0, &targets);
-            mapped_desc.fragment(&mapped_fragment_desc);
-        }
-
-        let mut mapped_multisample = web_sys::GpuMultisampleState::new();
-        mapped_multisample.count(desc.multisample.count);
-        mapped_multisample.mask(desc.multisample.mask as u32);
-        mapped_multisample.alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
-        mapped_desc.multisample(&mapped_multisample);
-
-        let mapped_primitive = map_primitive_state(&desc.primitive);
-        mapped_desc.primitive(&mapped_primitive);
-
+        let mapped_desc = map_render_pipeline_descriptor(desc);
         create_identified(device_data.0.create_render_pipeline(&mapped_desc))
     }
 
+    fn device_create_render_pipeline_async(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::RenderPipelineDescriptor,
+    ) -> Self::CreateRenderPipelineFuture {
+        let mapped_desc = map_render_pipeline_descriptor(desc);
+        let promise = device_data.0.create_render_pipeline_async(&mapped_desc);
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(promise),
+            future_create_pipeline,
+        )
+    }
+
     fn device_create_compute_pipeline(
         &self,
         _device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &crate::ComputePipelineDescriptor,
     ) -> (Self::ComputePipelineId, Self::ComputePipelineData) {
-        let shader_module: &<Context as crate::Context>::ShaderModuleData =
-            downcast_ref(desc.module.data.as_ref());
-        let mapped_compute_stage =
-            web_sys::GpuProgrammableStage::new(desc.entry_point, &shader_module.0);
-        let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
-        let mut mapped_desc = web_sys::GpuComputePipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout: &<Context as crate::Context>::PipelineLayoutData =
-                        downcast_ref(layout.data.as_ref());
-                    JsValue::from(&layout.0)
-                }
-                None => auto_layout,
-            },
-            &mapped_compute_stage,
-        );
-        if let Some(label) = desc.label {
-            mapped_desc.label(label);
-        }
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
         create_identified(device_data.0.create_compute_pipeline(&mapped_desc))
     }
 
+    fn device_create_compute_pipeline_async(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::ComputePipelineDescriptor,
+    ) -> Self::CreateComputePipelineFuture {
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
+        let promise = device_data.0.create_compute_pipeline_async(&mapped_desc);
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(promise),
+            future_create_pipeline,
+        )
+    }
+
     fn device_create_buffer(
         &self,
         _device: &Self::DeviceId,
@@ -1867,6 +1966,16 @@ impl crate::context::Context for Context {
         create_identified(device_data.0.create_sampler_with_descriptor(&mapped_desc))
     }
 
+    fn device_create_external_texture(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::ExternalTextureDescriptor,
+        _planes: &[&crate::TextureView],
+    ) -> (Self::ExternalTextureId, Self::ExternalTextureData) {
+        panic!("Web backend does not support creating external textures from planes")
+    }
+
     fn device_create_query_set(
         &self,
         _device: &Self::DeviceId,
@@ -1929,6 +2038,15 @@ impl crate::context::Context for Context {
         create_identified(device_data.0.create_render_bundle_encoder(&mapped_desc))
     }
 
+    fn device_create_compute_bundle_encoder(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::ComputeBundleEncoderDescriptor,
+    ) -> (Self::ComputeBundleEncoderId, Self::ComputeBundleEncoderData) {
+        create_identified(Vec::new())
+    }
+
     fn device_drop(&self, _device: &Self::DeviceId, _device_data: &Self::DeviceData) {
         // Device is dropped automatically
     }
@@ -2107,6 +2225,14 @@ impl crate::context::Context for Context {
         // Dropped automatically
     }
 
+    fn external_texture_drop(
+        &self,
+        _external_texture: &Self::ExternalTextureId,
+        _external_texture_data: &Self::ExternalTextureData,
+    ) {
+        // Dropped automatically
+    }
+
     fn query_set_drop(&self, _query_set: &Self::QuerySetId, _query_set_data: &Self::QuerySetData) {
         // Dropped automatically
     }
@@ -2167,6 +2293,14 @@ impl crate::context::Context for Context {
         // Dropped automatically
     }
 
+    fn compute_bundle_drop(
+        &self,
+        _compute_bundle: &Self::ComputeBundleId,
+        _compute_bundle_data: &Self::ComputeBundleData,
+    ) {
+        // Dropped automatically
+    }
+
     fn compute_pipeline_drop(
         &self,
         _pipeline: &Self::ComputePipelineId,
@@ -2522,6 +2656,15 @@ impl crate::context::Context for Context {
         })
     }
 
+    fn compute_bundle_encoder_finish(
+        &self,
+        _encoder: Self::ComputeBundleEncoderId,
+        encoder_data: Self::ComputeBundleEncoderData,
+        _desc: &crate::ComputeBundleDescriptor,
+    ) -> (Self::ComputeBundleId, Self::ComputeBundleData) {
+        create_identified(encoder_data.0)
+    }
+
     fn queue_write_buffer(
         &self,
         _queue: &Self::QueueId,
@@ -2841,6 +2984,115 @@ impl crate::context::Context for Context {
             .dispatch_workgroups_indirect_with_f64(&indirect_buffer_data.0, indirect_offset as f64);
     }
 
+    fn compute_pass_execute_bundles<'a>(
+        &self,
+        _pass: &mut Self::ComputePassId,
+        pass_data: &mut Self::ComputePassData,
+        compute_bundles: Box<
+            dyn Iterator<Item = (Self::ComputeBundleId, &'a Self::ComputeBundleData)> + 'a,
+        >,
+    ) {
+        for (_, bundle_data) in compute_bundles {
+            for command in bundle_data.0.iter() {
+                match *command {
+                    ComputeBundleCommand::SetPipeline(ref pipeline) => {
+                        pass_data.0.set_pipeline(pipeline)
+                    }
+                    ComputeBundleCommand::SetBindGroup {
+                        index,
+                        ref bind_group,
+                        ref offsets,
+                    } => {
+                        if offsets.is_empty() {
+                            pass_data.0.set_bind_group(index, bind_group);
+                        } else {
+                            pass_data
+                                .0
+                                .set_bind_group_with_u32_array_and_f64_and_dynamic_offsets_data_length(
+                                    index,
+                                    bind_group,
+                                    offsets,
+                                    0f64,
+                                    offsets.len() as u32,
+                                );
+                        }
+                    }
+                    ComputeBundleCommand::Dispatch([x, y, z]) => pass_data
+                        .0
+                        .dispatch_workgroups_with_workgroup_count_y_and_workgroup_count_z(x, y, z),
+                    ComputeBundleCommand::DispatchIndirect { ref buffer, offset } => pass_data
+                        .0
+                        .dispatch_workgroups_indirect_with_f64(buffer, offset as f64),
+                }
+            }
+        }
+    }
+
+    fn compute_bundle_encoder_set_pipeline(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        _pipeline: &Self::ComputePipelineId,
+        pipeline_data: &Self::ComputePipelineData,
+    ) {
+        encoder_data
+            .0
+            .push(ComputeBundleCommand::SetPipeline(pipeline_data.0.clone()))
+    }
+
+    fn compute_bundle_encoder_set_bind_group(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        index: u32,
+        _bind_group: &Self::BindGroupId,
+        bind_group_data: &Self::BindGroupData,
+        offsets: &[wgt::DynamicOffset],
+    ) {
+        encoder_data.0.push(ComputeBundleCommand::SetBindGroup {
+            index,
+            bind_group: bind_group_data.0.clone(),
+            offsets: offsets.to_vec(),
+        })
+    }
+
+    fn compute_bundle_encoder_set_push_constants(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        _encoder_data: &mut Self::ComputeBundleEncoderData,
+        _offset: u32,
+        _data: &[u8],
+    ) {
+        panic!("PUSH_CONSTANTS feature must be enabled to call set_push_constants")
+    }
+
+    fn compute_bundle_encoder_dispatch_workgroups(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        x: u32,
+        y: u32,
+        z: u32,
+    ) {
+        encoder_data
+            .0
+            .push(ComputeBundleCommand::Dispatch([x, y, z]))
+    }
+
+    fn compute_bundle_encoder_dispatch_workgroups_indirect(
+        &self,
+        _encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        _indirect_buffer: &Self::BufferId,
+        indirect_buffer_data: &Self::BufferData,
+        indirect_offset: wgt::BufferAddress,
+    ) {
+        encoder_data.0.push(ComputeBundleCommand::DispatchIndirect {
+            buffer: indirect_buffer_data.0.clone(),
+            offset: indirect_offset,
+        })
+    }
+
     fn render_bundle_encoder_set_pipeline(
         &self,
         _encoder: &mut Self::RenderBundleEncoderId,
@@ -9,13 +9,14 @@ use wgt::{
 
 use crate::{
     AnyWasmNotSendSync, BindGroupDescriptor, BindGroupLayoutDescriptor, Buffer, BufferAsyncError,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DeviceDescriptor, Error, ErrorFilter, ImageCopyBuffer, ImageCopyTexture, Maintain, MapMode,
-    PipelineLayoutDescriptor, QuerySetDescriptor, RenderBundleDescriptor,
-    RenderBundleEncoderDescriptor, RenderPassDescriptor, RenderPipelineDescriptor,
-    RequestAdapterOptions, RequestDeviceError, SamplerDescriptor, ShaderModuleDescriptor,
-    ShaderModuleDescriptorSpirV, Texture, TextureDescriptor, TextureViewDescriptor,
-    UncapturedErrorHandler,
+    BufferDescriptor, CommandEncoderDescriptor, ComputeBundleDescriptor,
+    ComputeBundleEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
+    DeviceDescriptor, Error, ErrorFilter, ExternalTextureDescriptor, ImageCopyBuffer,
+    ImageCopyTexture, Maintain, MapMode, PipelineLayoutDescriptor, QuerySetDescriptor,
+    RenderBundleDescriptor, RenderBundleEncoderDescriptor, RenderPassDescriptor,
+    RenderPipelineDescriptor, RequestAdapterOptions, RequestDeviceError, SamplerDescriptor,
+    ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, Texture, TextureDescriptor, TextureView,
+    TextureViewDescriptor, UncapturedErrorHandler,
 };
 
 /// Meta trait for an id tracked by a context.
@@ -47,6 +48,8 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
     type TextureViewData: ContextData;
     type SamplerId: ContextId + WasmNotSend + WasmNotSync;
     type SamplerData: ContextData;
+    type ExternalTextureId: ContextId + WasmNotSend + WasmNotSync;
+    type ExternalTextureData: ContextData;
     type BufferId: ContextId + WasmNotSend + WasmNotSync;
     type BufferData: ContextData;
     type TextureId: ContextId + WasmNotSend + WasmNotSync;
@@ -71,6 +74,10 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
     type RenderBundleEncoderData: ContextData;
     type RenderBundleId: ContextId + WasmNotSend + WasmNotSync;
     type RenderBundleData: ContextData;
+    type ComputeBundleEncoderId: ContextId;
+    type ComputeBundleEncoderData: ContextData;
+    type ComputeBundleId: ContextId + WasmNotSend + WasmNotSync;
+    type ComputeBundleData: ContextData;
     type SurfaceId: ContextId + WasmNotSend + WasmNotSync;
     type SurfaceData: ContextData;
 
@@ -94,6 +101,12 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         > + WasmNotSend
         + 'static;
     type PopErrorScopeFuture: Future<Output = Option<Error>> + WasmNotSend + 'static;
+    type CreateRenderPipelineFuture: Future<Output = Result<(Self::RenderPipelineId, Self::RenderPipelineData), Error>>
+        + WasmNotSend
+        + 'static;
+    type CreateComputePipelineFuture: Future<Output = Result<(Self::ComputePipelineId, Self::ComputePipelineData), Error>>
+        + WasmNotSend
+        + 'static;
 
     fn init(instance_desc: wgt::InstanceDescriptor) -> Self;
     unsafe fn instance_create_surface(
@@ -226,12 +239,24 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         device_data: &Self::DeviceData,
         desc: &RenderPipelineDescriptor,
     ) -> (Self::RenderPipelineId, Self::RenderPipelineData);
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &RenderPipelineDescriptor,
+    ) -> Self::CreateRenderPipelineFuture;
     fn device_create_compute_pipeline(
         &self,
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &ComputePipelineDescriptor,
     ) -> (Self::ComputePipelineId, Self::ComputePipelineData);
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor,
+    ) -> Self::CreateComputePipelineFuture;
     fn device_create_buffer(
         &self,
         device: &Self::DeviceId,
@@ -250,6 +275,13 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         device_data: &Self::DeviceData,
         desc: &SamplerDescriptor,
     ) -> (Self::SamplerId, Self::SamplerData);
+    fn device_create_external_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ExternalTextureDescriptor,
+        planes: &[&TextureView],
+    ) -> (Self::ExternalTextureId, Self::ExternalTextureData);
     fn device_create_query_set(
         &self,
         device: &Self::DeviceId,
@@ -268,6 +300,12 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         device_data: &Self::DeviceData,
         desc: &RenderBundleEncoderDescriptor,
     ) -> (Self::RenderBundleEncoderId, Self::RenderBundleEncoderData);
+    fn device_create_compute_bundle_encoder(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ComputeBundleEncoderDescriptor,
+    ) -> (Self::ComputeBundleEncoderId, Self::ComputeBundleEncoderData);
     fn device_drop(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
     fn device_destroy(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
     fn device_lose(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
@@ -339,6 +377,11 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         texture_view_data: &Self::TextureViewData,
     );
     fn sampler_drop(&self, sampler: &Self::SamplerId, sampler_data: &Self::SamplerData);
+    fn external_texture_drop(
+        &self,
+        external_texture: &Self::ExternalTextureId,
+        external_texture_data: &Self::ExternalTextureData,
+    );
     fn query_set_drop(&self, query_set: &Self::QuerySetId, query_set_data: &Self::QuerySetData);
     fn bind_group_drop(
         &self,
@@ -375,6 +418,11 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         render_bundle: &Self::RenderBundleId,
         render_bundle_data: &Self::RenderBundleData,
     );
+    fn compute_bundle_drop(
+        &self,
+        compute_bundle: &Self::ComputeBundleId,
+        compute_bundle_data: &Self::ComputeBundleData,
+    );
     fn compute_pipeline_drop(
         &self,
         pipeline: &Self::ComputePipelineId,
@@ -531,6 +579,12 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         encoder_data: Self::RenderBundleEncoderData,
         desc: &RenderBundleDescriptor,
     ) -> (Self::RenderBundleId, Self::RenderBundleData);
+    fn compute_bundle_encoder_finish(
+        &self,
+        encoder: Self::ComputeBundleEncoderId,
+        encoder_data: Self::ComputeBundleEncoderData,
+        desc: &ComputeBundleDescriptor,
+    ) -> (Self::ComputeBundleId, Self::ComputeBundleData);
     fn queue_write_buffer(
         &self,
         queue: &Self::QueueId,
@@ -680,6 +734,54 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         indirect_buffer_data: &Self::BufferData,
         indirect_offset: BufferAddress,
     );
+    fn compute_pass_execute_bundles<'a>(
+        &self,
+        pass: &mut Self::ComputePassId,
+        pass_data: &mut Self::ComputePassData,
+        compute_bundles: Box<
+            dyn Iterator<Item = (Self::ComputeBundleId, &'a Self::ComputeBundleData)> + 'a,
+        >,
+    );
+
+    fn compute_bundle_encoder_set_pipeline(
+        &self,
+        encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        pipeline: &Self::ComputePipelineId,
+        pipeline_data: &Self::ComputePipelineData,
+    );
+    fn compute_bundle_encoder_set_bind_group(
+        &self,
+        encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        index: u32,
+        bind_group: &Self::BindGroupId,
+        bind_group_data: &Self::BindGroupData,
+        offsets: &[DynamicOffset],
+    );
+    fn compute_bundle_encoder_set_push_constants(
+        &self,
+        encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        offset: u32,
+        data: &[u8],
+    );
+    fn compute_bundle_encoder_dispatch_workgroups(
+        &self,
+        encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        x: u32,
+        y: u32,
+        z: u32,
+    );
+    fn compute_bundle_encoder_dispatch_workgroups_indirect(
+        &self,
+        encoder: &mut Self::ComputeBundleEncoderId,
+        encoder_data: &mut Self::ComputeBundleEncoderData,
+        indirect_buffer: &Self::BufferId,
+        indirect_buffer_data: &Self::BufferData,
+        indirect_offset: BufferAddress,
+    );
 
     fn render_bundle_encoder_set_pipeline(
         &self,
@@ -1183,6 +1285,25 @@ pub type DevicePopErrorFuture = Box<dyn Future<Output = Option<Error>> + Send>;
 )))]
 pub type DevicePopErrorFuture = Box<dyn Future<Output = Option<Error>>>;
 
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+pub type DeviceCreatePipelineFuture =
+    Box<dyn Future<Output = Result<(ObjectId, Box<crate::Data>), Error>> + Send>;
+#[cfg(not(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+)))]
+pub type DeviceCreatePipelineFuture =
+    Box<dyn Future<Output = Result<(ObjectId, Box<crate::Data>), Error>>>;
+
 #[cfg(any(
     not(target_arch = "wasm32"),
     all(
@@ -1322,12 +1443,24 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         device_data: &crate::Data,
         desc: &RenderPipelineDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &RenderPipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture>;
     fn device_create_compute_pipeline(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
         desc: &ComputePipelineDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputePipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture>;
     fn device_create_buffer(
         &self,
         device: &ObjectId,
@@ -1346,6 +1479,13 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         device_data: &crate::Data,
         desc: &SamplerDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_external_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ExternalTextureDescriptor,
+        planes: &[&TextureView],
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_query_set(
         &self,
         device: &ObjectId,
@@ -1364,6 +1504,12 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         device_data: &crate::Data,
         desc: &RenderBundleEncoderDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_compute_bundle_encoder(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputeBundleEncoderDescriptor,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_drop(&self, device: &ObjectId, device_data: &crate::Data);
     fn device_destroy(&self, device: &ObjectId, device_data: &crate::Data);
     fn device_lose(&self, device: &ObjectId, device_data: &crate::Data);
@@ -1426,6 +1572,11 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
     fn texture_drop(&self, texture: &ObjectId, texture_data: &crate::Data);
     fn texture_view_drop(&self, texture_view: &ObjectId, texture_view_data: &crate::Data);
     fn sampler_drop(&self, sampler: &ObjectId, sampler_data: &crate::Data);
+    fn external_texture_drop(
+        &self,
+        external_texture: &ObjectId,
+        external_texture_data: &crate::Data,
+    );
     fn query_set_drop(&self, query_set: &ObjectId, query_set_data: &crate::Data);
     fn bind_group_drop(&self, bind_group: &ObjectId, bind_group_data: &crate::Data);
     fn bind_group_layout_drop(
@@ -1438,6 +1589,7 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
     fn command_encoder_drop(&self, command_encoder: &ObjectId, command_encoder_data: &crate::Data);
     fn command_buffer_drop(&self, command_buffer: &ObjectId, command_buffer_data: &crate::Data);
     fn render_bundle_drop(&self, render_bundle: &ObjectId, render_bundle_data: &crate::Data);
+    fn compute_bundle_drop(&self, compute_bundle: &ObjectId, compute_bundle_data: &crate::Data);
     fn compute_pipeline_drop(&self, pipeline: &ObjectId, pipeline_data: &crate::Data);
     fn render_pipeline_drop(&self, pipeline: &ObjectId, pipeline_data: &crate::Data);
 
@@ -1582,6 +1734,12 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         encoder_data: Box<crate::Data>,
         desc: &RenderBundleDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn compute_bundle_encoder_finish(
+        &self,
+        encoder: ObjectId,
+        encoder_data: Box<crate::Data>,
+        desc: &ComputeBundleDescriptor,
+    ) -> (ObjectId, Box<crate::Data>);
     fn queue_write_buffer(
         &self,
         queue: &ObjectId,
@@ -1723,6 +1881,52 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         indirect_buffer_data: &crate::Data,
         indirect_offset: BufferAddress,
     );
+    fn compute_pass_execute_bundles<'a>(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        compute_bundles: Box<dyn Iterator<Item = (&'a ObjectId, &'a crate::Data)> + 'a>,
+    );
+
+    fn compute_bundle_encoder_set_pipeline(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        pipeline: &ObjectId,
+        pipeline_data: &crate::Data,
+    );
+    fn compute_bundle_encoder_set_bind_group(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        index: u32,
+        bind_group: &ObjectId,
+        bind_group_data: &crate::Data,
+        offsets: &[DynamicOffset],
+    );
+    fn compute_bundle_encoder_set_push_constants(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        offset: u32,
+        data: &[u8],
+    );
+    fn compute_bundle_encoder_dispatch_workgroups(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        x: u32,
+        y: u32,
+        z: u32,
+    );
+    fn compute_bundle_encoder_dispatch_workgroups_indirect(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+    );
 
     fn render_bundle_encoder_set_pipeline(
         &self,
@@ -2335,6 +2539,21 @@ where
         (render_pipeline.into(), Box::new(data) as _)
     }
 
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &RenderPipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future = Context::device_create_render_pipeline_async(self, &device, device_data, desc);
+        Box::pin(async move {
+            let (render_pipeline, data) = future.await?;
+            Ok((render_pipeline.into(), Box::new(data) as _))
+        })
+    }
+
     fn device_create_compute_pipeline(
         &self,
         device: &ObjectId,
@@ -2348,6 +2567,22 @@ where
         (compute_pipeline.into(), Box::new(data) as _)
     }
 
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputePipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future =
+            Context::device_create_compute_pipeline_async(self, &device, device_data, desc);
+        Box::pin(async move {
+            let (compute_pipeline, data) = future.await?;
+            Ok((compute_pipeline.into(), Box::new(data) as _))
+        })
+    }
+
     fn device_create_buffer(
         &self,
         device: &ObjectId,
@@ -2384,6 +2619,20 @@ where
         (sampler.into(), Box::new(data) as _)
     }
 
+    fn device_create_external_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ExternalTextureDescriptor,
+        planes: &[&TextureView],
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (external_texture, data) =
+            Context::device_create_external_texture(self, &device, device_data, desc, planes);
+        (external_texture.into(), Box::new(data) as _)
+    }
+
     fn device_create_query_set(
         &self,
         device: &ObjectId,
@@ -2422,6 +2671,19 @@ where
         (render_bundle_encoder.into(), Box::new(data) as _)
     }
 
+    fn device_create_compute_bundle_encoder(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputeBundleEncoderDescriptor,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (compute_bundle_encoder, data) =
+            Context::device_create_compute_bundle_encoder(self, &device, device_data, desc);
+        (compute_bundle_encoder.into(), Box::new(data) as _)
+    }
+
     fn device_drop(&self, device: &ObjectId, device_data: &crate::Data) {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
@@ -2588,6 +2850,16 @@ where
         Context::sampler_drop(self, &sampler, sampler_data)
     }
 
+    fn external_texture_drop(
+        &self,
+        external_texture: &ObjectId,
+        external_texture_data: &crate::Data,
+    ) {
+        let external_texture = <T::ExternalTextureId>::from(*external_texture);
+        let external_texture_data = downcast_ref(external_texture_data);
+        Context::external_texture_drop(self, &external_texture, external_texture_data)
+    }
+
     fn query_set_drop(&self, query_set: &ObjectId, query_set_data: &crate::Data) {
         let query_set = <T::QuerySetId>::from(*query_set);
         let query_set_data = downcast_ref(query_set_data);
@@ -2640,6 +2912,12 @@ where
         Context::render_bundle_drop(self, &render_bundle, render_bundle_data)
     }
 
+    fn compute_bundle_drop(&self, compute_bundle: &ObjectId, compute_bundle_data: &crate::Data) {
+        let compute_bundle = <T::ComputeBundleId>::from(*compute_bundle);
+        let compute_bundle_data = downcast_ref(compute_bundle_data);
+        Context::compute_bundle_drop(self, &compute_bundle, compute_bundle_data)
+    }
+
     fn compute_pipeline_drop(&self, pipeline: &ObjectId, pipeline_data: &crate::Data) {
         let pipeline = <T::ComputePipelineId>::from(*pipeline);
         let pipeline_data = downcast_ref(pipeline_data);
@@ -2965,6 +3243,18 @@ where
         (render_bundle.into(), Box::new(data) as _)
     }
 
+    fn compute_bundle_encoder_finish(
+        &self,
+        encoder: ObjectId,
+        encoder_data: Box<crate::Data>,
+        desc: &ComputeBundleDescriptor,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let encoder_data = *encoder_data.downcast().unwrap();
+        let (compute_bundle, data) =
+            Context::compute_bundle_encoder_finish(self, encoder.into(), encoder_data, desc);
+        (compute_bundle.into(), Box::new(data) as _)
+    }
+
     fn queue_write_buffer(
         &self,
         queue: &ObjectId,
@@ -3281,6 +3571,125 @@ where
         )
     }
 
+    fn compute_pass_execute_bundles<'a>(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        compute_bundles: Box<dyn Iterator<Item = (&'a ObjectId, &'a crate::Data)> + 'a>,
+    ) {
+        let mut pass = <T::ComputePassId>::from(*pass);
+        let pass_data = downcast_mut::<T::ComputePassData>(pass_data);
+        let compute_bundles = Box::new(compute_bundles.into_iter().map(|(id, data)| {
+            let compute_bundle_data: &<T as Context>::ComputeBundleData = downcast_ref(data);
+            (<T::ComputeBundleId>::from(*id), compute_bundle_data)
+        }));
+        Context::compute_pass_execute_bundles(self, &mut pass, pass_data, compute_bundles)
+    }
+
+    fn compute_bundle_encoder_set_pipeline(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        pipeline: &ObjectId,
+        pipeline_data: &crate::Data,
+    ) {
+        let mut encoder = <T::ComputeBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::ComputeBundleEncoderData>(encoder_data);
+        let pipeline = <T::ComputePipelineId>::from(*pipeline);
+        let pipeline_data = downcast_ref(pipeline_data);
+        Context::compute_bundle_encoder_set_pipeline(
+            self,
+            &mut encoder,
+            encoder_data,
+            &pipeline,
+            pipeline_data,
+        )
+    }
+
+    fn compute_bundle_encoder_set_bind_group(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        index: u32,
+        bind_group: &ObjectId,
+        bind_group_data: &crate::Data,
+        offsets: &[DynamicOffset],
+    ) {
+        let mut encoder = <T::ComputeBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::ComputeBundleEncoderData>(encoder_data);
+        let bind_group = <T::BindGroupId>::from(*bind_group);
+        let bind_group_data = downcast_ref(bind_group_data);
+        Context::compute_bundle_encoder_set_bind_group(
+            self,
+            &mut encoder,
+            encoder_data,
+            index,
+            &bind_group,
+            bind_group_data,
+            offsets,
+        )
+    }
+
+    fn compute_bundle_encoder_set_push_constants(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        offset: u32,
+        data: &[u8],
+    ) {
+        let mut encoder = <T::ComputeBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::ComputeBundleEncoderData>(encoder_data);
+        Context::compute_bundle_encoder_set_push_constants(
+            self,
+            &mut encoder,
+            encoder_data,
+            offset,
+            data,
+        )
+    }
+
+    fn compute_bundle_encoder_dispatch_workgroups(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        x: u32,
+        y: u32,
+        z: u32,
+    ) {
+        let mut encoder = <T::ComputeBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::ComputeBundleEncoderData>(encoder_data);
+        Context::compute_bundle_encoder_dispatch_workgroups(
+            self,
+            &mut encoder,
+            encoder_data,
+            x,
+            y,
+            z,
+        )
+    }
+
+    fn compute_bundle_encoder_dispatch_workgroups_indirect(
+        &self,
+        encoder: &mut ObjectId,
+        encoder_data: &mut crate::Data,
+        indirect_buffer: &ObjectId,
+        indirect_buffer_data: &crate::Data,
+        indirect_offset: BufferAddress,
+    ) {
+        let mut encoder = <T::ComputeBundleEncoderId>::from(*encoder);
+        let encoder_data = downcast_mut::<T::ComputeBundleEncoderData>(encoder_data);
+        let indirect_buffer = <T::BufferId>::from(*indirect_buffer);
+        let indirect_buffer_data = downcast_ref(indirect_buffer_data);
+        Context::compute_bundle_encoder_dispatch_workgroups_indirect(
+            self,
+            &mut encoder,
+            encoder_data,
+            &indirect_buffer,
+            indirect_buffer_data,
+            indirect_offset,
+        )
+    }
+
     fn render_bundle_encoder_set_pipeline(
         &self,
         encoder: &mut ObjectId,
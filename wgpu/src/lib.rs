@@ -33,18 +33,19 @@ pub use wgt::{
     BufferBindingType, BufferSize, BufferUsages, Color, ColorTargetState, ColorWrites,
     CommandBufferDescriptor, CompareFunction, CompositeAlphaMode, DepthBiasState,
     DepthStencilState, DeviceType, DownlevelCapabilities, DownlevelFlags, Dx12Compiler,
-    DynamicOffset, Extent3d, Face, Features, FilterMode, FrontFace, Gles3MinorVersion,
-    ImageDataLayout, ImageSubresourceRange, IndexFormat, InstanceDescriptor, InstanceFlags, Limits,
-    MultisampleState, Origin2d, Origin3d, PipelineStatisticsTypes, PolygonMode, PowerPreference,
-    PredefinedColorSpace, PresentMode, PresentationTimestamp, PrimitiveState, PrimitiveTopology,
-    PushConstantRange, QueryType, RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor,
-    ShaderLocation, ShaderModel, ShaderStages, StencilFaceState, StencilOperation, StencilState,
+    DynamicOffset, Extent3d, ExternalTextureFormat, Face, Features, FilterMode, FrontFace,
+    Gles3MinorVersion, ImageDataLayout, ImageSubresourceRange, IndexFormat, InstanceDescriptor,
+    InstanceFlags, Limits, MemoryHeapReport, MemoryReport, MemoryUsage, MultisampleState, Origin2d,
+    Origin3d, PipelineStatisticsTypes, PolygonMode, PowerPreference, PredefinedColorSpace,
+    PresentMode, PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange,
+    QueryType, RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, ShaderLocation,
+    ShaderModel, ShaderStages, StencilFaceState, StencilOperation, StencilState,
     StorageTextureAccess, SurfaceCapabilities, SurfaceStatus, TextureAspect, TextureDimension,
     TextureFormat, TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType,
     TextureUsages, TextureViewDimension, VertexAttribute, VertexFormat, VertexStepMode,
     WasmNotSend, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT,
     PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE,
-    VERTEX_STRIDE_ALIGNMENT,
+    VERTEX_STRIDE_ALIGNMENT, YUV_BT601_TO_RGB, YUV_BT709_TO_RGB,
 };
 
 #[cfg(any(
@@ -370,6 +371,39 @@ impl Drop for Sampler {
     }
 }
 
+/// Handle to an external texture.
+///
+/// An `ExternalTexture` is an image made of one to three planes, such as a
+/// decoded NV12 or I420 video frame, that shaders read as RGBA through a
+/// `texture_external` binding. The conversion from YUV happens in the shader.
+///
+/// It can be created with [`Device::create_external_texture`].
+///
+/// Corresponds to [WebGPU `GPUExternalTexture`](https://gpuweb.github.io/gpuweb/#gpuexternaltexture).
+#[derive(Debug)]
+pub struct ExternalTexture {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+static_assertions::assert_impl_all!(ExternalTexture: Send, Sync);
+
+impl Drop for ExternalTexture {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context
+                .external_texture_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
 /// Describes a [`Surface`].
 ///
 /// For use with [`Surface::configure`].
@@ -860,6 +894,57 @@ impl Drop for RenderBundle {
     }
 }
 
+/// Encodes a series of dispatches into a reusable "compute bundle".
+///
+/// It can be created with [`Device::create_compute_bundle_encoder`].
+/// It can be executed onto a [`CommandEncoder`] using [`ComputePass::execute_bundles`].
+///
+/// Compute bundles are a `wgpu` extension, there is no WebGPU equivalent. On
+/// the web, the commands are recorded on the client and replayed into the
+/// compute pass.
+#[derive(Debug)]
+pub struct ComputeBundleEncoder<'a> {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+    parent: &'a Device,
+    /// This type should be !Send !Sync, because it represents an allocation on this thread's
+    /// command buffer.
+    _p: PhantomData<*const u8>,
+}
+static_assertions::assert_not_impl_any!(ComputeBundleEncoder<'_>: Send, Sync);
+
+/// Pre-prepared reusable bundle of dispatches.
+///
+/// Its commands are validated once, when it is finished, so executing a
+/// [`ComputeBundle`] is cheaper than issuing the underlying commands manually.
+///
+/// It can be created by use of a [`ComputeBundleEncoder`], and executed onto a [`CommandEncoder`]
+/// using [`ComputePass::execute_bundles`].
+#[derive(Debug)]
+pub struct ComputeBundle {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+static_assertions::assert_impl_all!(ComputeBundle: Send, Sync);
+
+impl Drop for ComputeBundle {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context
+                .compute_bundle_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
 /// Handle to a query set.
 ///
 /// It can be created with [`Device::create_query_set`].
@@ -959,6 +1044,10 @@ pub enum BindingResource<'a> {
     /// Corresponds to [`wgt::BindingType::Texture`] and [`wgt::BindingType::StorageTexture`] with
     /// [`BindGroupLayoutEntry::count`] set to Some.
     TextureViewArray(&'a [&'a TextureView]),
+    /// Binding is backed by an external texture.
+    ///
+    /// Corresponds to [`wgt::BindingType::ExternalTexture`].
+    ExternalTexture(&'a ExternalTexture),
 }
 #[cfg(any(
     not(target_arch = "wasm32"),
@@ -1212,6 +1301,16 @@ static_assertions::assert_impl_all!(CommandEncoderDescriptor: Send, Sync);
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpurenderbundledescriptor).
 pub type RenderBundleDescriptor<'a> = wgt::RenderBundleDescriptor<Label<'a>>;
 static_assertions::assert_impl_all!(RenderBundleDescriptor: Send, Sync);
+/// Describes a [`ComputeBundleEncoder`].
+///
+/// For use with [`Device::create_compute_bundle_encoder`].
+pub type ComputeBundleEncoderDescriptor<'a> = wgt::ComputeBundleEncoderDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(ComputeBundleEncoderDescriptor: Send, Sync);
+/// Describes a [`ComputeBundle`].
+///
+/// For use with [`ComputeBundleEncoder::finish`].
+pub type ComputeBundleDescriptor<'a> = wgt::ComputeBundleDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(ComputeBundleDescriptor: Send, Sync);
 /// Describes a [`Texture`].
 ///
 /// For use with [`Device::create_texture`].
@@ -1228,6 +1327,11 @@ static_assertions::assert_impl_all!(TextureDescriptor: Send, Sync);
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpuquerysetdescriptor).
 pub type QuerySetDescriptor<'a> = wgt::QuerySetDescriptor<Label<'a>>;
 static_assertions::assert_impl_all!(QuerySetDescriptor: Send, Sync);
+/// Describes an [`ExternalTexture`].
+///
+/// For use with [`Device::create_external_texture`].
+pub type ExternalTextureDescriptor<'a> = wgt::ExternalTextureDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(ExternalTextureDescriptor: Send, Sync);
 pub use wgt::Maintain as MaintainBase;
 /// Passed to [`Device::poll`] to control how and if it should block.
 pub type Maintain = wgt::Maintain<SubmissionIndex>;
@@ -2182,7 +2286,8 @@ impl Adapter {
     ///
     /// - `desc` - Description of the features and limits requested from the given device.
     /// - `trace_path` - Can be used for API call tracing, if that feature is
-    ///   enabled in `wgpu-core`.
+    ///   enabled in `wgpu-core`. A path with the `wgputrace` extension records
+    ///   a single binary file, any other path is a directory for a RON trace.
     ///
     /// # Panics
     ///
@@ -2516,6 +2621,26 @@ impl Device {
         }
     }
 
+    /// Creates an empty [`ComputeBundleEncoder`].
+    pub fn create_compute_bundle_encoder(
+        &self,
+        desc: &ComputeBundleEncoderDescriptor,
+    ) -> ComputeBundleEncoder {
+        let (id, data) = DynContext::device_create_compute_bundle_encoder(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        ComputeBundleEncoder {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            parent: self,
+            _p: Default::default(),
+        }
+    }
+
     /// Creates a new [`BindGroup`].
     pub fn create_bind_group(&self, desc: &BindGroupDescriptor) -> BindGroup {
         let (id, data) = DynContext::device_create_bind_group(
@@ -2591,6 +2716,52 @@ impl Device {
         }
     }
 
+    /// Creates a [`RenderPipeline`] without blocking the calling thread.
+    ///
+    /// On native, shader translation and compilation run on a pool of worker
+    /// threads, sized by [`InstanceDescriptor::pipeline_worker_threads`]. On the
+    /// web, this maps to `createRenderPipelineAsync`.
+    ///
+    /// Unlike [`Device::create_render_pipeline`], errors are returned by the
+    /// future, rather than reported to the error scopes or the uncaptured error
+    /// handler.
+    pub fn create_render_pipeline_async(
+        &self,
+        desc: &RenderPipelineDescriptor,
+    ) -> impl Future<Output = Result<RenderPipeline, Error>> + WasmNotSend {
+        let context = Arc::clone(&self.context);
+        let pipeline = DynContext::device_create_render_pipeline_async(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        async move {
+            let (id, data) = pipeline.await?;
+            Ok(RenderPipeline { context, id, data })
+        }
+    }
+
+    /// Creates a [`ComputePipeline`] without blocking the calling thread.
+    ///
+    /// See [`Device::create_render_pipeline_async`].
+    pub fn create_compute_pipeline_async(
+        &self,
+        desc: &ComputePipelineDescriptor,
+    ) -> impl Future<Output = Result<ComputePipeline, Error>> + WasmNotSend {
+        let context = Arc::clone(&self.context);
+        let pipeline = DynContext::device_create_compute_pipeline_async(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        async move {
+            let (id, data) = pipeline.await?;
+            Ok(ComputePipeline { context, id, data })
+        }
+    }
+
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &BufferDescriptor) -> Buffer {
         let mut map_context = MapContext::new(desc.size);
@@ -2728,6 +2899,32 @@ impl Device {
         }
     }
 
+    /// Creates a new [`ExternalTexture`] from the views of its planes.
+    ///
+    /// `planes` must hold as many two-dimensional, filterable float views as
+    /// `desc.format` has planes, in the order the format defines them.
+    ///
+    /// Not supported on the web, where external textures are imported from
+    /// video elements by the browser.
+    pub fn create_external_texture(
+        &self,
+        desc: &ExternalTextureDescriptor,
+        planes: &[&TextureView],
+    ) -> ExternalTexture {
+        let (id, data) = DynContext::device_create_external_texture(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+            planes,
+        );
+        ExternalTexture {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates a new [`QuerySet`].
     pub fn create_query_set(&self, desc: &QuerySetDescriptor) -> QuerySet {
         let (id, data) =
@@ -2767,6 +2964,70 @@ impl Device {
         DynContext::device_stop_capture(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// Returns the device memory allocated for buffers and textures, per
+    /// resource kind and per memory heap, along with the heap budgets the
+    /// platform exposes.
+    ///
+    /// Returns `None` on the WebGPU backend, which doesn't expose this information,
+    /// and once the device is lost or destroyed.
+    #[cfg(any(
+        not(target_arch = "wasm32"),
+        target_os = "emscripten",
+        feature = "webgl"
+    ))]
+    pub fn memory_report(&self) -> Option<MemoryReport> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::Context>()
+            .and_then(|ctx| ctx.device_memory_report(self.data.as_ref().downcast_ref().unwrap()))
+    }
+
+    /// Returns the resources created by this device that are still alive,
+    /// including ones dropped by the user but kept alive by bind groups or
+    /// render bundles.
+    ///
+    /// Labels are kept in release builds, and creation backtraces recorded,
+    /// only with [`InstanceFlags::TRACK_LIVE_RESOURCES`] and
+    /// [`InstanceFlags::RESOURCE_BACKTRACES`].
+    ///
+    /// Returns `None` on the WebGPU backend, and if the device no longer exists.
+    #[cfg(any(
+        not(target_arch = "wasm32"),
+        target_os = "emscripten",
+        feature = "webgl"
+    ))]
+    pub fn live_resources(&self) -> Option<Vec<wgc::device::LiveResource>> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::Context>()
+            .and_then(|ctx| ctx.device_live_resources(self.data.as_ref().downcast_ref().unwrap()))
+    }
+
+    /// Makes this device fail the buffer and texture allocations and queue
+    /// submissions selected by `injector`, or stops doing so if it is `None`.
+    ///
+    /// Failures are reported like real out-of-memory or device-lost errors, which
+    /// lets error handling be tested deterministically.
+    ///
+    /// Does nothing on the WebGPU backend.
+    #[cfg(all(
+        feature = "fault-injection",
+        any(
+            not(target_arch = "wasm32"),
+            target_os = "emscripten",
+            feature = "webgl"
+        )
+    ))]
+    pub fn set_fault_injector(&self, injector: Option<wgc::device::fault::FaultInjector>) {
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::Context>()
+        {
+            ctx.device_set_fault_injector(self.data.as_ref().downcast_ref().unwrap(), injector);
+        }
+    }
+
     /// Apply a callback to this `Device`'s underlying backend device.
     ///
     /// If this `Device` is implemented by the backend API given by `A` (Vulkan,
@@ -4341,6 +4602,28 @@ impl<'a> ComputePass<'a> {
             indirect_offset,
         );
     }
+
+    /// Execute a [compute bundle][ComputeBundle], which is a set of pre-recorded dispatches
+    /// that can be run together.
+    ///
+    /// Commands in the bundle do not inherit this compute pass's current pipeline and bind
+    /// groups, and after the bundle has executed, they are **cleared** (not restored to the
+    /// previous state).
+    pub fn execute_bundles<I: IntoIterator<Item = &'a ComputeBundle> + 'a>(
+        &mut self,
+        compute_bundles: I,
+    ) {
+        DynContext::compute_pass_execute_bundles(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            Box::new(
+                compute_bundles
+                    .into_iter()
+                    .map(|cb| (&cb.id, cb.data.as_ref())),
+            ),
+        )
+    }
 }
 
 /// [`Features::PUSH_CONSTANTS`] must be enabled on the device in order to call these functions.
@@ -4651,6 +4934,101 @@ impl<'a> RenderBundleEncoder<'a> {
     }
 }
 
+impl<'a> ComputeBundleEncoder<'a> {
+    /// Finishes recording and returns a [`ComputeBundle`] that can be executed in other compute passes.
+    pub fn finish(self, desc: &ComputeBundleDescriptor) -> ComputeBundle {
+        let (id, data) =
+            DynContext::compute_bundle_encoder_finish(&*self.context, self.id, self.data, desc);
+        ComputeBundle {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
+    /// Sets the active bind group for a given bind group index. The bind group layout
+    /// in the active pipeline when any `dispatch()` function is called must match the layout of this bind group.
+    ///
+    /// If the bind group have dynamic offsets, provide them in the binding order.
+    pub fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: &'a BindGroup,
+        offsets: &[DynamicOffset],
+    ) {
+        DynContext::compute_bundle_encoder_set_bind_group(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            index,
+            &bind_group.id,
+            bind_group.data.as_ref(),
+            offsets,
+        )
+    }
+
+    /// Sets the active compute pipeline.
+    pub fn set_pipeline(&mut self, pipeline: &'a ComputePipeline) {
+        DynContext::compute_bundle_encoder_set_pipeline(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &pipeline.id,
+            pipeline.data.as_ref(),
+        )
+    }
+
+    /// Dispatches compute work operations.
+    ///
+    /// `x`, `y` and `z` denote the number of work groups to dispatch in each dimension.
+    pub fn dispatch_workgroups(&mut self, x: u32, y: u32, z: u32) {
+        DynContext::compute_bundle_encoder_dispatch_workgroups(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            x,
+            y,
+            z,
+        );
+    }
+
+    /// Dispatches compute work operations, based on the contents of the `indirect_buffer`.
+    ///
+    /// The structure expected in `indirect_buffer` must conform to [`DispatchIndirect`](crate::util::DispatchIndirect).
+    pub fn dispatch_workgroups_indirect(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: BufferAddress,
+    ) {
+        DynContext::compute_bundle_encoder_dispatch_workgroups_indirect(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            &indirect_buffer.id,
+            indirect_buffer.data.as_ref(),
+            indirect_offset,
+        );
+    }
+}
+
+/// [`Features::PUSH_CONSTANTS`] must be enabled on the device in order to call these functions.
+impl<'a> ComputeBundleEncoder<'a> {
+    /// Set push constant data for subsequent dispatch calls.
+    ///
+    /// Write the bytes in `data` at offset `offset` within push constant
+    /// storage.  Both `offset` and the length of `data` must be
+    /// multiples of [`PUSH_CONSTANT_ALIGNMENT`], which is always 4.
+    pub fn set_push_constants(&mut self, offset: u32, data: &[u8]) {
+        DynContext::compute_bundle_encoder_set_push_constants(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            offset,
+            data,
+        );
+    }
+}
+
 /// A write-only view into a staging buffer.
 ///
 /// Reading into this buffer won't yield the contents of the buffer from the
@@ -5289,6 +5667,19 @@ impl RenderBundle {
     }
 }
 
+#[cfg(feature = "expose-ids")]
+impl ComputeBundle {
+    /// Returns a globally-unique identifier for this `ComputeBundle`.
+    ///
+    /// Calling this method multiple times on the same object will always return the same value.
+    /// The returned value is guaranteed to be unique among all `ComputeBundle`s created from the same
+    /// `Instance`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "expose-ids")))]
+    pub fn global_id(&self) -> Id<ComputeBundle> {
+        Id(self.id.global_id(), std::marker::PhantomData)
+    }
+}
+
 #[cfg(feature = "expose-ids")]
 impl Surface {
     /// Returns a globally-unique identifier for this `Surface`.